@@ -0,0 +1,437 @@
+//! Actions module for Zellij Visual Notifications
+//!
+//! Every call that changes something in Zellij (renaming tabs and panes, focusing panes, running
+//! commands, opening and closing panes, typing into panes, web requests) or talks to other plugins
+//! and `zellij pipe` CLIs goes through `Actions`. Calls are skipped
+//! and counted while the permission they need is denied instead of failing silently in the host,
+//! each kind of call is rate limited (`action_rate_limit` per second) so a feedback loop can't
+//! flood Zellij, and with `actions_dry_run` calls are only logged. A read-only instance (`mirror`,
+//! or on standby) makes no calls besides its election announcements. Answers to `zellij pipe` are
+//! neither refused for a permission nor rate limited, since a CLI left blocked would hang. The
+//! `stats` command lists the counters.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use zellij_tile::prelude::*;
+use crate::diagnostics::{self, LogLevel};
+
+/// Default calls per action kind and second (0 = unlimited)
+pub const DEFAULT_ACTION_RATE_LIMIT: u32 = 50;
+
+/// Length of a rate limiting window (ms)
+const RATE_WINDOW_MS: u64 = 1000;
+
+/// Kind of Zellij call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ActionKind {
+    /// Rename a tab (badges)
+    RenameTab,
+    /// Rename a terminal or plugin pane (title badges, summary)
+    RenamePane,
+    /// Focus a terminal pane (auto-focus)
+    FocusPane,
+    /// Run a command in the background (checks, snapshots, count file)
+    RunCommand,
+    /// Open a command pane (automations)
+    OpenCommandPane,
+    /// Open a floating command pane (toasts)
+    OpenFloatingPane,
+    /// Close a terminal pane (toasts)
+    ClosePane,
+    /// Change a pane's layout state (fullscreen, scrollback editor)
+    PaneLayout,
+    /// Send an HTTP request (webhooks)
+    WebRequest,
+    /// Type into a terminal pane (quick replies)
+    WriteToPane,
+    /// Message another plugin (ID request replies)
+    MessagePlugin,
+    /// Announce this instance to the other instances (election; standby instances too)
+    Announce,
+    /// Write to, hold or release a `zellij pipe` CLI (command output, receipts, answers)
+    PipeOutput,
+}
+
+impl ActionKind {
+    /// Get the action kind name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionKind::RenameTab => "rename_tab",
+            ActionKind::RenamePane => "rename_pane",
+            ActionKind::FocusPane => "focus_pane",
+            ActionKind::RunCommand => "run_command",
+            ActionKind::OpenCommandPane => "open_command_pane",
+            ActionKind::OpenFloatingPane => "open_floating_pane",
+            ActionKind::ClosePane => "close_pane",
+            ActionKind::PaneLayout => "pane_layout",
+            ActionKind::WebRequest => "web_request",
+            ActionKind::WriteToPane => "write_to_pane",
+            ActionKind::MessagePlugin => "message_plugin",
+            ActionKind::Announce => "announce",
+            ActionKind::PipeOutput => "pipe_output",
+        }
+    }
+
+    /// Permission the host requires for the call
+    pub fn permission(&self) -> PermissionType {
+        match self {
+            ActionKind::RunCommand => PermissionType::RunCommands,
+            ActionKind::WebRequest => PermissionType::WebAccess,
+            ActionKind::WriteToPane => PermissionType::WriteToStdin,
+            ActionKind::MessagePlugin | ActionKind::Announce => PermissionType::MessageAndLaunchOtherPlugins,
+            ActionKind::PipeOutput => PermissionType::ReadCliPipes,
+            _ => PermissionType::ChangeApplicationState,
+        }
+    }
+
+    /// Whether the permission answer and the rate limit apply (not to answers to a CLI, which
+    /// would hang if its release were skipped)
+    fn limited(&self) -> bool {
+        *self != ActionKind::PipeOutput
+    }
+}
+
+/// Why a call was not made
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionError {
+    /// The permission the call needs was denied
+    PermissionDenied(ActionKind),
+    /// Too many calls of this kind within the last second
+    RateLimited(ActionKind),
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::PermissionDenied(kind) => write!(f, "{} skipped: {:?} permission denied", kind.name(), kind.permission()),
+            ActionError::RateLimited(kind) => write!(f, "{} skipped: rate limit reached", kind.name()),
+        }
+    }
+}
+
+/// Counters of one action kind
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ActionCounts {
+    /// Calls made
+    taken: u64,
+    /// Calls logged in dry-run mode
+    logged: u64,
+    /// Calls skipped for a denied permission
+    denied: u64,
+    /// Calls skipped by the rate limit
+    rate_limited: u64,
+    /// Calls skipped in a read-only mirror
+    read_only: u64,
+}
+
+/// Gate for the calls the plugin makes to Zellij
+#[derive(Debug, Clone)]
+pub struct Actions {
+    /// Whether the requested permissions were granted (None until the host answers)
+    permitted: Option<bool>,
+    /// Log calls instead of making them
+    dry_run: bool,
+    /// Skip every call (read-only mirror)
+    read_only: bool,
+    /// Calls per kind and second (0 = unlimited)
+    rate_limit: u32,
+    /// Plugin time (ms)
+    now_ms: u64,
+    /// Current rate window per kind: (start ms, calls)
+    windows: BTreeMap<ActionKind, (u64, u32)>,
+    /// Counters per kind
+    counts: BTreeMap<ActionKind, ActionCounts>,
+    /// Last skipped call
+    last_error: Option<ActionError>,
+}
+
+impl Default for Actions {
+    fn default() -> Self {
+        Self {
+            permitted: None,
+            dry_run: false,
+            read_only: false,
+            rate_limit: DEFAULT_ACTION_RATE_LIMIT,
+            now_ms: 0,
+            windows: BTreeMap::new(),
+            counts: BTreeMap::new(),
+            last_error: None,
+        }
+    }
+}
+
+impl Actions {
+    /// Apply the dry-run and rate limit settings
+    pub fn configure(&mut self, dry_run: bool, rate_limit: u32) {
+        self.dry_run = dry_run;
+        self.rate_limit = rate_limit;
+    }
+
+    /// Skip every call from now on (read-only mirror) or make them again
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Record the host's answer to the permission request
+    pub fn set_permitted(&mut self, permitted: bool) {
+        self.permitted = Some(permitted);
+    }
+
+    /// Set the plugin time used for rate limiting
+    pub fn set_time(&mut self, now_ms: u64) {
+        self.now_ms = now_ms;
+    }
+
+    /// Last skipped call
+    pub fn last_error(&self) -> Option<&ActionError> {
+        self.last_error.as_ref()
+    }
+
+    /// Decide whether a call is made: Ok(true) to call the host, Ok(false) when only logged
+    fn admit(&mut self, kind: ActionKind, detail: &str) -> Result<bool, ActionError> {
+        let window = self.windows.entry(kind).or_insert((self.now_ms, 0));
+        if self.now_ms >= window.0 + RATE_WINDOW_MS {
+            *window = (self.now_ms, 0);
+        }
+        let counts = self.counts.entry(kind).or_default();
+        let result = if kind.limited() && self.permitted == Some(false) {
+            counts.denied += 1;
+            Err(ActionError::PermissionDenied(kind))
+        } else if kind.limited() && self.rate_limit > 0 && window.1 >= self.rate_limit {
+            counts.rate_limited += 1;
+            Err(ActionError::RateLimited(kind))
+        } else {
+            window.1 += 1;
+            if self.dry_run {
+                counts.logged += 1;
+                diagnostics::log(LogLevel::Info, &format!("Dry run: {} {}", kind.name(), detail));
+            } else {
+                counts.taken += 1;
+            }
+            Ok(!self.dry_run)
+        };
+        if let Err(ref error) = result {
+            // Log the first of a run of skipped calls, not every one
+            if self.last_error.as_ref() != Some(error) {
+                diagnostics::log(LogLevel::Warn, &format!("{} ({})", error, detail));
+            }
+            self.last_error = Some(error.clone());
+        }
+        result
+    }
+
+    /// Make a call when admitted; returns whether it was made (or logged in dry-run mode)
+    fn perform(&mut self, kind: ActionKind, detail: &str, call: impl FnOnce()) -> bool {
+        // Expected in a mirror, so neither logged nor kept as the last error; a standby keeps
+        // announcing itself to stay in the election
+        if self.read_only && kind != ActionKind::Announce {
+            self.counts.entry(kind).or_default().read_only += 1;
+            return false;
+        }
+        match self.admit(kind, detail) {
+            Ok(true) => {
+                call();
+                true
+            }
+            Ok(false) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Rename a tab (1-based position)
+    pub fn rename_tab(&mut self, position: u32, name: &str) -> bool {
+        self.perform(ActionKind::RenameTab, &format!("tab {} to {:?}", position, name), || rename_tab(position, name))
+    }
+
+    /// Rename a terminal pane
+    pub fn rename_terminal_pane(&mut self, pane_id: u32, title: &str) -> bool {
+        self.perform(ActionKind::RenamePane, &format!("pane {} to {:?}", pane_id, title), || rename_terminal_pane(pane_id, title))
+    }
+
+    /// Rename a plugin pane
+    pub fn rename_plugin_pane(&mut self, plugin_id: u32, title: &str) -> bool {
+        self.perform(ActionKind::RenamePane, &format!("plugin pane {} to {:?}", plugin_id, title), || rename_plugin_pane(plugin_id, title))
+    }
+
+    /// Focus a terminal pane
+    pub fn focus_terminal_pane(&mut self, pane_id: u32) -> bool {
+        self.perform(ActionKind::FocusPane, &format!("pane {}", pane_id), || focus_terminal_pane(pane_id, false))
+    }
+
+    /// Run a command in the background; its result arrives as a `RunCommandResult` with `context`
+    pub fn run_command(&mut self, command: &[String], context: BTreeMap<String, String>) -> bool {
+        self.perform(ActionKind::RunCommand, &command.join(" "), || {
+            let args: Vec<&str> = command.iter().map(String::as_str).collect();
+            run_command(&args, context);
+        })
+    }
+
+    /// Open a tiled command pane
+    pub fn open_command_pane(&mut self, command: CommandToRun, context: BTreeMap<String, String>) -> bool {
+        let detail = format!("{} {}", command.path.display(), command.args.join(" "));
+        self.perform(ActionKind::OpenCommandPane, &detail, || open_command_pane(command, context))
+    }
+
+    /// Open a floating command pane
+    pub fn open_floating_pane(
+        &mut self,
+        command: CommandToRun,
+        coordinates: Option<FloatingPaneCoordinates>,
+        context: BTreeMap<String, String>,
+    ) -> bool {
+        let detail = format!("{} {}", command.path.display(), command.args.join(" "));
+        self.perform(ActionKind::OpenFloatingPane, &detail, || open_command_pane_floating(command, coordinates, context))
+    }
+
+    /// Close a terminal pane
+    pub fn close_terminal_pane(&mut self, pane_id: u32) -> bool {
+        self.perform(ActionKind::ClosePane, &format!("pane {}", pane_id), || close_terminal_pane(pane_id))
+    }
+
+    /// Toggle a terminal pane's fullscreen state
+    pub fn toggle_fullscreen(&mut self, pane_id: u32) -> bool {
+        self.perform(ActionKind::PaneLayout, &format!("fullscreen pane {}", pane_id),
+            || toggle_pane_id_fullscreen(PaneId::Terminal(pane_id)))
+    }
+
+    /// Open a terminal pane's scrollback in the editor
+    pub fn edit_scrollback(&mut self, pane_id: u32) -> bool {
+        self.perform(ActionKind::PaneLayout, &format!("edit scrollback of pane {}", pane_id),
+            || edit_scrollback_for_pane_with_id(PaneId::Terminal(pane_id)))
+    }
+
+    /// Type text into a terminal pane, as if entered there
+    pub fn write_to_terminal_pane(&mut self, pane_id: u32, text: &str) -> bool {
+        self.perform(ActionKind::WriteToPane, &format!("{:?} to pane {}", text, pane_id),
+            || write_chars_to_pane_id(text, PaneId::Terminal(pane_id)))
+    }
+
+    /// Send a POST request; its result arrives as a `WebRequestResult` with `context`
+    pub fn post(&mut self, url: String, headers: BTreeMap<String, String>, body: Vec<u8>, context: BTreeMap<String, String>) -> bool {
+        let detail = format!("POST {}", url);
+        self.perform(ActionKind::WebRequest, &detail, || web_request(url, HttpVerb::Post, headers, body, context))
+    }
+
+    /// Send a message to other plugins
+    pub fn message_plugin(&mut self, message: MessageToPlugin) -> bool {
+        let detail = format!("{} {}", message.message_name, message.message_payload.as_deref().unwrap_or_default());
+        self.perform(ActionKind::MessagePlugin, &detail, || pipe_message_to_plugin(message))
+    }
+
+    /// Send this instance's election announcement to the other instances
+    pub fn announce(&mut self, message: MessageToPlugin) -> bool {
+        let detail = format!("{} {}", message.message_name, message.message_payload.as_deref().unwrap_or_default());
+        self.perform(ActionKind::Announce, &detail, || pipe_message_to_plugin(message))
+    }
+
+    /// Write to a `zellij pipe` CLI
+    pub fn cli_pipe_output(&mut self, pipe_id: &str, text: &str) -> bool {
+        self.perform(ActionKind::PipeOutput, &format!("{:?} to pipe {}", text, pipe_id), || cli_pipe_output(pipe_id, text))
+    }
+
+    /// Hold a `zellij pipe` CLI open until it is released
+    pub fn block_cli_pipe_input(&mut self, pipe_id: &str) -> bool {
+        self.perform(ActionKind::PipeOutput, &format!("hold pipe {}", pipe_id), || block_cli_pipe_input(pipe_id))
+    }
+
+    /// Release a `zellij pipe` CLI held open
+    pub fn unblock_cli_pipe_input(&mut self, pipe_id: &str) -> bool {
+        self.perform(ActionKind::PipeOutput, &format!("release pipe {}", pipe_id), || unblock_cli_pipe_input(pipe_id))
+    }
+
+    /// Send command output to the originating CLI pipe (and the log)
+    pub fn reply(&mut self, source: &PipeSource, text: &str) {
+        if let PipeSource::Cli(pipe_id) = source {
+            self.cli_pipe_output(pipe_id, text);
+        }
+        diagnostics::log(LogLevel::Info, text.trim_end());
+    }
+
+    /// Stats line, e.g. `actions: rename_tab 12, run_command 3 (2 denied)`
+    pub fn line(&self) -> String {
+        let kinds: Vec<String> = self.counts.iter()
+            .map(|(kind, counts)| {
+                let mut skipped = Vec::new();
+                if counts.logged > 0 {
+                    skipped.push(format!("{} dry run", counts.logged));
+                }
+                if counts.denied > 0 {
+                    skipped.push(format!("{} denied", counts.denied));
+                }
+                if counts.rate_limited > 0 {
+                    skipped.push(format!("{} rate limited", counts.rate_limited));
+                }
+                if counts.read_only > 0 {
+                    skipped.push(format!("{} read-only", counts.read_only));
+                }
+                if skipped.is_empty() {
+                    format!("{} {}", kind.name(), counts.taken)
+                } else {
+                    format!("{} {} ({})", kind.name(), counts.taken, skipped.join(", "))
+                }
+            })
+            .collect();
+        format!("actions: {}", if kinds.is_empty() { "-".to_string() } else { kinds.join(", ") })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denied_permission_skips_calls() {
+        let mut actions = Actions::default();
+        actions.configure(true, 0);
+        assert!(actions.rename_tab(1, "build"));
+        actions.set_permitted(false);
+        assert!(!actions.run_command(&["echo".to_string()], BTreeMap::new()));
+        assert!(!actions.run_command(&["echo".to_string()], BTreeMap::new()));
+        assert_eq!(actions.last_error(), Some(&ActionError::PermissionDenied(ActionKind::RunCommand)));
+        assert_eq!(actions.last_error().unwrap().to_string(), "run_command skipped: RunCommands permission denied");
+        assert_eq!(actions.line(), "actions: rename_tab 0 (1 dry run), run_command 0 (2 denied)");
+    }
+
+    #[test]
+    fn test_rate_limit_per_kind_and_second() {
+        let mut actions = Actions::default();
+        actions.configure(true, 2);
+        actions.set_time(10_000);
+        assert!(actions.focus_terminal_pane(1));
+        assert!(actions.focus_terminal_pane(2));
+        assert!(!actions.focus_terminal_pane(3));
+        assert_eq!(actions.last_error(), Some(&ActionError::RateLimited(ActionKind::FocusPane)));
+        // Other kinds have their own budget, and the next second starts afresh
+        assert!(actions.close_terminal_pane(4));
+        actions.set_time(11_000);
+        assert!(actions.focus_terminal_pane(3));
+        assert_eq!(actions.line(), "actions: focus_pane 0 (3 dry run, 1 rate limited), close_pane 0 (1 dry run)");
+    }
+
+    #[test]
+    fn test_pipe_answers_are_never_refused_or_rate_limited() {
+        let mut actions = Actions::default();
+        actions.configure(true, 1);
+        actions.set_permitted(false);
+        assert!(actions.block_cli_pipe_input("1"));
+        assert!(actions.cli_pipe_output("1", "ok\n"));
+        assert!(actions.unblock_cli_pipe_input("1"));
+        assert!(!actions.message_plugin(MessageToPlugin::new("reply")));
+        assert_eq!(actions.line(), "actions: message_plugin 0 (1 denied), pipe_output 0 (3 dry run)");
+    }
+
+    #[test]
+    fn test_read_only_skips_every_call() {
+        let mut actions = Actions::default();
+        actions.configure(true, 0);
+        actions.set_read_only(true);
+        assert!(!actions.rename_tab(1, "build"));
+        assert!(!actions.focus_terminal_pane(2));
+        assert!(!actions.cli_pipe_output("1", "ok\n"));
+        assert_eq!(actions.last_error(), None);
+        assert_eq!(actions.line(), "actions: rename_tab 0 (1 read-only), focus_pane 0 (1 read-only), pipe_output 0 (1 read-only)");
+
+        // A standby still takes part in the election
+        assert!(actions.announce(MessageToPlugin::new("election")));
+    }
+}
@@ -0,0 +1,176 @@
+//! Session aggregation module for Zellij Visual Notifications
+//!
+//! One plugin instance, e.g. in a `dashboard` session, can collect the notifications of the other
+//! sessions on the machine. Those sessions add the `forward` sink with `forward_session
+//! "dashboard"`: each notification is piped to that session (`zellij --session dashboard pipe`)
+//! tagged with `origin_session`, and withdrawn again once it is acknowledged or expires. With
+//! `aggregator true`, the dashboard keeps forwarded notifications apart from its own panes,
+//! grouped by session: the `sessions` status segment counts them, and the sessions view (`j`)
+//! lists them with a `zellij attach <session>` hint.
+
+use std::collections::BTreeMap;
+use crate::notification::{Notification, NotificationType};
+use crate::query::format_age;
+
+/// Context key marking forwarding commands
+pub const FORWARD_CONTEXT_KEY: &str = "forward";
+
+/// Plugin alias the forwarded notifications are piped to
+pub const FORWARD_PLUGIN: &str = "visual-notifications";
+
+/// Entries kept per origin session (oldest dropped first)
+pub const MAX_ENTRIES_PER_SESSION: usize = 50;
+
+/// A notification forwarded from another session
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteEntry {
+    /// Notification ID in the origin session
+    pub id: String,
+    pub notification_type: NotificationType,
+    pub message: String,
+    /// Pane in the origin session
+    pub pane_id: Option<u32>,
+    /// Plugin time of arrival (ms)
+    pub received_ms: u64,
+}
+
+impl RemoteEntry {
+    /// Detail line, e.g. `error pane 3, 2m ago: Build failed`
+    pub fn line(&self, now_ms: u64) -> String {
+        let pane = self.pane_id.map(|pane_id| format!(" pane {}", pane_id)).unwrap_or_default();
+        format!("{}{}, {} ago: {}",
+            self.notification_type.name(), pane, format_age(now_ms.saturating_sub(self.received_ms)), self.message)
+    }
+}
+
+/// Forwarded notifications by origin session
+#[derive(Debug, Clone, Default)]
+pub struct SessionAggregate {
+    sessions: BTreeMap<String, Vec<RemoteEntry>>,
+}
+
+impl SessionAggregate {
+    /// Keep a forwarded notification (replacing one with the same ID from that session)
+    pub fn record(&mut self, session: &str, notification: &Notification, now_ms: u64) {
+        self.restore(session, RemoteEntry {
+            id: notification.id.clone(),
+            notification_type: notification.notification_type.clone(),
+            message: notification.message.clone(),
+            pane_id: notification.pane_id,
+            received_ms: now_ms,
+        });
+    }
+
+    /// Keep an entry in order of arrival (replacing one with the same ID from that session)
+    pub fn restore(&mut self, session: &str, entry: RemoteEntry) {
+        let entries = self.sessions.entry(session.to_string()).or_default();
+        entries.retain(|kept| kept.id != entry.id);
+        let position = entries.partition_point(|kept| kept.received_ms <= entry.received_ms);
+        entries.insert(position, entry);
+        if entries.len() > MAX_ENTRIES_PER_SESSION {
+            entries.remove(0);
+        }
+    }
+
+    /// Drop a forwarded notification by ID; returns whether one was kept
+    pub fn withdraw(&mut self, id: &str) -> bool {
+        let mut withdrawn = false;
+        for entries in self.sessions.values_mut() {
+            let before = entries.len();
+            entries.retain(|entry| entry.id != id);
+            withdrawn |= entries.len() < before;
+        }
+        self.sessions.retain(|_, entries| !entries.is_empty());
+        withdrawn
+    }
+
+    /// Whether nothing is kept
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Sessions and their entries (oldest first), by session name
+    pub fn sessions(&self) -> impl Iterator<Item = (&str, &[RemoteEntry])> {
+        self.sessions.iter().map(|(session, entries)| (session.as_str(), entries.as_slice()))
+    }
+
+    /// Grouped listing for the `sessions` command: a header with the jump hint per session, then
+    /// its entries, newest first
+    pub fn describe(&self, now_ms: u64) -> String {
+        if self.is_empty() {
+            return "No forwarded notifications".to_string();
+        }
+        let mut lines = Vec::new();
+        for (session, entries) in self.sessions() {
+            lines.push(format!("{} ({}) - zellij attach {}", session, entries.len(), session));
+            lines.extend(entries.iter().rev().map(|entry| format!("  {}", entry.line(now_ms))));
+        }
+        lines.join("\n")
+    }
+}
+
+/// JSON message forwarding a notification to another session
+pub fn forward_payload(notification: &Notification, origin_session: &str) -> String {
+    serde_json::json!({
+        "type": notification.notification_type.name(),
+        "id": notification.id,
+        "message": notification.message,
+        "title": notification.title,
+        "source": notification.source,
+        "priority": notification.priority.name(),
+        "pane_id": notification.pane_id,
+        "branch": notification.branch,
+        "worktree": notification.worktree,
+        "origin_session": origin_session,
+    }).to_string()
+}
+
+/// JSON message withdrawing a forwarded notification
+pub fn withdraw_payload(id: &str, origin_session: &str) -> String {
+    serde_json::json!({ "type": "cancel", "id": id, "origin_session": origin_session }).to_string()
+}
+
+/// Command piping a message to the plugin in another session
+pub fn forward_command(session: &str, payload: &str) -> Vec<String> {
+    ["zellij", "--session", session, "pipe", "--plugin", FORWARD_PLUGIN, "--", payload]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_groups_by_session() {
+        let mut aggregate = SessionAggregate::default();
+        let build = Notification::error("Build failed").for_pane(3);
+        aggregate.record("api", &build, 0);
+        aggregate.record("web", &Notification::success("Deployed"), 1_000);
+        aggregate.record("api", &build, 2_000);
+
+        let sessions: Vec<(&str, usize)> = aggregate.sessions().map(|(name, entries)| (name, entries.len())).collect();
+        assert_eq!(sessions, vec![("api", 1), ("web", 1)]);
+        let listing = aggregate.describe(62_000);
+        assert!(listing.starts_with("api (1) - zellij attach api\n  error pane 3, 1m ago: Build failed"));
+
+        assert!(aggregate.withdraw(&build.id));
+        assert!(!aggregate.withdraw(&build.id));
+        assert_eq!(aggregate.sessions().count(), 1);
+    }
+
+    #[test]
+    fn test_forward_payload_round_trips() {
+        let notification = Notification::attention("Waiting").for_pane(2);
+        let payload = forward_payload(&notification, "dev");
+        let value: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(value["origin_session"], "dev");
+        assert_eq!(value["type"], "attention");
+        assert_eq!(value["pane_id"], 2);
+
+        let command = forward_command("dashboard", &payload);
+        assert_eq!(&command[..6], ["zellij", "--session", "dashboard", "pipe", "--plugin", FORWARD_PLUGIN]);
+        assert_eq!(command.last().unwrap(), &payload);
+    }
+}
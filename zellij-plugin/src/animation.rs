@@ -1,10 +1,60 @@
 //! Animation engine module for Zellij Visual Notifications
 //!
-//! Provides smooth animations for visual notifications including pulse, fade, flash, and breathe effects.
+//! Provides smooth animations for visual notifications including pulse, fade, flash, breathe, and
+//! gradient effects.
+//!
+//! Animation timing is defined in ticks of `DEFAULT_TICK_MS`, so a faster tick rate raises the
+//! frame rate without speeding animations up. With interpolation, frames are computed from the
+//! elapsed plugin time instead of counted ticks, so late timer events skip ahead rather than
+//! stretching the animation.
+//!
+//! With `priority_scaling`, the priority of a notification scales its animation: Critical ones
+//! run at least `CRITICAL_CYCLES` cycles with dips down to full darkness, Low ones a single
+//! shallow cycle.
+//!
+//! Clear-all leaves a short [`ClearCascade`] behind: the cleared entries fade out left to right
+//! over `CLEAR_CASCADE_MS`, so it is visible how many went.
 
-use crate::config::{AnimationConfig, AnimationStyle};
+use crate::config::{AnimationConfig, AnimationStyle, DEFAULT_TICK_MS};
+use crate::notification::{NotificationType, Priority};
 use crate::state::VisualState;
 
+/// Minimum cycles of a Critical notification's animation
+pub const CRITICAL_CYCLES: u64 = 5;
+
+/// Depth of a Critical notification's brightness dips (beyond 1.0 reaches full darkness)
+const CRITICAL_DEPTH: f32 = 1.5;
+
+/// Depth of a Low notification's brightness dips
+const LOW_DEPTH: f32 = 0.4;
+
+/// Duration of the clear-all cascade (ms)
+pub const CLEAR_CASCADE_MS: u64 = 300;
+
+/// How strongly a notification animates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Intensity {
+    /// Animation cycles
+    pub cycles: u64,
+    /// Scale of the brightness dips (1.0 = as the style draws them)
+    pub depth: f32,
+}
+
+/// Point in time an animation is sampled at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Frame {
+    /// Timer tick
+    pub tick: u64,
+    /// Plugin time (ms), used when interpolating
+    pub now_ms: u64,
+}
+
+impl From<u64> for Frame {
+    fn from(tick: u64) -> Self {
+        Self { tick, now_ms: 0 }
+    }
+}
+
 /// Animation engine for managing visual effects
 #[derive(Debug, Clone)]
 pub struct AnimationEngine {
@@ -42,16 +92,45 @@ impl AnimationEngine {
         self.config.enabled && self.config.style != AnimationStyle::None
     }
 
+    /// Animation intensity for a priority (the configured cycles at full depth without scaling)
+    pub fn intensity(&self, priority: Priority) -> Intensity {
+        let cycles = self.config.cycles as u64;
+        match priority {
+            _ if !self.config.priority_scaling => Intensity { cycles, depth: 1.0 },
+            Priority::Critical => Intensity { cycles: cycles.max(CRITICAL_CYCLES), depth: CRITICAL_DEPTH },
+            Priority::Low => Intensity { cycles: cycles.min(1), depth: LOW_DEPTH },
+            Priority::Normal | Priority::High => Intensity { cycles, depth: 1.0 },
+        }
+    }
+
+    /// Total animation ticks of a visual state, scaled by its priority
+    fn total_ticks_for(&self, visual_state: &VisualState) -> u64 {
+        self.ticks_per_cycle * self.intensity(visual_state.priority).cycles
+    }
+
+    /// Elapsed time of a visual state's animation, in default-length ticks (a paused animation
+    /// stays at the frame it was paused at)
+    fn elapsed_ticks(&self, visual_state: &VisualState, frame: Frame) -> f32 {
+        let frame = visual_state.animation_paused_at.unwrap_or(frame);
+        if self.config.interpolate {
+            frame.now_ms.saturating_sub(visual_state.animation_start_ms) as f32 / DEFAULT_TICK_MS as f32
+        } else {
+            let ticks = frame.tick.saturating_sub(visual_state.animation_start_tick);
+            (ticks * self.config.tick_ms) as f32 / DEFAULT_TICK_MS as f32
+        }
+    }
+
     /// Update animation state based on current tick
-    pub fn update_animation(&self, visual_state: &mut VisualState, current_tick: u64) {
+    pub fn update_animation(&self, visual_state: &mut VisualState, frame: impl Into<Frame>) {
         if !self.is_enabled() || !visual_state.is_animating {
             return;
         }
 
-        let elapsed_ticks = current_tick.saturating_sub(visual_state.animation_start_tick);
+        let elapsed_ticks = self.elapsed_ticks(visual_state, frame.into());
 
         // Check if animation is complete
-        if elapsed_ticks >= self.total_ticks {
+        let total_ticks = self.total_ticks_for(visual_state);
+        if elapsed_ticks >= total_ticks as f32 {
             visual_state.is_animating = false;
             visual_state.animation_phase = 0.0;
             visual_state.brightness = 1.0;
@@ -59,16 +138,27 @@ impl AnimationEngine {
         }
 
         // Calculate animation phase (0.0 - 1.0)
-        let phase = (elapsed_ticks as f32 / self.total_ticks as f32).clamp(0.0, 1.0);
+        let phase = (elapsed_ticks / total_ticks as f32).clamp(0.0, 1.0);
         visual_state.animation_phase = phase;
 
         // Calculate brightness based on animation style
-        visual_state.brightness = self.calculate_brightness(elapsed_ticks, &visual_state.animation_style);
+        visual_state.brightness = self.scaled_brightness(elapsed_ticks, visual_state);
+    }
+
+    /// Brightness of a visual state's animation, with cycles and depth scaled by its priority
+    fn scaled_brightness(&self, elapsed_ticks: f32, visual_state: &VisualState) -> f32 {
+        let brightness = match visual_state.animation_style {
+            // Fade spans the whole animation, however many cycles it has
+            AnimationStyle::Fade => 1.0 - elapsed_ticks / self.total_ticks_for(visual_state) as f32,
+            ref style => self.calculate_brightness(elapsed_ticks, style),
+        };
+        let depth = self.intensity(visual_state.priority).depth;
+        (1.0 - (1.0 - brightness) * depth).clamp(0.0, 1.0)
     }
 
     /// Calculate brightness value based on animation style and elapsed ticks
-    fn calculate_brightness(&self, elapsed_ticks: u64, style: &AnimationStyle) -> f32 {
-        let cycle_phase = (elapsed_ticks % self.ticks_per_cycle) as f32 / self.ticks_per_cycle as f32;
+    fn calculate_brightness(&self, elapsed_ticks: f32, style: &AnimationStyle) -> f32 {
+        let cycle_phase = (elapsed_ticks % self.ticks_per_cycle as f32) / self.ticks_per_cycle as f32;
 
         match style {
             AnimationStyle::Pulse => {
@@ -88,7 +178,7 @@ impl AnimationEngine {
             }
             AnimationStyle::Fade => {
                 // Gradual fade out over entire animation
-                let total_phase = elapsed_ticks as f32 / self.total_ticks as f32;
+                let total_phase = elapsed_ticks / self.total_ticks as f32;
                 1.0 - total_phase
             }
             AnimationStyle::Breathe => {
@@ -96,67 +186,135 @@ impl AnimationEngine {
                 let angle = cycle_phase * std::f32::consts::PI;
                 0.4 + 0.6 * angle.sin()
             }
-            AnimationStyle::None => 1.0,
+            // Gradient cycles the color instead of the brightness
+            AnimationStyle::Gradient | AnimationStyle::None => 1.0,
+        }
+    }
+
+    /// Position within the current cycle (0.0 - 1.0) of a running Gradient animation
+    pub fn gradient_position(&self, visual_state: &VisualState, frame: impl Into<Frame>) -> Option<f32> {
+        if !self.is_enabled() || !visual_state.is_animating || visual_state.animation_style != AnimationStyle::Gradient {
+            return None;
         }
+
+        let elapsed_ticks = self.elapsed_ticks(visual_state, frame.into());
+        Some((elapsed_ticks % self.ticks_per_cycle as f32) / self.ticks_per_cycle as f32)
     }
 
     /// Get the current brightness for a visual state
-    pub fn get_brightness(&self, visual_state: &VisualState, current_tick: u64) -> f32 {
+    pub fn get_brightness(&self, visual_state: &VisualState, frame: impl Into<Frame>) -> f32 {
         if !self.is_enabled() || !visual_state.is_animating {
             return 1.0;
         }
 
-        let elapsed_ticks = current_tick.saturating_sub(visual_state.animation_start_tick);
-        self.calculate_brightness(elapsed_ticks, &visual_state.animation_style)
+        let elapsed_ticks = self.elapsed_ticks(visual_state, frame.into());
+        self.scaled_brightness(elapsed_ticks, visual_state)
     }
 
     /// Check if animation should continue
-    pub fn should_continue(&self, visual_state: &VisualState, current_tick: u64) -> bool {
+    pub fn should_continue(&self, visual_state: &VisualState, frame: impl Into<Frame>) -> bool {
         if !visual_state.is_animating {
             return false;
         }
 
-        let elapsed_ticks = current_tick.saturating_sub(visual_state.animation_start_tick);
-        elapsed_ticks < self.total_ticks
+        self.elapsed_ticks(visual_state, frame.into()) < self.total_ticks_for(visual_state) as f32
     }
 
     /// Reset animation for a visual state
-    pub fn reset_animation(&self, visual_state: &mut VisualState, current_tick: u64) {
-        visual_state.animation_start_tick = current_tick;
+    pub fn reset_animation(&self, visual_state: &mut VisualState, frame: impl Into<Frame>) {
+        let frame = frame.into();
+        visual_state.animation_start_tick = frame.tick;
+        visual_state.animation_start_ms = frame.now_ms;
         visual_state.animation_phase = 0.0;
         visual_state.brightness = 1.0;
     }
 
     /// Start a new animation for a visual state
-    pub fn start_animation(&self, visual_state: &mut VisualState, current_tick: u64, style: AnimationStyle) {
+    pub fn start_animation(&self, visual_state: &mut VisualState, frame: impl Into<Frame>, style: AnimationStyle) {
         if !self.is_enabled() {
             return;
         }
 
+        let frame = frame.into();
         visual_state.is_animating = true;
-        visual_state.animation_start_tick = current_tick;
+        visual_state.animation_start_tick = frame.tick;
+        visual_state.animation_start_ms = frame.now_ms;
+        visual_state.animation_paused_at = None;
         visual_state.animation_phase = 0.0;
         visual_state.animation_style = style;
         visual_state.brightness = 1.0;
     }
 
+    /// Hold a running animation at its current frame (e.g. while its pane's tab is not shown)
+    pub fn pause_animation(&self, visual_state: &mut VisualState, frame: impl Into<Frame>) {
+        if visual_state.is_animating && visual_state.animation_paused_at.is_none() {
+            visual_state.animation_paused_at = Some(frame.into());
+        }
+    }
+
+    /// Resume a paused animation where it was held, shifting its start by the time it was paused
+    pub fn resume_animation(&self, visual_state: &mut VisualState, frame: impl Into<Frame>) {
+        let Some(paused_at) = visual_state.animation_paused_at.take() else {
+            return;
+        };
+        let frame = frame.into();
+        visual_state.animation_start_tick += frame.tick.saturating_sub(paused_at.tick);
+        visual_state.animation_start_ms += frame.now_ms.saturating_sub(paused_at.now_ms);
+    }
+
     /// Stop animation for a visual state
     pub fn stop_animation(&self, visual_state: &mut VisualState) {
         visual_state.is_animating = false;
+        visual_state.animation_paused_at = None;
         visual_state.animation_phase = 0.0;
         visual_state.brightness = 1.0;
     }
 
     /// Get animation progress as percentage (0-100)
-    pub fn get_progress(&self, visual_state: &VisualState, current_tick: u64) -> u8 {
+    pub fn get_progress(&self, visual_state: &VisualState, frame: impl Into<Frame>) -> u8 {
         if !visual_state.is_animating {
             return 100;
         }
 
-        let elapsed_ticks = current_tick.saturating_sub(visual_state.animation_start_tick);
-        let progress = (elapsed_ticks as f32 / self.total_ticks as f32 * 100.0).min(100.0);
+        let elapsed_ticks = self.elapsed_ticks(visual_state, frame.into());
+        let progress = (elapsed_ticks / self.total_ticks_for(visual_state) as f32 * 100.0).min(100.0);
         progress as u8
     }
+
+    /// Brightness of a cleared entry in the cascade: each entry fades over half the cascade,
+    /// starting a little after the entry to its left, so the last one is gone at the end
+    pub fn cascade_brightness(&self, index: usize, count: usize, elapsed_ms: u64) -> f32 {
+        let fade_ms = CLEAR_CASCADE_MS as f32 / 2.0;
+        let stagger_ms = if count > 1 { fade_ms / (count - 1) as f32 } else { 0.0 };
+        let progress = ((elapsed_ms as f32 - index as f32 * stagger_ms) / fade_ms).clamp(0.0, 1.0);
+        1.0 - easing::ease_in(progress)
+    }
+}
+
+/// Entries removed by clear-all, drawn fading out for `CLEAR_CASCADE_MS`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearCascade {
+    /// Cleared entries in status bar order: pane and type
+    pub entries: Vec<(u32, NotificationType)>,
+    /// Plugin time the cascade started (ms)
+    pub start_ms: u64,
+}
+
+impl ClearCascade {
+    /// Start a cascade for the cleared entries
+    pub fn new(entries: Vec<(u32, NotificationType)>, now_ms: u64) -> Self {
+        Self { entries, start_ms: now_ms }
+    }
+
+    /// Time since the cascade started (ms)
+    pub fn elapsed_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.start_ms)
+    }
+
+    /// Whether the cascade is still drawn
+    pub fn is_running(&self, now_ms: u64) -> bool {
+        self.elapsed_ms(now_ms) < CLEAR_CASCADE_MS
+    }
 }
 
 /// Animation keyframe for complex animations
@@ -385,13 +543,14 @@ mod tests {
             speed: 50,
             cycles: 3,
             duration_ms: 2000,
+            ..AnimationConfig::default()
         };
         let engine = AnimationEngine::new(&config);
 
         // Test brightness at different points
-        let b0 = engine.calculate_brightness(0, &AnimationStyle::Pulse);
-        let b_quarter = engine.calculate_brightness(engine.ticks_per_cycle / 4, &AnimationStyle::Pulse);
-        let b_half = engine.calculate_brightness(engine.ticks_per_cycle / 2, &AnimationStyle::Pulse);
+        let b0 = engine.calculate_brightness(0.0, &AnimationStyle::Pulse);
+        let b_quarter = engine.calculate_brightness(engine.ticks_per_cycle as f32 / 4.0, &AnimationStyle::Pulse);
+        let b_half = engine.calculate_brightness(engine.ticks_per_cycle as f32 / 2.0, &AnimationStyle::Pulse);
 
         // Brightness should vary during pulse
         assert!(b0 >= 0.0 && b0 <= 1.0);
@@ -407,17 +566,105 @@ mod tests {
             speed: 50,
             cycles: 1,
             duration_ms: 2000,
+            ..AnimationConfig::default()
         };
         let engine = AnimationEngine::new(&config);
 
-        let b_start = engine.calculate_brightness(0, &AnimationStyle::Fade);
-        let b_end = engine.calculate_brightness(engine.total_ticks, &AnimationStyle::Fade);
+        let b_start = engine.calculate_brightness(0.0, &AnimationStyle::Fade);
+        let b_end = engine.calculate_brightness(engine.total_ticks as f32, &AnimationStyle::Fade);
 
         assert!(b_start > b_end);
         assert!(b_start > 0.9);
         assert!(b_end < 0.1);
     }
 
+    #[test]
+    fn test_tick_rate_keeps_animation_timing() {
+        let base = AnimationEngine::new(&AnimationConfig::default());
+        let fast = AnimationEngine::new(&AnimationConfig { tick_ms: 10, ..AnimationConfig::default() });
+        let mut state = VisualState::new();
+        base.start_animation(&mut state, 0, AnimationStyle::Pulse);
+
+        // 5 ticks of 10ms sample the same point as 1 tick of 50ms
+        assert_eq!(fast.get_brightness(&state, 5), base.get_brightness(&state, 1));
+        assert!(fast.should_continue(&state, base.total_ticks * 5 - 1));
+        assert!(!fast.should_continue(&state, base.total_ticks * 5));
+    }
+
+    #[test]
+    fn test_priority_scales_cycles_and_depth() {
+        let engine = AnimationEngine::new(&AnimationConfig { style: AnimationStyle::Flash, ..AnimationConfig::default() });
+        let mut state = VisualState::new();
+        engine.start_animation(&mut state, 0, AnimationStyle::Flash);
+        // Middle of a cycle, where Flash dips
+        let dip = engine.ticks_per_cycle * 2 / 5;
+
+        state.priority = Priority::Critical;
+        assert_eq!(engine.intensity(Priority::Critical).cycles, CRITICAL_CYCLES);
+        assert_eq!(engine.get_brightness(&state, dip), 0.0);
+        assert!(engine.should_continue(&state, engine.ticks_per_cycle * CRITICAL_CYCLES - 1));
+
+        state.priority = Priority::Normal;
+        assert!((engine.get_brightness(&state, dip) - 0.3).abs() < 0.001);
+        assert!(!engine.should_continue(&state, engine.total_ticks));
+
+        state.priority = Priority::Low;
+        assert!((engine.get_brightness(&state, dip) - 0.72).abs() < 0.001);
+        assert!(!engine.should_continue(&state, engine.ticks_per_cycle));
+
+        // Without scaling every priority animates as configured
+        let flat = AnimationEngine::new(&AnimationConfig { priority_scaling: false, ..AnimationConfig::default() });
+        assert_eq!(flat.intensity(Priority::Critical), flat.intensity(Priority::Low));
+        assert_eq!(flat.intensity(Priority::Low), Intensity { cycles: 3, depth: 1.0 });
+    }
+
+    #[test]
+    fn test_interpolated_frames_follow_elapsed_time() {
+        let engine = AnimationEngine::new(&AnimationConfig { interpolate: true, ..AnimationConfig::default() });
+        let mut state = VisualState::new();
+        engine.start_animation(&mut state, Frame { tick: 3, now_ms: 1000 }, AnimationStyle::Pulse);
+
+        // A late tick lands where the elapsed time says, between whole ticks
+        let quarter_ms = engine.ticks_per_cycle * DEFAULT_TICK_MS / 4;
+        let brightness = engine.get_brightness(&state, Frame { tick: 4, now_ms: 1000 + quarter_ms });
+        assert!((brightness - 1.0).abs() < 0.001);
+        let between = engine.get_brightness(&state, Frame { tick: 4, now_ms: 1025 });
+        assert!(between > engine.get_brightness(&state, Frame { tick: 4, now_ms: 1000 }));
+        assert!(between < engine.get_brightness(&state, Frame { tick: 4, now_ms: 1050 }));
+    }
+
+    #[test]
+    fn test_paused_animation_resumes_where_it_stopped() {
+        let engine = AnimationEngine::new(&AnimationConfig { interpolate: true, ..AnimationConfig::default() });
+        let mut state = VisualState::new();
+        engine.start_animation(&mut state, Frame { tick: 0, now_ms: 0 }, AnimationStyle::Pulse);
+        let before = engine.get_brightness(&state, Frame { tick: 5, now_ms: 250 });
+
+        engine.pause_animation(&mut state, Frame { tick: 5, now_ms: 250 });
+        engine.pause_animation(&mut state, Frame { tick: 50, now_ms: 2_500 });
+        assert!((engine.get_brightness(&state, Frame { tick: 60, now_ms: 3_000 }) - before).abs() < 0.001);
+        engine.resume_animation(&mut state, Frame { tick: 200, now_ms: 10_250 });
+        assert_eq!(state.animation_start_ms, 10_000);
+        assert_eq!(state.animation_start_tick, 195);
+        assert!((engine.get_brightness(&state, Frame { tick: 200, now_ms: 10_250 }) - before).abs() < 0.001);
+        assert!(state.animation_paused_at.is_none());
+    }
+
+    #[test]
+    fn test_clear_cascade_fades_left_to_right() {
+        let engine = AnimationEngine::default();
+        assert_eq!(engine.cascade_brightness(0, 3, 0), 1.0);
+        // Halfway, the first entry is gone while the last has just started fading
+        assert_eq!(engine.cascade_brightness(0, 3, CLEAR_CASCADE_MS / 2), 0.0);
+        assert_eq!(engine.cascade_brightness(2, 3, CLEAR_CASCADE_MS / 2), 1.0);
+        assert!(engine.cascade_brightness(1, 3, CLEAR_CASCADE_MS / 2) < 1.0);
+        assert_eq!(engine.cascade_brightness(2, 3, CLEAR_CASCADE_MS), 0.0);
+
+        let cascade = ClearCascade::new(vec![(1, NotificationType::Error)], 1000);
+        assert!(cascade.is_running(1000 + CLEAR_CASCADE_MS - 1));
+        assert!(!cascade.is_running(1000 + CLEAR_CASCADE_MS));
+    }
+
     #[test]
     fn test_custom_animation_interpolation() {
         let anim = presets::gentle_pulse();
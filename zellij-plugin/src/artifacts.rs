@@ -0,0 +1,156 @@
+//! Derived artifacts module for Zellij Visual Notifications
+//!
+//! A displayed notification leaves marks outside the plugin pane: an icon in the target pane's
+//! title, a badge in its tab's name, the summary in the window title or session name, and the
+//! unread count file. The registry records each mark when the call that makes it went through,
+//! together with what it replaced, and the plugin brings all of them in line with the
+//! notifications in one pass after every event. A mark whose notification was acknowledged is
+//! restored in that pass; when the host refuses the restore (rate limit, denied permission) the
+//! mark stays registered and the restore is tried again on the next pass, so no badge is left
+//! behind because one call in the middle failed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A mark left outside the plugin pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Artifact {
+    /// Icon prefixed to a pane's title
+    PaneTitle(u32),
+    /// Badge in the name of the tab at a position
+    TabName(usize),
+    /// Summary in the window title or session name
+    WindowTitle,
+    /// Unread count file on the host
+    UnreadCount,
+}
+
+impl Artifact {
+    /// Short description for the stats line and logs
+    pub fn describe(&self) -> String {
+        match self {
+            Self::PaneTitle(pane_id) => format!("pane {} title", pane_id),
+            Self::TabName(position) => format!("tab {} name", position + 1),
+            Self::WindowTitle => "window title".to_string(),
+            Self::UnreadCount => "unread count".to_string(),
+        }
+    }
+}
+
+/// What a mark changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Applied {
+    /// Value written
+    pub value: String,
+    /// Value it replaced, written back to restore it
+    pub original: String,
+}
+
+impl Applied {
+    /// Whether the mark shows nothing (e.g. a window title without summary)
+    pub fn is_clean(&self) -> bool {
+        self.value == self.original
+    }
+}
+
+/// Marks applied outside the plugin pane
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactRegistry {
+    /// Applied marks
+    applied: BTreeMap<Artifact, Applied>,
+    /// Marks whose last restore the host refused
+    failed: BTreeSet<Artifact>,
+}
+
+impl ArtifactRegistry {
+    /// Record a mark once the call writing it went through
+    pub fn record(&mut self, artifact: Artifact, value: String, original: String) {
+        self.failed.remove(&artifact);
+        self.applied.insert(artifact, Applied { value, original });
+    }
+
+    /// Mark applied at a place, if any
+    pub fn applied(&self, artifact: Artifact) -> Option<&Applied> {
+        self.applied.get(&artifact)
+    }
+
+    /// Drop a mark once the call restoring the original went through, or when it is no longer the
+    /// plugin's to restore (pane closed, tab renamed by hand)
+    pub fn release(&mut self, artifact: Artifact) {
+        self.failed.remove(&artifact);
+        self.applied.remove(&artifact);
+    }
+
+    /// Note that the host refused to restore a mark (it is tried again on the next pass)
+    pub fn restore_failed(&mut self, artifact: Artifact) {
+        if self.applied.contains_key(&artifact) {
+            self.failed.insert(artifact);
+        }
+    }
+
+    /// Pane titles carrying an icon
+    pub fn pane_titles(&self) -> Vec<u32> {
+        self.applied.keys()
+            .filter_map(|artifact| match artifact {
+                Artifact::PaneTitle(pane_id) => Some(*pane_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Tab positions whose name carries a badge
+    pub fn tab_names(&self) -> Vec<usize> {
+        self.applied.keys()
+            .filter_map(|artifact| match artifact {
+                Artifact::TabName(position) => Some(*position),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Stats line, e.g. `artifacts: pane 3 title, tab 2 name (restore pending: pane 3 title)`
+    pub fn line(&self) -> String {
+        let marks: Vec<String> = self.applied.iter()
+            .filter(|(_, applied)| !applied.is_clean())
+            .map(|(artifact, _)| artifact.describe())
+            .collect();
+        if marks.is_empty() {
+            return "artifacts: none".to_string();
+        }
+        let mut line = format!("artifacts: {}", marks.join(", "));
+        if !self.failed.is_empty() {
+            let failed: Vec<String> = self.failed.iter().map(|artifact| artifact.describe()).collect();
+            line.push_str(&format!(" (restore pending: {})", failed.join(", ")));
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refused_restore_stays_registered() {
+        let mut registry = ArtifactRegistry::default();
+        assert_eq!(registry.line(), "artifacts: none");
+
+        registry.record(Artifact::PaneTitle(3), "✘ cargo".to_string(), "cargo".to_string());
+        registry.record(Artifact::TabName(1), "api ✘1".to_string(), "api".to_string());
+        registry.record(Artifact::WindowTitle, "work".to_string(), "work".to_string());
+        assert_eq!(registry.pane_titles(), vec![3]);
+        assert_eq!(registry.tab_names(), vec![1]);
+        assert_eq!(registry.line(), "artifacts: pane 3 title, tab 2 name");
+
+        // The host refused the restore: still registered, reported, and retried
+        registry.restore_failed(Artifact::PaneTitle(3));
+        assert_eq!(registry.applied(Artifact::PaneTitle(3)).map(|applied| applied.original.as_str()), Some("cargo"));
+        assert_eq!(registry.line(), "artifacts: pane 3 title, tab 2 name (restore pending: pane 3 title)");
+        registry.release(Artifact::PaneTitle(3));
+        registry.release(Artifact::TabName(1));
+        assert_eq!(registry.line(), "artifacts: none");
+
+        // Only applied marks can fail to be restored
+        registry.restore_failed(Artifact::UnreadCount);
+        assert_eq!(registry.line(), "artifacts: none");
+    }
+}
@@ -0,0 +1,228 @@
+//! Attention reason module for Zellij Visual Notifications
+//!
+//! Claude Code hook payloads say why Claude needs attention: a permission prompt, a finished
+//! task, an error, or an idle prompt waiting for input. Each reason gets its own sub-icon and
+//! color and a configurable policy (priority, animation style, whether it waits for a typing
+//! pause), so a permission prompt can interrupt immediately while a finished task only pulses
+//! gently.
+
+use serde::{Deserialize, Serialize};
+use crate::config::{AnimationStyle, ThemeConfig};
+use crate::notification::{NotificationType, Priority};
+
+/// Why Claude needs attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttentionReason {
+    /// A tool call waits for permission
+    Permission,
+    /// Claude finished its task
+    Finished,
+    /// A tool call or the session failed
+    Error,
+    /// Claude is idle, waiting for input
+    Input,
+}
+
+/// Attention reasons, in policy order
+pub const ATTENTION_REASONS: [AttentionReason; 4] = [
+    AttentionReason::Permission,
+    AttentionReason::Finished,
+    AttentionReason::Error,
+    AttentionReason::Input,
+];
+
+impl AttentionReason {
+    /// Parse an explicit reason name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "permission" | "permission_prompt" => Some(Self::Permission),
+            "finished" | "task_finished" | "stop" => Some(Self::Finished),
+            "error" => Some(Self::Error),
+            "input" | "waiting" | "idle_prompt" => Some(Self::Input),
+            _ => None,
+        }
+    }
+
+    /// Derive the reason from Claude Code hook fields (`hook_event_name`, `notification_type`,
+    /// `message`)
+    pub fn from_hook(event: &str, hook_type: Option<&str>, message: Option<&str>) -> Option<Self> {
+        match event {
+            "Stop" | "SubagentStop" => Some(Self::Finished),
+            "StopFailure" | "PostToolUseFailure" => Some(Self::Error),
+            "Notification" => hook_type.and_then(Self::from_name).or_else(|| {
+                // Older payloads only carry the message text
+                let message = message.unwrap_or_default().to_lowercase();
+                if message.contains("permission") {
+                    Some(Self::Permission)
+                } else {
+                    Some(Self::Input)
+                }
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reason name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Permission => "permission",
+            Self::Finished => "finished",
+            Self::Error => "error",
+            Self::Input => "input",
+        }
+    }
+
+    /// Notification type used when the sender did not give one
+    pub fn notification_type(&self) -> NotificationType {
+        match self {
+            Self::Permission | Self::Input => NotificationType::Attention,
+            Self::Finished => NotificationType::Success,
+            Self::Error => NotificationType::Error,
+        }
+    }
+
+    /// Sub-icon distinguishing the reason
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Permission => "\u{1F510}", // Lock with key
+            Self::Finished => "\u{1F3C1}",   // Chequered flag
+            Self::Error => "\u{1F6D1}",      // Stop sign
+            Self::Input => "\u{2328}",       // Keyboard
+        }
+    }
+
+    /// Theme color used unless the policy sets one
+    pub fn theme_color<'a>(&self, theme: &'a ThemeConfig) -> &'a str {
+        match self {
+            Self::Permission => &theme.warning_color,
+            Self::Finished => &theme.success_color,
+            Self::Error => &theme.error_color,
+            Self::Input => &theme.info_color,
+        }
+    }
+}
+
+/// Presentation policy for one attention reason
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttentionPolicy {
+    /// Priority given to the notification
+    pub priority: Priority,
+    /// Animation style
+    pub style: AnimationStyle,
+    /// Color override (#rrggbb; the reason's theme color if unset)
+    pub color: Option<String>,
+    /// Hold the notification while the user is typing (off: interrupt immediately)
+    pub defer: bool,
+}
+
+impl AttentionPolicy {
+    /// Default policy for a reason
+    fn default_for(reason: AttentionReason) -> Self {
+        let (priority, style) = match reason {
+            AttentionReason::Permission => (Priority::Critical, AnimationStyle::Flash),
+            AttentionReason::Finished => (Priority::Normal, AnimationStyle::Breathe),
+            AttentionReason::Error => (Priority::High, AnimationStyle::Pulse),
+            AttentionReason::Input => (Priority::High, AnimationStyle::Pulse),
+        };
+        Self { priority, style, color: None, defer: reason != AttentionReason::Permission }
+    }
+
+    /// Apply `key=value` settings (`priority=critical style=flash color=#ff8800 defer=false`)
+    pub fn apply(&mut self, settings: &[String]) -> Result<(), String> {
+        for setting in settings {
+            let (key, value) = setting.split_once('=')
+                .ok_or_else(|| format!("expected key=value, got: {}", setting))?;
+            match key {
+                "priority" => self.priority = Priority::from_name(value)
+                    .ok_or_else(|| format!("invalid priority: {}", value))?,
+                "style" => self.style = AnimationStyle::from_str(value),
+                "color" => self.color = Some(value.to_string()),
+                "defer" => self.defer = value.parse()
+                    .map_err(|_| format!("invalid defer: {} (expected true or false)", value))?,
+                other => return Err(format!("unknown attention setting: {}", other)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Policies for all attention reasons
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttentionPolicies {
+    /// Policy per reason, in `ATTENTION_REASONS` order
+    policies: Vec<(AttentionReason, AttentionPolicy)>,
+}
+
+impl Default for AttentionPolicies {
+    fn default() -> Self {
+        Self {
+            policies: ATTENTION_REASONS.iter()
+                .map(|reason| (*reason, AttentionPolicy::default_for(*reason)))
+                .collect(),
+        }
+    }
+}
+
+impl AttentionPolicies {
+    /// Policy for a reason
+    pub fn get(&self, reason: AttentionReason) -> &AttentionPolicy {
+        self.policies.iter()
+            .find(|(r, _)| *r == reason)
+            .map(|(_, policy)| policy)
+            .expect("every reason has a policy")
+    }
+
+    /// Mutable policy for a reason
+    pub fn get_mut(&mut self, reason: AttentionReason) -> &mut AttentionPolicy {
+        self.policies.iter_mut()
+            .find(|(r, _)| *r == reason)
+            .map(|(_, policy)| policy)
+            .expect("every reason has a policy")
+    }
+
+    /// Color for a reason: the policy override, else the reason's theme color
+    pub fn color(&self, reason: AttentionReason, theme: &ThemeConfig) -> String {
+        self.get(reason).color.clone().unwrap_or_else(|| reason.theme_color(theme).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_from_hook_payload() {
+        assert_eq!(AttentionReason::from_hook("Stop", None, None), Some(AttentionReason::Finished));
+        assert_eq!(
+            AttentionReason::from_hook("Notification", Some("permission_prompt"), None),
+            Some(AttentionReason::Permission)
+        );
+        assert_eq!(
+            AttentionReason::from_hook("Notification", None, Some("Claude needs your permission to use Bash")),
+            Some(AttentionReason::Permission)
+        );
+        assert_eq!(
+            AttentionReason::from_hook("Notification", None, Some("Claude is waiting for your input")),
+            Some(AttentionReason::Input)
+        );
+        assert_eq!(AttentionReason::from_hook("PreToolUse", None, None), None);
+    }
+
+    #[test]
+    fn test_policy_settings() {
+        let mut policies = AttentionPolicies::default();
+        assert_eq!(policies.get(AttentionReason::Permission).priority, Priority::Critical);
+        assert!(!policies.get(AttentionReason::Permission).defer);
+
+        let settings = vec!["priority=low".to_string(), "color=#00ff00".to_string()];
+        policies.get_mut(AttentionReason::Finished).apply(&settings).unwrap();
+        assert_eq!(policies.get(AttentionReason::Finished).priority, Priority::Low);
+        assert_eq!(policies.color(AttentionReason::Finished, &ThemeConfig::default()), "#00ff00");
+        assert_eq!(
+            policies.color(AttentionReason::Input, &ThemeConfig::default()),
+            ThemeConfig::default().info_color
+        );
+        assert!(policies.get_mut(AttentionReason::Error).apply(&["volume=11".to_string()]).is_err());
+    }
+}
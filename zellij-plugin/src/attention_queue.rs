@@ -0,0 +1,146 @@
+//! Attention queue module for Zellij Visual Notifications
+//!
+//! With several Claude sessions waiting for input at once, five flashing entries compete for the
+//! same glance. In attention queue mode only one Attention notification is surfaced at a time:
+//! it takes the first status bar slot and animates, while the others wait (counted as
+//! `+N waiting`, not drawn). Acknowledging the surfaced one brings up the next, oldest first or
+//! by priority.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::notification::NotificationType;
+use crate::state::VisualState;
+
+/// Whether and in which order Attention notifications are surfaced one at a time
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum AttentionQueue {
+    /// All Attention notifications are shown at once
+    #[default]
+    Off,
+    /// The longest-waiting notification first
+    Oldest,
+    /// The highest priority first, the longest-waiting among equals
+    Priority,
+}
+
+impl AttentionQueue {
+    /// Parse the mode name (`off`, `oldest`, `priority`; `true` means `oldest`)
+    pub fn from_str(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "oldest" | "oldest-first" | "oldest_first" | "on" | "true" => Self::Oldest,
+            "priority" => Self::Priority,
+            _ => Self::Off,
+        }
+    }
+}
+
+/// A pane's place in the attention queue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttentionSlot {
+    /// Not queued (queue off, or not an Attention notification)
+    #[default]
+    None,
+    /// Shown in the prominent slot
+    Surfaced,
+    /// Waiting for its turn (counted, not drawn)
+    Waiting,
+}
+
+/// Whether a pane's notification takes part in the queue: an unread Attention notification on a
+/// visible channel of the current project
+pub fn is_candidate(state: &VisualState) -> bool {
+    state.is_unread()
+        && state.notification_type == Some(NotificationType::Attention)
+        && !state.hidden
+        && !state.other_project
+}
+
+/// The pane whose notification is surfaced
+pub fn surfaced(pane_states: &BTreeMap<u32, VisualState>, mode: AttentionQueue) -> Option<u32> {
+    let candidates = pane_states.iter().filter(|(_, state)| is_candidate(state));
+    match mode {
+        AttentionQueue::Off => None,
+        AttentionQueue::Oldest => candidates
+            .min_by_key(|(pane_id, state)| (state.notification_timestamp, **pane_id)),
+        AttentionQueue::Priority => candidates
+            .min_by_key(|(pane_id, state)| (std::cmp::Reverse(state.priority), state.notification_timestamp, **pane_id)),
+    }
+    .map(|(pane_id, _)| *pane_id)
+}
+
+/// Slots for every pane; returns the panes that were waiting and are surfaced now
+pub fn assign(pane_states: &mut BTreeMap<u32, VisualState>, mode: AttentionQueue) -> Vec<u32> {
+    let surfaced = surfaced(pane_states, mode);
+    let mut promoted = Vec::new();
+    for (pane_id, state) in pane_states.iter_mut() {
+        let slot = match surfaced {
+            Some(surfaced) if surfaced == *pane_id => AttentionSlot::Surfaced,
+            Some(_) if is_candidate(state) => AttentionSlot::Waiting,
+            _ => AttentionSlot::None,
+        };
+        if state.attention_slot == AttentionSlot::Waiting && slot == AttentionSlot::Surfaced {
+            promoted.push(*pane_id);
+        }
+        state.attention_slot = slot;
+    }
+    promoted
+}
+
+/// Number of notifications waiting for their turn
+pub fn waiting_count(pane_states: &BTreeMap<u32, VisualState>) -> usize {
+    pane_states.values().filter(|state| state.has_notification() && state.attention_slot == AttentionSlot::Waiting).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::Priority;
+
+    fn waiting_pane(timestamp: u64, priority: Priority) -> VisualState {
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Attention);
+        state.notification_timestamp = timestamp;
+        state.priority = priority;
+        state
+    }
+
+    fn panes() -> BTreeMap<u32, VisualState> {
+        let mut panes = BTreeMap::new();
+        panes.insert(1, waiting_pane(300, Priority::Normal));
+        panes.insert(2, waiting_pane(100, Priority::Normal));
+        panes.insert(3, waiting_pane(200, Priority::High));
+        let mut success = waiting_pane(50, Priority::Normal);
+        success.notification_type = Some(NotificationType::Success);
+        panes.insert(4, success);
+        panes
+    }
+
+    #[test]
+    fn test_surfacing_order() {
+        assert_eq!(AttentionQueue::from_str("priority"), AttentionQueue::Priority);
+        assert_eq!(AttentionQueue::from_str("true"), AttentionQueue::Oldest);
+        assert_eq!(AttentionQueue::from_str("off"), AttentionQueue::Off);
+
+        let panes = panes();
+        assert_eq!(surfaced(&panes, AttentionQueue::Oldest), Some(2));
+        assert_eq!(surfaced(&panes, AttentionQueue::Priority), Some(3));
+        assert_eq!(surfaced(&panes, AttentionQueue::Off), None);
+    }
+
+    #[test]
+    fn test_acknowledging_advances_the_queue() {
+        let mut panes = panes();
+        assert!(assign(&mut panes, AttentionQueue::Oldest).is_empty());
+        assert_eq!(panes[&2].attention_slot, AttentionSlot::Surfaced);
+        assert_eq!(panes[&4].attention_slot, AttentionSlot::None);
+        assert_eq!(waiting_count(&panes), 2);
+
+        panes.get_mut(&2).unwrap().acknowledged = true;
+        assert_eq!(assign(&mut panes, AttentionQueue::Oldest), vec![3]);
+        assert_eq!(waiting_count(&panes), 1);
+
+        assign(&mut panes, AttentionQueue::Off);
+        assert_eq!(waiting_count(&panes), 0);
+        assert!(panes.values().all(|state| state.attention_slot == AttentionSlot::None));
+    }
+}
@@ -0,0 +1,211 @@
+//! Audit trail module for Zellij Visual Notifications
+//!
+//! Records the pipeline decisions taken for each notification (holds, priority changes,
+//! coalescing, routing and sink deliveries) so `explain <id>` can show why a notification
+//! looked or behaved the way it did.
+
+use std::collections::{BTreeMap, VecDeque};
+use crate::notification::Priority;
+use crate::queue::QueueMove;
+
+/// A pipeline decision taken for a notification
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditEvent {
+    /// Entered the pipeline
+    Received { source: String, notification_type: String, priority: Priority },
+    /// Matched a user rule (actions not applied in dry-run mode)
+    Rule { name: String, actions: String, dry_run: bool },
+    /// Targeted a pane the layout does not have (held, dropped or moved to the tab level)
+    MissingPane { pane_id: u32, outcome: &'static str },
+    /// Held while DND/snooze is active
+    Held(&'static str),
+    /// Deferred while the user is busy
+    Deferred,
+    /// Kept in history only because its pane is focused
+    FocusedPane(u32),
+    /// Same message acknowledged on the pane shortly before; shown dimmed
+    RepeatDimmed,
+    /// Priority raised (e.g. user idle)
+    Escalated { from: Priority, to: Priority },
+    /// Moved in the queue by the operator, now in the given lane
+    QueueMoved { queue_move: QueueMove, lane: Priority },
+    /// Folded into an equivalent queued notification
+    Coalesced(Option<String>),
+    /// Dropped by admission control
+    Rejected,
+    /// Visual state applied to a pane
+    Routed(u32),
+    /// Visual state suppressed because the pane is muted
+    MutedPane(u32),
+    /// Effect requested by a sink
+    Sink(&'static str),
+    /// Fired an `on_notify` automation (waiting for confirmation if `confirm`)
+    Automation { name: String, action: String, confirm: bool },
+    /// Expiry pushed back by the user (ms added)
+    Extended(u64),
+    /// Expiry removed by the user (stays until acknowledged)
+    MadeSticky,
+    /// Expired early by the user
+    ExpiredEarly,
+    /// Scheduled to move focus to its pane (`auto_focus`)
+    AutoFocus(u32),
+    /// Re-delivered out of terminal after staying unacknowledged
+    AckEscalated,
+    /// Left unacknowledged past its response-time SLO (target ms)
+    SloBreached(u64),
+    /// Left unacknowledged past the deadline its sender gave (deadline ms)
+    Overdue(u64),
+    /// Answered with a quick reply option typed into its pane
+    Replied(String),
+    /// Marked read by the user (kept as a dimmed badge)
+    MarkedRead,
+    /// Acknowledged by the user
+    Acknowledged,
+    /// TTL ran out
+    Expired,
+    /// TTL ran out; demoted to a badge-only Low notification
+    Demoted,
+    /// TTL ran out; archived to history without reaching the sinks
+    Archived,
+}
+
+impl AuditEvent {
+    /// One-line description, e.g. `escalated normal -> critical`
+    pub fn describe(&self) -> String {
+        match self {
+            AuditEvent::Received { source, notification_type, priority } => {
+                format!("received {} ({} priority) from {}", notification_type, priority.name(), source)
+            }
+            AuditEvent::Rule { name, actions, dry_run: false } => format!("rule {}: {}", name, actions),
+            AuditEvent::Rule { name, actions, dry_run: true } => format!("rule {} (dry run): would {}", name, actions),
+            AuditEvent::MissingPane { pane_id, outcome } => format!("pane {} not in layout: {}", pane_id, outcome),
+            AuditEvent::Held(kind) => format!("held ({})", kind),
+            AuditEvent::Deferred => "deferred (user busy)".to_string(),
+            AuditEvent::FocusedPane(pane_id) => format!("history only (pane {} focused)", pane_id),
+            AuditEvent::RepeatDimmed => "dimmed (repeats a recently acknowledged message)".to_string(),
+            AuditEvent::Escalated { from, to } => format!("escalated {} -> {}", from.name(), to.name()),
+            AuditEvent::QueueMoved { queue_move, lane } => {
+                format!("{} in queue by operator ({} lane)", queue_move.describe(), lane.name())
+            }
+            AuditEvent::Coalesced(Some(id)) => format!("coalesced with {}", id),
+            AuditEvent::Coalesced(None) => "coalesced".to_string(),
+            AuditEvent::Rejected => "rejected (queue near capacity)".to_string(),
+            AuditEvent::Routed(pane_id) => format!("routed to pane {}", pane_id),
+            AuditEvent::MutedPane(pane_id) => format!("suppressed (pane {} muted)", pane_id),
+            AuditEvent::Sink(effect) => format!("sink: {}", effect),
+            AuditEvent::Automation { name, action, confirm: false } => format!("automation {}: {}", name, action),
+            AuditEvent::Automation { name, action, confirm: true } => {
+                format!("automation {}: {} (asked for confirmation)", name, action)
+            }
+            AuditEvent::Extended(extra_ms) => format!("expiry extended by {}ms", extra_ms),
+            AuditEvent::MadeSticky => "expiry removed (sticky)".to_string(),
+            AuditEvent::ExpiredEarly => "expired early by the user".to_string(),
+            AuditEvent::AutoFocus(pane_id) => format!("auto-focus of pane {} scheduled", pane_id),
+            AuditEvent::AckEscalated => "escalated out of terminal (not acknowledged)".to_string(),
+            AuditEvent::SloBreached(target_ms) => format!("SLO breached (not acknowledged within {}ms)", target_ms),
+            AuditEvent::Overdue(deadline_ms) => format!("overdue (not acknowledged within its {}ms deadline)", deadline_ms),
+            AuditEvent::Replied(option) => format!("replied {:?}", option),
+            AuditEvent::MarkedRead => "marked read".to_string(),
+            AuditEvent::Acknowledged => "acknowledged".to_string(),
+            AuditEvent::Expired => "expired".to_string(),
+            AuditEvent::Demoted => "expired, demoted to badge only".to_string(),
+            AuditEvent::Archived => "expired, archived".to_string(),
+        }
+    }
+}
+
+/// Audit records for the most recent notifications
+#[derive(Debug, Clone)]
+pub struct AuditTrail {
+    /// Notification IDs, oldest first
+    order: VecDeque<String>,
+    /// Recorded events by notification ID, with plugin time (ms)
+    events: BTreeMap<String, Vec<(u64, AuditEvent)>>,
+    /// Maximum notifications tracked
+    max_notifications: usize,
+}
+
+impl Default for AuditTrail {
+    fn default() -> Self {
+        Self::new(crate::history::DEFAULT_HISTORY_MAX)
+    }
+}
+
+impl AuditTrail {
+    /// Create a trail tracking at most `max_notifications`
+    pub fn new(max_notifications: usize) -> Self {
+        Self {
+            order: VecDeque::new(),
+            events: BTreeMap::new(),
+            max_notifications: max_notifications.max(1),
+        }
+    }
+
+    /// Record an event for a notification
+    pub fn record(&mut self, id: &str, at_ms: u64, event: AuditEvent) {
+        if !self.events.contains_key(id) {
+            while self.order.len() >= self.max_notifications {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.events.remove(&oldest);
+                }
+            }
+            self.order.push_back(id.to_string());
+        }
+        self.events.entry(id.to_string()).or_default().push((at_ms, event));
+    }
+
+    /// Events recorded for a notification
+    pub fn events(&self, id: &str) -> Option<&[(u64, AuditEvent)]> {
+        self.events.get(id).map(|events| events.as_slice())
+    }
+
+    /// ID of the most recently recorded notification
+    pub fn latest(&self) -> Option<&str> {
+        self.order.back().map(|id| id.as_str())
+    }
+
+    /// Cause chain for a notification, one decision per line with time since receipt
+    pub fn explain(&self, id: &str) -> Option<String> {
+        let events = self.events(id)?;
+        let start = events.first().map(|(at, _)| *at).unwrap_or(0);
+        let mut lines = vec![format!("Notification {}:", id)];
+        for (at, event) in events {
+            lines.push(format!("  +{}ms {}", at.saturating_sub(start), event.describe()));
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_cause_chain() {
+        let mut trail = AuditTrail::new(10);
+        trail.record("n1", 1_000, AuditEvent::Received {
+            source: "claude".to_string(),
+            notification_type: "attention".to_string(),
+            priority: Priority::Normal,
+        });
+        trail.record("n1", 1_000, AuditEvent::Escalated { from: Priority::Normal, to: Priority::Critical });
+        trail.record("n1", 1_250, AuditEvent::Routed(3));
+
+        let text = trail.explain("n1").unwrap();
+        assert!(text.contains("+0ms received attention (normal priority) from claude"));
+        assert!(text.contains("escalated normal -> critical"));
+        assert!(text.contains("+250ms routed to pane 3"));
+        assert!(trail.explain("missing").is_none());
+        assert_eq!(trail.latest(), Some("n1"));
+    }
+
+    #[test]
+    fn test_trail_is_bounded() {
+        let mut trail = AuditTrail::new(2);
+        for id in ["a", "b", "c"] {
+            trail.record(id, 0, AuditEvent::Deferred);
+        }
+        assert!(trail.events("a").is_none());
+        assert_eq!(trail.events("c").map(|e| e.len()), Some(1));
+    }
+}
@@ -0,0 +1,131 @@
+//! Auto-focus module for Zellij Visual Notifications
+//!
+//! Notifications of the types switched on in `auto_focus` (e.g. `attention`, Claude Code waiting
+//! for input) move focus to their pane after `auto_focus_delay_ms`, with a countdown in the plugin
+//! pane. Other types never steal focus. A key pressed in the plugin pane cancels the pending
+//! focus, and so does the user focusing another pane before the countdown ends: either means
+//! they are busy elsewhere. A newer qualifying notification replaces the pending one.
+
+use std::collections::BTreeMap;
+use crate::notification::Notification;
+
+/// Default wait before focus moves (ms)
+pub const DEFAULT_AUTO_FOCUS_DELAY_MS: u64 = 3000;
+
+/// A focus change waiting for its countdown
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingFocus {
+    /// Pane to focus
+    pub pane_id: u32,
+    /// Notification that asked for it
+    pub notification_id: String,
+    /// Pane that had focus when it was scheduled
+    pub from_pane: Option<u32>,
+    /// Plugin time at which focus moves (ms)
+    pub due_ms: u64,
+}
+
+impl PendingFocus {
+    /// Countdown line, e.g. `Focusing pane 3 in 2s - any key cancels`
+    pub fn countdown(&self, now_ms: u64) -> String {
+        format!("Focusing pane {} in {}s - any key cancels",
+            self.pane_id,
+            self.due_ms.saturating_sub(now_ms).div_ceil(1000))
+    }
+}
+
+/// Tracks the pending focus change
+#[derive(Debug, Clone, Default)]
+pub struct AutoFocus {
+    pending: Option<PendingFocus>,
+}
+
+impl AutoFocus {
+    /// Schedule focus for a notification whose type is switched on in `types`. Notifications
+    /// without a pane, or for the pane already focused, are skipped. Returns whether focus was
+    /// scheduled.
+    pub fn schedule(
+        &mut self,
+        types: &BTreeMap<String, bool>,
+        delay_ms: u64,
+        notification: &Notification,
+        focused_pane: Option<u32>,
+        now_ms: u64,
+    ) -> bool {
+        let Some(pane_id) = notification.pane_id else {
+            return false;
+        };
+        if !types.get(notification.notification_type.name()).copied().unwrap_or(false) || focused_pane == Some(pane_id) {
+            return false;
+        }
+        self.pending = Some(PendingFocus {
+            pane_id,
+            notification_id: notification.id.clone(),
+            from_pane: focused_pane,
+            due_ms: now_ms + delay_ms,
+        });
+        true
+    }
+
+    /// The pending focus change, if any
+    pub fn pending(&self) -> Option<&PendingFocus> {
+        self.pending.as_ref()
+    }
+
+    /// Drop the pending focus change
+    pub fn cancel(&mut self) -> Option<PendingFocus> {
+        self.pending.take()
+    }
+
+    /// Record the focused pane; the pending change is cancelled when the user focused another
+    /// pane (or the target) themselves
+    pub fn focus_changed(&mut self, focused_pane: Option<u32>) -> Option<PendingFocus> {
+        match self.pending {
+            Some(ref pending) if focused_pane.is_some() && focused_pane != pending.from_pane => self.pending.take(),
+            _ => None,
+        }
+    }
+
+    /// Take the pending focus change once its countdown has run out
+    pub fn take_due(&mut self, now_ms: u64) -> Option<PendingFocus> {
+        match self.pending {
+            Some(ref pending) if now_ms >= pending.due_ms => self.pending.take(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types() -> BTreeMap<String, bool> {
+        BTreeMap::from([("attention".to_string(), true), ("error".to_string(), false)])
+    }
+
+    #[test]
+    fn test_only_enabled_types_schedule() {
+        let mut focus = AutoFocus::default();
+        assert!(!focus.schedule(&types(), 3000, &Notification::error("Build failed").for_pane(2), Some(1), 0));
+        assert!(!focus.schedule(&types(), 3000, &Notification::attention("Waiting"), Some(1), 0));
+        assert!(!focus.schedule(&types(), 3000, &Notification::attention("Waiting").for_pane(1), Some(1), 0));
+        assert!(focus.pending().is_none());
+
+        assert!(focus.schedule(&types(), 3000, &Notification::attention("Waiting").for_pane(2), Some(1), 1000));
+        assert_eq!(focus.pending().unwrap().countdown(1500), "Focusing pane 2 in 3s - any key cancels");
+        assert!(focus.take_due(3999).is_none());
+        assert_eq!(focus.take_due(4000).map(|p| p.pane_id), Some(2));
+        assert!(focus.pending().is_none());
+    }
+
+    #[test]
+    fn test_focus_elsewhere_cancels() {
+        let mut focus = AutoFocus::default();
+        focus.schedule(&types(), 3000, &Notification::attention("Waiting").for_pane(2), Some(1), 0);
+        // Updates that leave focus where it was keep the countdown
+        assert!(focus.focus_changed(Some(1)).is_none());
+        assert!(focus.focus_changed(None).is_none());
+        assert_eq!(focus.focus_changed(Some(5)).map(|p| p.pane_id), Some(2));
+        assert!(focus.take_due(10_000).is_none());
+    }
+}
@@ -0,0 +1,237 @@
+//! Automation module for Zellij Visual Notifications
+//!
+//! `on_notify` blocks run a Zellij action when a notification matching a rule filter
+//! (`type=error source=tests`) is queued: open a command pane (`run "cargo test"`), toggle
+//! fullscreen on the notifying pane, or open its scrollback in the editor. Any program that can
+//! pipe to the plugin can trigger them, so `run` actions ask for a y/n confirmation in the plugin
+//! pane unless `confirm false` is set, and each automation fires at most once per cooldown, so a
+//! burst of matching notifications does not open a burst of panes.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::checks::split_quoted;
+use crate::notification::Notification;
+use crate::query::parse_duration_ms;
+use crate::rules::RuleMatch;
+
+/// Default time an automation stays quiet after firing (ms)
+pub const DEFAULT_AUTOMATION_COOLDOWN_MS: u64 = 30_000;
+
+/// Zellij action an automation takes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LayoutAction {
+    /// Open a command pane running a shell command
+    Run(String),
+    /// Toggle fullscreen on the notifying pane
+    Fullscreen,
+    /// Open the notifying pane's scrollback in the default editor
+    EditScrollback,
+}
+
+impl LayoutAction {
+    /// Parse `run <command>`, `fullscreen` or `edit-scrollback`
+    pub fn parse(kind: &str, argument: &str) -> Result<Self, String> {
+        match (kind, argument.trim()) {
+            ("run", "") => Err("run needs a command".to_string()),
+            ("run", command) => Ok(Self::Run(command.to_string())),
+            ("fullscreen", "") => Ok(Self::Fullscreen),
+            ("edit-scrollback" | "scrollback", "") => Ok(Self::EditScrollback),
+            ("fullscreen" | "edit-scrollback" | "scrollback", _) => Err(format!("{} takes no argument", kind)),
+            (other, _) => Err(format!("unknown action: {}", other)),
+        }
+    }
+
+    /// Whether the action works on the notifying pane (and is skipped without one)
+    pub fn needs_pane(&self) -> bool {
+        !matches!(self, Self::Run(_))
+    }
+
+    /// Short description, e.g. `run "cargo test"`
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Run(command) => format!("run \"{}\"", command),
+            Self::Fullscreen => "toggle fullscreen".to_string(),
+            Self::EditScrollback => "edit scrollback".to_string(),
+        }
+    }
+}
+
+/// A named `on_notify` automation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Automation {
+    /// Name shown in the prompt and the audit trail
+    pub name: String,
+    /// Notifications it fires on
+    pub matcher: RuleMatch,
+    /// Action taken
+    pub action: LayoutAction,
+    /// Ask for confirmation before acting
+    pub confirm: bool,
+    /// Time it stays quiet after firing (ms)
+    pub cooldown_ms: u64,
+}
+
+impl Automation {
+    /// Parse an automation from its parts. `confirm` defaults to on for `run` and off for pane
+    /// actions; `cooldown` is a duration (`30s`, `5m`).
+    pub fn parse(
+        name: &str,
+        filters: &str,
+        kind: &str,
+        argument: &str,
+        confirm: Option<bool>,
+        cooldown: Option<&str>,
+    ) -> Result<Self, String> {
+        let action = LayoutAction::parse(kind, argument).map_err(|e| format!("on_notify {}: {}", name, e))?;
+        let cooldown_ms = match cooldown {
+            Some(cooldown) => parse_duration_ms(cooldown).map_err(|e| format!("on_notify {}: {}", name, e))?,
+            None => DEFAULT_AUTOMATION_COOLDOWN_MS,
+        };
+        Ok(Self {
+            name: name.to_string(),
+            matcher: RuleMatch::parse(filters).map_err(|e| format!("on_notify {}: {}", name, e))?,
+            confirm: confirm.unwrap_or(!action.needs_pane()),
+            action,
+            cooldown_ms,
+        })
+    }
+
+    /// Parse the plugin-config form `<match> -> <action> [confirm=<bool>] [cooldown=<duration>]`,
+    /// e.g. `type=error source=tests -> run "cargo test" cooldown=2m`
+    pub fn parse_spec(name: &str, spec: &str) -> Result<Self, String> {
+        let (filters, action) = spec.split_once("->")
+            .ok_or_else(|| format!("on_notify {}: expected `<match> -> <action>`", name))?;
+        let mut words = split_quoted(action);
+        let mut confirm = None;
+        let mut cooldown = None;
+        while let Some(last) = words.last() {
+            match last.split_once('=') {
+                Some(("confirm", value)) => {
+                    confirm = Some(value.parse().map_err(|_| format!("on_notify {}: invalid confirm: {}", name, value))?);
+                }
+                Some(("cooldown", value)) => cooldown = Some(value.to_string()),
+                _ => break,
+            }
+            words.pop();
+        }
+        let Some((kind, argument)) = words.split_first() else {
+            return Err(format!("on_notify {}: no action", name));
+        };
+        Self::parse(name, filters, kind, &argument.join(" "), confirm, cooldown.as_deref())
+    }
+}
+
+/// An automation fired by a notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutomationTrigger {
+    /// Automation name
+    pub name: String,
+    /// Action to take
+    pub action: LayoutAction,
+    /// Notifying pane
+    pub pane_id: Option<u32>,
+    /// Ask for confirmation first
+    pub confirm: bool,
+}
+
+impl AutomationTrigger {
+    /// Confirmation prompt, e.g. `tests: run "cargo test"? y/n`
+    pub fn prompt(&self) -> String {
+        let pane = self.pane_id.filter(|_| self.action.needs_pane())
+            .map(|pane_id| format!(" on pane {}", pane_id))
+            .unwrap_or_default();
+        format!("{}: {}{}? y/n", self.name, self.action.describe(), pane)
+    }
+}
+
+/// Tracks when each automation last fired
+#[derive(Debug, Clone, Default)]
+pub struct AutomationScheduler {
+    /// Plugin time each automation last fired (ms)
+    last_fired_ms: BTreeMap<String, u64>,
+}
+
+impl AutomationScheduler {
+    /// Automations a queued notification fires, in configured order. Automations in their
+    /// cooldown, and pane actions for notifications without a pane, are skipped.
+    pub fn triggered(&mut self, automations: &[Automation], notification: &Notification, now_ms: u64) -> Vec<AutomationTrigger> {
+        let mut triggers = Vec::new();
+        for automation in automations {
+            if !automation.matcher.matches(notification)
+                || (automation.action.needs_pane() && notification.pane_id.is_none())
+            {
+                continue;
+            }
+            if let Some(last) = self.last_fired_ms.get(&automation.name) {
+                if now_ms < last + automation.cooldown_ms {
+                    continue;
+                }
+            }
+            self.last_fired_ms.insert(automation.name.clone(), now_ms);
+            triggers.push(AutomationTrigger {
+                name: automation.name.clone(),
+                action: automation.action.clone(),
+                pane_id: notification.pane_id,
+                confirm: automation.confirm,
+            });
+        }
+        triggers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::{NotificationBuilder, NotificationType};
+
+    #[test]
+    fn test_parse_spec() {
+        let automation = Automation::parse_spec("tests", "type=error source=tests -> run \"cargo test\" cooldown=2m").unwrap();
+        assert_eq!(automation.action, LayoutAction::Run("cargo test".to_string()));
+        assert_eq!(automation.matcher.notification_type, Some(NotificationType::Error));
+        // Commands ask first unless told otherwise
+        assert!(automation.confirm);
+        assert_eq!(automation.cooldown_ms, 120_000);
+
+        let zoom = Automation::parse_spec("zoom", "type=error -> fullscreen").unwrap();
+        assert_eq!(zoom.action, LayoutAction::Fullscreen);
+        assert!(!zoom.confirm);
+        assert_eq!(zoom.cooldown_ms, DEFAULT_AUTOMATION_COOLDOWN_MS);
+        let quiet = Automation::parse_spec("quiet", "source=ci -> run make lint confirm=false").unwrap();
+        assert_eq!(quiet.action, LayoutAction::Run("make lint".to_string()));
+        assert!(!quiet.confirm);
+
+        assert!(Automation::parse_spec("x", "type=error -> run").is_err());
+        assert!(Automation::parse_spec("x", "type=error -> explode").is_err());
+        assert!(Automation::parse_spec("x", "type=error -> fullscreen now").is_err());
+        assert!(Automation::parse_spec("x", "type=error fullscreen").is_err());
+    }
+
+    #[test]
+    fn test_triggers_respect_match_pane_and_cooldown() {
+        let automations = vec![
+            Automation::parse("tests", "type=error source=tests", "run", "cargo test", None, Some("1m")).unwrap(),
+            Automation::parse("zoom", "type=error", "fullscreen", "", None, None).unwrap(),
+        ];
+        let mut scheduler = AutomationScheduler::default();
+        let failed = NotificationBuilder::new()
+            .notification_type(NotificationType::Error)
+            .message("3 failed")
+            .source("tests")
+            .pane_id(4)
+            .build();
+        let triggers = scheduler.triggered(&automations, &failed, 0);
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].prompt(), "tests: run \"cargo test\"? y/n");
+        assert_eq!(triggers[1].prompt(), "zoom: toggle fullscreen on pane 4? y/n");
+
+        // Both in their cooldown
+        assert!(scheduler.triggered(&automations, &failed, 20_000).is_empty());
+        // The command fires again; the pane action needs a pane
+        let mut paneless = failed.clone();
+        paneless.pane_id = None;
+        let triggers = scheduler.triggered(&automations, &paneless, 60_000);
+        assert_eq!(triggers.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["tests"]);
+        assert!(scheduler.triggered(&automations, &NotificationBuilder::new().message("ok").build(), 120_000).is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+//! Startup banner module for Zellij Visual Notifications
+//!
+//! When a sender script is updated but an older wasm is still installed (or the other way
+//! round), the first question is which plugin is running. On load the plugin shows a brief
+//! banner with its version, the protocol versions it accepts, where its configuration came from
+//! and whether it was granted its permissions; any key dismisses it, and it closes by itself
+//! after `STARTUP_BANNER_MS`. The `version` pipe command prints the same lines.
+
+use zellij_tile::prelude::PermissionType;
+
+use crate::event_bridge::{PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS};
+use crate::state::PluginState;
+
+/// How long the startup banner stays open unless dismissed (ms)
+pub const STARTUP_BANNER_MS: u64 = 6_000;
+
+/// Permission line, e.g. `granted: ReadApplicationState, RunCommands`
+pub fn permissions(plugin_state: &PluginState, requested: &[PermissionType]) -> String {
+    match plugin_state {
+        PluginState::Running => {
+            let names: Vec<String> = requested.iter().map(|permission| format!("{:?}", permission)).collect();
+            format!("granted: {}", names.join(", "))
+        }
+        PluginState::FallbackMode => "denied, running in fallback mode".to_string(),
+        PluginState::Error(error) => error.clone(),
+        _ => "requested, waiting for an answer".to_string(),
+    }
+}
+
+/// Lines of the `version` command: plugin version, protocol versions, configuration sources
+/// (see `ConfigManager::profile`) and permissions
+pub fn version_lines(profile: &str, permissions: &str) -> Vec<String> {
+    vec![
+        format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+        format!("protocol: {} (accepts {})", PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS.join(", ")),
+        format!("config: {}", profile),
+        format!("permissions: {}", permissions),
+    ]
+}
+
+/// Lines of the startup banner: the version lines and the dismiss hint
+pub fn banner_lines(profile: &str, permissions: &str) -> Vec<String> {
+    let mut lines = version_lines(profile, permissions);
+    lines.push("Any key to dismiss (echo 'version' | zellij pipe -p visual-notifications shows this again)".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_lines() {
+        let granted = permissions(&PluginState::Running, &[PermissionType::ReadApplicationState, PermissionType::RunCommands]);
+        assert_eq!(granted, "granted: ReadApplicationState, RunCommands");
+        assert_eq!(permissions(&PluginState::Initialized, &[]), "requested, waiting for an answer");
+
+        let lines = version_lines("plugin (2 keys), theme default", &granted);
+        assert_eq!(lines[0], format!("zellij-visual-notifications {}", env!("CARGO_PKG_VERSION")));
+        assert_eq!(lines[1], "protocol: 1.0 (accepts 1.0)");
+        assert_eq!(lines[2], "config: plugin (2 keys), theme default");
+        assert_eq!(banner_lines("defaults", &granted).len(), 5);
+    }
+}
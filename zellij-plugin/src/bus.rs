@@ -0,0 +1,142 @@
+//! Internal event bus for Zellij Visual Notifications
+//!
+//! Input handlers translate Zellij events, pipe messages and key presses into domain events
+//! and emit them on the bus; a single reducer drains the bus and updates plugin state. Keeping
+//! the notification lifecycle out of the raw event handlers lets the renderer work from a
+//! read-only view of the resulting state.
+
+use std::collections::VecDeque;
+use crate::confirm::ConfirmRequest;
+use crate::notification::{Notification, NotificationType};
+use crate::reminders::Reminder;
+use crate::suppression::SuppressionKind;
+use crate::timeline::SessionHook;
+
+/// Upper bound on events dispatched per drain (guards against reducer feedback loops)
+pub const MAX_EVENTS_PER_DISPATCH: usize = 1024;
+
+/// Domain event describing something that happened to the notification lifecycle
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    /// A notification was received (or released after deferral)
+    NotificationArrived(Box<Notification>),
+    /// A notification was withdrawn by ID, or all notifications for a pane
+    NotificationCancelled {
+        /// ID of the cancelled notification
+        id: Option<String>,
+        /// Pane whose notifications are cancelled
+        pane_id: Option<u32>,
+    },
+    /// A periodic reminder was scheduled
+    ReminderScheduled(Reminder),
+    /// A sender asked the user a yes/no question
+    ConfirmRequested(ConfirmRequest),
+    /// A Claude Code session reported a lifecycle-only hook
+    SessionHookReceived {
+        /// Claude Code session ID
+        session_id: String,
+        /// Pane the session runs in
+        pane_id: Option<u32>,
+        /// What happened
+        hook: SessionHook,
+    },
+    /// A notification was acknowledged by ID (`ack_id` custom message)
+    NotificationAcknowledged(String),
+    /// A pane gained focus (its notification is acknowledged)
+    PaneFocused(u32),
+    /// The notification on a pane was marked read by the user (kept as a dimmed badge)
+    PaneRead(u32),
+    /// The notification on a pane was dismissed by the user
+    PaneDismissed(u32),
+    /// A pane was muted or unmuted
+    PaneMuteChanged {
+        /// Target pane
+        pane_id: u32,
+        /// New mute state
+        muted: bool,
+    },
+    /// All notifications were cleared by the user (pinned ones only if forced)
+    AllCleared {
+        /// Also clear pinned notifications
+        force: bool,
+    },
+    /// All notifications of one type were acknowledged by the user
+    TypeAcknowledged(NotificationType),
+    /// DND or snooze was started
+    SuppressionStarted {
+        /// Kind of suppression
+        kind: SuppressionKind,
+        /// Duration (ms, None = until resumed)
+        duration_ms: Option<u64>,
+    },
+    /// DND or snooze was ended by the user
+    SuppressionEnded,
+    /// Timer tick with elapsed seconds
+    TickElapsed(f64),
+    /// User activity was observed (keys or mode changes)
+    UserActivity,
+}
+
+/// FIFO bus of pending domain events
+#[derive(Debug, Default)]
+pub struct EventBus {
+    /// Events waiting for the reducer
+    pending: VecDeque<DomainEvent>,
+    /// Total events dispatched (for diagnostics)
+    total_dispatched: u64,
+}
+
+impl EventBus {
+    /// Create an empty event bus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit an event for the reducer
+    pub fn emit(&mut self, event: DomainEvent) {
+        self.pending.push_back(event);
+    }
+
+    /// Take the next pending event
+    pub fn next_event(&mut self) -> Option<DomainEvent> {
+        let event = self.pending.pop_front();
+        if event.is_some() {
+            self.total_dispatched += 1;
+        }
+        event
+    }
+
+    /// Number of pending events
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Check if no events are pending
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Total events dispatched so far
+    pub fn total_dispatched(&self) -> u64 {
+        self.total_dispatched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bus_is_fifo() {
+        let mut bus = EventBus::new();
+        bus.emit(DomainEvent::UserActivity);
+        bus.emit(DomainEvent::PaneFocused(3));
+        assert_eq!(bus.len(), 2);
+
+        assert!(matches!(bus.next_event(), Some(DomainEvent::UserActivity)));
+        assert!(matches!(bus.next_event(), Some(DomainEvent::PaneFocused(3))));
+        assert!(bus.next_event().is_none());
+        assert!(bus.is_empty());
+        assert_eq!(bus.total_dispatched(), 2);
+    }
+}
@@ -0,0 +1,133 @@
+//! Channel module for Zellij Visual Notifications
+//!
+//! Named notification channels (e.g. `claude`, `ci`, `cron`) are independent of the
+//! notification type. Each channel has a runtime visibility toggle: hiding a channel hides its
+//! entries from the status bar, tab badges and title summaries, but its notifications are still
+//! received and counted.
+
+use crate::config::Config;
+
+/// A channel and its runtime state
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel {
+    /// Channel name
+    pub name: String,
+    /// Whether the channel's entries are shown
+    pub visible: bool,
+    /// Notifications received on the channel
+    pub count: u64,
+}
+
+/// Known channels, in configuration order then order of first use
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChannelList {
+    /// Channels
+    channels: Vec<Channel>,
+}
+
+impl ChannelList {
+    /// Create the channel list from the configured channels and hidden channels
+    pub fn from_config(config: &Config) -> Self {
+        let mut list = Self::default();
+        for name in config.channels.iter().chain(&config.hidden_channels) {
+            list.ensure(name);
+        }
+        for name in &config.hidden_channels {
+            list.set_visible(name, false);
+        }
+        list
+    }
+
+    /// Count a notification received on a channel
+    pub fn record(&mut self, name: &str) {
+        self.ensure(name).count += 1;
+    }
+
+    /// Show or hide a channel, returning whether its visibility changed
+    pub fn set_visible(&mut self, name: &str, visible: bool) -> bool {
+        let channel = self.ensure(name);
+        let changed = channel.visible != visible;
+        channel.visible = visible;
+        changed
+    }
+
+    /// Toggle visibility of a channel, returning the new visibility
+    pub fn toggle(&mut self, name: &str) -> bool {
+        let channel = self.ensure(name);
+        channel.visible = !channel.visible;
+        channel.visible
+    }
+
+    /// Check if entries on a channel are shown (notifications without a channel always are)
+    pub fn is_visible(&self, name: Option<&str>) -> bool {
+        match name {
+            Some(name) => self.channels.iter().find(|c| c.name == name).is_none_or(|c| c.visible),
+            None => true,
+        }
+    }
+
+    /// Channel at a list position (0-based)
+    pub fn get(&self, index: usize) -> Option<&Channel> {
+        self.channels.get(index)
+    }
+
+    /// Iterate over channels in list order
+    pub fn iter(&self) -> impl Iterator<Item = &Channel> {
+        self.channels.iter()
+    }
+
+    /// One line per channel, e.g. `2 [ ] ci (14)`
+    pub fn lines(&self) -> Vec<String> {
+        self.iter().enumerate()
+            .map(|(index, channel)| format!("{} [{}] {} ({})",
+                index + 1,
+                if channel.visible { "x" } else { " " },
+                channel.name,
+                channel.count))
+            .collect()
+    }
+
+    /// Look up a channel, adding it (visible) if unknown
+    fn ensure(&mut self, name: &str) -> &mut Channel {
+        let index = match self.channels.iter().position(|c| c.name == name) {
+            Some(index) => index,
+            None => {
+                self.channels.push(Channel { name: name.to_string(), visible: true, count: 0 });
+                self.channels.len() - 1
+            }
+        };
+        &mut self.channels[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channels_from_config() {
+        let config = Config {
+            channels: vec!["claude".to_string(), "ci".to_string()],
+            hidden_channels: vec!["cron".to_string()],
+            ..Config::default()
+        };
+        let channels = ChannelList::from_config(&config);
+        assert_eq!(channels.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["claude", "ci", "cron"]);
+        assert!(channels.is_visible(Some("ci")));
+        assert!(!channels.is_visible(Some("cron")));
+        assert!(channels.is_visible(Some("unknown")));
+        assert!(channels.is_visible(None));
+    }
+
+    #[test]
+    fn test_hidden_channels_keep_counting() {
+        let mut channels = ChannelList::default();
+        channels.record("ci");
+        assert!(!channels.toggle("ci"));
+        channels.record("ci");
+        assert_eq!(channels.get(0).map(|c| c.count), Some(2));
+        assert_eq!(channels.lines(), vec!["1 [ ] ci (2)"]);
+        assert!(channels.set_visible("ci", true));
+        assert!(!channels.set_visible("ci", true));
+    }
+}
@@ -0,0 +1,217 @@
+//! Character set module for Zellij Visual Notifications
+//!
+//! Decides whether the plugin pane is drawn with Unicode glyphs or a pure-ASCII fallback set.
+//! `ascii_only` forces either; in `auto` mode the fallback is used when a hint says the terminal
+//! cannot show Unicode: the `VISUAL_NOTIFICATIONS_ASCII_ONLY` variable or a non-UTF-8 locale
+//! (when the host passes the environment through), Zellij's `simplified_ui` (no arrow fonts), or
+//! notification text that arrives garbled (replacement characters or UTF-8 read as Latin-1). In
+//! ASCII mode every line drawn goes through [`to_ascii`], so no glyph slips through.
+
+use serde::{Deserialize, Serialize};
+
+/// Garbled notifications after which `auto` mode switches to ASCII
+pub const GARBLED_MESSAGE_THRESHOLD: u32 = 3;
+
+/// Environment variable forcing the ASCII fallback (`1`/`true`)
+pub const ASCII_ONLY_ENV: &str = "VISUAL_NOTIFICATIONS_ASCII_ONLY";
+
+/// When the ASCII fallback set is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AsciiMode {
+    /// When a hint says Unicode is not shown correctly
+    #[default]
+    Auto,
+    /// Always
+    On,
+    /// Never
+    Off,
+}
+
+impl AsciiMode {
+    /// Parse `auto`, `true`/`on` or `false`/`off` (anything else is `auto`)
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "true" | "on" | "always" | "yes" => AsciiMode::On,
+            "false" | "off" | "never" | "no" => AsciiMode::Off,
+            _ => AsciiMode::Auto,
+        }
+    }
+}
+
+/// Whether the environment asks for ASCII: the explicit variable wins, then the locale
+/// (`LC_ALL`, `LC_CTYPE`, `LANG`, first one set). `None` when nothing is set.
+pub fn env_hint(var: impl Fn(&str) -> Option<String>) -> Option<bool> {
+    if let Some(value) = var(ASCII_ONLY_ENV) {
+        return Some(matches!(value.to_lowercase().as_str(), "1" | "true" | "yes" | "on"));
+    }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())?;
+    let locale = locale.to_lowercase();
+    Some(!(locale.contains("utf-8") || locale.contains("utf8")))
+}
+
+/// Whether text looks garbled on its way in: a replacement character, or UTF-8 decoded as
+/// Latin-1 (`Ã©` for `é`, `â€”` for `—`)
+pub fn looks_garbled(text: &str) -> bool {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{FFFD}' {
+            return true;
+        }
+        // Lead byte of a two- or three-byte sequence followed by a continuation byte
+        if matches!(c, '\u{C2}'..='\u{C3}' | '\u{E2}')
+            && chars.peek().is_some_and(|next| matches!(next, '\u{80}'..='\u{BF}' | '\u{20AC}' | '\u{152}'..='\u{178}'))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Tracks the ASCII hints seen at runtime
+#[derive(Debug, Clone, Default)]
+pub struct CharsetDetector {
+    /// Environment hint (`Some(true)`: ASCII)
+    env_hint: Option<bool>,
+    /// Zellij runs with `simplified_ui`
+    simplified_ui: bool,
+    /// Garbled notifications received
+    garbled_messages: u32,
+}
+
+impl CharsetDetector {
+    /// Detector starting from an environment hint
+    pub fn new(env_hint: Option<bool>) -> Self {
+        Self { env_hint, ..Self::default() }
+    }
+
+    /// Record Zellij's UI capabilities; returns whether the hint changed
+    pub fn set_simplified_ui(&mut self, simplified_ui: bool) -> bool {
+        let changed = self.simplified_ui != simplified_ui;
+        self.simplified_ui = simplified_ui;
+        changed
+    }
+
+    /// Record a notification's text; returns whether it pushed the count to the threshold
+    pub fn observe(&mut self, text: &str) -> bool {
+        if !looks_garbled(text) || self.garbled_messages >= GARBLED_MESSAGE_THRESHOLD {
+            return false;
+        }
+        self.garbled_messages += 1;
+        self.garbled_messages == GARBLED_MESSAGE_THRESHOLD
+    }
+
+    /// Why the ASCII fallback is used, or `None` when it is not
+    pub fn ascii_reason(&self, mode: AsciiMode) -> Option<&'static str> {
+        match mode {
+            AsciiMode::On => Some("ascii_only"),
+            AsciiMode::Off => None,
+            AsciiMode::Auto if self.env_hint == Some(true) => Some("environment"),
+            AsciiMode::Auto if self.simplified_ui => Some("simplified_ui"),
+            AsciiMode::Auto if self.garbled_messages >= GARBLED_MESSAGE_THRESHOLD => Some("garbled text"),
+            AsciiMode::Auto => None,
+        }
+    }
+
+    /// Whether to draw with the ASCII fallback set
+    pub fn use_ascii(&self, mode: AsciiMode) -> bool {
+        self.ascii_reason(mode).is_some()
+    }
+}
+
+/// ASCII replacement of a glyph the plugin draws
+fn ascii_glyph(c: char) -> &'static str {
+    match c {
+        '\u{2714}' | '\u{2713}' => "v",               // Check marks
+        '\u{2718}' | '\u{2717}' => "x",               // X marks
+        '\u{26A0}' | '\u{2757}' => "!",               // Warning, exclamation
+        '\u{2139}' => "i",                            // Info
+        '\u{21BB}' => "~",                            // Rotating arrow
+        '\u{1F514}' => "[N]",                         // Bell
+        '\u{1F4CC}' => "^",                           // Pin
+        '\u{1F507}' => "M",                           // Muted speaker
+        '\u{23F1}' | '\u{23F2}' => "T",               // Stopwatch, timer
+        '\u{2298}' => "off:",                         // Circled slash
+        '\u{21C4}' => "<>",                           // Left-right arrows
+        '\u{2026}' => "...",                          // Ellipsis
+        '\u{00B7}' => "-",                            // Middle dot
+        '\u{221E}' => "inf",                          // Infinity
+        '\u{2261}' | '\u{2550}' => "=",               // Stacked marker, double line
+        '\u{2500}' | '\u{2501}' | '\u{2504}' | '\u{2508}' => "-",
+        '\u{2502}' | '\u{2503}' | '\u{2506}' | '\u{250A}' | '\u{2551}' | '\u{258C}' => "|",
+        '\u{250C}'..='\u{254B}' | '\u{2554}'..='\u{256C}' => "+", // Box corners and joins
+        '\u{2588}' | '\u{2580}' | '\u{2584}' => "#",  // Blocks
+        '\u{2800}'..='\u{28FF}' => "*",               // Braille (spinners)
+        '\u{2014}' | '\u{2013}' => "-",               // Dashes
+        '\u{2018}' | '\u{2019}' => "'",
+        '\u{201C}' | '\u{201D}' => "\"",
+        _ => "?",
+    }
+}
+
+/// Text with every non-ASCII character replaced by its ASCII fallback (escape sequences are
+/// ASCII and pass through)
+pub fn to_ascii(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            ascii.push(c);
+        } else if c != '\u{FE0F}' && c != '\u{200D}' {
+            // Variation selectors and joiners vanish with the glyph they modify
+            ascii.push_str(ascii_glyph(c));
+        }
+    }
+    ascii
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints_and_modes() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+        assert_eq!(env_hint(env(&[])), None);
+        assert_eq!(env_hint(env(&[("LANG", "en_US.UTF-8")])), Some(false));
+        assert_eq!(env_hint(env(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")])), Some(true));
+        assert_eq!(env_hint(env(&[(ASCII_ONLY_ENV, "1"), ("LANG", "en_US.utf8")])), Some(true));
+
+        let mut detector = CharsetDetector::new(None);
+        assert!(!detector.use_ascii(AsciiMode::Auto));
+        assert!(detector.use_ascii(AsciiMode::On));
+        assert!(detector.set_simplified_ui(true));
+        assert_eq!(detector.ascii_reason(AsciiMode::Auto), Some("simplified_ui"));
+        assert!(!detector.use_ascii(AsciiMode::Off));
+        assert_eq!(AsciiMode::from_str("true"), AsciiMode::On);
+        assert_eq!(AsciiMode::from_str("whatever"), AsciiMode::Auto);
+    }
+
+    #[test]
+    fn test_garbled_text_switches_after_threshold() {
+        assert!(looks_garbled("Build failed \u{FFFD} 3 errors"));
+        assert!(looks_garbled("Caf\u{C3}\u{A9} closed")); // "Café" read as Latin-1
+        assert!(looks_garbled("done \u{E2}\u{20AC}\u{201D} ok")); // "—" read as Windows-1252
+        assert!(!looks_garbled("Café \u{2014} na\u{EF}ve \u{1F514}"));
+
+        let mut detector = CharsetDetector::new(None);
+        assert!(!detector.observe("Caf\u{C3}\u{A9}"));
+        assert!(!detector.observe("fine"));
+        assert!(!detector.observe("\u{FFFD}"));
+        assert!(detector.observe("\u{FFFD}"));
+        assert!(!detector.observe("\u{FFFD}"));
+        assert_eq!(detector.ascii_reason(AsciiMode::Auto), Some("garbled text"));
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("\u{1b}[31m\u{2718}3\u{1b}[0m \u{2502} build\u{2026}"), "\u{1b}[31mx3\u{1b}[0m | build...");
+        assert_eq!(to_ascii("\u{250C}\u{2500}\u{2510} \u{280B} \u{26A0}\u{FE0F} na\u{EF}ve"), "+-+ * ! na?ve");
+        assert!(to_ascii("\u{2550}\u{2588}\u{2580}\u{2584}\u{1F4CC}\u{23F1}\u{221E}").is_ascii());
+    }
+}
@@ -0,0 +1,223 @@
+//! Health check module for Zellij Visual Notifications
+//!
+//! Configured checks are shell commands the plugin runs itself on an interval (with the
+//! `RunCommands` permission), e.g. `"git status --porcelain" every 5m notify-if-output warning
+//! "uncommitted changes"`. When a check's condition holds (it printed output, or it failed), a
+//! notification is fed into the plugin's own queue, so the plugin doubles as a lightweight
+//! watcher. A check is not started again while its previous run is outstanding.
+
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Deserialize, Serialize};
+use crate::notification::{Notification, NotificationBuilder, NotificationType};
+use crate::query::parse_duration_ms;
+
+/// Context key marking check command results (value: check name)
+pub const CHECK_CONTEXT_KEY: &str = "check";
+
+/// Shortest accepted check interval (ms)
+pub const MIN_CHECK_INTERVAL_MS: u64 = 10_000;
+
+/// When a check raises its notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckCondition {
+    /// The command printed something on stdout
+    Output,
+    /// The command exited with a non-zero status
+    Failure,
+}
+
+impl CheckCondition {
+    /// Parse `notify-if-output` / `notify-if-failed` (the `notify-if-` prefix is optional)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.strip_prefix("notify-if-").unwrap_or(name) {
+            "output" => Some(Self::Output),
+            "failed" | "failure" => Some(Self::Failure),
+            _ => None,
+        }
+    }
+}
+
+/// A named periodic check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Check {
+    /// Name (shown as the notification title)
+    pub name: String,
+    /// Shell command (run with `sh -c`)
+    pub command: String,
+    /// Interval between runs (ms)
+    pub every_ms: u64,
+    /// When to notify
+    pub condition: CheckCondition,
+    /// Type of the raised notification
+    pub notification_type: NotificationType,
+    /// Message of the raised notification
+    pub message: String,
+}
+
+impl Check {
+    /// Parse a check from its parts (`every` as `45s`, `5m`, `1h`; `condition` as `notify-if-output`)
+    pub fn parse(
+        name: &str,
+        command: &str,
+        every: &str,
+        condition: &str,
+        notification_type: &str,
+        message: &str,
+    ) -> Result<Self, String> {
+        if command.trim().is_empty() {
+            return Err(format!("check {}: no command", name));
+        }
+        let every_ms = parse_duration_ms(every).map_err(|e| format!("check {}: {}", name, e))?;
+        if every_ms < MIN_CHECK_INTERVAL_MS {
+            return Err(format!("check {}: interval must be at least {}s", name, MIN_CHECK_INTERVAL_MS / 1000));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            every_ms,
+            condition: CheckCondition::from_name(condition)
+                .ok_or_else(|| format!("check {}: unknown condition: {}", name, condition))?,
+            notification_type: NotificationType::from_name(notification_type)
+                .ok_or_else(|| format!("check {}: unknown notification type: {}", name, notification_type))?,
+            message: message.to_string(),
+        })
+    }
+
+    /// Parse the plugin-config form
+    /// `"<command>" every <interval> notify-if-output|notify-if-failed <type> "<message>"`
+    pub fn parse_spec(name: &str, spec: &str) -> Result<Self, String> {
+        let words = split_quoted(spec);
+        match words.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+            [command, "every", every, condition, notification_type, message @ ..] => {
+                Self::parse(name, command, every, condition, notification_type, &message.join(" "))
+            }
+            _ => Err(format!(
+                "check {}: expected `\"<command>\" every <interval> notify-if-output <type> \"<message>\"`", name)),
+        }
+    }
+
+    /// Whether a run's result should raise the notification
+    pub fn triggers(&self, exit_code: Option<i32>, stdout: &[u8]) -> bool {
+        match self.condition {
+            CheckCondition::Output => !String::from_utf8_lossy(stdout).trim().is_empty(),
+            CheckCondition::Failure => exit_code != Some(0),
+        }
+    }
+
+    /// Notification raised when the check triggers
+    pub fn notification(&self) -> Notification {
+        let message = if self.message.is_empty() { self.command.as_str() } else { self.message.as_str() };
+        NotificationBuilder::new()
+            .notification_type(self.notification_type.clone())
+            .message(message)
+            .title(&self.name)
+            .source("check")
+            .build()
+    }
+
+    /// Command line to run
+    pub fn command_line(&self) -> Vec<String> {
+        vec!["sh".to_string(), "-c".to_string(), self.command.clone()]
+    }
+}
+
+/// Split on whitespace, keeping double-quoted parts together (without the quotes)
+pub fn split_quoted(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+    for c in text.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+/// Schedules checks by plugin time
+#[derive(Debug, Clone, Default)]
+pub struct CheckScheduler {
+    /// Plugin time of each check's next run (ms)
+    next_due_ms: BTreeMap<String, u64>,
+    /// Checks whose result is outstanding
+    running: BTreeSet<String>,
+}
+
+impl CheckScheduler {
+    /// Checks due at `now_ms`, marked as running. A check first runs on the first call after it
+    /// was configured.
+    pub fn due<'a>(&mut self, checks: &'a [Check], now_ms: u64) -> Vec<&'a Check> {
+        let mut due = Vec::new();
+        for check in checks {
+            let next_due_ms = self.next_due_ms.entry(check.name.clone()).or_insert(now_ms);
+            if now_ms < *next_due_ms || self.running.contains(&check.name) {
+                continue;
+            }
+            *next_due_ms = now_ms + check.every_ms;
+            self.running.insert(check.name.clone());
+            due.push(check);
+        }
+        due
+    }
+
+    /// Record that a check's result arrived
+    pub fn finish(&mut self, name: &str) {
+        self.running.remove(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec_and_trigger() {
+        let check = Check::parse_spec("dirty",
+            "\"git status --porcelain\" every 300s notify-if-output warning \"uncommitted changes\"").unwrap();
+        assert_eq!(check.command, "git status --porcelain");
+        assert_eq!(check.every_ms, 300_000);
+        assert_eq!(check.condition, CheckCondition::Output);
+        assert_eq!(check.notification_type, NotificationType::Warning);
+        assert_eq!(check.message, "uncommitted changes");
+        assert!(check.triggers(Some(0), b" M src/main.rs\n"));
+        assert!(!check.triggers(Some(0), b"\n"));
+
+        let failing = Check::parse("ci", "make test", "10m", "notify-if-failed", "error", "").unwrap();
+        assert!(failing.triggers(Some(2), b""));
+        assert!(!failing.triggers(Some(0), b"ok"));
+        assert_eq!(failing.notification().message, "make test");
+
+        assert!(Check::parse_spec("x", "\"ls\" every 1s notify-if-output info hi").is_err());
+        assert!(Check::parse_spec("x", "\"ls\" every 5m notify-if-odd info hi").is_err());
+        assert!(Check::parse_spec("x", "ls now").is_err());
+    }
+
+    #[test]
+    fn test_scheduler_waits_for_interval_and_result() {
+        let checks = vec![Check::parse("dirty", "git status --porcelain", "60s", "output", "warning", "dirty").unwrap()];
+        let mut scheduler = CheckScheduler::default();
+        assert_eq!(scheduler.due(&checks, 0).len(), 1);
+        // Still running when the interval has passed
+        assert!(scheduler.due(&checks, 60_000).is_empty());
+        scheduler.finish("dirty");
+        assert!(scheduler.due(&checks, 59_999).is_empty());
+        assert_eq!(scheduler.due(&checks, 60_000).len(), 1);
+    }
+}
@@ -0,0 +1,126 @@
+//! Chord module for Zellij Visual Notifications
+//!
+//! A leader key (`chord_leader`, `Ctrl y` by default) followed by an action key acts on the
+//! notification of the focused pane. Single letters are easily taken by applications and other
+//! bindings; behind the leader the whole alphabet is free. While the chord waits for its second
+//! key, a hint bar lists the actions.
+
+use std::str::FromStr;
+use zellij_tile::prelude::KeyWithModifier;
+
+/// Default leader key
+pub const DEFAULT_CHORD_LEADER: &str = "Ctrl y";
+
+/// How long a chord waits for its action key (ms)
+pub const CHORD_TIMEOUT_MS: u64 = 3000;
+
+/// What the second key of a chord does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordAction {
+    /// Acknowledge the focused pane's notification
+    Acknowledge,
+    /// Mark the focused pane's notification read
+    Read,
+    /// Pin or unpin the focused pane's notification
+    Pin,
+    /// Push back the expiry of the focused pane's notification
+    Extend,
+    /// Mute or unmute the focused pane
+    Mute,
+    /// Clear all notifications (asks for confirmation)
+    ClearAll,
+}
+
+impl ChordAction {
+    /// All actions, in hint bar order
+    pub const ALL: [ChordAction; 6] = [
+        ChordAction::Acknowledge,
+        ChordAction::Read,
+        ChordAction::Pin,
+        ChordAction::Extend,
+        ChordAction::Mute,
+        ChordAction::ClearAll,
+    ];
+
+    /// Action key
+    pub fn key(&self) -> char {
+        match self {
+            ChordAction::Acknowledge => 'a',
+            ChordAction::Read => 'r',
+            ChordAction::Pin => 'p',
+            ChordAction::Extend => 'e',
+            ChordAction::Mute => 'm',
+            ChordAction::ClearAll => 'c',
+        }
+    }
+
+    /// Hint bar label
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChordAction::Acknowledge => "ack",
+            ChordAction::Read => "read",
+            ChordAction::Pin => "pin",
+            ChordAction::Extend => "extend",
+            ChordAction::Mute => "mute",
+            ChordAction::ClearAll => "clear all",
+        }
+    }
+
+    /// Action bound to a key
+    pub fn from_key(key: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.key() == key.to_ascii_lowercase())
+    }
+}
+
+/// Whether a configured leader switches chords off (empty or `off`)
+pub fn is_off(name: &str) -> bool {
+    name.trim().is_empty() || name.trim().eq_ignore_ascii_case("off")
+}
+
+/// Leader key from its configured name (`Ctrl y`, `Alt n`, ...); None when off or invalid
+pub fn parse_leader(name: &str) -> Option<KeyWithModifier> {
+    if is_off(name) {
+        return None;
+    }
+    KeyWithModifier::from_str(name.trim()).ok()
+}
+
+/// Hint bar text with the pane the actions apply to, e.g.
+/// `Ctrl y (pane 3): a ack  r read  ...  (other keys cancel)`
+pub fn hint(leader: &str, pane_id: Option<u32>) -> String {
+    let actions: Vec<String> = ChordAction::ALL.iter()
+        .map(|action| format!("{} {}", action.key(), action.label()))
+        .collect();
+    let target = match pane_id {
+        Some(pane_id) => format!("pane {}", pane_id),
+        None => "no notification".to_string(),
+    };
+    format!("{} ({}): {}  (other keys cancel)", leader.trim(), target, actions.join("  "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zellij_tile::prelude::{BareKey, KeyModifier};
+
+    #[test]
+    fn test_parse_leader() {
+        let leader = parse_leader(DEFAULT_CHORD_LEADER).unwrap();
+        assert_eq!(leader.bare_key, BareKey::Char('y'));
+        assert!(leader.key_modifiers.contains(&KeyModifier::Ctrl));
+        assert!(parse_leader("Alt n").unwrap().key_modifiers.contains(&KeyModifier::Alt));
+        assert!(parse_leader("").is_none());
+        assert!(parse_leader("off").is_none() && is_off(" OFF "));
+        assert!(parse_leader("Ctrl Shift").is_none());
+    }
+
+    #[test]
+    fn test_actions_and_hint() {
+        assert_eq!(ChordAction::from_key('a'), Some(ChordAction::Acknowledge));
+        assert_eq!(ChordAction::from_key('C'), Some(ChordAction::ClearAll));
+        assert_eq!(ChordAction::from_key('z'), None);
+        assert_eq!(hint("Ctrl y", Some(3)),
+            "Ctrl y (pane 3): a ack  r read  p pin  e extend  m mute  c clear all  (other keys cancel)");
+        assert!(hint(" Alt n ", None).starts_with("Alt n (no notification): a ack"));
+    }
+}
@@ -0,0 +1,42 @@
+//! Claude pane detection module for Zellij Visual Notifications
+//!
+//! Panes running Claude Code are told apart from the rest of the layout by their title (Claude
+//! Code sets it while it runs), by the command of command panes, or by the extra patterns in
+//! `claude_pane_patterns`. Their entries get a dedicated accent bar and sort first in the status
+//! bar, and passive title detection skips them: their hooks already report what title changes
+//! would, and reporting both would alert twice.
+
+/// Default patterns a Claude pane's title or command contains
+pub const DEFAULT_CLAUDE_PANE_PATTERNS: [&str; 1] = ["claude"];
+
+/// Default accent of Claude pane entries
+pub const DEFAULT_CLAUDE_PANE_COLOR: &str = "#d97757";
+
+/// Whether a pane runs Claude Code: its title or command contains one of `patterns`, ignoring
+/// case. Without patterns, no pane does.
+pub fn is_claude_pane(patterns: &[String], title: &str, command: Option<&str>) -> bool {
+    let title = title.to_lowercase();
+    let command = command.map(str::to_lowercase).unwrap_or_default();
+    patterns.iter()
+        .map(|pattern| pattern.trim().to_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .any(|pattern| title.contains(&pattern) || command.contains(&pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claude_panes_match_title_or_command() {
+        let patterns: Vec<String> = DEFAULT_CLAUDE_PANE_PATTERNS.iter().map(|p| p.to_string()).collect();
+        assert!(is_claude_pane(&patterns, "✳ Claude Code", None));
+        assert!(is_claude_pane(&patterns, "zsh", Some("/usr/local/bin/claude --resume")));
+        assert!(!is_claude_pane(&patterns, "cargo build", Some("cargo build")));
+
+        let patterns = vec!["aider".to_string(), " ".to_string()];
+        assert!(is_claude_pane(&patterns, "Aider v0.50", None));
+        assert!(!is_claude_pane(&patterns, "Claude Code", None));
+        assert!(!is_claude_pane(&[], "Claude Code", Some("claude")));
+    }
+}
@@ -0,0 +1,197 @@
+//! Clock and ID generation module for Zellij Visual Notifications
+//!
+//! Provides injectable time and ID sources. Production code uses a clock driven by host timer
+//! events and host-seeded IDs; tests install deterministic implementations instead of relying
+//! on `SystemTime`, which is nondeterministic and unreliable under WASM. Unix time (to compare
+//! with sender timestamps) is only known once the host clock has been synced with it.
+
+use std::cell::RefCell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of the current plugin time
+pub trait Clock {
+    /// Current time in milliseconds
+    fn now_ms(&self) -> u64;
+}
+
+/// Production clock, advanced by the elapsed time reported with host timer events
+#[derive(Debug, Clone, Default)]
+pub struct HostClock {
+    /// Accumulated elapsed time (ms)
+    elapsed_ms: u64,
+    /// Unix time at the last sync and the elapsed time then (ms)
+    unix_anchor: Option<(u64, u64)>,
+}
+
+impl HostClock {
+    /// Create a new host clock starting at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance by the elapsed seconds reported with a timer event
+    pub fn advance(&mut self, elapsed_secs: f64) {
+        self.elapsed_ms += (elapsed_secs * 1000.0) as u64;
+    }
+
+    /// Sync with the Unix time (ms)
+    pub fn sync_unix(&mut self, unix_ms: u64) {
+        self.unix_anchor = Some((unix_ms, self.elapsed_ms));
+    }
+
+    /// Current Unix time (ms), once synced
+    pub fn unix_ms(&self) -> Option<u64> {
+        self.unix_anchor.map(|(unix_ms, at_ms)| unix_ms + self.elapsed_ms.saturating_sub(at_ms))
+    }
+}
+
+/// Unix time from the host (ms), if the runtime provides it
+pub fn system_unix_ms() -> Option<u64> {
+    SystemTime::now().duration_since(UNIX_EPOCH).ok().map(|elapsed| elapsed.as_millis() as u64)
+}
+
+impl Clock for HostClock {
+    fn now_ms(&self) -> u64 {
+        self.elapsed_ms
+    }
+}
+
+/// Deterministic clock for tests, set explicitly
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    /// Current time (ms)
+    now_ms: u64,
+}
+
+impl ManualClock {
+    /// Create a manual clock at the given time
+    pub fn new(now_ms: u64) -> Self {
+        Self { now_ms }
+    }
+
+    /// Set the current time
+    pub fn set(&mut self, now_ms: u64) {
+        self.now_ms = now_ms;
+    }
+
+    /// Advance the current time
+    pub fn advance_ms(&mut self, elapsed_ms: u64) {
+        self.now_ms += elapsed_ms;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+}
+
+/// Source of unique notification IDs
+pub trait IdGenerator {
+    /// Produce the next ID
+    fn next_id(&mut self) -> String;
+}
+
+/// Production ID generator, seeded from host-provided values (plugin ID, process ID)
+#[derive(Debug, Clone, Default)]
+pub struct HostIdGenerator {
+    /// Per-instance seed so IDs differ between plugin instances
+    seed: u32,
+    /// Monotonic sequence number
+    sequence: u64,
+}
+
+impl HostIdGenerator {
+    /// Create a generator with a host-provided seed
+    pub fn new(seed: u32) -> Self {
+        Self { seed, sequence: 0 }
+    }
+}
+
+impl IdGenerator for HostIdGenerator {
+    fn next_id(&mut self) -> String {
+        self.sequence += 1;
+        format!("notif-{:08x}-{}", self.seed, self.sequence)
+    }
+}
+
+/// Deterministic ID generator for tests (`notif-1`, `notif-2`, ...)
+#[derive(Debug, Clone, Default)]
+pub struct SequentialIdGenerator {
+    /// Next sequence number
+    next: u64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator starting at 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&mut self) -> String {
+        self.next += 1;
+        format!("notif-{}", self.next)
+    }
+}
+
+thread_local! {
+    /// ID generator used by notification constructors (the plugin is single-threaded)
+    static ID_GENERATOR: RefCell<Box<dyn IdGenerator>> = RefCell::new(Box::new(HostIdGenerator::default()));
+}
+
+/// Install the ID generator used for new notifications
+pub fn set_id_generator(generator: Box<dyn IdGenerator>) {
+    ID_GENERATOR.with(|g| *g.borrow_mut() = generator);
+}
+
+/// Generate the next notification ID from the installed generator
+pub fn next_id() -> String {
+    ID_GENERATOR.with(|g| g.borrow_mut().next_id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_clock_advances_with_timer() {
+        let mut clock = HostClock::new();
+        clock.advance(0.05);
+        clock.advance(0.05);
+        assert_eq!(clock.now_ms(), 100);
+
+        // Unix time follows the plugin time from the last sync
+        assert_eq!(clock.unix_ms(), None);
+        clock.sync_unix(1_760_000_000_000);
+        clock.advance(0.25);
+        assert_eq!(clock.unix_ms(), Some(1_760_000_000_250));
+    }
+
+    #[test]
+    fn test_manual_clock() {
+        let mut clock = ManualClock::new(1000);
+        clock.advance_ms(500);
+        assert_eq!(clock.now_ms(), 1500);
+        clock.set(10);
+        assert_eq!(clock.now_ms(), 10);
+    }
+
+    #[test]
+    fn test_id_generators() {
+        let mut sequential = SequentialIdGenerator::new();
+        assert_eq!(sequential.next_id(), "notif-1");
+        assert_eq!(sequential.next_id(), "notif-2");
+
+        let mut host = HostIdGenerator::new(0xabc);
+        assert_eq!(host.next_id(), "notif-00000abc-1");
+    }
+
+    #[test]
+    fn test_installed_generator_is_deterministic() {
+        set_id_generator(Box::new(SequentialIdGenerator::new()));
+        assert_eq!(next_id(), "notif-1");
+        assert_eq!(next_id(), "notif-2");
+    }
+}
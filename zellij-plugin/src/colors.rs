@@ -3,7 +3,20 @@
 //! Handles terminal color capabilities, theme colors, and color interpolation for animations.
 
 use crate::config::ThemeConfig;
-use crate::notification::NotificationType;
+use crate::exitcode::ExitCodeColor;
+use crate::notification::{NotificationType, Priority};
+
+/// Colors generated between each pair of gradient stops
+const GRADIENT_SEGMENT_STEPS: usize = 16;
+
+/// Accent colors for correlation groups (hues apart from the notification type colors)
+const ACCENT_COLORS: [&str; 6] = ["#c084fc", "#22d3ee", "#f472b6", "#a3e635", "#fb923c", "#818cf8"];
+
+/// Share of the notification color in a chip background (the rest is the theme background)
+const CHIP_TINT: f32 = 0.3;
+
+/// Contrast ratio chip text keeps at least (WCAG AA for normal text)
+const CHIP_MIN_CONTRAST: f32 = 4.5;
 
 /// Color manager for handling terminal colors
 #[derive(Debug, Clone)]
@@ -14,6 +27,8 @@ pub struct ColorManager {
     color_capability: ColorCapability,
     /// High contrast mode enabled
     high_contrast: bool,
+    /// Colors of exit code ranges, first match wins
+    exit_code_colors: Vec<ExitCodeColor>,
 }
 
 impl Default for ColorManager {
@@ -22,6 +37,7 @@ impl Default for ColorManager {
             theme: ThemeConfig::default(),
             color_capability: ColorCapability::TrueColor,
             high_contrast: false,
+            exit_code_colors: Vec::new(),
         }
     }
 }
@@ -33,6 +49,7 @@ impl ColorManager {
             theme: theme.clone(),
             color_capability: Self::detect_capability(),
             high_contrast: false,
+            exit_code_colors: Vec::new(),
         }
     }
 
@@ -48,6 +65,20 @@ impl ColorManager {
         self.high_contrast = enabled;
     }
 
+    /// Suppress all color output (NO_COLOR), or return to the detected capability
+    pub fn set_no_color(&mut self, enabled: bool) {
+        self.color_capability = if enabled {
+            ColorCapability::Mono
+        } else {
+            Self::detect_capability()
+        };
+    }
+
+    /// Whether color output is suppressed
+    pub fn is_mono(&self) -> bool {
+        self.color_capability == ColorCapability::Mono
+    }
+
     /// Get the notification color based on type
     pub fn get_notification_color(&self, notification_type: &NotificationType) -> Option<String> {
         let base_color = match notification_type {
@@ -62,6 +93,24 @@ impl ColorManager {
         Some(self.adjust_for_capability(base_color))
     }
 
+    /// Resolve the display color, preferring a sender-provided override when present
+    pub fn resolve_color(&self, notification_type: &NotificationType, custom_color: Option<&str>) -> Option<String> {
+        match custom_color {
+            Some(color) => Some(self.adjust_for_capability(color)),
+            None => self.get_notification_color(notification_type),
+        }
+    }
+
+    /// Color the ranges in `exit_code_colors`
+    pub fn set_exit_code_colors(&mut self, colors: Vec<ExitCodeColor>) {
+        self.exit_code_colors = colors;
+    }
+
+    /// Color of the first range containing `code`, as configured (unadjusted, like a sender's color)
+    pub fn exit_code_color(&self, code: i32) -> Option<&str> {
+        self.exit_code_colors.iter().find(|range| range.contains(code)).map(|range| range.color.as_str())
+    }
+
     /// Get the background color
     pub fn get_background_color(&self) -> String {
         self.adjust_for_capability(&self.theme.background_color)
@@ -77,6 +126,18 @@ impl ColorManager {
         self.adjust_for_capability(&self.theme.dimmed_color)
     }
 
+    /// Get the highlight color
+    pub fn get_highlight_color(&self) -> String {
+        self.adjust_for_capability(&self.theme.highlight_color)
+    }
+
+    /// Accent color shared by the members of a correlation group (stable per ID)
+    pub fn accent_color(&self, key: &str) -> String {
+        // FNV-1a, so the same ID keeps its accent across reloads
+        let hash = key.bytes().fold(0x811c_9dc5_u32, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+        self.adjust_for_capability(ACCENT_COLORS[hash as usize % ACCENT_COLORS.len()])
+    }
+
     /// Adjust color based on terminal capability and high contrast mode
     fn adjust_for_capability(&self, hex_color: &str) -> String {
         let color = Color::from_hex(hex_color);
@@ -88,11 +149,12 @@ impl ColorManager {
                 ColorCapability::TrueColor => adjusted.to_hex(),
                 ColorCapability::Color256 => adjusted.to_ansi256().to_string(),
                 ColorCapability::Color16 => adjusted.to_ansi16().to_string(),
+                ColorCapability::Mono => hex_color.to_string(),
             };
         }
 
         match self.color_capability {
-            ColorCapability::TrueColor => hex_color.to_string(),
+            ColorCapability::TrueColor | ColorCapability::Mono => hex_color.to_string(),
             ColorCapability::Color256 => color.to_ansi256().to_string(),
             ColorCapability::Color16 => color.to_ansi16().to_string(),
         }
@@ -106,6 +168,34 @@ impl ColorManager {
         result.to_hex()
     }
 
+    /// Scale color intensity by priority, blending toward the theme background
+    /// (Critical = saturated theme color, lower priorities progressively dimmer)
+    pub fn priority_adjusted(&self, hex_color: &str, priority: &Priority) -> String {
+        let blend = match priority {
+            Priority::Critical => return hex_color.to_string(),
+            Priority::High => 0.1,
+            Priority::Normal => 0.25,
+            Priority::Low => 0.5,
+        };
+        self.interpolate(hex_color, &self.theme.background_color, blend)
+    }
+
+    /// Color at a position (0.0 - 1.0) along a multi-stop gradient
+    pub fn gradient_color(&self, stops: &[String], position: f32) -> String {
+        match stops {
+            [] => self.get_foreground_color(),
+            [only] => only.clone(),
+            _ => {
+                let colors: Vec<Color> = stops.iter().map(|stop| Color::from_hex(stop)).collect();
+                let gradient: Vec<Color> = colors.windows(2)
+                    .flat_map(|pair| generate_gradient(&pair[0], &pair[1], GRADIENT_SEGMENT_STEPS))
+                    .collect();
+                let index = (position.clamp(0.0, 1.0) * (gradient.len() - 1) as f32).round() as usize;
+                gradient[index].to_hex()
+            }
+        }
+    }
+
     /// Apply brightness to a color
     pub fn apply_brightness(&self, hex_color: &str, brightness: f32) -> String {
         let color = Color::from_hex(hex_color);
@@ -126,6 +216,7 @@ impl ColorManager {
             ColorCapability::Color16 => {
                 format!("\x1b[{}m", color.to_ansi16())
             }
+            ColorCapability::Mono => String::new(),
         }
     }
 
@@ -142,6 +233,47 @@ impl ColorManager {
             ColorCapability::Color16 => {
                 format!("\x1b[{}m", color.to_ansi16() + 10)
             }
+            ColorCapability::Mono => String::new(),
+        }
+    }
+
+    /// Chip background for a notification color: the color blended toward the theme background.
+    /// On 256 and 16 color terminals the tint is raised until the chip no longer falls on the
+    /// same palette entry as the background, so chips stay visible there too.
+    pub fn chip_background(&self, hex_color: &str) -> String {
+        let background = Color::from_hex(&self.theme.background_color);
+        let color = Color::from_hex(hex_color);
+        let mut tint = CHIP_TINT;
+        loop {
+            let chip = background.interpolate(&color, tint);
+            if tint >= 1.0 || !self.same_palette_entry(&chip, &background) {
+                return chip.to_hex();
+            }
+            tint = (tint + 0.1).min(1.0);
+        }
+    }
+
+    /// Text color for a chip: the theme foreground if it contrasts enough with the chip,
+    /// otherwise black or white, whichever contrasts more
+    pub fn chip_foreground(&self, chip_hex: &str) -> String {
+        let chip = Color::from_hex(chip_hex);
+        let foreground = Color::from_hex(&self.theme.foreground_color);
+        if foreground.contrast_ratio(&chip) >= CHIP_MIN_CONTRAST {
+            return foreground.to_hex();
+        }
+        if colors::BLACK.contrast_ratio(&chip) > colors::WHITE.contrast_ratio(&chip) {
+            colors::BLACK.to_hex()
+        } else {
+            colors::WHITE.to_hex()
+        }
+    }
+
+    /// Whether two colors are drawn the same at the detected capability
+    fn same_palette_entry(&self, a: &Color, b: &Color) -> bool {
+        match self.color_capability {
+            ColorCapability::TrueColor | ColorCapability::Mono => a.to_hex() == b.to_hex(),
+            ColorCapability::Color256 => a.to_ansi256() == b.to_ansi256(),
+            ColorCapability::Color16 => a.to_ansi16() == b.to_ansi16(),
         }
     }
 
@@ -160,6 +292,8 @@ pub enum ColorCapability {
     Color256,
     /// 16 color mode (basic ANSI)
     Color16,
+    /// No color output (NO_COLOR); emphasis comes from text attributes only
+    Mono,
 }
 
 /// RGB Color representation
@@ -293,6 +427,21 @@ impl Color {
     pub fn is_light(&self) -> bool {
         self.luminance() > 0.5
     }
+
+    /// Relative luminance as defined by WCAG (linear light, 0.0 - 1.0)
+    pub fn relative_luminance(&self) -> f32 {
+        let linear = |channel: u8| {
+            let c = channel as f32 / 255.0;
+            if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// WCAG contrast ratio against another color (1.0 - 21.0)
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
 }
 
 /// Predefined colors for quick access
@@ -356,6 +505,52 @@ mod tests {
         assert_eq!(color2.b, 0);
     }
 
+    #[test]
+    fn test_exit_code_color_resolver() {
+        let mut manager = ColorManager::default();
+        assert_eq!(manager.exit_code_color(1), None);
+        manager.set_exit_code_colors(vec![
+            ExitCodeColor::parse("1", "#ff6666").unwrap(),
+            ExitCodeColor::parse("1..=127", "#ff0000").unwrap(),
+            ExitCodeColor::parse("128..", "#cc0000").unwrap(),
+        ]);
+        assert_eq!(manager.exit_code_color(1), Some("#ff6666"));
+        assert_eq!(manager.exit_code_color(2), Some("#ff0000"));
+        assert_eq!(manager.exit_code_color(137), Some("#cc0000"));
+        assert_eq!(manager.exit_code_color(0), None);
+    }
+
+    #[test]
+    fn test_no_color_suppresses_escapes() {
+        let mut manager = ColorManager::default();
+        manager.set_no_color(true);
+        assert!(manager.is_mono());
+        assert_eq!(manager.fg_escape("#ff0000"), "");
+        assert_eq!(manager.bg_escape("#ff0000"), "");
+
+        manager.set_no_color(false);
+        assert_eq!(manager.fg_escape("#ff0000"), "\x1b[38;2;255;0;0m");
+    }
+
+    #[test]
+    fn test_chip_colors_stay_visible_and_readable() {
+        let mut manager = ColorManager::default();
+        let background = Color::from_hex(&manager.get_background_color());
+        let chip = manager.chip_background("#ef4444");
+        assert_ne!(chip, manager.get_background_color());
+        assert_ne!(chip, "#ef4444");
+        let text = Color::from_hex(&manager.chip_foreground(&chip));
+        assert!(text.contrast_ratio(&Color::from_hex(&chip)) >= CHIP_MIN_CONTRAST);
+        assert_eq!(manager.chip_foreground("#fde047"), "#000000");
+
+        // A faint tint that would round to the background's palette entry is strengthened
+        manager.color_capability = ColorCapability::Color16;
+        let faint = background.interpolate(&Color::from_hex("#ef4444"), CHIP_TINT);
+        assert_eq!(faint.to_ansi16(), background.to_ansi16());
+        let chip = Color::from_hex(&manager.chip_background("#ef4444"));
+        assert_ne!(chip.to_ansi16(), background.to_ansi16());
+    }
+
     #[test]
     fn test_color_to_hex() {
         let color = Color::new(255, 128, 64);
@@ -394,6 +589,48 @@ mod tests {
         assert!(ansi_gray >= 232 || (ansi_gray >= 16 && ansi_gray <= 231));
     }
 
+    #[test]
+    fn test_priority_adjusted_intensity() {
+        let manager = ColorManager::new(&ThemeConfig::default());
+        let base = "#ef4444";
+
+        assert_eq!(manager.priority_adjusted(base, &Priority::Critical), base);
+
+        let background = Color::from_hex(&ThemeConfig::default().background_color);
+        let distance = |hex: String| {
+            let c = Color::from_hex(&hex);
+            (c.r as i32 - background.r as i32).abs()
+                + (c.g as i32 - background.g as i32).abs()
+                + (c.b as i32 - background.b as i32).abs()
+        };
+
+        let high = distance(manager.priority_adjusted(base, &Priority::High));
+        let normal = distance(manager.priority_adjusted(base, &Priority::Normal));
+        let low = distance(manager.priority_adjusted(base, &Priority::Low));
+        assert!(high > normal);
+        assert!(normal > low);
+    }
+
+    #[test]
+    fn test_resolve_color_override() {
+        let manager = ColorManager::new(&ThemeConfig::default());
+        assert_eq!(manager.resolve_color(&NotificationType::Info, Some("#ff00ff")), Some("#ff00ff".to_string()));
+        assert_eq!(
+            manager.resolve_color(&NotificationType::Info, None),
+            manager.get_notification_color(&NotificationType::Info)
+        );
+    }
+
+    #[test]
+    fn test_multi_stop_gradient_color() {
+        let manager = ColorManager::default();
+        let stops = vec!["#ff0000".to_string(), "#ff8000".to_string(), "#ff0000".to_string()];
+        assert_eq!(manager.gradient_color(&stops, 0.0), "#ff0000");
+        assert_eq!(manager.gradient_color(&stops, 0.5), "#ff8000");
+        assert_eq!(manager.gradient_color(&stops, 1.0), "#ff0000");
+        assert_eq!(manager.gradient_color(&stops[..1], 0.7), "#ff0000");
+    }
+
     #[test]
     fn test_gradient_generation() {
         let start = Color::new(0, 0, 0);
@@ -0,0 +1,607 @@
+//! Control command module for Zellij Visual Notifications
+//!
+//! Parses plain-text control commands (`mute_pane 3`) received over pipes, as opposed to
+//! JSON notification messages handled by the event bridge.
+
+use crate::config::HistoryCompaction;
+use crate::notification::NotificationType;
+use crate::power::PowerSource;
+use crate::query::{parse_duration_ms, HistoryQuery};
+use crate::queue::QueueMove;
+use crate::replay::ReplayTarget;
+use crate::surface::DisplaySurface;
+
+/// Start of the parse error for a payload whose first word names no command
+const UNKNOWN_COMMAND: &str = "unknown command";
+
+/// A plain-text control command
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    /// Suppress visual updates for a pane
+    MutePane(u32),
+    /// Resume visual updates for a pane
+    UnmutePane(u32),
+    /// Clear all notifications (pinned ones only if forced)
+    ClearAll {
+        /// Also clear pinned notifications
+        force: bool,
+    },
+    /// Pin or unpin a displayed notification by ID
+    Pin {
+        /// Notification ID
+        id: String,
+        /// Pin (true) or unpin (false)
+        pinned: bool,
+    },
+    /// Push back the expiry of a queued or displayed notification
+    Extend {
+        /// Notification ID
+        id: String,
+        /// Time added to its TTL (ms)
+        extra_ms: u64,
+    },
+    /// Acknowledge all notifications of one type
+    AckType(NotificationType),
+    /// Mark a pane's notification read (dimmed badge, no animation)
+    MarkRead(u32),
+    /// Dismiss a pane's notification (read or not)
+    Dismiss(u32),
+    /// Hold non-critical notifications, optionally for a number of minutes
+    Dnd(Option<u64>),
+    /// Hold non-critical notifications for a number of minutes
+    Snooze(u64),
+    /// Batch non-critical notifications for a number of minutes (`focus_minutes` if None, which
+    /// ends focus mode instead while it is on)
+    Focus(Option<u64>),
+    /// End DND, snooze or focus mode and release held notifications
+    Resume,
+    /// Report sink delivery counters
+    SinkStatus,
+    /// Move a queued notification to another lane or the front of its own
+    QueueMove {
+        /// Notification ID
+        id: String,
+        /// Change to make
+        queue_move: QueueMove,
+    },
+    /// Change a theme color at runtime
+    ThemeSet {
+        /// Theme field (e.g. `success_color`)
+        field: String,
+        /// New color value
+        value: String,
+    },
+    /// Print the effective theme as a KDL block
+    ThemeDump,
+    /// Print effective configuration values (starting with a prefix) and their origin
+    ConfigShow(String),
+    /// Override a plugin configuration key at runtime
+    ConfigSet {
+        /// Plugin configuration key (e.g. `notification_timeout_ms`)
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// Remove a runtime override
+    ConfigUnset(String),
+    /// Drop the persisted preferences and return to the configuration
+    PrefsReset,
+    /// Write notifications, history, preferences and forwarded notifications to a JSON file
+    StateExport(String),
+    /// Load a state export written by `state_export`
+    StateImport(String),
+    /// Query the notification history
+    List(HistoryQuery),
+    /// Show the pipeline decisions for a notification (most recent if no ID)
+    Explain(Option<String>),
+    /// Show a pane's recent state transitions, visual state and queued notifications
+    DebugPane(u32),
+    /// Run history entries through rules, queue and sinks again without effects
+    Replay(ReplayTarget),
+    /// Switch the enlarged presentation view on or off (toggle if unspecified)
+    BigMode(Option<bool>),
+    /// Switch low power mode on or off (toggle if unspecified)
+    LowPower(Option<bool>),
+    /// Switch the sound theme (list the themes if unspecified)
+    SoundTheme(Option<String>),
+    /// Merge repeated history entries now (the configured mode, or exact if off, unless given)
+    CompactHistory(Option<HistoryCompaction>),
+    /// Answer an attention notification with its quick reply option `number` (the pane's, or
+    /// the one a chord would act on if unspecified)
+    Reply {
+        /// Option number, from 1
+        number: u32,
+        /// Pane whose notification is answered
+        pane_id: Option<u32>,
+    },
+    /// Switch the plain text log view on or off (toggle if unspecified)
+    TextLog(Option<bool>),
+    /// Power source reported by a battery monitor (battery switches low power mode on)
+    Power(PowerSource),
+    /// Switch a display surface on or off at runtime (toggle if unspecified)
+    Surface {
+        /// Surface to switch
+        surface: DisplaySurface,
+        /// New state
+        enabled: Option<bool>,
+    },
+    /// Print the scheduled reminders
+    Reminders,
+    /// Print the channel list with visibility and counts
+    Channels,
+    /// Print response-time SLO targets and statistics
+    Slo,
+    /// Print the correlated notification groups and their members
+    Groups,
+    /// Print the notifications forwarded from other sessions, by session
+    Sessions,
+    /// Show or hide a channel's entries (toggle if unspecified)
+    Channel {
+        /// Channel name
+        name: String,
+        /// New visibility
+        visible: Option<bool>,
+    },
+    /// Print the health checklist
+    Health,
+    /// List the experimental features and whether they are on
+    Features,
+    /// List the subsystems with their lifecycle status
+    Lifecycle,
+    /// Print the plugin and protocol versions, configuration sources and permissions
+    Version,
+    /// Show the first-run tutorial again
+    Tutorial,
+    /// Answer the sender question shown (default if unspecified)
+    Answer(Option<bool>),
+    /// Print the supported protocol versions, features and limits (JSON)
+    Capabilities,
+    /// Print frame timings (tick, update and render durations)
+    Stats,
+    /// Synthesize notifications at a rate to test throughput
+    Stress {
+        /// Notifications to send
+        count: u32,
+        /// Notifications per second
+        rate: u32,
+    },
+    /// Stop a running stress test
+    StressStop,
+    /// Print counters and queue gauges in Prometheus exposition format
+    Metrics,
+    /// Print a Claude Code session's lifecycle timeline (all sessions if unspecified)
+    Timeline {
+        /// Session ID
+        session_id: Option<String>,
+        /// Export as JSON
+        json: bool,
+    },
+    /// Ask for the plugin permissions again
+    RequestPermissions,
+    /// Feed a synthetic host event through the regular handlers (scripted routing tests)
+    Simulate(SimulatedEvent),
+    /// Change an accessibility setting at runtime
+    Set {
+        /// Setting name (e.g. `reduced_motion`)
+        setting: String,
+        /// New value
+        enabled: bool,
+    },
+}
+
+/// Synthetic host event for the `simulate` command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimulatedEvent {
+    /// A pane gains focus in its tab
+    Focus(u32),
+    /// A pane is closed
+    PaneClose(u32),
+}
+
+impl ControlCommand {
+    /// Parse a command line of the form `name [args...]`
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut parts = line.split_whitespace();
+        let name = parts.next().ok_or_else(|| "empty command".to_string())?;
+        let args: Vec<&str> = parts.collect();
+
+        match name {
+            "mute_pane" => Ok(Self::MutePane(parse_pane_id(&args)?)),
+            "unmute_pane" => Ok(Self::UnmutePane(parse_pane_id(&args)?)),
+            "clear" | "clear_all" => match args.as_slice() {
+                [] => Ok(Self::ClearAll { force: false }),
+                ["force"] => Ok(Self::ClearAll { force: true }),
+                _ => Err("expected nothing or force".to_string()),
+            },
+            "pin" | "unpin" => match args.as_slice() {
+                [id] => Ok(Self::Pin { id: id.to_string(), pinned: name == "pin" }),
+                _ => Err("expected a single notification ID".to_string()),
+            },
+            "extend" => match args.as_slice() {
+                [id, extra] => Ok(Self::Extend { id: id.to_string(), extra_ms: parse_extension_ms(extra)? }),
+                _ => Err("expected a notification ID and a duration".to_string()),
+            },
+            "ack_type" => Ok(Self::AckType(parse_notification_type(&args)?)),
+            "read" | "mark_read" => Ok(Self::MarkRead(parse_pane_id(&args)?)),
+            "dismiss" => Ok(Self::Dismiss(parse_pane_id(&args)?)),
+            "dnd" => match args.as_slice() {
+                [] => Ok(Self::Dnd(None)),
+                ["off"] => Ok(Self::Resume),
+                _ => Ok(Self::Dnd(Some(parse_minutes(&args)?))),
+            },
+            "snooze" => Ok(Self::Snooze(parse_minutes(&args)?)),
+            "focus" => match args.as_slice() {
+                [] => Ok(Self::Focus(None)),
+                ["off"] => Ok(Self::Resume),
+                _ => Ok(Self::Focus(Some(parse_minutes(&args)?))),
+            },
+            "resume" => Ok(Self::Resume),
+            "sink_status" => Ok(Self::SinkStatus),
+            "promote" | "demote" | "move_front" => match args.as_slice() {
+                [id] => Ok(Self::QueueMove {
+                    id: id.to_string(),
+                    queue_move: match name {
+                        "promote" => QueueMove::Promote,
+                        "demote" => QueueMove::Demote,
+                        _ => QueueMove::Front,
+                    },
+                }),
+                _ => Err("expected a single notification ID".to_string()),
+            },
+            "theme_set" => match args.as_slice() {
+                [field, value] => Ok(Self::ThemeSet { field: field.to_string(), value: value.to_string() }),
+                _ => Err("expected a theme field and a value".to_string()),
+            },
+            "theme_dump" => Ok(Self::ThemeDump),
+            "config_show" => match args.as_slice() {
+                [] => Ok(Self::ConfigShow(String::new())),
+                [prefix] => Ok(Self::ConfigShow(prefix.to_string())),
+                _ => Err("expected at most one key prefix".to_string()),
+            },
+            "config_set" => match args.split_first() {
+                Some((key, value)) if !value.is_empty() => Ok(Self::ConfigSet { key: key.to_string(), value: value.join(" ") }),
+                _ => Err("expected a configuration key and a value".to_string()),
+            },
+            "config_unset" => match args.as_slice() {
+                [key] => Ok(Self::ConfigUnset(key.to_string())),
+                _ => Err("expected a configuration key".to_string()),
+            },
+            "prefs_reset" => Ok(Self::PrefsReset),
+            "state_export" | "state_import" => match args.as_slice() {
+                [path] if name == "state_export" => Ok(Self::StateExport(path.to_string())),
+                [path] => Ok(Self::StateImport(path.to_string())),
+                _ => Err("expected a file path".to_string()),
+            },
+            "list" | "history" => Ok(Self::List(HistoryQuery::parse(&args)?)),
+            "reminders" => Ok(Self::Reminders),
+            "channels" => Ok(Self::Channels),
+            "slo" => Ok(Self::Slo),
+            "groups" => Ok(Self::Groups),
+            "sessions" => Ok(Self::Sessions),
+            "channel" => match args.as_slice() {
+                [name] => Ok(Self::Channel { name: name.to_string(), visible: None }),
+                [name, "show" | "on"] => Ok(Self::Channel { name: name.to_string(), visible: Some(true) }),
+                [name, "hide" | "off"] => Ok(Self::Channel { name: name.to_string(), visible: Some(false) }),
+                _ => Err("expected a channel name and optionally show or hide".to_string()),
+            },
+            "simulate" => match args.split_first() {
+                Some((&"focus", rest)) => Ok(Self::Simulate(SimulatedEvent::Focus(parse_pane_id(rest)?))),
+                Some((&"pane_close", rest)) => Ok(Self::Simulate(SimulatedEvent::PaneClose(parse_pane_id(rest)?))),
+                _ => Err("expected focus or pane_close and a pane id".to_string()),
+            },
+            "health" => Ok(Self::Health),
+            "features" => Ok(Self::Features),
+            "lifecycle" => Ok(Self::Lifecycle),
+            "version" => Ok(Self::Version),
+            "tutorial" => Ok(Self::Tutorial),
+            "answer" => match args.as_slice() {
+                [] | ["default"] => Ok(Self::Answer(None)),
+                ["y" | "yes"] => Ok(Self::Answer(Some(true))),
+                ["n" | "no"] => Ok(Self::Answer(Some(false))),
+                _ => Err("expected yes, no or default".to_string()),
+            },
+            "capabilities" => Ok(Self::Capabilities),
+            "stats" => Ok(Self::Stats),
+            "stress" => match args.as_slice() {
+                ["stop"] => Ok(Self::StressStop),
+                [count, rate] => Ok(Self::Stress {
+                    count: parse_positive(count, "count")?,
+                    rate: parse_positive(rate, "rate")?,
+                }),
+                _ => Err("expected a count and a rate per second, or stop".to_string()),
+            },
+            "metrics" => Ok(Self::Metrics),
+            "timeline" => match args.as_slice() {
+                [] => Ok(Self::Timeline { session_id: None, json: false }),
+                [session_id] => Ok(Self::Timeline { session_id: Some(session_id.to_string()), json: false }),
+                [session_id, "format=json"] => Ok(Self::Timeline { session_id: Some(session_id.to_string()), json: true }),
+                _ => Err("expected a session ID and optionally format=json".to_string()),
+            },
+            "request_permissions" => Ok(Self::RequestPermissions),
+            "big_mode" => match args.as_slice() {
+                [] => Ok(Self::BigMode(None)),
+                ["on" | "true"] => Ok(Self::BigMode(Some(true))),
+                ["off" | "false"] => Ok(Self::BigMode(Some(false))),
+                _ => Err("expected on or off".to_string()),
+            },
+            "low_power" => match args.as_slice() {
+                [] => Ok(Self::LowPower(None)),
+                ["on" | "true"] => Ok(Self::LowPower(Some(true))),
+                ["off" | "false"] => Ok(Self::LowPower(Some(false))),
+                _ => Err("expected on or off".to_string()),
+            },
+            "sound_theme" => match args.as_slice() {
+                [] => Ok(Self::SoundTheme(None)),
+                [name] => Ok(Self::SoundTheme(Some(name.to_string()))),
+                _ => Err("expected a sound theme name".to_string()),
+            },
+            "compact_history" => match args.as_slice() {
+                [] => Ok(Self::CompactHistory(None)),
+                [mode @ ("exact" | "similar")] => Ok(Self::CompactHistory(Some(HistoryCompaction::from_str(mode)))),
+                _ => Err("expected exact or similar".to_string()),
+            },
+            "reply" => match args.as_slice() {
+                [number] => Ok(Self::Reply { number: parse_positive(number, "option")?, pane_id: None }),
+                [number, pane] => Ok(Self::Reply {
+                    number: parse_positive(number, "option")?,
+                    pane_id: Some(parse_pane_id(&[pane])?),
+                }),
+                _ => Err("expected an option number and optionally a pane id".to_string()),
+            },
+            "text_log" => match args.as_slice() {
+                [] => Ok(Self::TextLog(None)),
+                ["on" | "true"] => Ok(Self::TextLog(Some(true))),
+                ["off" | "false"] => Ok(Self::TextLog(Some(false))),
+                _ => Err("expected on or off".to_string()),
+            },
+            "power" => match args.as_slice() {
+                [source] => PowerSource::from_name(source)
+                    .map(Self::Power)
+                    .ok_or_else(|| format!("unknown power source: {} (expected battery or ac)", source)),
+                _ => Err("expected battery or ac".to_string()),
+            },
+            "surface" => {
+                let (name, rest) = args.split_first().ok_or_else(|| "expected a surface name".to_string())?;
+                let surface = DisplaySurface::from_name(name)
+                    .ok_or_else(|| format!("unknown surface: {} (expected border, tabs, status or toasts)", name))?;
+                match rest {
+                    [] => Ok(Self::Surface { surface, enabled: None }),
+                    ["on" | "true"] => Ok(Self::Surface { surface, enabled: Some(true) }),
+                    ["off" | "false"] => Ok(Self::Surface { surface, enabled: Some(false) }),
+                    _ => Err("expected on or off".to_string()),
+                }
+            }
+            "explain" => match args.as_slice() {
+                [] => Ok(Self::Explain(None)),
+                [id] => Ok(Self::Explain(Some(id.to_string()))),
+                _ => Err("expected at most one notification ID".to_string()),
+            },
+            "debug_pane" => match args.as_slice() {
+                [pane_id] => pane_id.parse().map(Self::DebugPane)
+                    .map_err(|_| format!("invalid pane ID: {}", pane_id)),
+                _ => Err("expected a pane ID".to_string()),
+            },
+            "replay" => Ok(Self::Replay(ReplayTarget::parse(&args)?)),
+            "set" => match args.as_slice() {
+                [setting, value] => Ok(Self::Set {
+                    setting: setting.to_string(),
+                    enabled: value.parse().map_err(|_| format!("expected true or false, got: {}", value))?,
+                }),
+                _ => Err("expected a setting and true/false".to_string()),
+            },
+            other => Err(format!("{}: {}", UNKNOWN_COMMAND, other)),
+        }
+    }
+}
+
+/// Whether a parse error means the payload names no command at all (rather than a command
+/// with bad arguments)
+pub fn is_unknown_command(error: &str) -> bool {
+    error.starts_with(UNKNOWN_COMMAND)
+}
+
+/// Parse the single pane ID argument of a command
+fn parse_pane_id(args: &[&str]) -> Result<u32, String> {
+    match args {
+        [id] => id.parse().map_err(|_| format!("invalid pane id: {}", id)),
+        _ => Err("expected a single pane id".to_string()),
+    }
+}
+
+/// Parse a TTL extension: milliseconds, or a duration with a unit (`90s`, `10m`, `1h`)
+fn parse_extension_ms(value: &str) -> Result<u64, String> {
+    let extra_ms = match value.parse() {
+        Ok(ms) => ms,
+        Err(_) => parse_duration_ms(value)?,
+    };
+    if extra_ms == 0 {
+        return Err(format!("invalid duration: {}", value));
+    }
+    Ok(extra_ms)
+}
+
+/// Parse the single notification type argument of a command (canonical names only)
+fn parse_notification_type(args: &[&str]) -> Result<NotificationType, String> {
+    match args {
+        [name] => NotificationType::from_name(name).ok_or_else(|| format!("invalid notification type: {}", name)),
+        _ => Err("expected a single notification type".to_string()),
+    }
+}
+
+/// Parse a positive number argument
+fn parse_positive(value: &str, what: &str) -> Result<u32, String> {
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("invalid {}: {}", what, value)),
+    }
+}
+
+/// Parse the single positive minutes argument of a command
+fn parse_minutes(args: &[&str]) -> Result<u64, String> {
+    match args {
+        [minutes] => match minutes.parse() {
+            Ok(m) if m > 0 => Ok(m),
+            _ => Err(format!("invalid minutes: {}", minutes)),
+        },
+        _ => Err("expected a single duration in minutes".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(ControlCommand::parse("mute_pane 3"), Ok(ControlCommand::MutePane(3)));
+        assert_eq!(ControlCommand::parse("  unmute_pane 12 "), Ok(ControlCommand::UnmutePane(12)));
+        assert_eq!(ControlCommand::parse("clear"), Ok(ControlCommand::ClearAll { force: false }));
+        assert_eq!(ControlCommand::parse("clear force"), Ok(ControlCommand::ClearAll { force: true }));
+        assert_eq!(
+            ControlCommand::parse("pin n-1"),
+            Ok(ControlCommand::Pin { id: "n-1".to_string(), pinned: true })
+        );
+        assert_eq!(
+            ControlCommand::parse("unpin n-1"),
+            Ok(ControlCommand::Pin { id: "n-1".to_string(), pinned: false })
+        );
+        assert!(ControlCommand::parse("pin").is_err());
+        assert_eq!(
+            ControlCommand::parse("extend n-1 60000"),
+            Ok(ControlCommand::Extend { id: "n-1".to_string(), extra_ms: 60_000 })
+        );
+        assert_eq!(
+            ControlCommand::parse("extend n-1 10m"),
+            Ok(ControlCommand::Extend { id: "n-1".to_string(), extra_ms: 600_000 })
+        );
+        assert!(ControlCommand::parse("extend n-1").is_err());
+        assert!(ControlCommand::parse("extend n-1 0").is_err());
+        assert_eq!(ControlCommand::parse("dnd"), Ok(ControlCommand::Dnd(None)));
+        assert_eq!(ControlCommand::parse("dnd 30"), Ok(ControlCommand::Dnd(Some(30))));
+        assert_eq!(ControlCommand::parse("dnd off"), Ok(ControlCommand::Resume));
+        assert_eq!(ControlCommand::parse("snooze 10"), Ok(ControlCommand::Snooze(10)));
+        assert_eq!(ControlCommand::parse("focus"), Ok(ControlCommand::Focus(None)));
+        assert_eq!(ControlCommand::parse("focus 50"), Ok(ControlCommand::Focus(Some(50))));
+        assert_eq!(ControlCommand::parse("focus off"), Ok(ControlCommand::Resume));
+        assert_eq!(ControlCommand::parse("sink_status"), Ok(ControlCommand::SinkStatus));
+        assert_eq!(
+            ControlCommand::parse("move_front n-4"),
+            Ok(ControlCommand::QueueMove { id: "n-4".to_string(), queue_move: QueueMove::Front })
+        );
+        assert!(ControlCommand::parse("promote").is_err());
+        assert_eq!(
+            ControlCommand::parse("theme_set success_color #00ff88"),
+            Ok(ControlCommand::ThemeSet { field: "success_color".to_string(), value: "#00ff88".to_string() })
+        );
+        assert_eq!(ControlCommand::parse("theme_dump"), Ok(ControlCommand::ThemeDump));
+        assert_eq!(ControlCommand::parse("config_show"), Ok(ControlCommand::ConfigShow(String::new())));
+        assert_eq!(ControlCommand::parse("config_show theme"), Ok(ControlCommand::ConfigShow("theme".to_string())));
+        assert_eq!(
+            ControlCommand::parse("config_set rule_ci source=ci -> drop"),
+            Ok(ControlCommand::ConfigSet { key: "rule_ci".to_string(), value: "source=ci -> drop".to_string() })
+        );
+        assert_eq!(ControlCommand::parse("config_unset big_mode"), Ok(ControlCommand::ConfigUnset("big_mode".to_string())));
+        assert_eq!(ControlCommand::parse("prefs_reset"), Ok(ControlCommand::PrefsReset));
+        assert_eq!(ControlCommand::parse("state_export /host/notify.json"),
+            Ok(ControlCommand::StateExport("/host/notify.json".to_string())));
+        assert_eq!(ControlCommand::parse("state_import /host/notify.json"),
+            Ok(ControlCommand::StateImport("/host/notify.json".to_string())));
+        assert!(ControlCommand::parse("state_export").is_err());
+        assert_eq!(ControlCommand::parse("stress 500 100"), Ok(ControlCommand::Stress { count: 500, rate: 100 }));
+        assert_eq!(ControlCommand::parse("stress stop"), Ok(ControlCommand::StressStop));
+        assert!(ControlCommand::parse("stress 500 0").is_err());
+        assert!(ControlCommand::parse("stress 500").is_err());
+        assert_eq!(ControlCommand::parse("ack_type Error"), Ok(ControlCommand::AckType(NotificationType::Error)));
+        assert_eq!(ControlCommand::parse("list"), Ok(ControlCommand::List(HistoryQuery::default())));
+        assert_eq!(ControlCommand::parse("explain"), Ok(ControlCommand::Explain(None)));
+        assert_eq!(ControlCommand::parse("big_mode"), Ok(ControlCommand::BigMode(None)));
+        assert_eq!(ControlCommand::parse("low_power on"), Ok(ControlCommand::LowPower(Some(true))));
+        assert_eq!(ControlCommand::parse("power battery"), Ok(ControlCommand::Power(PowerSource::Battery)));
+        assert_eq!(ControlCommand::parse("text_log off"), Ok(ControlCommand::TextLog(Some(false))));
+        assert!(ControlCommand::parse("text_log loud").is_err());
+        assert_eq!(ControlCommand::parse("sound_theme loud"), Ok(ControlCommand::SoundTheme(Some("loud".to_string()))));
+        assert_eq!(ControlCommand::parse("sound_theme"), Ok(ControlCommand::SoundTheme(None)));
+        assert_eq!(ControlCommand::parse("compact_history similar"), Ok(ControlCommand::CompactHistory(Some(HistoryCompaction::Similar))));
+        assert!(ControlCommand::parse("compact_history off").is_err());
+        assert_eq!(ControlCommand::parse("reply 2"), Ok(ControlCommand::Reply { number: 2, pane_id: None }));
+        assert_eq!(ControlCommand::parse("reply 1 7"), Ok(ControlCommand::Reply { number: 1, pane_id: Some(7) }));
+        assert!(ControlCommand::parse("reply 0").is_err());
+        assert_eq!(ControlCommand::parse("health"), Ok(ControlCommand::Health));
+        assert_eq!(ControlCommand::parse("features"), Ok(ControlCommand::Features));
+        assert_eq!(ControlCommand::parse("lifecycle"), Ok(ControlCommand::Lifecycle));
+        assert_eq!(ControlCommand::parse("version"), Ok(ControlCommand::Version));
+        assert_eq!(ControlCommand::parse("tutorial"), Ok(ControlCommand::Tutorial));
+        assert_eq!(ControlCommand::parse("answer yes"), Ok(ControlCommand::Answer(Some(true))));
+        assert_eq!(ControlCommand::parse("answer"), Ok(ControlCommand::Answer(None)));
+        assert!(ControlCommand::parse("answer maybe").is_err());
+        assert_eq!(ControlCommand::parse("capabilities"), Ok(ControlCommand::Capabilities));
+        assert_eq!(ControlCommand::parse("stats"), Ok(ControlCommand::Stats));
+        assert_eq!(ControlCommand::parse("metrics"), Ok(ControlCommand::Metrics));
+        assert_eq!(ControlCommand::parse("timeline"), Ok(ControlCommand::Timeline { session_id: None, json: false }));
+        assert_eq!(
+            ControlCommand::parse("timeline abc format=json"),
+            Ok(ControlCommand::Timeline { session_id: Some("abc".to_string()), json: true })
+        );
+        assert!(ControlCommand::parse("timeline abc json").is_err());
+        assert_eq!(ControlCommand::parse("read 3"), Ok(ControlCommand::MarkRead(3)));
+        assert_eq!(ControlCommand::parse("dismiss 3"), Ok(ControlCommand::Dismiss(3)));
+        assert!(ControlCommand::parse("dismiss").is_err());
+        assert_eq!(ControlCommand::parse("request_permissions"), Ok(ControlCommand::RequestPermissions));
+        assert_eq!(ControlCommand::parse("big_mode off"), Ok(ControlCommand::BigMode(Some(false))));
+        assert!(ControlCommand::parse("big_mode huge").is_err());
+        assert_eq!(
+            ControlCommand::parse("surface toasts off"),
+            Ok(ControlCommand::Surface { surface: DisplaySurface::Toasts, enabled: Some(false) })
+        );
+        assert_eq!(
+            ControlCommand::parse("surface border"),
+            Ok(ControlCommand::Surface { surface: DisplaySurface::Border, enabled: None })
+        );
+        assert!(ControlCommand::parse("surface wallpaper off").is_err());
+        assert_eq!(ControlCommand::parse("channels"), Ok(ControlCommand::Channels));
+        assert_eq!(ControlCommand::parse("groups"), Ok(ControlCommand::Groups));
+        assert_eq!(ControlCommand::parse("sessions"), Ok(ControlCommand::Sessions));
+        assert_eq!(ControlCommand::parse("slo"), Ok(ControlCommand::Slo));
+        assert_eq!(ControlCommand::parse("reminders"), Ok(ControlCommand::Reminders));
+        assert_eq!(
+            ControlCommand::parse("channel ci hide"),
+            Ok(ControlCommand::Channel { name: "ci".to_string(), visible: Some(false) })
+        );
+        assert!(ControlCommand::parse("channel ci maybe").is_err());
+        assert_eq!(ControlCommand::parse("simulate focus 4"), Ok(ControlCommand::Simulate(SimulatedEvent::Focus(4))));
+        assert_eq!(
+            ControlCommand::parse("simulate pane_close 4"),
+            Ok(ControlCommand::Simulate(SimulatedEvent::PaneClose(4)))
+        );
+        assert_eq!(ControlCommand::parse("explain n1"), Ok(ControlCommand::Explain(Some("n1".to_string()))));
+        assert_eq!(ControlCommand::parse("debug_pane 3"), Ok(ControlCommand::DebugPane(3)));
+        assert!(ControlCommand::parse("debug_pane").is_err());
+        assert!(ControlCommand::parse("debug_pane three").is_err());
+        assert_eq!(ControlCommand::parse("replay last 5"), Ok(ControlCommand::Replay(ReplayTarget::Last(5))));
+        assert!(ControlCommand::parse("replay").is_err());
+        assert_eq!(
+            ControlCommand::parse("set reduced_motion true"),
+            Ok(ControlCommand::Set { setting: "reduced_motion".to_string(), enabled: true })
+        );
+    }
+
+    #[test]
+    fn test_parse_command_errors() {
+        assert!(ControlCommand::parse("").is_err());
+        assert!(ControlCommand::parse("mute_pane").is_err());
+        assert!(ControlCommand::parse("mute_pane abc").is_err());
+        assert!(ControlCommand::parse("explode").is_err());
+        assert!(ControlCommand::parse("snooze").is_err());
+        assert!(ControlCommand::parse("snooze 0").is_err());
+        assert!(ControlCommand::parse("theme_set success_color").is_err());
+        assert!(ControlCommand::parse("config_set big_mode").is_err());
+        assert!(ControlCommand::parse("ack_type").is_err());
+        assert!(ControlCommand::parse("ack_type bogus").is_err());
+        assert!(ControlCommand::parse("list colour=red").is_err());
+        assert!(ControlCommand::parse("set reduced_motion maybe").is_err());
+        assert!(ControlCommand::parse("simulate resize 4").is_err());
+        assert!(ControlCommand::parse("simulate focus").is_err());
+        assert!(ControlCommand::parse("power").is_err());
+        assert!(ControlCommand::parse("power solar").is_err());
+    }
+}
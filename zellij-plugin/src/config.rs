@@ -3,7 +3,42 @@
 //! Handles KDL configuration parsing, validation, and hot-reload functionality.
 
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use crate::actions::DEFAULT_ACTION_RATE_LIMIT;
+use crate::attention::{AttentionPolicies, AttentionReason, ATTENTION_REASONS};
+use crate::attention_queue::AttentionQueue;
+use crate::claude_panes::{DEFAULT_CLAUDE_PANE_COLOR, DEFAULT_CLAUDE_PANE_PATTERNS};
+use crate::history::DEFAULT_HISTORY_MAX;
+use crate::host::{HostPlatform, DEFAULT_TEMP_DIR};
+use crate::notification::NotificationType;
+use crate::payload::{OversizePolicy, DEFAULT_MAX_PAYLOAD_BYTES};
+use crate::queue::DequeueStrategy;
+use crate::passive::{
+    DEFAULT_PASSIVE_MAX_TITLE_CHANGES, DEFAULT_PASSIVE_MIN_DURATION_MS, DEFAULT_PASSIVE_SHELLS, DEFAULT_PASSIVE_TEMPLATE,
+};
+use crate::latency::DEFAULT_LATENCY_WARN_MS;
+use crate::profile::DEFAULT_FRAME_TIME_WARN_MS;
+use crate::raw_ansi::{RawAnsi, RawAnsiConfig};
+use crate::automation::Automation;
+use crate::autofocus::DEFAULT_AUTO_FOCUS_DELAY_MS;
+use crate::charset::AsciiMode;
+use crate::exitcode::ExitCodeColor;
+use crate::icons::IconWidthPolicy;
+use crate::ingest::{DEFAULT_INGEST_FILE_POLL_MS, MIN_INGEST_FILE_POLL_MS};
+use crate::checks::Check;
+use crate::chord::{self, DEFAULT_CHORD_LEADER};
+use crate::remap::TypeRemap;
+use crate::rules::Rule;
+use crate::recent::DEFAULT_RECENT_ACK_WINDOW_MS;
+use crate::renderer::{DEFAULT_COMPACT_LAYOUT_MIN_COLS, DEFAULT_STATUS_SEGMENTS, DEFAULT_WIDE_LAYOUT_MIN_COLS};
+use crate::pending_panes::DEFAULT_MISSING_PANE_HOLD_MS;
+use crate::power::{DEFAULT_LOW_POWER_TICK_MS, LOW_POWER_TICK_MS_RANGE};
+use crate::sequence::DEFAULT_SEQUENCE_HOLD_MS;
+use crate::sinks::WEBHOOK_LIFECYCLE_EVENTS;
+use crate::snapshot::MAX_SNAPSHOT_LINES;
+use crate::sound::{theme_names, SoundTheme};
+use crate::sources::DEFAULT_NOISY_SOURCE_THRESHOLD;
+use crate::unread::DEFAULT_UNREAD_COUNT_PATH;
 
 /// Main plugin configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,22 +47,240 @@ pub struct Config {
     pub enabled: bool,
     /// Theme configuration
     pub theme: ThemeConfig,
+    /// External KDL theme file replacing the theme (re-read on config reload)
+    pub theme_file: Option<String>,
     /// Animation configuration
     pub animation: AnimationConfig,
     /// Accessibility configuration
     pub accessibility: AccessibilityConfig,
+    /// Presentation policies per Claude attention reason
+    pub attention: AttentionPolicies,
+    /// Surface waiting Attention notifications one at a time (`off`, `oldest`, `priority`)
+    pub attention_queue: AttentionQueue,
     /// Notification timeout in milliseconds
     pub notification_timeout_ms: u64,
+    /// Time the `e` key adds to the expiry of the newest notification (ms)
+    pub ttl_extend_ms: u64,
+    /// Pause the timeout of notifications whose pane or tab is not visible
+    pub pause_ttl_in_background: bool,
+    /// Time a shown notification keeps its pane before a replacement of no higher priority, by type name (ms)
+    pub min_display_ms: BTreeMap<String, u64>,
+    /// Order notifications in the active tab one priority step up, and those in hidden tabs one down
+    pub focused_tab_boost: bool,
     /// Maximum queue size
     pub queue_max_size: usize,
+    /// Maximum total bytes of queued notification content
+    pub queue_max_bytes: usize,
+    /// Maximum notifications queued across all priority levels
+    pub queue_max_total: usize,
+    /// Largest pipe payload accepted as is (bytes, 0: no cap)
+    pub max_payload_bytes: usize,
+    /// What happens to larger payloads (`truncate` or `reject`)
+    pub oversize_payload: OversizePolicy,
+    /// Queue fill percentage above which Low/Normal notifications are merged or rejected
+    pub queue_high_water_percent: u8,
+    /// Order notifications of the same priority leave the queue in (`fifo` or `fair`)
+    pub queue_strategy: DequeueStrategy,
+    /// Notifications shown per second from the queue (0 = unlimited)
+    pub display_rate: u32,
+    /// Maximum retained state transitions (debug history)
+    pub transition_history_max: usize,
+    /// Maximum notifications kept for the `list` command
+    pub history_max: usize,
+    /// How eagerly repeated history entries are merged in the background
+    pub history_compaction: HistoryCompaction,
     /// Enable status bar widget
     pub show_status_bar: bool,
     /// Enable pane border colors
     pub show_border_colors: bool,
     /// Enable tab badges
     pub show_tab_badges: bool,
+    /// Order of notification entries in the status bar
+    pub status_order: StatusOrder,
+    /// Pane width from which status entries are laid out as a multi-column table (0 = never)
+    pub wide_layout_min_cols: usize,
+    /// Pane width from which status entries are listed; narrower panes show the icon and count (0 = always list)
+    pub compact_layout_min_cols: usize,
+    /// Number the first nine status entries; digit keys acknowledge the entry with that number
+    pub entry_hotkeys: bool,
+    /// Draw status entries as chips: a tint of the notification color behind a contrasting text
+    /// color, instead of brackets
+    pub entry_chips: bool,
+    /// When the pane is drawn with the pure-ASCII fallback set instead of Unicode glyphs
+    pub ascii_only: AsciiMode,
+    /// Which icon glyphs are replaced by verified single-width alternatives
+    pub icon_width_policy: IconWidthPolicy,
+    /// Status bar segments, in order (see `renderer::DEFAULT_STATUS_SEGMENTS`)
+    pub status_segments: Vec<String>,
+    /// Raw ANSI prefixes and suffixes around segments and entries of notification types
+    pub raw_ansi: RawAnsiConfig,
+    /// Prefix the target pane title with the notification icon when the pane is collapsed
+    pub collapsed_title_badge: bool,
+    /// Allow senders to override colors and icons per notification
+    pub allow_sender_overrides: bool,
+    /// Ask for confirmation before clearing all notifications (Ctrl+n)
+    pub clear_all_confirm: bool,
+    /// Leader key of the action chord (`Ctrl y`, empty or `off` to disable)
+    pub chord_leader: String,
+    /// Log Zellij actions (renames, focus, commands, panes, web requests) instead of taking them
+    pub actions_dry_run: bool,
+    /// Zellij actions of one kind allowed per second (0 = unlimited)
+    pub action_rate_limit: u32,
+    /// Enable idle detection (escalate Attention when away, defer while busy)
+    pub idle_detection: bool,
+    /// Time without activity after which the user is considered idle (ms)
+    pub idle_threshold_ms: u64,
+    /// Pause after activity before Attention interruptions are shown (ms)
+    pub typing_pause_ms: u64,
+    /// What happens to notifications for the pane the user is focused on
+    pub suppress_for_focused_pane: FocusedPaneSuppression,
+    /// What happens to notifications for panes the layout does not have
+    pub missing_pane: MissingPane,
+    /// How long `hold` waits for a missing pane to appear before dropping (ms)
+    pub missing_pane_hold_ms: u64,
+    /// Ordered list of notification sinks (visual, bell, webhook, desktop, desktop_daemon, screen_reader)
+    pub sinks: Vec<String>,
+    /// Webhook URL for the webhook sink
+    pub webhook_url: Option<String>,
+    /// Lifecycle events the webhook sink also POSTs (display, ack, expire)
+    pub webhook_events: Vec<String>,
+    /// Zellij session the forward sink pipes notifications to
+    pub forward_session: Option<String>,
+    /// Host helper the desktop_daemon sink shows and closes desktop copies with
+    pub desktop_daemon_helper: Option<String>,
+    /// Keep notifications forwarded from other sessions grouped by session
+    pub aggregator: bool,
+    /// Maximum delivery attempts per webhook request
+    pub webhook_max_attempts: u32,
+    /// Delay before the first webhook retry, doubled per further retry (ms)
+    pub webhook_retry_base_ms: u64,
+    /// Re-deliver Critical notifications unacknowledged for this long out of terminal (ms, 0 = off)
+    pub ack_escalation_ms: u64,
+    /// Length of a focus period started without a duration (`f` key, `focus` command, minutes)
+    pub focus_minutes: u64,
+    /// How long a numbered notification waits for a missing predecessor before the gap is skipped (ms)
+    pub sequence_hold_ms: u64,
+    /// Update or render time above which a frame counts as slow and is logged (ms, 0 = off)
+    pub frame_time_warn_ms: u64,
+    /// Time from sending to display above which a notification is logged as late (ms, 0 = off)
+    pub latency_warn_ms: u64,
+    /// Lines of the pane's screen attached to Error notifications for that pane (0 = off)
+    pub capture_error_lines: usize,
+    /// Explanations of command exit codes, added to or replacing the built-in ones
+    pub exit_codes: BTreeMap<i32, String>,
+    /// Colors of exit code ranges for notifications with an exit code, first match wins
+    pub exit_code_colors: Vec<ExitCodeColor>,
+    /// How long an acknowledged message is remembered per pane; repeats within it are dimmed (ms, 0 = off)
+    pub recent_ack_window_ms: u64,
+    /// Time within which notifications should be acknowledged, by type name (ms)
+    pub slo: BTreeMap<String, u64>,
+    /// Escalate notifications that breach their SLO out of terminal (desktop, webhook)
+    pub slo_escalate: bool,
+    /// Type changes per source, applied before the priority is derived
+    pub remaps: Vec<TypeRemap>,
+    /// User rules adjusting notifications as they arrive, in order
+    pub rules: Vec<Rule>,
+    /// Only record rule matches in the audit trail, without applying their actions
+    pub rules_dry_run: bool,
+    /// Notifications per minute above which a source is suggested for muting (0 = off)
+    pub noisy_source_threshold: u32,
+    /// Periodic host commands whose results raise notifications
+    pub checks: Vec<Check>,
+    /// Zellij actions run when matching notifications are queued
+    pub on_notify: Vec<Automation>,
+    /// Notification types that move focus to their pane, by type name
+    pub auto_focus: BTreeMap<String, bool>,
+    /// Countdown before an auto-focus moves focus (ms)
+    pub auto_focus_delay_ms: u64,
+    /// Show notification badges in tab names
+    pub tab_name_badges: bool,
+    /// Placement of badges in tab names
+    pub tab_badge_placement: TabBadgePlacement,
+    /// Separator between badge and tab name
+    pub tab_badge_separator: String,
+    /// Maximum badge width in tab names (characters)
+    pub tab_badge_max_width: usize,
+    /// Corner for floating toasts (toast sink)
+    pub toast_position: ToastPosition,
+    /// Toast width (columns)
+    pub toast_width: usize,
+    /// Toast height (rows)
+    pub toast_height: usize,
+    /// How long a toast stays open (ms)
+    pub toast_duration_ms: u64,
+    /// Maximum simultaneous toasts
+    pub toast_max_visible: usize,
+    /// Sound theme of the sound sink (built-in `subtle`, `loud`, or a configured pack)
+    pub sound_theme: String,
+    /// Command playing sound files, the file appended as last argument
+    pub sound_player: String,
+    /// Configured sound packs: sound file or shell command by type name, by pack name
+    pub sound_packs: BTreeMap<String, BTreeMap<String, String>>,
+    /// Mirror the aggregate notification state into the window title or session name
+    pub title_mirror: TitleMirror,
+    /// Show a compact notification summary in the plugin's own pane title
+    pub pane_title_summary: bool,
+    /// Enlarged, high contrast status view for presentations and screen sharing
+    pub big_mode: bool,
+    /// Plain text log of the notifications instead of the status view (None: on with `screen_reader`)
+    pub text_log: Option<bool>,
+    /// Show the version banner for a few seconds after load
+    pub startup_banner: bool,
+    /// Experimental features switched on (see `features`)
+    pub experimental_features: Vec<String>,
+    /// Read-only companion view: shows broadcast notifications, takes no keys or commands
+    pub mirror: bool,
+    /// Let only one of several instances on the same pipes run the sinks (the others render only)
+    pub instance_election: bool,
+    /// File whose appended JSON lines are ingested as notifications
+    pub ingest_file: Option<String>,
+    /// Time between two reads of `ingest_file` (ms)
+    pub ingest_file_poll_ms: u64,
+    /// Low power mode: slow timer, static emphasis instead of animations, spaced-out scans
+    pub low_power: bool,
+    /// Timer interval in low power mode (ms)
+    pub low_power_tick_ms: u64,
+    /// Frame the plugin pane with an animated ring while a Critical notification is active
+    pub focus_ring: bool,
+    /// Channels listed in the channel view from the start (others appear on first use)
+    pub channels: Vec<String>,
+    /// Channels whose entries are hidden at startup (still counted)
+    pub hidden_channels: Vec<String>,
+    /// Directories of the current project(s); notifications with a `cwd` outside all of them are
+    /// collapsed into an `other projects` count (empty = no filtering)
+    pub project_roots: Vec<String>,
+    /// Write the unread count to a host file for external tooling
+    pub unread_count_file: bool,
+    /// Unread count file path (`{session}` is replaced by the session name)
+    pub unread_count_path: String,
+    /// Report commands finishing in panes from their titles reverting to the shell
+    pub passive_detection: bool,
+    /// Completion message (`{command}`, `{title}`, `{shell}`, `{duration}`)
+    pub passive_template: String,
+    /// Shortest command run reported (ms)
+    pub passive_min_duration_ms: u64,
+    /// Title changes within 10 seconds above which a pane's titles are ignored (0: no limit)
+    pub passive_max_title_changes: usize,
+    /// Shell names a title reverts to
+    pub passive_shells: Vec<String>,
+    /// Patterns in a pane's title or command marking it as a Claude pane (empty = none)
+    pub claude_pane_patterns: Vec<String>,
+    /// Accent of Claude pane entries
+    pub claude_pane_color: String,
+    /// Pipe names this instance listens on for broadcast messages (empty = any)
+    pub pipes: Vec<String>,
+    /// Ignore broadcast messages on pipe names not in `pipes`
+    pub strict_pipes: bool,
+    /// Check once a day whether a newer release is out (never installs it)
+    pub update_check: bool,
+    /// Host command printing the latest release JSON, in place of `curl` to GitHub
+    pub update_check_helper: Option<String>,
     /// IPC socket path (for external communication)
     pub ipc_socket_path: Option<String>,
+    /// Operating system Zellij runs on (path style, available host features)
+    pub host_platform: HostPlatform,
+    /// Directory for temporary files on the host (pane snapshots, `{tmp}` in paths)
+    pub temp_dir: String,
     /// Debug mode
     pub debug: bool,
 }
@@ -37,14 +290,122 @@ impl Default for Config {
         Self {
             enabled: true,
             theme: ThemeConfig::default(),
+            theme_file: None,
             animation: AnimationConfig::default(),
             accessibility: AccessibilityConfig::default(),
+            attention: AttentionPolicies::default(),
+            attention_queue: AttentionQueue::Off,
             notification_timeout_ms: 300_000, // 5 minutes
+            ttl_extend_ms: 300_000,
+            pause_ttl_in_background: false,
+            min_display_ms: BTreeMap::new(),
+            focused_tab_boost: true,
             queue_max_size: 100,
+            queue_max_bytes: 1024 * 1024, // 1 MiB
+            max_payload_bytes: DEFAULT_MAX_PAYLOAD_BYTES,
+            oversize_payload: OversizePolicy::Truncate,
+            queue_max_total: 400,
+            queue_high_water_percent: 80,
+            queue_strategy: DequeueStrategy::Fifo,
+            display_rate: 10,
+            transition_history_max: 100,
+            history_max: DEFAULT_HISTORY_MAX,
+            history_compaction: HistoryCompaction::Off,
             show_status_bar: true,
             show_border_colors: true,
             show_tab_badges: true,
+            status_order: StatusOrder::Urgency,
+            wide_layout_min_cols: DEFAULT_WIDE_LAYOUT_MIN_COLS,
+            compact_layout_min_cols: DEFAULT_COMPACT_LAYOUT_MIN_COLS,
+            entry_hotkeys: false,
+            entry_chips: false,
+            ascii_only: AsciiMode::Auto,
+            icon_width_policy: IconWidthPolicy::Any,
+            status_segments: DEFAULT_STATUS_SEGMENTS.iter().map(|name| name.to_string()).collect(),
+            raw_ansi: RawAnsiConfig::default(),
+            collapsed_title_badge: false,
+            allow_sender_overrides: true,
+            clear_all_confirm: true,
+            chord_leader: DEFAULT_CHORD_LEADER.to_string(),
+            actions_dry_run: false,
+            action_rate_limit: DEFAULT_ACTION_RATE_LIMIT,
+            idle_detection: true,
+            idle_threshold_ms: 300_000, // 5 minutes
+            typing_pause_ms: 1500,
+            suppress_for_focused_pane: FocusedPaneSuppression::Off,
+            missing_pane: MissingPane::Hold,
+            missing_pane_hold_ms: DEFAULT_MISSING_PANE_HOLD_MS,
+            sinks: vec!["visual".to_string(), "bell".to_string()],
+            webhook_url: None,
+            webhook_events: Vec::new(),
+            forward_session: None,
+            desktop_daemon_helper: None,
+            aggregator: false,
+            webhook_max_attempts: 5,
+            webhook_retry_base_ms: 1000,
+            ack_escalation_ms: 0,
+            focus_minutes: 25,
+            sequence_hold_ms: DEFAULT_SEQUENCE_HOLD_MS,
+            frame_time_warn_ms: DEFAULT_FRAME_TIME_WARN_MS,
+            latency_warn_ms: DEFAULT_LATENCY_WARN_MS,
+            capture_error_lines: 0,
+            exit_codes: BTreeMap::new(),
+            exit_code_colors: Vec::new(),
+            recent_ack_window_ms: DEFAULT_RECENT_ACK_WINDOW_MS,
+            slo: BTreeMap::new(),
+            slo_escalate: false,
+            remaps: Vec::new(),
+            rules: Vec::new(),
+            rules_dry_run: false,
+            noisy_source_threshold: DEFAULT_NOISY_SOURCE_THRESHOLD,
+            checks: Vec::new(),
+            on_notify: Vec::new(),
+            auto_focus: BTreeMap::new(),
+            auto_focus_delay_ms: DEFAULT_AUTO_FOCUS_DELAY_MS,
+            tab_name_badges: false,
+            tab_badge_placement: TabBadgePlacement::Suffix,
+            tab_badge_separator: " ".to_string(),
+            tab_badge_max_width: 8,
+            toast_position: ToastPosition::TopRight,
+            toast_width: 40,
+            toast_height: 4,
+            toast_duration_ms: 5000,
+            toast_max_visible: 3,
+            sound_theme: "subtle".to_string(),
+            sound_player: "paplay".to_string(),
+            sound_packs: BTreeMap::new(),
+            title_mirror: TitleMirror::Off,
+            pane_title_summary: true,
+            big_mode: false,
+            text_log: None,
+            startup_banner: true,
+            experimental_features: Vec::new(),
+            mirror: false,
+            instance_election: true,
+            ingest_file: None,
+            ingest_file_poll_ms: DEFAULT_INGEST_FILE_POLL_MS,
+            low_power: false,
+            low_power_tick_ms: DEFAULT_LOW_POWER_TICK_MS,
+            focus_ring: true,
+            channels: Vec::new(),
+            hidden_channels: Vec::new(),
+            project_roots: Vec::new(),
+            unread_count_file: false,
+            unread_count_path: DEFAULT_UNREAD_COUNT_PATH.to_string(),
+            passive_detection: false,
+            passive_template: DEFAULT_PASSIVE_TEMPLATE.to_string(),
+            passive_min_duration_ms: DEFAULT_PASSIVE_MIN_DURATION_MS,
+            passive_max_title_changes: DEFAULT_PASSIVE_MAX_TITLE_CHANGES,
+            passive_shells: DEFAULT_PASSIVE_SHELLS.iter().map(|shell| shell.to_string()).collect(),
+            claude_pane_patterns: DEFAULT_CLAUDE_PANE_PATTERNS.iter().map(|pattern| pattern.to_string()).collect(),
+            claude_pane_color: DEFAULT_CLAUDE_PANE_COLOR.to_string(),
+            pipes: Vec::new(),
+            strict_pipes: false,
+            update_check: false,
+            update_check_helper: None,
             ipc_socket_path: None,
+            host_platform: HostPlatform::Unix,
+            temp_dir: DEFAULT_TEMP_DIR.to_string(),
             debug: false,
         }
     }
@@ -54,6 +415,13 @@ impl Config {
     /// Create configuration from Zellij plugin configuration map
     pub fn from_plugin_config(config_map: &BTreeMap<String, String>) -> Self {
         let mut config = Config::default();
+        config.apply_plugin_config(config_map);
+        config
+    }
+
+    /// Apply plugin configuration keys over this configuration (keys not in the map keep their value)
+    pub fn apply_plugin_config(&mut self, config_map: &BTreeMap<String, String>) {
+        let config = self;
 
         // Parse boolean options
         if let Some(enabled) = config_map.get("enabled") {
@@ -71,14 +439,120 @@ impl Config {
         if let Some(show_tab_badges) = config_map.get("show_tab_badges") {
             config.show_tab_badges = show_tab_badges.parse().unwrap_or(true);
         }
+        if let Some(status_order) = config_map.get("status_order") {
+            config.status_order = StatusOrder::from_str(status_order);
+        }
+        if let Some(cols) = config_map.get("wide_layout_min_cols") {
+            config.wide_layout_min_cols = cols.parse().unwrap_or(DEFAULT_WIDE_LAYOUT_MIN_COLS);
+        }
+        if let Some(cols) = config_map.get("compact_layout_min_cols") {
+            config.compact_layout_min_cols = cols.parse().unwrap_or(DEFAULT_COMPACT_LAYOUT_MIN_COLS);
+        }
+        if let Some(hotkeys) = config_map.get("entry_hotkeys") {
+            config.entry_hotkeys = hotkeys.parse().unwrap_or(false);
+        }
+        if let Some(chips) = config_map.get("entry_chips") {
+            config.entry_chips = chips.parse().unwrap_or(false);
+        }
+        if let Some(ascii_only) = config_map.get("ascii_only") {
+            config.ascii_only = AsciiMode::from_str(ascii_only);
+        }
+        if let Some(policy) = config_map.get("icon_width_policy") {
+            config.icon_width_policy = IconWidthPolicy::from_str(policy);
+        }
+        if let Some(title_badge) = config_map.get("collapsed_title_badge") {
+            config.collapsed_title_badge = title_badge.parse().unwrap_or(false);
+        }
+        if let Some(allow_overrides) = config_map.get("allow_sender_overrides") {
+            config.allow_sender_overrides = allow_overrides.parse().unwrap_or(true);
+        }
+        if let Some(clear_all_confirm) = config_map.get("clear_all_confirm") {
+            config.clear_all_confirm = clear_all_confirm.parse().unwrap_or(true);
+        }
+        if let Some(leader) = config_map.get("chord_leader") {
+            config.chord_leader = leader.clone();
+        }
+        if let Some(dry_run) = config_map.get("actions_dry_run") {
+            config.actions_dry_run = dry_run.parse().unwrap_or(false);
+        }
+        if let Some(limit) = config_map.get("action_rate_limit") {
+            config.action_rate_limit = limit.parse().unwrap_or(DEFAULT_ACTION_RATE_LIMIT);
+        }
 
         // Parse numeric options
         if let Some(timeout) = config_map.get("notification_timeout_ms") {
             config.notification_timeout_ms = timeout.parse().unwrap_or(300_000);
         }
+        if let Some(extend) = config_map.get("ttl_extend_ms") {
+            config.ttl_extend_ms = extend.parse().unwrap_or(300_000);
+        }
+        if let Some(pause) = config_map.get("pause_ttl_in_background") {
+            config.pause_ttl_in_background = pause.parse().unwrap_or(false);
+        }
+        // Minimum display times per type, e.g. min_display_ms_success "3000"
+        for (key, value) in config_map.iter() {
+            if let Some(type_name) = key.strip_prefix("min_display_ms_") {
+                if let Ok(min_display_ms) = value.parse() {
+                    config.min_display_ms.insert(type_name.to_string(), min_display_ms);
+                }
+            }
+        }
+        if let Some(boost) = config_map.get("focused_tab_boost") {
+            config.focused_tab_boost = boost.parse().unwrap_or(true);
+        }
         if let Some(max_size) = config_map.get("queue_max_size") {
             config.queue_max_size = max_size.parse().unwrap_or(100);
         }
+        if let Some(max_bytes) = config_map.get("queue_max_bytes") {
+            config.queue_max_bytes = max_bytes.parse().unwrap_or(1024 * 1024);
+        }
+        if let Some(max_bytes) = config_map.get("max_payload_bytes") {
+            config.max_payload_bytes = max_bytes.parse().unwrap_or(DEFAULT_MAX_PAYLOAD_BYTES);
+        }
+        if let Some(policy) = config_map.get("oversize_payload") {
+            config.oversize_payload = OversizePolicy::from_str(policy);
+        }
+        if let Some(max_total) = config_map.get("queue_max_total") {
+            config.queue_max_total = max_total.parse().unwrap_or(400);
+        }
+        if let Some(high_water) = config_map.get("queue_high_water_percent") {
+            config.queue_high_water_percent = high_water.parse().unwrap_or(80);
+        }
+        if let Some(strategy) = config_map.get("queue_strategy") {
+            config.queue_strategy = DequeueStrategy::from_str(strategy);
+        }
+        if let Some(rate) = config_map.get("display_rate") {
+            config.display_rate = rate.parse().unwrap_or(10);
+        }
+        if let Some(history_max) = config_map.get("transition_history_max") {
+            config.transition_history_max = history_max.parse().unwrap_or(100);
+        }
+        if let Some(history_max) = config_map.get("history_max") {
+            config.history_max = history_max.parse().unwrap_or(DEFAULT_HISTORY_MAX);
+        }
+        if let Some(mode) = config_map.get("history_compaction") {
+            config.history_compaction = HistoryCompaction::from_str(mode);
+        }
+
+        // Parse idle detection settings
+        if let Some(idle_detection) = config_map.get("idle_detection") {
+            config.idle_detection = idle_detection.parse().unwrap_or(true);
+        }
+        if let Some(threshold) = config_map.get("idle_threshold_ms") {
+            config.idle_threshold_ms = threshold.parse().unwrap_or(300_000);
+        }
+        if let Some(pause) = config_map.get("typing_pause_ms") {
+            config.typing_pause_ms = pause.parse().unwrap_or(1500);
+        }
+        if let Some(mode) = config_map.get("suppress_for_focused_pane") {
+            config.suppress_for_focused_pane = FocusedPaneSuppression::from_str(mode);
+        }
+        if let Some(mode) = config_map.get("missing_pane") {
+            config.missing_pane = MissingPane::from_str(mode);
+        }
+        if let Some(hold) = config_map.get("missing_pane_hold_ms") {
+            config.missing_pane_hold_ms = hold.parse().unwrap_or(DEFAULT_MISSING_PANE_HOLD_MS);
+        }
 
         // Parse theme
         if let Some(theme_name) = config_map.get("theme") {
@@ -98,6 +572,14 @@ impl Config {
         if let Some(info_color) = config_map.get("info_color") {
             config.theme.info_color = info_color.clone();
         }
+        for field in ["background_color", "foreground_color", "highlight_color", "dimmed_color"] {
+            if let Some(color) = config_map.get(field) {
+                let _ = config.theme.set_color(field, color);
+            }
+        }
+        if let Some(theme_file) = config_map.get("theme_file") {
+            config.theme_file = Some(theme_file.clone());
+        }
 
         // Parse animation settings
         if let Some(animation_enabled) = config_map.get("animation_enabled") {
@@ -112,6 +594,39 @@ impl Config {
         if let Some(animation_cycles) = config_map.get("animation_cycles") {
             config.animation.cycles = animation_cycles.parse().unwrap_or(3);
         }
+        if let Some(tick_ms) = config_map.get("animation_tick_ms") {
+            config.animation.tick_ms = tick_ms.parse().unwrap_or(DEFAULT_TICK_MS)
+                .clamp(*TICK_MS_RANGE.start(), *TICK_MS_RANGE.end());
+        }
+        if let Some(interpolate) = config_map.get("animation_interpolate") {
+            config.animation.interpolate = interpolate.parse().unwrap_or(false);
+        }
+        if let Some(scaling) = config_map.get("animation_priority_scaling") {
+            config.animation.priority_scaling = scaling.parse().unwrap_or(true);
+        }
+        if let Some(target) = config_map.get("animation_target") {
+            config.animation.target = AnimationTarget::from_str(target);
+        }
+        if let Some(hidden_tabs) = config_map.get("animation_hidden_tabs") {
+            config.animation.hidden_tabs = HiddenTabAnimation::from_str(hidden_tabs);
+        }
+        // Gradient stops per type, e.g. gradient_error "#ef4444 #f97316 #ef4444"
+        for (key, value) in config_map.iter() {
+            if let Some(type_name) = key.strip_prefix("gradient_") {
+                config.animation.gradient_stops.insert(type_name.to_string(), parse_list(value));
+            }
+        }
+
+        // Attention reason policies, e.g. attention_permission "priority=critical style=flash"
+        for reason in ATTENTION_REASONS {
+            if let Some(settings) = config_map.get(&format!("attention_{}", reason.name())) {
+                // Settings up to the first invalid one apply
+                let _ = config.attention.get_mut(reason).apply(&parse_list(settings));
+            }
+        }
+        if let Some(mode) = config_map.get("attention_queue") {
+            config.attention_queue = AttentionQueue::from_str(mode);
+        }
 
         // Parse accessibility settings
         if let Some(high_contrast) = config_map.get("high_contrast") {
@@ -123,13 +638,357 @@ impl Config {
                 config.animation.enabled = false;
             }
         }
+        if let Some(no_color) = config_map.get("no_color") {
+            config.accessibility.no_color = no_color.parse().unwrap_or(false);
+        }
+        if let Some(use_patterns) = config_map.get("use_patterns") {
+            config.accessibility.use_patterns = use_patterns.parse().unwrap_or(true);
+        }
+
+        // Parse sinks (comma or space separated, in delivery order)
+        if let Some(sinks) = config_map.get("sinks") {
+            config.sinks = parse_list(sinks);
+        }
+        if let Some(segments) = config_map.get("segments") {
+            config.status_segments = parse_list(segments);
+        }
+        // Raw ANSI snippets, e.g. raw_ansi_type_error "prefix=\e[4:3m suffix=\e[24m"
+        for (key, value) in config_map.iter() {
+            let Some(target) = key.strip_prefix("raw_ansi_") else { continue };
+            if let (Some((kind, name)), Ok(raw)) = (target.split_once('_'), RawAnsi::parse(&parse_list(value))) {
+                let _ = config.raw_ansi.set(kind, name, raw);
+            }
+        }
+        if let Some(webhook_url) = config_map.get("webhook_url") {
+            config.webhook_url = Some(webhook_url.clone());
+        }
+        if let Some(events) = config_map.get("webhook_events") {
+            config.webhook_events = parse_list(events);
+        }
+        if let Some(session) = config_map.get("forward_session") {
+            config.forward_session = Some(session.clone()).filter(|session| !session.is_empty());
+        }
+        if let Some(helper) = config_map.get("desktop_daemon_helper") {
+            config.desktop_daemon_helper = Some(helper.clone()).filter(|helper| !helper.trim().is_empty());
+        }
+        if let Some(aggregator) = config_map.get("aggregator") {
+            config.aggregator = aggregator.parse().unwrap_or(false);
+        }
+        if let Some(max_attempts) = config_map.get("webhook_max_attempts") {
+            config.webhook_max_attempts = max_attempts.parse().unwrap_or(5);
+        }
+        if let Some(retry_base) = config_map.get("webhook_retry_base_ms") {
+            config.webhook_retry_base_ms = retry_base.parse().unwrap_or(1000);
+        }
+        if let Some(escalation) = config_map.get("ack_escalation_ms") {
+            config.ack_escalation_ms = escalation.parse().unwrap_or(0);
+        }
+        if let Some(minutes) = config_map.get("focus_minutes") {
+            config.focus_minutes = minutes.parse().unwrap_or(25).max(1);
+        }
+        if let Some(hold) = config_map.get("sequence_hold_ms") {
+            config.sequence_hold_ms = hold.parse().unwrap_or(DEFAULT_SEQUENCE_HOLD_MS);
+        }
+        if let Some(threshold) = config_map.get("frame_time_warn_ms") {
+            config.frame_time_warn_ms = threshold.parse().unwrap_or(DEFAULT_FRAME_TIME_WARN_MS);
+        }
+        if let Some(threshold) = config_map.get("latency_warn_ms") {
+            config.latency_warn_ms = threshold.parse().unwrap_or(DEFAULT_LATENCY_WARN_MS);
+        }
+        if let Some(window) = config_map.get("recent_ack_window_ms") {
+            config.recent_ack_window_ms = window.parse().unwrap_or(DEFAULT_RECENT_ACK_WINDOW_MS);
+        }
+        // Response-time targets per type, e.g. slo_attention "300000"
+        for (key, value) in config_map.iter() {
+            match key.strip_prefix("slo_") {
+                Some("escalate") => config.slo_escalate = value.parse().unwrap_or(false),
+                Some(type_name) => {
+                    if let Ok(target) = value.parse() {
+                        config.slo.insert(type_name.to_string(), target);
+                    }
+                }
+                None => {}
+            }
+        }
+        // Type remaps, e.g. remap_flaky-tool "error=warning"
+        for (key, value) in config_map.iter() {
+            if let Some(source) = key.strip_prefix("remap_") {
+                if let Ok(remaps) = TypeRemap::parse_spec(source, value) {
+                    config.remaps.extend(remaps);
+                }
+            }
+        }
+        // Rules, e.g. rule_quiet_ci "source=ci type=info -> priority=low" (applied in name order)
+        for (key, value) in config_map.iter() {
+            if let Some(name) = key.strip_prefix("rule_") {
+                if let Ok(rule) = Rule::parse_spec(name, value) {
+                    config.rules.push(rule);
+                }
+            }
+        }
+        if let Some(dry_run) = config_map.get("rules_dry_run") {
+            config.rules_dry_run = dry_run.parse().unwrap_or(false);
+        }
+        if let Some(threshold) = config_map.get("noisy_source_threshold") {
+            config.noisy_source_threshold = threshold.parse().unwrap_or(DEFAULT_NOISY_SOURCE_THRESHOLD);
+        }
+        // Checks, e.g. check_dirty "\"git status --porcelain\" every 5m notify-if-output warning \"uncommitted changes\""
+        for (key, value) in config_map.iter() {
+            if let Some(name) = key.strip_prefix("check_") {
+                if let Ok(check) = Check::parse_spec(name, value) {
+                    config.checks.push(check);
+                }
+            }
+        }
+        // Automations, e.g. on_notify_tests "type=error source=tests -> run \"cargo test\"" (in name order)
+        for (key, value) in config_map.iter() {
+            if let Some(name) = key.strip_prefix("on_notify_") {
+                if let Ok(automation) = Automation::parse_spec(name, value) {
+                    config.on_notify.push(automation);
+                }
+            }
+        }
+        // Auto-focus per type, e.g. auto_focus_attention "true"
+        for (key, value) in config_map.iter() {
+            match key.strip_prefix("auto_focus_") {
+                Some("delay_ms") => config.auto_focus_delay_ms = value.parse().unwrap_or(DEFAULT_AUTO_FOCUS_DELAY_MS),
+                Some(type_name) => {
+                    if let Ok(enabled) = value.parse() {
+                        config.auto_focus.insert(type_name.to_string(), enabled);
+                    }
+                }
+                None => {}
+            }
+        }
+        if let Some(lines) = config_map.get("capture_error_lines") {
+            config.capture_error_lines = lines.parse::<usize>().unwrap_or(0).min(MAX_SNAPSHOT_LINES);
+        }
+        // Exit code explanations, e.g. exit_code_3 "lint failed"
+        for (key, value) in config_map.iter() {
+            if let Some(Ok(code)) = key.strip_prefix("exit_code_").map(str::parse) {
+                config.exit_codes.insert(code, value.clone());
+            }
+        }
+        // Exit code colors, e.g. exit_code_colors "1:#ff6666, 2..=127:#ff0000, 128..:#cc0000"
+        if let Some(ranges) = config_map.get("exit_code_colors") {
+            config.exit_code_colors = parse_list(ranges).iter()
+                .filter_map(|entry| entry.split_once(':'))
+                .filter_map(|(range, color)| ExitCodeColor::parse(range, color).ok())
+                .collect();
+        }
+
+        // Parse tab name badge options
+        if let Some(tab_name_badges) = config_map.get("tab_name_badges") {
+            config.tab_name_badges = tab_name_badges.parse().unwrap_or(false);
+        }
+        if let Some(placement) = config_map.get("tab_badge_placement") {
+            config.tab_badge_placement = TabBadgePlacement::from_str(placement);
+        }
+        if let Some(separator) = config_map.get("tab_badge_separator") {
+            config.tab_badge_separator = separator.clone();
+        }
+        if let Some(max_width) = config_map.get("tab_badge_max_width") {
+            config.tab_badge_max_width = max_width.parse().unwrap_or(8);
+        }
+
+        // Parse toast options
+        if let Some(position) = config_map.get("toast_position") {
+            config.toast_position = ToastPosition::from_str(position);
+        }
+        if let Some(width) = config_map.get("toast_width") {
+            config.toast_width = width.parse().unwrap_or(40);
+        }
+        if let Some(height) = config_map.get("toast_height") {
+            config.toast_height = height.parse().unwrap_or(4);
+        }
+        if let Some(duration) = config_map.get("toast_duration_ms") {
+            config.toast_duration_ms = duration.parse().unwrap_or(5000);
+        }
+        if let Some(max_visible) = config_map.get("toast_max_visible") {
+            config.toast_max_visible = max_visible.parse().unwrap_or(3);
+        }
+
+        // Experimental features, e.g. experimental_features "toasts"
+        if let Some(features) = config_map.get("experimental_features") {
+            config.experimental_features = parse_list(features);
+        }
+
+        // Sound themes, e.g. sound_theme "mine" with sound_pack_mine_error "/path/to/fail.oga"
+        if let Some(theme) = config_map.get("sound_theme") {
+            config.sound_theme = theme.clone();
+        }
+        if let Some(player) = config_map.get("sound_player") {
+            config.sound_player = player.clone();
+        }
+        for (key, value) in config_map.iter() {
+            if let Some((pack, type_name)) = key.strip_prefix("sound_pack_").and_then(|rest| rest.rsplit_once('_')) {
+                config.sound_packs.entry(pack.to_string()).or_default().insert(type_name.to_string(), value.clone());
+            }
+        }
+
+        // Parse title mirroring
+        if let Some(title_mirror) = config_map.get("title_mirror") {
+            config.title_mirror = TitleMirror::from_str(title_mirror);
+        }
+        if let Some(pane_title_summary) = config_map.get("pane_title_summary") {
+            config.pane_title_summary = pane_title_summary.parse().unwrap_or(true);
+        }
+        if let Some(big_mode) = config_map.get("big_mode") {
+            config.big_mode = big_mode.parse().unwrap_or(false);
+        }
+        if let Some(text_log) = config_map.get("text_log") {
+            config.text_log = text_log.parse().ok();
+        }
+        if let Some(startup_banner) = config_map.get("startup_banner") {
+            config.startup_banner = startup_banner.parse().unwrap_or(true);
+        }
+        if let Some(mirror) = config_map.get("mirror") {
+            config.mirror = mirror.parse().unwrap_or(false);
+        }
+        if let Some(election) = config_map.get("instance_election") {
+            config.instance_election = election.parse().unwrap_or(true);
+        }
+        if let Some(path) = config_map.get("ingest_file") {
+            config.ingest_file = Some(path.clone()).filter(|path| !path.is_empty());
+        }
+        if let Some(poll_ms) = config_map.get("ingest_file_poll_ms") {
+            config.ingest_file_poll_ms = poll_ms.parse().unwrap_or(DEFAULT_INGEST_FILE_POLL_MS);
+        }
+        if let Some(low_power) = config_map.get("low_power") {
+            config.low_power = low_power.parse().unwrap_or(false);
+        }
+        if let Some(tick_ms) = config_map.get("low_power_tick_ms") {
+            config.low_power_tick_ms = tick_ms.parse().unwrap_or(DEFAULT_LOW_POWER_TICK_MS);
+        }
+        if let Some(focus_ring) = config_map.get("focus_ring") {
+            config.focus_ring = focus_ring.parse().unwrap_or(true);
+        }
+
+        // Parse channels (comma or space separated)
+        if let Some(channels) = config_map.get("channels") {
+            config.channels = parse_list(channels);
+        }
+        if let Some(hidden_channels) = config_map.get("hidden_channels") {
+            config.hidden_channels = parse_list(hidden_channels);
+        }
+        // Comma separated only: paths may contain spaces
+        if let Some(project_roots) = config_map.get("project_roots") {
+            config.project_roots = project_roots.split(',')
+                .map(str::trim)
+                .filter(|root| !root.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        // Parse unread count file
+        if let Some(unread_count_file) = config_map.get("unread_count_file") {
+            config.unread_count_file = unread_count_file.parse().unwrap_or(false);
+        }
+        if let Some(path) = config_map.get("unread_count_path") {
+            config.unread_count_path = path.clone();
+        }
+
+        // Parse passive detection
+        if let Some(passive_detection) = config_map.get("passive_detection") {
+            config.passive_detection = passive_detection.parse().unwrap_or(false);
+        }
+        if let Some(template) = config_map.get("passive_template") {
+            config.passive_template = template.clone();
+        }
+        if let Some(duration) = config_map.get("passive_min_duration_ms") {
+            config.passive_min_duration_ms = duration.parse().unwrap_or(DEFAULT_PASSIVE_MIN_DURATION_MS);
+        }
+        if let Some(changes) = config_map.get("passive_max_title_changes") {
+            config.passive_max_title_changes = changes.parse().unwrap_or(DEFAULT_PASSIVE_MAX_TITLE_CHANGES);
+        }
+        if let Some(shells) = config_map.get("passive_shells") {
+            config.passive_shells = parse_list(shells);
+        }
+
+        // Parse Claude pane detection
+        if let Some(patterns) = config_map.get("claude_pane_patterns") {
+            config.claude_pane_patterns = parse_list(patterns);
+        }
+        if let Some(color) = config_map.get("claude_pane_color") {
+            config.claude_pane_color = color.to_lowercase();
+        }
+
+        if let Some(pipes) = config_map.get("pipes") {
+            config.pipes = parse_list(pipes);
+        }
+        if let Some(strict) = config_map.get("strict_pipes") {
+            config.strict_pipes = strict.parse().unwrap_or(false);
+        }
+        if let Some(update_check) = config_map.get("update_check") {
+            config.update_check = update_check.parse().unwrap_or(false);
+        }
+        if let Some(helper) = config_map.get("update_check_helper") {
+            config.update_check_helper = Some(helper.clone()).filter(|helper| !helper.trim().is_empty());
+        }
 
         // Parse IPC socket path
         if let Some(ipc_path) = config_map.get("ipc_socket_path") {
             config.ipc_socket_path = Some(ipc_path.clone());
         }
 
-        config
+        // Parse host platform settings
+        if let Some(platform) = config_map.get("host_platform") {
+            config.host_platform = HostPlatform::from_str(platform);
+        }
+        if let Some(temp_dir) = config_map.get("temp_dir") {
+            config.temp_dir = temp_dir.clone();
+        }
+    }
+
+    /// Normalize the paths handed to host commands for the host platform (`{tmp}` is replaced
+    /// by the temporary directory)
+    pub fn normalize_host_paths(&mut self) {
+        self.temp_dir = self.host_platform.normalize_path(&self.temp_dir);
+        self.unread_count_path = self.host_path(&self.unread_count_path);
+        self.ipc_socket_path = self.ipc_socket_path.as_deref().map(|path| self.host_path(path));
+    }
+
+    /// A path as handed to host commands
+    fn host_path(&self, path: &str) -> String {
+        let temp_dir = self.host_platform.normalize_path(&self.temp_dir);
+        self.host_platform.normalize_path(&path.replace("{tmp}", &temp_dir))
+    }
+
+    /// Time a shown notification of a type keeps its pane (ms, 0 = none)
+    pub fn min_display_ms(&self, notification_type: &NotificationType) -> u64 {
+        self.min_display_ms.get(notification_type.name()).copied().unwrap_or(0)
+    }
+
+    /// Whether an experimental feature is switched on
+    pub fn experimental(&self, name: &str) -> bool {
+        self.experimental_features.iter().any(|feature| feature == name)
+    }
+
+    /// Enabled features that need a shell the host does not have
+    pub fn unavailable_features(&self) -> Vec<&'static str> {
+        if self.host_platform.has_shell() {
+            return Vec::new();
+        }
+        let mut features = Vec::new();
+        if self.unread_count_file {
+            features.push("unread_count_file");
+        }
+        if self.capture_error_lines > 0 {
+            features.push("capture_error_lines");
+        }
+        if self.sinks.iter().any(|sink| sink == "toast") && self.experimental("toasts") {
+            features.push("toast sink");
+        }
+        if self.sinks.iter().any(|sink| sink == "sound") && self.experimental("sounds") {
+            features.push("sound sink");
+        }
+        if !self.checks.is_empty() {
+            features.push("checks");
+        }
+        if self.update_check {
+            features.push("update_check");
+        }
+        features
     }
 
     /// Validate the configuration
@@ -140,16 +999,143 @@ impl Config {
         if self.queue_max_size < 1 {
             return Err("queue_max_size must be at least 1".to_string());
         }
+        if self.queue_max_bytes < 1024 {
+            return Err("queue_max_bytes must be at least 1024".to_string());
+        }
+        if self.toast_max_visible < 1 {
+            return Err("toast_max_visible must be at least 1".to_string());
+        }
+        if self.webhook_max_attempts < 1 {
+            return Err("webhook_max_attempts must be at least 1".to_string());
+        }
+        if let Some(event) = self.webhook_events.iter().find(|event| !WEBHOOK_LIFECYCLE_EVENTS.contains(&event.to_lowercase().as_str())) {
+            return Err(format!("unknown webhook event: {} (expected {})", event, WEBHOOK_LIFECYCLE_EVENTS.join(", ")));
+        }
+        if self.ingest_file_poll_ms < MIN_INGEST_FILE_POLL_MS {
+            return Err(format!("ingest_file_poll_ms must be at least {}ms", MIN_INGEST_FILE_POLL_MS));
+        }
+        if self.queue_max_total < 1 {
+            return Err("queue_max_total must be at least 1".to_string());
+        }
+        if self.queue_high_water_percent > 100 {
+            return Err("queue_high_water_percent must be at most 100".to_string());
+        }
+        if self.transition_history_max < 1 {
+            return Err("transition_history_max must be at least 1".to_string());
+        }
+        if !chord::is_off(&self.chord_leader) && chord::parse_leader(&self.chord_leader).is_none() {
+            return Err(format!("chord_leader must be a key such as \"{}\" (or off)", DEFAULT_CHORD_LEADER));
+        }
+        if !is_hex_color(&self.claude_pane_color) {
+            return Err("claude_pane_color must be #rrggbb".to_string());
+        }
+        for reason in ATTENTION_REASONS {
+            if let Some(color) = &self.attention.get(reason).color {
+                if !is_hex_color(color) {
+                    return Err(format!("attention_{} color must be #rrggbb", reason.name()));
+                }
+            }
+        }
+        if self.history_max < 1 {
+            return Err("history_max must be at least 1".to_string());
+        }
+        if self.strict_pipes && self.pipes.is_empty() {
+            return Err("strict_pipes needs at least one name in pipes".to_string());
+        }
+        if self.unread_count_file && !self.host_platform.is_absolute(&self.host_path(&self.unread_count_path)) {
+            return Err("unread_count_path must be an absolute path".to_string());
+        }
+        if !self.host_platform.is_absolute(&self.host_platform.normalize_path(&self.temp_dir)) {
+            return Err("temp_dir must be an absolute path".to_string());
+        }
         if self.animation.speed < 1 || self.animation.speed > 100 {
             return Err("animation_speed must be between 1 and 100".to_string());
         }
         if self.animation.cycles < 1 || self.animation.cycles > 10 {
             return Err("animation_cycles must be between 1 and 10".to_string());
         }
+        if !TICK_MS_RANGE.contains(&self.animation.tick_ms) {
+            return Err(format!("animation_tick_ms must be between {} and {}",
+                TICK_MS_RANGE.start(), TICK_MS_RANGE.end()));
+        }
+        if !LOW_POWER_TICK_MS_RANGE.contains(&self.low_power_tick_ms) {
+            return Err(format!("low_power_tick_ms must be between {} and {}",
+                LOW_POWER_TICK_MS_RANGE.start(), LOW_POWER_TICK_MS_RANGE.end()));
+        }
+        for (type_name, raw) in &self.raw_ansi.types {
+            if NotificationType::from_name(type_name).is_none() {
+                return Err(format!("raw_ansi: unknown notification type: {}", type_name));
+            }
+            raw.validate().map_err(|e| format!("raw_ansi type {}: {}", type_name, e))?;
+        }
+        for (segment, raw) in &self.raw_ansi.segments {
+            raw.validate().map_err(|e| format!("raw_ansi segment {}: {}", segment, e))?;
+        }
+        if SoundTheme::from_config(self).is_none() {
+            return Err(format!("unknown sound_theme: {} (available: {})",
+                self.sound_theme, theme_names(&self.sound_packs).join(", ")));
+        }
+        for (pack, sounds) in &self.sound_packs {
+            if let Some(type_name) = sounds.keys().find(|name| NotificationType::from_name(name).is_none()) {
+                return Err(format!("sound pack {}: unknown notification type: {}", pack, type_name));
+            }
+        }
+        for (type_name, target) in &self.slo {
+            if NotificationType::from_name(type_name).is_none() {
+                return Err(format!("slo: unknown notification type: {}", type_name));
+            }
+            if *target < 1000 {
+                return Err(format!("slo_{} must be at least 1000ms", type_name));
+            }
+        }
+        if let Some(type_name) = self.min_display_ms.keys().find(|name| NotificationType::from_name(name).is_none()) {
+            return Err(format!("min_display_ms: unknown notification type: {}", type_name));
+        }
+        if let Some(type_name) = self.auto_focus.keys().find(|name| NotificationType::from_name(name).is_none()) {
+            return Err(format!("auto_focus: unknown notification type: {}", type_name));
+        }
+        for (type_name, stops) in &self.animation.gradient_stops {
+            if !GRADIENT_TYPES.contains(&type_name.as_str()) {
+                return Err(format!("unknown gradient type: {}", type_name));
+            }
+            if stops.len() < 2 || !stops.iter().all(|stop| is_hex_color(stop)) {
+                return Err(format!("gradient_{} needs at least two #rrggbb stops", type_name));
+            }
+        }
         Ok(())
     }
 }
 
+/// Configuration values by dotted path (`animation.speed`, `slo.attention`)
+fn flatten_config(config: &Config) -> BTreeMap<String, serde_json::Value> {
+    fn flatten(prefix: &str, value: serde_json::Value, out: &mut BTreeMap<String, serde_json::Value>) {
+        match value {
+            serde_json::Value::Object(fields) if !fields.is_empty() => {
+                for (name, field) in fields {
+                    let key = if prefix.is_empty() { name } else { format!("{}.{}", prefix, name) };
+                    flatten(&key, field, out);
+                }
+            }
+            leaf => {
+                out.insert(prefix.to_string(), leaf);
+            }
+        }
+    }
+    let mut out = BTreeMap::new();
+    flatten("", serde_json::to_value(config).unwrap_or_default(), &mut out);
+    out
+}
+
+/// Parse a comma or whitespace separated list of values
+pub fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|s| s.trim().trim_matches('"'))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
 /// Theme configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
@@ -189,7 +1175,74 @@ impl Default for ThemeConfig {
     }
 }
 
+/// Theme color fields, in dump order
+pub const THEME_COLOR_FIELDS: [&str; 8] = [
+    "success_color",
+    "error_color",
+    "warning_color",
+    "info_color",
+    "background_color",
+    "foreground_color",
+    "highlight_color",
+    "dimmed_color",
+];
+
 impl ThemeConfig {
+    /// Get a color field by name
+    pub fn color(&self, field: &str) -> Option<&str> {
+        match field {
+            "success_color" => Some(&self.success_color),
+            "error_color" => Some(&self.error_color),
+            "warning_color" => Some(&self.warning_color),
+            "info_color" => Some(&self.info_color),
+            "background_color" => Some(&self.background_color),
+            "foreground_color" => Some(&self.foreground_color),
+            "highlight_color" => Some(&self.highlight_color),
+            "dimmed_color" => Some(&self.dimmed_color),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable color field by name
+    pub fn color_mut(&mut self, field: &str) -> Option<&mut String> {
+        match field {
+            "success_color" => Some(&mut self.success_color),
+            "error_color" => Some(&mut self.error_color),
+            "warning_color" => Some(&mut self.warning_color),
+            "info_color" => Some(&mut self.info_color),
+            "background_color" => Some(&mut self.background_color),
+            "foreground_color" => Some(&mut self.foreground_color),
+            "highlight_color" => Some(&mut self.highlight_color),
+            "dimmed_color" => Some(&mut self.dimmed_color),
+            _ => None,
+        }
+    }
+
+    /// Set a color field at runtime, validating the field name and `#rrggbb` value
+    pub fn set_color(&mut self, field: &str, value: &str) -> Result<(), String> {
+        if !is_hex_color(value) {
+            return Err(format!("invalid color for {}: {} (expected #rrggbb)", field, value));
+        }
+        match self.color_mut(field) {
+            Some(color) => {
+                *color = value.to_lowercase();
+                Ok(())
+            }
+            None => Err(format!("unknown theme field: {} (expected one of {})",
+                field, THEME_COLOR_FIELDS.join(", "))),
+        }
+    }
+
+    /// Render the effective theme as a KDL block for the plugin configuration
+    pub fn to_kdl(&self) -> String {
+        let mut kdl = format!("theme \"{}\" {{\n", self.name);
+        for field in THEME_COLOR_FIELDS.iter() {
+            kdl.push_str(&format!("    {} \"{}\"\n", field, self.color(field).unwrap_or_default()));
+        }
+        kdl.push_str("}\n");
+        kdl
+    }
+
     /// Create a theme from a preset name
     pub fn from_preset(name: &str) -> Self {
         match name.to_lowercase().as_str() {
@@ -358,6 +1411,90 @@ impl ThemeConfig {
     }
 }
 
+/// A theme loaded from an external KDL theme file
+///
+/// ```kdl
+/// theme "mytheme" {
+///     error_color "#ff5555"
+///     gradient "error" "#ff5555" "#ffb86c" "#ff5555"
+/// }
+/// ```
+///
+/// The `theme` argument picks the preset the file starts from; color fields override it and
+/// `gradient` nodes override the animation colors per notification type. The nodes may also be
+/// written at the top level without the `theme` wrapper.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeFile {
+    /// Theme colors
+    pub theme: ThemeConfig,
+    /// Per-type gradient stops
+    pub gradient_stops: BTreeMap<String, Vec<String>>,
+}
+
+impl ThemeFile {
+    /// Parse a theme file, rejecting unknown fields and invalid colors
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let doc: kdl::KdlDocument = content.parse()
+            .map_err(|e: kdl::KdlError| format!("KDL parse error: {}", e))?;
+        let mut theme_file = Self::default();
+        theme_file.parse_nodes(doc.nodes())?;
+        Ok(theme_file)
+    }
+
+    /// Load and parse a theme file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read theme file {}: {}", path, e))?;
+        Self::parse(&content)
+    }
+
+    /// Replace the configured theme and per-type gradients with this file's
+    pub fn apply(&self, config: &mut Config) {
+        config.theme = self.theme.clone();
+        for (type_name, stops) in &self.gradient_stops {
+            config.animation.gradient_stops.insert(type_name.clone(), stops.clone());
+        }
+    }
+
+    fn parse_nodes(&mut self, nodes: &[kdl::KdlNode]) -> Result<(), String> {
+        for node in nodes {
+            let name = node.name().value();
+            let args: Vec<String> = node.entries().iter()
+                .filter(|e| e.name().is_none())
+                .filter_map(|e| e.value().as_string().map(|s| s.to_string()))
+                .collect();
+            match name {
+                "theme" => {
+                    if let Some(preset) = args.first() {
+                        self.theme = ThemeConfig::from_preset(preset);
+                        self.theme.name = preset.clone();
+                    }
+                    if let Some(children) = node.children() {
+                        self.parse_nodes(children.nodes())?;
+                    }
+                }
+                "gradient" => {
+                    match args.split_first() {
+                        Some((type_name, stops)) if GRADIENT_TYPES.contains(&type_name.as_str()) => {
+                            if stops.len() < 2 || !stops.iter().all(|stop| is_hex_color(stop)) {
+                                return Err(format!("gradient {} needs at least two #rrggbb stops", type_name));
+                            }
+                            self.gradient_stops.insert(type_name.clone(), stops.to_vec());
+                        }
+                        _ => return Err(format!("gradient needs one of {} and its stops", GRADIENT_TYPES.join(", "))),
+                    }
+                }
+                field => {
+                    let value = args.first()
+                        .ok_or_else(|| format!("theme field {} needs a value", field))?;
+                    self.theme.set_color(field, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Animation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnimationConfig {
@@ -371,8 +1508,29 @@ pub struct AnimationConfig {
     pub cycles: u8,
     /// Duration in milliseconds
     pub duration_ms: u64,
+    /// Gradient stops (#rrggbb) per notification type name, for the Gradient style
+    #[serde(default)]
+    pub gradient_stops: BTreeMap<String, Vec<String>>,
+    /// Timer tick interval (ms); lower values animate at a higher frame rate
+    pub tick_ms: u64,
+    /// Compute animation frames from elapsed plugin time instead of whole ticks
+    pub interpolate: bool,
+    /// Scale cycles and depth by priority (Critical: 5 deep cycles, Low: 1 shallow one)
+    pub priority_scaling: bool,
+    /// What the animation modulates in status entries
+    #[serde(default)]
+    pub target: AnimationTarget,
+    /// What happens to animations of panes in tabs that are not shown
+    #[serde(default)]
+    pub hidden_tabs: HiddenTabAnimation,
 }
 
+/// Default timer tick interval (ms, 20fps); animation timing is defined in ticks of this length
+pub const DEFAULT_TICK_MS: u64 = 50;
+
+/// Accepted timer tick intervals (ms)
+pub const TICK_MS_RANGE: std::ops::RangeInclusive<u64> = 10..=200;
+
 impl Default for AnimationConfig {
     fn default() -> Self {
         Self {
@@ -381,10 +1539,20 @@ impl Default for AnimationConfig {
             speed: 50,
             cycles: 3,
             duration_ms: 2000,
+            gradient_stops: BTreeMap::new(),
+            tick_ms: DEFAULT_TICK_MS,
+            interpolate: false,
+            priority_scaling: true,
+            target: AnimationTarget::Foreground,
+            hidden_tabs: HiddenTabAnimation::Pause,
         }
     }
 }
 
+/// Notification type names accepted for gradient stops
+const GRADIENT_TYPES: [&str; 6] = ["success", "error", "warning", "info", "progress", "attention"];
+
+
 /// Animation styles
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AnimationStyle {
@@ -396,6 +1564,8 @@ pub enum AnimationStyle {
     Fade,
     /// Breathe animation (smooth sine wave)
     Breathe,
+    /// Gradient animation (color cycles through per-type gradient stops)
+    Gradient,
     /// None (static, no animation)
     None,
 }
@@ -414,12 +1584,282 @@ impl AnimationStyle {
             "flash" => Self::Flash,
             "fade" => Self::Fade,
             "breathe" => Self::Breathe,
+            "gradient" => Self::Gradient,
             "none" | "disabled" => Self::None,
             _ => Self::Pulse,
         }
     }
 }
 
+/// What the animation of a status entry modulates (borders always animate their color)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum AnimationTarget {
+    /// Text color
+    #[default]
+    Foreground,
+    /// Background fill behind the entry, text color kept
+    Background,
+    /// Text color and background fill
+    Both,
+    /// Inverse video on the bright half of each cycle
+    Inverse,
+}
+
+impl AnimationTarget {
+    /// Parse animation target from string (anything unknown is `foreground`)
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "background" | "bg" | "fill" => Self::Background,
+            "both" => Self::Both,
+            "inverse" | "reverse" | "flash" => Self::Inverse,
+            _ => Self::Foreground,
+        }
+    }
+}
+
+/// What happens to the animation of a pane in a tab that is not shown
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum HiddenTabAnimation {
+    /// Hold the animation and resume it where it stopped once the tab is shown
+    #[default]
+    Pause,
+    /// End the animation, leaving the final state
+    Skip,
+    /// Keep animating
+    Run,
+}
+
+impl HiddenTabAnimation {
+    /// Parse the hidden tab policy from string (anything unknown is `pause`)
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "skip" | "finish" => Self::Skip,
+            "run" | "animate" => Self::Run,
+            _ => Self::Pause,
+        }
+    }
+}
+
+/// Where the aggregate notification state is mirrored outside the plugin pane
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TitleMirror {
+    /// Do not mirror
+    Off,
+    /// Prefix the terminal window title via an OSC escape
+    Osc,
+}
+
+impl Default for TitleMirror {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl TitleMirror {
+    /// Parse title mirror mode from string
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            // Renaming the session broke `zellij --session` forwarding, so it means the window title
+            "osc" | "window" | "title" | "session" => Self::Osc,
+            _ => Self::Off,
+        }
+    }
+}
+
+/// Handling of notifications that target the focused pane
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum FocusedPaneSuppression {
+    /// Show them like any other notification
+    Off,
+    /// Deliver to the sinks, then acknowledge immediately (the user is already looking)
+    Acknowledge,
+    /// Record in history only
+    HistoryOnly,
+}
+
+impl Default for FocusedPaneSuppression {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl FocusedPaneSuppression {
+    /// Parse focused pane suppression mode from string
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "ack" | "acknowledge" | "true" => Self::Acknowledge,
+            "history" | "history-only" | "history_only" => Self::HistoryOnly,
+            _ => Self::Off,
+        }
+    }
+}
+
+/// Handling of notifications for panes the layout does not have
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum MissingPane {
+    /// Drop them with a warning
+    Drop,
+    /// Show them at the tab level (their `tab_index`, else the active tab)
+    Tab,
+    /// Hold them until the pane appears, dropping them after `missing_pane_hold_ms`
+    Hold,
+}
+
+impl Default for MissingPane {
+    fn default() -> Self {
+        Self::Hold
+    }
+}
+
+impl MissingPane {
+    /// Parse missing pane handling from string
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "drop" | "warn" => Self::Drop,
+            "tab" | "tab-level" | "tab_level" => Self::Tab,
+            _ => Self::Hold,
+        }
+    }
+}
+
+/// Which consecutive history entries compaction merges
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum HistoryCompaction {
+    /// None (the `compact_history` command still merges identical ones)
+    Off,
+    /// Identical ones: same type, source, pane, title and message
+    Exact,
+    /// Ones that differ only in numbers (counters, durations, times)
+    Similar,
+}
+
+impl Default for HistoryCompaction {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+impl HistoryCompaction {
+    /// Parse history compaction from string
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "exact" | "identical" | "true" => Self::Exact,
+            "similar" | "aggressive" => Self::Similar,
+            _ => Self::Off,
+        }
+    }
+
+    /// Get the mode name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Exact => "exact",
+            Self::Similar => "similar",
+        }
+    }
+}
+
+/// Order of notification entries in the status bar
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum StatusOrder {
+    /// Most urgent first (priority, then type urgency), newest first among equals
+    Urgency,
+    /// By pane ID
+    Pane,
+}
+
+impl Default for StatusOrder {
+    fn default() -> Self {
+        Self::Urgency
+    }
+}
+
+impl StatusOrder {
+    /// Parse status order from string
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "pane" | "pane-order" | "pane_order" => Self::Pane,
+            _ => Self::Urgency,
+        }
+    }
+}
+
+/// Where notification badges go in tab names
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TabBadgePlacement {
+    /// Before the name (`✘2 3:work`)
+    Prefix,
+    /// After the name (`3:work ✘2`)
+    Suffix,
+    /// Instead of the name while notifications are active (`✘2`)
+    Replace,
+}
+
+impl Default for TabBadgePlacement {
+    fn default() -> Self {
+        Self::Suffix
+    }
+}
+
+impl TabBadgePlacement {
+    /// Parse tab badge placement from string
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "prefix" => Self::Prefix,
+            "replace" | "counter" => Self::Replace,
+            _ => Self::Suffix,
+        }
+    }
+}
+
+/// Screen corner floating toasts are anchored to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ToastPosition {
+    /// Top right corner (stacks downward)
+    TopRight,
+    /// Top left corner (stacks downward)
+    TopLeft,
+    /// Bottom right corner (stacks upward)
+    BottomRight,
+    /// Bottom left corner (stacks upward)
+    BottomLeft,
+}
+
+impl Default for ToastPosition {
+    fn default() -> Self {
+        Self::TopRight
+    }
+}
+
+impl ToastPosition {
+    /// Parse toast position from string (`top_right`, `bottom-left`, ...)
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "top_left" => Self::TopLeft,
+            "bottom_right" => Self::BottomRight,
+            "bottom_left" => Self::BottomLeft,
+            _ => Self::TopRight,
+        }
+    }
+
+    /// Whether toasts are anchored to the right edge
+    pub fn is_right(&self) -> bool {
+        matches!(self, Self::TopRight | Self::BottomRight)
+    }
+
+    /// Whether toasts are anchored to the bottom edge
+    pub fn is_bottom(&self) -> bool {
+        matches!(self, Self::BottomRight | Self::BottomLeft)
+    }
+}
+
+/// Check that a color is a `#rrggbb` hex string
+pub fn is_hex_color(value: &str) -> bool {
+    value.len() == 7
+        && value.starts_with('#')
+        && value[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
 /// Accessibility configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessibilityConfig {
@@ -431,6 +1871,61 @@ pub struct AccessibilityConfig {
     pub screen_reader: bool,
     /// Use patterns in addition to colors
     pub use_patterns: bool,
+    /// Suppress all color output (NO_COLOR); rely on icons, patterns and bold/reverse
+    #[serde(default)]
+    pub no_color: bool,
+}
+
+/// Accessibility settings that can be changed at runtime
+pub const ACCESSIBILITY_SETTINGS: [&str; 4] = ["reduced_motion", "high_contrast", "use_patterns", "no_color"];
+
+impl Config {
+    /// Change an accessibility setting at runtime (reduced motion also switches animations)
+    pub fn set_accessibility(&mut self, setting: &str, enabled: bool) -> Result<(), String> {
+        match setting {
+            "reduced_motion" => {
+                self.accessibility.reduced_motion = enabled;
+                self.animation.enabled = !enabled;
+            }
+            "high_contrast" => self.accessibility.high_contrast = enabled,
+            "use_patterns" => self.accessibility.use_patterns = enabled,
+            "no_color" => self.accessibility.no_color = enabled,
+            other => {
+                return Err(format!("unknown setting: {} (expected one of {})",
+                    other, ACCESSIBILITY_SETTINGS.join(", ")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the plugin pane shows the plain text log (by default with `screen_reader`)
+    pub fn text_log_enabled(&self) -> bool {
+        self.text_log.unwrap_or(self.accessibility.screen_reader)
+    }
+
+    /// Whether motion is reduced to static emphasis (reduced motion or low power mode)
+    pub fn motion_reduced(&self) -> bool {
+        self.accessibility.reduced_motion || self.low_power
+    }
+
+    /// Whether animations run (low power mode switches them off)
+    pub fn animations_enabled(&self) -> bool {
+        self.animation.enabled && !self.low_power
+    }
+
+    /// Animation settings in effect
+    pub fn effective_animation(&self) -> AnimationConfig {
+        AnimationConfig { enabled: self.animations_enabled(), ..self.animation.clone() }
+    }
+
+    /// Timer interval in effect (ms)
+    pub fn tick_ms(&self) -> u64 {
+        if self.low_power {
+            self.low_power_tick_ms.max(self.animation.tick_ms)
+        } else {
+            self.animation.tick_ms
+        }
+    }
 }
 
 impl Default for AccessibilityConfig {
@@ -440,17 +1935,73 @@ impl Default for AccessibilityConfig {
             reduced_motion: false,
             screen_reader: false,
             use_patterns: true,
+            no_color: false,
+        }
+    }
+}
+
+/// Configuration layer an effective value comes from, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigLayer {
+    /// Built-in default
+    Default,
+    /// KDL configuration file (`config_file`) or theme file (`theme_file`)
+    File,
+    /// Plugin configuration in the Zellij layout or config
+    PluginConfig,
+    /// Changed at runtime (`config_set`, `theme_set`, `set`, `big_mode`)
+    Runtime,
+}
+
+impl ConfigLayer {
+    /// Name shown by `config_show`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::File => "file",
+            Self::PluginConfig => "plugin",
+            Self::Runtime => "runtime",
         }
     }
 }
 
+/// A value set differently by more than one configuration layer (runtime overrides aside)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDrift {
+    /// Flattened key, e.g. `theme.error_color`
+    pub key: String,
+    /// Layers that set the value, lowest first; the last one wins
+    pub layers: Vec<ConfigLayer>,
+}
+
+impl ConfigDrift {
+    /// Description, e.g. `theme.error_color (file, plugin: plugin wins)`
+    pub fn line(&self) -> String {
+        let names: Vec<&str> = self.layers.iter().map(ConfigLayer::name).collect();
+        format!("{} ({}: {} wins)", self.key, names.join(", "), names.last().copied().unwrap_or("default"))
+    }
+}
+
 /// Configuration manager for hot-reload
+///
+/// The effective configuration is built in layers: defaults, the KDL file named by the
+/// `config_file` plugin option, the plugin configuration, the theme file (which replaces the
+/// theme, as before) and finally runtime overrides. Each layer only changes the values it sets,
+/// and the values after every layer are kept so `config_show` can tell where a value came from.
 #[derive(Default)]
 pub struct ConfigManager {
     /// Last known configuration
     last_config: Option<Config>,
     /// Configuration file path
     config_path: Option<String>,
+    /// Plugin configuration map
+    plugin_config: BTreeMap<String, String>,
+    /// Runtime overrides, by plugin configuration key
+    overrides: BTreeMap<String, String>,
+    /// Flattened values after each layer of the last resolution
+    layer_values: Vec<(ConfigLayer, BTreeMap<String, serde_json::Value>)>,
+    /// Drifting keys already warned about
+    drift_reported: BTreeSet<String>,
 }
 
 impl ConfigManager {
@@ -459,7 +2010,20 @@ impl ConfigManager {
         Self {
             last_config: None,
             config_path: None,
+            plugin_config: BTreeMap::new(),
+            overrides: BTreeMap::new(),
+            layer_values: Vec::new(),
+            drift_reported: BTreeSet::new(),
+        }
+    }
+
+    /// Use a plugin configuration map as the plugin layer (its `config_file` names the file layer)
+    pub fn with_plugin_config(mut self, config_map: &BTreeMap<String, String>) -> Self {
+        if let Some(path) = config_map.get("config_file") {
+            self.set_path(path);
         }
+        self.plugin_config = config_map.clone();
+        self
     }
 
     /// Set the configuration file path
@@ -467,22 +2031,161 @@ impl ConfigManager {
         self.config_path = Some(path.to_string());
     }
 
-    /// Reload configuration from file
-    pub fn reload(&mut self) -> Option<Config> {
-        // In WASM environment, we can't directly read files
-        // This would need to be triggered by a custom message from the host
-        // For now, return None to indicate no change
-        None
+    /// Set a runtime override (a plugin configuration key), returning the value it replaced;
+    /// applies on the next resolution
+    pub fn set_override(&mut self, key: &str, value: &str) -> Option<String> {
+        self.overrides.insert(key.to_string(), value.to_string())
+    }
+
+    /// Remove a runtime override, returning whether there was one
+    pub fn remove_override(&mut self, key: &str) -> bool {
+        self.overrides.remove(key).is_some()
+    }
+
+    /// Re-read the configuration and theme files and resolve all layers
+    pub fn reload(&mut self) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut layers = vec![(ConfigLayer::Default, config.clone())];
+
+        if let Some(path) = &self.config_path {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| format!("cannot read config file {}: {}", path, e))?;
+            config = self.parse_kdl_over(config, &content)?;
+            layers.push((ConfigLayer::File, config.clone()));
+        }
+
+        // The theme file is a file layer too, under the plugin configuration, whichever of the
+        // two names it
+        let theme_file = self.plugin_config.get("theme_file").or(config.theme_file.as_ref()).cloned();
+        if let Some(path) = theme_file {
+            ThemeFile::load(&path)?.apply(&mut config);
+            layers.push((ConfigLayer::File, config.clone()));
+        }
+
+        config.apply_plugin_config(&self.plugin_config);
+        layers.push((ConfigLayer::PluginConfig, config.clone()));
+
+        Ok(self.finish(config, layers))
+    }
+
+    /// Resolve defaults, plugin configuration and overrides only (when the files are unusable)
+    pub fn reload_without_files(&mut self) -> Config {
+        let mut config = Config::default();
+        let mut layers = vec![(ConfigLayer::Default, config.clone())];
+        config.apply_plugin_config(&self.plugin_config);
+        layers.push((ConfigLayer::PluginConfig, config.clone()));
+        self.finish(config, layers)
+    }
+
+    /// Apply runtime overrides and remember the values after each layer
+    fn finish(&mut self, mut config: Config, mut layers: Vec<(ConfigLayer, Config)>) -> Config {
+        if !self.overrides.is_empty() {
+            config.apply_plugin_config(&self.overrides);
+            layers.push((ConfigLayer::Runtime, config.clone()));
+        }
+        config.normalize_host_paths();
+        for (_, layer_config) in layers.iter_mut() {
+            layer_config.normalize_host_paths();
+        }
+        self.layer_values = layers.iter()
+            .map(|(layer, layer_config)| (*layer, flatten_config(layer_config)))
+            .collect();
+        self.last_config = Some(config.clone());
+        config
+    }
+
+    /// Effective values (whose key starts with `prefix`) and the layer each one comes from,
+    /// e.g. `notification_timeout_ms = 60000  (plugin)`. Values changed since the last
+    /// resolution count as runtime changes.
+    pub fn describe(&self, effective: &Config, prefix: &str) -> String {
+        let lines: Vec<String> = flatten_config(effective).into_iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| {
+                let origin = self.origin(&key, &value);
+                format!("{} = {}  ({})", key, value, origin.name())
+            })
+            .collect();
+        if lines.is_empty() {
+            return format!("no configuration values match {}", prefix);
+        }
+        lines.join("\n")
+    }
+
+    /// Where the effective configuration comes from, for the `version` command, e.g.
+    /// `file ~/.config/notify.kdl, plugin (3 keys), 1 runtime override, theme dracula`
+    pub fn profile(&self, effective: &Config) -> String {
+        let mut sources = Vec::new();
+        if let Some(path) = &self.config_path {
+            if self.layer_values.iter().any(|(layer, _)| *layer == ConfigLayer::File) {
+                sources.push(format!("file {}", path));
+            } else {
+                sources.push(format!("file {} (not loaded)", path));
+            }
+        }
+        let plugin_keys = self.plugin_config.keys().filter(|key| *key != "config_file").count();
+        if plugin_keys > 0 {
+            sources.push(format!("plugin ({} key{})", plugin_keys, if plugin_keys == 1 { "" } else { "s" }));
+        }
+        if !self.overrides.is_empty() {
+            sources.push(format!("{} runtime override{}", self.overrides.len(), if self.overrides.len() == 1 { "" } else { "s" }));
+        }
+        if sources.is_empty() {
+            sources.push("defaults".to_string());
+        }
+        match &effective.theme_file {
+            Some(path) => sources.push(format!("theme {} ({})", effective.theme.name, path)),
+            None => sources.push(format!("theme {}", effective.theme.name)),
+        }
+        sources.join(", ")
+    }
+
+    /// Values of the last resolution set differently by the file and plugin layers (or by the
+    /// configuration and theme files), e.g. a `theme_file` next to a `theme` in the layout
+    pub fn drift(&self) -> Vec<ConfigDrift> {
+        let Some((_, resolved)) = self.layer_values.last() else { return Vec::new() };
+        resolved.keys()
+            .filter_map(|key| {
+                let layers: Vec<ConfigLayer> = self.layer_values.windows(2)
+                    .filter(|pair| pair[1].0 != ConfigLayer::Runtime && pair[0].1.get(key) != pair[1].1.get(key))
+                    .map(|pair| pair[1].0)
+                    .collect();
+                (layers.len() > 1).then(|| ConfigDrift { key: key.clone(), layers })
+            })
+            .collect()
+    }
+
+    /// Drift not warned about before (each key is reported once per plugin run)
+    pub fn new_drift(&mut self) -> Vec<ConfigDrift> {
+        let drift: Vec<ConfigDrift> = self.drift().into_iter()
+            .filter(|drift| !self.drift_reported.contains(&drift.key))
+            .collect();
+        self.drift_reported.extend(drift.iter().map(|drift| drift.key.clone()));
+        drift
+    }
+
+    /// The highest layer that changed a value
+    fn origin(&self, key: &str, value: &serde_json::Value) -> ConfigLayer {
+        let resolved = self.layer_values.last().and_then(|(_, values)| values.get(key));
+        if resolved != Some(value) {
+            return ConfigLayer::Runtime;
+        }
+        self.layer_values.windows(2).rev()
+            .find(|pair| pair[0].1.get(key) != pair[1].1.get(key))
+            .map(|pair| pair[1].0)
+            .unwrap_or(ConfigLayer::Default)
     }
 
     /// Parse KDL configuration string
     pub fn parse_kdl(&self, content: &str) -> Result<Config, String> {
+        self.parse_kdl_over(Config::default(), content)
+    }
+
+    /// Parse KDL configuration over a base configuration (nodes not in the document keep their value)
+    fn parse_kdl_over(&self, mut config: Config, content: &str) -> Result<Config, String> {
         // Parse KDL content (kdl 4.x uses str::parse)
         let doc: kdl::KdlDocument = content.parse()
             .map_err(|e: kdl::KdlError| format!("KDL parse error: {}", e))?;
 
-        let mut config = Config::default();
-
         // Parse the document
         for node in doc.nodes() {
             match node.name().value() {
@@ -491,90 +2194,308 @@ impl ConfigManager {
                         config.enabled = val.value().as_bool().unwrap_or(true);
                     }
                 }
-                "theme" => {
+                "pane_title_summary" => {
                     if let Some(val) = node.get(0) {
-                        if let Some(name) = val.value().as_string() {
-                            config.theme = ThemeConfig::from_preset(name);
-                        }
+                        config.pane_title_summary = val.value().as_bool().unwrap_or(true);
                     }
-                    // Parse nested theme properties
-                    if let Some(children) = node.children() {
-                        for child in children.nodes() {
-                            match child.name().value() {
-                                "success_color" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(color) = val.value().as_string() {
-                                            config.theme.success_color = color.to_string();
-                                        }
-                                    }
-                                }
-                                "error_color" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(color) = val.value().as_string() {
-                                            config.theme.error_color = color.to_string();
-                                        }
-                                    }
-                                }
-                                "warning_color" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(color) = val.value().as_string() {
-                                            config.theme.warning_color = color.to_string();
-                                        }
-                                    }
-                                }
-                                "info_color" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(color) = val.value().as_string() {
-                                            config.theme.info_color = color.to_string();
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
+                }
+                "big_mode" => {
+                    if let Some(val) = node.get(0) {
+                        config.big_mode = val.value().as_bool().unwrap_or(false);
                     }
                 }
-                "animation" => {
-                    if let Some(children) = node.children() {
-                        for child in children.nodes() {
-                            match child.name().value() {
-                                "enabled" => {
-                                    if let Some(val) = child.get(0) {
-                                        config.animation.enabled = val.value().as_bool().unwrap_or(true);
-                                    }
-                                }
-                                "style" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(style) = val.value().as_string() {
-                                            config.animation.style = AnimationStyle::from_str(style);
-                                        }
-                                    }
-                                }
-                                "speed" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(speed) = val.value().as_i64() {
-                                            config.animation.speed = speed.clamp(1, 100) as u8;
-                                        }
-                                    }
-                                }
-                                "cycles" => {
-                                    if let Some(val) = child.get(0) {
-                                        if let Some(cycles) = val.value().as_i64() {
-                                            config.animation.cycles = cycles.clamp(1, 10) as u8;
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
+                "text_log" => {
+                    if let Some(val) = node.get(0) {
+                        config.text_log = val.value().as_bool();
                     }
                 }
-                "accessibility" => {
-                    if let Some(children) = node.children() {
-                        for child in children.nodes() {
-                            match child.name().value() {
-                                "high_contrast" => {
-                                    if let Some(val) = child.get(0) {
+                "startup_banner" => {
+                    if let Some(val) = node.get(0) {
+                        config.startup_banner = val.value().as_bool().unwrap_or(true);
+                    }
+                }
+                "mirror" => {
+                    if let Some(val) = node.get(0) {
+                        config.mirror = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "instance_election" => {
+                    if let Some(val) = node.get(0) {
+                        config.instance_election = val.value().as_bool().unwrap_or(true);
+                    }
+                }
+                "ingest_file" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(path) = val.value().as_string() {
+                            config.ingest_file = Some(path.to_string()).filter(|path| !path.is_empty());
+                        }
+                    }
+                }
+                "ingest_file_poll_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(poll_ms) = val.value().as_i64() {
+                            config.ingest_file_poll_ms = poll_ms.max(0) as u64;
+                        }
+                    }
+                }
+                "low_power" => {
+                    if let Some(val) = node.get(0) {
+                        config.low_power = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "low_power_tick_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(tick_ms) = val.value().as_i64() {
+                            config.low_power_tick_ms = tick_ms.max(0) as u64;
+                        }
+                    }
+                }
+                "focus_ring" => {
+                    if let Some(val) = node.get(0) {
+                        config.focus_ring = val.value().as_bool().unwrap_or(true);
+                    }
+                }
+                "project_roots" => {
+                    config.project_roots = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|root| root.to_string())
+                        .collect();
+                }
+                "channels" | "hidden_channels" => {
+                    let names = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|name| name.to_string())
+                        .collect();
+                    if node.name().value() == "channels" {
+                        config.channels = names;
+                    } else {
+                        config.hidden_channels = names;
+                    }
+                }
+                "unread_count_file" => {
+                    if let Some(val) = node.get(0) {
+                        config.unread_count_file = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "host_platform" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(platform) = val.value().as_string() {
+                            config.host_platform = HostPlatform::from_str(platform);
+                        }
+                    }
+                }
+                "temp_dir" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(path) = val.value().as_string() {
+                            config.temp_dir = path.to_string();
+                        }
+                    }
+                }
+                "unread_count_path" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(path) = val.value().as_string() {
+                            config.unread_count_path = path.to_string();
+                        }
+                    }
+                }
+                "passive_detection" => {
+                    if let Some(val) = node.get(0) {
+                        config.passive_detection = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "passive_template" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(template) = val.value().as_string() {
+                            config.passive_template = template.to_string();
+                        }
+                    }
+                }
+                "passive_min_duration_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(duration) = val.value().as_i64() {
+                            config.passive_min_duration_ms = duration.max(0) as u64;
+                        }
+                    }
+                }
+                "passive_max_title_changes" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(changes) = val.value().as_i64() {
+                            config.passive_max_title_changes = changes.max(0) as usize;
+                        }
+                    }
+                }
+                "passive_shells" => {
+                    config.passive_shells = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|shell| shell.to_string())
+                        .collect();
+                }
+                "claude_pane_patterns" => {
+                    config.claude_pane_patterns = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|pattern| pattern.to_string())
+                        .collect();
+                }
+                "claude_pane_color" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(color) = val.value().as_string() {
+                            config.claude_pane_color = color.to_lowercase();
+                        }
+                    }
+                }
+                "clear_all_confirm" => {
+                    if let Some(val) = node.get(0) {
+                        config.clear_all_confirm = val.value().as_bool().unwrap_or(true);
+                    }
+                }
+                "chord_leader" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(leader) = val.value().as_string() {
+                            config.chord_leader = leader.to_string();
+                        }
+                    }
+                }
+                "actions_dry_run" => {
+                    if let Some(val) = node.get(0) {
+                        config.actions_dry_run = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "action_rate_limit" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(limit) = val.value().as_i64() {
+                            config.action_rate_limit = limit.clamp(0, u32::MAX as i64) as u32;
+                        }
+                    }
+                }
+                "theme" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(name) = val.value().as_string() {
+                            config.theme = ThemeConfig::from_preset(name);
+                        }
+                    }
+                    // Parse nested theme properties
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            // Color fields (success_color, error_color, ...)
+                            if let (Some(slot), Some(val)) = (config.theme.color_mut(child.name().value()), child.get(0)) {
+                                if let Some(color) = val.value().as_string() {
+                                    *slot = color.to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                "theme_file" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(path) = val.value().as_string() {
+                            config.theme_file = Some(path.to_string());
+                        }
+                    }
+                }
+                "animation" => {
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            match child.name().value() {
+                                "enabled" => {
+                                    if let Some(val) = child.get(0) {
+                                        config.animation.enabled = val.value().as_bool().unwrap_or(true);
+                                    }
+                                }
+                                "style" => {
+                                    if let Some(val) = child.get(0) {
+                                        if let Some(style) = val.value().as_string() {
+                                            config.animation.style = AnimationStyle::from_str(style);
+                                        }
+                                    }
+                                }
+                                "speed" => {
+                                    if let Some(val) = child.get(0) {
+                                        if let Some(speed) = val.value().as_i64() {
+                                            config.animation.speed = speed.clamp(1, 100) as u8;
+                                        }
+                                    }
+                                }
+                                "cycles" => {
+                                    if let Some(val) = child.get(0) {
+                                        if let Some(cycles) = val.value().as_i64() {
+                                            config.animation.cycles = cycles.clamp(1, 10) as u8;
+                                        }
+                                    }
+                                }
+                                "tick_ms" => {
+                                    if let Some(val) = child.get(0) {
+                                        if let Some(tick_ms) = val.value().as_i64() {
+                                            config.animation.tick_ms = (tick_ms.max(0) as u64)
+                                                .clamp(*TICK_MS_RANGE.start(), *TICK_MS_RANGE.end());
+                                        }
+                                    }
+                                }
+                                "interpolate" => {
+                                    if let Some(val) = child.get(0) {
+                                        config.animation.interpolate = val.value().as_bool().unwrap_or(false);
+                                    }
+                                }
+                                "priority_scaling" => {
+                                    if let Some(val) = child.get(0) {
+                                        config.animation.priority_scaling = val.value().as_bool().unwrap_or(true);
+                                    }
+                                }
+                                "target" => {
+                                    if let Some(val) = child.get(0) {
+                                        if let Some(target) = val.value().as_string() {
+                                            config.animation.target = AnimationTarget::from_str(target);
+                                        }
+                                    }
+                                }
+                                "hidden_tabs" => {
+                                    if let Some(val) = child.get(0) {
+                                        if let Some(hidden_tabs) = val.value().as_string() {
+                                            config.animation.hidden_tabs = HiddenTabAnimation::from_str(hidden_tabs);
+                                        }
+                                    }
+                                }
+                                "gradient" => {
+                                    // gradient "error" "#ef4444" "#f97316" "#ef4444"
+                                    let values: Vec<String> = child.entries().iter()
+                                        .filter_map(|entry| entry.value().as_string().map(String::from))
+                                        .collect();
+                                    if let Some((type_name, stops)) = values.split_first() {
+                                        config.animation.gradient_stops.insert(type_name.clone(), stops.to_vec());
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                "attention" => {
+                    // permission priority="critical" style="flash" color="#f97316"
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            let reason = AttentionReason::from_name(child.name().value())
+                                .ok_or_else(|| format!("unknown attention reason: {}", child.name().value()))?;
+                            let settings: Vec<String> = child.entries().iter()
+                                .filter_map(|entry| {
+                                    let value = entry.value();
+                                    let value = value.as_string().map(String::from)
+                                        .or_else(|| value.as_bool().map(|b| b.to_string()))?;
+                                    Some(format!("{}={}", entry.name()?.value(), value))
+                                })
+                                .collect();
+                            config.attention.get_mut(reason).apply(&settings)?;
+                        }
+                    }
+                }
+                "accessibility" => {
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            match child.name().value() {
+                                "high_contrast" => {
+                                    if let Some(val) = child.get(0) {
                                         config.accessibility.high_contrast = val.value().as_bool().unwrap_or(false);
                                     }
                                 }
@@ -586,22 +2507,596 @@ impl ConfigManager {
                                         }
                                     }
                                 }
+                                "no_color" => {
+                                    if let Some(val) = child.get(0) {
+                                        config.accessibility.no_color = val.value().as_bool().unwrap_or(false);
+                                    }
+                                }
                                 _ => {}
                             }
                         }
                     }
                 }
-                "notification_timeout_ms" => {
+                "sinks" => {
+                    config.sinks = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|name| name.to_string())
+                        .collect();
+                }
+                "segments" => {
+                    config.status_segments = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|name| name.to_string())
+                        .collect();
+                }
+                "raw_ansi" => {
+                    // type "error" prefix="\e[4:3;58:2::255:0:0m" suffix="\e[59;24m"
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            let name = child.get(0).and_then(|val| val.value().as_string())
+                                .ok_or_else(|| format!("raw_ansi {} needs a name", child.name().value()))?;
+                            let settings: Vec<String> = child.entries().iter()
+                                .filter_map(|entry| Some(format!("{}={}", entry.name()?.value(), entry.value().as_string()?)))
+                                .collect();
+                            config.raw_ansi.set(child.name().value(), name, RawAnsi::parse(&settings)?)?;
+                        }
+                    }
+                }
+                "pipes" => {
+                    config.pipes = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|name| name.to_string())
+                        .collect();
+                }
+                "strict_pipes" => {
+                    if let Some(val) = node.get(0) {
+                        config.strict_pipes = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "update_check" => {
+                    if let Some(val) = node.get(0) {
+                        config.update_check = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "update_check_helper" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(helper) = val.value().as_string() {
+                            config.update_check_helper = Some(helper.to_string()).filter(|helper| !helper.trim().is_empty());
+                        }
+                    }
+                }
+                "webhook_url" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(url) = val.value().as_string() {
+                            config.webhook_url = Some(url.to_string());
+                        }
+                    }
+                }
+                "webhook_events" => {
+                    config.webhook_events = node.entries().iter()
+                        .filter(|entry| entry.name().is_none())
+                        .filter_map(|entry| entry.value().as_string())
+                        .map(|event| event.to_string())
+                        .collect();
+                }
+                "forward_session" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(session) = val.value().as_string() {
+                            config.forward_session = Some(session.to_string()).filter(|session| !session.is_empty());
+                        }
+                    }
+                }
+                "desktop_daemon_helper" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(helper) = val.value().as_string() {
+                            config.desktop_daemon_helper = Some(helper.to_string()).filter(|helper| !helper.trim().is_empty());
+                        }
+                    }
+                }
+                "aggregator" => {
+                    if let Some(val) = node.get(0) {
+                        config.aggregator = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "webhook_max_attempts" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(attempts) = val.value().as_i64() {
+                            config.webhook_max_attempts = attempts.clamp(1, 100) as u32;
+                        }
+                    }
+                }
+                "webhook_retry_base_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(delay) = val.value().as_i64() {
+                            config.webhook_retry_base_ms = delay.max(100) as u64;
+                        }
+                    }
+                }
+                "ack_escalation_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(escalation) = val.value().as_i64() {
+                            config.ack_escalation_ms = escalation.max(0) as u64;
+                        }
+                    }
+                }
+                "focus_minutes" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(minutes) = val.value().as_i64() {
+                            config.focus_minutes = minutes.max(1) as u64;
+                        }
+                    }
+                }
+                "sequence_hold_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(hold) = val.value().as_i64() {
+                            config.sequence_hold_ms = hold.max(0) as u64;
+                        }
+                    }
+                }
+                "frame_time_warn_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(threshold) = val.value().as_i64() {
+                            config.frame_time_warn_ms = threshold.max(0) as u64;
+                        }
+                    }
+                }
+                "latency_warn_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(threshold) = val.value().as_i64() {
+                            config.latency_warn_ms = threshold.max(0) as u64;
+                        }
+                    }
+                }
+                "recent_ack_window_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(window) = val.value().as_i64() {
+                            config.recent_ack_window_ms = window.max(0) as u64;
+                        }
+                    }
+                }
+                "slo" => {
+                    // attention 300000
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            if let Some(target) = child.get(0).and_then(|val| val.value().as_i64()) {
+                                config.slo.insert(child.name().value().to_string(), target.max(0) as u64);
+                            }
+                        }
+                    }
+                }
+                "sound_theme" => {
+                    if let Some(theme) = node.get(0).and_then(|val| val.value().as_string()) {
+                        config.sound_theme = theme.to_string();
+                    }
+                }
+                "sound_player" => {
+                    if let Some(player) = node.get(0).and_then(|val| val.value().as_string()) {
+                        config.sound_player = player.to_string();
+                    }
+                }
+                "sound_packs" => {
+                    // pack "mine" { error "/path/to/fail.oga"; success "say done"; }
+                    if let Some(children) = node.children() {
+                        for pack in children.nodes().iter().filter(|child| child.name().value() == "pack") {
+                            let name = pack.get(0).and_then(|val| val.value().as_string())
+                                .ok_or("sound pack needs a name")?;
+                            let sounds = config.sound_packs.entry(name.to_string()).or_default();
+                            for entry in pack.children().map(|children| children.nodes()).unwrap_or_default() {
+                                if let Some(sound) = entry.get(0).and_then(|val| val.value().as_string()) {
+                                    sounds.insert(entry.name().value().to_string(), sound.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+                "features" => {
+                    // experimental "toasts" "mouse"
+                    if let Some(children) = node.children() {
+                        for child in children.nodes().iter().filter(|child| child.name().value() == "experimental") {
+                            config.experimental_features.extend(child.entries().iter()
+                                .filter(|entry| entry.name().is_none())
+                                .filter_map(|entry| entry.value().as_string())
+                                .map(str::to_string));
+                        }
+                    }
+                }
+                "remap" => {
+                    // source "flaky-tool" from "error" to "warning" (or from="error" to="warning")
+                    if let Some(children) = node.children() {
+                        for child in children.nodes().iter().filter(|child| child.name().value() == "source") {
+                            let args: Vec<&str> = child.entries().iter()
+                                .filter(|entry| entry.name().is_none())
+                                .filter_map(|entry| entry.value().as_string())
+                                .collect();
+                            let source = args.first().ok_or("remap source needs a name")?;
+                            let setting = |key: &str| child.get(key)
+                                .and_then(|val| val.value().as_string())
+                                .or_else(|| args[1..].windows(2).find(|pair| pair[0] == key).map(|pair| pair[1]))
+                                .unwrap_or_default();
+                            config.remaps.push(TypeRemap::parse(source, setting("from"), setting("to"))?);
+                        }
+                    }
+                }
+                "rules" => {
+                    // rule "quiet-ci" match="source=ci type=info" action="priority=low"
+                    if let Some(children) = node.children() {
+                        for child in children.nodes().iter().filter(|child| child.name().value() == "rule") {
+                            let name = child.get(0).and_then(|val| val.value().as_string())
+                                .ok_or("rule needs a name")?;
+                            let setting = |key: &str| child.get(key)
+                                .and_then(|val| val.value().as_string())
+                                .unwrap_or_default()
+                                .to_string();
+                            config.rules.push(Rule::parse(name, &setting("match"), &setting("action"))?);
+                        }
+                    }
+                }
+                "checks" => {
+                    // check "dirty" run="git status --porcelain" every="5m" notify="output" type="warning" message="..."
+                    if let Some(children) = node.children() {
+                        for child in children.nodes().iter().filter(|child| child.name().value() == "check") {
+                            let name = child.get(0).and_then(|val| val.value().as_string())
+                                .ok_or("check needs a name")?;
+                            let setting = |key: &str, default: &str| child.get(key)
+                                .and_then(|val| val.value().as_string())
+                                .unwrap_or(default)
+                                .to_string();
+                            config.checks.push(Check::parse(
+                                name,
+                                &setting("run", ""),
+                                &setting("every", ""),
+                                &setting("notify", "output"),
+                                &setting("type", "warning"),
+                                &setting("message", ""),
+                            )?);
+                        }
+                    }
+                }
+                "on_notify" => {
+                    // on_notify "tests" { match "type=error source=tests"; action "run" "cargo test"; confirm true; cooldown "1m"; }
+                    let name = node.get(0).and_then(|val| val.value().as_string())
+                        .ok_or("on_notify needs a name")?;
+                    let child = |key: &str| node.children()
+                        .and_then(|children| children.get(key));
+                    let string = |key: &str, index: usize| child(key)
+                        .and_then(|child| child.get(index))
+                        .and_then(|val| val.value().as_string());
+                    config.on_notify.push(Automation::parse(
+                        name,
+                        string("match", 0).unwrap_or_default(),
+                        string("action", 0).ok_or_else(|| format!("on_notify {}: no action", name))?,
+                        string("action", 1).unwrap_or_default(),
+                        child("confirm").and_then(|child| child.get(0)).and_then(|val| val.value().as_bool()),
+                        string("cooldown", 0),
+                    )?);
+                }
+                "auto_focus" => {
+                    // attention true; error false
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            if let Some(enabled) = child.get(0).and_then(|val| val.value().as_bool()) {
+                                config.auto_focus.insert(child.name().value().to_string(), enabled);
+                            }
+                        }
+                    }
+                }
+                "auto_focus_delay_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(delay) = val.value().as_i64() {
+                            config.auto_focus_delay_ms = delay.max(0) as u64;
+                        }
+                    }
+                }
+                "rules_dry_run" => {
+                    if let Some(val) = node.get(0) {
+                        config.rules_dry_run = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "noisy_source_threshold" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(threshold) = val.value().as_i64() {
+                            config.noisy_source_threshold = threshold.clamp(0, u32::MAX as i64) as u32;
+                        }
+                    }
+                }
+                "slo_escalate" => {
+                    if let Some(val) = node.get(0) {
+                        config.slo_escalate = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "capture_error_lines" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(lines) = val.value().as_i64() {
+                            config.capture_error_lines = lines.clamp(0, MAX_SNAPSHOT_LINES as i64) as usize;
+                        }
+                    }
+                }
+                "exit_codes" => {
+                    // code 3 "lint failed"
+                    if let Some(children) = node.children() {
+                        for child in children.nodes().iter().filter(|child| child.name().value() == "code") {
+                            let code = child.get(0).and_then(|val| val.value().as_i64())
+                                .and_then(|code| i32::try_from(code).ok())
+                                .ok_or("exit code needs a number")?;
+                            let explanation = child.get(1).and_then(|val| val.value().as_string())
+                                .ok_or("exit code needs an explanation")?;
+                            config.exit_codes.insert(code, explanation.to_string());
+                        }
+                    }
+                }
+                "exit_code_colors" => {
+                    // range "2..=127" "#ff0000" (or range 1 "#ff6666")
+                    if let Some(children) = node.children() {
+                        config.exit_code_colors.clear();
+                        for child in children.nodes().iter().filter(|child| child.name().value() == "range") {
+                            let range = child.get(0).and_then(|val| {
+                                val.value().as_string().map(str::to_string).or_else(|| val.value().as_i64().map(|code| code.to_string()))
+                            }).ok_or("exit code color needs a range")?;
+                            let color = child.get(1).and_then(|val| val.value().as_string())
+                                .ok_or("exit code color needs a color")?;
+                            config.exit_code_colors.push(ExitCodeColor::parse(&range, color)?);
+                        }
+                    }
+                }
+                "tab_name_badges" => {
+                    if let Some(val) = node.get(0) {
+                        config.tab_name_badges = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "attention_queue" => {
+                    if let Some(val) = node.get(0) {
+                        match val.value().as_bool() {
+                            Some(enabled) => config.attention_queue = AttentionQueue::from_str(&enabled.to_string()),
+                            None => if let Some(mode) = val.value().as_string() {
+                                config.attention_queue = AttentionQueue::from_str(mode);
+                            },
+                        }
+                    }
+                }
+                "status_order" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(order) = val.value().as_string() {
+                            config.status_order = StatusOrder::from_str(order);
+                        }
+                    }
+                }
+                "wide_layout_min_cols" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(cols) = val.value().as_i64() {
+                            config.wide_layout_min_cols = cols.max(0) as usize;
+                        }
+                    }
+                }
+                "compact_layout_min_cols" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(cols) = val.value().as_i64() {
+                            config.compact_layout_min_cols = cols.max(0) as usize;
+                        }
+                    }
+                }
+                "entry_hotkeys" => {
+                    if let Some(val) = node.get(0) {
+                        config.entry_hotkeys = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "entry_chips" => {
+                    if let Some(val) = node.get(0) {
+                        config.entry_chips = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "ascii_only" => {
+                    // ascii_only true|false|"auto"
+                    if let Some(val) = node.get(0) {
+                        config.ascii_only = match (val.value().as_bool(), val.value().as_string()) {
+                            (Some(true), _) => AsciiMode::On,
+                            (Some(false), _) => AsciiMode::Off,
+                            (None, Some(mode)) => AsciiMode::from_str(mode),
+                            (None, None) => AsciiMode::Auto,
+                        };
+                    }
+                }
+                "icon_width_policy" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(policy) = val.value().as_string() {
+                            config.icon_width_policy = IconWidthPolicy::from_str(policy);
+                        }
+                    }
+                }
+                "tab_badge_placement" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(placement) = val.value().as_string() {
+                            config.tab_badge_placement = TabBadgePlacement::from_str(placement);
+                        }
+                    }
+                }
+                "tab_badge_separator" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(separator) = val.value().as_string() {
+                            config.tab_badge_separator = separator.to_string();
+                        }
+                    }
+                }
+                "tab_badge_max_width" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(max_width) = val.value().as_i64() {
+                            config.tab_badge_max_width = max_width.max(1) as usize;
+                        }
+                    }
+                }
+                "toast_position" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(position) = val.value().as_string() {
+                            config.toast_position = ToastPosition::from_str(position);
+                        }
+                    }
+                }
+                "toast_width" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(width) = val.value().as_i64() {
+                            config.toast_width = width.max(10) as usize;
+                        }
+                    }
+                }
+                "toast_height" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(height) = val.value().as_i64() {
+                            config.toast_height = height.max(3) as usize;
+                        }
+                    }
+                }
+                "toast_duration_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(duration) = val.value().as_i64() {
+                            config.toast_duration_ms = duration.max(500) as u64;
+                        }
+                    }
+                }
+                "toast_max_visible" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(max_visible) = val.value().as_i64() {
+                            config.toast_max_visible = max_visible.max(1) as usize;
+                        }
+                    }
+                }
+                "suppress_for_focused_pane" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(mode) = val.value().as_string() {
+                            config.suppress_for_focused_pane = FocusedPaneSuppression::from_str(mode);
+                        }
+                    }
+                }
+                "missing_pane" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(mode) = val.value().as_string() {
+                            config.missing_pane = MissingPane::from_str(mode);
+                        }
+                    }
+                }
+                "missing_pane_hold_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(hold) = val.value().as_i64() {
+                            config.missing_pane_hold_ms = hold.max(0) as u64;
+                        }
+                    }
+                }
+                "title_mirror" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(mode) = val.value().as_string() {
+                            config.title_mirror = TitleMirror::from_str(mode);
+                        }
+                    }
+                }
+                "pause_ttl_in_background" => {
+                    if let Some(val) = node.get(0) {
+                        config.pause_ttl_in_background = val.value().as_bool().unwrap_or(false);
+                    }
+                }
+                "min_display_ms" => {
+                    // success 3000
+                    if let Some(children) = node.children() {
+                        for child in children.nodes() {
+                            if let Some(min_display_ms) = child.get(0).and_then(|val| val.value().as_i64()) {
+                                config.min_display_ms.insert(child.name().value().to_string(), min_display_ms.max(0) as u64);
+                            }
+                        }
+                    }
+                }
+                "focused_tab_boost" => {
+                    if let Some(val) = node.get(0) {
+                        config.focused_tab_boost = val.value().as_bool().unwrap_or(true);
+                    }
+                }
+                "notification_timeout_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(timeout) = val.value().as_i64() {
+                            config.notification_timeout_ms = timeout.max(1000) as u64;
+                        }
+                    }
+                }
+                "ttl_extend_ms" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(extend) = val.value().as_i64() {
+                            config.ttl_extend_ms = extend.max(0) as u64;
+                        }
+                    }
+                }
+                "queue_max_size" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(size) = val.value().as_i64() {
+                            config.queue_max_size = size.max(1) as usize;
+                        }
+                    }
+                }
+                "queue_max_bytes" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(size) = val.value().as_i64() {
+                            config.queue_max_bytes = size.max(1024) as usize;
+                        }
+                    }
+                }
+                "max_payload_bytes" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(size) = val.value().as_i64() {
+                            config.max_payload_bytes = size.max(0) as usize;
+                        }
+                    }
+                }
+                "oversize_payload" => {
                     if let Some(val) = node.get(0) {
-                        if let Some(timeout) = val.value().as_i64() {
-                            config.notification_timeout_ms = timeout.max(1000) as u64;
+                        if let Some(policy) = val.value().as_string() {
+                            config.oversize_payload = OversizePolicy::from_str(policy);
                         }
                     }
                 }
-                "queue_max_size" => {
+                "queue_max_total" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(total) = val.value().as_i64() {
+                            config.queue_max_total = total.max(1) as usize;
+                        }
+                    }
+                }
+                "queue_high_water_percent" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(percent) = val.value().as_i64() {
+                            config.queue_high_water_percent = percent.clamp(0, 100) as u8;
+                        }
+                    }
+                }
+                "queue_strategy" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(strategy) = val.value().as_string() {
+                            config.queue_strategy = DequeueStrategy::from_str(strategy);
+                        }
+                    }
+                }
+                "display_rate" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(rate) = val.value().as_i64() {
+                            config.display_rate = rate.clamp(0, u32::MAX as i64) as u32;
+                        }
+                    }
+                }
+                "transition_history_max" => {
                     if let Some(val) = node.get(0) {
                         if let Some(size) = val.value().as_i64() {
-                            config.queue_max_size = size.max(1) as usize;
+                            config.transition_history_max = size.max(1) as usize;
+                        }
+                    }
+                }
+                "history_max" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(size) = val.value().as_i64() {
+                            config.history_max = size.max(1) as usize;
+                        }
+                    }
+                }
+                "history_compaction" => {
+                    if let Some(val) = node.get(0) {
+                        if let Some(mode) = val.value().as_string() {
+                            config.history_compaction = HistoryCompaction::from_str(mode);
                         }
                     }
                 }
@@ -639,6 +3134,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_accessibility() {
+        let mut config = Config::default();
+        config.set_accessibility("reduced_motion", true).unwrap();
+        assert!(config.accessibility.reduced_motion);
+        assert!(!config.animation.enabled);
+
+        config.set_accessibility("reduced_motion", false).unwrap();
+        assert!(config.animation.enabled);
+
+        config.set_accessibility("high_contrast", true).unwrap();
+        assert!(config.accessibility.high_contrast);
+        config.set_accessibility("no_color", true).unwrap();
+        assert!(config.accessibility.no_color);
+        assert!(config.set_accessibility("screen_size", true).is_err());
+    }
+
     #[test]
     fn test_config_validation() {
         let mut config = Config::default();
@@ -652,6 +3164,527 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list("visual, bell webhook"), vec!["visual", "bell", "webhook"]);
+        assert!(parse_list("  ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_kdl_sinks() {
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"sinks "visual" "screen_reader""#).unwrap();
+        assert_eq!(config.sinks, vec!["visual", "screen_reader"]);
+
+        let config = manager.parse_kdl(r#"sinks "visual" "forward"
+forward_session "dashboard"
+desktop_daemon_helper "/host/bin/notify-sync"
+aggregator true"#).unwrap();
+        assert_eq!(config.forward_session.as_deref(), Some("dashboard"));
+        assert_eq!(config.desktop_daemon_helper.as_deref(), Some("/host/bin/notify-sync"));
+        assert!(config.aggregator);
+
+        let mut map = BTreeMap::new();
+        map.insert("forward_session".to_string(), String::new());
+        assert_eq!(Config::from_plugin_config(&map).forward_session, None);
+    }
+
+    #[test]
+    fn test_ack_escalation_option() {
+        assert_eq!(Config::default().ack_escalation_ms, 0);
+        let manager = ConfigManager::new();
+        assert_eq!(manager.parse_kdl("ack_escalation_ms 600000").unwrap().ack_escalation_ms, 600_000);
+        assert_eq!(Config::default().focus_minutes, 25);
+        assert_eq!(manager.parse_kdl("focus_minutes 50").unwrap().focus_minutes, 50);
+        assert_eq!(manager.parse_kdl("focus_minutes 0").unwrap().focus_minutes, 1);
+        assert_eq!(manager.parse_kdl("sequence_hold_ms 250").unwrap().sequence_hold_ms, 250);
+        assert_eq!(manager.parse_kdl("frame_time_warn_ms 0").unwrap().frame_time_warn_ms, 0);
+        assert_eq!(manager.parse_kdl("latency_warn_ms 500").unwrap().latency_warn_ms, 500);
+        assert!(Config::default().focused_tab_boost);
+        assert!(!manager.parse_kdl("focused_tab_boost false").unwrap().focused_tab_boost);
+        assert_eq!(manager.parse_kdl("recent_ack_window_ms 0").unwrap().recent_ack_window_ms, 0);
+        assert_eq!(manager.parse_kdl(r#"segments "icon" "entries" "health""#).unwrap().status_segments,
+            vec!["icon", "entries", "health"]);
+        assert_eq!(manager.parse_kdl("capture_error_lines 1000").unwrap().capture_error_lines, MAX_SNAPSHOT_LINES);
+    }
+
+    #[test]
+    fn test_raw_ansi_options() {
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(
+            "raw_ansi {\n    type \"error\" prefix=\"\\\\e[4:3m\" suffix=\"\\\\e[24m\"\n    segment \"entries\" prefix=\"\\\\e[3m\" suffix=\"\\\\e[0m\"\n}",
+        ).unwrap();
+        assert_eq!(config.raw_ansi.types["error"].prefix, "\x1b[4:3m");
+        assert_eq!(config.raw_ansi.segments["entries"].suffix, "\x1b[0m");
+        assert!(manager.parse_kdl("raw_ansi {\n    type \"error\" prefix=\"\\\\e[4:3m\"\n}").unwrap_err().contains("does not turn off"));
+        assert!(manager.parse_kdl("raw_ansi {\n    type \"fatal\" prefix=\"\\\\e[1m\" suffix=\"\\\\e[0m\"\n}").is_err());
+
+        let mut map = BTreeMap::new();
+        map.insert("raw_ansi_segment_dnd".to_string(), "prefix=\\e[5m suffix=\\e[25m".to_string());
+        let config = Config::from_plugin_config(&map);
+        assert_eq!(config.raw_ansi.segments["dnd"].prefix, "\x1b[5m");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_pipe_names() {
+        let config = ConfigManager::new().parse_kdl(r#"pipes "claude-notify" "build-notify"
+strict_pipes true"#).unwrap();
+        assert_eq!(config.pipes, vec!["claude-notify", "build-notify"]);
+        assert!(config.strict_pipes && config.validate().is_ok());
+
+        let mut map = BTreeMap::new();
+        map.insert("pipes".to_string(), "claude-notify, build-notify".to_string());
+        assert_eq!(Config::from_plugin_config(&map).pipes, vec!["claude-notify", "build-notify"]);
+
+        let config = Config { strict_pipes: true, ..Config::default() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_check_options() {
+        assert!(!Config::default().update_check);
+        let config = ConfigManager::new().parse_kdl(r#"update_check true
+update_check_helper "/host/bin/latest-release""#).unwrap();
+        assert!(config.update_check);
+        assert_eq!(config.update_check_helper.as_deref(), Some("/host/bin/latest-release"));
+
+        let mut map = BTreeMap::new();
+        map.insert("update_check".to_string(), "true".to_string());
+        map.insert("update_check_helper".to_string(), " ".to_string());
+        let config = Config::from_plugin_config(&map);
+        assert!(config.update_check);
+        assert_eq!(config.update_check_helper, None);
+    }
+
+    #[test]
+    fn test_slo_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("slo_attention".to_string(), "300000".to_string());
+        config_map.insert("slo_escalate".to_string(), "true".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.slo["attention"], 300_000);
+        assert!(config.slo_escalate);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("slo { attention 300000; error 600000; }").unwrap();
+        assert_eq!(config.slo.len(), 2);
+        assert!(config.validate().is_ok());
+        assert!(manager.parse_kdl("slo { lunch 1000; }").is_err());
+    }
+
+    #[test]
+    fn test_exit_code_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("exit_code_3".to_string(), "lint failed".to_string());
+        config_map.insert("exit_code_x".to_string(), "ignored".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.exit_codes, BTreeMap::from([(3, "lint failed".to_string())]));
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"exit_codes { code 3 "lint failed"; code 137 "container memory limit"; }"#).unwrap();
+        assert_eq!(config.exit_codes[&137], "container memory limit");
+        assert!(manager.parse_kdl(r#"exit_codes { code "three" "lint failed"; }"#).is_err());
+    }
+
+    #[test]
+    fn test_exit_code_color_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("exit_code_colors".to_string(), "1:#ff6666, 2..=127:#ff0000, 128..:#cc0000, x:#000000".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.exit_code_colors.len(), 3);
+        assert_eq!(config.exit_code_colors[2], ExitCodeColor { start: 128, end: None, color: "#cc0000".to_string() });
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r##"exit_code_colors { range 1 "#ff6666"; range "2..=127" "#ff0000"; range "128.." "#cc0000"; }"##).unwrap();
+        assert_eq!(config.exit_code_colors.len(), 3);
+        assert_eq!(config.exit_code_colors[1].end, Some(127));
+        assert!(manager.parse_kdl(r##"exit_code_colors { range "128.." "dark red"; }"##).is_err());
+    }
+
+    #[test]
+    fn test_min_display_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("min_display_ms_success".to_string(), "3000".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.min_display_ms(&NotificationType::Success), 3000);
+        assert_eq!(config.min_display_ms(&NotificationType::Error), 0);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("min_display_ms { success 3000; info 1500; }").unwrap();
+        assert_eq!(config.min_display_ms(&NotificationType::Info), 1500);
+        assert!(manager.parse_kdl("min_display_ms { lunch 1000; }").is_err());
+    }
+
+    #[test]
+    fn test_auto_focus_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("auto_focus_attention".to_string(), "true".to_string());
+        config_map.insert("auto_focus_delay_ms".to_string(), "5000".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.auto_focus, BTreeMap::from([("attention".to_string(), true)]));
+        assert_eq!(config.auto_focus_delay_ms, 5000);
+        assert!(Config::default().auto_focus.is_empty());
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("auto_focus { attention true; error false; }\nauto_focus_delay_ms 1500").unwrap();
+        assert!(config.auto_focus["attention"]);
+        assert!(!config.auto_focus["error"]);
+        assert_eq!(config.auto_focus_delay_ms, 1500);
+        assert!(manager.parse_kdl("auto_focus { lunch true; }").is_err());
+    }
+
+    #[test]
+    fn test_rule_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("rule_quiet_ci".to_string(), "source=ci type=info -> priority=low".to_string());
+        config_map.insert("rules_dry_run".to_string(), "true".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.rules.len(), 1);
+        assert_eq!(config.rules[0].name, "quiet_ci");
+        assert!(config.rules_dry_run);
+        assert_eq!(config.noisy_source_threshold, DEFAULT_NOISY_SOURCE_THRESHOLD);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"rules { rule "no-heartbeat" match="message=heartbeat" action="drop"; }"#).unwrap();
+        assert_eq!(config.rules[0].name, "no-heartbeat");
+        assert_eq!(manager.parse_kdl("noisy_source_threshold 0").unwrap().noisy_source_threshold, 0);
+        assert!(manager.parse_kdl(r#"rules { rule "bad" match="type=lunch" action="drop"; }"#).is_err());
+    }
+
+    #[test]
+    fn test_remap_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("remap_flaky-tool".to_string(), "error=warning".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.remaps, vec![TypeRemap::parse("flaky-tool", "error", "warning").unwrap()]);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"remap {
+    source "flaky-tool" from "error" to "warning"
+    source "ci" from="attention" to="info"
+}"#).unwrap();
+        assert_eq!(config.remaps.len(), 2);
+        assert_eq!(config.remaps[1], TypeRemap::parse("ci", "attention", "info").unwrap());
+        assert!(manager.parse_kdl(r#"remap { source "ci" from "error" to "calm"; }"#).is_err());
+    }
+
+    #[test]
+    fn test_check_options() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("check_dirty".to_string(),
+            r#""git status --porcelain" every 300s notify-if-output warning "uncommitted changes""#.to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.checks.len(), 1);
+        assert_eq!(config.checks[0].name, "dirty");
+        assert_eq!(config.checks[0].command, "git status --porcelain");
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"checks { check "ci" run="make test" every="10m" notify="failed" type="error"; }"#).unwrap();
+        assert_eq!(config.checks[0].every_ms, 600_000);
+        assert!(manager.parse_kdl(r#"checks { check "fast" run="ls" every="1s"; }"#).is_err());
+    }
+
+    #[test]
+    fn test_on_notify_options() {
+        use crate::automation::LayoutAction;
+
+        let mut config_map = BTreeMap::new();
+        config_map.insert("on_notify_zoom".to_string(), "type=error -> fullscreen".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.on_notify.len(), 1);
+        assert_eq!(config.on_notify[0].action, LayoutAction::Fullscreen);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"
+            on_notify "tests" {
+                match "type=error source=tests"
+                action "run" "cargo test"
+                confirm false
+                cooldown "2m"
+            }
+        "#).unwrap();
+        let automation = &config.on_notify[0];
+        assert_eq!(automation.name, "tests");
+        assert_eq!(automation.action, LayoutAction::Run("cargo test".to_string()));
+        assert!(!automation.confirm);
+        assert_eq!(automation.cooldown_ms, 120_000);
+        assert!(manager.parse_kdl(r#"on_notify "bad" { match "type=error"; }"#).is_err());
+        assert!(manager.parse_kdl(r#"on_notify "bad" { action "explode"; }"#).is_err());
+    }
+
+    #[test]
+    fn test_channel_options() {
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("channels \"claude\" \"ci\"\nhidden_channels \"cron\"").unwrap();
+        assert_eq!(config.channels, vec!["claude", "ci"]);
+        assert_eq!(config.hidden_channels, vec!["cron"]);
+
+        let mut map = BTreeMap::new();
+        map.insert("hidden_channels".to_string(), "cron, ci".to_string());
+        assert_eq!(Config::from_plugin_config(&map).hidden_channels, vec!["cron", "ci"]);
+    }
+
+    #[test]
+    fn test_passive_detection_options() {
+        assert!(!Config::default().passive_detection);
+        assert!(Config::default().passive_shells.contains(&"zsh".to_string()));
+
+        let config = ConfigManager::new()
+            .parse_kdl("passive_detection true\npassive_template \"{command} done\"\npassive_shells \"fish\" \"nu\"")
+            .unwrap();
+        assert!(config.passive_detection);
+        assert_eq!(config.passive_template, "{command} done");
+        assert_eq!(config.passive_shells, vec!["fish", "nu"]);
+
+        let mut map = BTreeMap::new();
+        map.insert("passive_min_duration_ms".to_string(), "30000".to_string());
+        map.insert("passive_max_title_changes".to_string(), "0".to_string());
+        let config = Config::from_plugin_config(&map);
+        assert_eq!(config.passive_min_duration_ms, 30_000);
+        assert_eq!(config.passive_max_title_changes, 0);
+    }
+
+    #[test]
+    fn test_claude_pane_options() {
+        assert_eq!(Config::default().claude_pane_patterns, vec!["claude"]);
+        let config = ConfigManager::new()
+            .parse_kdl("claude_pane_patterns \"claude\" \"aider\"\nclaude_pane_color \"#FF8800\"")
+            .unwrap();
+        assert_eq!(config.claude_pane_patterns, vec!["claude", "aider"]);
+        assert_eq!(config.claude_pane_color, "#ff8800");
+
+        let mut map = BTreeMap::new();
+        map.insert("claude_pane_patterns".to_string(), String::new());
+        map.insert("claude_pane_color".to_string(), "orange".to_string());
+        let config = Config::from_plugin_config(&map);
+        assert!(config.claude_pane_patterns.is_empty());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_payload_limit_options() {
+        assert_eq!(Config::default().max_payload_bytes, 16 * 1024);
+        assert_eq!(Config::default().oversize_payload, OversizePolicy::Truncate);
+        let config = ConfigManager::new().parse_kdl("max_payload_bytes 4096\noversize_payload \"reject\"").unwrap();
+        assert_eq!(config.max_payload_bytes, 4096);
+        assert_eq!(config.oversize_payload, OversizePolicy::Reject);
+    }
+
+    #[test]
+    fn test_queue_strategy_option() {
+        assert_eq!(Config::default().queue_strategy, DequeueStrategy::Fifo);
+        let config = ConfigManager::new().parse_kdl("queue_strategy \"fair\"").unwrap();
+        assert_eq!(config.queue_strategy, DequeueStrategy::Fair);
+        let config = ConfigManager::new().parse_kdl("queue_strategy \"lifo\"").unwrap();
+        assert_eq!(config.queue_strategy, DequeueStrategy::Fifo);
+    }
+
+    #[test]
+    fn test_display_rate_option() {
+        assert_eq!(Config::default().display_rate, 10);
+        assert_eq!(ConfigManager::new().parse_kdl("display_rate 3").unwrap().display_rate, 3);
+        assert_eq!(ConfigManager::new().parse_kdl("display_rate -1").unwrap().display_rate, 0);
+    }
+
+    #[test]
+    fn test_project_root_options() {
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("project_roots \"/home/me/api\" \"/srv/my web\"").unwrap();
+        assert_eq!(config.project_roots, vec!["/home/me/api", "/srv/my web"]);
+
+        let mut map = BTreeMap::new();
+        map.insert("project_roots".to_string(), "/home/me/api, /srv/my web,".to_string());
+        assert_eq!(Config::from_plugin_config(&map).project_roots, vec!["/home/me/api", "/srv/my web"]);
+        assert!(Config::default().project_roots.is_empty());
+    }
+
+    #[test]
+    fn test_unread_count_file_options() {
+        let config = Config::default();
+        assert!(!config.unread_count_file);
+        assert_eq!(config.unread_count_path, DEFAULT_UNREAD_COUNT_PATH);
+
+        let mut config_map = BTreeMap::new();
+        config_map.insert("unread_count_file".to_string(), "true".to_string());
+        config_map.insert("unread_count_path".to_string(), "relative.count".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert!(config.unread_count_file);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_host_paths_are_normalized() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("host_platform".to_string(), "wsl".to_string());
+        config_map.insert("temp_dir".to_string(), r"C:\Temp\".to_string());
+        config_map.insert("unread_count_file".to_string(), "true".to_string());
+        config_map.insert("unread_count_path".to_string(), "{tmp}/notify.count".to_string());
+        let mut config = Config::from_plugin_config(&config_map);
+        assert!(config.validate().is_ok());
+        config.normalize_host_paths();
+        assert_eq!(config.temp_dir, "/mnt/c/Temp");
+        assert_eq!(config.unread_count_path, "/mnt/c/Temp/notify.count");
+        assert!(config.unavailable_features().is_empty());
+
+        let config = ConfigManager::new().parse_kdl(r#"host_platform "windows"; unread_count_file true"#).unwrap();
+        assert_eq!(config.unavailable_features(), vec!["unread_count_file"]);
+        assert!(ConfigManager::new().parse_kdl(r#"temp_dir "tmp""#).is_err());
+    }
+
+    #[test]
+    fn test_gradient_stops() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("gradient_error".to_string(), "#ef4444, #f97316 #ef4444".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.animation.gradient_stops["error"], vec!["#ef4444", "#f97316", "#ef4444"]);
+        assert!(config.validate().is_ok());
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r##"animation { gradient "info" "#000000" "#ffffff"; }"##).unwrap();
+        assert_eq!(config.animation.gradient_stops["info"], vec!["#000000", "#ffffff"]);
+        assert!(manager.parse_kdl(r##"animation { gradient "bogus" "#000000" "#ffffff"; }"##).is_err());
+        assert!(manager.parse_kdl(r##"animation { gradient "info" "#000000"; }"##).is_err());
+    }
+
+    #[test]
+    fn test_attention_policies() {
+        use crate::notification::Priority;
+
+        let mut config_map = BTreeMap::new();
+        config_map.insert("attention_finished".to_string(), "priority=low style=fade".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.attention.get(AttentionReason::Finished).priority, Priority::Low);
+        assert_eq!(config.attention.get(AttentionReason::Finished).style, AnimationStyle::Fade);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r##"attention { permission priority="high" color="#ff8800" defer=true; }"##).unwrap();
+        assert!(config.attention.get(AttentionReason::Permission).defer);
+        assert_eq!(config.attention.get(AttentionReason::Permission).priority, Priority::High);
+        assert_eq!(config.attention.get(AttentionReason::Permission).color.as_deref(), Some("#ff8800"));
+        assert!(manager.parse_kdl(r#"attention { lunch priority="high"; }"#).is_err());
+        assert_eq!(Config::default().attention_queue, AttentionQueue::Off);
+        assert_eq!(manager.parse_kdl(r#"attention_queue "priority""#).unwrap().attention_queue, AttentionQueue::Priority);
+        assert_eq!(manager.parse_kdl("attention_queue true").unwrap().attention_queue, AttentionQueue::Oldest);
+
+        let mut config = Config::default();
+        config.attention.get_mut(AttentionReason::Error).color = Some("red".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_animation_tick_rate() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("animation_tick_ms".to_string(), "16".to_string());
+        config_map.insert("animation_interpolate".to_string(), "true".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.animation.tick_ms, 16);
+        assert!(config.animation.interpolate);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("animation { tick_ms 1; interpolate true; }").unwrap();
+        assert_eq!(config.animation.tick_ms, 10);
+        assert!(config.animation.interpolate);
+        assert!(config.animation.priority_scaling);
+        assert!(!manager.parse_kdl("animation { priority_scaling false; }").unwrap().animation.priority_scaling);
+
+        let mut config = Config::default();
+        config.animation.tick_ms = 5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_layers_and_origins() {
+        let path = std::env::temp_dir().join("zellij-notify-test-layers.kdl");
+        std::fs::write(&path, "notification_timeout_ms 60000\nbig_mode true\nfocus_ring false\n").unwrap();
+        let plugin_config = BTreeMap::from([
+            ("config_file".to_string(), path.display().to_string()),
+            ("notification_timeout_ms".to_string(), "120000".to_string()),
+        ]);
+        let mut manager = ConfigManager::new().with_plugin_config(&plugin_config);
+        let config = manager.reload().unwrap();
+        assert_eq!(config.notification_timeout_ms, 120_000);
+        assert!(config.big_mode);
+        assert!(!config.focus_ring && Config::default().focus_ring);
+        assert_eq!(manager.describe(&config, "notification_timeout_ms"), "notification_timeout_ms = 120000  (plugin)");
+        assert_eq!(manager.describe(&config, "big_mode"), "big_mode = true  (file)");
+        assert_eq!(manager.describe(&config, "debug"), "debug = false  (default)");
+        assert_eq!(manager.profile(&config), format!("file {}, plugin (1 key), theme default", path.display()));
+
+        // The timeout is set by both the file and the plugin configuration; warned about once
+        let drift = manager.new_drift();
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].line(), "notification_timeout_ms (file, plugin: plugin wins)");
+        manager.reload().unwrap();
+        assert!(manager.new_drift().is_empty());
+        assert_eq!(manager.drift().len(), 1);
+
+        // Runtime overrides win until removed
+        manager.set_override("notification_timeout_ms", "30000");
+        let config = manager.reload().unwrap();
+        assert_eq!(manager.describe(&config, "notification_timeout_ms"), "notification_timeout_ms = 30000  (runtime)");
+        assert!(manager.profile(&config).contains("plugin (1 key), 1 runtime override"));
+        assert!(manager.remove_override("notification_timeout_ms"));
+        assert_eq!(manager.reload().unwrap().notification_timeout_ms, 120_000);
+        std::fs::remove_file(&path).unwrap();
+
+        // A missing file fails the reload; the other layers still resolve
+        assert!(manager.reload().is_err());
+        assert_eq!(manager.reload_without_files().notification_timeout_ms, 120_000);
+    }
+
+    #[test]
+    fn test_plugin_config_wins_over_theme_file() {
+        let path = std::env::temp_dir().join("zellij-notify-test-theme-layer.kdl");
+        std::fs::write(&path, "theme \"mytheme\" {\n    error_color \"#ff0000\"\n    info_color \"#00ffff\"\n}\n").unwrap();
+        let plugin_config = BTreeMap::from([
+            ("theme_file".to_string(), path.display().to_string()),
+            ("error_color".to_string(), "#00ff00".to_string()),
+        ]);
+        let mut manager = ConfigManager::new().with_plugin_config(&plugin_config);
+        let config = manager.reload().unwrap();
+        assert_eq!(config.theme.error_color, "#00ff00");
+        assert_eq!(config.theme.info_color, "#00ffff");
+        assert_eq!(manager.describe(&config, "theme.error_color"), "theme.error_color = \"#00ff00\"  (plugin)");
+        assert_eq!(manager.describe(&config, "theme.info_color"), "theme.info_color = \"#00ffff\"  (file)");
+
+        // Runtime overrides still win over both
+        manager.set_override("error_color", "#0000ff");
+        assert_eq!(manager.reload().unwrap().theme.error_color, "#0000ff");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_theme_file() {
+        let theme_file = ThemeFile::parse(r##"
+            theme "mytheme" {
+                error_color "#FF0000"
+                gradient "error" "#ff0000" "#00ff00"
+            }
+        "##).unwrap();
+        assert_eq!(theme_file.theme.name, "mytheme");
+        assert_eq!(theme_file.theme.error_color, "#ff0000");
+        assert_eq!(theme_file.theme.success_color, ThemeConfig::default().success_color);
+
+        // Preset base, top-level fields
+        let preset = ThemeFile::parse(r##"theme "nord"; info_color "#000000""##).unwrap();
+        assert_eq!(preset.theme.success_color, ThemeConfig::from_preset("nord").success_color);
+        assert_eq!(preset.theme.info_color, "#000000");
+
+        assert!(ThemeFile::parse(r##"error_color "red""##).is_err());
+        assert!(ThemeFile::parse(r##"border_color "#000000""##).is_err());
+        assert!(ThemeFile::parse(r##"gradient "error" "#000000""##).is_err());
+
+        let mut config = Config::default();
+        theme_file.apply(&mut config);
+        assert_eq!(config.theme.error_color, "#ff0000");
+        assert_eq!(config.animation.gradient_stops["error"], vec!["#ff0000", "#00ff00"]);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"theme_file "/host/mytheme.kdl""#).unwrap();
+        assert_eq!(config.theme_file.as_deref(), Some("/host/mytheme.kdl"));
+    }
+
     #[test]
     fn test_animation_style_parsing() {
         assert_eq!(AnimationStyle::from_str("pulse"), AnimationStyle::Pulse);
@@ -659,6 +3692,258 @@ mod tests {
         assert_eq!(AnimationStyle::from_str("fade"), AnimationStyle::Fade);
         assert_eq!(AnimationStyle::from_str("breathe"), AnimationStyle::Breathe);
         assert_eq!(AnimationStyle::from_str("none"), AnimationStyle::None);
+        assert_eq!(AnimationStyle::from_str("gradient"), AnimationStyle::Gradient);
         assert_eq!(AnimationStyle::from_str("invalid"), AnimationStyle::Pulse);
     }
+
+    #[test]
+    fn test_animation_target_parsing() {
+        assert_eq!(AnimationTarget::from_str("Background"), AnimationTarget::Background);
+        assert_eq!(AnimationTarget::from_str("both"), AnimationTarget::Both);
+        assert_eq!(AnimationTarget::from_str("inverse"), AnimationTarget::Inverse);
+        assert_eq!(AnimationTarget::from_str("sideways"), AnimationTarget::Foreground);
+        let config = ConfigManager::new().parse_kdl(r#"animation {
+    target "both"
+    hidden_tabs "skip"
+}"#).unwrap();
+        assert_eq!(config.animation.target, AnimationTarget::Both);
+        assert_eq!(config.animation.hidden_tabs, HiddenTabAnimation::Skip);
+        assert_eq!(Config::default().animation.target, AnimationTarget::Foreground);
+        assert_eq!(Config::default().animation.hidden_tabs, HiddenTabAnimation::Pause);
+        assert_eq!(HiddenTabAnimation::from_str("Run"), HiddenTabAnimation::Run);
+    }
+
+    #[test]
+    fn test_parse_kdl_clear_all_confirm() {
+        let manager = ConfigManager::new();
+        assert!(manager.parse_kdl("enabled true").unwrap().clear_all_confirm);
+        assert!(!manager.parse_kdl("clear_all_confirm false").unwrap().clear_all_confirm);
+        assert_eq!(manager.parse_kdl("enabled true").unwrap().chord_leader, DEFAULT_CHORD_LEADER);
+        let config = manager.parse_kdl("chord_leader \"Alt n\"").unwrap();
+        assert_eq!(config.chord_leader, "Alt n");
+        assert!(config.validate().is_ok());
+        assert!(manager.parse_kdl("chord_leader \"Ctrl\"").is_err());
+        assert!(manager.parse_kdl("chord_leader \"off\"").unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn test_action_options() {
+        let config = Config::default();
+        assert!(!config.actions_dry_run);
+        assert_eq!(config.action_rate_limit, DEFAULT_ACTION_RATE_LIMIT);
+
+        let mut config_map = BTreeMap::new();
+        config_map.insert("actions_dry_run".to_string(), "true".to_string());
+        config_map.insert("action_rate_limit".to_string(), "5".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert!(config.actions_dry_run);
+        assert_eq!(config.action_rate_limit, 5);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("actions_dry_run true\naction_rate_limit 0").unwrap();
+        assert!(config.actions_dry_run);
+        assert_eq!(config.action_rate_limit, 0);
+    }
+
+    #[test]
+    fn test_title_mirror_parsing() {
+        assert_eq!(TitleMirror::from_str("osc"), TitleMirror::Osc);
+        assert_eq!(TitleMirror::from_str("Session"), TitleMirror::Osc);
+        assert_eq!(TitleMirror::from_str("bogus"), TitleMirror::Off);
+        assert_eq!(Config::default().title_mirror, TitleMirror::Off);
+    }
+
+    #[test]
+    fn test_focused_pane_suppression_parsing() {
+        assert_eq!(FocusedPaneSuppression::from_str("ack"), FocusedPaneSuppression::Acknowledge);
+        assert_eq!(FocusedPaneSuppression::from_str("History"), FocusedPaneSuppression::HistoryOnly);
+        assert_eq!(FocusedPaneSuppression::from_str("false"), FocusedPaneSuppression::Off);
+        assert_eq!(Config::default().suppress_for_focused_pane, FocusedPaneSuppression::Off);
+    }
+
+    #[test]
+    fn test_webhook_events_option() {
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"webhook_events "display" "ack""#).unwrap();
+        assert_eq!(config.webhook_events, vec!["display", "ack"]);
+        assert!(manager.parse_kdl(r#"webhook_events "seen""#).unwrap_err().contains("unknown webhook event: seen"));
+    }
+
+    #[test]
+    fn test_mirror_option() {
+        assert!(!Config::default().mirror);
+        let mut config_map = BTreeMap::new();
+        config_map.insert("mirror".to_string(), "true".to_string());
+        assert!(Config::from_plugin_config(&config_map).mirror);
+        assert!(ConfigManager::new().parse_kdl("mirror true").unwrap().mirror);
+    }
+
+    #[test]
+    fn test_text_log_follows_screen_reader() {
+        let mut config = Config::default();
+        assert!(!config.text_log_enabled());
+        config.accessibility.screen_reader = true;
+        assert!(config.text_log_enabled());
+        config.text_log = Some(false);
+        assert!(!config.text_log_enabled());
+
+        let mut config_map = BTreeMap::new();
+        config_map.insert("text_log".to_string(), "true".to_string());
+        assert_eq!(Config::from_plugin_config(&config_map).text_log, Some(true));
+        assert_eq!(ConfigManager::new().parse_kdl("text_log false").unwrap().text_log, Some(false));
+    }
+
+    #[test]
+    fn test_instance_election_option() {
+        assert!(Config::default().instance_election);
+        let mut config_map = BTreeMap::new();
+        config_map.insert("instance_election".to_string(), "false".to_string());
+        assert!(!Config::from_plugin_config(&config_map).instance_election);
+        assert!(!ConfigManager::new().parse_kdl("instance_election false").unwrap().instance_election);
+    }
+
+    #[test]
+    fn test_ingest_file_options() {
+        let config = Config::default();
+        assert_eq!(config.ingest_file, None);
+        assert_eq!(config.ingest_file_poll_ms, DEFAULT_INGEST_FILE_POLL_MS);
+
+        let mut config_map = BTreeMap::new();
+        config_map.insert("ingest_file".to_string(), "/host/notify.jsonl".to_string());
+        config_map.insert("ingest_file_poll_ms".to_string(), "500".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.ingest_file.as_deref(), Some("/host/notify.jsonl"));
+        assert_eq!(config.ingest_file_poll_ms, 500);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("ingest_file \"/host/remote/notify.jsonl\"\ningest_file_poll_ms 2000").unwrap();
+        assert_eq!(config.ingest_file.as_deref(), Some("/host/remote/notify.jsonl"));
+        assert_eq!(config.ingest_file_poll_ms, 2000);
+        assert!(manager.parse_kdl("ingest_file_poll_ms 50").is_err());
+    }
+
+    #[test]
+    fn test_low_power_options() {
+        let config = Config::default();
+        assert!(!config.low_power);
+        assert_eq!(config.tick_ms(), DEFAULT_TICK_MS);
+        assert!(config.effective_animation().enabled);
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("low_power true\nlow_power_tick_ms 2000").unwrap();
+        assert_eq!(config.tick_ms(), 2000);
+        assert!(config.motion_reduced());
+        assert!(!config.effective_animation().enabled);
+        assert!(manager.parse_kdl("low_power_tick_ms 50").is_err());
+    }
+
+    #[test]
+    fn test_history_compaction_options() {
+        assert_eq!(HistoryCompaction::from_str("Similar"), HistoryCompaction::Similar);
+        assert_eq!(HistoryCompaction::from_str("anything"), HistoryCompaction::Off);
+        assert_eq!(Config::default().history_compaction, HistoryCompaction::Off);
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("history_compaction \"exact\"").unwrap();
+        assert_eq!(config.history_compaction, HistoryCompaction::Exact);
+    }
+
+    #[test]
+    fn test_missing_pane_options() {
+        assert_eq!(MissingPane::from_str("Drop"), MissingPane::Drop);
+        assert_eq!(MissingPane::from_str("tab-level"), MissingPane::Tab);
+        assert_eq!(MissingPane::from_str("anything"), MissingPane::Hold);
+        assert_eq!(Config::default().missing_pane, MissingPane::Hold);
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("missing_pane \"tab\"\nmissing_pane_hold_ms 1500").unwrap();
+        assert_eq!((config.missing_pane, config.missing_pane_hold_ms), (MissingPane::Tab, 1500));
+    }
+
+    #[test]
+    fn test_theme_set_and_dump() {
+        let mut theme = ThemeConfig::default();
+        assert!(theme.set_color("success_color", "#00FF88").is_ok());
+        assert_eq!(theme.success_color, "#00ff88");
+        assert!(theme.set_color("success_color", "green").is_err());
+        assert!(theme.set_color("border_color", "#000000").is_err());
+
+        // The dump round-trips through the KDL parser
+        theme.set_color("dimmed_color", "#123456").unwrap();
+        let config = ConfigManager::new().parse_kdl(&theme.to_kdl()).unwrap();
+        assert_eq!(config.theme.success_color, "#00ff88");
+        assert_eq!(config.theme.dimmed_color, "#123456");
+    }
+
+    #[test]
+    fn test_status_order_parsing() {
+        assert_eq!(StatusOrder::from_str("pane"), StatusOrder::Pane);
+        assert_eq!(StatusOrder::from_str("urgency"), StatusOrder::Urgency);
+        assert_eq!(Config::default().status_order, StatusOrder::Urgency);
+        let config = ConfigManager::new().parse_kdl(r#"status_order "pane""#).unwrap();
+        assert_eq!(config.status_order, StatusOrder::Pane);
+        assert_eq!(Config::default().wide_layout_min_cols, 81);
+        assert_eq!(ConfigManager::new().parse_kdl("wide_layout_min_cols 0").unwrap().wide_layout_min_cols, 0);
+        assert_eq!(Config::default().compact_layout_min_cols, 30);
+        assert_eq!(ConfigManager::new().parse_kdl("compact_layout_min_cols 40").unwrap().compact_layout_min_cols, 40);
+        assert!(!Config::default().entry_hotkeys);
+        assert!(ConfigManager::new().parse_kdl("entry_hotkeys true").unwrap().entry_hotkeys);
+        assert!(!Config::default().entry_chips);
+        assert!(ConfigManager::new().parse_kdl("entry_chips true").unwrap().entry_chips);
+        assert_eq!(Config::default().ascii_only, AsciiMode::Auto);
+        assert_eq!(ConfigManager::new().parse_kdl("ascii_only true").unwrap().ascii_only, AsciiMode::On);
+        assert_eq!(ConfigManager::new().parse_kdl(r#"ascii_only "off""#).unwrap().ascii_only, AsciiMode::Off);
+        assert_eq!(Config::default().icon_width_policy, IconWidthPolicy::Any);
+        assert_eq!(ConfigManager::new().parse_kdl(r#"icon_width_policy "single""#).unwrap().icon_width_policy,
+            IconWidthPolicy::Single);
+    }
+
+    #[test]
+    fn test_tab_badge_placement_parsing() {
+        assert_eq!(TabBadgePlacement::from_str("Prefix"), TabBadgePlacement::Prefix);
+        assert_eq!(TabBadgePlacement::from_str("counter"), TabBadgePlacement::Replace);
+        assert_eq!(TabBadgePlacement::from_str("bogus"), TabBadgePlacement::Suffix);
+        assert!(!Config::default().tab_name_badges);
+    }
+
+    #[test]
+    fn test_sound_theme_parsing() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("sound_theme".to_string(), "build_box".to_string());
+        config_map.insert("sound_pack_build_box_error".to_string(), "/sounds/fail.oga".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert_eq!(config.sound_packs["build_box"]["error"], "/sounds/fail.oga");
+        assert!(config.validate().is_ok());
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl(r#"sound_theme "mine"
+sound_packs {
+    pack "mine" {
+        success "say done"
+    }
+}"#).unwrap();
+        assert_eq!(config.sound_packs["mine"]["success"], "say done");
+        assert!(config.validate().is_ok());
+        assert!(manager.parse_kdl("sound_theme \"quiet\"").unwrap().validate().is_err());
+        assert!(manager.parse_kdl("sound_packs { pack \"mine\" { lunch \"bell.oga\"; }; }").unwrap().validate().is_err());
+    }
+
+    #[test]
+    fn test_experimental_features_parsing() {
+        let mut config_map = BTreeMap::new();
+        config_map.insert("experimental_features".to_string(), "toasts, mouse".to_string());
+        let config = Config::from_plugin_config(&config_map);
+        assert!(config.experimental("toasts"));
+        assert!(!config.experimental("confetti"));
+
+        let manager = ConfigManager::new();
+        let config = manager.parse_kdl("features { experimental \"toasts\" \"mouse\"; }").unwrap();
+        assert_eq!(config.experimental_features, vec!["toasts", "mouse"]);
+        assert!(!Config::default().experimental("toasts"));
+    }
+
+    #[test]
+    fn test_toast_position_parsing() {
+        assert_eq!(ToastPosition::from_str("bottom-left"), ToastPosition::BottomLeft);
+        assert_eq!(ToastPosition::from_str("TOP_LEFT"), ToastPosition::TopLeft);
+        assert_eq!(ToastPosition::from_str("unknown"), ToastPosition::TopRight);
+    }
 }
@@ -0,0 +1,182 @@
+//! Sender confirmation module for Zellij Visual Notifications
+//!
+//! A sender can ask a lightweight yes/no question with a `confirm` message (`message`,
+//! `timeout_ms`, `default`). The question is shown in the plugin pane with a countdown: `y` or `n`
+//! answers it, Enter takes the default, and so does the countdown running out. The answer goes
+//! back as one JSON line on the `zellij pipe` command that asked (`{"id": "...", "answer": "yes",
+//! "by": "user"}`), which is then released. Questions asked while another is shown wait their
+//! turn; their countdown starts when they come up.
+
+use std::collections::VecDeque;
+
+/// Countdown of a question that does not set `timeout_ms` (ms)
+pub const DEFAULT_CONFIRM_TIMEOUT_MS: u64 = 30_000;
+
+/// A yes/no question from a sender
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmRequest {
+    /// Sender-chosen ID, echoed in the answer
+    pub id: String,
+    /// Question shown to the user
+    pub question: String,
+    /// Pane the question is about
+    pub pane_id: Option<u32>,
+    /// Time the user has to answer (ms)
+    pub timeout_ms: u64,
+    /// Answer taken on Enter or when the countdown runs out
+    pub default: bool,
+    /// CLI pipe the answer is written to (None: log only)
+    pub pipe_id: Option<String>,
+}
+
+impl ConfirmRequest {
+    /// Parse a `default` value (`yes`/`y`/`true`; anything else is no)
+    pub fn parse_default(value: &str) -> bool {
+        matches!(value.trim().to_lowercase().as_str(), "yes" | "y" | "true" | "1")
+    }
+}
+
+/// The answer to a question
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmAnswer {
+    /// Question ID
+    pub id: String,
+    /// CLI pipe to write to (None: log only)
+    pub pipe_id: Option<String>,
+    /// Yes or no
+    pub yes: bool,
+    /// Whether the countdown ran out before the user answered
+    pub timed_out: bool,
+}
+
+impl ConfirmAnswer {
+    /// JSON line sent to the sender, newline-terminated
+    pub fn line(&self) -> String {
+        format!("{}\n", serde_json::json!({
+            "id": self.id,
+            "answer": if self.yes { "yes" } else { "no" },
+            "by": if self.timed_out { "timeout" } else { "user" },
+        }))
+    }
+}
+
+/// A question waiting for its answer
+#[derive(Debug, Clone)]
+struct PendingConfirm {
+    request: ConfirmRequest,
+    /// Plugin time at which the default is taken (ms, set when the question comes up)
+    deadline_ms: u64,
+}
+
+/// Questions in the order they were asked; the first one is shown
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmPrompts {
+    pending: VecDeque<PendingConfirm>,
+}
+
+impl ConfirmPrompts {
+    /// Queue a question, starting its countdown if nothing else is shown
+    pub fn ask(&mut self, request: ConfirmRequest, now_ms: u64) {
+        let deadline_ms = now_ms + request.timeout_ms;
+        self.pending.push_back(PendingConfirm { request, deadline_ms });
+    }
+
+    /// The question shown, if any
+    pub fn current(&self) -> Option<&ConfirmRequest> {
+        self.pending.front().map(|pending| &pending.request)
+    }
+
+    /// Questions waiting, the one shown included
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Prompt line of the question shown, e.g. `Deploy now? y/n (default no, 12s)`
+    pub fn prompt(&self, now_ms: u64) -> Option<String> {
+        let pending = self.pending.front()?;
+        let waiting = match self.pending.len() {
+            1 => String::new(),
+            n => format!(" +{} more", n - 1),
+        };
+        Some(format!("{} y/n (default {}, {}s){}",
+            pending.request.question,
+            if pending.request.default { "yes" } else { "no" },
+            pending.deadline_ms.saturating_sub(now_ms).div_ceil(1000),
+            waiting))
+    }
+
+    /// Answer the question shown (`None`: take its default)
+    pub fn answer(&mut self, yes: Option<bool>, now_ms: u64) -> Option<ConfirmAnswer> {
+        let pending = self.pending.pop_front()?;
+        self.start_next(now_ms);
+        Some(ConfirmAnswer {
+            yes: yes.unwrap_or(pending.request.default),
+            id: pending.request.id,
+            pipe_id: pending.request.pipe_id,
+            timed_out: false,
+        })
+    }
+
+    /// Take the default of the question shown once its countdown has run out
+    pub fn expire(&mut self, now_ms: u64) -> Option<ConfirmAnswer> {
+        if self.pending.front()?.deadline_ms > now_ms {
+            return None;
+        }
+        let mut answer = self.answer(None, now_ms)?;
+        answer.timed_out = true;
+        Some(answer)
+    }
+
+    /// Restart the countdown of the question that comes up next
+    fn start_next(&mut self, now_ms: u64) {
+        if let Some(next) = self.pending.front_mut() {
+            next.deadline_ms = now_ms + next.request.timeout_ms;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: &str, default: bool) -> ConfirmRequest {
+        ConfirmRequest {
+            id: id.to_string(),
+            question: format!("Run {}?", id),
+            pane_id: None,
+            timeout_ms: 10_000,
+            default,
+            pipe_id: Some("pipe-1".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_answers_in_order_with_countdown() {
+        let mut prompts = ConfirmPrompts::default();
+        prompts.ask(request("a", false), 0);
+        prompts.ask(request("b", true), 1_000);
+        assert_eq!(prompts.prompt(2_500).unwrap(), "Run a? y/n (default no, 8s) +1 more");
+
+        let answer = prompts.answer(Some(true), 3_000).unwrap();
+        assert_eq!(answer.line(), "{\"answer\":\"yes\",\"by\":\"user\",\"id\":\"a\"}\n");
+        assert_eq!(answer.pipe_id.as_deref(), Some("pipe-1"));
+
+        // The next question's countdown starts when it comes up
+        assert_eq!(prompts.current().unwrap().id, "b");
+        assert_eq!(prompts.expire(12_999), None);
+        let answer = prompts.expire(13_000).unwrap();
+        assert!(answer.yes && answer.timed_out);
+        assert_eq!(prompts.len(), 0);
+        assert_eq!(prompts.answer(None, 14_000), None);
+    }
+
+    #[test]
+    fn test_enter_takes_default() {
+        let mut prompts = ConfirmPrompts::default();
+        prompts.ask(request("a", true), 0);
+        let answer = prompts.answer(None, 100).unwrap();
+        assert!(answer.yes && !answer.timed_out);
+        assert!(ConfirmRequest::parse_default("Yes"));
+        assert!(!ConfirmRequest::parse_default("maybe"));
+    }
+}
@@ -0,0 +1,88 @@
+//! Correlation module for Zellij Visual Notifications
+//!
+//! Notifications carrying the same `correlation_id` belong to one workflow spread over several
+//! panes (server, tests, Claude). Their status entries share an accent color, and the group view
+//! lists every member of each group together.
+
+use std::collections::BTreeMap;
+use crate::markup;
+use crate::state::VisualState;
+
+/// Displayed notifications sharing a correlation ID
+#[derive(Debug, Clone)]
+pub struct CorrelationGroup<'a> {
+    /// Correlation ID
+    pub id: &'a str,
+    /// Member panes and their visual states, in pane order
+    pub members: Vec<(u32, &'a VisualState)>,
+}
+
+impl CorrelationGroup<'_> {
+    /// Header line, e.g. `deploy-42 (3)`
+    pub fn header(&self) -> String {
+        format!("{} ({})", self.id, self.members.len())
+    }
+
+    /// One line per member, e.g. `  pane 3  error      Tests failed`
+    pub fn member_lines(&self) -> Vec<String> {
+        self.members.iter()
+            .map(|(pane_id, state)| format!("  pane {:<3} {:<10} {}",
+                pane_id,
+                state.notification_type.as_ref().map(|t| t.name()).unwrap_or("-"),
+                markup::strip(state.notification_message.as_deref().unwrap_or_default())))
+            .collect()
+    }
+}
+
+/// Groups of active notifications by correlation ID, in ID order
+pub fn groups(pane_states: &BTreeMap<u32, VisualState>) -> Vec<CorrelationGroup<'_>> {
+    let mut groups: BTreeMap<&str, Vec<(u32, &VisualState)>> = BTreeMap::new();
+    for (pane_id, state) in pane_states.iter().filter(|(_, state)| state.is_shown()) {
+        if let Some(id) = state.correlation_id.as_deref() {
+            groups.entry(id).or_default().push((*pane_id, state));
+        }
+    }
+    groups.into_iter().map(|(id, members)| CorrelationGroup { id, members }).collect()
+}
+
+/// Plain-text listing of all groups and their members
+pub fn describe(pane_states: &BTreeMap<u32, VisualState>) -> String {
+    let groups = groups(pane_states);
+    if groups.is_empty() {
+        return "no correlated notifications".to_string();
+    }
+    groups.iter()
+        .flat_map(|group| std::iter::once(group.header()).chain(group.member_lines()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationType;
+
+    fn member(notification_type: NotificationType, message: &str, correlation_id: Option<&str>) -> VisualState {
+        VisualState {
+            notification_type: Some(notification_type),
+            notification_message: Some(message.to_string()),
+            correlation_id: correlation_id.map(String::from),
+            ..VisualState::default()
+        }
+    }
+
+    #[test]
+    fn test_groups_by_correlation_id() {
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(5, member(NotificationType::Success, "Server up", Some("deploy-42")));
+        pane_states.insert(3, member(NotificationType::Error, "Tests failed", Some("deploy-42")));
+        pane_states.insert(4, member(NotificationType::Info, "Unrelated", None));
+
+        let groups = groups(&pane_states);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].header(), "deploy-42 (2)");
+        assert_eq!(groups[0].members.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![3, 5]);
+        assert!(describe(&pane_states).contains("pane 3   error      Tests failed"));
+        assert_eq!(describe(&BTreeMap::new()), "no correlated notifications");
+    }
+}
@@ -0,0 +1,234 @@
+//! Webhook delivery module for Zellij Visual Notifications
+//!
+//! Queues outbound webhook requests and retries failed deliveries with exponential backoff.
+//! Requests are issued from the timer tick and completed by host `WebRequestResult` events, so
+//! a slow or flaky endpoint never blocks the render loop or silently drops an escalation.
+
+use std::collections::VecDeque;
+
+/// Context key carrying the delivery ID through the host web request
+pub const DELIVERY_CONTEXT_KEY: &str = "delivery_id";
+
+/// Upper bound on the retry delay (ms)
+const MAX_RETRY_DELAY_MS: u64 = 60_000;
+
+/// Upper bound on deliveries waiting for (re)send
+const MAX_PENDING_DELIVERIES: usize = 100;
+
+/// A webhook request awaiting delivery
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delivery {
+    /// Delivery ID (sent as request context)
+    pub id: u64,
+    /// Target URL
+    pub url: String,
+    /// JSON body
+    pub body: String,
+    /// Attempts made so far
+    pub attempts: u32,
+    /// Time of the next attempt (ms)
+    next_attempt_ms: u64,
+    /// Whether a request is currently outstanding
+    in_flight: bool,
+}
+
+/// Delivery counters exposed through the `sink_status` command
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeliveryStats {
+    /// Deliveries that succeeded
+    pub delivered: u64,
+    /// Failed attempts (including ones later retried)
+    pub failed_attempts: u64,
+    /// Deliveries abandoned after exhausting retries or overflowing the queue
+    pub dropped: u64,
+    /// Deliveries waiting for an attempt or a response
+    pub pending: usize,
+    /// Most recent failure description
+    pub last_error: Option<String>,
+}
+
+impl DeliveryStats {
+    /// One-line summary for pipe output and logs
+    pub fn summary(&self) -> String {
+        let mut line = format!("webhook: {} delivered, {} failed attempts, {} dropped, {} pending",
+            self.delivered, self.failed_attempts, self.dropped, self.pending);
+        if let Some(ref error) = self.last_error {
+            line.push_str(&format!(" (last error: {})", error));
+        }
+        line
+    }
+}
+
+/// Queue of webhook deliveries with retry and backoff
+#[derive(Debug, Clone)]
+pub struct DeliveryQueue {
+    /// Deliveries not yet completed
+    deliveries: VecDeque<Delivery>,
+    /// Maximum attempts per delivery
+    max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry (ms)
+    retry_base_ms: u64,
+    /// Next delivery ID
+    next_id: u64,
+    /// Delivery counters
+    stats: DeliveryStats,
+}
+
+impl Default for DeliveryQueue {
+    fn default() -> Self {
+        Self::new(5, 1000)
+    }
+}
+
+impl DeliveryQueue {
+    /// Create a delivery queue with a retry policy
+    pub fn new(max_attempts: u32, retry_base_ms: u64) -> Self {
+        Self {
+            deliveries: VecDeque::new(),
+            max_attempts: max_attempts.max(1),
+            retry_base_ms,
+            next_id: 0,
+            stats: DeliveryStats::default(),
+        }
+    }
+
+    /// Queue a request for delivery on the next tick
+    pub fn submit(&mut self, url: &str, body: &str, now_ms: u64) -> u64 {
+        if self.deliveries.len() >= MAX_PENDING_DELIVERIES {
+            // Drop the oldest delivery that is not awaiting a response
+            if let Some(index) = self.deliveries.iter().position(|d| !d.in_flight) {
+                self.deliveries.remove(index);
+                self.stats.dropped += 1;
+            }
+        }
+
+        self.next_id += 1;
+        self.deliveries.push_back(Delivery {
+            id: self.next_id,
+            url: url.to_string(),
+            body: body.to_string(),
+            attempts: 0,
+            next_attempt_ms: now_ms,
+            in_flight: false,
+        });
+        self.next_id
+    }
+
+    /// Take deliveries due for an attempt, marking them in flight
+    pub fn due(&mut self, now_ms: u64) -> Vec<Delivery> {
+        let mut due = Vec::new();
+        for delivery in self.deliveries.iter_mut() {
+            if !delivery.in_flight && delivery.next_attempt_ms <= now_ms {
+                delivery.in_flight = true;
+                delivery.attempts += 1;
+                due.push(delivery.clone());
+            }
+        }
+        due
+    }
+
+    /// Record the outcome of a delivery attempt
+    pub fn complete(&mut self, id: u64, result: Result<(), String>, now_ms: u64) {
+        let index = match self.deliveries.iter().position(|d| d.id == id) {
+            Some(index) => index,
+            None => return,
+        };
+
+        match result {
+            Ok(()) => {
+                self.deliveries.remove(index);
+                self.stats.delivered += 1;
+            }
+            Err(error) => {
+                self.stats.failed_attempts += 1;
+                self.stats.last_error = Some(error);
+                let attempts = self.deliveries[index].attempts;
+                if attempts >= self.max_attempts {
+                    self.deliveries.remove(index);
+                    self.stats.dropped += 1;
+                } else {
+                    let retry_at = now_ms + self.retry_delay_ms(attempts);
+                    let delivery = &mut self.deliveries[index];
+                    delivery.in_flight = false;
+                    delivery.next_attempt_ms = retry_at;
+                }
+            }
+        }
+    }
+
+    /// Backoff before the retry following the given attempt (ms)
+    pub fn retry_delay_ms(&self, attempts: u32) -> u64 {
+        let exponent = attempts.saturating_sub(1).min(16);
+        self.retry_base_ms.saturating_mul(1 << exponent).min(MAX_RETRY_DELAY_MS)
+    }
+
+    /// Current delivery counters
+    pub fn stats(&self) -> DeliveryStats {
+        DeliveryStats {
+            pending: self.deliveries.len(),
+            ..self.stats.clone()
+        }
+    }
+}
+
+/// Interpret an HTTP status as a delivery outcome
+pub fn status_result(status: u16) -> Result<(), String> {
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_with_backoff_until_delivered() {
+        let mut queue = DeliveryQueue::new(3, 1000);
+        let id = queue.submit("http://hook", "{}", 0);
+
+        assert_eq!(queue.due(0).len(), 1);
+        assert!(queue.due(0).is_empty()); // in flight
+        queue.complete(id, Err("HTTP 503".to_string()), 100);
+
+        // First retry after the base delay, second after double
+        assert!(queue.due(1000).is_empty());
+        assert_eq!(queue.due(1100)[0].attempts, 2);
+        queue.complete(id, Err("HTTP 503".to_string()), 1200);
+        assert!(queue.due(3100).is_empty());
+        assert_eq!(queue.due(3200).len(), 1);
+        queue.complete(id, status_result(204), 3300);
+
+        let stats = queue.stats();
+        assert_eq!(stats.delivered, 1);
+        assert_eq!(stats.failed_attempts, 2);
+        assert_eq!(stats.pending, 0);
+        assert_eq!(stats.last_error.as_deref(), Some("HTTP 503"));
+    }
+
+    #[test]
+    fn test_drop_after_max_attempts() {
+        let mut queue = DeliveryQueue::new(2, 10);
+        let id = queue.submit("http://hook", "{}", 0);
+
+        queue.due(0);
+        queue.complete(id, Err("timeout".to_string()), 0);
+        queue.due(10);
+        queue.complete(id, Err("timeout".to_string()), 10);
+
+        let stats = queue.stats();
+        assert_eq!(stats.dropped, 1);
+        assert_eq!(stats.pending, 0);
+        assert!(stats.summary().contains("1 dropped"));
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        let queue = DeliveryQueue::new(10, 1000);
+        assert_eq!(queue.retry_delay_ms(1), 1000);
+        assert_eq!(queue.retry_delay_ms(3), 4000);
+        assert_eq!(queue.retry_delay_ms(20), MAX_RETRY_DELAY_MS);
+    }
+}
@@ -0,0 +1,92 @@
+//! Desktop daemon module for Zellij Visual Notifications
+//!
+//! The `desktop_daemon` sink keeps a desktop notification daemon and the terminal in step through
+//! a helper on the host (`desktop_daemon_helper`), run with the RunCommands permission. For each
+//! high/critical or escalated notification the plugin runs `<helper> show <id> <title> <message>`;
+//! the helper shows the desktop copy, waits until it is closed and prints how: `dismissed` or
+//! `activated` (the user dealt with it) acknowledge the notification in the terminal as well,
+//! anything else (`expired`, `closed`) leaves it. The other way round, once the notification is
+//! acknowledged or expires in the terminal the plugin runs `<helper> close <id>`, so the desktop
+//! copy goes away too. A helper can be a small script around `notify-send --wait` or `gdbus`
+//! keeping the daemon's notification ID per plugin ID.
+
+use crate::markup;
+use crate::notification::Notification;
+
+/// Run command context key marking helper runs (value: the notification ID)
+pub const DAEMON_CONTEXT_KEY: &str = "desktop_daemon";
+
+/// Run command context key of the helper operation (`show` or `close`)
+pub const DAEMON_OP_CONTEXT_KEY: &str = "desktop_daemon_op";
+
+/// How the desktop copy of a notification was closed, as reported by the helper
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DesktopOutcome {
+    /// Closed by the user
+    Dismissed,
+    /// Clicked, or one of its actions picked
+    Activated,
+    /// Closed otherwise (timed out, or closed on the plugin's behalf)
+    Closed,
+}
+
+impl DesktopOutcome {
+    /// Outcome from the first line the helper printed
+    pub fn parse(stdout: &[u8]) -> Self {
+        let output = String::from_utf8_lossy(stdout);
+        match output.lines().next().unwrap_or_default().trim().to_lowercase().as_str() {
+            "dismissed" => Self::Dismissed,
+            "activated" | "clicked" => Self::Activated,
+            _ => Self::Closed,
+        }
+    }
+
+    /// Whether the terminal copy is acknowledged as well
+    pub fn acknowledges(&self) -> bool {
+        matches!(self, Self::Dismissed | Self::Activated)
+    }
+}
+
+/// Command showing the desktop copy of a notification
+pub fn show_command(helper: &str, notification: &Notification) -> Vec<String> {
+    let mut command = helper_words(helper);
+    command.extend([
+        "show".to_string(),
+        notification.id.clone(),
+        notification.title.clone().unwrap_or_else(|| "Zellij".to_string()),
+        markup::strip(&notification.message),
+    ]);
+    command
+}
+
+/// Command closing the desktop copy of a notification
+pub fn close_command(helper: &str, id: &str) -> Vec<String> {
+    let mut command = helper_words(helper);
+    command.extend(["close".to_string(), id.to_string()]);
+    command
+}
+
+/// The helper's program and arguments, split on whitespace. There is no shell: `~`, variables
+/// and quotes are passed as they are, so give the helper as an absolute path.
+fn helper_words(helper: &str) -> Vec<String> {
+    helper.split_whitespace().map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_commands_and_outcomes() {
+        let mut notification = Notification::error("Build **failed**").with_title("CI");
+        notification.id = "n-4".to_string();
+        assert_eq!(show_command("python3 /home/me/bin/notify-sync", &notification),
+            vec!["python3", "/home/me/bin/notify-sync", "show", "n-4", "CI", "Build failed"]);
+        assert_eq!(close_command("notify-sync", "n-4"), vec!["notify-sync", "close", "n-4"]);
+
+        assert_eq!(DesktopOutcome::parse(b"Dismissed\n"), DesktopOutcome::Dismissed);
+        assert!(DesktopOutcome::parse(b"clicked\nextra").acknowledges());
+        assert!(!DesktopOutcome::parse(b"expired\n").acknowledges());
+        assert!(!DesktopOutcome::parse(b"").acknowledges());
+    }
+}
@@ -0,0 +1,199 @@
+//! Diagnostics module for Zellij Visual Notifications
+//!
+//! Keeps a bounded ring of recent log entries for the in-plugin diagnostics view, and tracks
+//! automatic recovery attempts out of error states with exponential backoff.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Number of log entries kept for the diagnostics view
+pub const LOG_RING_CAPACITY: usize = 64;
+
+/// Severity of a log entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    /// Informational
+    Info,
+    /// Warning
+    Warn,
+}
+
+impl LogLevel {
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+        }
+    }
+}
+
+/// A recorded log line
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// Plugin time when logged (ms)
+    pub at_ms: u64,
+    /// Severity
+    pub level: LogLevel,
+    /// Message
+    pub message: String,
+}
+
+/// Bounded ring of recent log entries
+#[derive(Debug, Clone, Default)]
+pub struct LogRing {
+    /// Entries, oldest first
+    entries: VecDeque<LogEntry>,
+    /// Plugin time stamped on new entries (ms)
+    now_ms: u64,
+}
+
+impl LogRing {
+    /// Record an entry, dropping the oldest when full
+    pub fn push(&mut self, level: LogLevel, message: &str) {
+        if self.entries.len() >= LOG_RING_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            at_ms: self.now_ms,
+            level,
+            message: message.to_string(),
+        });
+    }
+
+    /// Most recent entries, newest last
+    pub fn recent(&self, count: usize) -> Vec<LogEntry> {
+        let skip = self.entries.len().saturating_sub(count);
+        self.entries.iter().skip(skip).cloned().collect()
+    }
+}
+
+thread_local! {
+    /// Log ring fed by the plugin's log functions (the plugin is single-threaded)
+    static LOG_RING: RefCell<LogRing> = RefCell::new(LogRing::default());
+}
+
+/// Record a log entry in the ring
+pub fn record(level: LogLevel, message: &str) {
+    LOG_RING.with(|ring| ring.borrow_mut().push(level, message));
+}
+
+/// Log a message to Zellij's log and the ring
+pub fn log(level: LogLevel, message: &str) {
+    record(level, message);
+    match level {
+        LogLevel::Info => eprintln!("[INFO] zellij-visual-notifications: {}", message),
+        LogLevel::Warn => eprintln!("[WARN] zellij-visual-notifications: {}", message),
+    }
+}
+
+/// Set the plugin time stamped on new log entries
+pub fn set_log_time(now_ms: u64) {
+    LOG_RING.with(|ring| ring.borrow_mut().now_ms = now_ms);
+}
+
+/// Most recent log entries, newest last
+pub fn recent_logs(count: usize) -> Vec<LogEntry> {
+    LOG_RING.with(|ring| ring.borrow().recent(count))
+}
+
+/// Automatic recovery attempts out of an error state, with exponential backoff
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveryTracker {
+    /// Attempts made for the current error
+    pub attempts: u32,
+    /// Maximum attempts per error
+    pub max_attempts: u32,
+    /// Errors recovered from (lifetime)
+    pub recovered: u32,
+    /// Delay before the first attempt, doubled per further attempt (ms)
+    base_delay_ms: u64,
+    /// Time of the next attempt (ms, None = no error pending)
+    next_attempt_ms: Option<u64>,
+}
+
+impl Default for RecoveryTracker {
+    fn default() -> Self {
+        Self::new(3, 30_000)
+    }
+}
+
+impl RecoveryTracker {
+    /// Create a tracker with a retry policy
+    pub fn new(max_attempts: u32, base_delay_ms: u64) -> Self {
+        Self {
+            attempts: 0,
+            max_attempts,
+            recovered: 0,
+            base_delay_ms,
+            next_attempt_ms: None,
+        }
+    }
+
+    /// Start tracking a new error
+    pub fn begin(&mut self, now_ms: u64) {
+        self.attempts = 0;
+        self.next_attempt_ms = Some(now_ms + self.base_delay_ms);
+    }
+
+    /// Check whether a recovery attempt is due, recording it if so
+    pub fn attempt_due(&mut self, now_ms: u64) -> bool {
+        match self.next_attempt_ms {
+            Some(at) if now_ms >= at && self.attempts < self.max_attempts => {
+                self.attempts += 1;
+                let delay = self.base_delay_ms.saturating_mul(1 << self.attempts.min(16));
+                self.next_attempt_ms = Some(now_ms + delay);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record that the error cleared
+    pub fn succeed(&mut self) {
+        if self.next_attempt_ms.take().is_some() {
+            self.recovered += 1;
+        }
+        self.attempts = 0;
+    }
+
+    /// Whether retries are exhausted for the current error
+    pub fn is_exhausted(&self) -> bool {
+        self.next_attempt_ms.is_some() && self.attempts >= self.max_attempts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_ring_is_bounded() {
+        let mut ring = LogRing::default();
+        for i in 0..(LOG_RING_CAPACITY + 5) {
+            ring.push(LogLevel::Info, &format!("line {}", i));
+        }
+        let recent = ring.recent(2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[1].message, format!("line {}", LOG_RING_CAPACITY + 4));
+        assert_eq!(ring.recent(1000).len(), LOG_RING_CAPACITY);
+    }
+
+    #[test]
+    fn test_recovery_backoff_and_exhaustion() {
+        let mut recovery = RecoveryTracker::new(2, 1000);
+        assert!(!recovery.attempt_due(5000)); // no error yet
+
+        recovery.begin(0);
+        assert!(!recovery.attempt_due(999));
+        assert!(recovery.attempt_due(1000));
+        assert!(!recovery.attempt_due(2999));
+        assert!(recovery.attempt_due(3000));
+        assert!(recovery.is_exhausted());
+        assert!(!recovery.attempt_due(100_000));
+
+        recovery.succeed();
+        assert_eq!(recovery.recovered, 1);
+        assert!(!recovery.is_exhausted());
+    }
+}
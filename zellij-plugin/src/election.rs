@@ -0,0 +1,155 @@
+//! Instance election module for Zellij Visual Notifications
+//!
+//! When two plugin instances listen on the same pipe (a layout loading the plugin twice, say),
+//! every notification is processed twice: two bells, two webhook posts, badges renamed twice.
+//! With `instance_election` on, the instances announce themselves every `HEARTBEAT_MS` over a
+//! broadcast pipe and the one with the lowest plugin ID is the primary: it runs the sinks and
+//! changes the session (tab badges, focus, titles), while the others are on standby and only
+//! render. A newcomer is answered right away, so a second instance stands by within a tick or
+//! two. An instance not heard from for `PEER_TIMEOUT_MS` is taken to be gone, and the next in
+//! line takes over. The `instance` status segment shows the role while there is more than one.
+//! Read-only mirrors (`mirror`) render only anyway and take no part.
+
+use std::collections::BTreeMap;
+
+/// Broadcast pipe the instances announce themselves on
+pub const ELECTION_PIPE: &str = "visual-notifications-election";
+
+/// Time between two announcements (ms)
+pub const HEARTBEAT_MS: u64 = 2_000;
+
+/// Silence after which an instance is taken to be gone (ms)
+pub const PEER_TIMEOUT_MS: u64 = 3 * HEARTBEAT_MS;
+
+/// Role of this instance among the ones listening
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Runs the sinks and changes the session
+    Primary,
+    /// Renders only while another instance is primary
+    Standby,
+}
+
+impl Role {
+    /// Name shown in the status bar and the logs
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Standby => "standby",
+        }
+    }
+}
+
+/// Election state of this instance
+#[derive(Debug, Clone, Default)]
+pub struct Election {
+    /// Own plugin ID, None until loaded
+    own_id: Option<u32>,
+    /// Other instances by plugin ID, with the plugin time they were last heard from (ms)
+    peers: BTreeMap<u32, u64>,
+    /// Plugin time of the last announcement (ms)
+    last_heartbeat_ms: Option<u64>,
+}
+
+impl Election {
+    /// Take part with the given plugin ID
+    pub fn join(&mut self, own_id: u32) {
+        if self.own_id != Some(own_id) {
+            self.own_id = Some(own_id);
+            self.last_heartbeat_ms = None;
+        }
+    }
+
+    /// Stop taking part (election switched off, or a mirror): primary from now on
+    pub fn leave(&mut self) {
+        self.own_id = None;
+        self.peers.clear();
+    }
+
+    /// Announcement payload when one is due
+    pub fn heartbeat(&mut self, now_ms: u64) -> Option<String> {
+        let own_id = self.own_id?;
+        if self.last_heartbeat_ms.is_some_and(|last| now_ms < last + HEARTBEAT_MS) {
+            return None;
+        }
+        self.last_heartbeat_ms = Some(now_ms);
+        Some(format!("hello {}", own_id))
+    }
+
+    /// Record another instance's announcement, returning an immediate answer when it is new
+    pub fn observe(&mut self, payload: &str, now_ms: u64) -> Option<String> {
+        let own_id = self.own_id?;
+        let peer_id: u32 = payload.trim().strip_prefix("hello ")?.trim().parse().ok()?;
+        if peer_id == own_id {
+            return None;
+        }
+        let known = self.peers.insert(peer_id, now_ms).is_some();
+        (!known).then(|| {
+            self.last_heartbeat_ms = Some(now_ms);
+            format!("hello {}", own_id)
+        })
+    }
+
+    /// Forget instances not heard from for `PEER_TIMEOUT_MS`
+    pub fn expire(&mut self, now_ms: u64) {
+        self.peers.retain(|_, last_heard| now_ms < *last_heard + PEER_TIMEOUT_MS);
+    }
+
+    /// Role of this instance: primary unless another instance has a lower plugin ID
+    pub fn role(&self) -> Role {
+        match (self.own_id, self.peers.keys().next()) {
+            (Some(own_id), Some(lowest)) if *lowest < own_id => Role::Standby,
+            _ => Role::Primary,
+        }
+    }
+
+    /// Role shown in the status bar, None while this is the only instance
+    pub fn visible_role(&self) -> Option<Role> {
+        (!self.peers.is_empty()).then(|| self.role())
+    }
+
+    /// Stats line, e.g. `instance: standby (plugin 4, primary 2, 1 other)`
+    pub fn line(&self) -> String {
+        match (self.own_id, self.peers.keys().next()) {
+            (None, _) => "instance: primary (no election)".to_string(),
+            (Some(own_id), None) => format!("instance: primary (plugin {}, alone)", own_id),
+            (Some(own_id), Some(lowest)) => format!("instance: {} (plugin {}, primary {}, {} other{})",
+                self.role().name(), own_id, own_id.min(*lowest), self.peers.len(),
+                if self.peers.len() == 1 { "" } else { "s" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_id_is_primary_with_failover() {
+        let mut election = Election::default();
+        assert_eq!(election.heartbeat(0), None);
+        election.join(4);
+        assert_eq!(election.heartbeat(0).as_deref(), Some("hello 4"));
+        assert_eq!(election.heartbeat(1_000), None);
+        assert_eq!(election.visible_role(), None);
+
+        // A newcomer with a higher ID is answered and stands by
+        assert_eq!(election.observe("hello 7", 1_500).as_deref(), Some("hello 4"));
+        assert_eq!(election.observe("hello 7", 1_600), None);
+        assert_eq!(election.visible_role(), Some(Role::Primary));
+
+        // An older instance takes precedence until it goes silent
+        election.observe("hello 2", 1_600);
+        assert_eq!(election.role(), Role::Standby);
+        assert_eq!(election.line(), "instance: standby (plugin 4, primary 2, 2 others)");
+        election.observe("hello 7", 7_000);
+        election.expire(7_600);
+        assert_eq!(election.role(), Role::Primary);
+        assert_eq!(election.line(), "instance: primary (plugin 4, primary 4, 1 other)");
+
+        assert_eq!(election.observe("hello 4", 8_000), None);
+        assert_eq!(election.observe("garbage", 8_000), None);
+        election.leave();
+        assert_eq!(election.role(), Role::Primary);
+    }
+}
@@ -0,0 +1,202 @@
+//! Entry cache module for Zellij Visual Notifications
+//!
+//! Keeps the styled string of each status bar entry together with the inputs it was drawn from,
+//! so frames where only one of dozens of entries animates reformat that one and reuse the rest.
+//! Animation levels are bucketed (`BRIGHTNESS_BUCKETS` steps) and times are compared at the
+//! second shown, so an entry is only rebuilt when its drawn form can change. The renderer owning
+//! the cache is rebuilt with every settings change; color changes clear it explicitly.
+//!
+//! The [`EntryLayout`] keeps the order entries were last drawn in, so entry numbers (shown with
+//! `entry_hotkeys`) resolve to the pane that was on screen under that number.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use crate::notification::Priority;
+
+/// Highest entry number (digit keys 1-9)
+pub const MAX_ENTRY_NUMBER: usize = 9;
+
+/// Steps animation brightness and gradient positions are bucketed into
+pub const BRIGHTNESS_BUCKETS: f32 = 64.0;
+
+/// Everything a status entry is drawn from, beyond the renderer settings and colors
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryKey {
+    /// Displayed notification
+    pub notification_id: Option<String>,
+    /// Notification type name
+    pub kind: &'static str,
+    /// Sender color
+    pub custom_color: Option<String>,
+    /// Correlation accent
+    pub correlation_id: Option<String>,
+    /// Claude pane accent
+    pub claude_pane: bool,
+    /// Animation level (brightness, or gradient position offset by one full range)
+    pub brightness_bucket: u16,
+    /// Seconds shown by the entry (progress elapsed and remaining)
+    pub age_bucket: Option<(u64, Option<u64>)>,
+    /// Progress percent shown (tenths)
+    pub percent: Option<u32>,
+    /// Icon (spinner frame or custom icon)
+    pub icon: String,
+    /// Priority (escalation changes the color)
+    pub priority: Priority,
+    /// Read (dimmed)
+    pub read: bool,
+    /// Pin marker
+    pub pinned: bool,
+    /// Animating marker
+    pub animating: bool,
+    /// Collapsed/stacked marker
+    pub fallback: bool,
+    /// SLO marker
+    pub slo_breached: bool,
+    /// Deadline countdown shown, and whether the overdue flash is on
+    pub deadline: Option<(String, bool)>,
+}
+
+impl EntryKey {
+    /// Bucket of an animation level (0.0 - 1.0)
+    pub fn bucket(level: f32, gradient: bool) -> u16 {
+        let bucket = (level.clamp(0.0, 1.0) * BRIGHTNESS_BUCKETS).round() as u16;
+        if gradient { bucket + BRIGHTNESS_BUCKETS as u16 + 1 } else { bucket }
+    }
+}
+
+/// Drawn status entries per pane
+#[derive(Debug, Clone, Default)]
+pub struct EntryCache {
+    /// Key and styled entry per pane
+    entries: RefCell<BTreeMap<u32, (EntryKey, String)>>,
+    /// Entries reused
+    hits: Cell<u64>,
+    /// Entries formatted
+    misses: Cell<u64>,
+}
+
+impl EntryCache {
+    /// The entry for a pane: reused while its key is unchanged, otherwise built and kept
+    pub fn get_or_build(&self, pane_id: u32, key: EntryKey, build: impl FnOnce() -> String) -> String {
+        let mut entries = self.entries.borrow_mut();
+        if let Some((cached_key, entry)) = entries.get(&pane_id) {
+            if *cached_key == key {
+                self.hits.set(self.hits.get() + 1);
+                return entry.clone();
+            }
+        }
+        self.misses.set(self.misses.get() + 1);
+        let entry = build();
+        entries.insert(pane_id, (key, entry.clone()));
+        entry
+    }
+
+    /// Forget panes that were not drawn
+    pub fn retain(&self, drawn: &[u32]) {
+        self.entries.borrow_mut().retain(|pane_id, _| drawn.contains(pane_id));
+    }
+
+    /// Forget every entry (colors changed)
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    /// Stats line, e.g. `status entries: 1180 reused, 64 formatted`
+    pub fn line(&self) -> String {
+        format!("status entries: {} reused, {} formatted", self.hits.get(), self.misses.get())
+    }
+}
+
+/// Panes of the status entries in the order they were last drawn
+#[derive(Debug, Clone, Default)]
+pub struct EntryLayout {
+    panes: RefCell<Vec<u32>>,
+}
+
+impl EntryLayout {
+    /// Remember the entries of the frame just drawn
+    pub fn record(&self, panes: &[u32]) {
+        let mut recorded = self.panes.borrow_mut();
+        recorded.clear();
+        recorded.extend_from_slice(panes);
+    }
+
+    /// Pane of the entry with a number (1-based), as last drawn
+    pub fn pane_at(&self, number: usize) -> Option<u32> {
+        if !(1..=MAX_ENTRY_NUMBER).contains(&number) {
+            return None;
+        }
+        self.panes.borrow().get(number - 1).copied()
+    }
+
+    /// Number label of the entry at a position (0-based), for the first nine
+    pub fn label(index: usize) -> Option<char> {
+        (index < MAX_ENTRY_NUMBER).then(|| char::from(b'1' + index as u8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(notification_id: &str, level: f32) -> EntryKey {
+        EntryKey {
+            notification_id: Some(notification_id.to_string()),
+            kind: "info",
+            custom_color: None,
+            correlation_id: None,
+            claude_pane: false,
+            brightness_bucket: EntryKey::bucket(level, false),
+            age_bucket: None,
+            percent: None,
+            icon: "x".to_string(),
+            priority: Priority::Normal,
+            read: false,
+            pinned: false,
+            animating: false,
+            fallback: false,
+            slo_breached: false,
+            deadline: None,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_entries_are_reused() {
+        let cache = EntryCache::default();
+        assert_eq!(cache.get_or_build(1, key("n1", 1.0), || "[x:1]".to_string()), "[x:1]");
+        assert_eq!(cache.get_or_build(1, key("n1", 1.0), || unreachable!()), "[x:1]");
+        // Levels within one bucket draw alike
+        assert_eq!(cache.get_or_build(1, key("n1", 0.999), || unreachable!()), "[x:1]");
+        assert_eq!(cache.get_or_build(1, key("n1", 0.5), || "[x:1 dim]".to_string()), "[x:1 dim]");
+        assert_eq!(cache.get_or_build(1, key("n2", 0.5), || "[y:1]".to_string()), "[y:1]");
+        assert_eq!(cache.line(), "status entries: 2 reused, 3 formatted");
+    }
+
+    #[test]
+    fn test_undrawn_panes_are_dropped() {
+        let cache = EntryCache::default();
+        cache.get_or_build(1, key("n1", 1.0), || "a".to_string());
+        cache.get_or_build(2, key("n2", 1.0), || "b".to_string());
+        cache.retain(&[2]);
+        assert_eq!(cache.get_or_build(1, key("n1", 1.0), || "a2".to_string()), "a2");
+        cache.clear();
+        assert_eq!(cache.get_or_build(2, key("n2", 1.0), || "b2".to_string()), "b2");
+        assert_ne!(EntryKey::bucket(0.5, true), EntryKey::bucket(0.5, false));
+    }
+
+    #[test]
+    fn test_layout_numbers_follow_the_last_frame() {
+        let layout = EntryLayout::default();
+        assert_eq!(layout.pane_at(1), None);
+        layout.record(&[7, 3, 12]);
+        assert_eq!(layout.pane_at(1), Some(7));
+        assert_eq!(layout.pane_at(3), Some(12));
+        assert_eq!(layout.pane_at(4), None);
+        assert_eq!(layout.pane_at(0), None);
+        layout.record(&[3]);
+        assert_eq!(layout.pane_at(1), Some(3));
+        assert_eq!(EntryLayout::label(0), Some('1'));
+        assert_eq!(EntryLayout::label(8), Some('9'));
+        assert_eq!(EntryLayout::label(9), None);
+    }
+}
@@ -1,9 +1,88 @@
 //! Event Bridge module for Zellij Visual Notifications
 //!
-//! Handles communication with the claude-notifications system via IPC/pipe messages.
+//! Handles communication with the claude-notifications system via IPC/pipe messages: JSON
+//! payloads, and a line protocol (`error|pane=3|msg=Build failed`) for shell one-liners.
 
 use serde::{Deserialize, Serialize};
-use crate::notification::{Notification, NotificationBuilder, NotificationType, Priority};
+use crate::attention::AttentionReason;
+use crate::confirm::{ConfirmRequest, DEFAULT_CONFIRM_TIMEOUT_MS};
+use crate::legacy::{self, LegacyFormat, LegacyUsage, LEGACY_SOURCE};
+use crate::notification::{ExpiryAction, Notification, NotificationBuilder, NotificationType, Priority};
+use crate::remap::{self, TypeRemap};
+use crate::reminders::Reminder;
+use crate::timeline::SessionHook;
+
+/// Current protocol version. Minor versions only add optional fields, so a sender written for
+/// `1.x` keeps working with any plugin accepting major version 1.
+pub const PROTOCOL_VERSION: &str = "1.0";
+
+/// Protocol versions accepted from senders
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[PROTOCOL_VERSION];
+
+/// Optional message features senders can detect, by stable name. Names are only ever added
+/// within a major protocol version, never renamed or removed.
+pub const FEATURES: &[&str] = &[
+    "line_protocol",     // `type|key=value` lines
+    "cancel",            // cancel messages by id or pane
+    "reminder",          // periodic reminders (`every_ms`)
+    "progress",          // `percent` with live estimates
+    "custom_style",      // `color` and `icon`
+    "channels",          // `channel`
+    "correlation",       // `correlation_id`
+    "sequence",          // `seq` reordering
+    "on_expire",         // expiry actions
+    "recur_until_ack",   // re-arming until acknowledged
+    "request_ack",       // delivery receipts
+    "attention_reasons", // `reason` and Claude Code hook payloads
+    "session_timeline",  // `session_id` and lifecycle-only hooks
+    "cwd",               // working directory, for `project_roots`
+    "confirm",           // yes/no questions answered on the CLI pipe
+    "origin_session",    // notifications forwarded from other sessions
+    "branch",            // `branch` and `worktree` context
+    "context_variables", // `{session}`, `{tab_name}`, `{pane_title}`, `{time}` in title and message
+    "id_messages",       // `ack_id`, `dismiss_id` and `query_id` custom messages from plugins
+    "reply_options",     // quick replies to attention notifications
+    "deadline",          // `deadline_ms` countdown and `overdue` sink event
+];
+
+/// Limits a sender should stay within
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProtocolLimits {
+    /// Largest notification that can be queued (the queue's byte budget)
+    pub max_payload_bytes: usize,
+    /// Queued notifications kept per priority lane
+    pub queue_max_size: usize,
+    /// Received notifications kept for `list`
+    pub history_max: usize,
+}
+
+/// Reply to the `capabilities` command
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Capabilities {
+    /// Plugin version
+    pub plugin_version: &'static str,
+    /// Current protocol version
+    pub protocol_version: &'static str,
+    /// Accepted protocol versions
+    pub supported_versions: &'static [&'static str],
+    /// Supported optional features
+    pub features: &'static [&'static str],
+    /// Limits in effect
+    pub limits: ProtocolLimits,
+}
+
+impl Capabilities {
+    /// Capabilities of this build under the given limits
+    pub fn new(limits: ProtocolLimits) -> Self {
+        Self {
+            plugin_version: env!("CARGO_PKG_VERSION"),
+            protocol_version: PROTOCOL_VERSION,
+            supported_versions: SUPPORTED_PROTOCOL_VERSIONS,
+            features: FEATURES,
+            limits,
+        }
+    }
+}
 
 /// Event bridge for receiving notifications from claude-notifications
 #[derive(Debug, Default)]
@@ -18,6 +97,10 @@ pub struct EventBridge {
     error_count: u32,
     /// Maximum errors before fallback
     max_errors: u32,
+    /// Type changes per source
+    remaps: Vec<TypeRemap>,
+    /// Legacy (v0) payloads seen
+    legacy: LegacyUsage,
 }
 
 /// Connection state for the event bridge
@@ -39,13 +122,20 @@ impl EventBridge {
     pub fn new() -> Self {
         Self {
             connection_state: ConnectionState::Disconnected,
-            protocol_version: "1.0".to_string(),
+            protocol_version: PROTOCOL_VERSION.to_string(),
             last_message_timestamp: 0,
             error_count: 0,
             max_errors: 5,
+            remaps: Vec::new(),
+            legacy: LegacyUsage::default(),
         }
     }
 
+    /// Set the type changes applied to arriving notifications (`remap`)
+    pub fn set_remaps(&mut self, remaps: &[TypeRemap]) {
+        self.remaps = remaps.to_vec();
+    }
+
     /// Get the current connection state
     pub fn connection_state(&self) -> &ConnectionState {
         &self.connection_state
@@ -58,20 +148,40 @@ impl EventBridge {
 
     /// Parse a notification from a JSON payload
     pub fn parse_notification(&mut self, payload: &str) -> Result<Notification, EventBridgeError> {
+        match self.parse_message(payload)? {
+            BridgeMessage::Notify(notification) => Ok(*notification),
+            BridgeMessage::Cancel { .. } => Err(EventBridgeError::InvalidFormat(
+                "cancel message is not a notification".to_string(),
+            )),
+            BridgeMessage::Remind(_) => Err(EventBridgeError::InvalidFormat(
+                "reminder message is not a notification".to_string(),
+            )),
+            BridgeMessage::Session { .. } => Err(EventBridgeError::InvalidFormat(
+                "session hook is not a notification".to_string(),
+            )),
+            BridgeMessage::Confirm(_) => Err(EventBridgeError::InvalidFormat(
+                "confirm message is not a notification".to_string(),
+            )),
+        }
+    }
+
+    /// Parse a protocol message (notification or cancellation) from a JSON payload
+    pub fn parse_message(&mut self, payload: &str) -> Result<BridgeMessage, EventBridgeError> {
         // Try to parse as NotificationMessage first
         match serde_json::from_str::<NotificationMessage>(payload) {
             Ok(msg) => {
                 self.connection_state = ConnectionState::Connected;
                 self.error_count = 0;
                 self.last_message_timestamp = msg.timestamp.unwrap_or(0);
-                Ok(self.convert_message_to_notification(msg))
+                self.resolve_message(msg)
             }
             Err(e) => {
                 // Try legacy format
                 if let Ok(legacy) = serde_json::from_str::<LegacyNotificationMessage>(payload) {
                     self.connection_state = ConnectionState::Connected;
                     self.error_count = 0;
-                    return Ok(self.convert_legacy_to_notification(legacy));
+                    self.legacy.record(LegacyFormat::Json);
+                    return Ok(BridgeMessage::Notify(Box::new(self.convert_legacy_to_notification(legacy))));
                 }
 
                 self.error_count += 1;
@@ -84,31 +194,121 @@ impl EventBridge {
         }
     }
 
+    /// Parse a legacy (v0) string payload, e.g. `ERROR:Build failed` or a plain message
+    pub fn parse_legacy(&mut self, payload: &str) -> Option<BridgeMessage> {
+        let (format, notification) = legacy::parse(payload)?;
+        self.legacy.record(format);
+        Some(BridgeMessage::Notify(Box::new(notification)))
+    }
+
+    /// Legacy (v0) payloads seen
+    pub fn legacy_usage(&self) -> &LegacyUsage {
+        &self.legacy
+    }
+
+    /// Deprecation warnings for legacy payloads not yet logged
+    pub fn take_deprecation_warnings(&mut self) -> Vec<String> {
+        self.legacy.take_warnings()
+    }
+
+    /// Parse a line-protocol message, e.g. `error|pane=3|msg=Build failed` or `cancel|id=ask-1`
+    /// (malformed lines are reported but do not count toward the bridge error state)
+    pub fn parse_line(&self, payload: &str) -> Result<BridgeMessage, EventBridgeError> {
+        let msg = parse_line_fields(payload).map_err(EventBridgeError::InvalidFormat)?;
+        self.resolve_message(msg)
+    }
+
+    /// Turn a parsed message into a notification, a reminder, a cancellation, a question or a
+    /// session event
+    fn resolve_message(&self, msg: NotificationMessage) -> Result<BridgeMessage, EventBridgeError> {
+        if let Some(hook) = msg.hook_event_name.as_deref().and_then(SessionHook::from_event) {
+            let session_id = msg.session_id.ok_or_else(|| EventBridgeError::InvalidFormat(
+                "session hook requires session_id".to_string(),
+            ))?;
+            return Ok(BridgeMessage::Session { session_id, pane_id: msg.pane_id, hook });
+        }
+
+        if msg.notification_type.as_deref().map(|t| t.eq_ignore_ascii_case("reminder")).unwrap_or(false) {
+            let every_ms = msg.every_ms.ok_or_else(|| EventBridgeError::InvalidFormat(
+                "reminder message requires every_ms".to_string(),
+            ))?;
+            return Ok(BridgeMessage::Remind(Reminder {
+                id: msg.id.unwrap_or_else(crate::clock::next_id),
+                message: msg.message.unwrap_or_else(|| "Reminder".to_string()),
+                title: msg.title,
+                pane_id: msg.pane_id,
+                every_ms,
+                next_due_ms: 0,
+            }));
+        }
+
+        if msg.notification_type.as_deref().map(|t| t.eq_ignore_ascii_case("cancel")).unwrap_or(false) {
+            if msg.id.is_none() && msg.pane_id.is_none() {
+                return Err(EventBridgeError::InvalidFormat(
+                    "cancel message requires id or pane_id".to_string(),
+                ));
+            }
+            return Ok(BridgeMessage::Cancel { id: msg.id, pane_id: msg.pane_id });
+        }
+
+        if msg.notification_type.as_deref().map(|t| t.eq_ignore_ascii_case("confirm")).unwrap_or(false) {
+            let question = msg.message.ok_or_else(|| EventBridgeError::InvalidFormat(
+                "confirm message requires message".to_string(),
+            ))?;
+            return Ok(BridgeMessage::Confirm(ConfirmRequest {
+                id: msg.id.unwrap_or_else(crate::clock::next_id),
+                question,
+                pane_id: msg.pane_id,
+                timeout_ms: msg.timeout_ms.unwrap_or(DEFAULT_CONFIRM_TIMEOUT_MS),
+                default: msg.default.as_deref().map(ConfirmRequest::parse_default).unwrap_or(false),
+                pipe_id: None,
+            }));
+        }
+
+        Ok(BridgeMessage::Notify(Box::new(self.convert_message_to_notification(msg))))
+    }
+
     /// Convert a NotificationMessage to a Notification
     fn convert_message_to_notification(&self, msg: NotificationMessage) -> Notification {
+        // An explicit reason wins over one derived from Claude Code hook fields
+        let reason = msg.reason.as_deref()
+            .and_then(AttentionReason::from_name)
+            .or_else(|| msg.hook_event_name.as_deref().and_then(|event| {
+                AttentionReason::from_hook(event, msg.hook_notification_type.as_deref(), msg.message.as_deref())
+            }));
+
+        let source = msg.source.unwrap_or_else(|| "claude-notifications".to_string());
         let notification_type = msg.notification_type
             .map(|t| NotificationType::from_str(&t))
+            .or_else(|| reason.map(|r| r.notification_type()))
             .unwrap_or(NotificationType::Attention);
+        // A source that misuses types gets the one it should have sent, before prioritization
+        let notification_type = remap::apply(&self.remaps, &source, notification_type);
+        let attention = notification_type == NotificationType::Attention;
 
         let priority = msg.priority
-            .map(|p| match p.to_lowercase().as_str() {
-                "low" => Priority::Low,
-                "normal" => Priority::Normal,
-                "high" => Priority::High,
-                "critical" => Priority::Critical,
-                _ => Priority::from(&notification_type),
-            })
+            .and_then(|p| Priority::from_name(&p))
             .unwrap_or_else(|| Priority::from(&notification_type));
 
         let mut builder = NotificationBuilder::new()
             .notification_type(notification_type)
-            .message(&msg.message.unwrap_or_else(|| "Claude is waiting...".to_string()))
+            .message(&msg.message.unwrap_or_else(|| match reason {
+                Some(AttentionReason::Permission) => "Claude needs permission".to_string(),
+                Some(AttentionReason::Finished) => "Claude finished".to_string(),
+                Some(AttentionReason::Error) => "Claude hit an error".to_string(),
+                Some(AttentionReason::Input) | None => "Claude is waiting...".to_string(),
+            }))
             .title(&msg.title.unwrap_or_else(|| "Claude Code".to_string()))
-            .source(&msg.source.unwrap_or_else(|| "claude-notifications".to_string()))
+            .source(&source)
             .priority(priority)
             .timestamp(msg.timestamp.unwrap_or(0))
             .ttl(msg.ttl_ms.unwrap_or(300_000));
 
+        // Keep the sender's ID so the notification can be cancelled later
+        if let Some(ref id) = msg.id {
+            builder = builder.id(id);
+        }
+
         // Add pane_id if present
         if let Some(pane_id) = msg.pane_id {
             builder = builder.pane_id(pane_id);
@@ -119,13 +319,97 @@ impl EventBridge {
             builder = builder.tab_index(tab_index);
         }
 
+        // Add sender branding overrides if present (sanitized by the builder)
+        if let Some(ref color) = msg.color {
+            builder = builder.color(color);
+        }
+        if let Some(ref icon) = msg.icon {
+            builder = builder.icon(icon);
+        }
+
+        // Add progress if present
+        if let Some(percent) = msg.percent {
+            builder = builder.percent(percent);
+        }
+
+        // Add channel if present
+        if let Some(ref channel) = msg.channel {
+            builder = builder.channel(channel);
+        }
+
+        if let Some(reason) = reason {
+            builder = builder.reason(reason);
+        }
+
+        if let Some(ref correlation_id) = msg.correlation_id {
+            builder = builder.correlation_id(correlation_id);
+        }
+
+        if let Some(seq) = msg.seq {
+            builder = builder.seq(seq);
+        }
+
+        if let Some(action) = msg.on_expire.as_deref().and_then(ExpiryAction::from_name) {
+            builder = builder.on_expire(action);
+        }
+
+        if let Some(secs) = msg.recur_until_ack {
+            builder = builder.recur_until_ack(secs);
+        }
+
+        if let Some(deadline_ms) = msg.deadline_ms {
+            builder = builder.deadline(deadline_ms);
+        }
+
+        if let Some(request_ack) = msg.request_ack {
+            builder = builder.request_ack(request_ack);
+        }
+
+        if let Some(ref session_id) = msg.session_id {
+            builder = builder.session_id(session_id);
+        }
+
+        if let Some(ref cwd) = msg.cwd {
+            builder = builder.cwd(cwd);
+        }
+
+        if let Some(ref branch) = msg.branch {
+            builder = builder.branch(branch);
+        }
+
+        if let Some(ref worktree) = msg.worktree {
+            builder = builder.worktree(worktree);
+        }
+
+        if let Some(ref origin_session) = msg.origin_session {
+            builder = builder.origin_session(origin_session);
+        }
+
+        // Quick replies answer prompts, so only attention notifications offer them
+        if let Some(ref options) = msg.reply_options {
+            if attention {
+                builder = builder.reply_options(options);
+            }
+        }
+
+        // Command metadata (`explain`, exit code colors)
+        if let Some(ref command) = msg.command {
+            builder = builder.command(command);
+        }
+        if let Some(exit_code) = msg.exit_code {
+            builder = builder.exit_code(exit_code);
+        }
+        if let Some(duration_ms) = msg.duration_ms {
+            builder = builder.duration(duration_ms);
+        }
+
         builder.build()
     }
 
     /// Convert a legacy message format to a Notification
     fn convert_legacy_to_notification(&self, msg: LegacyNotificationMessage) -> Notification {
         Notification::attention(&msg.message)
-            .from_source("claude-notifications-legacy")
+            .from_source(LEGACY_SOURCE)
     }
 
     /// Handle connection established
@@ -166,12 +450,42 @@ impl EventBridge {
     }
 }
 
+/// Parsed protocol message
+#[derive(Debug, Clone)]
+pub enum BridgeMessage {
+    /// Display a notification
+    Notify(Box<Notification>),
+    /// Withdraw a previously sent notification by ID, or everything for a pane
+    Cancel {
+        /// ID of the notification to cancel
+        id: Option<String>,
+        /// Pane whose notifications are cancelled
+        pane_id: Option<u32>,
+    },
+    /// Schedule a periodic reminder
+    Remind(Reminder),
+    /// Ask the user a yes/no question, answered on the sender's CLI pipe
+    Confirm(ConfirmRequest),
+    /// Record a lifecycle-only hook on a session's timeline
+    Session {
+        /// Claude Code session ID
+        session_id: String,
+        /// Pane the session runs in
+        pane_id: Option<u32>,
+        /// What happened
+        hook: SessionHook,
+    },
+}
+
 /// Notification message format from claude-notifications
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct NotificationMessage {
     /// Protocol version
     #[serde(default)]
     pub version: Option<String>,
+    /// Sender-chosen notification ID (referenced by cancel messages)
+    #[serde(default)]
+    pub id: Option<String>,
     /// Notification type (success, error, warning, info, attention)
     #[serde(rename = "type")]
     pub notification_type: Option<String>,
@@ -197,6 +511,128 @@ pub struct NotificationMessage {
     pub exit_code: Option<i32>,
     /// Duration in milliseconds
     pub duration_ms: Option<u64>,
+    /// Color override (#rrggbb)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Icon override (short Unicode string)
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Completion percent of a progress notification (0 - 100)
+    #[serde(default)]
+    pub percent: Option<f32>,
+    /// Channel (e.g. claude, ci, cron)
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Correlation ID grouping related notifications across panes (e.g. deploy-42)
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Sequence number per source; out-of-order notifications are reordered before display
+    #[serde(default)]
+    pub seq: Option<u64>,
+    /// What happens when the TTL runs out (discard, demote, archive, escalate)
+    #[serde(default)]
+    pub on_expire: Option<String>,
+    /// Re-arm interval (seconds) of a notification that stays until acknowledged
+    #[serde(default)]
+    pub recur_until_ack: Option<u64>,
+    /// Time the recipient has to respond (ms from arrival), e.g. before a job cancels
+    #[serde(default)]
+    pub deadline_ms: Option<u64>,
+    /// Reply with delivery receipts (queued, displayed, acknowledged, ...) on the CLI pipe
+    #[serde(default)]
+    pub request_ack: Option<bool>,
+    /// Interval of a reminder message (ms)
+    #[serde(default)]
+    pub every_ms: Option<u64>,
+    /// Time the user has to answer a confirm message (ms)
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Answer a confirm message defaults to (yes or no)
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Attention reason (permission, finished, error, input)
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Claude Code hook event (`Notification`, `Stop`, ...) when the hook payload is piped as-is
+    #[serde(default)]
+    pub hook_event_name: Option<String>,
+    /// Claude Code notification hook type (`permission_prompt`, `idle_prompt`)
+    #[serde(default, rename = "notification_type")]
+    pub hook_notification_type: Option<String>,
+    /// Claude Code session ID (recorded on the session timeline)
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Working directory of the sender (also sent by Claude Code hooks)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Git branch the sender works on (e.g. feat/login)
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Git worktree the sender works in (path)
+    #[serde(default)]
+    pub worktree: Option<String>,
+    /// Zellij session a forwarded notification comes from
+    #[serde(default)]
+    pub origin_session: Option<String>,
+    /// Quick reply options of an attention notification (e.g. ["yes", "no", "always"])
+    #[serde(default)]
+    pub reply_options: Option<Vec<String>>,
+}
+
+/// Parse the `type|key=value|...` line protocol into the JSON message shape
+fn parse_line_fields(payload: &str) -> Result<NotificationMessage, String> {
+    let mut fields = payload.trim().split('|');
+    let kind = fields.next().unwrap_or_default().trim().to_lowercase();
+    if !matches!(kind.as_str(), "cancel" | "reminder" | "confirm") && NotificationType::from_name(&kind).is_none() {
+        return Err(format!("unknown notification type: {}", kind));
+    }
+
+    let mut msg = NotificationMessage {
+        notification_type: Some(kind),
+        source: Some("pipe".to_string()),
+        ..NotificationMessage::default()
+    };
+    for field in fields {
+        let (key, value) = field.split_once('=')
+            .ok_or_else(|| format!("expected key=value, got: {}", field.trim()))?;
+        let (key, value) = (key.trim(), value.trim().to_string());
+        let number = |value: &str| value.parse::<u64>().map_err(|_| format!("invalid {}: {}", key, value));
+        match key {
+            "msg" | "message" => msg.message = Some(value),
+            "title" => msg.title = Some(value),
+            "source" => msg.source = Some(value),
+            "id" => msg.id = Some(value),
+            "pane" | "pane_id" => msg.pane_id = Some(number(&value)? as u32),
+            "tab" | "tab_index" => msg.tab_index = Some(number(&value)? as usize),
+            "priority" => msg.priority = Some(value),
+            "ttl" | "ttl_ms" => msg.ttl_ms = Some(number(&value)?),
+            "color" => msg.color = Some(value),
+            "icon" => msg.icon = Some(value),
+            "channel" => msg.channel = Some(value),
+            "reason" => msg.reason = Some(value),
+            "session" | "session_id" => msg.session_id = Some(value),
+            "cwd" => msg.cwd = Some(value),
+            "branch" => msg.branch = Some(value),
+            "worktree" => msg.worktree = Some(value),
+            "origin" | "origin_session" => msg.origin_session = Some(value),
+            "correlation" | "correlation_id" => msg.correlation_id = Some(value),
+            "seq" => msg.seq = Some(number(&value)?),
+            "on_expire" => msg.on_expire = Some(value),
+            "recur" | "recur_until_ack" => msg.recur_until_ack = Some(number(&value)?),
+            "deadline" | "deadline_ms" => msg.deadline_ms = Some(number(&value)?),
+            "request_ack" => msg.request_ack = Some(matches!(value.as_str(), "true" | "1" | "yes")),
+            "every" | "every_ms" => msg.every_ms = Some(number(&value)?),
+            "timeout" | "timeout_ms" => msg.timeout_ms = Some(number(&value)?),
+            "default" => msg.default = Some(value),
+            "reply" | "reply_options" => msg.reply_options = Some(value.split(',').map(str::to_string).collect()),
+            "percent" => {
+                let percent = value.trim_end_matches('%');
+                msg.percent = Some(percent.parse().map_err(|_| format!("invalid percent: {}", value))?);
+            }
+            other => return Err(format!("unknown field: {}", other)),
+        }
+    }
+    Ok(msg)
 }
 
 /// Legacy notification message format (simple JSON)
@@ -246,7 +682,8 @@ pub struct EventBridgeHealth {
 /// Create a test notification message (for testing)
 pub fn create_test_message(notification_type: &str, message: &str) -> String {
     let msg = NotificationMessage {
-        version: Some("1.0".to_string()),
+        version: Some(PROTOCOL_VERSION.to_string()),
+        id: None,
         notification_type: Some(notification_type.to_string()),
         message: Some(message.to_string()),
         title: Some("Test".to_string()),
@@ -259,6 +696,28 @@ pub fn create_test_message(notification_type: &str, message: &str) -> String {
         command: None,
         exit_code: None,
         duration_ms: None,
+        color: None,
+        icon: None,
+        percent: None,
+        channel: None,
+        correlation_id: None,
+        seq: None,
+        on_expire: None,
+        recur_until_ack: None,
+        deadline_ms: None,
+        request_ack: None,
+        every_ms: None,
+        timeout_ms: None,
+        default: None,
+        reason: None,
+        hook_event_name: None,
+        hook_notification_type: None,
+        session_id: None,
+        cwd: None,
+        branch: None,
+        worktree: None,
+        origin_session: None,
+        reply_options: None,
     };
     serde_json::to_string(&msg).unwrap_or_default()
 }
@@ -294,6 +753,132 @@ mod tests {
         assert_eq!(notif.message, "Build completed");
     }
 
+    #[test]
+    fn test_remap_applies_before_priority() {
+        let mut bridge = EventBridge::new();
+        bridge.set_remaps(&[TypeRemap::parse("flaky-tool", "error", "warning").unwrap()]);
+
+        let notif = bridge.parse_notification(r#"{"type": "error", "message": "Lint", "source": "flaky-tool"}"#).unwrap();
+        assert_eq!(notif.notification_type, NotificationType::Warning);
+        assert_eq!(notif.priority, Priority::High);
+
+        // Other sources, and priorities given by the sender, are left alone
+        let notif = bridge.parse_notification(r#"{"type": "error", "message": "Build failed", "source": "ci"}"#).unwrap();
+        assert_eq!(notif.notification_type, NotificationType::Error);
+        let notif = bridge.parse_notification(r#"{"type": "error", "message": "Lint", "source": "flaky-tool", "priority": "low"}"#).unwrap();
+        assert_eq!(notif.priority, Priority::Low);
+    }
+
+    #[test]
+    fn test_parse_color_and_icon_overrides() {
+        let mut bridge = EventBridge::new();
+
+        let json = r##"{"type": "info", "message": "Deployed", "color": "#FF00FF", "icon": "\ud83d\ude80"}"##;
+        let notif = bridge.parse_notification(json).unwrap();
+        assert_eq!(notif.color, Some("#ff00ff".to_string()));
+        assert_eq!(notif.icon, Some("\u{1F680}".to_string()));
+
+        let json = r#"{"type": "info", "message": "Bad", "color": "not-a-color"}"#;
+        let notif = bridge.parse_notification(json).unwrap();
+        assert!(notif.color.is_none());
+    }
+
+    #[test]
+    fn test_parse_cancel_message() {
+        let mut bridge = EventBridge::new();
+
+        let notif = bridge.parse_notification(r#"{"id": "ask-1", "type": "attention", "message": "Input?"}"#).unwrap();
+        assert_eq!(notif.id, "ask-1");
+
+        match bridge.parse_message(r#"{"type": "cancel", "id": "ask-1"}"#).unwrap() {
+            BridgeMessage::Cancel { id, pane_id } => {
+                assert_eq!(id, Some("ask-1".to_string()));
+                assert!(pane_id.is_none());
+            }
+            other => panic!("expected cancel, got {:?}", other),
+        }
+
+        assert!(matches!(
+            bridge.parse_message(r#"{"type": "cancel", "pane_id": 3}"#).unwrap(),
+            BridgeMessage::Cancel { pane_id: Some(3), .. }
+        ));
+        assert!(bridge.parse_message(r#"{"type": "cancel"}"#).is_err());
+        assert!(bridge.parse_notification(r#"{"type": "cancel", "pane_id": 3}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_line_protocol() {
+        let mut bridge = EventBridge::new();
+
+        match bridge.parse_line("error|pane=3|msg=Build failed: exit=2").unwrap() {
+            BridgeMessage::Notify(notif) => {
+                assert_eq!(notif.notification_type, NotificationType::Error);
+                assert_eq!(notif.pane_id, Some(3));
+                assert_eq!(notif.message, "Build failed: exit=2");
+            }
+            other => panic!("expected notification, got {:?}", other),
+        }
+        assert!(matches!(bridge.parse_line("cancel|id=ask-1").unwrap(), BridgeMessage::Cancel { .. }));
+        assert!(matches!(bridge.parse_line("reminder|msg=stand up|every=60000").unwrap(), BridgeMessage::Remind(_)));
+        assert_eq!(bridge.parse_line("progress|percent=42%").ok().and_then(|m| match m {
+            BridgeMessage::Notify(notif) => notif.percent,
+            _ => None,
+        }), Some(42.0));
+        assert_eq!(bridge.parse_line("error|msg=tests|branch=feat/login|worktree=/work/app-login").ok().and_then(|m| match m {
+            BridgeMessage::Notify(notif) => Some((notif.branch, notif.worktree)),
+            _ => None,
+        }), Some((Some("feat/login".to_string()), Some("/work/app-login".to_string()))));
+        assert_eq!(bridge.parse_line("attention|msg=Approve the deploy|deadline=600000").ok().and_then(|m| match m {
+            BridgeMessage::Notify(notif) => notif.deadline_ms,
+            _ => None,
+        }), Some(600_000));
+
+        // Malformed lines are errors but leave the bridge healthy
+        for line in ["bogus|msg=x", "error|pane=three", "error|msg", "error|colour=red"] {
+            assert!(bridge.parse_line(line).is_err(), "{}", line);
+        }
+        assert_eq!(bridge.error_count, 0);
+        assert!(bridge.parse_notification(r#"{"type": "info"}"#).is_ok());
+    }
+
+    #[test]
+    fn test_parse_reminder_message() {
+        let mut bridge = EventBridge::new();
+        let json = r#"{"type": "reminder", "id": "standup", "message": "stand up", "every_ms": 3600000}"#;
+        match bridge.parse_message(json).unwrap() {
+            BridgeMessage::Remind(reminder) => {
+                assert_eq!(reminder.id, "standup");
+                assert_eq!(reminder.message, "stand up");
+                assert_eq!(reminder.every_ms, 3_600_000);
+            }
+            other => panic!("expected reminder, got {:?}", other),
+        }
+        assert!(bridge.parse_message(r#"{"type": "reminder", "message": "no interval"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_confirm_message() {
+        let mut bridge = EventBridge::new();
+        let json = r#"{"type": "confirm", "id": "deploy", "message": "Deploy now?", "timeout_ms": 20000, "default": "yes"}"#;
+        match bridge.parse_message(json).unwrap() {
+            BridgeMessage::Confirm(request) => {
+                assert_eq!(request.id, "deploy");
+                assert_eq!(request.question, "Deploy now?");
+                assert_eq!(request.timeout_ms, 20_000);
+                assert!(request.default);
+            }
+            other => panic!("expected question, got {:?}", other),
+        }
+        match bridge.parse_line("confirm|msg=Run tests?|pane=2").unwrap() {
+            BridgeMessage::Confirm(request) => {
+                assert_eq!(request.timeout_ms, DEFAULT_CONFIRM_TIMEOUT_MS);
+                assert!(!request.default);
+            }
+            other => panic!("expected question, got {:?}", other),
+        }
+        assert!(bridge.parse_message(r#"{"type": "confirm"}"#).is_err());
+    }
+
     #[test]
     fn test_parse_legacy_message() {
         let mut bridge = EventBridge::new();
@@ -305,6 +890,14 @@ mod tests {
 
         let notif = result.unwrap();
         assert_eq!(notif.notification_type, NotificationType::Attention);
+
+        // Old hooks' string payloads are read too, and counted with a warning
+        match bridge.parse_legacy("WARNING:Disk almost full") {
+            Some(BridgeMessage::Notify(notification)) => assert_eq!(notification.notification_type, NotificationType::Warning),
+            other => panic!("expected notification, got {:?}", other),
+        }
+        assert_eq!(bridge.take_deprecation_warnings().len(), 1);
+        assert!(bridge.legacy_usage().line().contains("prefixed 1"));
     }
 
     #[test]
@@ -330,6 +923,18 @@ mod tests {
         assert_eq!(health.protocol_version, "1.0");
     }
 
+    #[test]
+    fn test_capabilities() {
+        let capabilities = Capabilities::new(ProtocolLimits { max_payload_bytes: 1024, queue_max_size: 100, history_max: 200 });
+        assert!(capabilities.features.contains(&"request_ack"));
+        assert!(!capabilities.features.contains(&"batch"));
+
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["protocol_version"], "1.0");
+        assert_eq!(json["supported_versions"][0], "1.0");
+        assert_eq!(json["limits"]["queue_max_size"], 100);
+    }
+
     #[test]
     fn test_create_test_message() {
         let msg = create_test_message("success", "Test message");
@@ -0,0 +1,139 @@
+//! Exit code module for Zellij Visual Notifications
+//!
+//! Explains the exit codes of command notifications in plain words: common shell codes (127
+//! command not found, 126 not executable) and signal deaths (128 + signal, e.g. 137 for SIGKILL,
+//! usually the out-of-memory killer). The `exit_codes` table in the configuration adds codes or
+//! replaces the built-in explanations. Shown by `explain` next to the notification's command.
+//! `exit_code_colors` gives ranges of codes their own color, so failure classes (a failed test
+//! run, a missing command, a signal death) render in different shades.
+
+use std::collections::BTreeMap;
+use crate::notification::sanitize_color;
+
+/// Name and explanation of a signal that ended a process
+fn signal(number: i32) -> Option<(&'static str, &'static str)> {
+    Some(match number {
+        1 => ("SIGHUP", "hangup, the terminal went away"),
+        2 => ("SIGINT", "interrupted, e.g. Ctrl+C"),
+        3 => ("SIGQUIT", "quit, e.g. Ctrl+\\"),
+        4 => ("SIGILL", "illegal instruction"),
+        6 => ("SIGABRT", "aborted, e.g. a failed assertion or abort()"),
+        7 => ("SIGBUS", "bus error, bad memory access"),
+        8 => ("SIGFPE", "arithmetic error, e.g. division by zero"),
+        9 => ("SIGKILL", "killed, often by the out-of-memory killer"),
+        11 => ("SIGSEGV", "segmentation fault"),
+        13 => ("SIGPIPE", "broken pipe, the reader went away"),
+        14 => ("SIGALRM", "timer expired"),
+        15 => ("SIGTERM", "terminated"),
+        _ => return None,
+    })
+}
+
+/// Built-in explanation of an exit code
+fn builtin(code: i32) -> Option<String> {
+    let explanation = match code {
+        0 => "success",
+        1 => "general failure",
+        2 => "misuse or invalid arguments",
+        126 => "command found but not executable (permissions?)",
+        127 => "command not found (check PATH and spelling)",
+        255 => "exit status out of range, or a failed ssh connection",
+        // Shells report 128 + n, some runtimes -n, for a process ended by signal n
+        129..=192 | -64..=-1 => {
+            let number = if code < 0 { -code } else { code - 128 };
+            return Some(match signal(number) {
+                Some((name, explanation)) => format!("{} ({})", explanation, name),
+                None => format!("ended by signal {}", number),
+            });
+        }
+        _ => return None,
+    };
+    Some(explanation.to_string())
+}
+
+/// Explanation of an exit code, e.g. `137: killed, often by the out-of-memory killer (SIGKILL)`;
+/// entries of `custom` win over the built-in ones
+pub fn describe(code: i32, custom: &BTreeMap<i32, String>) -> String {
+    match custom.get(&code).cloned().or_else(|| builtin(code)) {
+        Some(explanation) => format!("{}: {}", code, explanation),
+        None => code.to_string(),
+    }
+}
+
+/// Color of a range of exit codes (`exit_code_colors`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitCodeColor {
+    /// Lowest code in the range
+    pub start: i32,
+    /// Highest code in the range, None when open-ended
+    pub end: Option<i32>,
+    /// Color (#rrggbb)
+    pub color: String,
+}
+
+impl ExitCodeColor {
+    /// Parse a range (`3`, `2..=127`, `2..128`, `128..`) and its color
+    pub fn parse(range: &str, color: &str) -> Result<Self, String> {
+        let range = range.trim();
+        let number = |value: &str| value.trim().parse::<i32>().map_err(|_| format!("invalid exit code range: {}", range));
+        let (start, end) = if let Some((start, end)) = range.split_once("..=") {
+            (number(start)?, Some(number(end)?))
+        } else if let Some((start, end)) = range.split_once("..") {
+            let end = if end.trim().is_empty() { None } else { Some(number(end)?.saturating_sub(1)) };
+            (number(start)?, end)
+        } else {
+            let code = number(range)?;
+            (code, Some(code))
+        };
+        if end.is_some_and(|end| end < start) {
+            return Err(format!("empty exit code range: {}", range));
+        }
+        let color = sanitize_color(color).ok_or_else(|| format!("exit code range {} needs a #rrggbb color", range))?;
+        Ok(Self { start, end, color })
+    }
+
+    /// Whether the range contains `code`
+    pub fn contains(&self, code: i32) -> bool {
+        code >= self.start && self.end.is_none_or(|end| code <= end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_codes_and_signals() {
+        let custom = BTreeMap::new();
+        assert_eq!(describe(127, &custom), "127: command not found (check PATH and spelling)");
+        assert_eq!(describe(137, &custom), "137: killed, often by the out-of-memory killer (SIGKILL)");
+        assert_eq!(describe(139, &custom), "139: segmentation fault (SIGSEGV)");
+        assert_eq!(describe(-11, &custom), "-11: segmentation fault (SIGSEGV)");
+        assert_eq!(describe(158, &custom), "158: ended by signal 30");
+        assert_eq!(describe(42, &custom), "42");
+    }
+
+    #[test]
+    fn test_custom_codes_win() {
+        let custom = BTreeMap::from([(3, "lint failed".to_string()), (1, "tests failed".to_string())]);
+        assert_eq!(describe(3, &custom), "3: lint failed");
+        assert_eq!(describe(1, &custom), "1: tests failed");
+        assert_eq!(describe(130, &custom), "130: interrupted, e.g. Ctrl+C (SIGINT)");
+    }
+
+    #[test]
+    fn test_exit_code_color_ranges() {
+        let single = ExitCodeColor::parse("1", "#FF6666").unwrap();
+        assert_eq!(single, ExitCodeColor { start: 1, end: Some(1), color: "#ff6666".to_string() });
+        let shell = ExitCodeColor::parse("2..=127", "#ff0000").unwrap();
+        assert!(shell.contains(2) && shell.contains(127) && !shell.contains(128));
+        let exclusive = ExitCodeColor::parse("2..128", "#ff0000").unwrap();
+        assert_eq!(exclusive.end, Some(127));
+        let signals = ExitCodeColor::parse("128..", "#cc0000").unwrap();
+        assert!(signals.contains(137) && signals.contains(i32::MAX) && !signals.contains(1));
+
+        assert!(ExitCodeColor::parse("..5", "#cc0000").is_err());
+        assert!(ExitCodeColor::parse("5..=2", "#cc0000").is_err());
+        assert!(ExitCodeColor::parse("1..=1", "red").is_err());
+    }
+}
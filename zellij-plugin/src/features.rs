@@ -0,0 +1,86 @@
+//! Feature flags module for Zellij Visual Notifications
+//!
+//! Risky subsystems ship switched off as experimental features, and users opt in per feature
+//! with `features { experimental "toasts"; }` (plugin map: `experimental_features "toasts"`)
+//! instead of waiting for them to be declared stable. Subsystems ask `Config::experimental`
+//! whether they may run. A feature that becomes stable leaves the registry and is always on, so
+//! naming a feature this version does not know is only warned about, never an error. The
+//! `features` pipe command lists the registry with each feature's status.
+
+use crate::config::Config;
+
+/// An experimental feature that can be switched on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureInfo {
+    /// Name used in the configuration
+    pub name: &'static str,
+    /// What switching it on does
+    pub description: &'static str,
+}
+
+/// Experimental features of this version, all off unless named in the configuration
+pub const EXPERIMENTAL_FEATURES: &[FeatureInfo] = &[
+    FeatureInfo {
+        name: "toasts",
+        description: "toast sink: a small floating pane per notification",
+    },
+    FeatureInfo {
+        name: "sounds",
+        description: "sound sink: a sound per notification type from the sound theme",
+    },
+];
+
+/// Look up an experimental feature by name
+pub fn find(name: &str) -> Option<&'static FeatureInfo> {
+    EXPERIMENTAL_FEATURES.iter().find(|feature| feature.name == name)
+}
+
+/// Named features this version does not know (stable by now, or misspelled)
+pub fn unknown(config: &Config) -> Vec<&str> {
+    config.experimental_features.iter()
+        .map(String::as_str)
+        .filter(|name| find(name).is_none())
+        .collect()
+}
+
+/// Lines of the `features` command, e.g. `toasts  off  toast sink: ...`
+pub fn lines(config: &Config) -> Vec<String> {
+    let width = EXPERIMENTAL_FEATURES.iter().map(|feature| feature.name.len())
+        .chain(unknown(config).iter().map(|name| name.len()))
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<String> = EXPERIMENTAL_FEATURES.iter()
+        .map(|feature| format!("{:<width$}  {:<3}  {}",
+            feature.name,
+            if config.experimental(feature.name) { "on" } else { "off" },
+            feature.description,
+            width = width))
+        .collect();
+    lines.extend(unknown(config).iter()
+        .map(|name| format!("{:<width$}  -    not a feature of this version (stable or misspelled)", name, width = width)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_features_with_status() {
+        let mut config = Config::default();
+        assert!(!config.experimental("toasts"));
+        assert_eq!(lines(&config), vec![
+            "toasts  off  toast sink: a small floating pane per notification",
+            "sounds  off  sound sink: a sound per notification type from the sound theme",
+        ]);
+
+        config.experimental_features = vec!["toasts".to_string(), "mouse".to_string()];
+        assert!(config.experimental("toasts"));
+        assert_eq!(unknown(&config), vec!["mouse"]);
+        assert_eq!(lines(&config), vec![
+            "toasts  on   toast sink: a small floating pane per notification",
+            "sounds  off  sound sink: a sound per notification type from the sound theme",
+            "mouse   -    not a feature of this version (stable or misspelled)",
+        ]);
+    }
+}
@@ -0,0 +1,242 @@
+//! Health check module for Zellij Visual Notifications
+//!
+//! Self-check of what the plugin depends on (permissions, timer delivery, pipe reachability,
+//! configuration and rendering), run once after load and on demand via the `health` command,
+//! and formatted as a checklist.
+
+use crate::config::Config;
+use crate::state::PluginState;
+
+/// Columns below which the status bar is not drawn
+const MIN_RENDER_COLS: usize = 10;
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckStatus {
+    /// Working
+    Pass,
+    /// Working with limitations, or not verified yet
+    Warn,
+    /// Not working
+    Fail,
+}
+
+impl CheckStatus {
+    /// Checklist mark
+    pub fn mark(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "\u{2713}", // Check mark
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "\u{2717}", // Ballot X
+        }
+    }
+}
+
+/// A checked dependency
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthCheck {
+    /// What was checked
+    pub name: &'static str,
+    /// Outcome
+    pub status: CheckStatus,
+    /// Explanation (and what to do about a failure)
+    pub detail: String,
+}
+
+impl HealthCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+/// Plugin facts the health check inspects
+#[derive(Debug, Clone)]
+pub struct HealthInputs<'a> {
+    /// Lifecycle state (reflects the permission answer)
+    pub plugin_state: &'a PluginState,
+    /// Timer ticks received
+    pub ticks: u64,
+    /// Pipe messages received
+    pub pipe_messages: u64,
+    /// Broadcast messages ignored on pipe names this instance does not listen on
+    pub ignored_pipe_messages: u64,
+    /// Active configuration
+    pub config: &'a Config,
+    /// Size of the last render (columns, rows)
+    pub render_size: Option<(usize, usize)>,
+    /// Sequence gaps skipped after the hold time
+    pub sequence_gaps: u64,
+    /// Frames slower than `frame_time_warn_ms`
+    pub slow_frames: u64,
+    /// Why the pane is drawn with ASCII glyphs, if it is
+    pub ascii_reason: Option<&'static str>,
+    /// Subsystems that failed a lifecycle phase, with their status
+    pub failed_subsystems: Vec<(&'static str, String)>,
+}
+
+/// Results of a health check
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthReport {
+    /// Checks in display order
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// Run all checks
+    pub fn run(inputs: &HealthInputs) -> Self {
+        let permissions = match inputs.plugin_state {
+            PluginState::Running => HealthCheck::new("permissions", CheckStatus::Pass, "granted"),
+            PluginState::FallbackMode => HealthCheck::new("permissions", CheckStatus::Fail,
+                "denied, running in fallback mode (press r or send request_permissions)"),
+            PluginState::Error(error) => HealthCheck::new("permissions", CheckStatus::Fail, error.clone()),
+            _ => HealthCheck::new("permissions", CheckStatus::Warn, "waiting for an answer"),
+        };
+
+        let timer = if inputs.ticks > 0 {
+            HealthCheck::new("timer", CheckStatus::Pass, format!("{} ticks received", inputs.ticks))
+        } else {
+            HealthCheck::new("timer", CheckStatus::Fail, "no timer events received, animations and expiry are stalled")
+        };
+
+        let pipe = if inputs.pipe_messages > 0 {
+            let mut detail = format!("{} messages received", inputs.pipe_messages);
+            if !inputs.config.pipes.is_empty() {
+                detail.push_str(&format!(" on {}{}",
+                    inputs.config.pipes.join(", "),
+                    if inputs.config.strict_pipes { " (strict)" } else { "" }));
+            }
+            if inputs.ignored_pipe_messages > 0 {
+                detail.push_str(&format!(", {} ignored on other pipes", inputs.ignored_pipe_messages));
+            }
+            HealthCheck::new("pipe", CheckStatus::Pass, detail)
+        } else {
+            HealthCheck::new("pipe", CheckStatus::Warn, "no messages yet (try `zellij pipe -p visual-notifications -- health`)")
+        };
+
+        let config = match inputs.config.validate() {
+            Ok(()) => HealthCheck::new("config", CheckStatus::Pass, "valid"),
+            Err(e) => HealthCheck::new("config", CheckStatus::Fail, e),
+        };
+
+        let render = match inputs.render_size {
+            None => HealthCheck::new("render", CheckStatus::Warn, "not rendered yet"),
+            Some((cols, rows)) if cols < MIN_RENDER_COLS => HealthCheck::new("render", CheckStatus::Warn,
+                format!("{}x{} is too narrow for the status bar", cols, rows)),
+            Some((cols, rows)) => HealthCheck::new("render", CheckStatus::Pass, match inputs.ascii_reason {
+                Some(reason) => format!("{}x{}, ASCII glyphs ({})", cols, rows, reason),
+                None => format!("{}x{}", cols, rows),
+            }),
+        };
+
+        let ordering = if inputs.sequence_gaps == 0 {
+            HealthCheck::new("ordering", CheckStatus::Pass, "no sequence gaps")
+        } else {
+            HealthCheck::new("ordering", CheckStatus::Warn,
+                format!("{} sequence gaps skipped (messages lost or sequence_hold_ms too short)", inputs.sequence_gaps))
+        };
+
+        let frames = match (inputs.config.frame_time_warn_ms, inputs.slow_frames) {
+            (0, _) => HealthCheck::new("frames", CheckStatus::Pass, "slow frame detection off"),
+            (threshold, 0) => HealthCheck::new("frames", CheckStatus::Pass, format!("no frames over {}ms", threshold)),
+            (threshold, slow) => HealthCheck::new("frames", CheckStatus::Warn,
+                format!("{} frames over {}ms (see `stats`)", slow, threshold)),
+        };
+
+        let platform = inputs.config.host_platform.name();
+        let unavailable = inputs.config.unavailable_features();
+        let host = if unavailable.is_empty() {
+            HealthCheck::new("host", CheckStatus::Pass, platform)
+        } else {
+            HealthCheck::new("host", CheckStatus::Warn,
+                format!("{} host has no sh, disabled: {}", platform, unavailable.join(", ")))
+        };
+
+        let subsystems = if inputs.failed_subsystems.is_empty() {
+            HealthCheck::new("subsystems", CheckStatus::Pass, "all started")
+        } else {
+            let failed: Vec<String> = inputs.failed_subsystems.iter()
+                .map(|(name, status)| format!("{} {}", name, status))
+                .collect();
+            HealthCheck::new("subsystems", CheckStatus::Warn, format!("running without: {}", failed.join(", ")))
+        };
+
+        Self { checks: vec![permissions, timer, pipe, config, render, ordering, frames, subsystems, host] }
+    }
+
+    /// Whether no check failed
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|check| check.status != CheckStatus::Fail)
+    }
+
+    /// Checks that did not pass
+    pub fn problems(&self) -> impl Iterator<Item = &HealthCheck> {
+        self.checks.iter().filter(|check| check.status != CheckStatus::Pass)
+    }
+
+    /// One checklist line per check, e.g. `✓ timer: 120 ticks received`
+    pub fn lines(&self) -> Vec<String> {
+        self.checks.iter()
+            .map(|check| format!("{} {}: {}", check.status.mark(), check.name, check.detail))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs<'a>(plugin_state: &'a PluginState, config: &'a Config) -> HealthInputs<'a> {
+        HealthInputs {
+            plugin_state,
+            ticks: 20,
+            pipe_messages: 1,
+            ignored_pipe_messages: 0,
+            config,
+            render_size: Some((80, 1)),
+            sequence_gaps: 0,
+            slow_frames: 0,
+            ascii_reason: None,
+            failed_subsystems: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_healthy_report() {
+        let config = Config::default();
+        let report = HealthReport::run(&inputs(&PluginState::Running, &config));
+        assert!(report.is_healthy());
+        assert_eq!(report.problems().count(), 0);
+        assert_eq!(report.lines()[1], "\u{2713} timer: 20 ticks received");
+    }
+
+    #[test]
+    fn test_denied_permissions_and_invalid_config_fail() {
+        let config = Config { notification_timeout_ms: 10, ..Config::default() };
+        let report = HealthReport::run(&HealthInputs {
+            render_size: None,
+            sequence_gaps: 2,
+            slow_frames: 3,
+            ..inputs(&PluginState::FallbackMode, &config)
+        });
+        assert!(!report.is_healthy());
+        let problems: Vec<&str> = report.problems().map(|check| check.name).collect();
+        assert_eq!(problems, vec!["permissions", "config", "render", "ordering", "frames"]);
+    }
+
+    #[test]
+    fn test_windows_host_reports_disabled_features() {
+        use crate::host::HostPlatform;
+
+        let config = Config {
+            host_platform: HostPlatform::Windows,
+            sinks: vec!["visual".to_string(), "toast".to_string()],
+            experimental_features: vec!["toasts".to_string()],
+            capture_error_lines: 20,
+            ..Config::default()
+        };
+        let report = HealthReport::run(&inputs(&PluginState::Running, &config));
+        assert!(report.is_healthy());
+        assert_eq!(report.lines().last().unwrap(),
+            "! host: windows host has no sh, disabled: capture_error_lines, toast sink");
+    }
+}
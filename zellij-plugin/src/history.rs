@@ -0,0 +1,309 @@
+//! Notification history module for Zellij Visual Notifications
+//!
+//! Keeps a bounded record of received notifications, stamped with the plugin time they arrived,
+//! for the `list` pipe command and other after-the-fact inspection.
+//!
+//! A watch task that reports the same thing every few seconds fills the history with copies.
+//! Compaction (`history_compaction`, every `HISTORY_COMPACTION_INTERVAL_MS`, or the
+//! `compact_history` pipe command) merges runs of consecutive identical entries into the newest
+//! one, which keeps the time of the first and a count, so the timeline still shows when the run
+//! started and how long it went on. Notifications still displayed or waiting are never merged.
+
+use std::collections::VecDeque;
+use crate::config::HistoryCompaction;
+use crate::notification::Notification;
+use crate::text_diff;
+
+/// Default number of notifications kept
+pub const DEFAULT_HISTORY_MAX: usize = 200;
+
+/// Time between two background compaction passes (ms)
+pub const HISTORY_COMPACTION_INTERVAL_MS: u64 = 60_000;
+
+/// A received notification
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// Plugin time when received (ms)
+    pub received_at_ms: u64,
+    /// The notification as received
+    pub notification: Notification,
+    /// Plugin time when it was re-delivered out of terminal for lack of acknowledgement (ms)
+    pub ack_escalated_at_ms: Option<u64>,
+    /// Last lines of the pane's screen captured when the notification arrived
+    pub snapshot: Option<Vec<String>>,
+    /// Earlier identical entries merged into this one by compaction
+    pub repeats: u32,
+    /// Plugin time when the first of the merged entries was received (ms)
+    pub first_received_at_ms: u64,
+    /// Message of the first of the merged entries, if it differs from this one's
+    pub first_message: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Entry for a notification received at `received_at_ms`
+    pub fn new(notification: Notification, received_at_ms: u64) -> Self {
+        Self {
+            received_at_ms,
+            notification,
+            ack_escalated_at_ms: None,
+            snapshot: None,
+            repeats: 0,
+            first_received_at_ms: received_at_ms,
+            first_message: None,
+        }
+    }
+
+    /// Number of notifications the entry stands for
+    pub fn count(&self) -> u32 {
+        self.repeats + 1
+    }
+
+    /// Message showing how it changed over the merged entries, e.g. `tests failed: 3→5` (None if
+    /// it didn't, or changed too much to show in place)
+    pub fn trend(&self) -> Option<String> {
+        let first = self.first_message.as_deref()?;
+        text_diff::change(first, &self.notification.message)?.compact()
+    }
+
+    /// Title and message as listed, with the trend in place of the message if there is one
+    pub fn display_text(&self) -> String {
+        match (self.trend(), &self.notification.title) {
+            (Some(trend), Some(title)) => format!("{}: {}", title, trend),
+            (Some(trend), None) => trend,
+            (None, _) => self.notification.display_text(),
+        }
+    }
+
+    /// Whether a later entry repeats this one closely enough to be merged
+    fn repeated_by(&self, later: &HistoryEntry, mode: HistoryCompaction) -> bool {
+        let (a, b) = (&self.notification, &later.notification);
+        let text_matches = |x: &str, y: &str| match mode {
+            HistoryCompaction::Off => false,
+            HistoryCompaction::Exact => x == y,
+            HistoryCompaction::Similar => without_digits(x) == without_digits(y),
+        };
+        a.notification_type == b.notification_type
+            && a.source == b.source
+            && a.pane_id == b.pane_id
+            && a.tab_index == b.tab_index
+            && text_matches(&a.message, &b.message)
+            && text_matches(a.title.as_deref().unwrap_or(""), b.title.as_deref().unwrap_or(""))
+    }
+}
+
+/// Text with each run of digits replaced by `#` (counters, durations, times)
+fn without_digits(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !c.is_ascii_digit() {
+            result.push(c);
+        } else if !result.ends_with('#') {
+            result.push('#');
+        }
+    }
+    result
+}
+
+/// Bounded history of received notifications, oldest first
+#[derive(Debug, Clone)]
+pub struct NotificationHistory {
+    /// Entries, oldest first
+    entries: VecDeque<HistoryEntry>,
+    /// Maximum entries kept
+    max_entries: usize,
+    /// Entries dropped to keep the history bounded
+    evicted: u64,
+    /// Plugin time of the last compaction pass (ms)
+    compacted_at_ms: u64,
+}
+
+impl Default for NotificationHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_MAX)
+    }
+}
+
+impl NotificationHistory {
+    /// Create a history keeping at most `max_entries`
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_entries: max_entries.max(1),
+            evicted: 0,
+            compacted_at_ms: 0,
+        }
+    }
+
+    /// Record a notification (a notification re-entering the pipeline, e.g. after DND, is kept once)
+    pub fn record(&mut self, notification: &Notification, now_ms: u64) {
+        if self.entries.iter().any(|e| e.notification.id == notification.id) {
+            return;
+        }
+        while self.entries.len() >= self.max_entries {
+            self.entries.pop_front();
+            self.evicted += 1;
+        }
+        self.entries.push_back(HistoryEntry::new(notification.clone(), now_ms));
+    }
+
+    /// Put back an entry from another plugin instance, in order of arrival (kept once, like
+    /// recorded notifications)
+    pub fn restore(&mut self, entry: HistoryEntry) {
+        if self.get(&entry.notification.id).is_some() {
+            return;
+        }
+        let position = self.entries.partition_point(|e| e.received_at_ms <= entry.received_at_ms);
+        self.entries.insert(position, entry);
+        while self.entries.len() > self.max_entries {
+            self.entries.pop_front();
+            self.evicted += 1;
+        }
+    }
+
+    /// Mark a notification as escalated for lack of acknowledgement
+    pub fn mark_ack_escalated(&mut self, id: &str, now_ms: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.notification.id == id) {
+            entry.ack_escalated_at_ms = Some(now_ms);
+        }
+    }
+
+    /// Attach a pane snapshot to a notification
+    pub fn attach_snapshot(&mut self, id: &str, lines: Vec<String>) -> bool {
+        match self.entries.iter_mut().find(|e| e.notification.id == id) {
+            Some(entry) => {
+                entry.snapshot = Some(lines);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the background compaction pass is due
+    pub fn compaction_due(&self, now_ms: u64) -> bool {
+        now_ms >= self.compacted_at_ms + HISTORY_COMPACTION_INTERVAL_MS
+    }
+
+    /// Merge runs of consecutive entries repeating each other into their newest entry, leaving
+    /// the entries of `live` notifications alone, and return the number of entries merged away
+    pub fn compact(&mut self, mode: HistoryCompaction, live: impl Fn(&str) -> bool, now_ms: u64) -> usize {
+        self.compacted_at_ms = now_ms;
+        if mode == HistoryCompaction::Off {
+            return 0;
+        }
+        let before = self.entries.len();
+        let mut compacted: VecDeque<HistoryEntry> = VecDeque::with_capacity(before);
+        for mut entry in std::mem::take(&mut self.entries) {
+            let repeats = compacted.back().is_some_and(|previous| previous.repeated_by(&entry, mode)
+                && !live(&previous.notification.id)
+                && !live(&entry.notification.id));
+            if repeats {
+                if let Some(previous) = compacted.pop_back() {
+                    entry.repeats += previous.repeats + 1;
+                    entry.first_received_at_ms = previous.first_received_at_ms;
+                    entry.first_message = previous.first_message
+                        .or(Some(previous.notification.message))
+                        .filter(|first| *first != entry.notification.message);
+                    entry.snapshot = entry.snapshot.or(previous.snapshot);
+                }
+            }
+            compacted.push_back(entry);
+        }
+        self.entries = compacted;
+        before - self.entries.len()
+    }
+
+    /// Entry for a notification
+    pub fn get(&self, id: &str) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|e| e.notification.id == id)
+    }
+
+    /// Entries, newest first
+    pub fn newest_first(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter().rev()
+    }
+
+    /// Number of recorded notifications
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maximum entries kept
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Number of entries dropped to keep the history bounded
+    pub fn evicted_count(&self) -> u64 {
+        self.evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_is_bounded_and_deduplicated() {
+        let mut history = NotificationHistory::new(2);
+        let first = Notification::info("first");
+        history.record(&first, 0);
+        history.record(&first, 10);
+        assert_eq!(history.len(), 1);
+
+        history.record(&Notification::info("second"), 20);
+        history.record(&Notification::info("third"), 30);
+        let messages: Vec<&str> = history.newest_first().map(|e| e.notification.message.as_str()).collect();
+        assert_eq!(messages, vec!["third", "second"]);
+    }
+
+    #[test]
+    fn test_restored_entries_keep_arrival_order() {
+        let mut history = NotificationHistory::new(3);
+        history.record(&Notification::info("live"), 50);
+        for (message, received_at_ms) in [("older", 10), ("oldest", 0), ("newer", 60)] {
+            history.restore(HistoryEntry::new(Notification::info(message), received_at_ms));
+        }
+        let messages: Vec<&str> = history.newest_first().map(|e| e.notification.message.as_str()).collect();
+        assert_eq!(messages, vec!["newer", "live", "older"]);
+    }
+
+    #[test]
+    fn test_compaction_merges_runs_of_repeats() {
+        let mut history = NotificationHistory::new(10);
+        for (i, message) in ["Watching: 3 files", "Watching: 3 files", "Watching: 5 files", "Build failed", "Watching: 3 files"]
+            .iter()
+            .enumerate()
+        {
+            history.record(&Notification::info(message).from_source("watch"), i as u64 * 1000);
+        }
+
+        assert_eq!(history.compact(HistoryCompaction::Exact, |_| false, 0), 1);
+        let oldest = history.newest_first().last().unwrap();
+        assert_eq!((oldest.count(), oldest.first_received_at_ms, oldest.received_at_ms), (2, 0, 1000));
+        assert_eq!(history.compact(HistoryCompaction::Exact, |_| false, 0), 0);
+
+        // Digits are ignored when similar; a different message ends the run
+        assert_eq!(history.compact(HistoryCompaction::Similar, |_| false, 0), 1);
+        let messages: Vec<(&str, u32)> = history.newest_first()
+            .map(|e| (e.notification.message.as_str(), e.count()))
+            .collect();
+        assert_eq!(messages, vec![("Watching: 3 files", 1), ("Build failed", 1), ("Watching: 5 files", 3)]);
+        let merged = history.newest_first().last().unwrap();
+        assert_eq!(merged.trend().as_deref(), Some("Watching: 3\u{2192}5 files"));
+        assert_eq!(history.newest_first().next().unwrap().trend(), None);
+        assert!(!history.compaction_due(HISTORY_COMPACTION_INTERVAL_MS - 1));
+
+        // Live notifications are kept apart
+        let mut history = NotificationHistory::new(10);
+        let shown = Notification::info("tick");
+        history.record(&Notification::info("tick"), 0);
+        history.record(&shown, 1000);
+        assert_eq!(history.compact(HistoryCompaction::Exact, |id| id == shown.id, 0), 0);
+        assert_eq!(history.compact(HistoryCompaction::Off, |_| false, 0), 0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Host platform module for Zellij Visual Notifications
+//!
+//! Paths handed to commands on the host (the unread count file, pane snapshots, the IPC socket)
+//! are written in the host's own style, which on WSL and Windows-hosted builds means backslashes
+//! and drive letters. They are normalized here, and the platform decides which features can
+//! run at all: those that shell out through `sh` are switched off on a Windows host.
+
+use serde::{Deserialize, Serialize};
+
+/// Default directory for temporary files on the host
+pub const DEFAULT_TEMP_DIR: &str = "/tmp";
+
+/// Operating system Zellij runs on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HostPlatform {
+    /// Linux, macOS and other Unix hosts
+    #[default]
+    Unix,
+    /// Linux under WSL (Windows drive paths map to `/mnt/<drive>`)
+    Wsl,
+    /// Native Windows build (no POSIX shell)
+    Windows,
+}
+
+impl HostPlatform {
+    /// Parse a platform name (`unix`, `wsl`, `windows`)
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "wsl" => Self::Wsl,
+            "windows" | "win" => Self::Windows,
+            _ => Self::Unix,
+        }
+    }
+
+    /// Platform name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Unix => "unix",
+            Self::Wsl => "wsl",
+            Self::Windows => "windows",
+        }
+    }
+
+    /// Whether host commands can run through `sh` (snapshots, toasts, the unread count file)
+    pub fn has_shell(&self) -> bool {
+        *self != Self::Windows
+    }
+
+    /// Normalize a host path: backslashes become slashes (WSL and Windows), drive paths map to
+    /// `/mnt/<drive>` on WSL, and repeated and trailing slashes are removed
+    pub fn normalize_path(&self, path: &str) -> String {
+        let mut path = path.trim().to_string();
+        if *self != Self::Unix {
+            path = path.replace('\\', "/");
+        }
+        if *self == Self::Wsl {
+            if let Some(drive) = drive_letter(&path) {
+                path = format!("/mnt/{}/{}", drive.to_ascii_lowercase(), &path[2..]);
+            }
+        }
+
+        let mut normalized = String::with_capacity(path.len());
+        for c in path.chars() {
+            if !(c == '/' && normalized.ends_with('/')) {
+                normalized.push(c);
+            }
+        }
+        if normalized.len() > 1 && normalized.ends_with('/') && !normalized.ends_with(":/") {
+            normalized.pop();
+        }
+        normalized
+    }
+
+    /// Whether a normalized path is absolute on this host (`/...`, or `C:/...` on Windows)
+    pub fn is_absolute(&self, path: &str) -> bool {
+        path.starts_with('/')
+            || (*self == Self::Windows && drive_letter(path).is_some() && path[2..].starts_with('/'))
+    }
+}
+
+/// Drive letter of a `C:...` path
+fn drive_letter(path: &str) -> Option<char> {
+    let mut chars = path.chars();
+    match (chars.next(), chars.next()) {
+        (Some(drive), Some(':')) if drive.is_ascii_alphabetic() => Some(drive),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_paths_per_platform() {
+        let wsl = HostPlatform::Wsl;
+        assert_eq!(wsl.normalize_path(r"C:\Users\me\notify.count"), "/mnt/c/Users/me/notify.count");
+        assert_eq!(wsl.normalize_path("/tmp//zellij/"), "/tmp/zellij");
+
+        let windows = HostPlatform::Windows;
+        assert_eq!(windows.normalize_path(r"C:\Temp\"), "C:/Temp");
+        assert_eq!(windows.normalize_path(r"D:\"), "D:/");
+        assert!(windows.is_absolute("C:/Temp"));
+        assert!(!windows.is_absolute("C:Temp") && !windows.has_shell());
+
+        // Backslashes are ordinary file name characters on Unix
+        let unix = HostPlatform::Unix;
+        assert_eq!(unix.normalize_path(r"/tmp/a\b"), r"/tmp/a\b");
+        assert!(!unix.is_absolute("C:/Temp"));
+        assert_eq!(HostPlatform::from_str("WSL"), HostPlatform::Wsl);
+    }
+}
@@ -0,0 +1,182 @@
+//! Icon module for Zellij Visual Notifications
+//!
+//! Width-verified icon tables for the Unicode and ASCII sets. Some glyphs (`⚠`, `ℹ`, `✔`, `⏱`)
+//! are text-presentation emoji that terminals draw one or two cells wide, and others (`❗`, `🔔`,
+//! `📌`) are double-width; either breaks column alignment where the terminal disagrees with the
+//! width the plugin counted. Every entry records its width class and a single-width alternative,
+//! and `icon_width_policy` decides which classes are replaced by their alternative.
+
+use serde::{Deserialize, Serialize};
+use crate::notification::NotificationType;
+
+/// How many cells a glyph takes across terminals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphWidth {
+    /// One cell everywhere
+    Single,
+    /// One or two cells depending on the terminal and font (text-presentation emoji)
+    Ambiguous,
+    /// Two cells (emoji presentation)
+    Double,
+}
+
+/// Which glyphs are replaced by their single-width alternative
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IconWidthPolicy {
+    /// Draw every glyph as is
+    #[default]
+    Any,
+    /// Replace double-width glyphs, keep ambiguous ones
+    NoDouble,
+    /// Replace double-width and ambiguous glyphs
+    Single,
+}
+
+impl IconWidthPolicy {
+    /// Parse `any`, `no_double` or `single` (anything else is `any`)
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "single" | "single_width" | "narrow" | "strict" => Self::Single,
+            "no_double" | "no_wide" => Self::NoDouble,
+            _ => Self::Any,
+        }
+    }
+
+    /// Whether a glyph of this width is drawn as is
+    pub fn allows(&self, width: GlyphWidth) -> bool {
+        match self {
+            Self::Any => true,
+            Self::NoDouble => width != GlyphWidth::Double,
+            Self::Single => width == GlyphWidth::Single,
+        }
+    }
+}
+
+/// Icons the renderer draws
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    // One per notification type
+    Success,
+    Error,
+    Warning,
+    Info,
+    Progress,
+    Attention,
+    /// Plugin icon (status bar)
+    Bell,
+    /// Pinned entry marker
+    Pin,
+    /// Muted panes indicator
+    Muted,
+    /// SLO breach marker
+    Stopwatch,
+    /// Time since the latest notification
+    Timer,
+    /// Response deadline countdown
+    Deadline,
+}
+
+impl Icon {
+    /// Icon of a notification type
+    pub fn for_type(notification_type: &NotificationType) -> Self {
+        match notification_type {
+            NotificationType::Success => Icon::Success,
+            NotificationType::Error => Icon::Error,
+            NotificationType::Warning => Icon::Warning,
+            NotificationType::Info => Icon::Info,
+            NotificationType::Progress => Icon::Progress,
+            NotificationType::Attention => Icon::Attention,
+        }
+    }
+}
+
+/// An icon's glyph in one set, with its width class and single-width alternative
+#[derive(Debug, Clone, Copy)]
+pub struct IconEntry {
+    pub icon: Icon,
+    pub glyph: &'static str,
+    pub width: GlyphWidth,
+    /// Drawn instead of `glyph` when the policy rejects its width
+    pub single: &'static str,
+}
+
+const fn entry(icon: Icon, glyph: &'static str, width: GlyphWidth, single: &'static str) -> IconEntry {
+    IconEntry { icon, glyph, width, single }
+}
+
+/// Unicode icon set
+pub const UNICODE_ICONS: &[IconEntry] = &[
+    entry(Icon::Success, "\u{2714}", GlyphWidth::Ambiguous, "\u{2713}"), // Heavy check, check mark
+    entry(Icon::Error, "\u{2718}", GlyphWidth::Single, "\u{2718}"),      // X mark
+    entry(Icon::Warning, "\u{26A0}", GlyphWidth::Ambiguous, "!"),        // Warning triangle
+    entry(Icon::Info, "\u{2139}", GlyphWidth::Ambiguous, "i"),           // Info symbol
+    entry(Icon::Progress, "\u{21BB}", GlyphWidth::Single, "\u{21BB}"),   // Rotating arrow
+    entry(Icon::Attention, "\u{2757}", GlyphWidth::Double, "!"),         // Exclamation mark
+    entry(Icon::Bell, "\u{1F514}", GlyphWidth::Double, "[N]"),
+    entry(Icon::Pin, "\u{1F4CC}", GlyphWidth::Double, "^"),
+    entry(Icon::Muted, "\u{1F507}", GlyphWidth::Double, "M"),            // Muted speaker
+    entry(Icon::Stopwatch, "\u{23F1}", GlyphWidth::Ambiguous, "SLO"),
+    entry(Icon::Timer, "\u{23F2}", GlyphWidth::Ambiguous, "T"),          // Timer clock
+    entry(Icon::Deadline, "\u{231B}", GlyphWidth::Double, "\u{29D7}"),    // Hourglass, black hourglass
+];
+
+/// ASCII fallback set
+pub const ASCII_ICONS: &[IconEntry] = &[
+    entry(Icon::Success, "+", GlyphWidth::Single, "+"),
+    entry(Icon::Error, "X", GlyphWidth::Single, "X"),
+    entry(Icon::Warning, "!", GlyphWidth::Single, "!"),
+    entry(Icon::Info, "i", GlyphWidth::Single, "i"),
+    entry(Icon::Progress, "~", GlyphWidth::Single, "~"),
+    entry(Icon::Attention, "!", GlyphWidth::Single, "!"),
+    entry(Icon::Bell, "[N]", GlyphWidth::Single, "[N]"),
+    entry(Icon::Pin, "^", GlyphWidth::Single, "^"),
+    entry(Icon::Muted, "M", GlyphWidth::Single, "M"),
+    entry(Icon::Stopwatch, "SLO", GlyphWidth::Single, "SLO"),
+    entry(Icon::Timer, "T", GlyphWidth::Single, "T"),
+    entry(Icon::Deadline, "D", GlyphWidth::Single, "D"),
+];
+
+/// Glyph drawn for an icon in the Unicode or ASCII set under a width policy
+pub fn glyph(icon: Icon, unicode: bool, policy: IconWidthPolicy) -> &'static str {
+    let set = if unicode { UNICODE_ICONS } else { ASCII_ICONS };
+    set.iter()
+        .find(|entry| entry.icon == icon)
+        .map(|entry| if policy.allows(entry.width) { entry.glyph } else { entry.single })
+        .unwrap_or("?")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrap::display_width;
+
+    #[test]
+    fn test_policy_substitutes_by_width() {
+        assert_eq!(glyph(Icon::Warning, true, IconWidthPolicy::Any), "\u{26A0}");
+        assert_eq!(glyph(Icon::Warning, true, IconWidthPolicy::NoDouble), "\u{26A0}");
+        assert_eq!(glyph(Icon::Warning, true, IconWidthPolicy::Single), "!");
+        assert_eq!(glyph(Icon::Bell, true, IconWidthPolicy::NoDouble), "[N]");
+        assert_eq!(glyph(Icon::Success, true, IconWidthPolicy::Single), "\u{2713}");
+        // Single-width glyphs are kept
+        assert_eq!(glyph(Icon::Error, true, IconWidthPolicy::Single), "\u{2718}");
+        assert_eq!(glyph(Icon::Info, false, IconWidthPolicy::Single), "i");
+
+        assert_eq!(IconWidthPolicy::from_str("single"), IconWidthPolicy::Single);
+        assert_eq!(IconWidthPolicy::from_str("no-double"), IconWidthPolicy::NoDouble);
+        assert_eq!(IconWidthPolicy::from_str("whatever"), IconWidthPolicy::Any);
+    }
+
+    #[test]
+    fn test_tables_are_complete_and_alternatives_single_width() {
+        for set in [UNICODE_ICONS, ASCII_ICONS] {
+            assert_eq!(set.len(), ASCII_ICONS.len());
+            for entry in set {
+                // One cell per character, and the alternative is itself a single-width glyph
+                assert_eq!(display_width(entry.single), entry.single.chars().count(), "{:?}", entry.icon);
+                assert!(!set.iter().any(|other| other.glyph == entry.single
+                    && other.width != GlyphWidth::Single), "{:?}", entry.icon);
+            }
+        }
+        assert!(ASCII_ICONS.iter().all(|entry| entry.width == GlyphWidth::Single && entry.glyph.is_ascii()));
+    }
+}
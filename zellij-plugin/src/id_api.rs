@@ -0,0 +1,157 @@
+//! Notification ID API module for Zellij Visual Notifications
+//!
+//! Custom status bars and dashboards built as plugins of their own can act on single
+//! notifications by ID with three custom messages: `ack_id` acknowledges a notification as if
+//! the user had seen it, `dismiss_id` withdraws it (a `cancelled` receipt) and `query_id` only
+//! looks it up. The payload is the ID, or a JSON object `{"id": "...", "reply_to": "<plugin url>"}`.
+//! Every request is answered on the `visual-notifications-reply` pipe, sent to `reply_to` or
+//! broadcast to all plugins, with the state the notification was in when the request arrived
+//! and whether the request changed anything.
+
+use serde::{Deserialize, Serialize};
+
+/// Pipe the answers are sent on
+pub const REPLY_PIPE: &str = "visual-notifications-reply";
+
+/// What a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdRequestKind {
+    /// Acknowledge the notification (`ack_id`)
+    Ack,
+    /// Withdraw the notification (`dismiss_id`)
+    Dismiss,
+    /// Look the notification up (`query_id`)
+    Query,
+}
+
+impl IdRequestKind {
+    /// Kind of a custom message, if it is an ID request
+    pub fn from_message(message: &str) -> Option<Self> {
+        match message {
+            "ack_id" => Some(Self::Ack),
+            "dismiss_id" => Some(Self::Dismiss),
+            "query_id" => Some(Self::Query),
+            _ => None,
+        }
+    }
+
+    /// Custom message name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ack => "ack_id",
+            Self::Dismiss => "dismiss_id",
+            Self::Query => "query_id",
+        }
+    }
+}
+
+/// Payload of an ID request
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct IdRequest {
+    /// Notification ID
+    pub id: String,
+    /// Plugin URL the answer is sent to (None = broadcast)
+    #[serde(default)]
+    pub reply_to: Option<String>,
+}
+
+impl IdRequest {
+    /// Parse a JSON object payload or a bare ID
+    pub fn parse(payload: &str) -> Result<Self, String> {
+        let payload = payload.trim();
+        let request = if payload.starts_with('{') {
+            serde_json::from_str(payload).map_err(|e| format!("invalid request: {}", e))?
+        } else {
+            Self { id: payload.to_string(), reply_to: None }
+        };
+        if request.id.is_empty() {
+            return Err("missing notification id".to_string());
+        }
+        Ok(request)
+    }
+}
+
+/// Where a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdStatus {
+    /// Shown on its pane
+    Displayed,
+    /// Shown on its pane and marked read
+    Read,
+    /// Waiting in the queue
+    Queued,
+    /// Held back (user busy, suppression, pane not yet there)
+    Held,
+    /// Handled already, only in the history
+    Gone,
+    /// Never seen, or forgotten by the history
+    Unknown,
+}
+
+/// Answer to an ID request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IdReply {
+    /// Custom message answered
+    pub request: &'static str,
+    /// Notification ID
+    pub id: String,
+    /// Whether the request was carried out
+    pub ok: bool,
+    /// State of the notification when the request arrived
+    pub status: IdStatus,
+    /// Pane the notification is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pane_id: Option<u32>,
+    /// Why the request was not carried out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IdReply {
+    /// Answer to a request that could not be read
+    pub fn invalid(kind: IdRequestKind, error: String) -> Self {
+        Self {
+            request: kind.name(),
+            id: String::new(),
+            ok: false,
+            status: IdStatus::Unknown,
+            pane_id: None,
+            error: Some(error),
+        }
+    }
+
+    /// JSON payload of the answer
+    pub fn payload(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_requests_and_replies() {
+        assert_eq!(IdRequestKind::from_message("dismiss_id"), Some(IdRequestKind::Dismiss));
+        assert_eq!(IdRequestKind::from_message("clear"), None);
+
+        assert_eq!(IdRequest::parse(" n-7\n").unwrap(), IdRequest { id: "n-7".to_string(), reply_to: None });
+        let request = IdRequest::parse(r#"{"id": "n-7", "reply_to": "file:/tmp/bar.wasm"}"#).unwrap();
+        assert_eq!(request.reply_to.as_deref(), Some("file:/tmp/bar.wasm"));
+        assert!(IdRequest::parse("").is_err());
+        assert!(IdRequest::parse(r#"{"reply_to": "bar"}"#).is_err());
+
+        let reply = IdReply {
+            request: IdRequestKind::Ack.name(),
+            id: "n-7".to_string(),
+            ok: true,
+            status: IdStatus::Displayed,
+            pane_id: Some(2),
+            error: None,
+        };
+        assert_eq!(reply.payload(), r#"{"request":"ack_id","id":"n-7","ok":true,"status":"displayed","pane_id":2}"#);
+        assert_eq!(IdReply::invalid(IdRequestKind::Query, "missing notification id".to_string()).payload(),
+            r#"{"request":"query_id","id":"","ok":false,"status":"unknown","error":"missing notification id"}"#);
+    }
+}
@@ -0,0 +1,125 @@
+//! Idle detection module for Zellij Visual Notifications
+//!
+//! Tracks user activity across the session (key and mode events) to decide whether Attention
+//! notifications should be escalated (user away) or briefly deferred (user actively working
+//! elsewhere). Tab and pane updates don't count: running programs change pane titles all the
+//! time, which would keep the user busy for good.
+
+/// Activity classification derived from the time since the last activity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivityState {
+    /// Activity within the typing pause window
+    Busy,
+    /// Neither busy nor idle
+    Active,
+    /// No activity for longer than the idle threshold
+    Idle,
+}
+
+/// Tracker for session-wide user activity
+#[derive(Debug, Clone)]
+pub struct IdleTracker {
+    /// Timestamp of the last observed activity (ms), None before the first
+    last_activity_ms: Option<u64>,
+    /// Time without activity after which the user is considered idle (ms)
+    idle_threshold_ms: u64,
+    /// Pause after activity before visual interruptions are allowed (ms)
+    typing_pause_ms: u64,
+    /// Whether idle detection is enabled
+    enabled: bool,
+}
+
+impl Default for IdleTracker {
+    fn default() -> Self {
+        Self::new(300_000, 1500)
+    }
+}
+
+impl IdleTracker {
+    /// Create a new idle tracker
+    pub fn new(idle_threshold_ms: u64, typing_pause_ms: u64) -> Self {
+        Self {
+            last_activity_ms: None,
+            idle_threshold_ms,
+            typing_pause_ms,
+            enabled: true,
+        }
+    }
+
+    /// Enable or disable idle detection
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Record user activity at the given time
+    pub fn record_activity(&mut self, now_ms: u64) {
+        self.last_activity_ms = Some(now_ms);
+    }
+
+    /// Milliseconds since the last activity (since load if there was none)
+    pub fn idle_for(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.last_activity_ms.unwrap_or(0))
+    }
+
+    /// Classify the current activity state
+    pub fn activity_state(&self, now_ms: u64) -> ActivityState {
+        if !self.enabled {
+            return ActivityState::Active;
+        }
+
+        let idle_for = self.idle_for(now_ms);
+        if idle_for >= self.idle_threshold_ms {
+            ActivityState::Idle
+        } else if self.last_activity_ms.is_some() && idle_for < self.typing_pause_ms {
+            ActivityState::Busy
+        } else {
+            ActivityState::Active
+        }
+    }
+
+    /// Check if the user is considered idle
+    pub fn is_idle(&self, now_ms: u64) -> bool {
+        self.activity_state(now_ms) == ActivityState::Idle
+    }
+
+    /// Check if the user is actively working (within the typing pause window)
+    pub fn is_busy(&self, now_ms: u64) -> bool {
+        self.activity_state(now_ms) == ActivityState::Busy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_states() {
+        let mut tracker = IdleTracker::new(10_000, 1000);
+        tracker.record_activity(5000);
+
+        assert_eq!(tracker.activity_state(5500), ActivityState::Busy);
+        assert_eq!(tracker.activity_state(7000), ActivityState::Active);
+        assert_eq!(tracker.activity_state(15_000), ActivityState::Idle);
+        assert_eq!(tracker.idle_for(7000), 2000);
+    }
+
+    #[test]
+    fn test_activity_resets_idle() {
+        let mut tracker = IdleTracker::new(10_000, 1000);
+        assert!(!tracker.is_busy(0));
+        assert!(tracker.is_idle(20_000));
+
+        tracker.record_activity(20_000);
+        assert!(!tracker.is_idle(20_500));
+        assert!(tracker.is_busy(20_500));
+    }
+
+    #[test]
+    fn test_disabled_tracker() {
+        let mut tracker = IdleTracker::new(10_000, 1000);
+        tracker.set_enabled(false);
+
+        assert!(!tracker.is_idle(50_000));
+        assert!(!tracker.is_busy(0));
+    }
+}
@@ -0,0 +1,200 @@
+//! File ingestion module for Zellij Visual Notifications
+//!
+//! Where `zellij pipe` is awkward (remote scripts over SSH writing to a shared mount, say),
+//! notifications can be appended to a file as JSON lines instead. With `ingest_file` set, the
+//! plugin reads what was appended every `ingest_file_poll_ms` and handles each complete line like
+//! a JSON pipe message. The file must be reachable from the plugin, e.g. under `/host` (the
+//! directory Zellij was started in). The read offset is kept in the plugin data directory, so a
+//! restarted plugin carries on where the last one stopped instead of replaying old lines; a file
+//! seen for the first time is read from its end, and one that shrank (truncated or rotated) from
+//! its start.
+
+use std::fs::File;
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+/// Default location of the persisted read offset (plugin data directory)
+pub const INGEST_STATE_PATH: &str = "/data/ingest_offset";
+
+/// Default time between two reads of the file (ms)
+pub const DEFAULT_INGEST_FILE_POLL_MS: u64 = 1_000;
+
+/// Shortest allowed time between two reads (ms)
+pub const MIN_INGEST_FILE_POLL_MS: u64 = 100;
+
+/// Most bytes read at once; the rest is read on the following polls
+const MAX_READ_BYTES: u64 = 256 * 1024;
+
+/// Read position in the ingested file
+#[derive(Debug, Clone, Default)]
+pub struct FileTail {
+    /// Watched file
+    path: String,
+    /// Bytes already ingested, None until the file was first looked at
+    offset: Option<u64>,
+    /// Plugin time of the last read (ms)
+    last_poll_ms: Option<u64>,
+    /// Whether the last read failed (the failure is reported once)
+    failing: bool,
+    /// Offset changed since the last save
+    dirty: bool,
+}
+
+impl FileTail {
+    /// Tail `path`, resuming from a persisted offset when it was saved for the same file
+    pub fn parse(path: &str, state: &str) -> Self {
+        let offset = state.lines().next()
+            .and_then(|line| line.split_once('\t'))
+            .filter(|(_, saved_path)| *saved_path == path)
+            .and_then(|(offset, _)| offset.parse().ok());
+        Self { path: path.to_string(), offset, ..Self::default() }
+    }
+
+    /// Serialize the offset to its persisted form (`<offset>\t<path>`)
+    pub fn serialize(&self) -> String {
+        self.offset.map(|offset| format!("{}\t{}\n", offset, self.path)).unwrap_or_default()
+    }
+
+    /// Tail `path` with the offset saved in `state_path`
+    pub fn load(path: &str, state_path: &str) -> Self {
+        Self::parse(path, &std::fs::read_to_string(state_path).unwrap_or_default())
+    }
+
+    /// Save the offset to a file when it changed
+    pub fn save(&mut self, state_path: &str) -> std::io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.dirty = false;
+        std::fs::write(state_path, self.serialize())
+    }
+
+    /// Watched file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether the file is due to be read again
+    pub fn due(&mut self, poll_ms: u64, now_ms: u64) -> bool {
+        if self.last_poll_ms.is_some_and(|last| now_ms < last + poll_ms) {
+            return false;
+        }
+        self.last_poll_ms = Some(now_ms);
+        true
+    }
+
+    /// Read the complete lines appended since the last read (blank ones skipped). A failure is
+    /// returned once, until a read succeeds again.
+    pub fn read(&mut self) -> Result<Vec<String>, String> {
+        match self.read_lines() {
+            Ok(lines) => {
+                self.failing = false;
+                Ok(lines)
+            }
+            Err(_) if self.failing => Ok(Vec::new()),
+            Err(e) => {
+                self.failing = true;
+                Err(format!("can't read {}: {}", self.path, e))
+            }
+        }
+    }
+
+    fn read_lines(&mut self) -> std::io::Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound && self.offset.is_none() => {
+                // Everything written once it appears is new
+                self.set_offset(0);
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e),
+        };
+        let len = file.metadata()?.len();
+        let offset = match self.offset {
+            None => {
+                self.set_offset(len);
+                return Ok(Vec::new());
+            }
+            Some(offset) if offset > len => 0,
+            Some(offset) => offset,
+        };
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buffer = Vec::new();
+        file.take(MAX_READ_BYTES).read_to_end(&mut buffer)?;
+
+        // Only complete lines; a partial last line is read again once its newline is written
+        let end = match buffer.iter().rposition(|byte| *byte == b'\n') {
+            Some(newline) => newline + 1,
+            None if buffer.len() as u64 >= MAX_READ_BYTES => {
+                self.set_offset(offset + buffer.len() as u64);
+                return Err(std::io::Error::new(ErrorKind::InvalidData, "skipped a line longer than 256 KiB"));
+            }
+            None => {
+                self.set_offset(offset);
+                return Ok(Vec::new());
+            }
+        };
+        self.set_offset(offset + end as u64);
+        Ok(String::from_utf8_lossy(&buffer[..end]).lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn set_offset(&mut self, offset: u64) {
+        if self.offset != Some(offset) {
+            self.offset = Some(offset);
+            self.dirty = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn append(path: &std::path::Path, text: &str) {
+        std::fs::OpenOptions::new().create(true).append(true).open(path).unwrap().write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_reads_appended_lines_and_resumes() {
+        let path = std::env::temp_dir().join("zellij-notify-test-ingest.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let name = path.display().to_string();
+
+        // Not created yet: everything written later is new
+        let mut tail = FileTail::parse(&name, "");
+        assert_eq!(tail.read(), Ok(vec![]));
+        append(&path, "{\"message\":\"one\"}\n\n{\"message\":\"tw");
+        assert_eq!(tail.read(), Ok(vec!["{\"message\":\"one\"}".to_string()]));
+        append(&path, "o\"}\n");
+        assert_eq!(tail.read(), Ok(vec!["{\"message\":\"two\"}".to_string()]));
+
+        // A restart resumes from the saved offset
+        let mut resumed = FileTail::parse(&name, &tail.serialize());
+        append(&path, "{\"message\":\"three\"}\n");
+        assert_eq!(resumed.read(), Ok(vec!["{\"message\":\"three\"}".to_string()]));
+
+        // A file seen for the first time is read from its end; a truncated one from its start
+        let mut fresh = FileTail::parse(&name, "12\t/host/other.jsonl\n");
+        assert_eq!(fresh.read(), Ok(vec![]));
+        std::fs::write(&path, "{\"message\":\"four\"}\n").unwrap();
+        assert_eq!(fresh.read(), Ok(vec!["{\"message\":\"four\"}".to_string()]));
+        std::fs::remove_file(&path).unwrap();
+
+        // A missing file is reported once
+        assert!(fresh.read().is_err());
+        assert_eq!(fresh.read(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_polls_spaced_by_interval() {
+        let mut tail = FileTail::parse("/host/notify.jsonl", "");
+        assert!(tail.due(1_000, 0));
+        assert!(!tail.due(1_000, 999));
+        assert!(tail.due(1_000, 1_000));
+        assert_eq!(tail.path(), "/host/notify.jsonl");
+    }
+}
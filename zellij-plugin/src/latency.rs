@@ -0,0 +1,146 @@
+//! Delivery latency module for Zellij Visual Notifications
+//!
+//! Messages may carry the sender's Unix time (`timestamp`, ms). The plugin compares it with the
+//! host clock twice: when the message arrives (hook script and pipe) and when the notification is
+//! displayed (adding the queue, DND holds and sequencing). The `stats` command shows the p50 and
+//! p95 of both over the recent notifications, so slowness can be placed in the sender, the pipe,
+//! or the plugin's queue processing. Notifications displayed more than `latency_warn_ms` after
+//! they were sent are counted and logged (at most once a minute). Sender and host clocks are
+//! assumed to agree: skew shows up as latency, or as none when the sender's clock is ahead.
+
+use std::collections::VecDeque;
+
+/// Notifications kept per stage for the percentiles
+pub const LATENCY_WINDOW: usize = 200;
+
+/// Arrived notifications remembered until they are displayed (oldest forgotten first)
+pub const MAX_PENDING_DISPLAYS: usize = 500;
+
+/// Default latency above which a display is logged (ms)
+pub const DEFAULT_LATENCY_WARN_MS: u64 = 2_000;
+
+/// Shortest time between two latency warnings (ms)
+pub const LATENCY_WARNING_INTERVAL_MS: u64 = 60_000;
+
+/// Recent latencies of one stage
+#[derive(Debug, Clone, Default)]
+struct Samples {
+    /// Recent latencies (ms), oldest first
+    recent_ms: VecDeque<u64>,
+}
+
+impl Samples {
+    fn record(&mut self, latency_ms: u64) {
+        if self.recent_ms.len() >= LATENCY_WINDOW {
+            self.recent_ms.pop_front();
+        }
+        self.recent_ms.push_back(latency_ms);
+    }
+
+    /// Percentile of the recent latencies (ms), None before the first
+    fn percentile(&self, percent: usize) -> Option<u64> {
+        let mut sorted: Vec<u64> = self.recent_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        sorted.get((sorted.len() * percent).div_ceil(100).saturating_sub(1)).copied()
+    }
+
+    /// `p50 40ms, p95 120ms`, or `-` before the first
+    fn summary(&self) -> String {
+        match (self.percentile(50), self.percentile(95)) {
+            (Some(p50), Some(p95)) => format!("p50 {}, p95 {}", format_latency(p50), format_latency(p95)),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+/// Latency from sender to arrival and to display
+#[derive(Debug, Clone, Default)]
+pub struct LatencyTracker {
+    /// Sender to arrival at the plugin
+    arrival: Samples,
+    /// Sender to display
+    display: Samples,
+    /// Sender timestamps of arrived notifications not displayed yet, by ID, oldest first
+    pending: VecDeque<(String, u64)>,
+    /// Displays over the threshold
+    slow: u64,
+    /// Plugin time of the last warning (ms)
+    last_warning_ms: Option<u64>,
+}
+
+impl LatencyTracker {
+    /// Record the arrival at Unix time `unix_ms` of a notification sent at `sent_ms`
+    pub fn arrived(&mut self, id: &str, sent_ms: u64, unix_ms: u64) {
+        self.arrival.record(unix_ms.saturating_sub(sent_ms));
+        self.pending.retain(|(pending, _)| pending != id);
+        if self.pending.len() >= MAX_PENDING_DISPLAYS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((id.to_string(), sent_ms));
+    }
+
+    /// Record the display at Unix time `unix_ms` of an arrived notification; a `threshold_ms` of
+    /// 0 disables warnings. Returns a warning to log when the display was late and no warning was
+    /// given in the last minute.
+    pub fn displayed(&mut self, id: &str, unix_ms: u64, threshold_ms: u64, now_ms: u64) -> Option<String> {
+        let position = self.pending.iter().position(|(pending, _)| pending == id)?;
+        let (_, sent_ms) = self.pending.remove(position)?;
+        let latency_ms = unix_ms.saturating_sub(sent_ms);
+        self.display.record(latency_ms);
+        if threshold_ms == 0 || latency_ms <= threshold_ms {
+            return None;
+        }
+        self.slow += 1;
+        if self.last_warning_ms.is_some_and(|last| now_ms < last + LATENCY_WARNING_INTERVAL_MS) {
+            return None;
+        }
+        self.last_warning_ms = Some(now_ms);
+        Some(format!("Notification {} displayed {} after it was sent (threshold {}ms, arrival {})",
+            id, format_latency(latency_ms), threshold_ms, self.arrival.summary()))
+    }
+
+    /// Stats line, e.g. `latency: arrival p50 40ms, p95 90ms; display p50 60ms, p95 2.4s; 1 slow`
+    pub fn line(&self) -> String {
+        format!("latency: arrival {}; display {}; {} slow", self.arrival.summary(), self.display.summary(), self.slow)
+    }
+}
+
+/// Latency in ms below ten seconds, seconds with one decimal above, e.g. `850ms`, `12.5s`
+fn format_latency(latency_ms: u64) -> String {
+    if latency_ms < 10_000 {
+        format!("{}ms", latency_ms)
+    } else {
+        format!("{:.1}s", latency_ms as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrival_and_display_percentiles() {
+        let mut tracker = LatencyTracker::default();
+        assert_eq!(tracker.line(), "latency: arrival -; display -; 0 slow");
+        for (index, id) in ["a", "b", "c", "d"].iter().enumerate() {
+            let sent = 1_000_000 + index as u64 * 10_000;
+            tracker.arrived(id, sent, sent + 40 + index as u64 * 20);
+            assert_eq!(tracker.displayed(id, sent + 100, 2_000, 0), None);
+        }
+        assert_eq!(tracker.line(), "latency: arrival p50 60ms, p95 100ms; display p50 100ms, p95 100ms; 0 slow");
+        // Unknown IDs (no sender timestamp) are not counted
+        assert_eq!(tracker.displayed("e", 2_000_000, 2_000, 0), None);
+    }
+
+    #[test]
+    fn test_late_displays_warn_once_a_minute() {
+        let mut tracker = LatencyTracker::default();
+        tracker.arrived("held", 0, 50);
+        tracker.arrived("queued", 0, 50);
+        let warning = tracker.displayed("held", 12_500, 2_000, 1_000).unwrap();
+        assert_eq!(warning, "Notification held displayed 12.5s after it was sent (threshold 2000ms, arrival p50 50ms, p95 50ms)");
+        assert_eq!(tracker.displayed("queued", 3_000, 2_000, 2_000), None);
+        assert!(tracker.line().ends_with("; 2 slow"));
+        assert_eq!(tracker.displayed("held", 12_500, 2_000, 1_000), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Legacy format module for Zellij Visual Notifications
+//!
+//! Shell hooks written for the first claude-notifications releases (message format v0) send
+//! payloads the current protocol doesn't describe: a JSON object with only a `message`, a plain
+//! string (`Claude is waiting for you...`), or a string with a type prefix (`ERROR:Build failed`).
+//! They are still turned into notifications - plain strings as attention requests, as the v0
+//! hooks only ever sent those, prefixed strings with the type named - so nothing is lost after an
+//! upgrade. Each use is counted for `stats`, and the first use of each format logs a deprecation
+//! warning naming its replacement. A single word is never read as a plain string: it is more
+//! likely a mistyped command, and keeps being reported as one.
+
+use std::collections::BTreeMap;
+
+use crate::notification::{Notification, NotificationType};
+
+/// Source of notifications from legacy payloads
+pub const LEGACY_SOURCE: &str = "claude-notifications-legacy";
+
+/// Longest type prefix looked for (characters)
+const MAX_PREFIX_CHARS: usize = 16;
+
+/// A legacy payload format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LegacyFormat {
+    /// `{"message": "..."}`
+    Json,
+    /// `Claude is waiting for you...`
+    Plain,
+    /// `ERROR:Build failed`
+    Prefixed,
+}
+
+impl LegacyFormat {
+    /// Format name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LegacyFormat::Json => "json",
+            LegacyFormat::Plain => "plain",
+            LegacyFormat::Prefixed => "prefixed",
+        }
+    }
+
+    /// What to send instead
+    pub fn replacement(&self) -> &'static str {
+        match self {
+            LegacyFormat::Json => r#"add a "type" to the JSON payload"#,
+            LegacyFormat::Plain => "send `attention|msg=...`",
+            LegacyFormat::Prefixed => "send `<type>|msg=...`",
+        }
+    }
+}
+
+/// Notification from a plain or type-prefixed string payload (JSON payloads are left to the
+/// event bridge)
+pub fn parse(payload: &str) -> Option<(LegacyFormat, Notification)> {
+    let payload = payload.trim();
+    if let Some((prefix, message)) = payload.split_once(':') {
+        let message = message.trim();
+        if let Some(notification_type) = prefix_type(prefix.trim()).filter(|_| !message.is_empty()) {
+            return Some((LegacyFormat::Prefixed, Notification::new(notification_type, message).from_source(LEGACY_SOURCE)));
+        }
+    }
+    if !payload.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((LegacyFormat::Plain, Notification::attention(payload).from_source(LEGACY_SOURCE)))
+}
+
+/// Type named by a prefix, e.g. `ERROR` or `done`
+fn prefix_type(prefix: &str) -> Option<NotificationType> {
+    if prefix.is_empty() || prefix.len() > MAX_PREFIX_CHARS || !prefix.chars().all(|c| c.is_ascii_alphabetic() || c == '_') {
+        return None;
+    }
+    // `from_str` reads any name it doesn't know as info
+    let notification_type = NotificationType::from_str(prefix);
+    let named = notification_type != NotificationType::Info
+        || matches!(prefix.to_lowercase().as_str(), "info" | "information");
+    named.then_some(notification_type)
+}
+
+/// Legacy payloads seen by this instance
+#[derive(Debug, Clone, Default)]
+pub struct LegacyUsage {
+    /// Payloads per format
+    counts: BTreeMap<LegacyFormat, u64>,
+    /// Deprecation warnings not yet logged
+    warnings: Vec<String>,
+}
+
+impl LegacyUsage {
+    /// Count a legacy payload; the first of each format queues a deprecation warning
+    pub fn record(&mut self, format: LegacyFormat) {
+        let count = self.counts.entry(format).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            self.warnings.push(format!(
+                "Deprecated {} notification payload (claude-notifications v0 format); {}",
+                format.name(),
+                format.replacement()
+            ));
+        }
+    }
+
+    /// Deprecation warnings to log, once each
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Stats line, e.g. `legacy payloads: plain 3, prefixed 1 (deprecated)`
+    pub fn line(&self) -> String {
+        if self.counts.is_empty() {
+            return "legacy payloads: none".to_string();
+        }
+        let counts: Vec<String> = self.counts.iter()
+            .map(|(format, count)| format!("{} {}", format.name(), count))
+            .collect();
+        format!("legacy payloads: {} (deprecated)", counts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_v0_payloads() {
+        let (format, notification) = parse("ERROR:Build failed: 3 tests").unwrap();
+        assert_eq!(format, LegacyFormat::Prefixed);
+        assert_eq!(notification.notification_type, NotificationType::Error);
+        assert_eq!(notification.message, "Build failed: 3 tests");
+        assert_eq!(parse("done: all green").unwrap().1.notification_type, NotificationType::Success);
+
+        let (format, notification) = parse("Claude is waiting for you...").unwrap();
+        assert_eq!(format, LegacyFormat::Plain);
+        assert_eq!(notification.notification_type, NotificationType::Attention);
+        assert_eq!(notification.source, LEGACY_SOURCE);
+
+        // Unknown prefixes are part of a plain message; single words are left to the commands
+        assert_eq!(parse("Note: deploy at 5").unwrap().0, LegacyFormat::Plain);
+        assert_eq!(parse("ERROR:").map(|(format, _)| format), None);
+        assert_eq!(parse("claer").map(|(format, _)| format), None);
+
+        let mut usage = LegacyUsage::default();
+        usage.record(LegacyFormat::Plain);
+        usage.record(LegacyFormat::Plain);
+        usage.record(LegacyFormat::Prefixed);
+        assert_eq!(usage.take_warnings().len(), 2);
+        assert!(usage.take_warnings().is_empty());
+        assert_eq!(usage.line(), "legacy payloads: plain 2, prefixed 1 (deprecated)");
+    }
+}
@@ -0,0 +1,86 @@
+//! Legend module for Zellij Visual Notifications
+//!
+//! The legend view (`?`) explains what the plugin draws and which keys it takes: the type icons
+//! and pattern suffixes, the entry markers, the keys, the channels and sources seen with their
+//! accent colors, and what is currently held back (DND, muted panes and sources). It is built
+//! from the live configuration and state on every render, so keys switched off in the
+//! configuration are left out and nothing is listed that the plugin does not do.
+
+use std::collections::BTreeSet;
+use crate::chord::{self, ChordAction};
+use crate::config::Config;
+use crate::surface::DisplaySurface;
+
+/// State the legend describes besides what the render view carries
+#[derive(Debug, Clone, Default)]
+pub struct LegendContext {
+    /// Keys and what they do, in display order
+    pub keys: Vec<(String, String)>,
+    /// Sources seen in the last hour
+    pub sources: Vec<String>,
+    /// Sources muted from the diagnostics view
+    pub muted_sources: BTreeSet<String>,
+    /// Panes whose visual updates are suppressed
+    pub muted_panes: Vec<u32>,
+}
+
+/// Keys the plugin pane takes under a configuration, with what they do
+pub fn key_bindings(config: &Config) -> Vec<(String, String)> {
+    let mut keys: Vec<(String, String)> = [
+        ("q", "queue"),
+        ("l", "history"),
+        ("d", "diagnostics"),
+        ("h", "health"),
+        ("c", "channels"),
+        ("g", "groups"),
+        ("j", "sessions"),
+        ("i", "notification detail"),
+        ("?", "legend"),
+        ("v", "mark read"),
+        ("x", "dismiss"),
+        ("p", "pin"),
+        ("e", "extend"),
+        ("m", "mute pane"),
+        ("f", "focus mode"),
+        ("a + type key", "acknowledge a type"),
+        ("Ctrl+n", "clear all"),
+    ]
+        .iter()
+        .map(|(key, action)| (key.to_string(), action.to_string()))
+        .collect();
+    keys.extend(DisplaySurface::ALL.iter()
+        .filter(|surface| surface.available(config))
+        .map(|surface| (surface.key().to_string(), format!("{} on/off", surface.name()))));
+    keys.push(("1-9 in the detail view".to_string(), "type the numbered quick reply".to_string()));
+    if config.entry_hotkeys {
+        keys.push(("1-9".to_string(), "acknowledge numbered entry".to_string()));
+    }
+    if !chord::is_off(&config.chord_leader) {
+        let actions: Vec<String> = ChordAction::ALL.iter().map(|action| action.key().to_string()).collect();
+        keys.push((format!("{} + {}", config.chord_leader.trim(), actions.join("/")), "act on focused pane".to_string()));
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_follow_configuration() {
+        let keys = key_bindings(&Config::default());
+        assert!(keys.contains(&("b".to_string(), "border on/off".to_string())));
+        assert!(!keys.iter().any(|(key, _)| key == "o"));
+        assert!(keys.iter().any(|(key, _)| key == "Ctrl y + a/r/p/e/m/c"));
+        assert!(!keys.iter().any(|(key, _)| key == "1-9"));
+
+        let config = Config { entry_hotkeys: true, chord_leader: "off".to_string(), ..Config::default() };
+        let keys = key_bindings(&config);
+        assert!(keys.iter().any(|(key, _)| key == "1-9"));
+        assert!(!keys.iter().any(|(_, action)| action == "act on focused pane"));
+
+        // The toasts key only exists with the experimental feature
+        let config = Config { experimental_features: vec!["toasts".to_string()], ..Config::default() };
+        assert!(key_bindings(&config).contains(&("o".to_string(), "toasts on/off".to_string())));
+    }
+}
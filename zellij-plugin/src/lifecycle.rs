@@ -0,0 +1,257 @@
+//! Lifecycle module for Zellij Visual Notifications
+//!
+//! Subsystems with state of their own (preferences, reminders, muted panes, usage counters, the
+//! last update check) implement `Subsystem` and are taken through their phases by the
+//! `LifecycleManager`: `init` on load, `suspend` and `resume` when the instance goes to standby
+//! and back, and `shutdown` before the plugin is closed, in reverse order. A failing subsystem is marked failed and left
+//! out of the later phases while the others carry on, so an unreadable state file costs that
+//! subsystem its persistence instead of breaking the plugin, and is never overwritten with
+//! defaults on the way out. The `lifecycle` command lists each subsystem's status; failures show
+//! in the health check.
+
+use std::io::ErrorKind;
+
+/// Phase a subsystem is taken through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Start up (plugin loaded)
+    Init,
+    /// Pause (instance on standby)
+    Suspend,
+    /// Pick up again (instance primary again)
+    Resume,
+    /// Tear down (plugin about to close)
+    Shutdown,
+}
+
+impl Phase {
+    /// Phase name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Init => "init",
+            Phase::Suspend => "suspend",
+            Phase::Resume => "resume",
+            Phase::Shutdown => "shutdown",
+        }
+    }
+}
+
+/// What a subsystem is told about the plugin when taken through a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifecycleContext {
+    /// Plugin time (ms)
+    pub now_ms: u64,
+    /// Whether state may be written (not in a read-only mirror)
+    pub persist: bool,
+}
+
+/// A part of the plugin with its own startup and teardown
+pub trait Subsystem {
+    /// Name shown by the `lifecycle` command and in the log
+    fn name(&self) -> &'static str;
+
+    /// Start up, e.g. restore persisted state
+    fn init(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Pause while another instance is primary
+    fn suspend(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Pick up again after a suspend
+    fn resume(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Tear down, e.g. persist state for the next instance
+    fn shutdown(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Where a subsystem is in its lifecycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubsystemStatus {
+    /// Not started, or shut down
+    Stopped,
+    /// Started
+    Running,
+    /// Paused
+    Suspended,
+    /// A phase failed; left out of the later phases
+    Failed {
+        /// Phase that failed
+        phase: Phase,
+        /// Why
+        error: String,
+    },
+}
+
+impl SubsystemStatus {
+    /// Status description, e.g. `failed in init: permission denied`
+    pub fn describe(&self) -> String {
+        match self {
+            SubsystemStatus::Stopped => "stopped".to_string(),
+            SubsystemStatus::Running => "running".to_string(),
+            SubsystemStatus::Suspended => "suspended".to_string(),
+            SubsystemStatus::Failed { phase, error } => format!("failed in {}: {}", phase.name(), error),
+        }
+    }
+}
+
+/// Drives subsystems through their phases and keeps their status
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleManager {
+    /// Status per subsystem, in init order
+    statuses: Vec<(&'static str, SubsystemStatus)>,
+}
+
+impl LifecycleManager {
+    /// Take subsystems (in init order) through a phase, returning the failures as
+    /// `(name, error)`. Shutdown runs in reverse order; a failed subsystem only runs init again.
+    pub fn run(
+        &mut self,
+        phase: Phase,
+        subsystems: &mut [&mut dyn Subsystem],
+        context: &LifecycleContext,
+    ) -> Vec<(&'static str, String)> {
+        let mut failures = Vec::new();
+        let order: Vec<usize> = match phase {
+            Phase::Shutdown => (0..subsystems.len()).rev().collect(),
+            _ => (0..subsystems.len()).collect(),
+        };
+        for index in order {
+            let subsystem = &mut *subsystems[index];
+            let name = subsystem.name();
+            let status = self.status(name);
+            let (result, next) = match (phase, &status) {
+                (Phase::Init, _) => (subsystem.init(context), SubsystemStatus::Running),
+                (Phase::Suspend, SubsystemStatus::Running) => (subsystem.suspend(context), SubsystemStatus::Suspended),
+                (Phase::Resume, SubsystemStatus::Suspended) => (subsystem.resume(context), SubsystemStatus::Running),
+                (Phase::Shutdown, SubsystemStatus::Running | SubsystemStatus::Suspended) => {
+                    (subsystem.shutdown(context), SubsystemStatus::Stopped)
+                }
+                _ => continue,
+            };
+            let next = match result {
+                Ok(()) => next,
+                Err(error) => {
+                    failures.push((name, error.clone()));
+                    SubsystemStatus::Failed { phase, error }
+                }
+            };
+            self.set_status(name, next);
+        }
+        failures
+    }
+
+    /// Status of a subsystem (stopped if never seen)
+    pub fn status(&self, name: &str) -> SubsystemStatus {
+        self.statuses.iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, status)| status.clone())
+            .unwrap_or(SubsystemStatus::Stopped)
+    }
+
+    /// Whether a subsystem failed (its state must not be relied on or written back)
+    pub fn is_failed(&self, name: &str) -> bool {
+        matches!(self.status(name), SubsystemStatus::Failed { .. })
+    }
+
+    /// Failed subsystems with their status description
+    pub fn failures(&self) -> Vec<(&'static str, String)> {
+        self.statuses.iter()
+            .filter(|(_, status)| matches!(status, SubsystemStatus::Failed { .. }))
+            .map(|(name, status)| (*name, status.describe()))
+            .collect()
+    }
+
+    /// Lines of the `lifecycle` command, e.g. `prefs  running`
+    pub fn lines(&self) -> Vec<String> {
+        let width = self.statuses.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        self.statuses.iter()
+            .map(|(name, status)| format!("{:<width$}  {}", name, status.describe(), width = width))
+            .collect()
+    }
+
+    fn set_status(&mut self, name: &'static str, status: SubsystemStatus) {
+        match self.statuses.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, known)) => *known = status,
+            None => self.statuses.push((name, status)),
+        }
+    }
+}
+
+/// Read a state file for `init`: None if there is none yet, an error if it can't be read
+pub fn read_state(path: &str) -> Result<Option<String>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", path, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Subsystem failing in the phases it is told to, recording the ones it went through
+    struct Probe {
+        name: &'static str,
+        fail_in: Vec<Phase>,
+        ran: Vec<Phase>,
+    }
+
+    impl Probe {
+        fn new(name: &'static str, fail_in: &[Phase]) -> Self {
+            Self { name, fail_in: fail_in.to_vec(), ran: Vec::new() }
+        }
+
+        fn step(&mut self, phase: Phase) -> Result<(), String> {
+            self.ran.push(phase);
+            if self.fail_in.contains(&phase) {
+                return Err("disk on fire".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    impl Subsystem for Probe {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+        fn init(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+            self.step(Phase::Init)
+        }
+        fn suspend(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+            self.step(Phase::Suspend)
+        }
+        fn shutdown(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+            self.step(Phase::Shutdown)
+        }
+    }
+
+    #[test]
+    fn test_failing_subsystem_is_isolated() {
+        let context = LifecycleContext { now_ms: 0, persist: true };
+        let mut manager = LifecycleManager::default();
+        let (mut prefs, mut metrics) = (Probe::new("prefs", &[Phase::Init]), Probe::new("metrics", &[]));
+
+        let failures = manager.run(Phase::Init, &mut [&mut prefs, &mut metrics], &context);
+        assert_eq!(failures, vec![("prefs", "disk on fire".to_string())]);
+        assert!(manager.is_failed("prefs"));
+        assert_eq!(manager.status("metrics"), SubsystemStatus::Running);
+
+        // The failed subsystem sits out the later phases; the others go on
+        manager.run(Phase::Suspend, &mut [&mut prefs, &mut metrics], &context);
+        assert_eq!(manager.status("metrics"), SubsystemStatus::Suspended);
+        assert!(manager.run(Phase::Shutdown, &mut [&mut prefs, &mut metrics], &context).is_empty());
+        assert_eq!(prefs.ran, vec![Phase::Init]);
+        assert_eq!(metrics.ran, vec![Phase::Init, Phase::Suspend, Phase::Shutdown]);
+        assert_eq!(manager.lines(), vec!["prefs    failed in init: disk on fire", "metrics  stopped"]);
+        assert_eq!(manager.failures(), vec![("prefs", "failed in init: disk on fire".to_string())]);
+        assert_eq!(read_state("/nonexistent/visual-notifications-state"), Ok(None));
+    }
+}
@@ -14,27 +14,177 @@
 
 mod config;
 mod state;
+mod actions;
+mod artifacts;
+mod banner;
+mod aggregator;
 mod animation;
+mod attention;
+mod attention_queue;
+mod audit;
+mod autofocus;
+mod automation;
+mod bus;
+mod channels;
+mod charset;
+mod chord;
+mod checks;
+mod claude_panes;
+mod clock;
 mod colors;
+mod commands;
+mod confirm;
+mod correlation;
+mod desktop_daemon;
+mod diagnostics;
+mod election;
+mod delivery;
+mod entry_cache;
 mod notification;
+mod passive;
+mod payload;
+mod profile;
+mod progress;
 mod event_bridge;
+mod exitcode;
+mod features;
+mod health;
+mod history;
+mod host;
+mod icons;
+mod id_api;
+mod ingest;
+mod idle;
+mod latency;
+mod legacy;
+mod legend;
+mod lifecycle;
+mod markup;
+mod metrics;
+mod migration;
+mod mute;
+mod prefs;
+mod query;
 mod queue;
+mod queue_diff;
+mod quick_reply;
+mod pager;
+mod pending_panes;
+mod power;
+mod raw_ansi;
+mod receipts;
+mod remap;
+mod recent;
+mod reminders;
+mod replay;
+mod rules;
+mod screen;
+mod sequence;
+mod snapshot;
 mod renderer;
+mod sinks;
+mod slo;
+mod sound;
+mod sources;
+mod sparkline;
+mod stress;
+mod surface;
+mod table;
+mod text_diff;
+mod text_log;
+mod tab_badge;
+mod template;
+mod timeline;
+mod suppression;
+mod title;
+mod toast;
+mod tutorial;
+mod unread;
+mod update_check;
+mod workspace;
+mod world;
+mod wrap;
 
 #[cfg(test)]
 mod tests;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Instant;
 use zellij_tile::prelude::*;
 
-use crate::config::{Config, ConfigManager};
-use crate::state::{PluginState, VisualState};
-use crate::animation::AnimationEngine;
+use crate::config::{Config, ConfigManager, FocusedPaneSuppression, HiddenTabAnimation, HistoryCompaction, MissingPane, TitleMirror};
+use crate::state::{
+    InteractionMode, PluginState, StateManager, StateTransition, TabLocality, VisualNotificationState, VisualState,
+};
+use crate::actions::Actions;
+use crate::artifacts::{Artifact, ArtifactRegistry};
+use crate::banner::STARTUP_BANNER_MS;
+use crate::aggregator::{self, SessionAggregate, FORWARD_CONTEXT_KEY};
+use crate::animation::{AnimationEngine, ClearCascade, Frame};
+use crate::audit::{AuditEvent, AuditTrail};
+use crate::bus::{DomainEvent, EventBus, MAX_EVENTS_PER_DISPATCH};
+use crate::channels::ChannelList;
+use crate::charset::{AsciiMode, CharsetDetector};
+use crate::chord::{ChordAction, CHORD_TIMEOUT_MS};
+use crate::autofocus::AutoFocus;
+use crate::automation::{AutomationScheduler, AutomationTrigger, LayoutAction};
+use crate::checks::{CheckScheduler, CHECK_CONTEXT_KEY};
+use crate::profile::{FramePhase, FrameProfiler};
+use crate::clock::{Clock, HostClock, HostIdGenerator};
 use crate::colors::ColorManager;
-use crate::notification::Notification;
-use crate::event_bridge::EventBridge;
-use crate::queue::NotificationQueue;
-use crate::renderer::Renderer;
+use crate::commands::{ControlCommand, SimulatedEvent};
+use crate::confirm::{ConfirmAnswer, ConfirmPrompts};
+use crate::diagnostics::{LogLevel, RecoveryTracker};
+use crate::delivery::{status_result, DeliveryQueue, DELIVERY_CONTEXT_KEY};
+use crate::notification::{ExpiryAction, Notification, NotificationType, Priority};
+use crate::passive::PassiveDetector;
+use crate::payload::PayloadTooLarge;
+use crate::progress::ProgressTracker;
+use crate::event_bridge::{BridgeMessage, Capabilities, EventBridge, ProtocolLimits};
+use crate::health::{HealthInputs, HealthReport};
+use crate::history::{HistoryEntry, NotificationHistory};
+use crate::id_api::{IdReply, IdRequest, IdRequestKind, IdStatus, REPLY_PIPE};
+use crate::election::{Election, Role, ELECTION_PIPE};
+use crate::idle::IdleTracker;
+use crate::ingest::{FileTail, INGEST_STATE_PATH};
+use crate::latency::LatencyTracker;
+use crate::legend::{self, LegendContext};
+use crate::lifecycle::{LifecycleContext, LifecycleManager, Phase, Subsystem};
+use crate::metrics::{Metrics, METRICS_STATE_PATH};
+use crate::migration::StateExport;
+use crate::mute::{MuteList, MUTE_STATE_PATH};
+use crate::prefs::{Preferences, PREFS_STATE_PATH};
+use crate::pager::Pager;
+use crate::pending_panes::PendingPanes;
+use crate::power::{PowerSource, ScanThrottle};
+use crate::query::{arrange, format_age, Grouping, SortMode};
+use crate::queue::{Admission, NotificationQueue};
+use crate::queue_diff::QueueDiff;
+use crate::receipts::{DeliveryStatus, ReceiptTracker};
+use crate::recent::RecentAcks;
+use crate::reminders::{ReminderScheduler, REMINDER_STATE_PATH};
+use crate::replay::ReplayTarget;
+use crate::renderer::{format_duration_ms, RenderView, Renderer, BIG_MODE_MIN_ROWS, FOCUS_RING_MIN_ROWS};
+use crate::screen::ScreenBuffer;
+use crate::sequence::Sequencer;
+use crate::snapshot::SNAPSHOT_CONTEXT_KEY;
+use crate::sinks::{SinkEffect, SinkPipeline};
+use crate::slo::SloTracker;
+use crate::sound::{SoundTheme, SOUND_CONTEXT_KEY};
+use crate::desktop_daemon::{DesktopOutcome, DAEMON_CONTEXT_KEY, DAEMON_OP_CONTEXT_KEY};
+use crate::sources::{NoisySource, SourceStats};
+use crate::sparkline::RateHistory;
+use crate::stress::{StressRun, STRESS_SOURCE};
+use crate::surface::{DisplaySurface, Surface, SurfaceSelector, SurfaceToggles};
+use crate::tab_badge::TabBadgeStyle;
+use crate::suppression::{FocusDigest, SuppressionKind, SuppressionManager};
+use crate::template::TemplateContext;
+use crate::timeline::SessionTimeline;
+use crate::toast::{ToastAction, ToastManager, ToastSettings, TOAST_CONTEXT_KEY};
+use crate::tutorial::TUTORIAL_STATE_PATH;
+use crate::unread::UnreadCountFile;
+use crate::update_check::{Release, UpdateChecker, UPDATE_CONTEXT_KEY, UPDATE_STATE_PATH};
+use crate::world::World;
 
 /// Main plugin state structure
 #[derive(Default)]
@@ -45,12 +195,18 @@ pub struct State {
     config_manager: ConfigManager,
     /// Current visual state per pane
     pane_states: BTreeMap<u32, VisualState>,
+    /// State transition history (bounded, for debugging)
+    state_manager: StateManager,
     /// Animation engine for visual effects
     animation_engine: AnimationEngine,
+    /// Entries fading out after clear-all
+    clear_cascade: Option<ClearCascade>,
     /// Color management system
     color_manager: ColorManager,
     /// Event bridge for claude-notifications IPC
     event_bridge: EventBridge,
+    /// Internal bus of domain events awaiting the reducer
+    bus: EventBus,
     /// Notification queue with priority and TTL
     notification_queue: NotificationQueue,
     /// Renderer for visual output
@@ -61,36 +217,205 @@ pub struct State {
     tick_count: u64,
     /// Last update timestamp
     last_update_ms: u64,
+    /// Monotonic plugin time driven by host timer events
+    clock: HostClock,
+    /// Session-wide user activity tracker
+    idle_tracker: IdleTracker,
+    /// Attention notifications deferred while the user is busy
+    deferred_notifications: Vec<Notification>,
+    /// Received notifications, for the `list` command
+    history: NotificationHistory,
+    /// Periodic reminders, re-raised on the timer path until cancelled
+    reminders: ReminderScheduler,
+    /// Schedule of the configured host checks
+    checks: CheckScheduler,
+    /// Cooldowns of the configured `on_notify` automations
+    automations: AutomationScheduler,
+    /// Pending auto-focus countdown
+    auto_focus: AutoFocus,
+    /// Yes/no questions from senders, the first one shown
+    confirms: ConfirmPrompts,
+    /// Notifications forwarded from other sessions (aggregator instance)
+    aggregate: SessionAggregate,
+    /// Session and lifetime usage counters
+    metrics: Metrics,
+    /// Rolling per-source counts, for noisy-source suggestions
+    source_stats: SourceStats,
+    /// Rolling per-type counts, for the rate sparklines
+    rate_history: RateHistory,
+    /// Tick, update and render timings
+    frames: FrameProfiler,
+    /// Time from the sender's timestamp to arrival and display
+    latency: LatencyTracker,
+    /// Hints for drawing with the ASCII fallback set
+    charset: CharsetDetector,
+    /// Lifecycle of Claude Code sessions, for the `timeline` command
+    timeline: SessionTimeline,
+    /// Reordering of numbered notifications per source
+    sequencer: Sequencer,
+    /// Notifications waiting for their panes to appear (`missing_pane hold`)
+    pending_panes: PendingPanes,
+    /// Read position in `ingest_file`, once it is configured
+    ingest: Option<FileTail>,
+    /// Role among the instances listening on the same pipes
+    election: Election,
+    /// Spacing of the periodic scans (spaced out in low power mode)
+    scans: ScanThrottle,
+    /// Whether low power mode was switched on by the `power battery` signal
+    low_power_from_battery: bool,
+    /// Messages acknowledged per pane shortly before; repeats are dimmed
+    recent_acks: RecentAcks,
+    /// Notifications whose sender asked for delivery receipts
+    receipts: ReceiptTracker,
+    /// Progress trackers by pane, fed by percent updates
+    progress: BTreeMap<u32, ProgressTracker>,
+    /// Pipeline decisions per notification, for the `explain` command
+    audit: AuditTrail,
+    /// Notification channels with visibility toggles and counts
+    channels: ChannelList,
+    /// Ordered notification sinks
+    sinks: SinkPipeline,
+    /// Outbound webhook requests with retry state
+    webhook_deliveries: DeliveryQueue,
+    /// Floating toast panes opened by the toast sink
+    toasts: ToastManager,
+    /// Chooses signaling surfaces for collapsed/stacked panes
+    surface_selector: SurfaceSelector,
+    /// Notification currently displayed per pane
+    displayed: BTreeMap<u32, Notification>,
+    /// Displayed notifications already re-delivered out of terminal for lack of acknowledgement
+    ack_escalated: BTreeSet<String>,
+    /// Response-time SLO breaches and statistics
+    slo: SloTracker,
+    /// Panes whose visual updates are suppressed
+    muted_panes: MuteList,
+    /// DND/snooze state and notifications held while it is active
+    suppression: SuppressionManager,
+    /// Pane that most recently received a visual notification
+    last_notified_pane: Option<u32>,
+    /// Raw terminal output (bell, escapes) to emit on the next render
+    pending_output: Vec<String>,
+    /// Latest screen reader announcement
+    last_announcement: Option<String>,
+    /// Title last applied to the plugin's own pane
+    own_pane_title: Option<String>,
+    /// Badge style for tab names
+    tab_badge_style: TabBadgeStyle,
+    /// Marks applied outside the plugin pane (title icons, tab badges, window title, unread count)
+    artifacts: ArtifactRegistry,
+    /// Unread count mirrored to a host file
+    unread_count_file: UnreadCountFile,
     /// Error state for fallback mode
     error_state: Option<String>,
+    /// Automatic recovery attempts out of the error state
+    recovery: RecoveryTracker,
     /// Current pane info
     own_pane_id: Option<u32>,
     /// Mode info
     mode_info: ModeInfo,
-    /// Tab info for status bar
-    tab_info: Option<LocalTabInfo>,
-    /// All pane manifests
-    pane_manifest: BTreeMap<u32, LocalPaneInfo>,
+    /// Session layout (panes, tabs, focus)
+    world: World,
+    /// Last pane manifest from the host (base for simulated pane events)
+    pane_manifest: PaneManifest,
+    /// Whether the queue inspection overlay is shown
+    show_queue_overlay: bool,
+    /// Changes to the queue shown in the queue inspector
+    queue_diff: QueueDiff,
+    /// Whether the diagnostics view is shown
+    show_diagnostics: bool,
+    /// Whether the health checklist is shown
+    show_health: bool,
+    /// Whether the channel list is shown
+    show_channels: bool,
+    /// Whether the correlation group view is shown
+    show_groups: bool,
+    /// Whether the history view is shown
+    show_history: bool,
+    /// Whether the sessions view is shown
+    show_sessions: bool,
+    /// Whether the notification detail view is shown
+    show_detail: bool,
+    /// Page of a long message in the detail view
+    detail_pager: Pager,
+    /// Whether the legend view is shown
+    show_legend: bool,
+    /// Whether the first-run tutorial is shown
+    show_tutorial: bool,
+    /// Plugin time until which the startup banner is shown
+    startup_banner_until: Option<u64>,
+    /// Gate for the calls made to Zellij
+    actions: Actions,
+    /// Pane title watcher for passive completion detection
+    passive: PassiveDetector,
+    /// Order of the history view
+    history_sort: SortMode,
+    /// Grouping of the history view
+    history_grouping: Grouping,
+    /// Display surfaces switched off at runtime
+    surface_toggles: SurfaceToggles,
+    /// Runtime-adjusted preferences as last persisted
+    prefs: Preferences,
+    /// Daily check for a newer release (`update_check`)
+    updates: UpdateChecker,
+    /// Lifecycle status of the subsystems with state of their own
+    lifecycle: LifecycleManager,
+    /// Latest throughput stress test (kept for the stats once finished)
+    stress: Option<StressRun>,
+    /// Pipe messages received (for the health check)
+    pipe_messages: u64,
+    /// Broadcast messages ignored on pipe names this instance does not listen on
+    ignored_pipe_messages: u64,
+    /// Unexpected pipe names already logged
+    unexpected_pipes: BTreeSet<String>,
+    /// Size of the last render as (columns, rows)
+    render_size: Option<(usize, usize)>,
+    /// Back buffer multi-row views are composed in, reused between renders
+    screen: ScreenBuffer,
+    /// Transient status line message and the time it disappears (ms)
+    status_message: Option<(String, u64)>,
+    /// Transient key handling mode (e.g. pending clear-all confirmation)
+    interaction_mode: InteractionMode,
 }
 
-/// Local tab information for status bar rendering (distinct from zellij_tile::TabInfo)
-#[derive(Default, Clone)]
-struct LocalTabInfo {
-    position: usize,
-    name: String,
-    active: bool,
-    panes_count: usize,
-}
+/// Permissions the plugin needs for full operation
+const PLUGIN_PERMISSIONS: [PermissionType; 5] = [
+    PermissionType::ReadApplicationState,
+    PermissionType::ChangeApplicationState,
+    PermissionType::RunCommands,
+    PermissionType::MessageAndLaunchOtherPlugins,
+    PermissionType::WriteToStdin,
+];
 
-/// Local pane information (distinct from zellij_tile types)
-#[derive(Default, Clone)]
-struct LocalPaneInfo {
-    id: u32,
-    is_focused: bool,
-    title: String,
-    is_plugin: bool,
+/// Permissions a configuration needs: the full set, plus WebAccess with a webhook sink
+fn plugin_permissions(config: &Config) -> Vec<PermissionType> {
+    let mut permissions = PLUGIN_PERMISSIONS.to_vec();
+    if config.webhook_url.is_some() && config.sinks.iter().any(|sink| sink.eq_ignore_ascii_case("webhook")) {
+        permissions.push(PermissionType::WebAccess);
+    }
+    permissions
 }
 
+/// How long the clear-all confirmation prompt stays open (ms)
+const CLEAR_CONFIRM_TIMEOUT_MS: u64 = 5000;
+
+/// How long the acknowledge-by-type chord waits for its type key (ms)
+const ACK_CHORD_TIMEOUT_MS: u64 = 2000;
+
+/// How long an automation's confirmation prompt stays open (ms)
+const AUTOMATION_CONFIRM_TIMEOUT_MS: u64 = 15_000;
+
+/// Context key marking command panes opened by automations (value: automation name)
+const AUTOMATION_CONTEXT_KEY: &str = "automation";
+
+/// How long a confirmation message stays in the status line (ms)
+const STATUS_MESSAGE_MS: u64 = 3000;
+
+/// How long the digest of a focus period stays in the status line (ms)
+const FOCUS_DIGEST_MS: u64 = 30_000;
+
+/// Time after load before the startup health check runs (s, time for permissions)
+const STARTUP_HEALTH_CHECK_SECS: u64 = 1;
+
 register_plugin!(State);
 
 // Export WASM entry point that Zellij expects
@@ -99,13 +424,6 @@ pub extern "C" fn _start() {}
 
 impl ZellijPlugin for State {
     fn load(&mut self, configuration: BTreeMap<String, String>) {
-        // Request necessary permissions
-        request_permission(&[
-            PermissionType::ReadApplicationState,
-            PermissionType::ChangeApplicationState,
-            PermissionType::RunCommands,
-        ]);
-
         // Subscribe to events
         subscribe(&[
             EventType::ModeUpdate,
@@ -115,48 +433,140 @@ impl ZellijPlugin for State {
             EventType::Key,
             EventType::PermissionRequestResult,
             EventType::CustomMessage,
+            EventType::WebRequestResult,
+            EventType::CommandPaneOpened,
+            EventType::RunCommandResult,
+            EventType::BeforeClose,
         ]);
 
-        // Initialize configuration from plugin configuration map
-        self.config = Config::from_plugin_config(&configuration);
-        self.config_manager = ConfigManager::new();
+        // Initialize configuration from the configuration file and plugin configuration map
+        self.config_manager = ConfigManager::new().with_plugin_config(&configuration);
+
+        // Restore what the previous plugin instance persisted (preferences, reminders, muted
+        // panes, usage counters); a subsystem that fails to start runs without its state
+        self.run_lifecycle(Phase::Init);
+
+        // Preferences from the previous plugin instance go on top of the configuration
+        for (key, spec) in self.prefs.mute_rules() {
+            self.config_manager.set_override(&key, &spec);
+        }
+        for surface in self.prefs.surfaces_off() {
+            self.surface_toggles.set(surface, Some(false));
+        }
+        self.config = self.config_manager.reload().unwrap_or_else(|e| {
+            log_warn(&format!("Using plugin configuration only: {}", e));
+            self.config_manager.reload_without_files()
+        });
+        self.warn_unavailable_features();
+        self.warn_experimental_features();
+        self.warn_config_drift();
+
+        // Request the permissions the configuration needs
+        request_permission(&plugin_permissions(&self.config));
+
+        self.charset = CharsetDetector::new(charset::env_hint(|name| std::env::var(name).ok()));
+        if !self.config.pipes.is_empty() {
+            log_info(&format!("Listening on pipes: {}{}",
+                self.config.pipes.join(", "),
+                if self.config.strict_pipes { " (strict)" } else { "" }));
+        }
+
+        // Initialize bounded transition history
+        self.state_manager = StateManager::new().with_max_history(self.config.transition_history_max);
+        self.history = NotificationHistory::new(self.config.history_max);
+        self.audit = AuditTrail::new(self.config.history_max);
+        self.channels = ChannelList::from_config(&self.config);
+        self.sequencer = Sequencer::new(self.config.sequence_hold_ms);
 
         // Initialize color manager with theme
-        self.color_manager = ColorManager::new(&self.config.theme);
+        self.rebuild_color_manager();
 
         // Initialize animation engine
-        self.animation_engine = AnimationEngine::new(&self.config.animation);
+        self.animation_engine = AnimationEngine::new(&self.config.effective_animation());
 
         // Initialize notification queue
-        self.notification_queue = NotificationQueue::new(
-            self.config.queue_max_size,
-            self.config.notification_timeout_ms,
-        );
+        self.notification_queue = NotificationQueue::from_config(&self.config);
 
         // Initialize renderer
-        self.renderer = Renderer::new(&self.config);
+        self.rebuild_renderer();
+        self.sync_tab_locality();
+
+        // Initialize notification sinks
+        self.actions.configure(self.config.actions_dry_run, self.config.action_rate_limit);
+        self.actions.set_read_only(self.render_only());
+        self.passive = self.passive_detector();
+        self.sinks = SinkPipeline::from_config(&self.config);
+        self.surface_selector = SurfaceSelector::new(self.config.collapsed_title_badge);
+        self.webhook_deliveries = DeliveryQueue::new(
+            self.config.webhook_max_attempts,
+            self.config.webhook_retry_base_ms,
+        );
+        self.toasts = ToastManager::new(ToastSettings::from_config(&self.config));
+        self.tab_badge_style = TabBadgeStyle::from_config(&self.config);
+
+        // Initialize idle detection
+        self.idle_tracker = IdleTracker::new(self.config.idle_threshold_ms, self.config.typing_pause_ms);
+        self.idle_tracker.set_enabled(self.config.idle_detection);
 
         // Initialize event bridge for IPC
         self.event_bridge = EventBridge::new();
+        self.event_bridge.set_remaps(&self.config.remaps);
+
+        // Nothing persisted yet: introduce the plugin (a mirror could not be told to close it)
+        self.show_tutorial = !self.config.mirror && tutorial::is_first_run(&[
+            TUTORIAL_STATE_PATH, METRICS_STATE_PATH, MUTE_STATE_PATH, REMINDER_STATE_PATH, PREFS_STATE_PATH,
+        ]);
+
+        // Say which plugin, protocol and configuration are running (a mirror could not dismiss it)
+        if self.config.startup_banner && !self.config.mirror {
+            self.startup_banner_until = Some(self.clock.now_ms() + STARTUP_BANNER_MS);
+        }
+
+        // Restore muted panes from the previous plugin instance
+        let muted: Vec<u32> = self.muted_panes.iter().copied().collect();
+        for pane_id in muted {
+            self.set_pane_muted(pane_id, true);
+        }
+
+        // Restore DND and pinned notifications from the previous plugin instance
+        self.restore_prefs();
+
+        // Remember our own pane so its title can carry the summary
+        let plugin_ids = get_plugin_ids();
+        self.own_pane_id = Some(plugin_ids.plugin_id);
+        self.sync_election_membership();
+
+        // Seed notification IDs from host-provided values
+        clock::set_id_generator(Box::new(HostIdGenerator::new(
+            plugin_ids.zellij_pid.wrapping_mul(31).wrapping_add(plugin_ids.plugin_id),
+        )));
 
         // Set plugin state to initialized
         self.plugin_state = PluginState::Initialized;
 
-        // Start timer for animations (60fps = ~16ms, we use 50ms for efficiency)
-        set_timeout(0.05);
+        // Start timer for animations (50ms by default, see animation_tick_ms)
+        set_timeout(self.tick_interval_secs());
 
         // Log initialization
         log_info("Zellij Visual Notifications plugin loaded");
     }
 
     fn update(&mut self, event: Event) -> bool {
+        let started = Instant::now();
+        self.sync_unix_time();
+        let phase = if matches!(event, Event::Timer(_)) { FramePhase::Tick } else { FramePhase::Update };
         let mut should_render = false;
 
+        // Translate host events into domain events (plus view bookkeeping)
         match event {
-            Event::Timer(_elapsed) => {
-                should_render = self.handle_timer();
+            Event::Timer(elapsed) => {
+                self.bus.emit(DomainEvent::TickElapsed(elapsed));
             }
             Event::ModeUpdate(mode_info) => {
+                self.bus.emit(DomainEvent::UserActivity);
+                if self.charset.set_simplified_ui(!mode_info.capabilities.arrow_fonts) {
+                    self.rebuild_renderer();
+                }
                 self.mode_info = mode_info;
                 should_render = true;
             }
@@ -164,16 +574,21 @@ impl ZellijPlugin for State {
                 should_render = self.handle_tab_update(tabs);
             }
             Event::PaneUpdate(pane_manifest) => {
+                // Not user activity: running programs change pane titles all the time
                 should_render = self.handle_pane_update(pane_manifest);
             }
             Event::Key(key) => {
+                self.bus.emit(DomainEvent::UserActivity);
                 // Check for Ctrl+N to clear notifications
                 // In zellij-tile 0.42+, key handling uses KeyWithModifier
-                if let KeyWithModifier { bare_key: BareKey::Char('n'), key_modifiers } = key {
-                    if key_modifiers.contains(&KeyModifier::Ctrl) {
-                        self.clear_all_notifications();
-                        should_render = true;
-                    }
+                if self.config.mirror {
+                    // A mirror takes no keys, so nothing can be changed from it by accident
+                } else if key.bare_key == BareKey::Char('n') && key.key_modifiers.contains(&KeyModifier::Ctrl)
+                    && self.auto_focus.pending().is_none() && !self.is_chord_leader(&key)
+                {
+                    should_render = self.request_clear_all();
+                } else {
+                    should_render = self.handle_key(&key);
                 }
             }
             Event::CustomMessage(message, payload) => {
@@ -181,240 +596,3928 @@ impl ZellijPlugin for State {
             }
             Event::PermissionRequestResult(result) => {
                 self.handle_permission_result(result);
+                // The startup banner shows the answer
+                should_render = self.startup_banner_until.is_some();
+            }
+            Event::WebRequestResult(status, _headers, body, context) => {
+                self.handle_web_request_result(status, &body, &context);
+            }
+            Event::RunCommandResult(exit_code, stdout, stderr, context) => {
+                self.handle_run_command_result(exit_code, &stdout, &stderr, &context);
+            }
+            Event::CommandPaneOpened(pane_id, context) => {
+                if let Some(id) = context.get(TOAST_CONTEXT_KEY).and_then(|id| id.parse().ok()) {
+                    let actions = self.toasts.opened(id, pane_id).into_iter().collect();
+                    self.apply_toast_actions(actions);
+                }
+            }
+            Event::BeforeClose => {
+                // Persist what the next plugin instance restores
+                self.run_lifecycle(Phase::Shutdown);
+                log_info("Zellij Visual Notifications plugin closing");
             }
             _ => {}
         }
 
-        // Process any queued notifications
-        if self.process_notification_queue() {
-            should_render = true;
-        }
+        should_render |= self.dispatch();
+        should_render |= self.sync_derived_artifacts();
+        should_render |= self.refresh_queue_diff();
 
+        self.record_frame(phase, started, !should_render);
         should_render
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
-        // Render the status bar widget
-        self.renderer.render_status_bar(
-            rows,
-            cols,
-            &self.pane_states,
-            &self.notification_queue,
-            &self.color_manager,
-            &self.animation_engine,
-            self.tick_count,
-        );
+        let started = Instant::now();
+        for output in self.pending_output.drain(..) {
+            print!("{}", output);
+        }
+        self.render_size = Some((cols, rows));
+
+        let mut screen = std::mem::take(&mut self.screen);
+        self.render_frame(&mut screen, rows, cols);
+        self.screen = screen;
+        self.record_frame(FramePhase::Render, started, false);
     }
 
     fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        if pipe_message.name == ELECTION_PIPE {
+            return self.handle_election_message(pipe_message);
+        }
+        if !self.accepts_pipe(&pipe_message) {
+            return false;
+        }
+
         // Handle piped messages from claude-notifications
-        self.handle_pipe_message(pipe_message)
+        let started = Instant::now();
+        self.sync_unix_time();
+        let mut should_render = if self.config.mirror {
+            self.handle_mirror_pipe_message(pipe_message)
+        } else {
+            self.handle_pipe_message(pipe_message)
+        };
+        should_render |= self.dispatch();
+        should_render |= self.sync_derived_artifacts();
+        should_render |= self.refresh_queue_diff();
+        self.record_frame(FramePhase::Update, started, false);
+        should_render
     }
 }
 
 impl State {
-    /// Handle timer events for animations
-    fn handle_timer(&mut self) -> bool {
-        self.tick_count = self.tick_count.wrapping_add(1);
+    /// Sync the plugin clock with the host's Unix time, for the delivery latency
+    fn sync_unix_time(&mut self) {
+        if let Some(unix_ms) = clock::system_unix_ms() {
+            self.clock.sync_unix(unix_ms);
+        }
+    }
 
-        // Update animation states
-        let mut needs_render = false;
+    /// Record how long a frame took, logging slow frames
+    fn record_frame(&mut self, phase: FramePhase, started: Instant, idle: bool) {
+        let warning = self.frames.record(phase, started.elapsed(), idle,
+            self.config.frame_time_warn_ms, self.clock.now_ms());
+        if let Some(warning) = warning {
+            log_warn(&warning);
+        }
+    }
 
-        for (_pane_id, visual_state) in self.pane_states.iter_mut() {
-            if visual_state.is_animating {
-                self.animation_engine.update_animation(visual_state, self.tick_count);
-                needs_render = true;
-            }
+    /// Render the current view; multi-row views are composed in the screen buffer
+    fn render_frame(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize) {
+        let view = self.view();
+
+        if let InteractionMode::ConfirmClearAll { count, .. } = self.interaction_mode {
+            self.renderer.render_clear_confirm(cols, count, &view);
+            return;
         }
 
-        // Check for expired notifications
-        self.notification_queue.cleanup_expired();
+        if let InteractionMode::AckTypeChord { .. } = self.interaction_mode {
+            self.renderer.render_ack_chord_prompt(cols, &view);
+            return;
+        }
 
-        // Restart timer for next tick
-        set_timeout(0.05);
+        if let InteractionMode::Chord { .. } = self.interaction_mode {
+            self.renderer.render_chord_hint(cols, &chord::hint(&self.config.chord_leader, self.chord_target()), &view);
+            return;
+        }
 
-        needs_render
+        if let InteractionMode::ConfirmAutomation { ref trigger, .. } = self.interaction_mode {
+            self.renderer.render_automation_confirm(cols, &trigger.prompt(), &view);
+            return;
+        }
+
+        if let Some(prompt) = self.confirms.prompt(self.clock.now_ms()) {
+            self.renderer.render_sender_confirm(cols, &prompt, &view);
+            return;
+        }
+
+        if let Some(pending) = self.auto_focus.pending() {
+            self.renderer.render_auto_focus_countdown(cols, &pending.countdown(self.clock.now_ms()), &view);
+            return;
+        }
+
+        if self.show_tutorial {
+            let lines = tutorial::lines(self.mode_info.session_name.as_deref(), &self.config.chord_leader);
+            self.renderer.render_tutorial(screen, rows, cols, &lines);
+            return;
+        }
+
+        if self.startup_banner_until.is_some() {
+            self.renderer.render_banner(screen, rows, cols, &banner::banner_lines(&self.config_profile(), &self.permissions_line()));
+            return;
+        }
+
+        if self.show_diagnostics {
+            let noisy = self.noisy_source().map(|noisy| noisy.suggestion());
+            let update = self.updates.line();
+            self.renderer.render_diagnostics(screen, rows, cols, &view, noisy.as_deref(), update.as_deref(), &diagnostics::recent_logs(rows));
+            return;
+        }
+
+        if self.show_health {
+            self.renderer.render_health(screen, rows, cols, &self.health_report(), &view);
+            return;
+        }
+
+        if self.show_channels {
+            self.renderer.render_channels(screen, rows, cols, &view);
+            return;
+        }
+
+        if self.show_groups {
+            self.renderer.render_groups(screen, rows, cols, &view);
+            return;
+        }
+
+        if self.show_history {
+            let now = self.clock.now_ms();
+            let entries: Vec<&HistoryEntry> = self.history.newest_first().collect();
+            let groups = arrange(&entries, self.history_sort, self.history_grouping, now);
+            let title = format!("History - s sort: {}, g group: {}, l to close",
+                self.history_sort.name(), self.history_grouping.name());
+            self.renderer.render_history(screen, rows, cols, &title, &groups, now);
+            return;
+        }
+
+        if self.show_sessions {
+            self.renderer.render_sessions(screen, rows, cols, &view);
+            return;
+        }
+
+        if self.show_detail {
+            let notification = self.chord_target().and_then(|pane_id| self.displayed.get(&pane_id));
+            self.renderer.render_detail(screen, rows, cols, notification, &self.detail_pager, self.config.ttl_extend_ms, &view);
+            return;
+        }
+
+        if self.show_legend {
+            let context = LegendContext {
+                keys: legend::key_bindings(&self.config),
+                sources: self.source_stats.sources(self.clock.now_ms()),
+                muted_sources: self.prefs.muted_sources.clone(),
+                muted_panes: self.pane_states.iter().filter(|(_, state)| state.muted).map(|(pane_id, _)| *pane_id).collect(),
+            };
+            self.renderer.render_legend(screen, rows, cols, &context, &view);
+            return;
+        }
+
+        if self.show_queue_overlay {
+            self.renderer.render_queue_overlay(screen, rows, cols, &view);
+            return;
+        }
+
+        if self.config.text_log_enabled() {
+            let now = self.clock.now_ms();
+            let mut entries: Vec<(&HistoryEntry, Option<u64>)> = self.history.newest_first()
+                .map(|entry| (entry, self.clock.unix_ms().map(|unix_ms| unix_ms.saturating_sub(now.saturating_sub(entry.received_at_ms)))))
+                .collect();
+            entries.reverse();
+            self.renderer.render_text_log(screen, rows, cols, &text_log::lines(&entries, rows, cols));
+            return;
+        }
+
+        if let Some(cascade) = view.cascade {
+            self.renderer.render_clear_cascade(cols, cascade, &view);
+            return;
+        }
+
+        if self.config.big_mode && rows >= BIG_MODE_MIN_ROWS {
+            self.renderer.render_big_status(screen, rows, cols, &view);
+            return;
+        }
+
+        // Critical notifications frame the pane, so they stand out even without other cues
+        if self.renderer.render_focus_ring(screen, rows, cols, &view, self.last_announcement.as_deref()) {
+            return;
+        }
+
+        // Render the status bar widget; the wide layout already lists the messages
+        if self.renderer.render_status_bar(screen, rows, cols, &view) {
+            return;
+        }
+
+        if let Some(ref announcement) = self.last_announcement {
+            if rows > 1 {
+                print!("\n{}", announcement);
+            }
+        }
     }
 
-    /// Handle tab update events
-    fn handle_tab_update(&mut self, tabs: Vec<zellij_tile::prelude::TabInfo>) -> bool {
-        // Find active tab
-        for tab in tabs {
-            if tab.active {
-                self.tab_info = Some(LocalTabInfo {
-                    position: tab.position,
-                    name: tab.name.clone(),
-                    active: true,
-                    panes_count: 0, // Pane count tracked separately via PaneUpdate
-                });
+    /// Read-only view of the state consumed by the renderer
+    fn view(&self) -> RenderView<'_> {
+        RenderView {
+            pane_states: &self.pane_states,
+            queue: &self.notification_queue,
+            color_manager: &self.color_manager,
+            animation_engine: &self.animation_engine,
+            frame: self.frame(),
+            suppression: self.suppression.status(self.clock.now_ms()),
+            error: self.error_state.as_deref(),
+            status_message: self.status_message.as_ref().map(|(message, _)| message.as_str()),
+            recovery: &self.recovery,
+            channels: &self.channels,
+            bridge: self.event_bridge.connection_state(),
+            aggregate: &self.aggregate,
+            cascade: self.clear_cascade.as_ref().filter(|cascade| cascade.is_running(self.clock.now_ms())),
+            rate: &self.rate_history,
+            queue_diff: &self.queue_diff,
+            instance: self.election.visible_role(),
+        }
+    }
+
+    /// Drain the event bus through the reducer
+    fn dispatch(&mut self) -> bool {
+        let mut needs_render = false;
+        let mut dispatched = 0;
+
+        while let Some(event) = self.bus.next_event() {
+            needs_render |= self.reduce(event);
+            dispatched += 1;
+            if dispatched >= MAX_EVENTS_PER_DISPATCH {
+                log_warn("Event dispatch limit reached, deferring remaining events");
                 break;
             }
         }
-        true
-    }
 
-    /// Handle pane update events
-    fn handle_pane_update(&mut self, pane_manifest: PaneManifest) -> bool {
-        // Update pane information
-        self.pane_manifest.clear();
-
-        for (_tab_index, pane_info_list) in pane_manifest.panes {
-            for pane in pane_info_list {
-                let info = LocalPaneInfo {
-                    id: pane.id,
-                    is_focused: pane.is_focused,
-                    title: pane.title.clone(),
-                    is_plugin: pane.is_plugin,
-                };
-                self.pane_manifest.insert(pane.id, info.clone());
+        // Show what the queue lets through
+        needs_render |= self.process_notification_queue();
+        needs_render |= self.sync_attention_queue();
+        needs_render
+    }
 
-                // If this pane is focused and has a notification, clear it
-                if pane.is_focused {
-                    self.clear_pane_notification(pane.id);
+    /// Surface one waiting Attention notification at a time; the next one animates afresh when
+    /// it comes up
+    fn sync_attention_queue(&mut self) -> bool {
+        let promoted = attention_queue::assign(&mut self.pane_states, self.config.attention_queue);
+        for pane_id in &promoted {
+            if let Some(state) = self.pane_states.get_mut(pane_id) {
+                if self.config.animations_enabled() && !state.badge_only {
+                    state.is_animating = true;
+                    state.animation_start_tick = self.tick_count;
+                    state.animation_start_ms = self.clock.now_ms();
                 }
             }
         }
-
-        true
+        !promoted.is_empty()
     }
 
-    /// Handle custom messages (from other plugins or IPC)
-    fn handle_custom_message(&mut self, message: String, payload: String) -> bool {
-        match message.as_str() {
-            "notification" => {
-                self.handle_notification_message(&payload)
+    /// Apply a domain event to the plugin state
+    fn reduce(&mut self, event: DomainEvent) -> bool {
+        match event {
+            DomainEvent::NotificationArrived(notification) => {
+                self.queue_notification(*notification);
+                true
             }
-            "clear" => {
-                self.clear_all_notifications();
+            DomainEvent::NotificationCancelled { id, pane_id } => {
+                self.cancel_notification(id.as_deref(), pane_id)
+            }
+            DomainEvent::ReminderScheduled(reminder) => {
+                log_info(&format!("Reminder {} every {}ms", reminder.id, reminder.every_ms));
+                self.reminders.add(reminder, self.clock.now_ms());
+                self.save_reminders();
+                false
+            }
+            DomainEvent::ConfirmRequested(request) => {
+                log_info(&format!("Question {}: {}", request.id, request.question));
+                self.confirms.ask(request, self.clock.now_ms());
                 true
             }
-            "config_reload" => {
-                self.reload_config();
+            DomainEvent::SessionHookReceived { session_id, pane_id, hook } => {
+                self.timeline.record_hook(&session_id, pane_id, hook, self.clock.now_ms());
+                false
+            }
+            DomainEvent::NotificationAcknowledged(id) => self.acknowledge_id(&id),
+            DomainEvent::PaneFocused(pane_id) => {
+                let had_notification = self.pane_states.get(&pane_id)
+                    .map(|s| s.has_notification())
+                    .unwrap_or(false);
+                self.clear_pane_notification(pane_id);
+                had_notification
+            }
+            DomainEvent::PaneRead(pane_id) => self.mark_read(pane_id),
+            DomainEvent::PaneDismissed(pane_id) => {
+                let had_notification = self.pane_states.get(&pane_id).is_some_and(|s| s.has_notification());
+                self.clear_pane_notification(pane_id);
+                had_notification
+            }
+            DomainEvent::PaneMuteChanged { pane_id, muted } => self.set_pane_muted(pane_id, muted),
+            DomainEvent::AllCleared { force } => {
+                self.clear_all_notifications(force);
                 true
             }
-            _ => false,
+            DomainEvent::TypeAcknowledged(notification_type) => {
+                let count = self.acknowledge_type(&notification_type);
+                self.status_message = Some((
+                    format!("Acknowledged {} {}", count, notification_type.name()),
+                    self.clock.now_ms() + STATUS_MESSAGE_MS,
+                ));
+                true
+            }
+            DomainEvent::SuppressionStarted { kind, duration_ms } => {
+                match kind {
+                    SuppressionKind::Dnd => self.suppression.start_dnd(self.clock.now_ms(), duration_ms),
+                    SuppressionKind::Snooze => {
+                        self.suppression.snooze(self.clock.now_ms(), duration_ms.unwrap_or_default())
+                    }
+                    SuppressionKind::Focus => {
+                        self.suppression.start_focus(self.clock.now_ms(), duration_ms.unwrap_or_default())
+                    }
+                }
+                true
+            }
+            DomainEvent::SuppressionEnded => {
+                let kind = self.suppression.kind();
+                let held = self.suppression.stop();
+                match kind {
+                    Some(SuppressionKind::Focus) => self.end_focus(held),
+                    _ => self.release_held(held),
+                }
+                true
+            }
+            DomainEvent::TickElapsed(elapsed) => self.handle_timer(elapsed),
+            DomainEvent::UserActivity => {
+                self.idle_tracker.record_activity(self.clock.now_ms());
+                false
+            }
         }
     }
 
-    /// Handle permission request results
-    fn handle_permission_result(&mut self, result: PermissionStatus) {
-        match result {
-            PermissionStatus::Granted => {
-                self.plugin_state = PluginState::Running;
-                log_info("Permissions granted, plugin fully operational");
-            }
-            PermissionStatus::Denied => {
-                self.error_state = Some("Permissions denied, running in fallback mode".to_string());
-                self.plugin_state = PluginState::FallbackMode;
-                log_warn("Permissions denied, entering fallback mode");
+    /// Handle timer events for animations
+    fn handle_timer(&mut self, elapsed: f64) -> bool {
+        self.tick_count = self.tick_count.wrapping_add(1);
+        self.clock.advance(elapsed);
+        self.notification_queue.update_timestamp(self.clock.now_ms());
+        self.actions.set_time(self.clock.now_ms());
+
+        // Update animation states
+        let mut needs_render = false;
+
+        // A new display rate window may let more of the queue through
+        needs_render |= self.process_notification_queue();
+
+        // Release deferred Attention notifications once the user pauses
+        if !self.deferred_notifications.is_empty() && !self.idle_tracker.is_busy(self.clock.now_ms()) {
+            for notification in std::mem::take(&mut self.deferred_notifications) {
+                self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
             }
+            needs_render = true;
         }
-    }
 
-    /// Handle piped messages from external sources (claude-notifications)
-    fn handle_pipe_message(&mut self, pipe_message: PipeMessage) -> bool {
-        // Parse the pipe message
-        if let Some(payload) = pipe_message.payload {
-            return self.handle_notification_message(&payload);
+        // Release numbered notifications whose predecessors did not arrive in time
+        if self.sequencer.held_count() > 0 {
+            let (released, gaps) = self.sequencer.expire(self.clock.now_ms());
+            for gap in gaps {
+                log_warn(&format!("Sequence gap: {}", gap));
+                self.status_message = Some((format!("Sequence gap: {}", gap), self.clock.now_ms() + STATUS_MESSAGE_MS));
+            }
+            for notification in released {
+                self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+                needs_render = true;
+            }
         }
-        false
-    }
 
-    /// Handle notification messages from IPC
-    fn handle_notification_message(&mut self, payload: &str) -> bool {
-        match self.event_bridge.parse_notification(payload) {
-            Ok(notification) => {
-                self.queue_notification(notification);
-                true
+        // Drop notifications whose panes did not appear in time
+        if self.pending_panes.held_count() > 0 {
+            for notification in self.pending_panes.expire(self.config.missing_pane_hold_ms, self.clock.now_ms()) {
+                self.drop_missing_pane(&notification);
             }
-            Err(e) => {
-                log_warn(&format!("Failed to parse notification: {}", e));
-                false
+        }
+
+        // Raise reminders that are due
+        for notification in self.reminders.due(self.clock.now_ms()) {
+            self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+            needs_render = true;
+        }
+
+        // Synthesize the stress test notifications due at its rate
+        let now = self.clock.now_ms();
+        for notification in self.stress.as_mut().map(|run| run.due(now)).unwrap_or_default() {
+            self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+            needs_render = true;
+        }
+
+        // Announce this instance and fail over from silent ones
+        needs_render |= self.run_election();
+
+        // Ingest the JSON lines appended to `ingest_file`
+        needs_render |= self.poll_ingest_file();
+
+        // Start host checks that are due
+        self.run_due_checks();
+        self.check_for_update();
+
+        // Release notifications held by an expired DND/snooze/focus period
+        let kind = self.suppression.kind();
+        if let Some(held) = self.suppression.expire(self.clock.now_ms()) {
+            match kind {
+                Some(SuppressionKind::Focus) => self.end_focus(held),
+                _ => self.release_held(held),
             }
+            needs_render = true;
         }
-    }
 
-    /// Queue a notification for display
-    fn queue_notification(&mut self, notification: Notification) {
-        self.notification_queue.enqueue(notification.clone());
+        // Keep the suppression countdown live (once per second)
+        if self.suppression.is_active(self.clock.now_ms()) && self.tick_count.is_multiple_of(self.ticks_per_second()) {
+            needs_render = true;
+        }
 
-        // If targeting a specific pane, update its visual state
-        if let Some(pane_id) = notification.pane_id {
-            self.update_pane_visual_state(pane_id, &notification);
+        // Keep deadline countdowns and overdue flashing live (once per second)
+        if self.tick_count.is_multiple_of(self.ticks_per_second()) {
+            needs_render |= self.check_deadlines();
         }
-    }
 
-    /// Process queued notifications
-    fn process_notification_queue(&mut self) -> bool {
-        let mut needs_render = false;
+        // Report problems found once the plugin has settled after load
+        if self.tick_count == STARTUP_HEALTH_CHECK_SECS * self.ticks_per_second() {
+            for check in self.health_report().problems() {
+                log_warn(&format!("Health check: {}: {}", check.name, check.detail));
+            }
+        }
 
-        while let Some(notification) = self.notification_queue.dequeue_ready() {
-            if let Some(pane_id) = notification.pane_id {
-                self.update_pane_visual_state(pane_id, &notification);
+        // Retry getting out of fallback mode
+        diagnostics::set_log_time(self.clock.now_ms());
+        if self.recovery.attempt_due(self.clock.now_ms()) {
+            log_info(&format!("Recovery attempt {}/{}: requesting permissions again",
+                self.recovery.attempts, self.recovery.max_attempts));
+            request_permission(&plugin_permissions(&self.config));
+            needs_render = true;
+        }
+
+        // Dismiss an unanswered confirmation prompt or acknowledge chord
+        if let InteractionMode::ConfirmClearAll { deadline_ms, .. }
+            | InteractionMode::AckTypeChord { deadline_ms }
+            | InteractionMode::Chord { deadline_ms }
+            | InteractionMode::ConfirmAutomation { deadline_ms, .. } = self.interaction_mode
+        {
+            if self.clock.now_ms() >= deadline_ms {
+                if let InteractionMode::ConfirmAutomation { ref trigger, .. } = self.interaction_mode {
+                    log_info(&format!("Automation {} not confirmed, skipped", trigger.name));
+                }
+                self.interaction_mode = InteractionMode::Normal;
                 needs_render = true;
             }
         }
 
-        needs_render
-    }
+        // Take the default of an unanswered sender question, and keep its countdown live
+        if let Some(answer) = self.confirms.expire(self.clock.now_ms()) {
+            self.send_confirm_answer(answer);
+            needs_render = true;
+        } else if self.confirms.current().is_some() && self.tick_count.is_multiple_of(self.ticks_per_second()) {
+            needs_render = true;
+        }
 
-    /// Update visual state for a pane based on notification
-    fn update_pane_visual_state(&mut self, pane_id: u32, notification: &Notification) {
-        let visual_state = self.pane_states.entry(pane_id).or_insert_with(VisualState::default);
+        // Draw the clear-all cascade on every tick until it has run
+        if let Some(ref cascade) = self.clear_cascade {
+            if !cascade.is_running(self.clock.now_ms()) {
+                self.clear_cascade = None;
+            }
+            needs_render = true;
+        }
 
-        // Set border color based on notification type
-        visual_state.border_color = self.color_manager.get_notification_color(&notification.notification_type);
+        // Move focus once the auto-focus countdown has run out, and keep the countdown live
+        let counting_down = self.auto_focus.pending().is_some();
+        if self.focus_due_pane() || (counting_down && self.tick_count.is_multiple_of(self.ticks_per_second())) {
+            needs_render = true;
+        }
 
-        // Set badge icon
-        visual_state.badge_icon = notification.notification_type.icon();
+        // Keep the focus ring moving while it is drawn
+        if self.render_size.is_some_and(|(_, rows)| rows >= FOCUS_RING_MIN_ROWS)
+            && self.renderer.focus_ring_active(&self.pane_states)
+            && self.tick_count.is_multiple_of((self.ticks_per_second() / 2).max(1))
+        {
+            needs_render = true;
+        }
 
-        // Start animation if enabled
-        if self.config.animation.enabled {
-            visual_state.is_animating = true;
-            visual_state.animation_start_tick = self.tick_count;
-            visual_state.animation_style = self.config.animation.style.clone();
+        // Show replacements whose wait for the minimum display time of the one before is over
+        for notification in self.state_manager.due(self.clock.now_ms()) {
+            self.apply_sink_effects(vec![SinkEffect::ShowVisual], &notification);
+            needs_render = true;
         }
 
-        // Set notification message for tooltip
-        visual_state.notification_message = Some(notification.message.clone());
-        visual_state.notification_type = Some(notification.notification_type.clone());
-    }
+        // Merge repeated history entries in the background
+        if self.config.history_compaction != HistoryCompaction::Off && self.history.compaction_due(self.clock.now_ms()) {
+            let merged = self.compact_history(self.config.history_compaction);
+            if merged > 0 {
+                log_info(&format!("History compaction merged {} entries", merged));
+            }
+        }
 
-    /// Clear notification state for a pane
-    fn clear_pane_notification(&mut self, pane_id: u32) {
-        if let Some(visual_state) = self.pane_states.get_mut(&pane_id) {
-            visual_state.clear();
+        // Persist changed usage counters (spaced out to limit writes)
+        if self.metrics.save_due(self.clock.now_ms()) {
+            self.save_metrics();
         }
-        self.notification_queue.remove_for_pane(pane_id);
-    }
 
-    /// Clear all notifications
-    fn clear_all_notifications(&mut self) {
-        for (_pane_id, visual_state) in self.pane_states.iter_mut() {
-            visual_state.clear();
+        // Close the startup banner once its time is up
+        if self.startup_banner_until.is_some_and(|until_ms| self.clock.now_ms() >= until_ms) {
+            self.startup_banner_until = None;
+            needs_render = true;
+        }
+
+        // Drop an expired status line message
+        if let Some((_, until_ms)) = self.status_message {
+            if self.clock.now_ms() >= until_ms {
+                self.status_message = None;
+                needs_render = true;
+            }
+        }
+
+        // Periodic scans run every tick, or spaced out in low power mode
+        let scan = self.scans.due(self.config.low_power, self.clock.now_ms());
+
+        // Re-deliver Critical notifications left unacknowledged out of terminal, once each
+        if scan && self.config.ack_escalation_ms > 0 {
+            self.escalate_unacknowledged();
+        }
+
+        // Mark notifications left unacknowledged past their response-time target
+        if scan && !self.config.slo.is_empty() {
+            needs_render |= self.check_slo();
+        }
+
+        // Refresh progress estimates (the elapsed time and ETA change every second)
+        for (pane_id, tracker) in &self.progress {
+            if let Some(visual_state) = self.pane_states.get_mut(pane_id) {
+                visual_state.progress = Some(tracker.estimate(self.clock.now_ms()));
+            }
+        }
+        if !self.progress.is_empty() && scan && self.tick_count.is_multiple_of(self.ticks_per_second()) {
+            needs_render = true;
+        }
+
+        // Step Progress spinners
+        if !self.config.motion_reduced() && self.pane_states.values().any(|s| s.is_spinning()) {
+            needs_render = true;
         }
-        self.notification_queue.clear();
-    }
 
-    /// Reload configuration
-    fn reload_config(&mut self) {
-        if let Some(new_config) = self.config_manager.reload() {
-            self.config = new_config;
-            self.color_manager = ColorManager::new(&self.config.theme);
-            self.animation_engine = AnimationEngine::new(&self.config.animation);
-            self.renderer = Renderer::new(&self.config);
-            log_info("Configuration reloaded");
+        // Animate only what can be seen: panes in tabs that are not shown pause, skip or run on
+        let frame = self.frame();
+        let hidden_tabs = self.config.animation.hidden_tabs;
+        let hidden: BTreeSet<u32> = match hidden_tabs {
+            HiddenTabAnimation::Run => BTreeSet::new(),
+            _ => self.pane_states.keys().copied().filter(|pane_id| !self.is_pane_shown(*pane_id)).collect(),
+        };
+        for (pane_id, visual_state) in self.pane_states.iter_mut() {
+            if !visual_state.is_animating {
+                continue;
+            }
+            if hidden.contains(pane_id) {
+                if hidden_tabs == HiddenTabAnimation::Skip {
+                    self.animation_engine.stop_animation(visual_state);
+                    needs_render = true;
+                } else {
+                    self.animation_engine.pause_animation(visual_state, frame);
+                }
+                continue;
+            }
+            self.animation_engine.resume_animation(visual_state, frame);
+            self.animation_engine.update_animation(visual_state, frame);
+            needs_render = true;
         }
+
+        // Close toasts whose time is up
+        let actions = self.toasts.expire(self.clock.now_ms());
+        self.apply_toast_actions(actions);
+
+        // Send webhook deliveries that are due (first attempts and retries)
+        self.flush_webhook_deliveries();
+
+        // Check for expired notifications
+        if scan {
+            for notification in self.notification_queue.cleanup_expired() {
+                self.expire_notification(&notification);
+                needs_render = true;
+            }
+        }
+
+        // Count the tick against displayed notifications (paused ones in hidden tabs)
+        let elapsed_ms = (elapsed * 1000.0) as u64;
+        let mut expired_panes = Vec::new();
+        let mut rearmed_panes = Vec::new();
+        for (pane_id, notification) in self.displayed.iter_mut() {
+            if notification.advance_recurrence(elapsed_ms) {
+                rearmed_panes.push(*pane_id);
+            }
+            if self.pane_states.get(pane_id).is_some_and(|s| s.pinned) {
+                continue;
+            }
+            if !self.notification_queue.is_ttl_paused(notification) {
+                notification.advance_ttl(elapsed_ms);
+            }
+            if notification.is_ttl_spent() {
+                expired_panes.push(*pane_id);
+            }
+        }
+        for pane_id in expired_panes {
+            needs_render |= self.expire_displayed(pane_id);
+        }
+
+        // Re-animate recurring notifications still waiting for acknowledgement (no escalation)
+        for pane_id in rearmed_panes {
+            if let Some(visual_state) = self.pane_states.get_mut(&pane_id).filter(|s| !s.is_read()) {
+                let style = visual_state.animation_style.clone();
+                self.animation_engine.start_animation(visual_state, frame, style);
+                needs_render = true;
+            }
+        }
+
+        // Persist preferences changed since the last tick
+        self.sync_prefs();
+
+        // Restart timer for next tick
+        set_timeout(self.tick_interval_secs());
+
+        needs_render
+    }
+
+    /// Mirror the aggregate notification summary into the window title
+    fn sync_title_mirror(&mut self) -> bool {
+        if self.config.title_mirror == TitleMirror::Off || self.render_only() {
+            return false;
+        }
+
+        let summary = title::compact_summary(&self.pane_states);
+        let base = self.mode_info.session_name.clone().unwrap_or_else(|| "Zellij".to_string());
+        let new_title = title::format_title(&summary, &base);
+        if self.artifacts.applied(Artifact::WindowTitle).is_some_and(|applied| applied.value == new_title) {
+            return false;
+        }
+
+        self.pending_output.push(title::window_title_escape(&new_title));
+        self.artifacts.record(Artifact::WindowTitle, new_title, base);
+        true
+    }
+
+    /// Bring every mark outside the plugin pane in line with the notifications in one pass: pane
+    /// title icons, tab badges, the unread count file, the plugin's own pane title and the window
+    /// title. Restores the host refused on an earlier pass are tried again. Returns whether the
+    /// plugin needs to render.
+    fn sync_derived_artifacts(&mut self) -> bool {
+        self.sync_pane_title_badges();
+        self.sync_tab_badges();
+        self.sync_unread_count();
+        self.sync_pane_title();
+        self.sync_title_mirror()
+    }
+
+    /// Compare the queue with the last snapshot while the queue inspector is open, returning
+    /// whether it needs to be drawn again
+    fn refresh_queue_diff(&mut self) -> bool {
+        if !self.show_queue_overlay {
+            self.queue_diff.reset();
+            return false;
+        }
+        self.queue_diff.refresh(&self.notification_queue, self.clock.now_ms())
+    }
+
+    /// Restore the titles of panes that no longer want an icon (acknowledged, cleared, expanded)
+    fn sync_pane_title_badges(&mut self) {
+        for pane_id in self.artifacts.pane_titles() {
+            if self.world.pane(pane_id).is_none() && self.world.panes().next().is_some() {
+                // Closed along with its title
+                self.artifacts.release(Artifact::PaneTitle(pane_id));
+                continue;
+            }
+            let wanted = self.pane_states.get(&pane_id)
+                .is_some_and(|state| state.has_notification() && state.surfaces.contains(&Surface::TitleBadge));
+            if !wanted {
+                self.restore_pane_title(pane_id);
+            }
+        }
+    }
+
+    /// Restore a pane title the plugin put an icon in; a refused rename is retried on the next pass
+    fn restore_pane_title(&mut self, pane_id: u32) {
+        let artifact = Artifact::PaneTitle(pane_id);
+        let Some(original) = self.artifacts.applied(artifact).map(|applied| applied.original.clone()) else {
+            return;
+        };
+        if self.actions.rename_terminal_pane(pane_id, &original) {
+            self.artifacts.release(artifact);
+        } else {
+            self.artifacts.restore_failed(artifact);
+        }
+    }
+
+    /// Show the compact summary in the plugin's own pane title (renamed only when it changes)
+    fn sync_pane_title(&mut self) {
+        if !self.config.pane_title_summary || self.plugin_state != PluginState::Running {
+            return;
+        }
+
+        if let Some(plugin_id) = self.own_pane_id {
+            let new_title = title::pane_title(&title::compact_summary(&self.pane_states));
+            if self.own_pane_title.as_deref() != Some(new_title.as_str())
+                && self.actions.rename_plugin_pane(plugin_id, &new_title)
+            {
+                self.own_pane_title = Some(new_title);
+            }
+        }
+    }
+
+    /// Clear all notifications, asking for confirmation first if configured
+    fn request_clear_all(&mut self) -> bool {
+        let count = self.pane_states.values().filter(|s| s.has_notification() && !s.pinned).count();
+        if !self.config.clear_all_confirm || count == 0 {
+            self.bus.emit(DomainEvent::AllCleared { force: false });
+            return true;
+        }
+
+        self.interaction_mode = InteractionMode::ConfirmClearAll {
+            count,
+            deadline_ms: self.clock.now_ms() + CLEAR_CONFIRM_TIMEOUT_MS,
+        };
+        true
+    }
+
+    /// Handle plugin-local keybindings (view toggles, confirmation prompts)
+    fn handle_key(&mut self, key: &KeyWithModifier) -> bool {
+        if self.show_tutorial {
+            // Any key closes the tutorial for good
+            self.show_tutorial = false;
+            if let Err(e) = tutorial::mark_seen(TUTORIAL_STATE_PATH) {
+                log_warn(&format!("Failed to remember the tutorial was seen: {}", e));
+            }
+            return true;
+        }
+
+        if self.startup_banner_until.take().is_some() {
+            // Any key dismisses the startup banner, and does nothing else
+            return true;
+        }
+
+        if let Some(pending) = self.auto_focus.cancel() {
+            // Any key cancels a pending auto-focus, and does nothing else
+            log_info(&format!("Auto-focus of pane {} cancelled", pending.pane_id));
+            return true;
+        }
+
+        if let InteractionMode::ConfirmClearAll { .. } = self.interaction_mode {
+            // Any key answers the prompt; only 'y' confirms
+            self.interaction_mode = InteractionMode::Normal;
+            if key.key_modifiers.is_empty() && matches!(key.bare_key, BareKey::Char('y') | BareKey::Char('Y')) {
+                self.bus.emit(DomainEvent::AllCleared { force: false });
+            }
+            return true;
+        }
+
+        if let InteractionMode::ConfirmAutomation { .. } = self.interaction_mode {
+            // Any key answers the prompt; only 'y' lets the automation act
+            if let InteractionMode::ConfirmAutomation { trigger, .. } = std::mem::take(&mut self.interaction_mode) {
+                if key.key_modifiers.is_empty() && matches!(key.bare_key, BareKey::Char('y') | BareKey::Char('Y')) {
+                    self.run_automation(&trigger);
+                } else {
+                    log_info(&format!("Automation {} declined", trigger.name));
+                }
+            }
+            return true;
+        }
+
+        if let InteractionMode::AckTypeChord { .. } = self.interaction_mode {
+            // Second key of the chord picks the type; anything else cancels
+            self.interaction_mode = InteractionMode::Normal;
+            if let (true, BareKey::Char(c)) = (key.key_modifiers.is_empty(), key.bare_key) {
+                if let Some(notification_type) = chord_notification_type(c) {
+                    self.bus.emit(DomainEvent::TypeAcknowledged(notification_type));
+                }
+            }
+            return true;
+        }
+
+        if let InteractionMode::Chord { .. } = self.interaction_mode {
+            // Second key of the chord picks the action; anything else cancels
+            self.interaction_mode = InteractionMode::Normal;
+            if let (true, BareKey::Char(c)) = (key.key_modifiers.is_empty(), key.bare_key) {
+                if let Some(action) = ChordAction::from_key(c) {
+                    self.run_chord_action(action);
+                }
+            }
+            return true;
+        }
+
+        if self.confirms.current().is_some() && key.key_modifiers.is_empty() {
+            // y/n answers the sender's question, Enter takes its default; other keys work as usual
+            let answer = match key.bare_key {
+                BareKey::Char('y') | BareKey::Char('Y') => Some(Some(true)),
+                BareKey::Char('n') | BareKey::Char('N') => Some(Some(false)),
+                BareKey::Enter => Some(None),
+                _ => None,
+            };
+            if let Some(yes) = answer {
+                return self.answer_confirm(yes);
+            }
+        }
+
+        if self.is_chord_leader(key) {
+            self.interaction_mode = InteractionMode::Chord {
+                deadline_ms: self.clock.now_ms() + CHORD_TIMEOUT_MS,
+            };
+            return true;
+        }
+
+        if !key.key_modifiers.is_empty() {
+            return false;
+        }
+        match key.bare_key {
+            BareKey::Char('a') => {
+                self.interaction_mode = InteractionMode::AckTypeChord {
+                    deadline_ms: self.clock.now_ms() + ACK_CHORD_TIMEOUT_MS,
+                };
+                true
+            }
+            BareKey::Char('q') => {
+                self.show_queue_overlay = !self.show_queue_overlay;
+                true
+            }
+            BareKey::Char('d') => {
+                self.show_diagnostics = !self.show_diagnostics;
+                true
+            }
+            BareKey::Char('f') => {
+                self.toggle_focus(None);
+                true
+            }
+            BareKey::Char('h') => {
+                self.show_health = !self.show_health;
+                true
+            }
+            BareKey::Char('c') => {
+                self.show_channels = !self.show_channels;
+                true
+            }
+            BareKey::Char('s') if self.show_history => {
+                self.history_sort = self.history_sort.next();
+                true
+            }
+            BareKey::Char('g') if self.show_history => {
+                self.history_grouping = self.history_grouping.next();
+                true
+            }
+            BareKey::Char('g') => {
+                self.show_groups = !self.show_groups;
+                true
+            }
+            BareKey::Char('l') => {
+                self.show_history = !self.show_history;
+                true
+            }
+            BareKey::Char('j') => {
+                self.show_sessions = !self.show_sessions;
+                true
+            }
+            BareKey::Char('i') => {
+                self.show_detail = !self.show_detail;
+                self.detail_pager = Pager::default();
+                true
+            }
+            BareKey::PageDown | BareKey::PageUp | BareKey::Home | BareKey::End if self.show_detail => {
+                // Page through a long message in the detail view
+                let pages = self.detail_page_count();
+                match key.bare_key {
+                    BareKey::PageDown => self.detail_pager.next_page(pages),
+                    BareKey::PageUp => self.detail_pager.previous_page(pages),
+                    BareKey::Home => self.detail_pager.first_page(pages),
+                    _ => self.detail_pager.last_page(pages),
+                }
+            }
+            BareKey::Char('?') => {
+                self.show_legend = !self.show_legend;
+                true
+            }
+            BareKey::Char(c @ ('e' | 'k' | 'x')) if self.show_detail => {
+                // TTL controls for the notification in the detail view
+                let Some(id) = self.chord_target()
+                    .and_then(|pane_id| self.displayed.get(&pane_id))
+                    .map(|notification| notification.id.clone())
+                else {
+                    return false;
+                };
+                let outcome = match c {
+                    'e' => self.extend_notification(&id, self.config.ttl_extend_ms)
+                        .map(|remaining_ms| format!("Expires in {}", format_duration_ms(remaining_ms))),
+                    'k' => self.make_sticky(&id).map(|()| "Kept until acknowledged".to_string()),
+                    _ => self.expire_now(&id).map(|()| "Expired".to_string()),
+                };
+                let message = outcome.unwrap_or_else(|e| e);
+                self.status_message = Some((message, self.clock.now_ms() + STATUS_MESSAGE_MS));
+                true
+            }
+            BareKey::Char(c @ '1'..='9') if self.show_channels => {
+                // Toggle the channel at that position in the list
+                let index = c as usize - '1' as usize;
+                match self.channels.get(index).map(|channel| channel.name.clone()) {
+                    Some(name) => self.set_channel_visible(&name, None),
+                    None => false,
+                }
+            }
+            BareKey::Char(c @ '1'..='9') if self.config.entry_hotkeys && !self.show_detail => {
+                // Acknowledge the entry drawn with that number
+                match self.renderer.entry_pane(c as usize - '0' as usize) {
+                    Some(pane_id) => {
+                        self.bus.emit(DomainEvent::PaneDismissed(pane_id));
+                        self.status_message = Some((format!("Acknowledged pane {}", pane_id), self.clock.now_ms() + STATUS_MESSAGE_MS));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            BareKey::Char(c @ '1'..='9') if self.show_detail && self.reply_target(c as u32 - '0' as u32).is_some() => {
+                // Answer the prompt shown in the detail view with that option; only there, so a
+                // stray digit never types into a pane
+                let outcome = self.quick_reply(None, c as u32 - '0' as u32);
+                let message = outcome.unwrap_or_else(|e| e);
+                self.status_message = Some((message, self.clock.now_ms() + STATUS_MESSAGE_MS));
+                true
+            }
+            BareKey::Char('n') if self.show_diagnostics => self.mute_noisy_source(),
+            BareKey::Char('r') if self.error_state.is_some() => {
+                self.request_permissions_again();
+                true
+            }
+            BareKey::Char('p') => {
+                // Toggle the pin of the notification shown for the pane that notified most recently
+                match self.last_notified_pane.filter(|pane_id| self.displayed.contains_key(pane_id)) {
+                    Some(pane_id) => {
+                        let pinned = !self.pane_states.get(&pane_id).is_some_and(|s| s.pinned);
+                        self.set_pinned(pane_id, pinned)
+                    }
+                    None => false,
+                }
+            }
+            BareKey::Char('e') => {
+                // Keep the notification of the pane that notified most recently around for longer
+                let id = self.last_notified_pane
+                    .and_then(|pane_id| self.displayed.get(&pane_id))
+                    .map(|notification| notification.id.clone());
+                match id {
+                    Some(id) => match self.extend_notification(&id, self.config.ttl_extend_ms) {
+                        Ok(remaining_ms) => {
+                            log_info(&format!("Notification {} now expires in {}", id, format_duration_ms(remaining_ms)));
+                            true
+                        }
+                        Err(_) => false,
+                    },
+                    None => false,
+                }
+            }
+            BareKey::Char('v') => {
+                // Mark the newest unread notification read
+                match self.newest_pane(VisualState::is_unread) {
+                    Some(pane_id) => {
+                        self.bus.emit(DomainEvent::PaneRead(pane_id));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            BareKey::Char('x') => {
+                // Dismiss the newest notification, read or not
+                match self.newest_pane(VisualState::has_notification) {
+                    Some(pane_id) => {
+                        self.bus.emit(DomainEvent::PaneDismissed(pane_id));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            BareKey::Char('m') => {
+                // Toggle mute for the pane that notified most recently
+                match self.last_notified_pane {
+                    Some(pane_id) => {
+                        let muted = !self.muted_panes.is_muted(pane_id);
+                        self.bus.emit(DomainEvent::PaneMuteChanged { pane_id, muted });
+                        true
+                    }
+                    None => false,
+                }
+            }
+            BareKey::Char(c) => match DisplaySurface::from_key(c).filter(|surface| surface.available(&self.config)) {
+                Some(surface) => self.set_surface_enabled(surface, None),
+                None => false,
+            },
+            BareKey::Esc if self.show_queue_overlay || self.show_diagnostics || self.show_health || self.show_channels
+                || self.show_groups || self.show_history || self.show_sessions || self.show_detail
+                || self.show_legend =>
+            {
+                self.show_queue_overlay = false;
+                self.show_diagnostics = false;
+                self.show_health = false;
+                self.show_channels = false;
+                self.show_groups = false;
+                self.show_history = false;
+                self.show_sessions = false;
+                self.show_detail = false;
+                self.detail_pager = Pager::default();
+                self.show_legend = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a key is the configured chord leader
+    fn is_chord_leader(&self, key: &KeyWithModifier) -> bool {
+        chord::parse_leader(&self.config.chord_leader).as_ref() == Some(key)
+    }
+
+    /// Pane a chord acts on: the focused terminal pane when it has a notification, otherwise the
+    /// pane that notified most recently
+    fn chord_target(&self) -> Option<u32> {
+        let has_notification = |pane_id: &u32| self.pane_states.get(pane_id).is_some_and(|s| s.has_notification());
+        self.world.visible_panes()
+            .filter(|pane| pane.is_focused && !pane.is_plugin)
+            .map(|pane| pane.id)
+            .find(has_notification)
+            .or(self.last_notified_pane.filter(has_notification))
+    }
+
+    /// Pane a quick reply with option `number` goes to: the chord target, when its notification
+    /// offers that option
+    fn reply_target(&self, number: u32) -> Option<u32> {
+        self.chord_target().filter(|pane_id| self.displayed.get(pane_id)
+            .is_some_and(|notification| quick_reply::pick(&notification.reply_options, number as usize).is_some()))
+    }
+
+    /// Answer the notification of a pane (the chord target if unspecified) with its quick reply
+    /// option `number`: type the option and Enter into the pane, then acknowledge the notification
+    fn quick_reply(&mut self, pane_id: Option<u32>, number: u32) -> Result<String, String> {
+        let pane_id = pane_id.or_else(|| self.chord_target())
+            .ok_or_else(|| "No notification to reply to".to_string())?;
+        let notification = self.displayed.get(&pane_id)
+            .ok_or_else(|| format!("No notification for pane {}", pane_id))?;
+        let option = quick_reply::pick(&notification.reply_options, number as usize)
+            .ok_or_else(|| format!("Pane {} has no reply option {}", pane_id, number))?
+            .to_string();
+        let id = notification.id.clone();
+        if !self.actions.write_to_terminal_pane(pane_id, &quick_reply::keystrokes(&option)) {
+            return Err(format!("Could not type into pane {}", pane_id));
+        }
+        self.audit.record(&id, self.clock.now_ms(), AuditEvent::Replied(option.clone()));
+        self.bus.emit(DomainEvent::PaneDismissed(pane_id));
+        Ok(format!("Replied {:?} to pane {}", option, pane_id))
+    }
+
+    /// Run the action picked by the second key of a chord
+    fn run_chord_action(&mut self, action: ChordAction) {
+        match (action, self.chord_target()) {
+            (ChordAction::ClearAll, _) => {
+                self.request_clear_all();
+            }
+            (_, None) => {
+                self.status_message = Some(("No notification to act on".to_string(), self.clock.now_ms() + STATUS_MESSAGE_MS));
+            }
+            (ChordAction::Acknowledge, Some(pane_id)) => self.bus.emit(DomainEvent::PaneDismissed(pane_id)),
+            (ChordAction::Read, Some(pane_id)) => self.bus.emit(DomainEvent::PaneRead(pane_id)),
+            (ChordAction::Pin, Some(pane_id)) => {
+                let pinned = !self.pane_states.get(&pane_id).is_some_and(|s| s.pinned);
+                self.set_pinned(pane_id, pinned);
+            }
+            (ChordAction::Extend, Some(pane_id)) => {
+                if let Some(id) = self.displayed.get(&pane_id).map(|notification| notification.id.clone()) {
+                    if let Ok(remaining_ms) = self.extend_notification(&id, self.config.ttl_extend_ms) {
+                        log_info(&format!("Notification {} now expires in {}", id, format_duration_ms(remaining_ms)));
+                    }
+                }
+            }
+            (ChordAction::Mute, Some(pane_id)) => {
+                let muted = !self.muted_panes.is_muted(pane_id);
+                self.bus.emit(DomainEvent::PaneMuteChanged { pane_id, muted });
+            }
+        }
+    }
+
+    /// Handle tab update events
+    fn handle_tab_update(&mut self, tabs: Vec<zellij_tile::prelude::TabInfo>) -> bool {
+        self.world.apply_tabs(&tabs);
+
+        // Keep the known base name unless the tab was renamed (or restored) externally, or is gone
+        for position in self.artifacts.tab_names() {
+            let artifact = Artifact::TabName(position);
+            let current = self.world.tabs().find(|tab| tab.position == position).map(|tab| tab.name.as_str());
+            if self.artifacts.applied(artifact).map(|applied| applied.value.as_str()) != current {
+                self.artifacts.release(artifact);
+            }
+        }
+        self.update_ttl_pause();
+        self.sync_tab_locality();
+        true
+    }
+
+    /// Where a pane is relative to the active tab (unknown while the boost is off or the layout
+    /// is not known yet)
+    fn tab_locality(&self, pane_id: u32) -> TabLocality {
+        if !self.config.focused_tab_boost {
+            return TabLocality::Unknown;
+        }
+        match (self.world.active_tab(), self.world.tab_of_pane(pane_id)) {
+            (Some(active), Some(position)) if active.position == position => TabLocality::Active,
+            (Some(_), Some(_)) => TabLocality::Hidden,
+            _ => TabLocality::Unknown,
+        }
+    }
+
+    /// Whether a pane is in the tab being shown (assumed so while the layout is not known)
+    fn is_pane_shown(&self, pane_id: u32) -> bool {
+        match (self.world.active_tab(), self.world.tab_of_pane(pane_id)) {
+            (Some(active), Some(position)) => active.position == position,
+            _ => true,
+        }
+    }
+
+    /// Re-decide which displayed notifications belong to other projects after the roots changed
+    fn sync_project_scope(&mut self) {
+        for (pane_id, notification) in &self.displayed {
+            if let Some(visual_state) = self.pane_states.get_mut(pane_id) {
+                visual_state.other_project = workspace::is_other_project(&self.config.project_roots, notification.cwd.as_deref());
+            }
+        }
+    }
+
+    /// Whether a pane runs Claude Code, by its title or command
+    fn is_claude_pane(&self, pane_id: u32) -> bool {
+        self.world.pane(pane_id).is_some_and(|pane| {
+            !pane.is_plugin && claude_panes::is_claude_pane(&self.config.claude_pane_patterns, &pane.title, pane.command.as_deref())
+        })
+    }
+
+    /// Re-decide which panes run Claude Code after their titles or the patterns changed
+    fn sync_claude_panes(&mut self) {
+        let claude_panes: Vec<(u32, bool)> = self.pane_states.keys()
+            .map(|pane_id| (*pane_id, self.is_claude_pane(*pane_id)))
+            .collect();
+        for (pane_id, claude_pane) in claude_panes {
+            if let Some(visual_state) = self.pane_states.get_mut(&pane_id) {
+                visual_state.claude_pane = claude_pane;
+            }
+        }
+    }
+
+    /// Refresh every pane's tab locality after a tab switch or layout change
+    fn sync_tab_locality(&mut self) {
+        let localities: Vec<(u32, TabLocality)> = self.pane_states.keys()
+            .map(|pane_id| (*pane_id, self.tab_locality(*pane_id)))
+            .collect();
+        for (pane_id, locality) in localities {
+            if let Some(visual_state) = self.pane_states.get_mut(&pane_id) {
+                visual_state.tab_locality = locality;
+            }
+        }
+    }
+
+    /// Pause TTL countdown for notifications targeting panes and tabs that are not visible
+    fn update_ttl_pause(&mut self) {
+        let active_tab = match (self.config.pause_ttl_in_background, self.world.active_tab()) {
+            (true, Some(tab)) => tab.position,
+            _ => {
+                self.notification_queue.set_ttl_paused(BTreeSet::new(), BTreeSet::new());
+                return;
+            }
+        };
+
+        let panes = self.world.panes()
+            .filter(|pane| pane.tab_position != active_tab)
+            .map(|pane| pane.id)
+            .collect();
+        let tabs = self.world.tabs()
+            .map(|tab| tab.position)
+            .filter(|position| *position != active_tab)
+            .collect();
+        self.notification_queue.set_ttl_paused(panes, tabs);
+    }
+
+    /// Apply per-tab notification badges to tab names
+    fn sync_tab_badges(&mut self) {
+        if !self.config.tab_name_badges || self.plugin_state != PluginState::Running {
+            return;
+        }
+
+        // Tab renames are costly, so badge spinners step once per second
+        let step = self.tick_count / self.ticks_per_second();
+        let tabs: Vec<(usize, String)> = self.world.tabs().map(|tab| (tab.position, tab.name.clone())).collect();
+        for (position, tab_name) in tabs {
+            let artifact = Artifact::TabName(position);
+            let (base, current) = match self.artifacts.applied(artifact) {
+                Some(applied) => (applied.original.clone(), applied.value.clone()),
+                None => (self.tab_badge_style.strip(&tab_name), tab_name),
+            };
+            let states = self.pane_states.iter()
+                .filter(|(pane_id, _)| self.world.tab_of_pane(**pane_id) == Some(position))
+                .map(|(_, state)| state);
+            let badge = if self.surface_toggles.is_enabled(DisplaySurface::TabBadges) {
+                self.tab_badge_style.badge(states, step)
+            } else {
+                String::new()
+            };
+            let name = self.tab_badge_style.apply(&base, &badge);
+            if name == current {
+                continue;
+            }
+            // Tab positions are 1-based for rename_tab
+            if !self.actions.rename_tab(position as u32 + 1, &name) {
+                if badge.is_empty() {
+                    self.artifacts.restore_failed(artifact);
+                }
+            } else if name == base {
+                self.artifacts.release(artifact);
+            } else {
+                self.artifacts.record(artifact, name, base);
+            }
+        }
+    }
+
+    /// Write the unread count to the host file when it changes
+    fn sync_unread_count(&mut self) {
+        if !self.config.unread_count_file
+            || self.plugin_state != PluginState::Running
+            || !self.config.host_platform.has_shell()
+        {
+            return;
+        }
+        // The file is named after the session, so wait until it is known
+        let session = match self.mode_info.session_name.as_deref() {
+            Some(session) => session.to_string(),
+            None => return,
+        };
+
+        let count = self.pane_states.values().filter(|s| s.is_unread()).count();
+        if let Some(command) = self.unread_count_file.update(&self.config.unread_count_path, &session, count) {
+            if self.actions.run_command(&command, BTreeMap::new()) {
+                self.artifacts.record(Artifact::UnreadCount, count.to_string(), "0".to_string());
+            } else {
+                // Written again on the next pass
+                self.unread_count_file.reset();
+                if count == 0 {
+                    self.artifacts.restore_failed(Artifact::UnreadCount);
+                }
+            }
+        }
+    }
+
+    /// Handle pane update events
+    fn handle_pane_update(&mut self, pane_manifest: PaneManifest) -> bool {
+        let changes = self.world.apply_pane_manifest(&pane_manifest);
+        self.pane_manifest = pane_manifest;
+
+        // Focused panes acknowledge their notifications
+        for pane in self.world.focused_panes() {
+            self.bus.emit(DomainEvent::PaneFocused(pane.id));
+        }
+        // Focusing another pane first means the user is busy elsewhere
+        if let Some(pending) = self.auto_focus.focus_changed(self.world.focused_pane()) {
+            log_info(&format!("Auto-focus of pane {} cancelled: focus moved", pending.pane_id));
+        }
+
+        // Claude panes are told by their titles, which change as they run
+        self.sync_claude_panes();
+
+        // Commands finishing in panes, told by their titles reverting
+        if self.config.passive_detection {
+            self.detect_title_completions();
+        }
+
+        // Notifications held for panes that have now appeared
+        for notification in self.pending_panes.release(|pane_id| self.world.pane(pane_id).is_some()) {
+            self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+
+        // Notifications for closed panes can never be seen
+        for pane_id in changes.closed {
+            self.state_manager.forget_pane(pane_id);
+            if self.pane_states.get(&pane_id).map(|s| s.has_notification()).unwrap_or(false) {
+                self.bus.emit(DomainEvent::NotificationCancelled { id: None, pane_id: Some(pane_id) });
+            }
+        }
+
+        // Re-select surfaces as panes get stacked, collapsed or expanded
+        let active: Vec<u32> = self.pane_states.iter()
+            .filter(|(_, s)| s.has_notification())
+            .map(|(id, _)| *id)
+            .collect();
+        for pane_id in active {
+            self.refresh_surfaces(pane_id);
+        }
+
+        self.update_ttl_pause();
+        self.sync_tab_locality();
+        true
+    }
+
+    /// Handle custom messages (from other plugins or IPC)
+    fn handle_custom_message(&mut self, message: String, payload: String) -> bool {
+        match message.as_str() {
+            "notification" => match self.limit_payload(payload) {
+                Ok(payload) => self.handle_notification_message(&payload, None),
+                Err(_) => false,
+            },
+            "clear" => {
+                self.bus.emit(DomainEvent::AllCleared { force: false });
+                true
+            }
+            "ack_id" | "dismiss_id" | "query_id" => self.handle_id_message(&message, &payload),
+            "config_reload" => match self.reload_config() {
+                Ok(()) => true,
+                Err(e) => {
+                    log_warn(&format!("Keeping current configuration: {}", e));
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Answer an `ack_id`, `dismiss_id` or `query_id` message on the reply pipe
+    fn handle_id_message(&mut self, message: &str, payload: &str) -> bool {
+        let Some(kind) = IdRequestKind::from_message(message) else {
+            return false;
+        };
+        let (reply, reply_to) = match IdRequest::parse(payload) {
+            Ok(request) => (self.answer_id_request(kind, &request.id), request.reply_to),
+            Err(e) => (IdReply::invalid(kind, e), None),
+        };
+        // Only one instance answers when several listen: a render-only one's actions are read-only
+        let mut answer = MessageToPlugin::new(REPLY_PIPE).with_payload(reply.payload());
+        if let Some(url) = reply_to {
+            answer = answer.with_plugin_url(url);
+        }
+        self.actions.message_plugin(answer);
+        reply.ok && kind != IdRequestKind::Query
+    }
+
+    /// Carry out an ID request, emitting the acknowledgment or withdrawal
+    fn answer_id_request(&mut self, kind: IdRequestKind, id: &str) -> IdReply {
+        let (status, pane_id) = self.notification_status(id);
+        let pending = matches!(status, IdStatus::Displayed | IdStatus::Read | IdStatus::Queued | IdStatus::Held);
+        let error = match (kind, status) {
+            (IdRequestKind::Query, _) => None,
+            (_, IdStatus::Gone) => Some("already handled".to_string()),
+            (_, IdStatus::Unknown) => Some("unknown notification".to_string()),
+            _ => None,
+        };
+        match kind {
+            IdRequestKind::Ack if pending => self.bus.emit(DomainEvent::NotificationAcknowledged(id.to_string())),
+            IdRequestKind::Dismiss if pending => {
+                self.bus.emit(DomainEvent::NotificationCancelled { id: Some(id.to_string()), pane_id: None })
+            }
+            _ => {}
+        }
+        IdReply {
+            request: kind.name(),
+            id: id.to_string(),
+            ok: error.is_none() && (pending || kind == IdRequestKind::Query),
+            status,
+            pane_id,
+            error,
+        }
+    }
+
+    /// Where a notification is, and the pane it is for
+    fn notification_status(&self, id: &str) -> (IdStatus, Option<u32>) {
+        if let Some((pane_id, _)) = self.displayed.iter().find(|(_, n)| n.id == id) {
+            let read = self.pane_states.get(pane_id).is_some_and(|s| s.is_read());
+            return (if read { IdStatus::Read } else { IdStatus::Displayed }, Some(*pane_id));
+        }
+        if let Some(notification) = self.notification_queue.all().into_iter().find(|n| n.id == id) {
+            return (IdStatus::Queued, notification.pane_id);
+        }
+        if let Some(notification) = self.deferred_notifications.iter().find(|n| n.id == id)
+            .or_else(|| self.pending_panes.find(id))
+            .or_else(|| self.state_manager.find_waiting(id))
+        {
+            return (IdStatus::Held, notification.pane_id);
+        }
+        match self.history.get(id) {
+            Some(entry) => (IdStatus::Gone, entry.notification.pane_id),
+            None => (IdStatus::Unknown, None),
+        }
+    }
+
+    /// Run the health check against the current state
+    fn health_report(&self) -> HealthReport {
+        HealthReport::run(&HealthInputs {
+            plugin_state: &self.plugin_state,
+            ticks: self.tick_count,
+            pipe_messages: self.pipe_messages,
+            ignored_pipe_messages: self.ignored_pipe_messages,
+            config: &self.config,
+            render_size: self.render_size,
+            sequence_gaps: self.sequencer.gap_count(),
+            slow_frames: self.frames.slow_frames(),
+            ascii_reason: self.charset.ascii_reason(self.config.ascii_only),
+            failed_subsystems: self.lifecycle.failures(),
+        })
+    }
+
+    /// Pages of the message in the detail view at the last render size
+    fn detail_page_count(&self) -> usize {
+        let Some((cols, rows)) = self.render_size else { return 1 };
+        let lines = self.chord_target()
+            .and_then(|pane_id| self.displayed.get(&pane_id))
+            .map(|notification| self.renderer.detail_body(notification, cols).len())
+            .unwrap_or(0);
+        Pager::page_count(lines, Renderer::detail_page_rows(rows))
+    }
+
+    /// Where the effective configuration comes from (`version` command, startup banner)
+    fn config_profile(&self) -> String {
+        self.config_manager.profile(&self.config)
+    }
+
+    /// Permission state (`version` command, startup banner)
+    fn permissions_line(&self) -> String {
+        banner::permissions(&self.plugin_state, &plugin_permissions(&self.config))
+    }
+
+    /// Ask for permissions again now, restarting automatic recovery if it gave up
+    fn request_permissions_again(&mut self) {
+        if self.error_state.is_some() {
+            self.recovery.begin(self.clock.now_ms());
+        }
+        log_info("Requesting permissions again");
+        request_permission(&plugin_permissions(&self.config));
+    }
+
+    /// Handle permission request results
+    fn handle_permission_result(&mut self, result: PermissionStatus) {
+        match result {
+            PermissionStatus::Granted => {
+                self.actions.set_permitted(true);
+                if self.error_state.take().is_some() {
+                    self.recovery.succeed();
+                    log_info("Recovered from fallback mode");
+                }
+                self.plugin_state = PluginState::Running;
+                log_info("Permissions granted, plugin fully operational");
+            }
+            PermissionStatus::Denied => {
+                self.actions.set_permitted(false);
+                if self.error_state.is_none() {
+                    self.recovery.begin(self.clock.now_ms());
+                }
+                self.error_state = Some("Permissions denied, running in fallback mode".to_string());
+                self.plugin_state = PluginState::FallbackMode;
+                log_warn("Permissions denied, entering fallback mode");
+            }
+        }
+    }
+
+    /// Whether a pipe message is for this instance. Messages addressed to it (`zellij pipe -p`)
+    /// always are; broadcasts (`zellij pipe --name`) reach every plugin in the session, so with
+    /// `pipes` configured, other names are ignored under `strict_pipes` and logged otherwise.
+    fn accepts_pipe(&mut self, pipe_message: &PipeMessage) -> bool {
+        if pipe_message.is_private || self.config.pipes.is_empty() || self.config.pipes.contains(&pipe_message.name) {
+            return true;
+        }
+        if self.config.strict_pipes {
+            self.ignored_pipe_messages += 1;
+            return false;
+        }
+        if self.unexpected_pipes.insert(pipe_message.name.clone()) {
+            log_warn(&format!("Message on unexpected pipe {} (listening on {}; strict_pipes ignores it)",
+                pipe_message.name, self.config.pipes.join(", ")));
+        }
+        true
+    }
+
+    /// Handle piped messages from external sources (claude-notifications)
+    fn handle_pipe_message(&mut self, pipe_message: PipeMessage) -> bool {
+        // Parse the pipe message: JSON notifications, `type|key=value` lines, or control commands
+        self.pipe_messages += 1;
+        if let Some(payload) = pipe_message.payload {
+            let payload = match self.limit_payload(payload) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    self.actions.reply(&pipe_message.source, &format!("{}\n", e.json()));
+                    return false;
+                }
+            };
+            if payload.trim_start().starts_with('{') {
+                return self.handle_notification_message(&payload, Some(&pipe_message.source));
+            }
+            if payload.contains('|') {
+                return match self.event_bridge.parse_line(&payload) {
+                    Ok(message) => self.handle_pipe_notification(message, &pipe_message.source),
+                    Err(e) => {
+                        self.actions.reply(&pipe_message.source, &format!("Rejected notification line: {}\n", e));
+                        false
+                    }
+                };
+            }
+            return match ControlCommand::parse(&payload) {
+                Ok(command) => self.handle_control_command(command, &pipe_message.source),
+                Err(e) => {
+                    // Not a command: maybe a string payload from an old hook script
+                    if commands::is_unknown_command(&e) {
+                        if let Some(message) = self.event_bridge.parse_legacy(&payload) {
+                            self.log_deprecations();
+                            return self.handle_pipe_notification(message, &pipe_message.source);
+                        }
+                    }
+                    self.actions.reply(&pipe_message.source, &format!(
+                        "Ignoring pipe payload: {} (expected JSON, a `type|key=value` line or a command)\n", e));
+                    false
+                }
+            };
+        }
+        false
+    }
+
+    /// Follow a pipe message as a read-only mirror: notifications broadcast to every instance
+    /// (`zellij pipe --name`) are shown like the other instances show them, while messages
+    /// addressed to this instance and control commands are ignored, and nothing is written back
+    /// to the sender (the other instances answer it)
+    fn handle_mirror_pipe_message(&mut self, pipe_message: PipeMessage) -> bool {
+        let payload = match pipe_message.payload {
+            Some(payload) if !pipe_message.is_private => payload,
+            _ => return false,
+        };
+        self.pipe_messages += 1;
+        let Ok(payload) = self.limit_payload(payload) else { return false };
+        if payload.trim_start().starts_with('{') {
+            return self.handle_notification_message(&payload, None);
+        }
+        if payload.contains('|') {
+            if let Ok(message) = self.event_bridge.parse_line(&payload) {
+                return self.handle_bridge_message(message);
+            }
+        }
+        if ControlCommand::parse(&payload).is_err_and(|e| commands::is_unknown_command(&e)) {
+            if let Some(message) = self.event_bridge.parse_legacy(&payload) {
+                self.log_deprecations();
+                return self.handle_bridge_message(message);
+            }
+        }
+        false
+    }
+
+    /// Apply `max_payload_bytes` to an incoming payload, logging what was cut or refused
+    fn limit_payload(&mut self, payload: String) -> Result<String, PayloadTooLarge> {
+        let size = payload.len();
+        match payload::enforce(payload, self.config.max_payload_bytes, self.config.oversize_payload) {
+            Ok(payload) => {
+                if payload.len() < size {
+                    log_warn(&format!("Truncated payload of {} bytes to {}", size, payload.len()));
+                }
+                Ok(payload)
+            }
+            Err(e) => {
+                log_warn(&format!("Rejected {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle a plain-text control command (replies go back to CLI pipes)
+    fn handle_control_command(&mut self, command: ControlCommand, source: &PipeSource) -> bool {
+        match command {
+            ControlCommand::MutePane(pane_id) => {
+                self.bus.emit(DomainEvent::PaneMuteChanged { pane_id, muted: true });
+            }
+            ControlCommand::UnmutePane(pane_id) => {
+                self.bus.emit(DomainEvent::PaneMuteChanged { pane_id, muted: false });
+            }
+            ControlCommand::ClearAll { force } => self.bus.emit(DomainEvent::AllCleared { force }),
+            ControlCommand::Pin { id, pinned } => {
+                let pane_id = self.displayed.iter()
+                    .find(|(_, n)| n.id == id)
+                    .map(|(pane_id, _)| *pane_id);
+                match pane_id {
+                    Some(pane_id) => {
+                        self.set_pinned(pane_id, pinned);
+                        self.actions.reply(source, &format!("{} {}\n", if pinned { "pinned" } else { "unpinned" }, id));
+                    }
+                    None => {
+                        self.actions.reply(source, &format!("error: no displayed notification {}\n", id));
+                        return false;
+                    }
+                }
+            }
+            ControlCommand::Extend { id, extra_ms } => match self.extend_notification(&id, extra_ms) {
+                Ok(remaining_ms) => self.actions.reply(source, &format!("{} expires in {}\n", id, format_duration_ms(remaining_ms))),
+                Err(e) => {
+                    self.actions.reply(source, &format!("error: {}\n", e));
+                    return false;
+                }
+            },
+            ControlCommand::MarkRead(pane_id) => self.bus.emit(DomainEvent::PaneRead(pane_id)),
+            ControlCommand::Dismiss(pane_id) => self.bus.emit(DomainEvent::PaneDismissed(pane_id)),
+            ControlCommand::AckType(notification_type) => {
+                self.bus.emit(DomainEvent::TypeAcknowledged(notification_type))
+            }
+            ControlCommand::Dnd(minutes) => self.bus.emit(DomainEvent::SuppressionStarted {
+                kind: SuppressionKind::Dnd,
+                duration_ms: minutes.map(|m| m.saturating_mul(60_000)),
+            }),
+            ControlCommand::Snooze(minutes) => self.bus.emit(DomainEvent::SuppressionStarted {
+                kind: SuppressionKind::Snooze,
+                duration_ms: Some(minutes.saturating_mul(60_000)),
+            }),
+            ControlCommand::Focus(minutes) => self.toggle_focus(minutes),
+            ControlCommand::Resume => self.bus.emit(DomainEvent::SuppressionEnded),
+            ControlCommand::SinkStatus => {
+                let status = format!("sinks: {}\n{}\nreceipts: {} pending\n",
+                    self.sinks.names().join(", "),
+                    self.webhook_deliveries.stats().summary(),
+                    self.receipts.pending());
+                self.actions.reply(source, &status);
+                return false;
+            }
+            ControlCommand::QueueMove { id, queue_move } => {
+                return match self.notification_queue.reorder(&id, queue_move) {
+                    Ok(lane) => {
+                        self.audit.record(&id, self.clock.now_ms(), AuditEvent::QueueMoved { queue_move, lane });
+                        self.actions.reply(source, &format!("{} {} ({} lane)\n", id, queue_move.describe(), lane.name()));
+                        true
+                    }
+                    Err(e) => {
+                        self.actions.reply(source, &format!("error: {}\n", e));
+                        false
+                    }
+                };
+            }
+            ControlCommand::ThemeSet { field, value } => {
+                return match self.config.theme.set_color(&field, &value) {
+                    Ok(()) => {
+                        self.config_manager.set_override(&field, &value);
+                        self.rebuild_color_manager();
+                        self.actions.reply(source, &format!("{} = {}\n", field, self.config.theme.color(&field).unwrap_or_default()));
+                        true
+                    }
+                    Err(e) => {
+                        self.actions.reply(source, &format!("error: {}\n", e));
+                        false
+                    }
+                };
+            }
+            ControlCommand::ThemeDump => {
+                self.actions.reply(source, &self.config.theme.to_kdl());
+                return false;
+            }
+            ControlCommand::ConfigShow(prefix) => {
+                self.actions.reply(source, &format!("{}\n", self.config_manager.describe(&self.config, &prefix)));
+                return false;
+            }
+            ControlCommand::ConfigSet { key, value } => {
+                let previous = self.config_manager.set_override(&key, &value);
+                let resolved = self.config_manager.reload()
+                    .and_then(|config| config.validate().map(|()| config));
+                return match resolved {
+                    Ok(config) => {
+                        self.apply_config(config);
+                        self.actions.reply(source, &format!("{} = {}  (runtime)\n", key, value));
+                        true
+                    }
+                    Err(e) => {
+                        if let Some(previous) = previous {
+                            self.config_manager.set_override(&key, &previous);
+                        } else {
+                            self.config_manager.remove_override(&key);
+                        }
+                        let _ = self.config_manager.reload();
+                        self.actions.reply(source, &format!("error: {}\n", e));
+                        false
+                    }
+                };
+            }
+            ControlCommand::ConfigUnset(key) => {
+                if !self.config_manager.remove_override(&key) {
+                    self.actions.reply(source, &format!("no runtime override for {}\n", key));
+                    return false;
+                }
+                // Unsetting a muted source's rule unmutes it for good
+                if self.prefs.forget_mute_rule(&key) {
+                    self.save_prefs();
+                }
+                return match self.reload_config() {
+                    Ok(()) => {
+                        self.actions.reply(source, &format!("{} override removed\n", key));
+                        true
+                    }
+                    Err(e) => {
+                        self.actions.reply(source, &format!("error: {}\n", e));
+                        false
+                    }
+                };
+            }
+            ControlCommand::PrefsReset => {
+                let count = self.reset_prefs();
+                self.actions.reply(source, &format!("{} preference(s) reset\n", count));
+            }
+            ControlCommand::StateExport(path) => {
+                let export = self.export_state();
+                match export.save(&path) {
+                    Ok(()) => self.actions.reply(source, &format!("exported {} to {}\n", export.summary(), path)),
+                    Err(e) => self.actions.reply(source, &format!("error: {}\n", e)),
+                }
+                return false;
+            }
+            ControlCommand::StateImport(path) => {
+                return match StateExport::load(&path) {
+                    Ok(export) => {
+                        self.actions.reply(source, &format!("imported {} from {}\n", export.summary(), path));
+                        self.import_state(export);
+                        true
+                    }
+                    Err(e) => {
+                        self.actions.reply(source, &format!("error: {}\n", e));
+                        false
+                    }
+                };
+            }
+            ControlCommand::Set { setting, enabled } => {
+                return match self.config.set_accessibility(&setting, enabled) {
+                    Ok(()) => {
+                        self.config_manager.set_override(&setting, &enabled.to_string());
+                        self.apply_accessibility();
+                        self.actions.reply(source, &format!("{} = {}\n", setting, enabled));
+                        true
+                    }
+                    Err(e) => {
+                        self.actions.reply(source, &format!("error: {}\n", e));
+                        false
+                    }
+                };
+            }
+            ControlCommand::Health => {
+                let report = self.health_report();
+                self.actions.reply(source, &format!("{}\n", report.lines().join("\n")));
+                return false;
+            }
+            ControlCommand::Version => {
+                self.actions.reply(source, &format!("{}\n", banner::version_lines(&self.config_profile(), &self.permissions_line()).join("\n")));
+                return false;
+            }
+            ControlCommand::Features => {
+                self.actions.reply(source, &format!("{}\n", features::lines(&self.config).join("\n")));
+                return false;
+            }
+            ControlCommand::Lifecycle => {
+                self.actions.reply(source, &format!("{}\n", self.lifecycle.lines().join("\n")));
+                return false;
+            }
+            ControlCommand::Stats => {
+                let mut lines = self.frames.lines(self.config.tick_ms(), self.config.frame_time_warn_ms);
+                lines.extend(self.metrics.lines());
+                lines.push(self.latency.line());
+                lines.push(self.source_stats.line(self.clock.now_ms()));
+                lines.push(self.notification_queue.stats().line());
+                lines.push(self.memory_line());
+                lines.push(self.event_bridge.legacy_usage().line());
+                lines.extend(self.rate_history.lines(self.config.ascii_only != AsciiMode::On, self.clock.now_ms()));
+                lines.push(self.renderer.entry_cache().line());
+                lines.push(self.actions.line());
+                lines.push(self.artifacts.line());
+                lines.push(self.election.line());
+                if let Some(run) = self.stress.as_ref() {
+                    lines.push(run.line(self.clock.now_ms()));
+                }
+                self.actions.reply(source, &format!("{}\n", lines.join("\n")));
+                return false;
+            }
+            ControlCommand::Stress { count, rate } => {
+                let panes: Vec<u32> = self.world.panes().filter(|pane| !pane.is_plugin).map(|pane| pane.id).collect();
+                let run = StressRun::new(count, rate, panes, self.clock.now_ms());
+                log_info(&format!("Stress test started ({} notifications at {}/s)", count, rate));
+                self.actions.reply(source, &format!("{}\n", run.line(self.clock.now_ms())));
+                self.stress = Some(run);
+                return false;
+            }
+            ControlCommand::StressStop => {
+                let now = self.clock.now_ms();
+                let stopped = self.stress.as_mut().is_some_and(|run| run.stop(now));
+                self.actions.reply(source, if stopped { "stress test stopped\n" } else { "no stress test running\n" });
+                return false;
+            }
+            ControlCommand::Metrics => {
+                let unread = self.pane_states.values().filter(|s| s.is_unread()).count();
+                self.actions.reply(source, &self.metrics.exposition(&self.notification_queue.stats(), unread));
+                return false;
+            }
+            ControlCommand::Capabilities => {
+                let capabilities = Capabilities::new(ProtocolLimits {
+                    max_payload_bytes: match self.config.max_payload_bytes {
+                        0 => self.config.queue_max_bytes,
+                        max_bytes => max_bytes.min(self.config.queue_max_bytes),
+                    },
+                    queue_max_size: self.config.queue_max_size,
+                    history_max: self.config.history_max,
+                });
+                self.actions.reply(source, &format!("{}\n", serde_json::to_string(&capabilities).unwrap_or_default()));
+                return false;
+            }
+            ControlCommand::Timeline { session_id, json } => {
+                let now = self.clock.now_ms();
+                let text = match session_id {
+                    None => {
+                        let lines = self.timeline.summary_lines(now);
+                        if lines.is_empty() { "no sessions".to_string() } else { lines.join("\n") }
+                    }
+                    Some(session_id) if json => self.timeline.to_json(&session_id)
+                        .unwrap_or_else(|| format!("no timeline for session {}", session_id)),
+                    Some(session_id) => self.timeline.lines(&session_id, now)
+                        .map(|lines| lines.join("\n"))
+                        .unwrap_or_else(|| format!("no timeline for session {}", session_id)),
+                };
+                self.actions.reply(source, &format!("{}\n", text));
+                return false;
+            }
+            ControlCommand::RequestPermissions => self.request_permissions_again(),
+            ControlCommand::Slo => {
+                let lines = self.slo.lines(&self.config.slo);
+                self.actions.reply(source, &format!("{}\n", if lines.is_empty() { "no SLO targets".to_string() } else { lines.join("\n") }));
+                return false;
+            }
+            ControlCommand::Reminders => {
+                let now = self.clock.now_ms();
+                let text = if self.reminders.is_empty() {
+                    "no reminders".to_string()
+                } else {
+                    self.reminders.iter()
+                        .map(|r| format!("{}: {} (every {}s, next in {}s)",
+                            r.id, r.message, r.every_ms / 1000, r.next_due_ms.saturating_sub(now) / 1000))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                self.actions.reply(source, &format!("{}\n", text));
+                return false;
+            }
+            ControlCommand::Channels => {
+                let lines = self.channels.lines();
+                self.actions.reply(source, &format!("{}\n", if lines.is_empty() { "no channels".to_string() } else { lines.join("\n") }));
+                return false;
+            }
+            ControlCommand::Groups => {
+                self.actions.reply(source, &format!("{}\n", correlation::describe(&self.pane_states)));
+                return false;
+            }
+            ControlCommand::Sessions => {
+                self.actions.reply(source, &format!("{}\n", self.aggregate.describe(self.clock.now_ms())));
+                return false;
+            }
+            ControlCommand::Channel { name, visible } => return self.set_channel_visible(&name, visible),
+            ControlCommand::Simulate(event) => return self.simulate(event, source),
+            ControlCommand::BigMode(enabled) => {
+                self.config.big_mode = enabled.unwrap_or(!self.config.big_mode);
+                self.config_manager.set_override("big_mode", &self.config.big_mode.to_string());
+                self.rebuild_color_manager();
+                log_info(&format!("Big mode {}", if self.config.big_mode { "on" } else { "off" }));
+            }
+            ControlCommand::SoundTheme(None) => {
+                let names: Vec<String> = sound::theme_names(&self.config.sound_packs).into_iter()
+                    .map(|name| if name == self.config.sound_theme { format!("{} (active)", name) } else { name })
+                    .collect();
+                self.actions.reply(source, &format!("{}\n", names.join("\n")));
+                return false;
+            }
+            ControlCommand::SoundTheme(Some(name)) => {
+                if SoundTheme::find(&name, &self.config.sound_packs).is_none() {
+                    self.actions.reply(source, &format!("unknown sound theme: {}\n", name));
+                    return false;
+                }
+                self.config.sound_theme = name.clone();
+                self.config_manager.set_override("sound_theme", &name);
+                self.sinks = SinkPipeline::from_config(&self.config);
+                log_info(&format!("Sound theme {}", name));
+                self.actions.reply(source, &format!("sound_theme {}\n", name));
+            }
+            ControlCommand::CompactHistory(mode) => {
+                let mode = match (mode, self.config.history_compaction) {
+                    (Some(mode), _) => mode,
+                    (None, HistoryCompaction::Off) => HistoryCompaction::Exact,
+                    (None, configured) => configured,
+                };
+                let before = self.history.len();
+                let merged = self.compact_history(mode);
+                self.actions.reply(source, &format!("Compacted history ({}): {} entries merged, {} -> {}\n",
+                    mode.name(), merged, before, self.history.len()));
+            }
+            ControlCommand::Reply { number, pane_id } => {
+                let outcome = self.quick_reply(pane_id, number);
+                self.actions.reply(source, &format!("{}\n", outcome.unwrap_or_else(|e| e)));
+            }
+            ControlCommand::TextLog(enabled) => {
+                let enabled = enabled.unwrap_or(!self.config.text_log_enabled());
+                self.config.text_log = Some(enabled);
+                self.config_manager.set_override("text_log", &enabled.to_string());
+                log_info(&format!("Text log {}", if enabled { "on" } else { "off" }));
+                self.actions.reply(source, &format!("text_log {}\n", if enabled { "on" } else { "off" }));
+            }
+            ControlCommand::LowPower(enabled) => {
+                self.low_power_from_battery = false;
+                self.set_low_power(enabled.unwrap_or(!self.config.low_power));
+                self.actions.reply(source, &format!("low_power {}\n", if self.config.low_power { "on" } else { "off" }));
+            }
+            ControlCommand::Power(power_source) => {
+                match power_source {
+                    PowerSource::Battery if !self.config.low_power => {
+                        self.low_power_from_battery = true;
+                        self.set_low_power(true);
+                    }
+                    // Plugging in only undoes what the battery signal did
+                    PowerSource::Ac if self.low_power_from_battery => {
+                        self.low_power_from_battery = false;
+                        self.set_low_power(false);
+                    }
+                    _ => {}
+                }
+                self.actions.reply(source, &format!("power {}: low_power {}\n", power_source.name(),
+                    if self.config.low_power { "on" } else { "off" }));
+            }
+            ControlCommand::Surface { surface, enabled } => {
+                self.set_surface_enabled(surface, enabled);
+                self.actions.reply(source, &format!("{} {}\n", surface.name(),
+                    if self.surface_toggles.is_enabled(surface) { "on" } else { "off" }));
+            }
+            ControlCommand::Tutorial => self.show_tutorial = true,
+            ControlCommand::Answer(yes) => return self.answer_confirm(yes),
+            ControlCommand::Explain(id) => {
+                self.actions.reply(source, &self.explain(id));
+                return false;
+            }
+            ControlCommand::DebugPane(pane_id) => {
+                self.actions.reply(source, &self.debug_pane(pane_id));
+                return false;
+            }
+            ControlCommand::Replay(target) => {
+                self.actions.reply(source, &self.replay(&target));
+                return false;
+            }
+            ControlCommand::List(query) => {
+                let now = self.clock.now_ms();
+                let results = query.evaluate(&self.history, now);
+                if let PipeSource::Cli(pipe_id) = source {
+                    self.actions.cli_pipe_output(pipe_id, &query.format_results(&results, now));
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Issue webhook requests whose (re)try time has come
+    fn flush_webhook_deliveries(&mut self) {
+        for delivery in self.webhook_deliveries.due(self.clock.now_ms()) {
+            let mut headers = BTreeMap::new();
+            headers.insert("Content-Type".to_string(), "application/json".to_string());
+            let mut context = BTreeMap::new();
+            context.insert(DELIVERY_CONTEXT_KEY.to_string(), delivery.id.to_string());
+            self.actions.post(delivery.url, headers, delivery.body.into_bytes(), context);
+        }
+    }
+
+    /// Record the outcome of a webhook request
+    fn handle_web_request_result(&mut self, status: u16, body: &[u8], context: &BTreeMap<String, String>) {
+        let id = match context.get(DELIVERY_CONTEXT_KEY).and_then(|id| id.parse().ok()) {
+            Some(id) => id,
+            None => return,
+        };
+        let result = status_result(status);
+        if result.is_err() {
+            log_warn(&format!("Webhook delivery {} failed with HTTP {}: {}",
+                id, status, String::from_utf8_lossy(body)));
+        }
+        self.webhook_deliveries.complete(id, result, self.clock.now_ms());
+    }
+
+    /// Apply a synthetic pane event to the last host manifest and handle it like a real update
+    fn simulate(&mut self, event: SimulatedEvent, source: &PipeSource) -> bool {
+        let pane_id = match event {
+            SimulatedEvent::Focus(pane_id) | SimulatedEvent::PaneClose(pane_id) => pane_id,
+        };
+        let mut manifest = self.pane_manifest.clone();
+        let panes = match manifest.panes.values_mut().find(|panes| panes.iter().any(|pane| pane.id == pane_id)) {
+            Some(panes) => panes,
+            None => {
+                self.actions.reply(source, &format!("error: unknown pane {}\n", pane_id));
+                return false;
+            }
+        };
+
+        match event {
+            SimulatedEvent::Focus(_) => {
+                for pane in panes.iter_mut() {
+                    pane.is_focused = pane.id == pane_id;
+                }
+            }
+            SimulatedEvent::PaneClose(_) => panes.retain(|pane| pane.id != pane_id),
+        }
+        log_info(&format!("Simulating {:?}", event));
+        self.handle_pane_update(manifest)
+    }
+
+    /// Title watcher for the configured shells and thresholds
+    fn passive_detector(&self) -> PassiveDetector {
+        PassiveDetector::new(
+            self.config.passive_shells.clone(),
+            self.config.passive_min_duration_ms,
+            self.config.passive_max_title_changes,
+        )
+    }
+
+    /// Raise a completion notification for each terminal pane whose title reverted to its shell.
+    /// Claude panes are left out: their hooks report the same runs.
+    fn detect_title_completions(&mut self) {
+        let now = self.clock.now_ms();
+        let mut pane_ids = Vec::new();
+        let mut completions = Vec::new();
+        let patterns = &self.config.claude_pane_patterns;
+        for pane in self.world.panes().filter(|pane| !pane.is_plugin) {
+            if claude_panes::is_claude_pane(patterns, &pane.title, pane.command.as_deref()) {
+                continue;
+            }
+            pane_ids.push(pane.id);
+            completions.extend(self.passive.observe(pane.id, &pane.title, now));
+        }
+        self.passive.retain(&pane_ids);
+        for completion in completions {
+            log_info(&format!("Pane {} finished {:?} after {}ms", completion.pane_id, completion.command, completion.duration_ms));
+            let notification = completion.notification(&self.config.passive_template);
+            self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+    }
+
+    /// Show, hide or toggle (`None`) a channel, updating the entries displayed on it
+    fn set_channel_visible(&mut self, name: &str, visible: Option<bool>) -> bool {
+        let visible = match visible {
+            Some(visible) => {
+                self.channels.set_visible(name, visible);
+                visible
+            }
+            None => self.channels.toggle(name),
+        };
+
+        for state in self.pane_states.values_mut().filter(|s| s.channel.as_deref() == Some(name)) {
+            state.hidden = !visible;
+        }
+        log_info(&format!("Channel {} {}", name, if visible { "shown" } else { "hidden" }));
+        true
+    }
+
+    /// Details of a notification (the most recent if no ID): its audit trail, what its exit code
+    /// means and the pane lines captured with it
+    fn explain(&self, id: Option<String>) -> String {
+        let id = id.or_else(|| self.audit.latest().map(|id| id.to_string()));
+        let mut text = id.as_deref()
+            .and_then(|id| self.audit.explain(id))
+            .unwrap_or_else(|| format!("no audit trail for {}", id.as_deref().unwrap_or("any notification")));
+        let entry = id.as_deref().and_then(|id| self.history.get(id));
+        if let Some(metadata) = entry.map(|entry| &entry.notification.metadata) {
+            if let Some(code) = metadata.exit_code {
+                text = format!("{}\n{} exited with {}\n",
+                    text.trim_end(),
+                    metadata.command.as_deref().unwrap_or("command"),
+                    exitcode::describe(code, &self.config.exit_codes));
+            }
+        }
+        if let Some(lines) = entry.and_then(|entry| entry.snapshot.as_ref()) {
+            text = format!("{}\nlast lines of pane:\n{}\n", text.trim_end(), lines.iter()
+                .map(|line| format!("  | {}", line))
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+        text
+    }
+
+    /// Dump a pane's recent state transitions, visual state and queued notifications
+    fn debug_pane(&self, pane_id: u32) -> String {
+        let now = self.clock.now_ms();
+        let mut lines = vec![format!("Pane {}:", pane_id)];
+        match self.pane_states.get(&pane_id) {
+            Some(state) => lines.extend(state.debug_lines().into_iter().map(|line| format!("  {}", line))),
+            None => lines.push("  no visual state".to_string()),
+        }
+        if let Some(notification) = self.displayed.get(&pane_id) {
+            lines.push(format!("  displayed: {} {} from {}", notification.id, notification.notification_type.name(), notification.source));
+        }
+
+        let transitions = self.state_manager.recent_transitions(pane_id, self.config.transition_history_max);
+        lines.push(format!("Transitions ({}, oldest first):", transitions.len()));
+        for transition in transitions {
+            lines.push(format!("  {} ago: {} -> {} ({})",
+                format_age(now.saturating_sub(transition.timestamp)),
+                transition.from.display_name(),
+                transition.to.display_name(),
+                transition.reason));
+        }
+
+        let queued = self.notification_queue.get_for_pane(pane_id);
+        lines.push(format!("Queued ({}):", queued.len()));
+        for notification in queued {
+            lines.push(format!("  {} {} ({} priority) from {}: {}",
+                notification.id,
+                notification.notification_type.name(),
+                notification.priority.name(),
+                notification.source,
+                notification.display_text()));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Replay history entries against the current configuration
+    fn replay(&self, target: &ReplayTarget) -> String {
+        replay::replay(&target.select(self.history.newest_first()), &self.config)
+    }
+
+    /// The noisiest source above the threshold right now
+    fn noisy_source(&self) -> Option<NoisySource> {
+        self.source_stats.noisy(self.config.noisy_source_threshold, self.clock.now_ms()).into_iter().next()
+    }
+
+    /// Apply the noisy-source suggestion: a runtime rule dropping the source's notifications
+    fn mute_noisy_source(&mut self) -> bool {
+        let Some(noisy) = self.noisy_source() else {
+            return false;
+        };
+        let Some(spec) = noisy.rule_spec() else {
+            log_warn(&format!("Source {} can't be muted with a rule", noisy.source));
+            return false;
+        };
+        let key = format!("rule_{}", noisy.rule_name());
+        self.config_manager.set_override(&key, &spec);
+        match self.config_manager.reload().and_then(|config| config.validate().map(|()| config)) {
+            Ok(config) => {
+                self.apply_config(config);
+                self.source_stats.forget(&noisy.source);
+                self.prefs.muted_sources.insert(noisy.source.clone());
+                self.save_prefs();
+                log_info(&format!("Muted noisy source {} ({} = {})", noisy.source, key, spec));
+                true
+            }
+            Err(e) => {
+                self.config_manager.remove_override(&key);
+                let _ = self.config_manager.reload();
+                log_warn(&format!("Failed to mute source {}: {}", noisy.source, e));
+                false
+            }
+        }
+    }
+
+    /// Stats line of the memory caps, e.g.
+    /// `memory: queue 3/64 KiB, 2 evicted; history 500/500, 41 evicted; transitions 7 evicted`
+    fn memory_line(&self) -> String {
+        let queue = self.notification_queue.stats();
+        format!("memory: queue {}/{} KiB, {} evicted; history {}/{}, {} evicted; transitions {} evicted",
+            queue.queued_bytes / 1024,
+            queue.max_bytes / 1024,
+            queue.total_evicted,
+            self.history.len(),
+            self.history.max_entries(),
+            self.history.evicted_count(),
+            self.state_manager.evicted_count())
+    }
+
+    /// Whether this instance only renders: a mirror, or on standby while another instance is primary
+    fn render_only(&self) -> bool {
+        self.config.mirror || self.election.role() == Role::Standby
+    }
+
+    /// Take part in the election unless switched off or a mirror
+    fn sync_election_membership(&mut self) {
+        let before = self.election.role();
+        match self.own_pane_id {
+            Some(own_id) if self.config.instance_election && !self.config.mirror => self.election.join(own_id),
+            _ => self.election.leave(),
+        }
+        self.apply_election_role(before);
+    }
+
+    /// Send the announcement when due and forget instances gone silent
+    fn run_election(&mut self) -> bool {
+        let before = self.election.role();
+        self.election.expire(self.clock.now_ms());
+        if let Some(payload) = self.election.heartbeat(self.clock.now_ms()) {
+            self.actions.announce(MessageToPlugin::new(ELECTION_PIPE).with_payload(payload));
+        }
+        self.apply_election_role(before)
+    }
+
+    /// Record another instance's announcement, answering a newcomer right away
+    fn handle_election_message(&mut self, pipe_message: PipeMessage) -> bool {
+        let before = self.election.role();
+        let answer = pipe_message.payload.as_deref().and_then(|payload| self.election.observe(payload, self.clock.now_ms()));
+        if let Some(payload) = answer {
+            self.actions.announce(MessageToPlugin::new(ELECTION_PIPE).with_payload(payload));
+        }
+        self.apply_election_role(before)
+    }
+
+    /// Follow a role change (standby makes no calls to Zellij), returning whether it changed
+    fn apply_election_role(&mut self, before: Role) -> bool {
+        let role = self.election.role();
+        if role == before {
+            return false;
+        }
+        self.actions.set_read_only(self.render_only());
+        self.run_lifecycle(if role == Role::Standby { Phase::Suspend } else { Phase::Resume });
+        log_info(&format!("Instance role changed to {} ({})", role.name(), self.election.line()));
+        true
+    }
+
+    /// Take the subsystems with state of their own through a lifecycle phase. A subsystem that
+    /// fails is logged and left out of the later phases; the plugin carries on without it.
+    fn run_lifecycle(&mut self, phase: Phase) {
+        let context = LifecycleContext { now_ms: self.clock.now_ms(), persist: !self.config.mirror };
+        let mut subsystems: [&mut dyn Subsystem; 5] = [
+            &mut self.prefs,
+            &mut self.reminders,
+            &mut self.muted_panes,
+            &mut self.metrics,
+            &mut self.updates,
+        ];
+        for (name, error) in self.lifecycle.run(phase, &mut subsystems, &context) {
+            log_warn(&format!("Subsystem {} failed in {}, continuing without it: {}", name, phase.name(), error));
+        }
+    }
+
+    /// Handle the JSON lines appended to `ingest_file` since the last read, when a read is due
+    fn poll_ingest_file(&mut self) -> bool {
+        // A mirror follows broadcasts only
+        let Some(path) = self.config.ingest_file.clone().filter(|_| !self.config.mirror) else {
+            self.ingest = None;
+            return false;
+        };
+        if self.ingest.as_ref().is_none_or(|tail| tail.path() != path) {
+            self.ingest = Some(FileTail::load(&path, INGEST_STATE_PATH));
+        }
+        let now = self.clock.now_ms();
+        let Some(tail) = self.ingest.as_mut() else { return false };
+        if !tail.due(self.config.ingest_file_poll_ms, now) {
+            return false;
+        }
+        let lines = match tail.read() {
+            Ok(lines) => lines,
+            Err(e) => {
+                log_warn(&format!("Ingest file: {}", e));
+                return false;
+            }
+        };
+        if let Err(e) = tail.save(INGEST_STATE_PATH) {
+            log_warn(&format!("Failed to persist the ingest file offset: {}", e));
+        }
+        let mut needs_render = false;
+        for line in lines {
+            let Ok(line) = self.limit_payload(line) else { continue };
+            needs_render |= self.handle_notification_message(&line, None);
+        }
+        needs_render
+    }
+
+    /// Persist the lifetime usage counters for the next plugin instance
+    fn save_metrics(&mut self) {
+        // A mirror counts what the other instances count already; failed counters are not trusted
+        if self.config.mirror || self.lifecycle.is_failed(self.metrics.name()) {
+            return;
+        }
+        if let Err(e) = self.metrics.save(METRICS_STATE_PATH, self.clock.now_ms()) {
+            log_warn(&format!("Failed to persist usage counters: {}", e));
+        }
+    }
+
+    /// Re-apply the persisted DND state and pinned notifications
+    fn restore_prefs(&mut self) {
+        if self.prefs.dnd {
+            self.suppression.start_dnd(self.clock.now_ms(), None);
+        }
+        for notification in self.prefs.pinned.clone() {
+            self.restore_displayed(notification, true);
+        }
+    }
+
+    /// Show a notification from an earlier plugin instance on its pane again
+    fn restore_displayed(&mut self, notification: Notification, pinned: bool) {
+        let Some(pane_id) = notification.pane_id else {
+            return;
+        };
+        self.update_pane_visual_state(pane_id, &notification);
+        self.displayed.insert(pane_id, notification);
+        if pinned {
+            self.set_pinned(pane_id, true);
+        }
+    }
+
+    /// Capture the notification context for `state_export`
+    fn export_state(&self) -> StateExport {
+        let prefs = self.current_prefs();
+        let displayed = self.displayed.values()
+            .filter(|notification| !prefs.pinned.iter().any(|pinned| pinned.id == notification.id))
+            .cloned()
+            .collect();
+        let queued = self.notification_queue.all().into_iter().cloned().collect();
+        StateExport::capture(displayed, queued, &self.history, prefs, &self.aggregate, self.clock.now_ms())
+    }
+
+    /// Load a `state_export` file: history and forwarded notifications are merged into the
+    /// current ones, notifications shown again, and the preferences applied on top of the current
+    /// ones (and persisted)
+    fn import_state(&mut self, export: StateExport) {
+        let now = self.clock.now_ms();
+        for entry in export.history_entries(now) {
+            self.history.restore(entry);
+        }
+        for (session, entry) in export.remote_entries(now) {
+            self.aggregate.restore(&session, entry);
+        }
+        for notification in export.queued {
+            self.notification_queue.enqueue(notification);
+        }
+        for notification in export.displayed {
+            self.restore_displayed(notification, false);
+        }
+
+        let prefs = export.prefs;
+        let mute_rules = prefs.mute_rules();
+        for (key, spec) in &mute_rules {
+            self.config_manager.set_override(key, spec);
+        }
+        if !mute_rules.is_empty() {
+            self.prefs.muted_sources.extend(prefs.muted_sources.iter().cloned());
+            if let Err(e) = self.reload_config() {
+                log_warn(&format!("Failed to reload configuration: {}", e));
+            }
+        }
+        for surface in prefs.surfaces_off() {
+            self.set_surface_enabled(surface, Some(false));
+        }
+        if prefs.dnd && !self.suppression.is_open_ended_dnd() {
+            self.suppression.start_dnd(now, None);
+        }
+        for notification in prefs.pinned {
+            self.restore_displayed(notification, true);
+        }
+        self.sync_prefs();
+        log_info("Imported plugin state");
+    }
+
+    /// Preferences as they currently are (muted sources are tracked as they change)
+    fn current_prefs(&self) -> Preferences {
+        Preferences {
+            dnd: self.suppression.is_open_ended_dnd(),
+            muted_sources: self.prefs.muted_sources.clone(),
+            disabled_surfaces: self.surface_toggles.disabled_names().iter().map(|name| name.to_string()).collect(),
+            pinned: self.displayed.iter()
+                .filter(|(pane_id, _)| self.pane_states.get(pane_id).is_some_and(|s| s.pinned))
+                .map(|(_, notification)| notification.clone())
+                .collect(),
+        }
+    }
+
+    /// Persist the preferences if they changed
+    fn sync_prefs(&mut self) {
+        let current = self.current_prefs();
+        if current != self.prefs {
+            self.prefs = current;
+            self.save_prefs();
+        }
+    }
+
+    /// Persist the preferences for the next plugin instance
+    fn save_prefs(&self) {
+        // Preferences that failed to load would overwrite the file with defaults
+        if self.config.mirror || self.lifecycle.is_failed(self.prefs.name()) {
+            return;
+        }
+        if let Err(e) = self.prefs.save(PREFS_STATE_PATH) {
+            log_warn(&format!("Failed to persist preferences: {}", e));
+        }
+    }
+
+    /// Drop the persisted preferences and return what they cover to the configuration, returning
+    /// how many preferences were reset
+    fn reset_prefs(&mut self) -> usize {
+        let prefs = std::mem::take(&mut self.prefs);
+        if let Err(e) = Preferences::remove(PREFS_STATE_PATH) {
+            log_warn(&format!("Failed to remove preferences: {}", e));
+        }
+        if prefs.dnd {
+            self.bus.emit(DomainEvent::SuppressionEnded);
+        }
+        for surface in prefs.surfaces_off() {
+            self.set_surface_enabled(surface, Some(true));
+        }
+        let mut removed = false;
+        for (key, _) in prefs.mute_rules() {
+            removed |= self.config_manager.remove_override(&key);
+        }
+        if removed {
+            if let Err(e) = self.reload_config() {
+                log_warn(&format!("Failed to reload configuration: {}", e));
+            }
+        }
+        for notification in &prefs.pinned {
+            if let Some(pane_id) = notification.pane_id {
+                self.set_pinned(pane_id, false);
+            }
+        }
+        log_info("Preferences reset to the configuration");
+        usize::from(prefs.dnd) + prefs.muted_sources.len() + prefs.disabled_surfaces.len() + prefs.pinned.len()
+    }
+
+    /// Persist the reminders for the next plugin instance
+    fn save_reminders(&self) {
+        if self.config.mirror || self.lifecycle.is_failed(self.reminders.name()) {
+            return;
+        }
+        if let Err(e) = self.reminders.save(REMINDER_STATE_PATH) {
+            log_warn(&format!("Failed to persist reminders: {}", e));
+        }
+    }
+
+    /// Mute or unmute a pane, clearing its visuals and persisting the mute list
+    fn set_pane_muted(&mut self, pane_id: u32, muted: bool) -> bool {
+        let changed = if muted {
+            self.muted_panes.mute(pane_id)
+        } else {
+            self.muted_panes.unmute(pane_id)
+        };
+
+        if muted {
+            self.clear_visual_state(pane_id);
+            self.displayed.remove(&pane_id);
+        }
+        self.pane_states.entry(pane_id).or_default().muted = muted;
+
+        if changed {
+            // A mute list that failed to load is kept in memory only, not written over the file
+            if !self.lifecycle.is_failed(self.muted_panes.name()) {
+                if let Err(e) = self.muted_panes.save(MUTE_STATE_PATH) {
+                    log_warn(&format!("Failed to persist muted panes: {}", e));
+                }
+            }
+            log_info(&format!("Pane {} {}", pane_id, if muted { "muted" } else { "unmuted" }));
+        }
+        true
+    }
+
+    /// Handle notification messages from IPC
+    fn handle_notification_message(&mut self, payload: &str, source: Option<&PipeSource>) -> bool {
+        let parsed = self.event_bridge.parse_message(payload);
+        self.log_deprecations();
+        match parsed {
+            Ok(message) => match source {
+                Some(source) => self.handle_pipe_notification(message, source),
+                None => self.handle_bridge_message(message),
+            },
+            Err(e) => {
+                log_warn(&format!("Failed to parse notification: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Log the deprecation warnings for legacy payloads the event bridge has read (once per format)
+    fn log_deprecations(&mut self) {
+        for warning in self.event_bridge.take_deprecation_warnings() {
+            log_warn(&warning);
+        }
+    }
+
+    /// Handle a protocol message from a pipe, keeping a CLI pipe open for requested receipts
+    fn handle_pipe_notification(&mut self, mut message: BridgeMessage, source: &PipeSource) -> bool {
+        if let (BridgeMessage::Confirm(request), PipeSource::Cli(pipe_id)) = (&mut message, source) {
+            // Held open until the question is answered
+            self.actions.block_cli_pipe_input(pipe_id);
+            request.pipe_id = Some(pipe_id.clone());
+        }
+        if let BridgeMessage::Notify(ref notification) = message {
+            if notification.request_ack {
+                let pipe_id = match source {
+                    PipeSource::Cli(pipe_id) => {
+                        self.actions.block_cli_pipe_input(pipe_id);
+                        Some(pipe_id.clone())
+                    }
+                    _ => None,
+                };
+                self.receipts.subscribe(&notification.id, pipe_id);
+            }
+        }
+        self.handle_bridge_message(message)
+    }
+
+    /// Send a delivery receipt if the notification's sender asked for them (`last`: nothing
+    /// follows this transition, so the CLI pipe is released)
+    fn send_receipt(&mut self, id: &str, status: DeliveryStatus, last: bool) {
+        let Some(receipt) = self.receipts.update(id, status, last) else { return };
+        if let Some(ref pipe_id) = receipt.pipe_id {
+            self.actions.cli_pipe_output(pipe_id, &receipt.line);
+            if receipt.last {
+                self.actions.unblock_cli_pipe_input(pipe_id);
+            }
+        }
+        log_info(&format!("Receipt: {}", receipt.line.trim_end()));
+    }
+
+    /// Answer the sender question shown (`None`: its default)
+    fn answer_confirm(&mut self, yes: Option<bool>) -> bool {
+        match self.confirms.answer(yes, self.clock.now_ms()) {
+            Some(answer) => {
+                self.send_confirm_answer(answer);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Write a question's answer to the sender's CLI pipe and release it
+    fn send_confirm_answer(&mut self, answer: ConfirmAnswer) {
+        if let Some(ref pipe_id) = answer.pipe_id {
+            self.actions.cli_pipe_output(pipe_id, &answer.line());
+            self.actions.unblock_cli_pipe_input(pipe_id);
+        }
+        log_info(&format!("Answer: {}", answer.line().trim_end()));
+    }
+
+    /// Emit the domain event for a parsed protocol message
+    fn handle_bridge_message(&mut self, message: BridgeMessage) -> bool {
+        match message {
+            BridgeMessage::Notify(mut notification) => {
+                if let Some(origin) = notification.origin_session.clone() {
+                    if self.config.aggregator {
+                        // Kept apart from this session's panes, grouped by origin
+                        self.aggregate.record(&origin, &notification, self.clock.now_ms());
+                        return true;
+                    }
+                    // Pane IDs of another session mean nothing here
+                    notification.pane_id = None;
+                }
+                if self.charset.observe(&notification.message) && self.config.ascii_only == AsciiMode::Auto {
+                    log_warn("Notification text arrives garbled, drawing with ASCII glyphs");
+                    self.rebuild_renderer();
+                }
+                // Sessions are timed by arrival, even if the notification is held back or dropped
+                if let Some(ref session_id) = notification.session_id {
+                    self.timeline.record_notification(
+                        session_id, notification.pane_id, notification.reason, self.clock.now_ms());
+                }
+                // Numbered notifications may wait for a missing predecessor
+                for notification in self.sequencer.accept(*notification, self.clock.now_ms()) {
+                    self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+                }
+            }
+            BridgeMessage::Cancel { id, pane_id } => {
+                self.bus.emit(DomainEvent::NotificationCancelled { id, pane_id });
+            }
+            BridgeMessage::Remind(reminder) => {
+                self.bus.emit(DomainEvent::ReminderScheduled(reminder));
+            }
+            BridgeMessage::Confirm(request) => {
+                self.bus.emit(DomainEvent::ConfirmRequested(request));
+            }
+            BridgeMessage::Session { session_id, pane_id, hook } => {
+                self.bus.emit(DomainEvent::SessionHookReceived { session_id, pane_id, hook });
+            }
+        }
+        true
+    }
+
+    /// Withdraw a notification by ID, or all notifications for a pane
+    fn cancel_notification(&mut self, id: Option<&str>, pane_id: Option<u32>) -> bool {
+        let matches = |n: &Notification| {
+            id.map(|id| n.id == id).unwrap_or(false) || (pane_id.is_some() && n.pane_id == pane_id)
+        };
+
+        let mut cancelled = false;
+        if let Some(id) = id {
+            self.send_receipt(id, DeliveryStatus::Cancelled, true);
+            cancelled |= self.notification_queue.remove_by_id(id);
+            if self.reminders.cancel(id) {
+                self.save_reminders();
+                cancelled = true;
+            }
+            cancelled |= self.aggregate.withdraw(id);
+        }
+        if let Some(pane_id) = pane_id {
+            let before = self.notification_queue.len();
+            self.notification_queue.remove_for_pane(pane_id);
+            cancelled |= self.notification_queue.len() < before;
+        }
+
+        cancelled |= !self.state_manager.take_waiting(&matches).is_empty();
+        let deferred_before = self.deferred_notifications.len();
+        self.deferred_notifications.retain(|n| !matches(n));
+        cancelled |= self.deferred_notifications.len() < deferred_before;
+        cancelled |= self.pending_panes.cancel(&matches);
+
+        let displayed: Vec<u32> = self.displayed.iter()
+            .filter(|(_, n)| matches(n))
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        for pane_id in displayed {
+            if let Some(notification) = self.displayed.remove(&pane_id) {
+                self.send_receipt(&notification.id, DeliveryStatus::Cancelled, true);
+            }
+            self.clear_visual_state(pane_id);
+            cancelled = true;
+        }
+
+        if !cancelled {
+            log_info(&format!("Cancel matched nothing (id={:?}, pane_id={:?})", id, pane_id));
+        }
+        cancelled
+    }
+
+    /// Dump the screen of an Error notification's pane, if it is focused, for its history entry
+    fn capture_snapshot(&mut self, notification: &Notification) {
+        let Some(pane_id) = notification.pane_id else { return };
+        if self.config.capture_error_lines == 0
+            || notification.notification_type != NotificationType::Error
+            || self.plugin_state != PluginState::Running
+            || !self.config.host_platform.has_shell()
+        {
+            return;
+        }
+        // dump-screen only reaches the focused pane
+        if self.world.focused_pane() != Some(pane_id) {
+            log_info(&format!("Not capturing pane {}: it is not focused", pane_id));
+            return;
+        }
+        let command = snapshot::capture_command(&notification.id, &self.config.temp_dir);
+        let mut context = BTreeMap::new();
+        context.insert(SNAPSHOT_CONTEXT_KEY.to_string(), notification.id.clone());
+        self.actions.run_command(&command, context);
+    }
+
+    /// Run the configured host checks that are due
+    fn run_due_checks(&mut self) {
+        if self.config.checks.is_empty()
+            || self.plugin_state != PluginState::Running
+            || !self.config.host_platform.has_shell()
+        {
+            return;
+        }
+        for check in self.checks.due(&self.config.checks, self.clock.now_ms()) {
+            let command = check.command_line();
+            let mut context = BTreeMap::new();
+            context.insert(CHECK_CONTEXT_KEY.to_string(), check.name.clone());
+            self.actions.run_command(&command, context);
+        }
+    }
+
+    /// Ask for the latest release when `update_check` is on and a day has passed since the last
+    /// check (only the primary instance asks)
+    fn check_for_update(&mut self) {
+        if !self.config.update_check
+            || self.plugin_state != PluginState::Running
+            || !self.config.host_platform.has_shell()
+            || self.render_only()
+        {
+            return;
+        }
+        let Some(unix_ms) = self.clock.unix_ms().filter(|unix_ms| self.updates.due(*unix_ms)) else { return };
+        let command = update_check::command(self.config.update_check_helper.as_deref());
+        let mut context = BTreeMap::new();
+        context.insert(UPDATE_CONTEXT_KEY.to_string(), String::new());
+        if self.actions.run_command(&command, context) {
+            self.updates.start(unix_ms);
+        }
+    }
+
+    /// Note the latest release; a newer one is logged and shown in the diagnostics view
+    fn handle_update_check_result(&mut self, exit_code: Option<i32>, stdout: &[u8], stderr: &[u8]) {
+        let release = if exit_code == Some(0) { Release::parse(stdout) } else { None };
+        if release.is_none() {
+            log_warn(&format!("Update check failed, trying again tomorrow: {}", String::from_utf8_lossy(stderr).trim()));
+        }
+        if self.updates.finish(release) {
+            log_info(&self.updates.line().unwrap_or_default());
+        }
+        self.save_update_check();
+    }
+
+    /// Persist the update check state, so a reload or crash doesn't check again within the day
+    fn save_update_check(&self) {
+        if self.config.mirror || self.lifecycle.is_failed(self.updates.name()) {
+            return;
+        }
+        if let Err(e) = self.updates.save(UPDATE_STATE_PATH) {
+            log_warn(&format!("Failed to persist the update check: {}", e));
+        }
+    }
+
+    /// Raise a check's notification when its result meets the condition
+    fn handle_check_result(&mut self, name: &str, exit_code: Option<i32>, stdout: &[u8]) {
+        self.checks.finish(name);
+        let Some(check) = self.config.checks.iter().find(|check| check.name == name) else {
+            log_info(&format!("Result for removed check {}", name));
+            return;
+        };
+        if check.triggers(exit_code, stdout) {
+            log_info(&format!("Check {} triggered (exit code {:?})", name, exit_code));
+            self.bus.emit(DomainEvent::NotificationArrived(Box::new(check.notification())));
+        }
+    }
+
+    /// Handle command results: check runs, forwarding to another session, and pane snapshots
+    /// attached to their notification's history entry
+    fn handle_run_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stdout: &[u8],
+        stderr: &[u8],
+        context: &BTreeMap<String, String>,
+    ) {
+        if let Some(name) = context.get(CHECK_CONTEXT_KEY) {
+            self.handle_check_result(name, exit_code, stdout);
+            return;
+        }
+        if context.contains_key(UPDATE_CONTEXT_KEY) {
+            self.handle_update_check_result(exit_code, stdout, stderr);
+            return;
+        }
+        if context.contains_key(SOUND_CONTEXT_KEY) {
+            if exit_code != Some(0) {
+                log_warn(&format!("Sound player failed: {}", String::from_utf8_lossy(stderr).trim()));
+            }
+            return;
+        }
+        if let Some(id) = context.get(DAEMON_CONTEXT_KEY) {
+            let showing = context.get(DAEMON_OP_CONTEXT_KEY).is_some_and(|op| op == "show");
+            self.handle_desktop_daemon_result(id, showing, exit_code, stdout, stderr);
+            return;
+        }
+        if let Some(session) = context.get(FORWARD_CONTEXT_KEY) {
+            if exit_code != Some(0) {
+                log_warn(&format!("Forwarding to session {} failed: {}", session, String::from_utf8_lossy(stderr).trim()));
+            }
+            return;
+        }
+        let Some(id) = context.get(SNAPSHOT_CONTEXT_KEY) else { return };
+        if exit_code != Some(0) {
+            log_warn(&format!("Pane snapshot for {} failed: {}", id, String::from_utf8_lossy(stderr).trim()));
+            return;
+        }
+        let lines = snapshot::last_lines(stdout, self.config.capture_error_lines);
+        if !self.history.attach_snapshot(id, lines) {
+            log_info(&format!("Pane snapshot for {} arrived after it left the history", id));
+        }
+    }
+
+    /// Acknowledge a notification whose desktop copy the user dismissed or clicked (the helper's
+    /// `show` run ends when the desktop copy is closed)
+    fn handle_desktop_daemon_result(&mut self, id: &str, showing: bool, exit_code: Option<i32>, stdout: &[u8], stderr: &[u8]) {
+        if exit_code != Some(0) {
+            log_warn(&format!("Desktop daemon helper failed for {}: {}", id, String::from_utf8_lossy(stderr).trim()));
+            return;
+        }
+        let outcome = DesktopOutcome::parse(stdout);
+        if !showing || !outcome.acknowledges() {
+            return;
+        }
+        log_info(&format!("Desktop copy of {} closed by the user, acknowledging it in the terminal", id));
+        self.bus.emit(DomainEvent::NotificationAcknowledged(id.to_string()));
+    }
+
+    /// Start focus mode (`focus_minutes` unless given), or end it early when started without a
+    /// duration while it is on
+    fn toggle_focus(&mut self, minutes: Option<u64>) {
+        if minutes.is_none() && self.suppression.kind() == Some(SuppressionKind::Focus) {
+            self.bus.emit(DomainEvent::SuppressionEnded);
+            return;
+        }
+        self.bus.emit(DomainEvent::SuppressionStarted {
+            kind: SuppressionKind::Focus,
+            duration_ms: Some(minutes.unwrap_or(self.config.focus_minutes).saturating_mul(60_000)),
+        });
+    }
+
+    /// Present what arrived during a focus period: the digest in the status line, the newest
+    /// notification per pane or tab shown, the older ones only kept in the history
+    fn end_focus(&mut self, held: Vec<Notification>) {
+        let digest = FocusDigest::new(held);
+        log_info(&digest.summary);
+        self.status_message = Some((digest.summary, self.clock.now_ms() + FOCUS_DIGEST_MS));
+        for notification in &digest.superseded {
+            self.send_receipt(&notification.id, DeliveryStatus::Replaced, true);
+        }
+        self.release_held(digest.latest);
+    }
+
+    /// Re-emit notifications held during DND/snooze
+    fn release_held(&mut self, held: Vec<Notification>) {
+        if !held.is_empty() {
+            log_info(&format!("Releasing {} held notifications", held.len()));
+        }
+        for notification in held {
+            self.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+    }
+
+    /// Apply `missing_pane` to a notification for a pane the layout does not have. Returns the
+    /// notification when it goes on: its pane exists, the layout is not known yet, or it was moved
+    /// to the tab level.
+    fn resolve_missing_pane(&mut self, mut notification: Notification) -> Option<Notification> {
+        let Some(pane_id) = notification.pane_id else { return Some(notification) };
+        // Before the first pane update every pane looks missing
+        if self.world.pane(pane_id).is_some() || self.world.panes().next().is_none() {
+            return Some(notification);
+        }
+        let now = self.clock.now_ms();
+        match self.config.missing_pane {
+            MissingPane::Drop => {
+                self.drop_missing_pane(&notification);
+                None
+            }
+            MissingPane::Tab => {
+                notification.pane_id = None;
+                if notification.tab_index.is_none() {
+                    notification.tab_index = self.world.active_tab().map(|tab| tab.position);
+                }
+                self.audit.record(&notification.id, now, AuditEvent::MissingPane { pane_id, outcome: "moved to the tab level" });
+                Some(notification)
+            }
+            MissingPane::Hold => {
+                self.audit.record(&notification.id, now, AuditEvent::MissingPane { pane_id, outcome: "held until it appears" });
+                if let Some(evicted) = self.pending_panes.hold(notification, now) {
+                    self.drop_missing_pane(&evicted);
+                }
+                None
+            }
+        }
+    }
+
+    /// Drop a notification for a pane the layout does not have, with a warning
+    fn drop_missing_pane(&mut self, notification: &Notification) {
+        let pane_id = notification.pane_id.unwrap_or_default();
+        log_warn(&format!("Dropping notification {}: pane {} does not exist", notification.id, pane_id));
+        self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::MissingPane { pane_id, outcome: "dropped" });
+        self.send_receipt(&notification.id, DeliveryStatus::Dropped, true);
+    }
+
+    /// Replace the context variables (`{session}`, `{tab_name}`, `{pane_title}`, `{time}`) in a
+    /// notification's title and message
+    fn expand_template_variables(&self, notification: &mut Notification) {
+        if !notification.message.contains('{') && !notification.title.as_ref().is_some_and(|title| title.contains('{')) {
+            return;
+        }
+        let pane = notification.pane_id.and_then(|pane_id| self.world.pane(pane_id));
+        let context = TemplateContext {
+            session: self.mode_info.session_name.clone(),
+            tab_name: pane
+                .and_then(|pane| self.world.tabs().find(|tab| tab.position == pane.tab_position))
+                .map(|tab| tab.name.clone()),
+            pane_title: pane.map(|pane| pane.title.clone()),
+            unix_ms: self.clock.unix_ms(),
+        };
+        notification.message = context.expand(&notification.message);
+        notification.title = notification.title.as_deref().map(|title| context.expand(title));
+    }
+
+    /// Queue a notification for display
+    fn queue_notification(&mut self, notification: Notification) {
+        let Some(mut notification) = self.resolve_missing_pane(notification) else { return };
+        let now = self.clock.now_ms();
+        // Sender timestamps are Unix times
+        if let Some(unix_ms) = self.clock.unix_ms().filter(|_| notification.timestamp > 0) {
+            self.latency.arrived(&notification.id, notification.timestamp, unix_ms);
+        }
+        if let Some(reason) = notification.reason {
+            notification.priority = self.config.attention.get(reason).priority;
+        }
+        self.history.record(&notification, now);
+        self.metrics.record_notification(&notification);
+        self.audit.record(&notification.id, now, AuditEvent::Received {
+            source: notification.source.clone(),
+            notification_type: notification.notification_type.name().to_string(),
+            priority: notification.priority,
+        });
+
+        // User rules (only recorded in the audit trail in dry-run mode)
+        let dry_run = self.config.rules_dry_run;
+        let outcomes = rules::apply_rules(&self.config.rules, &mut notification, dry_run);
+        for outcome in &outcomes {
+            self.audit.record(&notification.id, now, AuditEvent::Rule {
+                name: outcome.rule.clone(),
+                actions: outcome.actions.iter().map(|action| action.describe()).collect::<Vec<_>>().join(", "),
+                dry_run,
+            });
+        }
+        if !dry_run && outcomes.iter().any(|outcome| outcome.drops()) {
+            self.send_receipt(&notification.id, DeliveryStatus::Dropped, true);
+            return;
+        }
+
+        // Count what gets through per source; warn once a source crosses the threshold
+        self.source_stats.record(&notification.source, now);
+        self.rate_history.record(&notification.notification_type, now);
+        let threshold = self.config.noisy_source_threshold;
+        if let Some(noisy) = self.source_stats.noisy(threshold, now).iter().find(|noisy| noisy.source == notification.source) {
+            if noisy.per_minute == threshold + 1 {
+                log_warn(&noisy.suggestion());
+            }
+        }
+
+        self.capture_snapshot(&notification);
+        if let Some(ref channel) = notification.channel {
+            self.channels.record(channel);
+        }
+
+        // Hold non-critical notifications while DND/snooze is active
+        let suppression = self.suppression.status(now).map(|status| status.kind.label());
+        let id = notification.id.clone();
+        notification = match self.suppression.try_hold(notification, now) {
+            Some(notification) => notification,
+            None => {
+                self.audit.record(&id, now, AuditEvent::Held(suppression.unwrap_or("suppressed")));
+                return;
+            }
+        };
+
+        // The user is already looking at the focused pane
+        let focused_pane = notification.pane_id.filter(|pane_id| Some(*pane_id) == self.world.focused_pane());
+        if let Some(pane_id) = focused_pane {
+            if self.config.suppress_for_focused_pane == FocusedPaneSuppression::HistoryOnly {
+                self.audit.record(&notification.id, now, AuditEvent::FocusedPane(pane_id));
+                self.send_receipt(&notification.id, DeliveryStatus::Dropped, true);
+                return;
+            }
+        }
+
+        // The same message was just acknowledged on this pane: show it dimmed, without escalation
+        if self.recent_acks.is_repeat(&notification, now) {
+            self.audit.record(&notification.id, now, AuditEvent::RepeatDimmed);
+            notification.dimmed = true;
+            notification.priority = Priority::Low;
+        }
+
+        if notification.notification_type == NotificationType::Attention {
+            if self.idle_tracker.is_idle(self.clock.now_ms()) && !notification.dimmed {
+                // User is away: escalate so the alert is noticed on return
+                self.audit.record(&notification.id, now, AuditEvent::Escalated {
+                    from: notification.priority,
+                    to: Priority::Critical,
+                });
+                notification.priority = Priority::Critical;
+                notification.escalated = true;
+                log_info(&format!("User idle for {}ms, escalating attention notification",
+                    self.idle_tracker.idle_for(self.clock.now_ms())));
+            } else if notification.reason.is_none_or(|reason| self.config.attention.get(reason).defer)
+                && self.idle_tracker.is_busy(self.clock.now_ms()) {
+                // User is actively working: defer until a short pause
+                self.audit.record(&notification.id, now, AuditEvent::Deferred);
+                self.deferred_notifications.push(notification);
+                return;
+            }
+        }
+
+        // Context variables take the values of the moment it is shown
+        self.expand_template_variables(&mut notification);
+
+        let equivalent = self.notification_queue.equivalent_id(&notification);
+        let admission = self.notification_queue.enqueue(notification.clone());
+        if notification.source == STRESS_SOURCE {
+            if let Some(run) = self.stress.as_mut() {
+                run.record(admission);
+            }
+        }
+        match admission {
+            Admission::Admitted => {}
+            Admission::Merged => {
+                self.audit.record(&notification.id, now, AuditEvent::Coalesced(equivalent));
+            }
+            Admission::Rejected => {
+                self.audit.record(&notification.id, now, AuditEvent::Rejected);
+                log_warn(&format!("Queue near capacity, dropped {} priority notification",
+                    notification.priority.name()));
+                self.send_receipt(&notification.id, DeliveryStatus::Dropped, true);
+                return;
+            }
+        }
+        self.send_receipt(&notification.id, DeliveryStatus::Queued, false);
+        self.trigger_automations(&notification);
+        self.schedule_auto_focus(&notification);
+
+        // Fan out to the configured sinks; the visual sink's notification stays queued and is
+        // shown when `process_notification_queue` takes it
+        let (visual, effects): (Vec<_>, Vec<_>) = self.sinks.notify(&notification).into_iter()
+            .partition(|effect| matches!(effect, SinkEffect::ShowVisual));
+        if visual.is_empty() || notification.pane_id.is_none() {
+            self.notification_queue.remove_by_id(&notification.id);
+        }
+        self.apply_sink_effects(effects, &notification);
+        if notification.pane_id.is_none() {
+            // Nothing follows for notifications shown by pane-less sinks only
+            self.send_receipt(&notification.id, DeliveryStatus::Displayed, true);
+            let effects = self.sinks.display(&notification);
+            self.apply_sink_effects(effects, &notification);
+        }
+
+        if let Some(pane_id) = focused_pane {
+            if self.config.suppress_for_focused_pane == FocusedPaneSuppression::Acknowledge {
+                // Shown and acknowledged at once, ahead of the queue
+                if self.notification_queue.remove_by_id(&notification.id) {
+                    self.apply_sink_effects(vec![SinkEffect::ShowVisual], &notification);
+                }
+                self.clear_pane_notification(pane_id);
+            }
+        }
+    }
+
+    /// Fire the `on_notify` automations a queued notification matches; those needing confirmation
+    /// wait for a y/n answer (one prompt at a time)
+    fn trigger_automations(&mut self, notification: &Notification) {
+        let now = self.clock.now_ms();
+        for trigger in self.automations.triggered(&self.config.on_notify, notification, now) {
+            self.audit.record(&notification.id, now, AuditEvent::Automation {
+                name: trigger.name.clone(),
+                action: trigger.action.describe(),
+                confirm: trigger.confirm,
+            });
+            if !trigger.confirm {
+                self.run_automation(&trigger);
+            } else if self.interaction_mode == InteractionMode::Normal {
+                self.interaction_mode = InteractionMode::ConfirmAutomation {
+                    trigger,
+                    deadline_ms: now + AUTOMATION_CONFIRM_TIMEOUT_MS,
+                };
+            } else {
+                log_info(&format!("Automation {} skipped: another prompt is open", trigger.name));
+            }
+        }
+    }
+
+    /// Start the auto-focus countdown for a queued notification whose type is set in `auto_focus`
+    fn schedule_auto_focus(&mut self, notification: &Notification) {
+        let now = self.clock.now_ms();
+        let focused_pane = self.world.focused_pane();
+        if self.auto_focus.schedule(&self.config.auto_focus, self.config.auto_focus_delay_ms, notification, focused_pane, now) {
+            let pane_id = notification.pane_id.unwrap_or_default();
+            self.audit.record(&notification.id, now, AuditEvent::AutoFocus(pane_id));
+            self.focus_due_pane();
+        }
+    }
+
+    /// Move focus to the auto-focus pane once its countdown has run out; returns whether the
+    /// countdown ended
+    fn focus_due_pane(&mut self) -> bool {
+        let Some(pending) = self.auto_focus.take_due(self.clock.now_ms()) else {
+            return false;
+        };
+        if self.world.panes().next().is_some() && self.world.pane(pending.pane_id).is_none() {
+            log_info(&format!("Auto-focus of pane {} skipped: pane closed", pending.pane_id));
+            return true;
+        }
+        log_info(&format!("Auto-focus: pane {}", pending.pane_id));
+        self.actions.focus_terminal_pane(pending.pane_id);
+        true
+    }
+
+    /// Take an automation's Zellij action
+    fn run_automation(&mut self, trigger: &AutomationTrigger) {
+        log_info(&format!("Automation {}: {}", trigger.name, trigger.action.describe()));
+        match (&trigger.action, trigger.pane_id) {
+            (LayoutAction::Run(command), _) => {
+                let mut context = BTreeMap::new();
+                context.insert(AUTOMATION_CONTEXT_KEY.to_string(), trigger.name.clone());
+                self.actions.open_command_pane(CommandToRun {
+                    path: "sh".into(),
+                    args: vec!["-c".to_string(), command.clone()],
+                    cwd: None,
+                }, context);
+            }
+            (LayoutAction::Fullscreen, Some(pane_id)) => {
+                self.actions.toggle_fullscreen(pane_id);
+            }
+            (LayoutAction::EditScrollback, Some(pane_id)) => {
+                self.actions.edit_scrollback(pane_id);
+            }
+            (_, None) => {}
+        }
+    }
+
+    /// Apply side effects requested by sinks
+    fn apply_sink_effects(&mut self, effects: Vec<SinkEffect>, notification: &Notification) {
+        for effect in effects {
+            // The other instances ring, toast, post and forward; a mirror or standby only shows
+            if self.render_only() && !matches!(effect, SinkEffect::ShowVisual | SinkEffect::ClearVisual(_)) {
+                continue;
+            }
+            self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::Sink(effect.label()));
+            match effect {
+                SinkEffect::ShowVisual => {
+                    if let Some(pane_id) = notification.pane_id {
+                        // A replacement waits for the minimum display time of the notification shown
+                        let replaces = self.displayed.get(&pane_id).is_none_or(|shown| shown.id != notification.id);
+                        if replaces && !self.state_manager.admit(pane_id, notification, self.clock.now_ms()) {
+                            self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::Held("minimum display time"));
+                            continue;
+                        }
+                        if self.muted_panes.is_muted(pane_id) {
+                            self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::MutedPane(pane_id));
+                            // Keep a record of what the mute suppressed
+                            let state = self.pane_states.get(&pane_id).map(|s| s.state.clone()).unwrap_or_default();
+                            self.state_manager.record_transition(pane_id, StateTransition::new(
+                                state.clone(),
+                                state,
+                                &format!("suppressed {} on muted pane {}", notification.notification_type.name(), pane_id),
+                            ).at(self.clock.now_ms()));
+                            self.send_receipt(&notification.id, DeliveryStatus::Dropped, true);
+                            continue;
+                        }
+                        self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::Routed(pane_id));
+                        self.send_receipt(&notification.id, DeliveryStatus::Displayed, false);
+                        if let Some(unix_ms) = self.clock.unix_ms() {
+                            let late = self.latency.displayed(&notification.id, unix_ms, self.config.latency_warn_ms, self.clock.now_ms());
+                            if let Some(warning) = late {
+                                log_warn(&warning);
+                            }
+                        }
+                        self.update_pane_visual_state(pane_id, notification);
+                        let min_display_ms = self.config.min_display_ms(&notification.notification_type);
+                        self.state_manager.shown(pane_id, notification.priority, min_display_ms, self.clock.now_ms());
+                        if let Some(previous) = self.displayed.insert(pane_id, notification.clone()) {
+                            if previous.id != notification.id {
+                                self.send_receipt(&previous.id, DeliveryStatus::Replaced, true);
+                            }
+                        }
+                        self.last_notified_pane = Some(pane_id);
+                        let effects = self.sinks.display(notification);
+                        self.apply_sink_effects(effects, notification);
+                    }
+                }
+                SinkEffect::ClearVisual(pane_id) => {
+                    self.clear_visual_state(pane_id);
+                    self.displayed.remove(&pane_id);
+                }
+                SinkEffect::Bell => self.pending_output.push("\u{7}".to_string()),
+                SinkEffect::Escape(sequence) => self.pending_output.push(sequence),
+                SinkEffect::Announce(text) => self.last_announcement = Some(text),
+                // Synthetic load stays on this machine
+                SinkEffect::WebRequest { .. }
+                | SinkEffect::Forward { .. }
+                | SinkEffect::Sound(_)
+                | SinkEffect::Daemon { .. } if notification.source == STRESS_SOURCE => {}
+                SinkEffect::WebRequest { url, body } => {
+                    // Sent on the next tick, retried with backoff on failure
+                    self.webhook_deliveries.submit(&url, &body, self.clock.now_ms());
+                }
+                SinkEffect::Toast if !self.surface_toggles.is_enabled(DisplaySurface::Toasts)
+                    || !self.config.host_platform.has_shell() => {}
+                SinkEffect::Toast => {
+                    let actions = self.toasts.show(notification, self.clock.now_ms(), self.world.screen_size());
+                    self.apply_toast_actions(actions);
+                }
+                SinkEffect::Sound(_) if !self.config.host_platform.has_shell() => {}
+                SinkEffect::Sound(command) => {
+                    let mut context = BTreeMap::new();
+                    context.insert(SOUND_CONTEXT_KEY.to_string(), notification.id.clone());
+                    self.actions.run_command(&command, context);
+                }
+                SinkEffect::Forward { session, withdraw } => self.forward_notification(&session, notification, withdraw),
+                SinkEffect::Daemon { .. } if !self.config.host_platform.has_shell() => {}
+                SinkEffect::Daemon { command, close } => {
+                    let mut context = BTreeMap::new();
+                    context.insert(DAEMON_CONTEXT_KEY.to_string(), notification.id.clone());
+                    context.insert(DAEMON_OP_CONTEXT_KEY.to_string(), if close { "close" } else { "show" }.to_string());
+                    self.actions.run_command(&command, context);
+                }
+            }
+        }
+    }
+
+    /// Pipe a notification to the plugin in another session, or withdraw it there
+    fn forward_notification(&mut self, session: &str, notification: &Notification, withdraw: bool) {
+        if !self.config.host_platform.has_shell() {
+            return;
+        }
+        let Some(origin) = self.mode_info.session_name.clone() else {
+            log_info("Not forwarding: session name not known yet");
+            return;
+        };
+        if origin == session {
+            return;
+        }
+        let payload = if withdraw {
+            aggregator::withdraw_payload(&notification.id, &origin)
+        } else {
+            aggregator::forward_payload(notification, &origin)
+        };
+        let mut context = BTreeMap::new();
+        context.insert(FORWARD_CONTEXT_KEY.to_string(), session.to_string());
+        self.actions.run_command(&aggregator::forward_command(session, &payload), context);
+    }
+
+    /// Re-deliver displayed Critical notifications unacknowledged past `ack_escalation_ms`
+    /// to the out-of-terminal sinks (desktop, webhook), once per notification
+    fn escalate_unacknowledged(&mut self) {
+        let now = self.clock.now_ms();
+        self.ack_escalated.retain(|id| self.displayed.values().any(|n| n.id == *id));
+
+        let due: Vec<Notification> = self.displayed.iter()
+            .filter(|(pane_id, notification)| {
+                notification.priority == Priority::Critical
+                    && !self.ack_escalated.contains(&notification.id)
+                    && self.pane_states.get(pane_id).is_some_and(|state| {
+                        state.is_unread()
+                            && now.saturating_sub(state.notification_timestamp) >= self.config.ack_escalation_ms
+                    })
+            })
+            .map(|(_, notification)| notification.clone())
+            .collect();
+
+        for notification in due {
+            self.ack_escalated.insert(notification.id.clone());
+            let effects = self.sinks.escalate(&notification);
+            if effects.is_empty() {
+                continue;
+            }
+            log_info(&format!("Notification {} not acknowledged in {}ms, escalating out of terminal",
+                notification.id, self.config.ack_escalation_ms));
+            self.audit.record(&notification.id, now, AuditEvent::AckEscalated);
+            self.history.mark_ack_escalated(&notification.id, now);
+            self.apply_sink_effects(effects, &notification);
+        }
+    }
+
+    /// Flag displayed notifications unacknowledged past their SLO, returning whether any breached
+    fn check_slo(&mut self) -> bool {
+        let now = self.clock.now_ms();
+        let displayed = &self.displayed;
+        self.slo.retain(|id| displayed.values().any(|n| n.id == id));
+
+        let mut breached = Vec::new();
+        for (pane_id, notification) in &self.displayed {
+            let Some(&target_ms) = self.config.slo.get(notification.notification_type.name()) else { continue };
+            let Some(state) = self.pane_states.get(pane_id).filter(|s| s.has_notification() && !s.acknowledged) else {
+                continue;
+            };
+            let waited_ms = now.saturating_sub(state.notification_timestamp);
+            if self.slo.check(&notification.id, &notification.notification_type, waited_ms, target_ms) {
+                breached.push((*pane_id, notification.clone(), target_ms));
+            }
+        }
+
+        let any = !breached.is_empty();
+        for (pane_id, notification, target_ms) in breached {
+            log_warn(&format!("Notification {} on pane {} not acknowledged within its {}ms SLO",
+                notification.id, pane_id, target_ms));
+            self.audit.record(&notification.id, now, AuditEvent::SloBreached(target_ms));
+            if let Some(state) = self.pane_states.get_mut(&pane_id) {
+                state.slo_breached = true;
+            }
+            if self.config.slo_escalate {
+                let effects = self.sinks.escalate(&notification);
+                self.apply_sink_effects(effects, &notification);
+            }
+        }
+        any
+    }
+
+    /// Mark displayed notifications whose response deadline passed unacknowledged as overdue
+    /// and tell the sinks, once per notification. Returns whether any entry shows a deadline.
+    fn check_deadlines(&mut self) -> bool {
+        let now = self.clock.now_ms();
+        let mut overdue = Vec::new();
+        let mut counting = false;
+        for (pane_id, notification) in &self.displayed {
+            let Some(state) = self.pane_states.get(pane_id).filter(|s| s.has_notification()) else { continue };
+            let Some(deadline_at_ms) = state.deadline_at_ms else { continue };
+            counting = true;
+            if !state.overdue && now >= deadline_at_ms {
+                overdue.push((*pane_id, notification.clone()));
+            }
+        }
+
+        for (pane_id, notification) in overdue {
+            let deadline_ms = notification.deadline_ms.unwrap_or_default();
+            log_warn(&format!("Notification {} on pane {} is overdue ({}ms deadline passed)",
+                notification.id, pane_id, deadline_ms));
+            self.audit.record(&notification.id, now, AuditEvent::Overdue(deadline_ms));
+            if let Some(state) = self.pane_states.get_mut(&pane_id) {
+                state.overdue = true;
+            }
+            let effects = self.sinks.overdue(&notification);
+            self.apply_sink_effects(effects, &notification);
+        }
+        counting
+    }
+
+    /// Deliver an acknowledgement to the sinks
+    fn ack_notification(&mut self, notification: &Notification) {
+        self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::Acknowledged);
+        self.recent_acks.remember(notification, self.clock.now_ms(), self.config.recent_ack_window_ms);
+        self.send_receipt(&notification.id, DeliveryStatus::Acknowledged, true);
+        if let Some(ref session_id) = notification.session_id {
+            self.timeline.resume(session_id, self.clock.now_ms());
+        }
+        if let Some(entry) = self.history.get(&notification.id) {
+            let response_ms = self.clock.now_ms().saturating_sub(entry.received_at_ms);
+            self.slo.acknowledge(&notification.id, &notification.notification_type, response_ms);
+            self.metrics.record_ack(response_ms);
+        }
+        let effects = self.sinks.ack(notification);
+        self.apply_sink_effects(effects, notification);
+    }
+
+    /// Merge repeated history entries, leaving notifications still shown or waiting alone, and
+    /// return the number of entries merged away
+    fn compact_history(&mut self, mode: HistoryCompaction) -> usize {
+        let live: BTreeSet<&str> = self.displayed.values()
+            .chain(self.notification_queue.all())
+            .chain(self.deferred_notifications.iter())
+            .map(|notification| notification.id.as_str())
+            .collect();
+        let (pending_panes, state_manager) = (&self.pending_panes, &self.state_manager);
+        self.history.compact(mode,
+            |id| live.contains(id) || pending_panes.find(id).is_some() || state_manager.find_waiting(id).is_some(),
+            self.clock.now_ms())
+    }
+
+    /// Acknowledge a notification by ID wherever it waits, returning whether there was one
+    fn acknowledge_id(&mut self, id: &str) -> bool {
+        let displayed = self.displayed.iter().find(|(_, n)| n.id == id).map(|(pane_id, _)| *pane_id);
+        let queued = self.notification_queue.all().into_iter().find(|n| n.id == id).cloned();
+        let notification = if let Some(pane_id) = displayed {
+            self.clear_visual_state(pane_id);
+            self.displayed.remove(&pane_id)
+        } else if let Some(notification) = queued {
+            self.notification_queue.remove_by_id(id);
+            Some(notification)
+        } else if let Some(index) = self.deferred_notifications.iter().position(|n| n.id == id) {
+            Some(self.deferred_notifications.remove(index))
+        } else if let Some(notification) = self.pending_panes.find(id).cloned() {
+            self.pending_panes.cancel(|n| n.id == id);
+            Some(notification)
+        } else {
+            self.state_manager.take_waiting(|n| n.id == id).pop()
+        };
+        match notification {
+            Some(notification) => {
+                self.ack_notification(&notification);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Expire the notification displayed for a pane, returning whether there was one
+    fn expire_displayed(&mut self, pane_id: u32) -> bool {
+        let Some(mut notification) = self.displayed.remove(&pane_id) else {
+            return false;
+        };
+        if notification.demote() {
+            // Stays on the pane as a badge-only Low notification for another TTL
+            self.audit.record(&notification.id, self.clock.now_ms(), AuditEvent::Demoted);
+            self.update_pane_visual_state(pane_id, &notification);
+            self.displayed.insert(pane_id, notification);
+        } else {
+            self.expire_notification(&notification);
+        }
+        true
+    }
+
+    /// Handle an expiry according to the notification's `on_expire` action
+    fn expire_notification(&mut self, notification: &Notification) {
+        let now = self.clock.now_ms();
+        self.send_receipt(&notification.id, DeliveryStatus::Expired, true);
+        match notification.on_expire {
+            ExpiryAction::Archive => {
+                // Silent: nothing reaches the sinks, the history keeps it
+                self.audit.record(&notification.id, now, AuditEvent::Archived);
+                return;
+            }
+            ExpiryAction::Escalate => {
+                self.audit.record(&notification.id, now, AuditEvent::Expired);
+                let effects = self.sinks.escalate(notification);
+                self.apply_sink_effects(effects, notification);
+            }
+            ExpiryAction::Discard | ExpiryAction::Demote => {
+                self.audit.record(&notification.id, now, AuditEvent::Expired);
+            }
+        }
+        let effects = self.sinks.expire(notification);
+        self.apply_sink_effects(effects, notification);
+    }
+
+    /// Open or close toast panes
+    fn apply_toast_actions(&mut self, actions: Vec<ToastAction>) {
+        for action in actions {
+            match action {
+                ToastAction::Open { id, text, geometry } => {
+                    // Print the text and stay alive until the plugin closes the pane
+                    let script = format!("printf '%s\\n' \"$1\"; sleep {}", self.toasts.pane_lifetime_secs());
+                    let command = CommandToRun::new_with_args("sh", vec!["-c", &script, "toast", &text]);
+                    let coordinates = FloatingPaneCoordinates::default()
+                        .with_x_fixed(geometry.x)
+                        .with_y_fixed(geometry.y)
+                        .with_width_fixed(geometry.width)
+                        .with_height_fixed(geometry.height);
+                    let mut context = BTreeMap::new();
+                    context.insert(TOAST_CONTEXT_KEY.to_string(), id.to_string());
+                    self.actions.open_floating_pane(command, Some(coordinates), context);
+                }
+                ToastAction::Close(pane_id) => {
+                    self.actions.close_terminal_pane(pane_id);
+                }
+            }
+        }
+    }
+
+    /// Show queued notifications through the visual sink; returns whether any was taken
+    fn process_notification_queue(&mut self) -> bool {
+        let mut needs_render = false;
+
+        // Show queued notifications in priority order, at most `display_rate` a second
+        while let Some(notification) = self.notification_queue.dequeue_ready() {
+            self.apply_sink_effects(vec![SinkEffect::ShowVisual], &notification);
+            needs_render = true;
+        }
+
+        needs_render
+    }
+
+    /// Update visual state for a pane based on notification
+    fn update_pane_visual_state(&mut self, pane_id: u32, notification: &Notification) {
+        let tab_locality = self.tab_locality(pane_id);
+        let claude_pane = self.is_claude_pane(pane_id);
+        let visual_state = self.pane_states.entry(pane_id).or_insert_with(VisualState::default);
+        visual_state.tab_locality = tab_locality;
+        visual_state.claude_pane = claude_pane;
+        self.state_manager.record_transition(pane_id, StateTransition::new(
+            visual_state.state.clone(),
+            VisualNotificationState::Active,
+            &format!("{} {} {} from {}", if notification.demoted { "demoted" } else { "showed" },
+                notification.notification_type.name(), notification.id, notification.source),
+        ).at(self.clock.now_ms()));
+        visual_state.state = VisualNotificationState::Active;
+        // A pin and an SLO breach belong to the notification they were set on
+        visual_state.pinned = false;
+        visual_state.slo_breached = false;
+
+        // Apply sender branding overrides unless disallowed
+        if self.config.allow_sender_overrides {
+            visual_state.custom_color = notification.color.clone();
+            visual_state.custom_icon = notification.icon.clone();
+        } else {
+            visual_state.custom_color = None;
+            visual_state.custom_icon = None;
+        }
+
+        // Attention reasons bring their own sub-icon and color
+        if let Some(reason) = notification.reason {
+            visual_state.custom_color.get_or_insert_with(|| self.config.attention.color(reason, &self.config.theme));
+            visual_state.custom_icon.get_or_insert_with(|| reason.icon().to_string());
+        }
+
+        // Failure classes get the color of their exit code range
+        if let Some(color) = notification.metadata.exit_code.and_then(|code| self.color_manager.exit_code_color(code)) {
+            visual_state.custom_color.get_or_insert_with(|| color.to_string());
+        }
+
+        // Set border color based on notification type, scaled by priority
+        visual_state.border_color = self.color_manager
+            .resolve_color(&notification.notification_type, visual_state.custom_color.as_deref())
+            .map(|color| self.color_manager.priority_adjusted(&color, &notification.priority));
+        visual_state.priority = notification.priority;
+        visual_state.notification_timestamp = self.clock.now_ms();
+
+        // A deadline counts from arrival; an overdue mark belongs to the notification it was set on
+        if visual_state.notification_id.as_deref() != Some(notification.id.as_str()) {
+            visual_state.overdue = false;
+        }
+        let received_at_ms = self.history.get(&notification.id).map_or(self.clock.now_ms(), |entry| entry.received_at_ms);
+        visual_state.deadline_at_ms = notification.deadline_ms.map(|deadline_ms| received_at_ms + deadline_ms);
+        visual_state.notification_id = Some(notification.id.clone());
+
+        // Set badge icon
+        visual_state.badge_icon = visual_state.custom_icon.clone()
+            .or_else(|| notification.notification_type.icon());
+
+        // Demoted and dimmed notifications only keep their badge
+        let badge_only = notification.demoted || notification.dimmed;
+        visual_state.badge_only = badge_only;
+        if badge_only {
+            visual_state.is_animating = false;
+        }
+
+        // Start animation if enabled
+        if self.config.animations_enabled() && !badge_only {
+            visual_state.is_animating = true;
+            visual_state.animation_start_tick = self.tick_count;
+            visual_state.animation_start_ms = self.clock.now_ms();
+            visual_state.animation_style = match notification.reason {
+                Some(reason) => self.config.attention.get(reason).style.clone(),
+                None => self.config.animation.style.clone(),
+            };
+        }
+
+        // Track percent updates of progress notifications
+        let now = self.clock.now_ms();
+        match notification.percent {
+            Some(percent) => {
+                self.progress.entry(pane_id)
+                    .and_modify(|tracker| tracker.update(percent, now))
+                    .or_insert_with(|| ProgressTracker::new(percent, now));
+            }
+            None => {
+                self.progress.remove(&pane_id);
+            }
+        }
+        visual_state.progress = self.progress.get(&pane_id).map(|tracker| tracker.estimate(now));
+
+        // Entries on hidden channels are kept but not shown
+        visual_state.channel = notification.channel.clone();
+        visual_state.correlation_id = notification.correlation_id.clone();
+        visual_state.branch = notification.branch_label().map(str::to_string);
+        visual_state.hidden = !self.channels.is_visible(notification.channel.as_deref());
+        visual_state.other_project = workspace::is_other_project(&self.config.project_roots, notification.cwd.as_deref());
+
+        // Set notification message for tooltip
+        visual_state.notification_message = Some(notification.message.clone());
+        visual_state.notification_type = Some(notification.notification_type.clone());
+        visual_state.reply_options = notification.reply_options.clone();
+
+        self.refresh_surfaces(pane_id);
+    }
+
+    /// Select signaling surfaces for a pane and apply or remove its title badge
+    fn refresh_surfaces(&mut self, pane_id: u32) {
+        let pane = self.world.pane(pane_id);
+        let visibility = pane.map(|p| p.visibility).unwrap_or_default();
+        let surfaces = self.surface_selector.select(visibility);
+        let wants_badge = surfaces.contains(&Surface::TitleBadge);
+
+        let artifact = Artifact::PaneTitle(pane_id);
+        if let Some(visual_state) = self.pane_states.get_mut(&pane_id) {
+            visual_state.surfaces = surfaces;
+
+            if wants_badge && self.artifacts.applied(artifact).is_none() {
+                if let (Some(pane), Some(icon)) = (pane, visual_state.badge_icon.as_ref()) {
+                    let title = format!("{} {}", icon, pane.title);
+                    if !pane.is_plugin && self.actions.rename_terminal_pane(pane_id, &title) {
+                        self.artifacts.record(artifact, title, pane.title.clone());
+                    }
+                }
+            }
+        }
+        if !wants_badge {
+            self.restore_pane_title(pane_id);
+        }
+    }
+
+    /// Clear a pane's visual state, restoring any title badge
+    fn clear_visual_state(&mut self, pane_id: u32) {
+        self.restore_pane_title(pane_id);
+        self.state_manager.ended(pane_id);
+        if let Some(visual_state) = self.pane_states.get_mut(&pane_id) {
+            if visual_state.state != VisualNotificationState::Idle {
+                let reason = match &visual_state.notification_id {
+                    Some(id) => format!("cleared {}", id),
+                    None => "cleared".to_string(),
+                };
+                self.state_manager.record_transition(pane_id, StateTransition::new(
+                    visual_state.state.clone(),
+                    VisualNotificationState::Idle,
+                    &reason,
+                ).at(self.clock.now_ms()));
+            }
+            visual_state.clear();
+        }
+        self.progress.remove(&pane_id);
+    }
+
+    /// Clear notification state for a pane
+    fn clear_pane_notification(&mut self, pane_id: u32) {
+        self.clear_visual_state(pane_id);
+        self.notification_queue.remove_for_pane(pane_id);
+
+        if let Some(notification) = self.displayed.remove(&pane_id) {
+            self.ack_notification(&notification);
+        }
+    }
+
+    /// Clear all notifications, keeping pinned ones unless forced
+    fn clear_all_notifications(&mut self, force: bool) {
+        let pane_ids: BTreeSet<u32> = self.pane_states.keys()
+            .chain(self.displayed.keys())
+            .copied()
+            .filter(|pane_id| force || !self.pane_states.get(pane_id).is_some_and(|s| s.pinned))
+            .collect();
+        // Fade the cleared entries out rather than dropping them at once
+        if self.animation_engine.is_enabled() && !self.config.motion_reduced() {
+            let cleared: Vec<_> = self.renderer.listed_entries(&self.pane_states).into_iter()
+                .filter(|(pane_id, _)| pane_ids.contains(pane_id))
+                .collect();
+            if !cleared.is_empty() {
+                self.clear_cascade = Some(ClearCascade::new(cleared, self.clock.now_ms()));
+            }
+        }
+        let waiting = self.state_manager.take_waiting(|n| n.pane_id.is_none_or(|pane_id| pane_ids.contains(&pane_id)));
+        for pane_id in pane_ids {
+            self.clear_visual_state(pane_id);
+            if let Some(notification) = self.displayed.remove(&pane_id) {
+                self.ack_notification(&notification);
+            }
+        }
+        for notification in waiting {
+            self.ack_notification(&notification);
+        }
+        self.notification_queue.clear();
+    }
+
+    /// Pane whose matching notification arrived last
+    fn newest_pane(&self, matches: fn(&VisualState) -> bool) -> Option<u32> {
+        self.pane_states.iter()
+            .filter(|(_, state)| matches(state))
+            .max_by_key(|(_, state)| state.notification_timestamp)
+            .map(|(pane_id, _)| *pane_id)
+    }
+
+    /// Mark the notification shown for a pane read, returning whether it changed. It stops
+    /// animating and escalating, and stays as a dimmed badge until dismissed.
+    fn mark_read(&mut self, pane_id: u32) -> bool {
+        let now = self.clock.now_ms();
+        let Some(visual_state) = self.pane_states.get_mut(&pane_id).filter(|s| s.is_unread()) else {
+            return false;
+        };
+        let from = visual_state.state.clone();
+        visual_state.mark_read();
+        self.state_manager.record_transition(pane_id, StateTransition::new(
+            from,
+            VisualNotificationState::Read,
+            &format!("marked read on pane {}", pane_id),
+        ).at(now));
+        if let Some(id) = self.displayed.get(&pane_id).map(|n| n.id.clone()) {
+            self.audit.record(&id, now, AuditEvent::MarkedRead);
+            self.send_receipt(&id, DeliveryStatus::Read, false);
+        }
+        true
+    }
+
+    /// Push back the expiry of a notification, queued and displayed alike, returning the TTL left
+    fn extend_notification(&mut self, id: &str, extra_ms: u64) -> Result<u64, String> {
+        let mut found = false;
+        let mut remaining_ms = None;
+        let displayed = self.displayed.values_mut().filter(|notification| notification.id == id);
+        for notification in self.notification_queue.get_mut(id).into_iter().chain(displayed) {
+            found = true;
+            if notification.extend_ttl(extra_ms) {
+                remaining_ms = remaining_ms.max(notification.ttl_left());
+            }
+        }
+        match (found, remaining_ms) {
+            (false, _) => Err(format!("no queued or displayed notification {}", id)),
+            (true, None) => Err(format!("{} never expires", id)),
+            (true, Some(remaining_ms)) => {
+                self.audit.record(id, self.clock.now_ms(), AuditEvent::Extended(extra_ms));
+                Ok(remaining_ms)
+            }
+        }
+    }
+
+    /// Drop the expiry of a queued or displayed notification so it stays until acknowledged
+    fn make_sticky(&mut self, id: &str) -> Result<(), String> {
+        let mut found = false;
+        let mut changed = false;
+        let displayed = self.displayed.values_mut().filter(|notification| notification.id == id);
+        for notification in self.notification_queue.get_mut(id).into_iter().chain(displayed) {
+            found = true;
+            changed |= notification.make_sticky();
+        }
+        match (found, changed) {
+            (false, _) => Err(format!("no queued or displayed notification {}", id)),
+            (true, false) => Err(format!("{} never expires", id)),
+            (true, true) => {
+                self.audit.record(id, self.clock.now_ms(), AuditEvent::MadeSticky);
+                Ok(())
+            }
+        }
+    }
+
+    /// Expire a queued or displayed notification now, as if its TTL had run out
+    fn expire_now(&mut self, id: &str) -> Result<(), String> {
+        let queued = self.notification_queue.get_mut(id).map(|notification| notification.clone());
+        let pane_id = self.displayed.iter()
+            .find(|(_, notification)| notification.id == id)
+            .map(|(pane_id, _)| *pane_id);
+        if queued.is_none() && pane_id.is_none() {
+            return Err(format!("no queued or displayed notification {}", id));
+        }
+        self.audit.record(id, self.clock.now_ms(), AuditEvent::ExpiredEarly);
+        if let Some(notification) = queued {
+            self.notification_queue.remove_by_id(id);
+            if pane_id.is_none() {
+                self.expire_notification(&notification);
+            }
+        }
+        if let Some(pane_id) = pane_id {
+            self.set_pinned(pane_id, false);
+            self.expire_displayed(pane_id);
+        }
+        Ok(())
+    }
+
+    /// Pin or unpin the notification displayed for a pane, returning whether it changed
+    fn set_pinned(&mut self, pane_id: u32, pinned: bool) -> bool {
+        match self.pane_states.get_mut(&pane_id) {
+            Some(visual_state) if visual_state.has_notification() && visual_state.pinned != pinned => {
+                visual_state.pinned = pinned;
+                log_info(&format!("{} notification for pane {}", if pinned { "Pinned" } else { "Unpinned" }, pane_id));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Acknowledge all notifications of one type across panes and the queue, returning the count
+    fn acknowledge_type(&mut self, notification_type: &NotificationType) -> usize {
+        let pane_ids: Vec<u32> = self.pane_states.iter()
+            .filter(|(_, s)| s.has_notification() && s.notification_type.as_ref() == Some(notification_type))
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        for pane_id in &pane_ids {
+            self.clear_visual_state(*pane_id);
+        }
+        let queued = self.notification_queue.remove_by_type(notification_type);
+
+        let acked: Vec<u32> = self.displayed.iter()
+            .filter(|(_, n)| n.notification_type == *notification_type)
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        for pane_id in acked {
+            if let Some(notification) = self.displayed.remove(&pane_id) {
+                self.ack_notification(&notification);
+            }
+        }
+
+        pane_ids.len() + queued
+    }
+
+    /// Current animation frame
+    fn frame(&self) -> Frame {
+        Frame { tick: self.tick_count, now_ms: self.clock.now_ms() }
+    }
+
+    /// Timer tick interval (s)
+    fn tick_interval_secs(&self) -> f64 {
+        self.config.tick_ms() as f64 / 1000.0
+    }
+
+    /// Timer ticks per second at the configured tick interval
+    fn ticks_per_second(&self) -> u64 {
+        (1000 / self.config.tick_ms().max(1)).max(1)
+    }
+
+    /// Rebuild the renderer from the configuration, surface toggles and character set hints
+    fn rebuild_renderer(&mut self) {
+        self.renderer = Renderer::new(&self.config)
+            .with_surface_toggles(&self.surface_toggles)
+            .with_ascii(self.charset.use_ascii(self.config.ascii_only));
+    }
+
+    /// Rebuild the color manager from the theme and accessibility settings
+    fn rebuild_color_manager(&mut self) {
+        self.color_manager = ColorManager::new(&self.config.theme);
+        self.color_manager.set_high_contrast(self.config.accessibility.high_contrast || self.config.big_mode);
+        self.color_manager.set_no_color(self.config.accessibility.no_color);
+        self.color_manager.set_exit_code_colors(self.config.exit_code_colors.clone());
+        self.renderer.entry_cache().clear();
+    }
+
+    /// Apply changed accessibility settings to everything already on screen
+    fn apply_accessibility(&mut self) {
+        self.rebuild_color_manager();
+        self.animation_engine = AnimationEngine::new(&self.config.effective_animation());
+        self.rebuild_renderer();
+
+        for visual_state in self.pane_states.values_mut() {
+            // Stop in-flight animations; reduced motion and low power switch to static emphasis
+            if !self.config.animations_enabled() {
+                self.animation_engine.stop_animation(visual_state);
+            }
+            if let Some(notification_type) = visual_state.notification_type.as_ref() {
+                visual_state.border_color = self.color_manager
+                    .resolve_color(notification_type, visual_state.custom_color.as_deref())
+                    .map(|color| self.color_manager.priority_adjusted(&color, &visual_state.priority));
+            }
+        }
+    }
+
+    /// Switch low power mode on or off, moving animations to static emphasis and back
+    fn set_low_power(&mut self, enabled: bool) {
+        self.config.low_power = enabled;
+        self.config_manager.set_override("low_power", &enabled.to_string());
+        self.apply_accessibility();
+        log_info(&format!("Low power mode {}", if enabled { "on" } else { "off" }));
+    }
+
+    /// Switch a display surface on or off (toggle if unspecified)
+    fn set_surface_enabled(&mut self, surface: DisplaySurface, enabled: Option<bool>) -> bool {
+        let enabled = self.surface_toggles.set(surface, enabled);
+        self.rebuild_renderer();
+        if surface == DisplaySurface::TabBadges {
+            self.sync_tab_badges();
+        }
+        log_info(&format!("Surface {} {}", surface.name(), if enabled { "on" } else { "off" }));
+        true
+    }
+
+    /// Reload configuration (the configuration and theme files are re-read so edits apply live)
+    fn reload_config(&mut self) -> Result<(), String> {
+        let config = self.config_manager.reload()?;
+        self.apply_config(config);
+        self.warn_config_drift();
+        Ok(())
+    }
+
+    /// Switch to a new effective configuration
+    fn apply_config(&mut self, config: Config) {
+        let web_access_added = !plugin_permissions(&self.config).contains(&PermissionType::WebAccess)
+            && plugin_permissions(&config).contains(&PermissionType::WebAccess);
+        self.config = config;
+        if web_access_added {
+            log_info("Webhook sink configured: requesting web access");
+            request_permission(&[PermissionType::WebAccess]);
+        }
+        self.apply_accessibility();
+        self.actions.configure(self.config.actions_dry_run, self.config.action_rate_limit);
+        self.actions.set_read_only(self.render_only());
+        self.passive = self.passive_detector();
+        self.sinks = SinkPipeline::from_config(&self.config);
+        self.event_bridge.set_remaps(&self.config.remaps);
+        self.surface_selector = SurfaceSelector::new(self.config.collapsed_title_badge);
+        self.toasts.set_settings(ToastSettings::from_config(&self.config));
+        self.sequencer.set_hold_ms(self.config.sequence_hold_ms);
+        self.sync_election_membership();
+        self.tab_badge_style = TabBadgeStyle::from_config(&self.config);
+        self.sync_project_scope();
+        self.sync_claude_panes();
+        self.unread_count_file.reset();
+        self.artifacts.release(Artifact::WindowTitle);
+        self.own_pane_title = None;
+        self.warn_unavailable_features();
+        self.warn_experimental_features();
+        log_info("Configuration reloaded");
+    }
+
+    /// Warn once per key about values set differently by several configuration layers, e.g. a
+    /// theme in the layout that the theme file replaces
+    fn warn_config_drift(&mut self) {
+        let drift = self.config_manager.new_drift();
+        if !drift.is_empty() {
+            let lines: Vec<String> = drift.iter().map(|drift| drift.line()).collect();
+            log_warn(&format!("Config drift, set in more than one place (config_show <key> shows the value): {}",
+                lines.join(", ")));
+        }
+    }
+
+    /// Log the enabled features the host platform cannot run
+    fn warn_unavailable_features(&self) {
+        let features = self.config.unavailable_features();
+        if !features.is_empty() {
+            log_warn(&format!("Disabled on {} hosts (no sh): {}",
+                self.config.host_platform.name(), features.join(", ")));
+        }
+    }
+
+    /// Log named experimental features this version does not know, and experimental subsystems
+    /// configured without their feature
+    fn warn_experimental_features(&self) {
+        let unknown = features::unknown(&self.config);
+        if !unknown.is_empty() {
+            log_warn(&format!("Ignoring unknown experimental features (stable or misspelled): {}", unknown.join(", ")));
+        }
+        for (sink, feature) in [("toast", "toasts"), ("sound", "sounds")] {
+            if self.config.sinks.iter().any(|name| name == sink) && !self.config.experimental(feature) {
+                log_warn(&format!("{} sink is experimental: add features {{ experimental \"{}\"; }} to use it", sink, feature));
+            }
+        }
+    }
+}
+
+/// Notification type picked by the second key of the acknowledge chord
+fn chord_notification_type(key: char) -> Option<NotificationType> {
+    match key {
+        'e' => Some(NotificationType::Error),
+        's' => Some(NotificationType::Success),
+        'w' => Some(NotificationType::Warning),
+        'i' => Some(NotificationType::Info),
+        'p' => Some(NotificationType::Progress),
+        'a' => Some(NotificationType::Attention),
+        _ => None,
     }
 }
 
 /// Log info message
 fn log_info(msg: &str) {
-    // Use Zellij's logging
-    eprintln!("[INFO] zellij-visual-notifications: {}", msg);
+    diagnostics::log(LogLevel::Info, msg);
 }
 
 /// Log warning message
 fn log_warn(msg: &str) {
-    eprintln!("[WARN] zellij-visual-notifications: {}", msg);
+    diagnostics::log(LogLevel::Warn, msg);
 }
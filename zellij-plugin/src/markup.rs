@@ -0,0 +1,191 @@
+//! Message markup module for Zellij Visual Notifications
+//!
+//! Notification messages may use a minimal markup subset: `**bold**`, `` `code` `` and line
+//! breaks. Multi-line views (toasts, big mode, the queue overlay) draw bold as bold and code as
+//! inverse video; one-line views (the status bar, group listings, desktop notifications) strip
+//! the markers so formatted hook messages don't show literal asterisks and backticks. A marker
+//! without its closing partner on the same line is kept as text.
+
+use crate::wrap::clusters;
+
+const BOLD_ON: &str = "\x1b[1m";
+const BOLD_OFF: &str = "\x1b[22m";
+const CODE_ON: &str = "\x1b[7m";
+const CODE_OFF: &str = "\x1b[27m";
+
+/// Style of a run of message text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Unformatted text
+    Plain,
+    /// `**bold**`
+    Bold,
+    /// `` `code` `` (drawn inverse)
+    Code,
+}
+
+impl Style {
+    fn escapes(&self) -> (&'static str, &'static str) {
+        match self {
+            Style::Plain => ("", ""),
+            Style::Bold => (BOLD_ON, BOLD_OFF),
+            Style::Code => (CODE_ON, CODE_OFF),
+        }
+    }
+}
+
+/// A run of text in one style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    /// Style of the run
+    pub style: Style,
+    /// Text without markers
+    pub text: String,
+}
+
+/// Parse one line into styled spans
+pub fn parse_line(line: &str) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut push = |style: Style, text: &str| {
+        if text.is_empty() {
+            return;
+        }
+        match spans.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(text),
+            _ => spans.push(Span { style, text: text.to_string() }),
+        }
+    };
+    let mut rest = line;
+    while !rest.is_empty() {
+        let (style, marker) = match rest.find(['*', '`']) {
+            Some(at) if rest[at..].starts_with("**") => {
+                push(Style::Plain, &rest[..at]);
+                rest = &rest[at..];
+                (Style::Bold, "**")
+            }
+            Some(at) if rest[at..].starts_with('`') => {
+                push(Style::Plain, &rest[..at]);
+                rest = &rest[at..];
+                (Style::Code, "`")
+            }
+            Some(at) => {
+                push(Style::Plain, &rest[..=at]);
+                rest = &rest[at + 1..];
+                continue;
+            }
+            None => {
+                push(Style::Plain, rest);
+                break;
+            }
+        };
+        let inner = &rest[marker.len()..];
+        match inner.find(marker) {
+            Some(end) if end > 0 => {
+                push(style, &inner[..end]);
+                rest = &inner[end + marker.len()..];
+            }
+            _ => {
+                // Unclosed or empty: the marker is text
+                push(Style::Plain, marker);
+                rest = inner;
+            }
+        }
+    }
+    spans
+}
+
+/// Parse a message into lines of styled spans
+pub fn parse(text: &str) -> Vec<Vec<Span>> {
+    text.lines().map(parse_line).collect()
+}
+
+/// Message without markers, line breaks joined with spaces
+pub fn strip(text: &str) -> String {
+    parse(text).iter()
+        .map(|line| line.iter().map(|span| span.text.as_str()).collect::<String>())
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Styled lines of a message (escape sequences, one string per line)
+pub fn render(text: &str) -> Vec<String> {
+    parse(text).iter().map(|line| render_line(line, usize::MAX)).collect()
+}
+
+/// Message styled on one line (line breaks joined with spaces), cut to `width` columns with an
+/// ellipsis; every style is closed again
+pub fn render_inline(text: &str, width: usize) -> String {
+    let mut spans: Vec<Span> = Vec::new();
+    for line in parse(text) {
+        if line.iter().all(|span| span.text.trim().is_empty()) {
+            continue;
+        }
+        if !spans.is_empty() {
+            spans.push(Span { style: Style::Plain, text: " ".to_string() });
+        }
+        spans.extend(line);
+    }
+    render_line(&spans, width)
+}
+
+/// Draw a line of spans within `width` columns, ending a cut with an ellipsis
+pub fn render_line(spans: &[Span], width: usize) -> String {
+    let total: usize = spans.iter().flat_map(|span| clusters(&span.text)).map(|(_, w)| w).sum();
+    let budget = if total > width { width.saturating_sub(1) } else { width };
+    let mut out = String::new();
+    let mut used = 0;
+    for span in spans {
+        let (on, off) = span.style.escapes();
+        let mut text = String::new();
+        let mut cut = false;
+        for (cluster, cluster_width) in clusters(&span.text) {
+            if used + cluster_width > budget {
+                cut = true;
+                break;
+            }
+            text.push_str(&cluster);
+            used += cluster_width;
+        }
+        if !text.is_empty() {
+            out.push_str(&format!("{}{}{}", on, text, off));
+        }
+        if cut {
+            break;
+        }
+    }
+    if total > width && width > 0 {
+        out.push('\u{2026}'); // Ellipsis
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markers() {
+        assert_eq!(parse_line("Run **cargo test** in `crates/core` now"), vec![
+            Span { style: Style::Plain, text: "Run ".to_string() },
+            Span { style: Style::Bold, text: "cargo test".to_string() },
+            Span { style: Style::Plain, text: " in ".to_string() },
+            Span { style: Style::Code, text: "crates/core".to_string() },
+            Span { style: Style::Plain, text: " now".to_string() },
+        ]);
+        // Unclosed and empty markers stay text; markers do not nest inside code
+        assert_eq!(strip("2 * 3 = **6"), "2 * 3 = **6");
+        assert_eq!(strip("empty ** and `` marks"), "empty ** and `` marks");
+        assert_eq!(strip("`a **b**` c"), "a **b** c");
+        assert_eq!(strip("**Permission needed**\nAllow `rm -rf target`?\r\n"), "Permission needed Allow rm -rf target?");
+    }
+
+    #[test]
+    fn test_render_styles_and_cuts() {
+        assert_eq!(render("**Done**\nsee `log`"), ["\x1b[1mDone\x1b[22m", "see \x1b[7mlog\x1b[27m"]);
+        assert_eq!(render_inline("**Build failed**\n3 errors", 40), "\x1b[1mBuild failed\x1b[22m 3 errors");
+        // A cut inside a styled run still closes it
+        assert_eq!(render_inline("**Build failed** now", 8), "\x1b[1mBuild f\x1b[22m\u{2026}");
+        assert_eq!(render_inline("plain", 5), "plain");
+    }
+}
@@ -0,0 +1,281 @@
+//! Usage metrics module for Zellij Visual Notifications
+//!
+//! Counts notifications by type and source, acknowledgements and the time they took, both for
+//! this plugin instance (session) and cumulatively (lifetime). The lifetime counters are persisted
+//! in the plugin data directory, at most every `METRICS_SAVE_INTERVAL_MS` while they change, so
+//! usage insights survive Zellij restarts. The `stats` command shows both; the `metrics` command
+//! prints the lifetime counters and the queue gauges in Prometheus exposition format for scraping.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use serde::{Deserialize, Serialize};
+use crate::lifecycle::{read_state, LifecycleContext, Subsystem};
+use crate::notification::Notification;
+use crate::queue::QueueStats;
+use crate::renderer::format_duration_ms;
+
+/// Default location of the persisted lifetime counters (plugin data directory)
+pub const METRICS_STATE_PATH: &str = "/data/metrics";
+
+/// Shortest time between two saves of changed counters (ms)
+pub const METRICS_SAVE_INTERVAL_MS: u64 = 30_000;
+
+/// Sources counted by name; later ones are counted as `other`
+pub const MAX_METRIC_SOURCES: usize = 50;
+
+/// Sources listed in the stats
+const TOP_SOURCES: usize = 5;
+
+/// Prefix of the exported metric names
+const METRIC_PREFIX: &str = "zellij_notifications";
+
+/// Cumulative counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Counters {
+    /// Notifications by type name
+    pub by_type: BTreeMap<String, u64>,
+    /// Notifications by source
+    pub by_source: BTreeMap<String, u64>,
+    /// Notifications acknowledged
+    pub acknowledged: u64,
+    /// Total time from arrival to acknowledgement (ms)
+    pub ack_latency_ms: u64,
+    /// Plugin instances counted
+    pub starts: u64,
+}
+
+impl Counters {
+    /// Notifications counted
+    pub fn total(&self) -> u64 {
+        self.by_type.values().sum()
+    }
+
+    /// Average time to acknowledge (ms)
+    pub fn average_ack_ms(&self) -> Option<u64> {
+        (self.acknowledged > 0).then(|| self.ack_latency_ms / self.acknowledged)
+    }
+
+    fn record_notification(&mut self, notification: &Notification) {
+        *self.by_type.entry(notification.notification_type.name().to_string()).or_default() += 1;
+        let source = if self.by_source.contains_key(&notification.source) || self.by_source.len() < MAX_METRIC_SOURCES {
+            notification.source.clone()
+        } else {
+            "other".to_string()
+        };
+        *self.by_source.entry(source).or_default() += 1;
+    }
+
+    fn record_ack(&mut self, latency_ms: u64) {
+        self.acknowledged += 1;
+        self.ack_latency_ms = self.ack_latency_ms.saturating_add(latency_ms);
+    }
+
+    /// Summary line, e.g. `session: 12 notifications (error 3, success 9), 4 acknowledged, avg ack 12s`
+    fn line(&self, label: &str) -> String {
+        let by_type: Vec<String> = self.by_type.iter().map(|(name, count)| format!("{} {}", name, count)).collect();
+        format!("{}: {} notifications{}, {} acknowledged, avg ack {}",
+            label,
+            self.total(),
+            if by_type.is_empty() { String::new() } else { format!(" ({})", by_type.join(", ")) },
+            self.acknowledged,
+            self.average_ack_ms().map(format_duration_ms).unwrap_or_else(|| "-".to_string()))
+    }
+}
+
+/// Session and lifetime counters
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    /// Since this plugin instance loaded
+    session: Counters,
+    /// Since the counters were first persisted
+    lifetime: Counters,
+    /// Lifetime counters changed since the last save
+    dirty: bool,
+    /// Plugin time of the last save (ms)
+    last_saved_ms: u64,
+}
+
+impl Metrics {
+    /// Parse lifetime counters from their persisted form (JSON) and count this start
+    pub fn parse(content: &str) -> Self {
+        let mut lifetime: Counters = serde_json::from_str(content).unwrap_or_default();
+        lifetime.starts += 1;
+        Self { lifetime, dirty: true, ..Self::default() }
+    }
+
+    /// Serialize the lifetime counters to their persisted form
+    pub fn serialize(&self) -> String {
+        serde_json::to_string(&self.lifetime).unwrap_or_default()
+    }
+
+    /// Save the lifetime counters to a file
+    pub fn save(&mut self, path: &str, now_ms: u64) -> std::io::Result<()> {
+        self.dirty = false;
+        self.last_saved_ms = now_ms;
+        std::fs::write(path, self.serialize())
+    }
+
+    /// Whether changed counters are due to be saved
+    pub fn save_due(&self, now_ms: u64) -> bool {
+        self.dirty && now_ms >= self.last_saved_ms + METRICS_SAVE_INTERVAL_MS
+    }
+
+    /// Count a queued notification
+    pub fn record_notification(&mut self, notification: &Notification) {
+        self.session.record_notification(notification);
+        self.lifetime.record_notification(notification);
+        self.dirty = true;
+    }
+
+    /// Count an acknowledgement that came `latency_ms` after the notification
+    pub fn record_ack(&mut self, latency_ms: u64) {
+        self.session.record_ack(latency_ms);
+        self.lifetime.record_ack(latency_ms);
+        self.dirty = true;
+    }
+
+    /// Stats report: session and lifetime lines and the busiest sources
+    pub fn lines(&self) -> Vec<String> {
+        let mut sources: Vec<(&String, &u64)> = self.lifetime.by_source.iter().collect();
+        sources.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let top: Vec<String> = sources.iter().take(TOP_SOURCES).map(|(name, count)| format!("{} {}", name, count)).collect();
+        vec![
+            self.session.line("session"),
+            format!("{} over {} starts", self.lifetime.line("lifetime"), self.lifetime.starts),
+            format!("top sources: {}", if top.is_empty() { "-".to_string() } else { top.join(", ") }),
+        ]
+    }
+
+    /// Prometheus exposition text: lifetime counters, acknowledgement latency and queue gauges
+    pub fn exposition(&self, queue: &QueueStats, unread: usize) -> String {
+        let mut out = String::new();
+        let mut family = |name: &str, kind: &str, help: &str, samples: &[(String, f64)]| {
+            let _ = writeln!(out, "# HELP {}_{} {}", METRIC_PREFIX, name, help);
+            let _ = writeln!(out, "# TYPE {}_{} {}", METRIC_PREFIX, name, kind);
+            for (suffix, value) in samples {
+                let _ = writeln!(out, "{}_{}{} {}", METRIC_PREFIX, name, suffix, value);
+            }
+        };
+
+        let by_type: Vec<(String, f64)> = self.lifetime.by_type.iter()
+            .map(|(name, count)| (format!("{{type=\"{}\"}}", name), *count as f64))
+            .collect();
+        family("notifications_total", "counter", "Notifications received, by type", &by_type);
+        family("acknowledged_total", "counter", "Notifications acknowledged", &[(String::new(), self.lifetime.acknowledged as f64)]);
+        family("ack_latency_seconds", "summary", "Time from arrival to acknowledgement", &[
+            ("_sum".to_string(), self.lifetime.ack_latency_ms as f64 / 1000.0),
+            ("_count".to_string(), self.lifetime.acknowledged as f64),
+        ]);
+        family("queue_depth", "gauge", "Notifications queued, by priority", &[
+            ("{priority=\"critical\"}".to_string(), queue.critical_count as f64),
+            ("{priority=\"high\"}".to_string(), queue.high_count as f64),
+            ("{priority=\"normal\"}".to_string(), queue.normal_count as f64),
+            ("{priority=\"low\"}".to_string(), queue.low_count as f64),
+        ]);
+        family("queue_bytes", "gauge", "Queued notification content", &[(String::new(), queue.queued_bytes as f64)]);
+        family("dropped_total", "counter", "Notifications dropped by the queue (this instance)", &[
+            ("{reason=\"expired\"}".to_string(), queue.total_expired as f64),
+            ("{reason=\"evicted\"}".to_string(), queue.total_evicted as f64),
+            ("{reason=\"rejected\"}".to_string(), queue.total_rejected as f64),
+        ]);
+        let by_source: Vec<(String, f64)> = queue.dequeued_by_source.iter()
+            .map(|(source, count)| (format!("{{source={:?}}}", source), *count as f64))
+            .collect();
+        family("dequeued_total", "counter", "Notifications taken off the queue, by source (this instance)", &by_source);
+        family("unread", "gauge", "Panes with an unread notification", &[(String::new(), unread as f64)]);
+        family("starts_total", "counter", "Plugin instances started", &[(String::new(), self.lifetime.starts as f64)]);
+        out
+    }
+}
+
+impl Subsystem for Metrics {
+    fn name(&self) -> &'static str {
+        "metrics"
+    }
+
+    fn init(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        *self = Self::parse(&read_state(METRICS_STATE_PATH)?.unwrap_or_default());
+        Ok(())
+    }
+
+    /// Counters are flushed on standby, as the primary counts from here on
+    fn suspend(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        self.shutdown(context)
+    }
+
+    fn shutdown(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        if !context.persist {
+            return Ok(());
+        }
+        self.save(METRICS_STATE_PATH, context.now_ms).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifetime_counters_survive_restart() {
+        let mut metrics = Metrics::parse("");
+        metrics.record_notification(&Notification::error("Build failed"));
+        metrics.record_notification(&Notification::success("Build done"));
+        metrics.record_ack(10_000);
+        metrics.record_ack(20_000);
+
+        let mut restarted = Metrics::parse(&metrics.serialize());
+        restarted.record_notification(&Notification::error("Build failed again"));
+        assert_eq!(restarted.session.total(), 1);
+        assert_eq!(restarted.lifetime.total(), 3);
+        assert_eq!(restarted.lifetime.average_ack_ms(), Some(15_000));
+        assert_eq!(restarted.lifetime.starts, 2);
+
+        let lines = restarted.lines();
+        assert_eq!(lines[0], "session: 1 notifications (error 1), 0 acknowledged, avg ack -");
+        assert_eq!(lines[1], "lifetime: 3 notifications (error 2, success 1), 2 acknowledged, avg ack 15s over 2 starts");
+        assert_eq!(lines[2], "top sources: unknown 3");
+    }
+
+    #[test]
+    fn test_saves_are_spaced_and_sources_capped() {
+        let mut metrics = Metrics::parse("{\"acknowledged\": 4, \"bogus\": true}");
+        assert_eq!(metrics.lifetime.acknowledged, 4);
+        assert!(metrics.save_due(METRICS_SAVE_INTERVAL_MS));
+        metrics.dirty = false;
+        metrics.last_saved_ms = METRICS_SAVE_INTERVAL_MS;
+        metrics.record_ack(1000);
+        assert!(!metrics.save_due(METRICS_SAVE_INTERVAL_MS + 1000));
+        assert!(metrics.save_due(2 * METRICS_SAVE_INTERVAL_MS));
+
+        for i in 0..MAX_METRIC_SOURCES + 2 {
+            metrics.record_notification(&Notification::info("tick").from_source(&format!("source-{}", i)));
+        }
+        assert_eq!(metrics.lifetime.by_source.len(), MAX_METRIC_SOURCES + 1);
+        assert_eq!(metrics.lifetime.by_source["other"], 2);
+    }
+
+    #[test]
+    fn test_exposition_format() {
+        let mut metrics = Metrics::parse("");
+        metrics.record_notification(&Notification::error("Build failed"));
+        metrics.record_notification(&Notification::error("Build failed again"));
+        metrics.record_notification(&Notification::attention("Waiting"));
+        metrics.record_ack(1500);
+        let queue = QueueStats { high_count: 2, normal_count: 1, total_queued: 3, queued_bytes: 420, ..QueueStats::default() };
+
+        let text = metrics.exposition(&queue, 1);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(&lines[..4], [
+            "# HELP zellij_notifications_notifications_total Notifications received, by type",
+            "# TYPE zellij_notifications_notifications_total counter",
+            "zellij_notifications_notifications_total{type=\"attention\"} 1",
+            "zellij_notifications_notifications_total{type=\"error\"} 2",
+        ]);
+        assert!(lines.contains(&"zellij_notifications_ack_latency_seconds_sum 1.5"));
+        assert!(lines.contains(&"zellij_notifications_ack_latency_seconds_count 1"));
+        assert!(lines.contains(&"zellij_notifications_queue_depth{priority=\"high\"} 2"));
+        assert!(lines.contains(&"zellij_notifications_unread 1"));
+        assert!(text.ends_with('\n'));
+    }
+}
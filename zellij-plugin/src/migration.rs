@@ -0,0 +1,212 @@
+//! State migration module for Zellij Visual Notifications
+//!
+//! `state_export <path>` writes the plugin's notification context - the notifications on display
+//! and in the queue, the history, the preferences and the notifications forwarded from other
+//! sessions - to one JSON file, and `state_import <path>` loads it into another plugin instance,
+//! so the context survives recreating a session or moving to another machine. The path must be
+//! reachable from the plugin, e.g. under `/host` (the directory Zellij was started in). Plugin
+//! times are stored as ages, since every instance counts from its own start. Imported
+//! notifications are shown again without passing rules or sinks, and keep their pane IDs.
+
+use serde::{Deserialize, Serialize};
+use crate::aggregator::{RemoteEntry, SessionAggregate};
+use crate::history::{HistoryEntry, NotificationHistory};
+use crate::notification::{Notification, NotificationType};
+use crate::prefs::Preferences;
+
+/// Version of the export format written by this plugin
+pub const STATE_EXPORT_VERSION: u32 = 1;
+
+/// A history entry, aged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedHistoryEntry {
+    /// Time since the notification was received (ms)
+    pub age_ms: u64,
+    pub notification: Notification,
+    #[serde(default)]
+    pub snapshot: Option<Vec<String>>,
+    /// Earlier identical entries merged into this one by history compaction
+    #[serde(default)]
+    pub repeats: u32,
+    /// Time since the first of the merged entries was received (ms)
+    #[serde(default)]
+    pub first_age_ms: Option<u64>,
+    /// Message of the first of the merged entries, if it differs
+    #[serde(default)]
+    pub first_message: Option<String>,
+}
+
+/// A notification forwarded from another session, aged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedRemoteEntry {
+    /// Origin session
+    pub session: String,
+    pub id: String,
+    pub notification_type: NotificationType,
+    pub message: String,
+    #[serde(default)]
+    pub pane_id: Option<u32>,
+    /// Time since the notification arrived (ms)
+    pub age_ms: u64,
+}
+
+/// Exported plugin state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateExport {
+    /// Format version
+    pub version: u32,
+    /// Notifications on display (pinned ones are part of the preferences)
+    #[serde(default)]
+    pub displayed: Vec<Notification>,
+    /// Notifications in the queue
+    #[serde(default)]
+    pub queued: Vec<Notification>,
+    /// Received notifications, oldest first
+    #[serde(default)]
+    pub history: Vec<ExportedHistoryEntry>,
+    #[serde(default)]
+    pub prefs: Preferences,
+    /// Notifications forwarded from other sessions, oldest first per session
+    #[serde(default)]
+    pub sessions: Vec<ExportedRemoteEntry>,
+}
+
+impl StateExport {
+    /// Capture the state at plugin time `now_ms`
+    pub fn capture(
+        displayed: Vec<Notification>,
+        queued: Vec<Notification>,
+        history: &NotificationHistory,
+        prefs: Preferences,
+        aggregate: &SessionAggregate,
+        now_ms: u64,
+    ) -> Self {
+        let mut history: Vec<ExportedHistoryEntry> = history.newest_first()
+            .map(|entry| ExportedHistoryEntry {
+                age_ms: now_ms.saturating_sub(entry.received_at_ms),
+                notification: entry.notification.clone(),
+                snapshot: entry.snapshot.clone(),
+                repeats: entry.repeats,
+                first_age_ms: (entry.repeats > 0).then(|| now_ms.saturating_sub(entry.first_received_at_ms)),
+                first_message: entry.first_message.clone(),
+            })
+            .collect();
+        history.reverse();
+        let sessions = aggregate.sessions()
+            .flat_map(|(session, entries)| entries.iter().map(move |entry| ExportedRemoteEntry {
+                session: session.to_string(),
+                id: entry.id.clone(),
+                notification_type: entry.notification_type.clone(),
+                message: entry.message.clone(),
+                pane_id: entry.pane_id,
+                age_ms: now_ms.saturating_sub(entry.received_ms),
+            }))
+            .collect();
+        Self { version: STATE_EXPORT_VERSION, displayed, queued, history, prefs, sessions }
+    }
+
+    /// History entries dated back from plugin time `now_ms`, oldest first
+    pub fn history_entries(&self, now_ms: u64) -> Vec<HistoryEntry> {
+        self.history.iter()
+            .map(|entry| HistoryEntry {
+                received_at_ms: now_ms.saturating_sub(entry.age_ms),
+                notification: entry.notification.clone(),
+                ack_escalated_at_ms: None,
+                snapshot: entry.snapshot.clone(),
+                repeats: entry.repeats,
+                first_received_at_ms: now_ms.saturating_sub(entry.first_age_ms.unwrap_or(entry.age_ms)),
+                first_message: entry.first_message.clone(),
+            })
+            .collect()
+    }
+
+    /// Forwarded entries by origin session, dated back from plugin time `now_ms`
+    pub fn remote_entries(&self, now_ms: u64) -> Vec<(String, RemoteEntry)> {
+        self.sessions.iter()
+            .map(|entry| (entry.session.clone(), RemoteEntry {
+                id: entry.id.clone(),
+                notification_type: entry.notification_type.clone(),
+                message: entry.message.clone(),
+                pane_id: entry.pane_id,
+                received_ms: now_ms.saturating_sub(entry.age_ms),
+            }))
+            .collect()
+    }
+
+    /// Short description, e.g. `2 notification(s), 40 history entries, 3 forwarded`
+    pub fn summary(&self) -> String {
+        format!("{} notification(s), {} history entries, {} forwarded",
+            self.displayed.len() + self.queued.len() + self.prefs.pinned.len(),
+            self.history.len(),
+            self.sessions.len())
+    }
+
+    /// Parse an export (JSON), rejecting versions newer than this plugin writes
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let export: Self = serde_json::from_str(content).map_err(|e| format!("invalid state export: {}", e))?;
+        if export.version > STATE_EXPORT_VERSION {
+            return Err(format!("unsupported state export version {} (up to {} supported)",
+                export.version, STATE_EXPORT_VERSION));
+        }
+        Ok(export)
+    }
+
+    /// Serialize the export
+    pub fn serialize(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Load an export from a file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+        Self::parse(&content)
+    }
+
+    /// Write the export to a file
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        std::fs::write(path, self.serialize()).map_err(|e| format!("can't write {}: {}", path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_round_trips_with_ages() {
+        let mut history = NotificationHistory::new(10);
+        let build = Notification::error("Build failed").for_pane(3);
+        history.record(&Notification::info("Started"), 1_000);
+        history.record(&build, 4_000);
+        history.attach_snapshot(&build.id, vec!["error[E0308]".to_string()]);
+        let mut aggregate = SessionAggregate::default();
+        aggregate.record("api", &Notification::success("Deployed"), 2_000);
+        let export = StateExport::capture(
+            vec![build.clone()],
+            vec![Notification::warning("Lint")],
+            &history,
+            Preferences { dnd: true, ..Preferences::default() },
+            &aggregate,
+            10_000,
+        );
+        assert_eq!(export.summary(), "2 notification(s), 2 history entries, 1 forwarded");
+
+        // A new instance dates the entries back from its own clock
+        let imported = StateExport::parse(&export.serialize()).unwrap();
+        let entries = imported.history_entries(100_000);
+        assert_eq!(entries.iter().map(|entry| entry.received_at_ms).collect::<Vec<_>>(), vec![91_000, 94_000]);
+        assert_eq!(entries[1].snapshot.as_deref(), Some(&["error[E0308]".to_string()][..]));
+        let remote = imported.remote_entries(100_000);
+        assert_eq!((remote[0].0.as_str(), remote[0].1.received_ms), ("api", 92_000));
+        assert_eq!(imported.displayed[0].id, build.id);
+        assert!(imported.prefs.dnd);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_versions() {
+        assert!(StateExport::parse("{\"version\": 1}").unwrap().history.is_empty());
+        assert!(StateExport::parse("{\"version\": 2}").unwrap_err().contains("version 2"));
+        assert!(StateExport::parse("{}").is_err());
+        assert!(StateExport::load("/nonexistent/state.json").unwrap_err().starts_with("can't read"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Pane mute module for Zellij Visual Notifications
+//!
+//! Keeps the set of panes whose visual updates are suppressed and persists it in the plugin
+//! data directory so mutes survive plugin reloads.
+
+use std::collections::BTreeSet;
+use crate::lifecycle::{read_state, LifecycleContext, Subsystem};
+
+/// Default location of the persisted mute list (plugin data directory)
+pub const MUTE_STATE_PATH: &str = "/data/muted_panes";
+
+/// Set of muted panes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MuteList {
+    /// Muted pane IDs
+    panes: BTreeSet<u32>,
+}
+
+impl MuteList {
+    /// Create an empty mute list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a mute list from its persisted form (whitespace separated pane IDs)
+    pub fn parse(content: &str) -> Self {
+        Self {
+            panes: content
+                .split_whitespace()
+                .filter_map(|id| id.parse().ok())
+                .collect(),
+        }
+    }
+
+    /// Serialize the mute list to its persisted form
+    pub fn serialize(&self) -> String {
+        self.panes.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Save the mute list to a file
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.serialize())
+    }
+
+    /// Mute a pane, returning whether it was newly muted
+    pub fn mute(&mut self, pane_id: u32) -> bool {
+        self.panes.insert(pane_id)
+    }
+
+    /// Unmute a pane, returning whether it was muted
+    pub fn unmute(&mut self, pane_id: u32) -> bool {
+        self.panes.remove(&pane_id)
+    }
+
+    /// Toggle mute for a pane, returning the new muted state
+    pub fn toggle(&mut self, pane_id: u32) -> bool {
+        if self.unmute(pane_id) {
+            false
+        } else {
+            self.mute(pane_id)
+        }
+    }
+
+    /// Check if a pane is muted
+    pub fn is_muted(&self, pane_id: u32) -> bool {
+        self.panes.contains(&pane_id)
+    }
+
+    /// Iterate over muted pane IDs
+    pub fn iter(&self) -> impl Iterator<Item = &u32> {
+        self.panes.iter()
+    }
+}
+
+impl Subsystem for MuteList {
+    fn name(&self) -> &'static str {
+        "muted_panes"
+    }
+
+    fn init(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        *self = read_state(MUTE_STATE_PATH)?.map(|content| Self::parse(&content)).unwrap_or_default();
+        Ok(())
+    }
+
+    fn shutdown(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        if !context.persist {
+            return Ok(());
+        }
+        self.save(MUTE_STATE_PATH).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mute_toggle() {
+        let mut mutes = MuteList::new();
+        assert!(mutes.toggle(3));
+        assert!(mutes.is_muted(3));
+        assert!(!mutes.toggle(3));
+        assert!(!mutes.is_muted(3));
+    }
+
+    #[test]
+    fn test_mute_list_round_trip() {
+        let mut mutes = MuteList::new();
+        mutes.mute(7);
+        mutes.mute(2);
+        assert_eq!(mutes.serialize(), "2 7");
+        assert_eq!(MuteList::parse("2 7 bogus\n"), mutes);
+    }
+}
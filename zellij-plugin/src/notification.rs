@@ -3,6 +3,7 @@
 //! Defines notification types, structures, and processing logic.
 
 use serde::{Deserialize, Serialize};
+use crate::attention::AttentionReason;
 
 /// Notification type enumeration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -27,7 +28,40 @@ impl Default for NotificationType {
     }
 }
 
+/// Frame set of an animated icon, stepped through once per timer tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IconFrames(pub &'static [&'static str]);
+
+impl IconFrames {
+    /// Braille dots spinner
+    pub const BRAILLE: IconFrames = IconFrames(&[
+        "\u{280B}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283C}",
+        "\u{2834}", "\u{2826}", "\u{2827}", "\u{2807}", "\u{280F}",
+    ]);
+    /// ASCII line spinner
+    pub const LINE: IconFrames = IconFrames(&["|", "/", "-", "\\"]);
+
+    /// Frame for a step, wrapping around the set
+    pub fn frame(&self, step: u64) -> &'static str {
+        self.0[(step % self.0.len() as u64) as usize]
+    }
+
+    /// Whether a character is one of the frames
+    pub fn contains(&self, c: char) -> bool {
+        self.0.iter().any(|frame| frame.starts_with(c))
+    }
+}
+
 impl NotificationType {
+    /// Get the spinner frames for types with an animated icon (Progress only)
+    pub fn icon_frames(&self, unicode: bool) -> Option<IconFrames> {
+        match self {
+            NotificationType::Progress if unicode => Some(IconFrames::BRAILLE),
+            NotificationType::Progress => Some(IconFrames::LINE),
+            _ => None,
+        }
+    }
+
     /// Get the icon for this notification type
     pub fn icon(&self) -> Option<String> {
         Some(match self {
@@ -52,6 +86,21 @@ impl NotificationType {
         }
     }
 
+    /// Parse a canonical type name (unlike `from_str`, unknown names are rejected)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        [
+            NotificationType::Success,
+            NotificationType::Error,
+            NotificationType::Warning,
+            NotificationType::Info,
+            NotificationType::Progress,
+            NotificationType::Attention,
+        ]
+        .into_iter()
+        .find(|t| t.name() == name)
+    }
+
     /// Get urgency level (0 = low, 1 = normal, 2 = high, 3 = critical)
     pub fn urgency(&self) -> u8 {
         match self {
@@ -102,6 +151,87 @@ impl Default for Priority {
     }
 }
 
+impl Priority {
+    /// Get the display name for this priority
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+            Priority::Critical => "critical",
+        }
+    }
+
+    /// Parse a priority name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "low" => Some(Priority::Low),
+            "normal" => Some(Priority::Normal),
+            "high" => Some(Priority::High),
+            "critical" => Some(Priority::Critical),
+            _ => None,
+        }
+    }
+
+    /// The next higher priority, if any
+    pub fn raised(&self) -> Option<Self> {
+        match self {
+            Priority::Low => Some(Priority::Normal),
+            Priority::Normal => Some(Priority::High),
+            Priority::High => Some(Priority::Critical),
+            Priority::Critical => None,
+        }
+    }
+
+    /// The next lower priority, if any
+    pub fn lowered(&self) -> Option<Self> {
+        match self {
+            Priority::Low => None,
+            Priority::Normal => Some(Priority::Low),
+            Priority::High => Some(Priority::Normal),
+            Priority::Critical => Some(Priority::High),
+        }
+    }
+}
+
+/// What happens when a notification's TTL runs out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpiryAction {
+    /// Remove it (expiry is reported to the sinks)
+    #[default]
+    Discard,
+    /// Keep it as a badge-only Low notification for another TTL (Error becomes Warning)
+    Demote,
+    /// Remove it silently; it stays in the history
+    Archive,
+    /// Remove it and escalate it out of terminal (desktop, webhook)
+    Escalate,
+}
+
+impl ExpiryAction {
+    /// Parse an action name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "discard" => Some(Self::Discard),
+            "demote" => Some(Self::Demote),
+            "archive" => Some(Self::Archive),
+            "escalate" => Some(Self::Escalate),
+            _ => None,
+        }
+    }
+
+    /// Action name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Discard => "discard",
+            Self::Demote => "demote",
+            Self::Archive => "archive",
+            Self::Escalate => "escalate",
+        }
+    }
+}
+
 impl From<&NotificationType> for Priority {
     fn from(notification_type: &NotificationType) -> Self {
         match notification_type {
@@ -136,10 +266,78 @@ pub struct Notification {
     pub timestamp: u64,
     /// Time-to-live in milliseconds (0 = no expiry)
     pub ttl_ms: u64,
+    /// Time counted against the TTL so far (ms; stops while TTL is paused)
+    #[serde(skip)]
+    pub ttl_elapsed_ms: u64,
     /// Source of the notification
     pub source: String,
     /// Additional metadata
     pub metadata: NotificationMetadata,
+    /// Whether the notification was escalated (e.g. user idle) for out-of-band delivery
+    #[serde(default)]
+    pub escalated: bool,
+    /// Sender-provided color override (validated #rrggbb)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Sender-provided icon override (sanitized, at most two characters)
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Completion percent of a progress notification (0 - 100)
+    #[serde(default)]
+    pub percent: Option<f32>,
+    /// Channel the notification belongs to (e.g. `ci`), independent of its type
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Why Claude needs attention (from the hook payload)
+    #[serde(default)]
+    pub reason: Option<AttentionReason>,
+    /// Workflow the notification belongs to; notifications sharing it are grouped across panes
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Claude Code session that sent the notification
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Working directory the notification comes from (decides its project)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Git branch the sender works on
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Git worktree the sender works in (path)
+    #[serde(default)]
+    pub worktree: Option<String>,
+    /// Zellij session the notification was forwarded from
+    #[serde(default)]
+    pub origin_session: Option<String>,
+    /// Sender sequence number (per source), used to restore the sending order
+    #[serde(default)]
+    pub seq: Option<u64>,
+    /// What happens when the TTL runs out
+    #[serde(default)]
+    pub on_expire: ExpiryAction,
+    /// Whether the notification was demoted on expiry (shown as a badge only)
+    #[serde(default)]
+    pub demoted: bool,
+    /// Whether the sender asked for delivery receipts
+    #[serde(default)]
+    pub request_ack: bool,
+    /// Whether the notification repeats a message acknowledged shortly before (shown as a badge only)
+    #[serde(default)]
+    pub dimmed: bool,
+    /// Re-arm interval (ms) of a notification that stays until acknowledged, re-animating briefly
+    /// each interval without escalating
+    #[serde(default)]
+    pub recur_until_ack_ms: Option<u64>,
+    /// Time counted towards the next re-arm (ms)
+    #[serde(default)]
+    pub recur_elapsed_ms: u64,
+    /// Answers offered for a quick reply (attention notifications only), typed into the pane
+    #[serde(default)]
+    pub reply_options: Vec<String>,
+    /// Time the sender gives for a response (ms from arrival); counted down on the entry, which
+    /// turns overdue once it has passed
+    #[serde(default)]
+    pub deadline_ms: Option<u64>,
 }
 
 impl Default for Notification {
@@ -154,8 +352,30 @@ impl Default for Notification {
             priority: Priority::Normal,
             timestamp: 0,
             ttl_ms: 300_000, // 5 minutes default
+            ttl_elapsed_ms: 0,
             source: "unknown".to_string(),
             metadata: NotificationMetadata::default(),
+            escalated: false,
+            color: None,
+            icon: None,
+            percent: None,
+            channel: None,
+            reason: None,
+            correlation_id: None,
+            session_id: None,
+            cwd: None,
+            branch: None,
+            worktree: None,
+            origin_session: None,
+            seq: None,
+            on_expire: ExpiryAction::Discard,
+            demoted: false,
+            request_ack: false,
+            dimmed: false,
+            recur_until_ack_ms: None,
+            recur_elapsed_ms: 0,
+            reply_options: Vec::new(),
+            deadline_ms: None,
         }
     }
 }
@@ -165,7 +385,6 @@ impl Notification {
     pub fn new(notification_type: NotificationType, message: &str) -> Self {
         let priority = Priority::from(&notification_type);
         Self {
-            id: generate_id(),
             notification_type,
             message: message.to_string(),
             priority,
@@ -253,6 +472,95 @@ impl Notification {
         current_time > self.timestamp + self.ttl_ms
     }
 
+    /// Get the remaining time-to-live in milliseconds (None = no expiry)
+    pub fn ttl_remaining(&self, current_time: u64) -> Option<u64> {
+        if self.ttl_ms == 0 {
+            return None;
+        }
+        Some((self.timestamp + self.ttl_ms).saturating_sub(current_time))
+    }
+
+    /// Count elapsed time against the TTL
+    pub fn advance_ttl(&mut self, elapsed_ms: u64) {
+        self.ttl_elapsed_ms = self.ttl_elapsed_ms.saturating_add(elapsed_ms);
+    }
+
+    /// Push the expiry back by `extra_ms` (time already counted stays counted); false if the
+    /// notification never expires
+    pub fn extend_ttl(&mut self, extra_ms: u64) -> bool {
+        if self.ttl_ms == 0 {
+            return false;
+        }
+        self.ttl_ms = self.ttl_ms.saturating_add(extra_ms);
+        true
+    }
+
+    /// Drop the expiry so the notification stays until acknowledged; false if it never expired
+    pub fn make_sticky(&mut self) -> bool {
+        if self.ttl_ms == 0 {
+            return false;
+        }
+        self.ttl_ms = 0;
+        self.ttl_elapsed_ms = 0;
+        true
+    }
+
+    /// Check if the TTL is used up by counted time (see [`Notification::advance_ttl`])
+    pub fn is_ttl_spent(&self) -> bool {
+        self.ttl_ms != 0 && self.recur_until_ack_ms.is_none() && self.ttl_elapsed_ms > self.ttl_ms
+    }
+
+    /// Count time towards the next re-arm of a recurring notification; true when it is due
+    pub fn advance_recurrence(&mut self, elapsed_ms: u64) -> bool {
+        let Some(interval_ms) = self.recur_until_ack_ms else {
+            return false;
+        };
+        self.recur_elapsed_ms = self.recur_elapsed_ms.saturating_add(elapsed_ms);
+        if self.recur_elapsed_ms < interval_ms {
+            return false;
+        }
+        self.recur_elapsed_ms = 0;
+        true
+    }
+
+    /// Remaining TTL by counted time in milliseconds (None = no expiry)
+    pub fn ttl_left(&self) -> Option<u64> {
+        if self.ttl_ms == 0 {
+            return None;
+        }
+        Some(self.ttl_ms.saturating_sub(self.ttl_elapsed_ms))
+    }
+
+    /// Demote an expired notification with `on_expire: demote`: Error becomes Warning, priority
+    /// drops to Low and the TTL starts over, after which it is archived. False if not demotable.
+    pub fn demote(&mut self) -> bool {
+        if self.on_expire != ExpiryAction::Demote {
+            return false;
+        }
+        if self.notification_type == NotificationType::Error {
+            self.notification_type = NotificationType::Warning;
+        }
+        self.priority = Priority::Low;
+        self.ttl_elapsed_ms = 0;
+        self.on_expire = ExpiryAction::Archive;
+        self.demoted = true;
+        true
+    }
+
+    /// Approximate memory footprint of the notification's text content in bytes
+    pub fn approx_size(&self) -> usize {
+        self.message.len()
+            + self.title.as_ref().map(|t| t.len()).unwrap_or(0)
+            + self.source.len()
+            + self.id.len()
+    }
+
+    /// Branch context shown next to its entry: the branch, or else the worktree's directory name
+    pub fn branch_label(&self) -> Option<&str> {
+        self.branch.as_deref()
+            .or_else(|| self.worktree.as_deref().map(|path| path.rsplit('/').next().unwrap_or(path)))
+    }
+
     /// Get the notification icon
     pub fn icon(&self) -> Option<String> {
         self.notification_type.icon()
@@ -281,28 +589,33 @@ pub struct NotificationMetadata {
     pub custom: Option<serde_json::Value>,
 }
 
-/// Generate a unique notification ID
-fn generate_id() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    let duration = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
-
-    format!("notif-{}-{}", duration.as_millis(), rand_u32())
+/// Validate and normalize a sender-provided color (`#rrggbb` or `rrggbb`)
+pub fn sanitize_color(color: &str) -> Option<String> {
+    let hex = color.trim().trim_start_matches('#');
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("#{}", hex.to_lowercase()))
+    } else {
+        None
+    }
 }
 
-/// Simple pseudo-random number generator (WASM compatible)
-fn rand_u32() -> u32 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    let time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
+/// Sanitize a sender-provided icon (no control/whitespace characters, at most two characters)
+pub fn sanitize_icon(icon: &str) -> Option<String> {
+    let cleaned: String = icon
+        .chars()
+        .filter(|c| !c.is_control() && !c.is_whitespace())
+        .take(2)
+        .collect();
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
 
-    // Simple LCG-based PRNG
-    let seed = time.as_nanos() as u32;
-    seed.wrapping_mul(1103515245).wrapping_add(12345)
+/// Generate a unique notification ID from the installed generator
+fn generate_id() -> String {
+    crate::clock::next_id()
 }
 
 /// Builder for creating notifications
@@ -325,6 +638,12 @@ impl NotificationBuilder {
         self
     }
 
+    /// Set the ID (overrides the generated one)
+    pub fn id(mut self, id: &str) -> Self {
+        self.notification.id = id.to_string();
+        self
+    }
+
     /// Set the message
     pub fn message(mut self, msg: &str) -> Self {
         self.notification.message = msg.to_string();
@@ -391,6 +710,115 @@ impl NotificationBuilder {
         self
     }
 
+    /// Set a color override (ignored if invalid)
+    pub fn color(mut self, color: &str) -> Self {
+        self.notification.color = sanitize_color(color);
+        self
+    }
+
+    /// Set an icon override (ignored if empty after sanitizing)
+    pub fn icon(mut self, icon: &str) -> Self {
+        self.notification.icon = sanitize_icon(icon);
+        self
+    }
+
+    /// Set the completion percent (clamped to 0 - 100)
+    pub fn percent(mut self, percent: f32) -> Self {
+        self.notification.percent = Some(percent.clamp(0.0, 100.0));
+        self
+    }
+
+    /// Set the attention reason
+    pub fn reason(mut self, reason: AttentionReason) -> Self {
+        self.notification.reason = Some(reason);
+        self
+    }
+
+    /// Set the channel (ignored if blank)
+    pub fn channel(mut self, channel: &str) -> Self {
+        let channel = channel.trim();
+        self.notification.channel = (!channel.is_empty()).then(|| channel.to_string());
+        self
+    }
+
+    /// Set the correlation ID (ignored if blank)
+    pub fn correlation_id(mut self, correlation_id: &str) -> Self {
+        let correlation_id = correlation_id.trim();
+        self.notification.correlation_id = (!correlation_id.is_empty()).then(|| correlation_id.to_string());
+        self
+    }
+
+    /// Set the Claude Code session ID (ignored if blank)
+    pub fn session_id(mut self, session_id: &str) -> Self {
+        let session_id = session_id.trim();
+        self.notification.session_id = (!session_id.is_empty()).then(|| session_id.to_string());
+        self
+    }
+
+    /// Set the working directory (ignored if blank)
+    pub fn cwd(mut self, cwd: &str) -> Self {
+        let cwd = cwd.trim();
+        self.notification.cwd = (!cwd.is_empty()).then(|| cwd.to_string());
+        self
+    }
+
+    /// Set the git branch (ignored if blank)
+    pub fn branch(mut self, branch: &str) -> Self {
+        let branch = branch.trim();
+        self.notification.branch = (!branch.is_empty()).then(|| branch.to_string());
+        self
+    }
+
+    /// Set the git worktree path (ignored if blank)
+    pub fn worktree(mut self, worktree: &str) -> Self {
+        let worktree = worktree.trim().trim_end_matches('/');
+        self.notification.worktree = (!worktree.is_empty()).then(|| worktree.to_string());
+        self
+    }
+
+    /// Set the session the notification was forwarded from (ignored if blank)
+    pub fn origin_session(mut self, session: &str) -> Self {
+        let session = session.trim();
+        self.notification.origin_session = (!session.is_empty()).then(|| session.to_string());
+        self
+    }
+
+    /// Set the expiry action
+    pub fn on_expire(mut self, action: ExpiryAction) -> Self {
+        self.notification.on_expire = action;
+        self
+    }
+
+    /// Ask for delivery receipts
+    pub fn request_ack(mut self, request_ack: bool) -> Self {
+        self.notification.request_ack = request_ack;
+        self
+    }
+
+    /// Re-arm every `secs` seconds until acknowledged (ignored if 0)
+    pub fn recur_until_ack(mut self, secs: u64) -> Self {
+        self.notification.recur_until_ack_ms = (secs > 0).then(|| secs.saturating_mul(1000));
+        self
+    }
+
+    /// Set the response deadline in milliseconds from arrival (ignored if 0)
+    pub fn deadline(mut self, deadline_ms: u64) -> Self {
+        self.notification.deadline_ms = (deadline_ms > 0).then_some(deadline_ms);
+        self
+    }
+
+    /// Set the quick reply options (sanitized, at most nine)
+    pub fn reply_options(mut self, options: &[String]) -> Self {
+        self.notification.reply_options = crate::quick_reply::sanitize(options);
+        self
+    }
+
+    /// Set the sender sequence number
+    pub fn seq(mut self, seq: u64) -> Self {
+        self.notification.seq = Some(seq);
+        self
+    }
+
     /// Build the notification
     pub fn build(self) -> Notification {
         self.notification
@@ -414,6 +842,13 @@ mod tests {
         assert_eq!(notif.message, "Build completed");
     }
 
+    #[test]
+    fn test_notification_ids_from_installed_generator() {
+        crate::clock::set_id_generator(Box::new(crate::clock::SequentialIdGenerator::new()));
+        assert_eq!(Notification::info("First").id, "notif-1");
+        assert_eq!(Notification::error("Second").id, "notif-2");
+    }
+
     #[test]
     fn test_notification_builder() {
         let notif = NotificationBuilder::new()
@@ -428,6 +863,18 @@ mod tests {
         assert_eq!(notif.pane_id, Some(42));
         assert_eq!(notif.metadata.command, Some("npm test".to_string()));
         assert_eq!(notif.metadata.exit_code, Some(1));
+        assert_eq!(notif.branch_label(), None);
+    }
+
+    #[test]
+    fn test_branch_label() {
+        let worktree = NotificationBuilder::new().worktree("/home/me/repo-login/ ").build();
+        assert_eq!(worktree.worktree.as_deref(), Some("/home/me/repo-login"));
+        assert_eq!(worktree.branch_label(), Some("repo-login"));
+
+        let both = NotificationBuilder::new().worktree("/home/me/repo-login").branch(" feat/login ").build();
+        assert_eq!(both.branch_label(), Some("feat/login"));
+        assert_eq!(NotificationBuilder::new().branch("  ").build().branch, None);
     }
 
     #[test]
@@ -456,6 +903,52 @@ mod tests {
         assert!(notif.is_expired(7000));
     }
 
+    #[test]
+    fn test_notification_ttl_remaining() {
+        let notif = Notification::info("Test").at_time(1000).with_ttl(5000);
+        assert_eq!(notif.ttl_remaining(2000), Some(4000));
+        assert_eq!(notif.ttl_remaining(9000), Some(0));
+
+        let sticky = Notification::info("Sticky").with_ttl(0);
+        assert_eq!(sticky.ttl_remaining(9000), None);
+        let mut kept = Notification::info("Kept").with_ttl(1000);
+        assert!(kept.make_sticky());
+        assert!(!kept.make_sticky());
+        assert_eq!(kept.ttl_left(), None);
+
+        let mut counted = Notification::info("Counted").with_ttl(5000);
+        counted.advance_ttl(4000);
+        assert_eq!(counted.ttl_left(), Some(1000));
+        assert!(!counted.is_ttl_spent());
+        counted.advance_ttl(1001);
+        assert!(counted.is_ttl_spent());
+
+        // A recurring notification outlives its TTL and re-arms each interval
+        let mut recurring = NotificationBuilder::new()
+            .notification_type(NotificationType::Error)
+            .message("Tests red")
+            .ttl(1000)
+            .recur_until_ack(2)
+            .build();
+        recurring.advance_ttl(5000);
+        assert!(!recurring.is_ttl_spent());
+        assert!(!recurring.advance_recurrence(1500));
+        assert!(recurring.advance_recurrence(500));
+        assert!(!recurring.advance_recurrence(1500));
+    }
+
+    #[test]
+    fn test_sanitize_overrides() {
+        assert_eq!(sanitize_color("#FF00ff"), Some("#ff00ff".to_string()));
+        assert_eq!(sanitize_color("00ff00"), Some("#00ff00".to_string()));
+        assert_eq!(sanitize_color("red"), None);
+        assert_eq!(sanitize_color("#ff00f"), None);
+
+        assert_eq!(sanitize_icon("\u{1F680}"), Some("\u{1F680}".to_string()));
+        assert_eq!(sanitize_icon("\x1b[31mX"), Some("[3".to_string()));
+        assert_eq!(sanitize_icon(" \n"), None);
+    }
+
     #[test]
     fn test_priority_from_type() {
         assert_eq!(Priority::from(&NotificationType::Info), Priority::Low);
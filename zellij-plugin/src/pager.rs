@@ -0,0 +1,94 @@
+//! Pager module for Zellij Visual Notifications
+//!
+//! Long messages (multi-paragraph explanations) don't fit the detail view, so their wrapped lines
+//! are shown a page at a time: PgDn and PgUp turn pages, Home and End jump to the first and last
+//! one, and the header shows the position (`2/5`). The page is kept as a number and clamped to
+//! the pages there are when used, so a resized pane or a shorter message never leaves it past
+//! the end.
+
+/// Position in a paged view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pager {
+    /// Page shown, from 0 (may be past the last page until clamped)
+    page: usize,
+}
+
+impl Pager {
+    /// Number of pages for `lines` lines shown `rows` at a time (at least one)
+    pub fn page_count(lines: usize, rows: usize) -> usize {
+        lines.div_ceil(rows.max(1)).max(1)
+    }
+
+    /// Page shown, clamped to `count` pages
+    pub fn page(&self, count: usize) -> usize {
+        self.page.min(count.saturating_sub(1))
+    }
+
+    /// Turn to the next page, returning whether there was one
+    pub fn next_page(&mut self, count: usize) -> bool {
+        let page = self.page(count);
+        self.page = (page + 1).min(count.saturating_sub(1));
+        self.page != page
+    }
+
+    /// Turn to the previous page, returning whether there was one
+    pub fn previous_page(&mut self, count: usize) -> bool {
+        let page = self.page(count);
+        self.page = page.saturating_sub(1);
+        self.page != page
+    }
+
+    /// Jump to the first page, returning whether the page changed
+    pub fn first_page(&mut self, count: usize) -> bool {
+        let page = self.page(count);
+        self.page = 0;
+        page != 0
+    }
+
+    /// Jump to the last page, returning whether the page changed
+    pub fn last_page(&mut self, count: usize) -> bool {
+        let page = self.page(count);
+        self.page = count.saturating_sub(1);
+        self.page != page
+    }
+
+    /// Lines of the page shown, `rows` per page
+    pub fn visible<'a>(&self, lines: &'a [String], rows: usize) -> &'a [String] {
+        let rows = rows.max(1);
+        let start = self.page(Self::page_count(lines.len(), rows)) * rows;
+        &lines[start.min(lines.len())..(start + rows).min(lines.len())]
+    }
+
+    /// Position indicator, e.g. `2/5`
+    pub fn indicator(&self, count: usize) -> String {
+        format!("{}/{}", self.page(count) + 1, count.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns_pages_within_bounds() {
+        let lines: Vec<String> = (1..=7).map(|n| format!("line {}", n)).collect();
+        let count = Pager::page_count(lines.len(), 3);
+        assert_eq!(count, 3);
+
+        let mut pager = Pager::default();
+        assert_eq!(pager.indicator(count), "1/3");
+        assert!(!pager.previous_page(count));
+        assert!(pager.next_page(count));
+        assert_eq!(pager.visible(&lines, 3), &lines[3..6]);
+        assert!(pager.last_page(count));
+        assert!(!pager.next_page(count));
+        assert_eq!(pager.visible(&lines, 3), &lines[6..]);
+        assert_eq!(pager.indicator(count), "3/3");
+
+        // A taller pane has fewer pages: the last one is shown
+        assert_eq!(pager.visible(&lines, 5), &lines[5..]);
+        assert_eq!(pager.indicator(Pager::page_count(lines.len(), 5)), "2/2");
+        assert!(pager.first_page(count));
+        assert_eq!(Pager::page_count(0, 3), 1);
+    }
+}
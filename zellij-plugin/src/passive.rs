@@ -0,0 +1,234 @@
+//! Passive detection module for Zellij Visual Notifications
+//!
+//! Panes without a hook integration can still report finished commands: most shells set the
+//! pane title to the running command and back to the shell (or the prompt title) when it ends.
+//! Diffing the title before and after turns that into a completion message such as
+//! `` `cargo build` finished (title reverted to zsh) ``. Short commands are ignored, and panes
+//! whose titles change constantly (prompts showing the time, progress in the title) are left
+//! alone while they churn.
+
+use std::collections::{BTreeMap, VecDeque};
+use crate::notification::{Notification, NotificationBuilder, NotificationType};
+
+/// Default completion message; `{command}`, `{title}`, `{shell}` and `{duration}` are replaced
+pub const DEFAULT_PASSIVE_TEMPLATE: &str = "`{command}` finished (title reverted to {shell})";
+
+/// Default shortest command run reported (ms)
+pub const DEFAULT_PASSIVE_MIN_DURATION_MS: u64 = 10_000;
+
+/// Default title changes within [`TITLE_CHURN_WINDOW_MS`] above which a pane is ignored
+pub const DEFAULT_PASSIVE_MAX_TITLE_CHANGES: usize = 6;
+
+/// Window title changes are counted in (ms)
+pub const TITLE_CHURN_WINDOW_MS: u64 = 10_000;
+
+/// Default shell names a title reverts to
+pub const DEFAULT_PASSIVE_SHELLS: [&str; 11] =
+    ["bash", "zsh", "fish", "sh", "dash", "ksh", "tcsh", "nu", "pwsh", "xonsh", "elvish"];
+
+/// A command run detected from a pane title
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleCompletion {
+    /// Pane ID
+    pub pane_id: u32,
+    /// Title while the command ran (usually the command line)
+    pub command: String,
+    /// Title the pane reverted to
+    pub title: String,
+    /// How long the command title was shown (ms)
+    pub duration_ms: u64,
+}
+
+impl TitleCompletion {
+    /// Completion message from a template
+    pub fn message(&self, template: &str) -> String {
+        template
+            .replace("{command}", &self.command)
+            .replace("{title}", &self.title)
+            .replace("{shell}", &program_name(&self.title))
+            .replace("{duration}", &format_seconds(self.duration_ms))
+    }
+
+    /// Success notification for the pane
+    pub fn notification(&self, template: &str) -> Notification {
+        NotificationBuilder::new()
+            .notification_type(NotificationType::Success)
+            .message(&self.message(template))
+            .pane_id(self.pane_id)
+            .source("passive")
+            .command(&self.command)
+            .duration(self.duration_ms)
+            .build()
+    }
+}
+
+/// Title history of one pane
+#[derive(Debug, Clone, Default)]
+struct PaneTitle {
+    /// Title while no command runs
+    idle: String,
+    /// Current title
+    current: String,
+    /// Plugin time the running command's title appeared (ms)
+    command_since: Option<u64>,
+    /// Plugin times of recent title changes (ms)
+    changes: VecDeque<u64>,
+}
+
+/// Watches pane titles for commands starting and finishing
+#[derive(Debug, Clone)]
+pub struct PassiveDetector {
+    /// Shell names a title reverts to
+    shells: Vec<String>,
+    /// Shortest command run reported (ms)
+    min_duration_ms: u64,
+    /// Title changes within the churn window above which a pane is ignored (0: no limit)
+    max_title_changes: usize,
+    /// Title history by pane
+    panes: BTreeMap<u32, PaneTitle>,
+}
+
+impl Default for PassiveDetector {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_PASSIVE_SHELLS.iter().map(|shell| shell.to_string()).collect(),
+            DEFAULT_PASSIVE_MIN_DURATION_MS,
+            DEFAULT_PASSIVE_MAX_TITLE_CHANGES,
+        )
+    }
+}
+
+impl PassiveDetector {
+    /// Create a detector
+    pub fn new(shells: Vec<String>, min_duration_ms: u64, max_title_changes: usize) -> Self {
+        Self {
+            shells: shells.into_iter().map(|shell| shell.to_lowercase()).collect(),
+            min_duration_ms,
+            max_title_changes,
+            panes: BTreeMap::new(),
+        }
+    }
+
+    /// Whether a title shows a shell rather than a running command
+    pub fn is_shell(&self, title: &str) -> bool {
+        self.shells.contains(&program_name(title))
+    }
+
+    /// Record a pane's current title; returns a completion when a command title reverts
+    pub fn observe(&mut self, pane_id: u32, title: &str, now_ms: u64) -> Option<TitleCompletion> {
+        let is_shell = self.is_shell(title);
+        let pane = match self.panes.get_mut(&pane_id) {
+            Some(pane) => pane,
+            None => {
+                // Whatever shows first is taken as the idle title
+                let pane = PaneTitle {
+                    idle: title.to_string(),
+                    current: title.to_string(),
+                    command_since: None,
+                    changes: VecDeque::new(),
+                };
+                self.panes.insert(pane_id, pane);
+                return None;
+            }
+        };
+        if pane.current == title {
+            return None;
+        }
+
+        pane.changes.push_back(now_ms);
+        while pane.changes.front().is_some_and(|at| now_ms.saturating_sub(*at) > TITLE_CHURN_WINDOW_MS) {
+            pane.changes.pop_front();
+        }
+        let previous = std::mem::replace(&mut pane.current, title.to_string());
+        if self.max_title_changes > 0 && pane.changes.len() > self.max_title_changes {
+            // Churning titles say nothing about commands
+            pane.idle = title.to_string();
+            pane.command_since = None;
+            return None;
+        }
+
+        match pane.command_since {
+            None if is_shell || title == pane.idle => {
+                pane.idle = title.to_string();
+                None
+            }
+            None => {
+                pane.command_since = Some(now_ms);
+                None
+            }
+            Some(since) if is_shell || title == pane.idle => {
+                pane.idle = title.to_string();
+                pane.command_since = None;
+                let duration_ms = now_ms.saturating_sub(since);
+                (duration_ms >= self.min_duration_ms).then(|| TitleCompletion {
+                    pane_id,
+                    command: previous,
+                    title: title.to_string(),
+                    duration_ms,
+                })
+            }
+            // The command changed its own title (progress, subcommands)
+            Some(_) => None,
+        }
+    }
+
+    /// Forget panes that are gone
+    pub fn retain(&mut self, pane_ids: &[u32]) {
+        self.panes.retain(|pane_id, _| pane_ids.contains(pane_id));
+    }
+}
+
+/// Program name at the start of a title: `-zsh` and `/bin/zsh` give `zsh`
+fn program_name(title: &str) -> String {
+    let first = title.split_whitespace().next().unwrap_or_default();
+    let first = first.trim_start_matches('-');
+    first.rsplit('/').next().unwrap_or(first).to_lowercase()
+}
+
+/// Whole seconds, e.g. `42s`
+fn format_seconds(ms: u64) -> String {
+    format!("{}s", ms / 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverting_title_completes_a_command() {
+        let mut detector = PassiveDetector::default();
+        assert_eq!(detector.observe(1, "zsh", 0), None);
+        assert_eq!(detector.observe(1, "cargo build --release", 1_000), None);
+        assert_eq!(detector.observe(1, "cargo build --release", 5_000), None);
+        let completion = detector.observe(1, "-zsh", 31_000).unwrap();
+        assert_eq!(completion.command, "cargo build --release");
+        assert_eq!(completion.duration_ms, 30_000);
+        assert_eq!(completion.message(DEFAULT_PASSIVE_TEMPLATE), "`cargo build --release` finished (title reverted to zsh)");
+        assert_eq!(completion.message("{command} done in {duration}"), "cargo build --release done in 30s");
+
+        // A prompt title that is not a shell name counts once it is the idle title
+        assert_eq!(detector.observe(2, "~/src/api", 0), None);
+        assert_eq!(detector.observe(2, "make test", 1_000), None);
+        assert!(detector.observe(2, "~/src/api", 20_000).is_some());
+
+        // Short commands are not reported
+        assert_eq!(detector.observe(1, "ls", 40_000), None);
+        assert_eq!(detector.observe(1, "zsh", 40_500), None);
+    }
+
+    #[test]
+    fn test_churning_titles_are_ignored() {
+        let mut detector = PassiveDetector::new(vec!["bash".to_string()], 0, 3);
+        detector.observe(1, "bash", 0);
+        for (i, title) in ["12:00:01", "12:00:02", "12:00:03", "12:00:04"].iter().enumerate() {
+            assert_eq!(detector.observe(1, title, 1_000 * (i as u64 + 1)), None);
+        }
+        assert_eq!(detector.observe(1, "bash", 5_000), None);
+
+        // Quiet again after the window
+        assert_eq!(detector.observe(1, "vim notes.md", 30_000), None);
+        assert!(detector.observe(1, "bash", 40_000).is_some());
+        detector.retain(&[]);
+        assert_eq!(detector.observe(1, "bash", 50_000), None);
+    }
+}
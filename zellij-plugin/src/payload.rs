@@ -0,0 +1,180 @@
+//! Payload limit module for Zellij Visual Notifications
+//!
+//! Caps the size of incoming pipe payloads before they are parsed, so a sender piping an entire
+//! build log as the message body cannot exhaust the plugin's memory or flood the renderer.
+//! Oversized payloads are either truncated (the longest text fields of a JSON notification are
+//! shortened, with a marker saying how much was cut) or rejected with a structured error.
+
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Default payload cap (16 KiB)
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 16 * 1024;
+
+/// Shortest field left when truncating (bytes)
+const MIN_FIELD_BYTES: usize = 16;
+
+/// What happens to payloads over the cap
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum OversizePolicy {
+    /// Shorten the payload and mark the cut
+    #[default]
+    Truncate,
+    /// Refuse the payload
+    Reject,
+}
+
+impl OversizePolicy {
+    /// Parse the policy name
+    pub fn from_str(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "reject" => Self::Reject,
+            _ => Self::Truncate,
+        }
+    }
+}
+
+/// A payload refused for its size
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadTooLarge {
+    /// Payload size (bytes)
+    pub size: usize,
+    /// Cap in effect (bytes)
+    pub max_bytes: usize,
+}
+
+impl PayloadTooLarge {
+    /// Structured error reply
+    pub fn json(&self) -> String {
+        serde_json::json!({
+            "status": "rejected",
+            "error": "payload_too_large",
+            "size": self.size,
+            "max_bytes": self.max_bytes,
+        }).to_string()
+    }
+}
+
+impl fmt::Display for PayloadTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "payload of {} bytes exceeds max_payload_bytes ({})", self.size, self.max_bytes)
+    }
+}
+
+/// Marker appended where text was cut
+fn marker(cut: usize) -> String {
+    format!("… [truncated {} bytes]", cut)
+}
+
+/// Longest prefix of `text` at most `len` bytes long, on a character boundary
+fn prefix(text: &str, len: usize) -> &str {
+    let mut end = len.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Bytes a character takes inside a JSON string
+fn escaped_len(c: char) -> usize {
+    match c {
+        '"' | '\\' | '\n' | '\r' | '\t' | '\u{08}' | '\u{0c}' => 2,
+        c if (c as u32) < 0x20 => 6,
+        c => c.len_utf8(),
+    }
+}
+
+/// Longest prefix of `text` taking at most `budget` bytes once escaped for JSON
+fn json_prefix(text: &str, budget: usize) -> &str {
+    let mut used = 0;
+    for (index, c) in text.char_indices() {
+        used += escaped_len(c);
+        if used > budget {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+/// Apply the cap to a payload (`max_bytes` 0 disables it)
+pub fn enforce(payload: String, max_bytes: usize, policy: OversizePolicy) -> Result<String, PayloadTooLarge> {
+    if max_bytes == 0 || payload.len() <= max_bytes {
+        return Ok(payload);
+    }
+    let too_large = PayloadTooLarge { size: payload.len(), max_bytes };
+    if policy == OversizePolicy::Reject {
+        return Err(too_large);
+    }
+    if payload.trim_start().starts_with('{') {
+        return truncate_json(&payload, max_bytes).ok_or(too_large);
+    }
+
+    // Plain lines and commands: keep the start
+    let kept = prefix(&payload, max_bytes.saturating_sub(marker(payload.len()).len()));
+    Ok(format!("{}{}", kept, marker(payload.len() - kept.len())))
+}
+
+/// Shorten the longest top-level string fields of a JSON object until it fits; None when it
+/// cannot be parsed or does not fit even then
+fn truncate_json(payload: &str, max_bytes: usize) -> Option<String> {
+    let mut value: Value = serde_json::from_str(payload).ok()?;
+    let object = value.as_object_mut()?;
+    let mut text = serde_json::to_string(object).ok()?;
+    while text.len() > max_bytes {
+        let excess = text.len() - max_bytes;
+        let (_, field) = object.iter_mut()
+            .filter(|(_, field)| field.is_string())
+            .max_by_key(|(_, field)| field.as_str().map(str::len).unwrap_or(0))?;
+        let original = field.as_str()?.to_string();
+        let escaped: usize = original.chars().map(escaped_len).sum();
+        let budget = escaped.checked_sub(excess + marker(original.len()).len())?;
+        if budget < MIN_FIELD_BYTES {
+            return None;
+        }
+        let kept = json_prefix(&original, budget);
+        *field = Value::String(format!("{}{}", kept, marker(original.len() - kept.len())));
+        text = serde_json::to_string(object).ok()?;
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_message_is_truncated_with_marker() {
+        let log = "error: build failed\n".repeat(2000);
+        let payload = serde_json::json!({ "type": "error", "message": log, "pane_id": 3 }).to_string();
+        let capped = enforce(payload, 1024, OversizePolicy::Truncate).unwrap();
+        assert!(capped.len() <= 1024, "{}", capped.len());
+        let value: Value = serde_json::from_str(&capped).unwrap();
+        assert_eq!(value["pane_id"], 3);
+        let message = value["message"].as_str().unwrap();
+        assert!(message.starts_with("error: build failed"));
+        assert!(message.contains("… [truncated "), "{}", message);
+
+        // Small payloads pass untouched, multi-byte text is cut on a character boundary
+        assert_eq!(enforce("clear".to_string(), 1024, OversizePolicy::Reject).unwrap(), "clear");
+        let line = format!("error|message={}", "é".repeat(600));
+        let capped = enforce(line, 256, OversizePolicy::Truncate).unwrap();
+        assert!(capped.len() <= 256 && capped.ends_with("bytes]"));
+    }
+
+    #[test]
+    fn test_rejection_is_structured() {
+        let payload = format!("{{\"type\": \"info\", \"message\": \"{}\"}}", "x".repeat(100));
+        let error = enforce(payload, 64, OversizePolicy::Reject).unwrap_err();
+        assert_eq!(error.size, 131);
+        let json: Value = serde_json::from_str(&error.json()).unwrap();
+        assert_eq!(json["error"], "payload_too_large");
+        assert_eq!(json["max_bytes"], 64);
+        assert_eq!(OversizePolicy::from_str("reject"), OversizePolicy::Reject);
+
+        // A payload that does not fit even with its text cut is rejected
+        let nested = serde_json::json!({ "type": "info", "metadata": { "custom": "y".repeat(500) } }).to_string();
+        assert!(enforce(nested, 128, OversizePolicy::Truncate).is_err());
+        assert!(enforce("{not json".repeat(20), 64, OversizePolicy::Truncate).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Pending panes module for Zellij Visual Notifications
+//!
+//! Senders often notify a pane they have just asked Zellij to open, and the message can arrive
+//! before the pane shows up in the layout. Notifications for panes the layout does not have are
+//! handled by `missing_pane`: dropped with a warning, moved to the tab level, or held here until
+//! the pane appears. Held notifications still waiting after `missing_pane_hold_ms` are dropped,
+//! so nothing lingers on a phantom pane.
+
+use crate::notification::Notification;
+
+/// Default time a notification waits for its pane to appear (ms)
+pub const DEFAULT_MISSING_PANE_HOLD_MS: u64 = 5_000;
+
+/// Notifications held at once (the oldest is dropped beyond this)
+pub const MAX_PENDING_NOTIFICATIONS: usize = 100;
+
+/// Notifications waiting for their panes to appear
+#[derive(Debug, Clone, Default)]
+pub struct PendingPanes {
+    /// Held notifications with the time they were held (ms), oldest first
+    held: Vec<(Notification, u64)>,
+}
+
+impl PendingPanes {
+    /// Hold a notification until its pane appears, returning the oldest one when full
+    pub fn hold(&mut self, notification: Notification, now_ms: u64) -> Option<Notification> {
+        let evicted = if self.held.len() >= MAX_PENDING_NOTIFICATIONS {
+            Some(self.held.remove(0).0)
+        } else {
+            None
+        };
+        self.held.push((notification, now_ms));
+        evicted
+    }
+
+    /// Release the notifications whose panes now exist, in arrival order
+    pub fn release(&mut self, exists: impl Fn(u32) -> bool) -> Vec<Notification> {
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.held).into_iter()
+            .partition(|(notification, _)| notification.pane_id.is_none_or(&exists));
+        self.held = waiting;
+        ready.into_iter().map(|(notification, _)| notification).collect()
+    }
+
+    /// Remove the notifications held for `hold_ms` or longer
+    pub fn expire(&mut self, hold_ms: u64, now_ms: u64) -> Vec<Notification> {
+        let (expired, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.held).into_iter()
+            .partition(|(_, held_since)| now_ms.saturating_sub(*held_since) >= hold_ms);
+        self.held = waiting;
+        expired.into_iter().map(|(notification, _)| notification).collect()
+    }
+
+    /// Stop holding the notifications `matches` selects, returning whether there were any
+    pub fn cancel(&mut self, matches: impl Fn(&Notification) -> bool) -> bool {
+        let before = self.held.len();
+        self.held.retain(|(notification, _)| !matches(notification));
+        self.held.len() < before
+    }
+
+    /// Held notification with an ID
+    pub fn find(&self, id: &str) -> Option<&Notification> {
+        self.held.iter().map(|(notification, _)| notification).find(|notification| notification.id == id)
+    }
+
+    /// Notifications currently held
+    pub fn held_count(&self) -> usize {
+        self.held.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_when_pane_appears_or_expire() {
+        let mut pending = PendingPanes::default();
+        assert!(pending.hold(Notification::error("Build failed").for_pane(7), 0).is_none());
+        assert!(pending.hold(Notification::info("Started").for_pane(8), 1_000).is_none());
+
+        assert!(pending.release(|pane_id| pane_id == 9).is_empty());
+        let released = pending.release(|pane_id| pane_id == 8);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].message, "Started");
+
+        assert!(pending.expire(5_000, 4_999).is_empty());
+        let expired = pending.expire(5_000, 5_000);
+        assert_eq!(expired[0].message, "Build failed");
+        assert_eq!(pending.held_count(), 0);
+    }
+
+    #[test]
+    fn test_oldest_dropped_when_full() {
+        let mut pending = PendingPanes::default();
+        for index in 0..MAX_PENDING_NOTIFICATIONS {
+            assert!(pending.hold(Notification::info(&format!("n{}", index)).for_pane(1), 0).is_none());
+        }
+        let evicted = pending.hold(Notification::info("newest").for_pane(1), 0).unwrap();
+        assert_eq!(evicted.message, "n0");
+        assert_eq!(pending.held_count(), MAX_PENDING_NOTIFICATIONS);
+    }
+}
@@ -0,0 +1,85 @@
+//! Power module for Zellij Visual Notifications
+//!
+//! Low power mode (`low_power`) trades liveliness for fewer wake-ups on laptops running on
+//! battery: the timer ticks every `low_power_tick_ms` instead of every animation frame,
+//! animations are replaced by their static emphasis, the periodic scans (queue expiry, response
+//! time targets, acknowledgement escalation) and once-a-second redraws run every few seconds,
+//! and nothing is redrawn between them unless something changed. It can be switched at runtime
+//! (`low_power on|off`) or follow a battery monitor script that pipes `power battery` and
+//! `power ac`: switching to AC only turns off what the battery signal turned on.
+
+/// Default timer interval in low power mode (ms)
+pub const DEFAULT_LOW_POWER_TICK_MS: u64 = 1_000;
+
+/// Allowed timer intervals in low power mode (ms)
+pub const LOW_POWER_TICK_MS_RANGE: std::ops::RangeInclusive<u64> = 200..=10_000;
+
+/// Time between the periodic scans in low power mode (ms)
+pub const LOW_POWER_SCAN_MS: u64 = 5_000;
+
+/// Power source reported by a battery monitor script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    /// Running on battery
+    Battery,
+    /// Plugged in
+    Ac,
+}
+
+impl PowerSource {
+    /// Parse a power source (`battery`, `ac`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "battery" | "bat" => Some(Self::Battery),
+            "ac" | "plugged" | "charging" => Some(Self::Ac),
+            _ => None,
+        }
+    }
+
+    /// Name shown in replies
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Battery => "battery",
+            Self::Ac => "ac",
+        }
+    }
+}
+
+/// Spacing of the periodic scans
+#[derive(Debug, Clone, Default)]
+pub struct ScanThrottle {
+    /// Plugin time of the last scan (ms)
+    last_scan_ms: Option<u64>,
+}
+
+impl ScanThrottle {
+    /// Whether the periodic scans run now: on every tick, or every `LOW_POWER_SCAN_MS` in low
+    /// power mode
+    pub fn due(&mut self, low_power: bool, now_ms: u64) -> bool {
+        if low_power && self.last_scan_ms.is_some_and(|last| now_ms < last + LOW_POWER_SCAN_MS) {
+            return false;
+        }
+        self.last_scan_ms = Some(now_ms);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scans_spaced_out_in_low_power() {
+        let mut throttle = ScanThrottle::default();
+        assert!(throttle.due(false, 0));
+        assert!(throttle.due(false, 50));
+        assert!(!throttle.due(true, 1_000));
+        assert!(!throttle.due(true, 5_049));
+        assert!(throttle.due(true, 5_050));
+        assert!(throttle.due(false, 5_100));
+
+        assert_eq!(PowerSource::from_name("Battery"), Some(PowerSource::Battery));
+        assert_eq!(PowerSource::from_name("ac"), Some(PowerSource::Ac));
+        assert_eq!(PowerSource::from_name("solar"), None);
+    }
+}
@@ -0,0 +1,155 @@
+//! User preference module for Zellij Visual Notifications
+//!
+//! What the user adjusts at runtime - do-not-disturb, sources muted from the diagnostics view,
+//! display surfaces switched off, pinned notifications - is kept in `prefs.json` in the plugin
+//! data directory, apart from the declarative KDL configuration, so it survives plugin reloads
+//! without the configuration file being rewritten. Preferences are applied on top of the
+//! configuration: a muted source becomes a runtime `mute_<source>` rule (replacing a configured
+//! rule of the same name), and `config_set` overrides still win over both but are not persisted.
+//! Only an open-ended DND is kept; timed DND and snooze end with the plugin instance.
+//! `prefs_reset` deletes the file and returns everything it covers to the configuration.
+
+use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+use crate::lifecycle::{read_state, LifecycleContext, Subsystem};
+use crate::notification::Notification;
+use crate::sources::NoisySource;
+use crate::surface::DisplaySurface;
+
+/// Default location of the persisted preferences (plugin data directory)
+pub const PREFS_STATE_PATH: &str = "/data/prefs.json";
+
+/// Runtime-adjusted preferences
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// Do-not-disturb switched on without a time limit
+    pub dnd: bool,
+    /// Sources muted from the diagnostics view
+    pub muted_sources: BTreeSet<String>,
+    /// Display surfaces switched off, by name (`border`, `tabs`, `status`, `toasts`)
+    pub disabled_surfaces: Vec<String>,
+    /// Pinned notifications, redisplayed on their panes after a reload
+    pub pinned: Vec<Notification>,
+}
+
+impl Preferences {
+    /// Parse preferences from their persisted form (JSON; defaults for anything unreadable)
+    pub fn parse(content: &str) -> Self {
+        serde_json::from_str(content).unwrap_or_default()
+    }
+
+    /// Serialize the preferences to their persisted form
+    pub fn serialize(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Save the preferences to a file
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.serialize())
+    }
+
+    /// Delete the preferences file (a missing file is not an error)
+    pub fn remove(path: &str) -> std::io::Result<()> {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether nothing differs from the configuration
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// IDs of the pinned notifications
+    fn pinned_ids(&self) -> impl Iterator<Item = &str> {
+        self.pinned.iter().map(|notification| notification.id.as_str())
+    }
+
+    /// Display surfaces switched off (unknown names skipped)
+    pub fn surfaces_off(&self) -> Vec<DisplaySurface> {
+        self.disabled_surfaces.iter().filter_map(|name| DisplaySurface::from_name(name)).collect()
+    }
+
+    /// Runtime rules (key, spec) dropping the muted sources
+    pub fn mute_rules(&self) -> Vec<(String, String)> {
+        self.muted_sources.iter()
+            .filter_map(|source| mute_rule(source).rule_spec().map(|spec| (mute_rule_key(source), spec)))
+            .collect()
+    }
+
+    /// Unmute the source whose rule has the given key, returning whether one was muted
+    pub fn forget_mute_rule(&mut self, key: &str) -> bool {
+        let before = self.muted_sources.len();
+        self.muted_sources.retain(|source| mute_rule_key(source) != key);
+        self.muted_sources.len() < before
+    }
+}
+
+/// Rule muting a source
+fn mute_rule(source: &str) -> NoisySource {
+    NoisySource { source: source.to_string(), per_minute: 0 }
+}
+
+/// Configuration key of the rule muting a source (`rule_mute_<source>`)
+fn mute_rule_key(source: &str) -> String {
+    format!("rule_{}", mute_rule(source).rule_name())
+}
+
+/// Pinned notifications compare by ID
+impl PartialEq for Preferences {
+    fn eq(&self, other: &Self) -> bool {
+        self.dnd == other.dnd
+            && self.muted_sources == other.muted_sources
+            && self.disabled_surfaces == other.disabled_surfaces
+            && self.pinned_ids().eq(other.pinned_ids())
+    }
+}
+
+impl Subsystem for Preferences {
+    fn name(&self) -> &'static str {
+        "prefs"
+    }
+
+    fn init(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        *self = read_state(PREFS_STATE_PATH)?.map(|content| Self::parse(&content)).unwrap_or_default();
+        Ok(())
+    }
+
+    fn shutdown(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        if !context.persist {
+            return Ok(());
+        }
+        self.save(PREFS_STATE_PATH).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_partial_files() {
+        let mut prefs = Preferences {
+            dnd: true,
+            disabled_surfaces: vec!["toasts".to_string(), "bogus".to_string()],
+            pinned: vec![Notification::error("Build failed").for_pane(3)],
+            ..Preferences::default()
+        };
+        prefs.muted_sources.insert("ci".to_string());
+        let restored = Preferences::parse(&prefs.serialize());
+        assert_eq!(restored.pinned[0].message, "Build failed");
+        assert_eq!(restored.surfaces_off(), vec![DisplaySurface::Toasts]);
+        assert_eq!(restored.mute_rules(), vec![("rule_mute_ci".to_string(), "source=ci -> drop".to_string())]);
+        prefs.muted_sources.insert("CI/nightly".to_string());
+        assert!(prefs.forget_mute_rule("rule_mute_ci_nightly"));
+        assert!(!prefs.forget_mute_rule("rule_mute_ci_nightly"));
+        assert_eq!(prefs.muted_sources.len(), 1);
+
+        // Missing fields take their defaults; garbage is ignored
+        let partial = Preferences::parse("{\"dnd\": true}");
+        assert!(partial.dnd && partial.muted_sources.is_empty());
+        assert!(Preferences::parse("not json").is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+//! Frame profiling module for Zellij Visual Notifications
+//!
+//! Measures how long the plugin spends handling timer ticks, other host events and pipe
+//! messages, and rendering, using the host's monotonic clock. The `stats` command reports
+//! averages, 95th percentiles and maxima over the recent frames, how much of the tick interval
+//! the busiest ticks use, and what ticks that changed nothing (idle ticks) cost. A frame slower
+//! than `frame_time_warn_ms` is counted and logged (at most once a minute), so a renderer or queue
+//! regression shows up before it is felt as lag.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Frames kept per phase for the percentiles
+pub const FRAME_WINDOW: usize = 120;
+
+/// Shortest time between two slow-frame warnings (ms)
+pub const FRAME_WARNING_INTERVAL_MS: u64 = 60_000;
+
+/// Default slow-frame threshold (ms)
+pub const DEFAULT_FRAME_TIME_WARN_MS: u64 = 8;
+
+/// Part of the plugin's work being timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramePhase {
+    /// Timer tick (animations, expiry, scheduled work)
+    Tick,
+    /// Other host events and pipe messages
+    Update,
+    /// Drawing the pane
+    Render,
+}
+
+impl FramePhase {
+    /// Name shown in the stats
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Tick => "tick",
+            Self::Update => "update",
+            Self::Render => "render",
+        }
+    }
+}
+
+/// Timings of one phase
+#[derive(Debug, Clone, Default)]
+struct PhaseStats {
+    /// Recent durations (µs), oldest first
+    recent_us: VecDeque<u64>,
+    /// Frames timed
+    frames: u64,
+    /// Slowest frame (µs)
+    max_us: u64,
+    /// Frames over the threshold
+    slow: u64,
+}
+
+impl PhaseStats {
+    fn record(&mut self, duration_us: u64, slow: bool) {
+        if self.recent_us.len() >= FRAME_WINDOW {
+            self.recent_us.pop_front();
+        }
+        self.recent_us.push_back(duration_us);
+        self.frames += 1;
+        self.max_us = self.max_us.max(duration_us);
+        self.slow += u64::from(slow);
+    }
+
+    /// Average of the recent frames (µs)
+    fn average_us(&self) -> u64 {
+        self.recent_us.iter().sum::<u64>() / (self.recent_us.len().max(1) as u64)
+    }
+
+    /// 95th percentile of the recent frames (µs)
+    fn p95_us(&self) -> u64 {
+        let mut sorted: Vec<u64> = self.recent_us.iter().copied().collect();
+        sorted.sort_unstable();
+        sorted.get((sorted.len() * 95).div_ceil(100).saturating_sub(1)).copied().unwrap_or(0)
+    }
+}
+
+/// Frame timings of all phases
+#[derive(Debug, Clone, Default)]
+pub struct FrameProfiler {
+    tick: PhaseStats,
+    update: PhaseStats,
+    render: PhaseStats,
+    /// Ticks that changed nothing
+    idle_ticks: u64,
+    /// Time spent in idle ticks (µs)
+    idle_tick_us: u64,
+    /// Plugin time of the last slow-frame warning (ms)
+    last_warning_ms: Option<u64>,
+}
+
+impl FrameProfiler {
+    fn phase(&self, phase: FramePhase) -> &PhaseStats {
+        match phase {
+            FramePhase::Tick => &self.tick,
+            FramePhase::Update => &self.update,
+            FramePhase::Render => &self.render,
+        }
+    }
+
+    fn phase_mut(&mut self, phase: FramePhase) -> &mut PhaseStats {
+        match phase {
+            FramePhase::Tick => &mut self.tick,
+            FramePhase::Update => &mut self.update,
+            FramePhase::Render => &mut self.render,
+        }
+    }
+
+    /// Record a frame. `idle` marks a tick that changed nothing; a `threshold_ms` of 0 disables
+    /// slow-frame detection. Returns a warning to log when the frame was slow and no warning was
+    /// given in the last minute.
+    pub fn record(&mut self, phase: FramePhase, duration: Duration, idle: bool, threshold_ms: u64, now_ms: u64) -> Option<String> {
+        let duration_us = duration.as_micros().min(u128::from(u64::MAX)) as u64;
+        let slow = threshold_ms > 0 && duration_us > threshold_ms * 1000;
+        self.phase_mut(phase).record(duration_us, slow);
+        if phase == FramePhase::Tick && idle {
+            self.idle_ticks += 1;
+            self.idle_tick_us += duration_us;
+        }
+        if !slow || self.last_warning_ms.is_some_and(|last| now_ms < last + FRAME_WARNING_INTERVAL_MS) {
+            return None;
+        }
+        self.last_warning_ms = Some(now_ms);
+        Some(format!("Slow {} frame: {} (threshold {}ms)", phase.name(), format_us(duration_us), threshold_ms))
+    }
+
+    /// Frames over the threshold, all phases
+    pub fn slow_frames(&self) -> u64 {
+        self.tick.slow + self.update.slow + self.render.slow
+    }
+
+    /// Stats report; `tick_ms` is the tick interval the tick budget refers to
+    pub fn lines(&self, tick_ms: u64, threshold_ms: u64) -> Vec<String> {
+        let mut lines: Vec<String> = [FramePhase::Tick, FramePhase::Update, FramePhase::Render].iter()
+            .map(|phase| {
+                let stats = self.phase(*phase);
+                format!("{}: {} frames, avg {}, p95 {}, max {}, {} slow",
+                    phase.name(),
+                    stats.frames,
+                    format_us(stats.average_us()),
+                    format_us(stats.p95_us()),
+                    format_us(stats.max_us),
+                    stats.slow)
+            })
+            .collect();
+        let budget_us = tick_ms.max(1) * 1000;
+        lines.push(format!("tick budget: p95 uses {:.1}% of {}ms, max {:.1}%",
+            self.tick.p95_us() as f64 * 100.0 / budget_us as f64,
+            tick_ms,
+            self.tick.max_us as f64 * 100.0 / budget_us as f64));
+        lines.push(format!("idle ticks: {}, avg {}",
+            self.idle_ticks,
+            format_us(self.idle_tick_us / self.idle_ticks.max(1))));
+        lines.push(if threshold_ms > 0 {
+            format!("slow frame threshold: {}ms", threshold_ms)
+        } else {
+            "slow frame threshold: off".to_string()
+        });
+        lines
+    }
+}
+
+/// Duration in µs as milliseconds with two decimals, e.g. `0.25ms`
+fn format_us(duration_us: u64) -> String {
+    format!("{:.2}ms", duration_us as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_and_tick_budget() {
+        let mut profiler = FrameProfiler::default();
+        for us in 1..=100 {
+            profiler.record(FramePhase::Tick, Duration::from_micros(us * 10), us % 2 == 0, 8, 0);
+        }
+        profiler.record(FramePhase::Render, Duration::from_micros(2500), false, 8, 0);
+        let lines = profiler.lines(50, 8);
+        assert_eq!(lines[0], "tick: 100 frames, avg 0.51ms, p95 0.95ms, max 1.00ms, 0 slow");
+        assert_eq!(lines[1], "update: 0 frames, avg 0.00ms, p95 0.00ms, max 0.00ms, 0 slow");
+        assert_eq!(lines[2], "render: 1 frames, avg 2.50ms, p95 2.50ms, max 2.50ms, 0 slow");
+        assert_eq!(lines[3], "tick budget: p95 uses 1.9% of 50ms, max 2.0%");
+        assert_eq!(lines[4], "idle ticks: 50, avg 0.51ms");
+
+        // Only the recent frames count towards the percentiles
+        for _ in 0..FRAME_WINDOW {
+            profiler.record(FramePhase::Tick, Duration::from_micros(100), true, 8, 0);
+        }
+        assert!(profiler.lines(50, 8)[0].starts_with("tick: 220 frames, avg 0.10ms, p95 0.10ms, max 1.00ms"));
+    }
+
+    #[test]
+    fn test_slow_frames_warn_once_a_minute() {
+        let mut profiler = FrameProfiler::default();
+        let slow = Duration::from_millis(12);
+        assert_eq!(profiler.record(FramePhase::Render, slow, false, 8, 1_000).as_deref(),
+            Some("Slow render frame: 12.00ms (threshold 8ms)"));
+        assert!(profiler.record(FramePhase::Update, slow, false, 8, 30_000).is_none());
+        assert!(profiler.record(FramePhase::Render, Duration::from_millis(2), false, 8, 70_000).is_none());
+        assert!(profiler.record(FramePhase::Render, slow, false, 8, 70_000).is_some());
+        assert_eq!(profiler.slow_frames(), 3);
+
+        // Disabled threshold
+        assert!(profiler.record(FramePhase::Render, slow, false, 0, 200_000).is_none());
+        assert_eq!(profiler.slow_frames(), 3);
+    }
+}
@@ -0,0 +1,130 @@
+//! Progress module for Zellij Visual Notifications
+//!
+//! Tracks repeated percent updates of a progress notification and estimates the time remaining
+//! from the rate of change. The rate is smoothed (exponential moving average) so a single
+//! fast or slow step does not make the ETA jump around.
+
+/// Weight of the newest rate sample in the moving average (0.0 - 1.0)
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Completion percent of a finished task
+const COMPLETE_PERCENT: f32 = 100.0;
+
+/// Progress of a single task, fed by percent updates
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressTracker {
+    /// Plugin time of the first update (ms)
+    started_ms: u64,
+    /// Plugin time of the latest update (ms)
+    updated_ms: u64,
+    /// Latest completion percent (0 - 100)
+    percent: f32,
+    /// Smoothed completion rate (percent per ms), once two updates are known
+    rate: Option<f64>,
+}
+
+/// Snapshot for rendering
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressEstimate {
+    /// Latest completion percent (0 - 100)
+    pub percent: f32,
+    /// Time since the first update (ms)
+    pub elapsed_ms: u64,
+    /// Estimated time remaining (ms), once a rate is known
+    pub eta_ms: Option<u64>,
+}
+
+impl ProgressTracker {
+    /// Start tracking at `percent`
+    pub fn new(percent: f32, now_ms: u64) -> Self {
+        Self {
+            started_ms: now_ms,
+            updated_ms: now_ms,
+            percent: percent.clamp(0.0, COMPLETE_PERCENT),
+            rate: None,
+        }
+    }
+
+    /// Record a percent update (going backwards restarts the tracking)
+    pub fn update(&mut self, percent: f32, now_ms: u64) {
+        let percent = percent.clamp(0.0, COMPLETE_PERCENT);
+        if percent < self.percent {
+            *self = Self::new(percent, now_ms);
+            return;
+        }
+
+        let elapsed = now_ms.saturating_sub(self.updated_ms);
+        if elapsed == 0 {
+            self.percent = percent;
+            return;
+        }
+
+        let sample = f64::from(percent - self.percent) / elapsed as f64;
+        self.rate = Some(match self.rate {
+            Some(rate) => rate + RATE_SMOOTHING * (sample - rate),
+            None => sample,
+        });
+        self.percent = percent;
+        self.updated_ms = now_ms;
+    }
+
+    /// Elapsed time and ETA at `now_ms` (the ETA counts down between updates)
+    pub fn estimate(&self, now_ms: u64) -> ProgressEstimate {
+        let eta_ms = if self.percent >= COMPLETE_PERCENT {
+            Some(0)
+        } else {
+            self.rate.filter(|rate| *rate > 0.0).map(|rate| {
+                let remaining = f64::from(COMPLETE_PERCENT - self.percent) / rate;
+                (remaining as u64).saturating_sub(now_ms.saturating_sub(self.updated_ms))
+            })
+        };
+
+        ProgressEstimate {
+            percent: self.percent,
+            elapsed_ms: now_ms.saturating_sub(self.started_ms),
+            eta_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eta_from_rate() {
+        let mut tracker = ProgressTracker::new(0.0, 0);
+        assert_eq!(tracker.estimate(0).eta_ms, None);
+
+        // 10% per second: 90% left is 9s, counting down between updates
+        tracker.update(10.0, 1_000);
+        let estimate = tracker.estimate(1_000);
+        assert_eq!(estimate.percent, 10.0);
+        assert_eq!(estimate.elapsed_ms, 1_000);
+        assert_eq!(estimate.eta_ms, Some(9_000));
+        assert_eq!(tracker.estimate(3_000).eta_ms, Some(7_000));
+
+        tracker.update(100.0, 2_000);
+        assert_eq!(tracker.estimate(2_000).eta_ms, Some(0));
+    }
+
+    #[test]
+    fn test_rate_is_smoothed() {
+        let mut tracker = ProgressTracker::new(0.0, 0);
+        tracker.update(10.0, 1_000);
+        // A sudden jump moves the rate only part of the way (10%/s -> 31%/s, not 80%/s)
+        tracker.update(90.0, 2_000);
+        let eta = tracker.estimate(2_000).eta_ms.unwrap();
+        assert!((320..=325).contains(&eta), "eta {}", eta);
+    }
+
+    #[test]
+    fn test_going_backwards_restarts() {
+        let mut tracker = ProgressTracker::new(50.0, 0);
+        tracker.update(60.0, 1_000);
+        tracker.update(5.0, 5_000);
+        let estimate = tracker.estimate(5_000);
+        assert_eq!(estimate.elapsed_ms, 0);
+        assert_eq!(estimate.eta_ms, None);
+    }
+}
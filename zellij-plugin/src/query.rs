@@ -0,0 +1,446 @@
+//! History query module for Zellij Visual Notifications
+//!
+//! Parses the `key=value` filters of the `list` pipe command
+//! (`list type=error source=claude since=30m limit=10 format=json`) and evaluates them against
+//! the notification history, so scripts can pull exactly the records they need. Results can be
+//! sorted (`sort=time|type|source|pane`) and grouped (`group=day|source|branch`) under headers with
+//! counts; the history view in the plugin arranges its entries the same way.
+
+use std::collections::BTreeMap;
+use chrono::DateTime;
+use crate::history::{HistoryEntry, NotificationHistory};
+use crate::notification::NotificationType;
+
+/// Results returned when no limit is given
+pub const DEFAULT_QUERY_LIMIT: usize = 20;
+
+/// Output format of a query
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum QueryFormat {
+    /// One line per notification
+    #[default]
+    Text,
+    /// A JSON array
+    Json,
+}
+
+/// Order of listed notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    /// Newest first
+    #[default]
+    Time,
+    /// By type name, newest first within a type
+    Type,
+    /// By source, newest first within a source
+    Source,
+    /// By pane ID (notifications without a pane last), newest first within a pane
+    Pane,
+}
+
+impl SortMode {
+    /// All sort modes, in cycling order
+    const ALL: [SortMode; 4] = [SortMode::Time, SortMode::Type, SortMode::Source, SortMode::Pane];
+
+    /// Get the sort mode name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortMode::Time => "time",
+            SortMode::Type => "type",
+            SortMode::Source => "source",
+            SortMode::Pane => "pane",
+        }
+    }
+
+    /// Parse a sort mode name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    /// The sort mode after this one (history view key)
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Grouping of listed notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Grouping {
+    /// One flat list
+    #[default]
+    None,
+    /// By the day the notification was sent (UTC)
+    Day,
+    /// By source
+    Source,
+    /// By git branch (or worktree) of the sender
+    Branch,
+}
+
+impl Grouping {
+    /// All groupings, in cycling order
+    const ALL: [Grouping; 4] = [Grouping::None, Grouping::Day, Grouping::Source, Grouping::Branch];
+
+    /// Get the grouping name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Grouping::None => "none",
+            Grouping::Day => "day",
+            Grouping::Source => "source",
+            Grouping::Branch => "branch",
+        }
+    }
+
+    /// Parse a grouping name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|grouping| grouping.name() == name)
+    }
+
+    /// The grouping after this one (history view key)
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|grouping| grouping == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Group label of an entry (None when not grouping)
+    fn label(&self, entry: &HistoryEntry, now_ms: u64) -> Option<String> {
+        match self {
+            Grouping::None => None,
+            Grouping::Day => Some(day_label(entry, now_ms)),
+            Grouping::Source => Some(entry.notification.source.clone()),
+            Grouping::Branch => Some(entry.notification.branch_label().unwrap_or("no branch").to_string()),
+        }
+    }
+}
+
+/// Day an entry was sent, e.g. `2026-10-16`; entries without a sender timestamp are bucketed by
+/// their age (`today`, `1 day ago`, ...)
+fn day_label(entry: &HistoryEntry, now_ms: u64) -> String {
+    let sent = Some(entry.notification.timestamp)
+        .filter(|timestamp| *timestamp > 0)
+        .and_then(|timestamp| DateTime::from_timestamp_millis(timestamp as i64));
+    match sent {
+        Some(sent) => sent.format("%Y-%m-%d").to_string(),
+        None => match now_ms.saturating_sub(entry.received_at_ms) / 86_400_000 {
+            0 => "today".to_string(),
+            1 => "1 day ago".to_string(),
+            days => format!("{} days ago", days),
+        },
+    }
+}
+
+/// Listed notifications under one group header
+#[derive(Debug, Clone)]
+pub struct ResultGroup<'a> {
+    /// Group label (None when not grouping)
+    pub label: Option<String>,
+    /// Entries in the sort order
+    pub entries: Vec<&'a HistoryEntry>,
+}
+
+impl ResultGroup<'_> {
+    /// Header line with the count, e.g. `ci (12)`
+    pub fn header(&self) -> Option<String> {
+        self.label.as_ref().map(|label| format!("{} ({})", label, self.entries.len()))
+    }
+}
+
+/// Sort and group newest-first results. Groups keep the order their newest entry gives them.
+pub fn arrange<'a>(results: &[&'a HistoryEntry], sort: SortMode, grouping: Grouping, now_ms: u64) -> Vec<ResultGroup<'a>> {
+    let mut groups: Vec<ResultGroup<'a>> = Vec::new();
+    let mut index: BTreeMap<Option<String>, usize> = BTreeMap::new();
+    for entry in results {
+        let label = grouping.label(entry, now_ms);
+        let position = *index.entry(label.clone()).or_insert_with(|| {
+            groups.push(ResultGroup { label, entries: Vec::new() });
+            groups.len() - 1
+        });
+        groups[position].entries.push(entry);
+    }
+    for group in &mut groups {
+        // Stable sorts keep newest first within equal keys
+        match sort {
+            SortMode::Time => {}
+            SortMode::Type => group.entries.sort_by_key(|entry| entry.notification.notification_type.name()),
+            SortMode::Source => group.entries.sort_by_key(|entry| entry.notification.source.to_lowercase()),
+            SortMode::Pane => group.entries.sort_by_key(|entry| (entry.notification.pane_id.is_none(), entry.notification.pane_id)),
+        }
+    }
+    groups
+}
+
+/// One line describing a history entry, e.g. `   45s ago  error     ci: Tests failed (pane 3)`
+pub fn entry_line(entry: &HistoryEntry, now_ms: u64) -> String {
+    let n = &entry.notification;
+    let pane = n.pane_id.map(|p| format!(" (pane {})", p)).unwrap_or_default();
+    let escalated = if entry.ack_escalated_at_ms.is_some() { " [escalated]" } else { "" };
+    let snapshot = if entry.snapshot.is_some() { " [snapshot]" } else { "" };
+    let repeats = if entry.repeats > 0 {
+        format!(" [x{} over {}]", entry.count(), format_age(entry.received_at_ms.saturating_sub(entry.first_received_at_ms)))
+    } else {
+        String::new()
+    };
+    format!("{:>6} ago  {:<9} {}: {}{}{}{}{}",
+        format_age(now_ms.saturating_sub(entry.received_at_ms)),
+        n.notification_type.name(),
+        n.source,
+        entry.display_text(),
+        pane,
+        repeats,
+        escalated,
+        snapshot)
+}
+
+/// A parsed history query; unset filters match everything
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryQuery {
+    /// Notification type
+    pub notification_type: Option<NotificationType>,
+    /// Case-insensitive substring of the source
+    pub source: Option<String>,
+    /// Target pane
+    pub pane_id: Option<u32>,
+    /// Only notifications received within this window (ms)
+    pub since_ms: Option<u64>,
+    /// Maximum results (newest first)
+    pub limit: usize,
+    /// Output format
+    pub format: QueryFormat,
+    /// Order of the results
+    pub sort: SortMode,
+    /// Grouping of the results
+    pub group: Grouping,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            notification_type: None,
+            source: None,
+            pane_id: None,
+            since_ms: None,
+            limit: DEFAULT_QUERY_LIMIT,
+            format: QueryFormat::Text,
+            sort: SortMode::Time,
+            group: Grouping::None,
+        }
+    }
+}
+
+impl HistoryQuery {
+    /// Parse `key=value` filter arguments
+    pub fn parse(args: &[&str]) -> Result<Self, String> {
+        let mut query = Self::default();
+
+        for arg in args {
+            let (key, value) = arg.split_once('=')
+                .ok_or_else(|| format!("expected key=value, got: {}", arg))?;
+            match key {
+                "type" => {
+                    query.notification_type = Some(NotificationType::from_name(value)
+                        .ok_or_else(|| format!("invalid notification type: {}", value))?);
+                }
+                "source" => query.source = Some(value.to_lowercase()),
+                "pane" => query.pane_id = Some(value.parse().map_err(|_| format!("invalid pane id: {}", value))?),
+                "since" => query.since_ms = Some(parse_duration_ms(value)?),
+                "limit" => query.limit = value.parse().map_err(|_| format!("invalid limit: {}", value))?,
+                "format" => {
+                    query.format = match value {
+                        "text" => QueryFormat::Text,
+                        "json" => QueryFormat::Json,
+                        other => return Err(format!("invalid format: {}", other)),
+                    }
+                }
+                "sort" => query.sort = SortMode::from_name(value).ok_or_else(|| format!("invalid sort: {}", value))?,
+                "group" => query.group = Grouping::from_name(value).ok_or_else(|| format!("invalid group: {}", value))?,
+                other => return Err(format!("unknown filter: {}", other)),
+            }
+        }
+
+        Ok(query)
+    }
+
+    /// Check whether an entry passes the filters
+    pub fn matches(&self, entry: &HistoryEntry, now_ms: u64) -> bool {
+        let notification = &entry.notification;
+        self.notification_type.as_ref().is_none_or(|t| notification.notification_type == *t)
+            && self.source.as_ref().is_none_or(|s| notification.source.to_lowercase().contains(s.as_str()))
+            && self.pane_id.is_none_or(|p| notification.pane_id == Some(p))
+            && self.since_ms.is_none_or(|since| now_ms.saturating_sub(entry.received_at_ms) <= since)
+    }
+
+    /// Matching entries, newest first, up to the limit
+    pub fn evaluate<'a>(&self, history: &'a NotificationHistory, now_ms: u64) -> Vec<&'a HistoryEntry> {
+        history.newest_first()
+            .filter(|entry| self.matches(entry, now_ms))
+            .take(self.limit)
+            .collect()
+    }
+
+    /// Format results in the requested output format, sorted and grouped
+    pub fn format_results(&self, results: &[&HistoryEntry], now_ms: u64) -> String {
+        let groups = arrange(results, self.sort, self.group, now_ms);
+        match self.format {
+            QueryFormat::Json => {
+                let records: Vec<serde_json::Value> = groups.iter()
+                    .flat_map(|group| group.entries.iter().map(move |entry| (group, entry)))
+                    .map(|(group, entry)| {
+                        let n = &entry.notification;
+                        serde_json::json!({
+                            "id": n.id,
+                            "type": n.notification_type.name(),
+                            "priority": n.priority.name(),
+                            "message": n.message,
+                            "title": n.title,
+                            "source": n.source,
+                            "branch": n.branch,
+                            "worktree": n.worktree,
+                            "pane_id": n.pane_id,
+                            "tab_index": n.tab_index,
+                            "timestamp": n.timestamp,
+                            "age_ms": now_ms.saturating_sub(entry.received_at_ms),
+                            "count": entry.count(),
+                            "first_age_ms": now_ms.saturating_sub(entry.first_received_at_ms),
+                            "first_message": entry.first_message,
+                            "ack_escalated": entry.ack_escalated_at_ms.is_some(),
+                            "on_expire": n.on_expire.name(),
+                            "snapshot": entry.snapshot,
+                            "group": group.label,
+                        })
+                    })
+                    .collect();
+                format!("{}\n", serde_json::Value::Array(records))
+            }
+            QueryFormat::Text => groups.iter()
+                .flat_map(|group| group.header().into_iter()
+                    .chain(group.entries.iter().map(|entry| entry_line(entry, now_ms))))
+                .map(|line| format!("{}\n", line))
+                .collect(),
+        }
+    }
+}
+
+/// Parse a duration such as `45s`, `30m`, `2h` or `1d` (plain numbers are minutes)
+pub fn parse_duration_ms(value: &str) -> Result<u64, String> {
+    let (digits, unit_ms) = match value.char_indices().last() {
+        Some((index, 's')) => (&value[..index], 1000),
+        Some((index, 'm')) => (&value[..index], 60_000),
+        Some((index, 'h')) => (&value[..index], 3_600_000),
+        Some((index, 'd')) => (&value[..index], 86_400_000),
+        _ => (value, 60_000),
+    };
+    digits.parse::<u64>()
+        .map(|n| n.saturating_mul(unit_ms))
+        .map_err(|_| format!("invalid duration: {}", value))
+}
+
+/// Format an age coarsely (e.g. "45s", "12m", "3h")
+pub fn format_age(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::{Notification, NotificationBuilder};
+
+    fn sourced(mut notification: Notification, source: &str) -> Notification {
+        notification.source = source.to_string();
+        notification
+    }
+
+    fn history() -> NotificationHistory {
+        let mut history = NotificationHistory::default();
+        history.record(&sourced(Notification::error("Old failure"), "claude-notifications"), 0);
+        history.record(&sourced(Notification::success("Built"), "ci"), 60 * 60_000);
+        history.record(&sourced(Notification::error("Tests failed").for_pane(3), "claude-notifications"), 90 * 60_000);
+        history
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = HistoryQuery::parse(&["type=error", "source=Claude", "since=30m", "limit=10", "format=json"]).unwrap();
+        assert_eq!(query.notification_type, Some(NotificationType::Error));
+        assert_eq!(query.source.as_deref(), Some("claude"));
+        assert_eq!(query.since_ms, Some(30 * 60_000));
+        assert_eq!(query.limit, 10);
+        assert_eq!(query.format, QueryFormat::Json);
+
+        assert!(HistoryQuery::parse(&["type=bogus"]).is_err());
+        assert!(HistoryQuery::parse(&["since=soon"]).is_err());
+        assert!(HistoryQuery::parse(&["limit"]).is_err());
+        assert_eq!(parse_duration_ms("2h"), Ok(7_200_000));
+        assert_eq!(parse_duration_ms("5"), Ok(300_000));
+    }
+
+    #[test]
+    fn test_evaluate_filters_newest_first() {
+        let history = history();
+        let now = 100 * 60_000;
+
+        let query = HistoryQuery::parse(&["type=error", "source=claude"]).unwrap();
+        let results = query.evaluate(&history, now);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].notification.message, "Tests failed");
+
+        let query = HistoryQuery::parse(&["since=30m"]).unwrap();
+        assert_eq!(query.evaluate(&history, now).len(), 1);
+
+        let query = HistoryQuery::parse(&["limit=1", "format=json"]).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&query.format_results(&query.evaluate(&history, now), now)).unwrap();
+        assert_eq!(json[0]["type"], "error");
+        assert_eq!(json[0]["pane_id"], 3);
+        assert_eq!(json[0]["age_ms"], 10 * 60_000);
+    }
+
+    #[test]
+    fn test_sorted_and_grouped_results() {
+        let mut history = history();
+        history.record(&sourced(Notification::info("Deployed").at_time(1_760_572_800_000), "ci"), 95 * 60_000);
+        let now = 100 * 60_000;
+
+        let query = HistoryQuery::parse(&["sort=type", "group=source"]).unwrap();
+        assert_eq!((query.sort, query.group), (SortMode::Type, Grouping::Source));
+        let text = query.format_results(&query.evaluate(&history, now), now);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "ci (2)");
+        assert!(lines[1].contains("info") && lines[2].contains("success"));
+        assert_eq!(lines[3], "claude-notifications (2)");
+        assert!(lines[4].contains("Tests failed"));
+
+        let groups = arrange(&query.evaluate(&history, now), SortMode::Pane, Grouping::Day, now);
+        let headers: Vec<String> = groups.iter().filter_map(|group| group.header()).collect();
+        assert_eq!(headers, vec!["2025-10-16 (1)", "today (3)"]);
+        assert_eq!(groups[1].entries[0].notification.pane_id, Some(3));
+
+        assert!(HistoryQuery::parse(&["sort=size"]).is_err());
+        assert!(HistoryQuery::parse(&["group=week"]).is_err());
+        assert_eq!(SortMode::Pane.next(), SortMode::Time);
+        assert_eq!(Grouping::None.next(), Grouping::Day);
+        assert_eq!(Grouping::Branch.next(), Grouping::None);
+    }
+
+    #[test]
+    fn test_grouped_by_branch() {
+        let mut history = history();
+        let login = NotificationBuilder::new().message("Login tests failed").branch("feat/login").build();
+        history.record(&login, 95 * 60_000);
+        let worktree = NotificationBuilder::new().message("Lint ok").worktree("/work/app-billing").build();
+        history.record(&worktree, 96 * 60_000);
+        let now = 100 * 60_000;
+
+        let query = HistoryQuery::parse(&["group=branch"]).unwrap();
+        let groups = arrange(&query.evaluate(&history, now), query.sort, query.group, now);
+        let headers: Vec<String> = groups.iter().filter_map(|group| group.header()).collect();
+        assert_eq!(headers, vec!["app-billing (1)", "feat/login (1)", "no branch (3)"]);
+    }
+}
@@ -1,9 +1,83 @@
 //! Notification queue module for Zellij Visual Notifications
 //!
-//! Manages queued notifications with priority and TTL support.
+//! Manages queued notifications with priority and TTL support. Within a priority level
+//! notifications leave oldest first, or with the `fair` strategy round-robin across sources
+//! (oldest first within a source), so one source flooding a level can't starve the others.
 
-use std::collections::VecDeque;
-use crate::notification::{Notification, Priority};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use crate::config::Config;
+use crate::notification::{Notification, NotificationType, Priority};
+
+/// Default cap on total queued notification content (1 MiB)
+pub const DEFAULT_MAX_BYTES: usize = 1024 * 1024;
+
+/// Length of a display rate window (ms)
+const RATE_WINDOW_MS: u64 = 1000;
+
+/// Global admission policy applied across all priority lanes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdmissionPolicy {
+    /// Maximum notifications queued across all lanes
+    pub max_total: usize,
+    /// Fill ratio (0.0 - 1.0) above which Low/Normal notifications are merged or rejected
+    pub high_water_ratio: f32,
+}
+
+impl Default for AdmissionPolicy {
+    fn default() -> Self {
+        Self {
+            max_total: 200,
+            high_water_ratio: 0.8,
+        }
+    }
+}
+
+impl AdmissionPolicy {
+    /// Number of queued notifications at which Low/Normal admission is restricted
+    pub fn high_water_mark(&self) -> usize {
+        ((self.max_total as f32) * self.high_water_ratio.clamp(0.0, 1.0)) as usize
+    }
+}
+
+/// Outcome of offering a notification to the queue
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Admission {
+    /// Queued as a new entry
+    Admitted,
+    /// Folded into an equivalent queued notification
+    Merged,
+    /// Dropped by admission control
+    Rejected,
+}
+
+/// How notifications of the same priority are taken off the queue
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum DequeueStrategy {
+    /// Oldest first
+    #[default]
+    Fifo,
+    /// Round-robin across sources (in name order), oldest first within a source
+    Fair,
+}
+
+impl DequeueStrategy {
+    /// Parse the strategy name
+    pub fn from_str(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "fair" => Self::Fair,
+            _ => Self::Fifo,
+        }
+    }
+
+    /// Strategy name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fifo => "fifo",
+            Self::Fair => "fair",
+        }
+    }
+}
 
 /// Notification queue with priority and TTL support
 #[derive(Debug)]
@@ -18,14 +92,42 @@ pub struct NotificationQueue {
     low_queue: VecDeque<Notification>,
     /// Maximum queue size (per priority level)
     max_size: usize,
+    /// Maximum total bytes of queued notification content (across all levels)
+    max_bytes: usize,
+    /// Current total bytes of queued notification content
+    queued_bytes: usize,
     /// Default TTL for notifications in milliseconds
     default_ttl_ms: u64,
     /// Current timestamp (updated externally)
     current_timestamp: u64,
+    /// Panes whose notifications' TTL is paused (not visible)
+    ttl_paused_panes: BTreeSet<u32>,
+    /// Tabs whose notifications' TTL is paused (not visible)
+    ttl_paused_tabs: BTreeSet<usize>,
     /// Total notifications processed
     total_processed: u64,
     /// Total notifications expired
     total_expired: u64,
+    /// Total notifications evicted to enforce size caps
+    total_evicted: u64,
+    /// Cross-lane admission policy
+    admission: AdmissionPolicy,
+    /// Notifications dropped (rejected or evicted), indexed by priority
+    dropped: [u64; 4],
+    /// Total notifications rejected at admission
+    total_rejected: u64,
+    /// Total notifications merged into an equivalent queued notification
+    total_merged: u64,
+    /// Order notifications of the same priority leave in
+    strategy: DequeueStrategy,
+    /// Source served last, indexed by priority (fair strategy)
+    last_served: [Option<String>; 4],
+    /// Notifications dequeued, by source
+    dequeued_by_source: BTreeMap<String, u64>,
+    /// Notifications dequeued per second (0 = unlimited)
+    display_rate: u32,
+    /// Current rate window: (start ms, notifications dequeued)
+    rate_window: (u64, u32),
 }
 
 impl Default for NotificationQueue {
@@ -43,20 +145,115 @@ impl NotificationQueue {
             normal_queue: VecDeque::with_capacity(max_size),
             low_queue: VecDeque::with_capacity(max_size),
             max_size,
+            max_bytes: DEFAULT_MAX_BYTES,
+            queued_bytes: 0,
             default_ttl_ms,
             current_timestamp: 0,
+            ttl_paused_panes: BTreeSet::new(),
+            ttl_paused_tabs: BTreeSet::new(),
             total_processed: 0,
             total_expired: 0,
+            total_evicted: 0,
+            // All four lanes full by default; tighter budgets come from configuration
+            admission: AdmissionPolicy {
+                max_total: max_size.saturating_mul(4),
+                ..AdmissionPolicy::default()
+            },
+            dropped: [0; 4],
+            total_rejected: 0,
+            total_merged: 0,
+            strategy: DequeueStrategy::Fifo,
+            last_served: Default::default(),
+            dequeued_by_source: BTreeMap::new(),
+            display_rate: 0,
+            rate_window: (0, 0),
         }
     }
 
-    /// Set the current timestamp
+    /// Create a queue with the configured lane size, timeout, byte cap, admission policy and
+    /// dequeue strategy
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.queue_max_size, config.notification_timeout_ms)
+            .with_max_bytes(config.queue_max_bytes)
+            .with_strategy(config.queue_strategy)
+            .with_display_rate(config.display_rate)
+            .with_admission(AdmissionPolicy {
+                max_total: config.queue_max_total,
+                high_water_ratio: f32::from(config.queue_high_water_percent) / 100.0,
+            })
+    }
+
+    /// Set the cross-lane admission policy
+    pub fn with_admission(mut self, admission: AdmissionPolicy) -> Self {
+        self.admission = admission;
+        self
+    }
+
+    /// Set the order notifications of the same priority leave in
+    pub fn with_strategy(mut self, strategy: DequeueStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set how many notifications may leave the queue per second (0 = unlimited)
+    pub fn with_display_rate(mut self, display_rate: u32) -> Self {
+        self.display_rate = display_rate;
+        self
+    }
+
+    /// Set the maximum total bytes of queued notification content
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes.max(1);
+        self
+    }
+
+    /// Set the current timestamp, counting the elapsed time against unpaused TTLs
     pub fn update_timestamp(&mut self, timestamp: u64) {
+        let elapsed = timestamp.saturating_sub(self.current_timestamp);
         self.current_timestamp = timestamp;
+        if elapsed == 0 {
+            return;
+        }
+
+        let paused_panes = &self.ttl_paused_panes;
+        let paused_tabs = &self.ttl_paused_tabs;
+        for queue in [
+            &mut self.critical_queue,
+            &mut self.high_queue,
+            &mut self.normal_queue,
+            &mut self.low_queue,
+        ] {
+            for notification in queue.iter_mut() {
+                if !is_paused(notification, paused_panes, paused_tabs) {
+                    notification.advance_ttl(elapsed);
+                }
+            }
+        }
+    }
+
+    /// Pause TTL countdown for notifications targeting the given panes or tabs
+    pub fn set_ttl_paused(&mut self, panes: BTreeSet<u32>, tabs: BTreeSet<usize>) {
+        self.ttl_paused_panes = panes;
+        self.ttl_paused_tabs = tabs;
+    }
+
+    /// Check if a notification's TTL countdown is paused
+    pub fn is_ttl_paused(&self, notification: &Notification) -> bool {
+        is_paused(notification, &self.ttl_paused_panes, &self.ttl_paused_tabs)
+    }
+
+    /// Get the remaining TTL for a notification (None = no expiry)
+    pub fn ttl_remaining(&self, notification: &Notification) -> Option<u64> {
+        notification.ttl_left()
     }
 
-    /// Enqueue a notification
-    pub fn enqueue(&mut self, mut notification: Notification) {
+    /// Get all notifications in a single priority lane, in dequeue order
+    pub fn lane(&self, priority: &Priority) -> Vec<&Notification> {
+        self.get_queue(priority).iter().collect()
+    }
+
+    /// Enqueue a notification, applying admission control
+    pub fn enqueue(&mut self, mut notification: Notification) -> Admission {
         // Set default TTL if not specified
         if notification.ttl_ms == 0 {
             notification.ttl_ms = self.default_ttl_ms;
@@ -67,25 +264,151 @@ impl NotificationQueue {
             notification.timestamp = self.current_timestamp;
         }
 
+        // Count time already passed since a backdated timestamp
+        notification.ttl_elapsed_ms = notification.ttl_elapsed_ms
+            .max(self.current_timestamp.saturating_sub(notification.timestamp));
+
+        let priority = notification.priority;
+
+        // Near capacity, Low/Normal notifications are merged or rejected before anything
+        // more important has to be dropped
+        if priority <= Priority::Normal && self.len() >= self.admission.high_water_mark() {
+            if self.merge_equivalent(&notification) {
+                self.total_merged += 1;
+                return Admission::Merged;
+            }
+            return self.reject(&priority);
+        }
+
+        // At global capacity, make room by evicting a strictly lower priority entry
+        // (Critical may only displace the oldest Critical)
+        if self.len() >= self.admission.max_total {
+            let made_room = if priority == Priority::Critical {
+                self.evict_lowest_up_to(Priority::Critical)
+            } else {
+                self.evict_lowest_below(priority)
+            };
+            if !made_room {
+                return self.reject(&priority);
+            }
+        }
+
         // Copy max_size before mutable borrow
         let max_size = self.max_size;
-        let queue = self.get_queue_mut(&notification.priority);
+        let size = notification.approx_size();
+        let queue = self.get_queue_mut(&priority);
 
         // If queue is full, remove oldest
-        if queue.len() >= max_size {
-            queue.pop_front();
+        let evicted = if queue.len() >= max_size {
+            queue.pop_front()
+        } else {
+            None
+        };
+        if let Some(evicted) = evicted {
+            self.queued_bytes = self.queued_bytes.saturating_sub(evicted.approx_size());
+            self.total_evicted += 1;
+            self.dropped[priority as usize] += 1;
+        }
+
+        // Enforce the global byte budget, evicting lowest priority (oldest first)
+        while self.queued_bytes + size > self.max_bytes && self.evict_lowest_up_to(Priority::Critical) {}
+
+        self.queued_bytes += size;
+        self.get_queue_mut(&priority).push_back(notification);
+        Admission::Admitted
+    }
+
+    /// Record a rejected notification
+    fn reject(&mut self, priority: &Priority) -> Admission {
+        self.total_rejected += 1;
+        self.dropped[*priority as usize] += 1;
+        Admission::Rejected
+    }
+
+    /// ID of the queued notification an incoming one would be folded into
+    pub fn equivalent_id(&self, notification: &Notification) -> Option<String> {
+        self.get_queue(&notification.priority).iter()
+            .find(|n| is_equivalent(n, notification))
+            .map(|n| n.id.clone())
+    }
+
+    /// Fold a notification into a queued one for the same pane and type (newest content wins)
+    fn merge_equivalent(&mut self, notification: &Notification) -> bool {
+        let queue = self.get_queue_mut(&notification.priority);
+        let existing = queue.iter_mut().find(|n| is_equivalent(n, notification));
+
+        match existing {
+            Some(existing) => {
+                let old_size = existing.approx_size();
+                existing.message = notification.message.clone();
+                existing.title = notification.title.clone();
+                existing.percent = notification.percent;
+                existing.timestamp = notification.timestamp;
+                let new_size = existing.approx_size();
+                self.queued_bytes = (self.queued_bytes + new_size).saturating_sub(old_size);
+                true
+            }
+            None => false,
         }
+    }
 
-        queue.push_back(notification);
+    /// Evict the oldest notification from the lowest non-empty level strictly below `ceiling`
+    fn evict_lowest_below(&mut self, ceiling: Priority) -> bool {
+        match ceiling {
+            Priority::Low => false,
+            Priority::Normal => self.evict_lowest_up_to(Priority::Low),
+            Priority::High => self.evict_lowest_up_to(Priority::Normal),
+            Priority::Critical => self.evict_lowest_up_to(Priority::High),
+        }
     }
 
-    /// Dequeue the highest priority ready notification
+    /// Evict the oldest notification from the lowest non-empty level up to `ceiling`
+    fn evict_lowest_up_to(&mut self, ceiling: Priority) -> bool {
+        for priority in [Priority::Low, Priority::Normal, Priority::High, Priority::Critical] {
+            if priority > ceiling {
+                break;
+            }
+            if let Some(evicted) = self.get_queue_mut(&priority).pop_front() {
+                self.queued_bytes = self.queued_bytes.saturating_sub(evicted.approx_size());
+                self.total_evicted += 1;
+                self.dropped[priority as usize] += 1;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Number of notifications dropped (rejected or evicted) for a priority
+    pub fn dropped_count(&self, priority: &Priority) -> u64 {
+        self.dropped[*priority as usize]
+    }
+
+    /// Recompute the queued byte total after bulk removals
+    fn recompute_bytes(&mut self) {
+        self.queued_bytes = self.all().iter().map(|n| n.approx_size()).sum();
+    }
+
+    /// Dequeue the highest priority ready notification (None once the display rate is used up
+    /// for the current second)
     pub fn dequeue_ready(&mut self) -> Option<Notification> {
+        if self.display_rate > 0 {
+            if self.current_timestamp >= self.rate_window.0 + RATE_WINDOW_MS {
+                self.rate_window = (self.current_timestamp, 0);
+            }
+            if self.rate_window.1 >= self.display_rate {
+                return None;
+            }
+        }
+
         // Try queues in priority order
         for priority in [Priority::Critical, Priority::High, Priority::Normal, Priority::Low] {
-            let queue = self.get_queue_mut(&priority);
-            if let Some(notification) = queue.pop_front() {
+            let Some(index) = self.next_index(&priority) else { continue };
+            if let Some(notification) = self.get_queue_mut(&priority).remove(index) {
+                self.queued_bytes = self.queued_bytes.saturating_sub(notification.approx_size());
+                self.rate_window.1 += 1;
                 self.total_processed += 1;
+                *self.dequeued_by_source.entry(notification.source.clone()).or_insert(0) += 1;
+                self.last_served[priority as usize] = Some(notification.source.clone());
                 return Some(notification);
             }
         }
@@ -95,14 +418,29 @@ impl NotificationQueue {
     /// Peek at the highest priority notification without removing
     pub fn peek(&self) -> Option<&Notification> {
         for priority in [Priority::Critical, Priority::High, Priority::Normal, Priority::Low] {
-            let queue = self.get_queue(&priority);
-            if let Some(notification) = queue.front() {
-                return Some(notification);
+            if let Some(index) = self.next_index(&priority) {
+                return self.get_queue(&priority).get(index);
             }
         }
         None
     }
 
+    /// Position in a priority level of the notification to dequeue next: the front, or with the
+    /// fair strategy the oldest of the source after the one served last (wrapping around)
+    fn next_index(&self, priority: &Priority) -> Option<usize> {
+        let queue = self.get_queue(priority);
+        if queue.is_empty() {
+            return None;
+        }
+        if self.strategy == DequeueStrategy::Fifo {
+            return Some(0);
+        }
+        let sources: BTreeSet<&str> = queue.iter().map(|n| n.source.as_str()).collect();
+        let last = self.last_served[*priority as usize].as_deref();
+        let next = sources.iter().find(|source| Some(**source) > last).or_else(|| sources.iter().next())?;
+        queue.iter().position(|n| n.source == *next)
+    }
+
     /// Get the total number of notifications in queue
     pub fn len(&self) -> usize {
         self.critical_queue.len()
@@ -127,6 +465,7 @@ impl NotificationQueue {
         self.high_queue.clear();
         self.normal_queue.clear();
         self.low_queue.clear();
+        self.queued_bytes = 0;
     }
 
     /// Clear notifications for a specific pane
@@ -135,6 +474,64 @@ impl NotificationQueue {
         self.high_queue.retain(|n| n.pane_id != Some(pane_id));
         self.normal_queue.retain(|n| n.pane_id != Some(pane_id));
         self.low_queue.retain(|n| n.pane_id != Some(pane_id));
+        self.recompute_bytes();
+    }
+
+    /// Remove a notification by ID, returning whether it was queued
+    pub fn remove_by_id(&mut self, id: &str) -> bool {
+        let before = self.len();
+        self.critical_queue.retain(|n| n.id != id);
+        self.high_queue.retain(|n| n.id != id);
+        self.normal_queue.retain(|n| n.id != id);
+        self.low_queue.retain(|n| n.id != id);
+        self.recompute_bytes();
+        self.len() < before
+    }
+
+    /// Lane and position of a queued notification
+    pub fn position(&self, id: &str) -> Option<(Priority, usize)> {
+        [Priority::Critical, Priority::High, Priority::Normal, Priority::Low].into_iter()
+            .find_map(|priority| self.get_queue(&priority).iter()
+                .position(|n| n.id == id)
+                .map(|index| (priority, index)))
+    }
+
+    /// A queued notification, for changes that keep its place (e.g. a longer TTL)
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Notification> {
+        let (lane, index) = self.position(id)?;
+        self.get_queue_mut(&lane).get_mut(index)
+    }
+
+    /// Move a queued notification to another lane or the front of its own, returning its lane.
+    /// Promoted and demoted notifications join the back of their new lane with its priority.
+    pub fn reorder(&mut self, id: &str, queue_move: QueueMove) -> Result<Priority, String> {
+        let (lane, index) = self.position(id).ok_or_else(|| format!("{} is not queued", id))?;
+        let target = match queue_move {
+            QueueMove::Promote => lane.raised().ok_or_else(|| format!("{} is already critical", id))?,
+            QueueMove::Demote => lane.lowered().ok_or_else(|| format!("{} is already low", id))?,
+            QueueMove::Front => lane,
+        };
+        let Some(mut notification) = self.get_queue_mut(&lane).remove(index) else {
+            return Err(format!("{} is not queued", id));
+        };
+        notification.priority = target;
+        if queue_move == QueueMove::Front {
+            self.get_queue_mut(&target).push_front(notification);
+        } else {
+            self.get_queue_mut(&target).push_back(notification);
+        }
+        Ok(target)
+    }
+
+    /// Remove notifications of one type, returning how many were queued
+    pub fn remove_by_type(&mut self, notification_type: &NotificationType) -> usize {
+        let before = self.len();
+        self.critical_queue.retain(|n| n.notification_type != *notification_type);
+        self.high_queue.retain(|n| n.notification_type != *notification_type);
+        self.normal_queue.retain(|n| n.notification_type != *notification_type);
+        self.low_queue.retain(|n| n.notification_type != *notification_type);
+        self.recompute_bytes();
+        before - self.len()
     }
 
     /// Clear notifications for a specific tab
@@ -143,12 +540,14 @@ impl NotificationQueue {
         self.high_queue.retain(|n| n.tab_index != Some(tab_index));
         self.normal_queue.retain(|n| n.tab_index != Some(tab_index));
         self.low_queue.retain(|n| n.tab_index != Some(tab_index));
+        self.recompute_bytes();
     }
 
-    /// Remove expired notifications
-    pub fn cleanup_expired(&mut self) {
-        let current = self.current_timestamp;
-        let mut expired_count = 0u64;
+    /// Remove expired notifications, returning them for expiry handling (notifications with
+    /// `on_expire: demote` are demoted and stay queued as Low for another TTL instead)
+    pub fn cleanup_expired(&mut self) -> Vec<Notification> {
+        let mut expired = Vec::new();
+        let mut demoted = Vec::new();
 
         for queue in [
             &mut self.critical_queue,
@@ -156,12 +555,25 @@ impl NotificationQueue {
             &mut self.normal_queue,
             &mut self.low_queue,
         ] {
-            let before_len = queue.len();
-            queue.retain(|n| !n.is_expired(current));
-            expired_count += (before_len - queue.len()) as u64;
+            let (kept, removed): (VecDeque<Notification>, VecDeque<Notification>) = queue
+                .drain(..)
+                .partition(|n| !n.is_ttl_spent());
+            *queue = kept;
+            for mut notification in removed {
+                if notification.demote() {
+                    demoted.push(notification);
+                } else {
+                    expired.push(notification);
+                }
+            }
         }
+        self.low_queue.extend(demoted);
 
-        self.total_expired += expired_count;
+        self.total_expired += expired.len() as u64;
+        if !expired.is_empty() {
+            self.recompute_bytes();
+        }
+        expired
     }
 
     /// Get queue statistics
@@ -174,7 +586,18 @@ impl NotificationQueue {
             low_count: self.low_queue.len(),
             total_processed: self.total_processed,
             total_expired: self.total_expired,
+            total_evicted: self.total_evicted,
+            total_rejected: self.total_rejected,
+            total_merged: self.total_merged,
+            dropped_low: self.dropped[Priority::Low as usize],
+            dropped_normal: self.dropped[Priority::Normal as usize],
+            dropped_high: self.dropped[Priority::High as usize],
+            dropped_critical: self.dropped[Priority::Critical as usize],
+            queued_bytes: self.queued_bytes,
             max_size: self.max_size,
+            max_bytes: self.max_bytes,
+            strategy: self.strategy,
+            dequeued_by_source: self.dequeued_by_source.clone(),
         }
     }
 
@@ -267,6 +690,39 @@ impl NotificationQueue {
     }
 }
 
+/// Operator change to a queued notification's place (`promote`, `demote`, `move_front`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueMove {
+    /// One priority lane up
+    Promote,
+    /// One priority lane down
+    Demote,
+    /// To the front of its lane
+    Front,
+}
+
+impl QueueMove {
+    /// Past tense description, e.g. `promoted`
+    pub fn describe(&self) -> &'static str {
+        match self {
+            QueueMove::Promote => "promoted",
+            QueueMove::Demote => "demoted",
+            QueueMove::Front => "moved to front",
+        }
+    }
+}
+
+/// Whether a notification targets a paused pane or tab
+fn is_paused(notification: &Notification, panes: &BTreeSet<u32>, tabs: &BTreeSet<usize>) -> bool {
+    notification.pane_id.map(|id| panes.contains(&id)).unwrap_or(false)
+        || notification.tab_index.map(|index| tabs.contains(&index)).unwrap_or(false)
+}
+
+/// Whether two notifications can be coalesced (same pane and type)
+fn is_equivalent(queued: &Notification, incoming: &Notification) -> bool {
+    queued.pane_id == incoming.pane_id && queued.notification_type == incoming.notification_type
+}
+
 /// Queue statistics
 #[derive(Debug, Clone, Default)]
 pub struct QueueStats {
@@ -284,14 +740,48 @@ pub struct QueueStats {
     pub total_processed: u64,
     /// Total notifications expired
     pub total_expired: u64,
+    /// Total notifications evicted to enforce size caps
+    pub total_evicted: u64,
+    /// Total notifications rejected at admission
+    pub total_rejected: u64,
+    /// Total notifications merged into an equivalent queued notification
+    pub total_merged: u64,
+    /// Low priority notifications dropped (rejected or evicted)
+    pub dropped_low: u64,
+    /// Normal priority notifications dropped (rejected or evicted)
+    pub dropped_normal: u64,
+    /// High priority notifications dropped (rejected or evicted)
+    pub dropped_high: u64,
+    /// Critical priority notifications dropped (rejected or evicted)
+    pub dropped_critical: u64,
+    /// Current total bytes of queued notification content
+    pub queued_bytes: usize,
     /// Maximum queue size
     pub max_size: usize,
+    /// Maximum total bytes of queued notification content
+    pub max_bytes: usize,
+    /// Order notifications of the same priority leave in
+    pub strategy: DequeueStrategy,
+    /// Notifications dequeued, by source
+    pub dequeued_by_source: BTreeMap<String, u64>,
+}
+
+impl QueueStats {
+    /// Stats line, e.g. `queue: fair, dequeued by source: ci 3, claude 4`
+    pub fn line(&self) -> String {
+        let sources: Vec<String> = self.dequeued_by_source.iter()
+            .map(|(source, count)| format!("{} {}", if source.is_empty() { "-" } else { source }, count))
+            .collect();
+        format!("queue: {}, dequeued by source: {}",
+            self.strategy.name(),
+            if sources.is_empty() { "none".to_string() } else { sources.join(", ") })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::notification::NotificationType;
+    use crate::notification::{ExpiryAction, NotificationType};
 
     #[test]
     fn test_queue_creation() {
@@ -382,6 +872,137 @@ mod tests {
         assert_eq!(queue.count_by_priority(&Priority::Low), 3);
     }
 
+    #[test]
+    fn test_lane_and_ttl_remaining() {
+        let mut queue = NotificationQueue::new(100, 300_000);
+        queue.update_timestamp(1000);
+
+        queue.enqueue(Notification::error("Error").with_ttl(10_000));
+        queue.enqueue(Notification::info("Info"));
+
+        queue.update_timestamp(4000);
+
+        let critical = queue.lane(&Priority::Critical);
+        assert_eq!(critical.len(), 1);
+        assert!(queue.lane(&Priority::High).is_empty());
+        assert_eq!(queue.ttl_remaining(critical[0]), Some(7000));
+    }
+
+    #[test]
+    fn test_ttl_paused_for_hidden_targets() {
+        let mut queue = NotificationQueue::new(100, 300_000);
+        queue.enqueue(Notification::error("Background").for_pane(7).with_ttl(5000));
+        queue.enqueue(Notification::error("Foreground").for_pane(1).with_ttl(5000));
+
+        queue.set_ttl_paused([7].into_iter().collect(), BTreeSet::new());
+        queue.update_timestamp(6000);
+        let expired = queue.cleanup_expired();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].pane_id, Some(1));
+
+        // Countdown resumes where it stopped once the pane is visible
+        queue.set_ttl_paused(BTreeSet::new(), BTreeSet::new());
+        queue.update_timestamp(10_000);
+        assert_eq!(queue.ttl_remaining(queue.peek().unwrap()), Some(1000));
+    }
+
+    #[test]
+    fn test_byte_cap_evicts_lowest_priority_first() {
+        let mut queue = NotificationQueue::new(100, 300_000).with_max_bytes(200);
+        let body = "x".repeat(40);
+
+        queue.enqueue(Notification::error(&body));
+        queue.enqueue(Notification::info(&body));
+        queue.enqueue(Notification::info(&body));
+        queue.enqueue(Notification::error(&body));
+
+        let stats = queue.stats();
+        assert!(stats.queued_bytes <= 200);
+        assert!(stats.total_evicted > 0);
+        // Critical notifications survive while low priority ones are evicted
+        assert_eq!(stats.critical_count, 2);
+        assert!(stats.low_count < 2);
+    }
+
+    #[test]
+    fn test_byte_accounting_on_removal() {
+        let mut queue = NotificationQueue::new(100, 300_000);
+        queue.enqueue(Notification::info("Pane 1").for_pane(1));
+        queue.enqueue(Notification::info("Pane 2").for_pane(2));
+        assert!(queue.stats().queued_bytes > 0);
+
+        queue.remove_for_pane(1);
+        queue.dequeue_ready();
+        assert_eq!(queue.stats().queued_bytes, 0);
+    }
+
+    #[test]
+    fn test_admission_rejects_low_before_dropping_critical() {
+        let policy = AdmissionPolicy { max_total: 4, high_water_ratio: 0.5 };
+        let mut queue = NotificationQueue::new(100, 300_000).with_admission(policy);
+
+        assert_eq!(queue.enqueue(Notification::error("Critical 1")), Admission::Admitted);
+        assert_eq!(queue.enqueue(Notification::error("Critical 2")), Admission::Admitted);
+
+        // At the high-water mark, Low is rejected (no equivalent to merge into)
+        assert_eq!(queue.enqueue(Notification::info("Low").for_pane(1)), Admission::Rejected);
+        assert_eq!(queue.dropped_count(&Priority::Low), 1);
+
+        // Critical still gets in until the global cap, then displaces only the oldest Critical
+        assert_eq!(queue.enqueue(Notification::error("Critical 3")), Admission::Admitted);
+        assert_eq!(queue.enqueue(Notification::error("Critical 4")), Admission::Admitted);
+        assert_eq!(queue.enqueue(Notification::error("Critical 5")), Admission::Admitted);
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.dropped_count(&Priority::Critical), 1);
+    }
+
+    #[test]
+    fn test_admission_merges_equivalent() {
+        let policy = AdmissionPolicy { max_total: 10, high_water_ratio: 0.0 };
+        let mut queue = NotificationQueue::new(100, 300_000).with_admission(policy);
+
+        // High priority bypasses the high-water restriction
+        queue.enqueue(Notification::warning("Disk 80%").for_pane(2).with_priority(Priority::High));
+        queue.enqueue(Notification::info("Seed").for_pane(2).with_priority(Priority::High));
+        assert_eq!(queue.len(), 2);
+
+        let mut low = Notification::info("Progress 10%").for_pane(5);
+        low.priority = Priority::Low;
+        assert_eq!(queue.enqueue(low), Admission::Rejected);
+
+        // Same pane and type as a queued Normal entry: merged
+        queue.get_queue_mut(&Priority::Normal).push_back(Notification::info("Build 1").for_pane(7).with_priority(Priority::Normal));
+        let update = Notification::info("Build 2").for_pane(7).with_priority(Priority::Normal);
+        assert_eq!(queue.enqueue(update), Admission::Merged);
+        assert_eq!(queue.lane(&Priority::Normal)[0].message, "Build 2");
+        assert_eq!(queue.stats().total_merged, 1);
+    }
+
+    #[test]
+    fn test_remove_by_id() {
+        let mut queue = NotificationQueue::new(100, 300_000);
+        let mut notification = Notification::attention("Input needed");
+        notification.id = "ask-1".to_string();
+        queue.enqueue(notification);
+        queue.enqueue(Notification::info("Other"));
+
+        assert!(queue.remove_by_id("ask-1"));
+        assert!(!queue.remove_by_id("ask-1"));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_by_type() {
+        let mut queue = NotificationQueue::new(100, 300_000);
+        queue.enqueue(Notification::success("Built"));
+        queue.enqueue(Notification::success("Tested").with_priority(Priority::High));
+        queue.enqueue(Notification::error("Deploy failed"));
+
+        assert_eq!(queue.remove_by_type(&NotificationType::Success), 2);
+        assert_eq!(queue.remove_by_type(&NotificationType::Success), 0);
+        assert_eq!(queue.len(), 1);
+    }
+
     #[test]
     fn test_stats() {
         let mut queue = NotificationQueue::new(100, 300_000);
@@ -396,4 +1017,84 @@ mod tests {
         assert_eq!(stats.high_count, 1);
         assert_eq!(stats.low_count, 1);
     }
+
+    #[test]
+    fn test_fair_strategy_interleaves_sources() {
+        let from = |source: &str, message: &str| Notification::info(message).from_source(source);
+        let mut queue = NotificationQueue::new(100, 300_000).with_strategy(DequeueStrategy::Fair);
+        for n in 1..=4 {
+            queue.enqueue(from("flood", &format!("flood {}", n)));
+        }
+        queue.enqueue(from("ci", "ci 1"));
+        queue.enqueue(from("claude", "claude 1"));
+        queue.enqueue(from("ci", "ci 2"));
+        queue.enqueue(Notification::error("error").from_source("flood"));
+
+        // Higher priorities still go first; within a level sources take turns, oldest first
+        assert_eq!(queue.peek().map(|n| n.message.as_str()), Some("error"));
+        let order: Vec<String> = std::iter::from_fn(|| queue.dequeue_ready()).map(|n| n.message).collect();
+        assert_eq!(order, vec!["error", "ci 1", "claude 1", "flood 1", "ci 2", "flood 2", "flood 3", "flood 4"]);
+
+        let stats = queue.stats();
+        assert_eq!(stats.dequeued_by_source.get("flood"), Some(&5));
+        assert_eq!(stats.line(), "queue: fair, dequeued by source: ci 2, claude 1, flood 5");
+
+        // The default strategy keeps arrival order
+        let mut queue = NotificationQueue::new(100, 300_000);
+        queue.enqueue(from("flood", "flood 1"));
+        queue.enqueue(from("flood", "flood 2"));
+        queue.enqueue(from("ci", "ci 1"));
+        assert_eq!(queue.dequeue_ready().map(|n| n.message), Some("flood 1".to_string()));
+        assert_eq!(queue.dequeue_ready().map(|n| n.message), Some("flood 2".to_string()));
+    }
+
+    #[test]
+    fn test_display_rate_limits_dequeues_per_second() {
+        let mut queue = NotificationQueue::new(100, 300_000).with_display_rate(2);
+        for n in 1..=3 {
+            queue.enqueue(Notification::info(&format!("info {}", n)));
+        }
+        assert!(queue.dequeue_ready().is_some());
+        assert!(queue.dequeue_ready().is_some());
+        assert!(queue.dequeue_ready().is_none());
+        assert_eq!(queue.len(), 1);
+
+        queue.update_timestamp(1000);
+        assert_eq!(queue.dequeue_ready().map(|n| n.message), Some("info 3".to_string()));
+    }
+
+    #[test]
+    fn test_cleanup_demotes_instead_of_discarding() {
+        let mut queue = NotificationQueue::new(10, 300_000);
+        let mut notification = Notification::error("Build failed");
+        notification.on_expire = ExpiryAction::Demote;
+        notification.ttl_ms = 1000;
+        notification.ttl_elapsed_ms = 2000;
+        queue.enqueue(notification);
+
+        assert!(queue.cleanup_expired().is_empty());
+        let demoted = queue.dequeue_ready().unwrap();
+        assert_eq!(demoted.notification_type, NotificationType::Warning);
+        assert!(demoted.demoted);
+    }
+
+    #[test]
+    fn test_reorder_by_id() {
+        let mut queue = NotificationQueue::new(10, 300_000);
+        for (id, pane_id) in [("a", 1), ("b", 2), ("c", 3)] {
+            let mut notification = Notification::info(id).for_pane(pane_id);
+            notification.id = id.to_string();
+            queue.enqueue(notification);
+        }
+
+        assert_eq!(queue.reorder("c", QueueMove::Front), Ok(Priority::Low));
+        assert_eq!(queue.position("c"), Some((Priority::Low, 0)));
+
+        assert_eq!(queue.reorder("b", QueueMove::Promote), Ok(Priority::Normal));
+        assert_eq!(queue.dequeue_ready().map(|n| (n.id, n.priority)), Some(("b".to_string(), Priority::Normal)));
+
+        assert!(queue.reorder("a", QueueMove::Demote).is_err());
+        assert!(queue.reorder("missing", QueueMove::Front).is_err());
+        assert_eq!(queue.len(), 2);
+    }
 }
@@ -0,0 +1,113 @@
+//! Queue diff module for Zellij Visual Notifications
+//!
+//! While the queue inspector is open, the queue is compared with the snapshot taken at the last
+//! refresh, so the overlay can show what changed: entries that arrived within the last
+//! `CHANGE_HIGHLIGHT_MS` are tinted green and marked `+`, and entries that left the queue (shown,
+//! expired, dropped by a rule, evicted) stay in their lane struck through and marked `-` for as
+//! long. Watching the overlay while sending test traffic then shows rules and rate limits at
+//! work. What is queued when the inspector opens is not highlighted.
+
+use std::collections::BTreeMap;
+
+use crate::notification::{Notification, Priority};
+use crate::queue::NotificationQueue;
+
+/// How long an added or removed entry stays highlighted (ms)
+pub const CHANGE_HIGHLIGHT_MS: u64 = 2_000;
+
+/// Difference between the queue and the earlier snapshots
+#[derive(Debug, Clone, Default)]
+pub struct QueueDiff {
+    /// Entries at the last refresh by ID, with the plugin time they were first seen (None when
+    /// already queued at the first refresh)
+    seen: BTreeMap<String, (Notification, Option<u64>)>,
+    /// Entries that left the queue, with the plugin time they were found missing
+    removed: Vec<(Notification, u64)>,
+    /// Whether a first snapshot was taken
+    started: bool,
+    /// Whether the last refresh found highlights
+    highlighted: bool,
+}
+
+impl QueueDiff {
+    /// Take a snapshot of the queue and compare it with the last one. Returns whether the
+    /// overlay needs to be drawn again: it shows highlights, or the last ones just ran out.
+    pub fn refresh(&mut self, queue: &NotificationQueue, now_ms: u64) -> bool {
+        let mut seen = BTreeMap::new();
+        for notification in queue.all() {
+            let entry = match self.seen.remove(&notification.id) {
+                Some((mut known, first_seen)) => {
+                    // Promoted or demoted since
+                    known.priority = notification.priority;
+                    (known, first_seen)
+                }
+                None => (notification.clone(), self.started.then_some(now_ms)),
+            };
+            seen.insert(notification.id.clone(), entry);
+        }
+        for (notification, _) in std::mem::replace(&mut self.seen, seen).into_values() {
+            self.removed.push((notification, now_ms));
+        }
+        self.removed.retain(|(_, removed_at)| now_ms < removed_at + CHANGE_HIGHLIGHT_MS);
+        self.started = true;
+
+        let highlighted = !self.removed.is_empty() || self.seen.keys().any(|id| self.is_new(id, now_ms));
+        let redraw = highlighted || self.highlighted;
+        self.highlighted = highlighted;
+        redraw
+    }
+
+    /// Forget the snapshots (inspector closed)
+    pub fn reset(&mut self) {
+        if self.started {
+            *self = Self::default();
+        }
+    }
+
+    /// Whether a queued entry arrived within the highlight time
+    pub fn is_new(&self, id: &str, now_ms: u64) -> bool {
+        self.seen.get(id)
+            .and_then(|(_, first_seen)| *first_seen)
+            .is_some_and(|first_seen| now_ms < first_seen + CHANGE_HIGHLIGHT_MS)
+    }
+
+    /// Entries of a lane that left the queue within the highlight time, oldest removal first
+    pub fn removed(&self, priority: &Priority, now_ms: u64) -> impl Iterator<Item = &Notification> {
+        self.removed.iter()
+            .filter(move |(notification, removed_at)| notification.priority == *priority && now_ms < removed_at + CHANGE_HIGHLIGHT_MS)
+            .map(|(notification, _)| notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_added_and_removed_entries_fade_out() {
+        let mut queue = NotificationQueue::new(100, 300_000);
+        let mut first = Notification::error("Build failed").for_pane(3);
+        first.id = "first".to_string();
+        queue.enqueue(first);
+
+        // Already queued when the inspector opens: not highlighted
+        let mut diff = QueueDiff::default();
+        assert!(!diff.refresh(&queue, 0));
+        assert!(!diff.is_new("first", 0));
+
+        let mut second = Notification::warning("Slow test").for_pane(4);
+        second.id = "second".to_string();
+        queue.enqueue(second);
+        queue.remove_by_id("first");
+        assert!(diff.refresh(&queue, 1_000));
+        assert!(diff.is_new("second", 1_000));
+        let removed: Vec<&str> = diff.removed(&Priority::Critical, 1_000).map(|n| n.id.as_str()).collect();
+        assert_eq!(removed, vec!["first"]);
+
+        // Both highlights run out, with one more redraw to clear them
+        assert!(diff.refresh(&queue, 1_000 + CHANGE_HIGHLIGHT_MS));
+        assert!(!diff.refresh(&queue, 2_000 + CHANGE_HIGHLIGHT_MS));
+        assert!(!diff.is_new("second", 1_000 + CHANGE_HIGHLIGHT_MS));
+        assert_eq!(diff.removed(&Priority::Critical, 1_000 + CHANGE_HIGHLIGHT_MS).count(), 0);
+    }
+}
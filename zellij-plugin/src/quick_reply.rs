@@ -0,0 +1,69 @@
+//! Quick reply module for Zellij Visual Notifications
+//!
+//! Attention notifications can carry `reply_options` (e.g. `["yes", "no", "always"]`) for the
+//! simple prompts Claude Code waits on. The options are shown numbered after the message
+//! (`1 yes  2 no  3 always`), and picking one (its digit key, or the `reply <n> [pane]` pipe
+//! command) types the option's text followed by Enter into the notified pane, then acknowledges
+//! the notification, so a permission prompt is answered without switching panes. Options come
+//! from the sender and end up on a terminal's input, so control characters are stripped and only
+//! the first nine short options are kept, one per digit key.
+
+/// Most options a notification keeps (digit keys 1 - 9)
+pub const MAX_REPLY_OPTIONS: usize = 9;
+
+/// Longest option kept (characters)
+const MAX_OPTION_CHARS: usize = 40;
+
+/// Clean sender options: control characters removed, blanks dropped, long ones cut, at most
+/// `MAX_REPLY_OPTIONS`
+pub fn sanitize(options: &[String]) -> Vec<String> {
+    options.iter()
+        .map(|option| option.chars().filter(|c| !c.is_control()).take(MAX_OPTION_CHARS).collect::<String>())
+        .map(|option| option.trim().to_string())
+        .filter(|option| !option.is_empty())
+        .take(MAX_REPLY_OPTIONS)
+        .collect()
+}
+
+/// Option picked by its number (1-based)
+pub fn pick(options: &[String], number: usize) -> Option<&str> {
+    number.checked_sub(1).and_then(|index| options.get(index)).map(String::as_str)
+}
+
+/// Numbered hints, e.g. `1 yes  2 no  3 always`
+pub fn hints(options: &[String]) -> String {
+    options.iter().enumerate()
+        .map(|(index, option)| format!("{} {}", index + 1, option))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// What is typed into the pane for an option: its text, then Enter
+pub fn keystrokes(option: &str) -> String {
+    format!("{}\r", option)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitizes_and_numbers_options() {
+        let options = sanitize(&[
+            " yes ".to_string(),
+            "".to_string(),
+            "no\u{1b}[2J\r\n".to_string(),
+            "always".to_string(),
+        ]);
+        assert_eq!(options, vec!["yes", "no[2J", "always"]);
+        assert_eq!(hints(&options), "1 yes  2 no[2J  3 always");
+        assert_eq!(pick(&options, 3), Some("always"));
+        assert_eq!(pick(&options, 0), None);
+        assert_eq!(pick(&options, 4), None);
+        assert_eq!(keystrokes("yes"), "yes\r");
+
+        let many: Vec<String> = (1..=12).map(|n| n.to_string()).collect();
+        assert_eq!(sanitize(&many).len(), MAX_REPLY_OPTIONS);
+        assert_eq!(sanitize(&["x".repeat(60)]).first().map(|option| option.len()), Some(40));
+    }
+}
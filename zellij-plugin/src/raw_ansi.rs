@@ -0,0 +1,192 @@
+//! Raw ANSI module for Zellij Visual Notifications
+//!
+//! An escape hatch for terminal features the color manager does not model, such as kitty's
+//! curly underlines (`\e[4:3m`) and underline colors (`\e[58:2::255:0:0m`): `raw_ansi` sets a
+//! prefix and suffix per status bar segment or notification type, written around what the
+//! plugin draws. Snippets may hold SGR sequences only (`\e[...m`, with `\e`, `\x1b` or `\033`
+//! for the escape character), and the suffix must turn off every attribute the prefix turns on,
+//! or reset them all, so nothing bleeds into the rest of the bar. The prefix is applied again
+//! after each reset inside the wrapped text. Monochrome output leaves the snippets out.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// Full SGR reset, as the color manager writes it
+const SGR_RESET: &str = "\x1b[0m";
+
+/// A prefix and suffix written around drawn text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawAnsi {
+    /// Written before the text (and after each reset inside it)
+    pub prefix: String,
+    /// Written after the text
+    pub suffix: String,
+}
+
+impl RawAnsi {
+    /// Parse `prefix=... suffix=...` settings (either may be left out)
+    pub fn parse(settings: &[String]) -> Result<Self, String> {
+        let mut raw = Self::default();
+        for setting in settings {
+            match setting.split_once('=') {
+                Some(("prefix", value)) => raw.prefix = unescape(value),
+                Some(("suffix", value)) => raw.suffix = unescape(value),
+                _ => return Err(format!("unknown raw_ansi setting: {} (expected prefix=... or suffix=...)", setting)),
+            }
+        }
+        Ok(raw)
+    }
+
+    /// Check that both snippets are SGR sequences only and that the suffix closes the prefix
+    pub fn validate(&self) -> Result<(), String> {
+        let opened = sgr_params(&self.prefix).map_err(|e| format!("prefix: {}", e))?;
+        let closed = sgr_params(&self.suffix).map_err(|e| format!("suffix: {}", e))?;
+        if closed.iter().any(|param| is_reset(param)) {
+            return Ok(());
+        }
+        let closers: Vec<u16> = closed.iter().filter_map(|param| leading_code(param)).collect();
+        for param in opened.iter().filter(|param| !is_reset(param)) {
+            if let Some(closer) = leading_code(param).and_then(closing_code) {
+                if !closers.contains(&closer) {
+                    return Err(format!("suffix does not turn off SGR {} (add {} or 0)", param, closer));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Text wrapped in the snippets
+    pub fn wrap(&self, text: &str) -> String {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return text.to_string();
+        }
+        let reapplied = format!("{}{}", SGR_RESET, self.prefix);
+        format!("{}{}{}", self.prefix, text.replace(SGR_RESET, &reapplied), self.suffix)
+    }
+}
+
+/// Raw ANSI snippets by status bar segment and notification type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawAnsiConfig {
+    /// By segment name
+    pub segments: BTreeMap<String, RawAnsi>,
+    /// By notification type name
+    pub types: BTreeMap<String, RawAnsi>,
+}
+
+impl RawAnsiConfig {
+    /// Set the snippets of a `segment` or `type` target
+    pub fn set(&mut self, kind: &str, name: &str, raw: RawAnsi) -> Result<(), String> {
+        let targets = match kind {
+            "segment" => &mut self.segments,
+            "type" => &mut self.types,
+            _ => return Err(format!("unknown raw_ansi target: {} (expected segment or type)", kind)),
+        };
+        targets.insert(name.to_lowercase(), raw);
+        Ok(())
+    }
+}
+
+/// Replace the `\e`, `\x1b` and `\033` notations with the escape character
+fn unescape(value: &str) -> String {
+    value.replace("\\x1b", "\x1b").replace("\\033", "\x1b").replace("\\e", "\x1b")
+}
+
+/// Parameters of the SGR sequences making up a snippet, e.g. `["4:3", "58:2::255:0:0"]`
+fn sgr_params(snippet: &str) -> Result<Vec<String>, String> {
+    let mut params = Vec::new();
+    let mut rest = snippet;
+    while !rest.is_empty() {
+        let sequence = rest.strip_prefix("\x1b[")
+            .ok_or_else(|| "only SGR sequences (\\e[...m) are allowed".to_string())?;
+        let end = sequence.find('m')
+            .ok_or_else(|| "unterminated SGR sequence".to_string())?;
+        let body = &sequence[..end];
+        if !body.chars().all(|c| c.is_ascii_digit() || c == ';' || c == ':') {
+            return Err(format!("not an SGR sequence: \\e[{}m", body));
+        }
+        params.extend(split_params(body));
+        rest = &sequence[end + 1..];
+    }
+    Ok(params)
+}
+
+/// Split SGR parameters at `;`, keeping the arguments of extended colors with their code
+fn split_params(body: &str) -> Vec<String> {
+    let parts: Vec<&str> = body.split(';').collect();
+    let mut params = Vec::new();
+    let mut index = 0;
+    while index < parts.len() {
+        let part = parts[index];
+        // 38;5;n and 38;2;r;g;b (also 48 and 58) take their arguments along
+        let take = match (part, parts.get(index + 1)) {
+            ("38" | "48" | "58", Some(&"5")) => 3,
+            ("38" | "48" | "58", Some(&"2")) => 5,
+            _ => 1,
+        };
+        let end = (index + take).min(parts.len());
+        params.push(parts[index..end].join(";"));
+        index = end;
+    }
+    params
+}
+
+/// Whether a parameter resets all attributes
+fn is_reset(param: &str) -> bool {
+    param.is_empty() || leading_code(param) == Some(0)
+}
+
+/// Code of a parameter, e.g. 4 for `4:3`
+fn leading_code(param: &str) -> Option<u16> {
+    param.split([':', ';']).next()?.parse().ok()
+}
+
+/// Code turning off what an SGR code turns on (None for codes that turn something off)
+fn closing_code(code: u16) -> Option<u16> {
+    match code {
+        1 | 2 => Some(22),
+        3 => Some(23),
+        4 | 21 => Some(24),
+        5 | 6 => Some(25),
+        7 => Some(27),
+        8 => Some(28),
+        9 => Some(29),
+        30..=38 | 90..=97 => Some(39),
+        40..=48 | 100..=107 => Some(49),
+        51 | 52 => Some(54),
+        53 => Some(55),
+        58 => Some(59),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(prefix: &str, suffix: &str) -> RawAnsi {
+        RawAnsi::parse(&[format!("prefix={}", prefix), format!("suffix={}", suffix)]).unwrap()
+    }
+
+    #[test]
+    fn test_suffix_must_close_prefix() {
+        assert!(raw("\\e[4:3;58:2::255:0:0m", "\\e[59;24m").validate().is_ok());
+        assert!(raw("\\x1b[1m\\x1b[38;2;255;0;0m", "\\033[0m").validate().is_ok());
+        assert!(raw("\\e[3m", "\\e[m").validate().is_ok());
+        assert!(raw("", "").validate().is_ok());
+
+        let error = raw("\\e[4:3;58:2::255:0:0m", "\\e[24m").validate().unwrap_err();
+        assert!(error.contains("SGR 58:2::255:0:0"));
+        assert!(raw("\\e]8;;https://example.com\\e\\\\", "").validate().unwrap_err().starts_with("prefix: only SGR"));
+        assert!(raw("\\e[1m", "\\e[22").validate().unwrap_err().contains("unterminated"));
+        assert!(raw("bold", "").validate().is_err());
+        assert!(RawAnsi::parse(&["color=red".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_wrap_reapplies_prefix_after_resets() {
+        let raw = raw("\\e[4:3m", "\\e[24m");
+        assert_eq!(raw.wrap("\x1b[31mA\x1b[0m B"), "\x1b[4:3m\x1b[31mA\x1b[0m\x1b[4:3m B\x1b[24m");
+        assert_eq!(RawAnsi::default().wrap("plain"), "plain");
+    }
+}
@@ -0,0 +1,126 @@
+//! Delivery receipt module for Zellij Visual Notifications
+//!
+//! A sender that sets `request_ack` gets its notification's delivery status back on the CLI pipe
+//! it came from, one JSON line per transition (`{"id": "...", "status": "queued"}`, then
+//! `displayed`, `acknowledged`, ...). The pipe is kept open until a final status, so a script
+//! can wait on `zellij pipe` to learn whether its notification was seen.
+
+use std::collections::BTreeMap;
+
+/// Delivery status of a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Admitted to the queue
+    Queued,
+    /// Shown on its pane (or handed to the sinks when it has none)
+    Displayed,
+    /// Marked read by the user (still shown as a badge)
+    Read,
+    /// Acknowledged by the user
+    Acknowledged,
+    /// TTL ran out
+    Expired,
+    /// Withdrawn by a cancel message
+    Cancelled,
+    /// Its pane was taken over by a newer notification
+    Replaced,
+    /// Discarded before display (rule, full queue, focused pane in history-only mode)
+    Dropped,
+}
+
+impl DeliveryStatus {
+    /// Status name as sent in receipts
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Displayed => "displayed",
+            Self::Read => "read",
+            Self::Acknowledged => "acknowledged",
+            Self::Expired => "expired",
+            Self::Cancelled => "cancelled",
+            Self::Replaced => "replaced",
+            Self::Dropped => "dropped",
+        }
+    }
+
+    /// Whether no further transitions follow
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Queued | Self::Displayed | Self::Read)
+    }
+}
+
+/// A receipt to send
+#[derive(Debug, Clone, PartialEq)]
+pub struct Receipt {
+    /// CLI pipe to write to (None: log only)
+    pub pipe_id: Option<String>,
+    /// JSON line, newline-terminated
+    pub line: String,
+    /// Whether the pipe can be released after this receipt
+    pub last: bool,
+}
+
+/// Subscribed notifications and their latest status
+#[derive(Debug, Clone, Default)]
+pub struct ReceiptTracker {
+    subscriptions: BTreeMap<String, (Option<String>, Option<DeliveryStatus>)>,
+}
+
+impl ReceiptTracker {
+    /// Send receipts for a notification to a CLI pipe (None for other sources)
+    pub fn subscribe(&mut self, id: &str, pipe_id: Option<String>) {
+        self.subscriptions.insert(id.to_string(), (pipe_id, None));
+    }
+
+    /// Record a transition, returning the receipt to send (None if unsubscribed or unchanged).
+    /// A final status, or `last` for transitions after which nothing more can happen, ends the
+    /// subscription.
+    pub fn update(&mut self, id: &str, status: DeliveryStatus, last: bool) -> Option<Receipt> {
+        let (pipe_id, previous) = self.subscriptions.get_mut(id)?;
+        if *previous == Some(status) {
+            return None;
+        }
+        *previous = Some(status);
+        let receipt = Receipt {
+            pipe_id: pipe_id.clone(),
+            line: format!("{}\n", serde_json::json!({ "id": id, "status": status.name() })),
+            last: last || status.is_final(),
+        };
+        if receipt.last {
+            self.subscriptions.remove(id);
+        }
+        Some(receipt)
+    }
+
+    /// Notifications still waiting for a final status
+    pub fn pending(&self) -> usize {
+        self.subscriptions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receipts_follow_transitions() {
+        let mut tracker = ReceiptTracker::default();
+        assert_eq!(tracker.update("a", DeliveryStatus::Queued, false), None);
+
+        tracker.subscribe("a", Some("pipe-1".to_string()));
+        let receipt = tracker.update("a", DeliveryStatus::Queued, false).unwrap();
+        assert_eq!(receipt.line, "{\"id\":\"a\",\"status\":\"queued\"}\n");
+        assert_eq!(receipt.pipe_id.as_deref(), Some("pipe-1"));
+        assert!(!receipt.last);
+        assert_eq!(tracker.update("a", DeliveryStatus::Queued, false), None);
+
+        tracker.update("a", DeliveryStatus::Displayed, false).unwrap();
+        assert_eq!(tracker.pending(), 1);
+        assert!(tracker.update("a", DeliveryStatus::Acknowledged, false).unwrap().last);
+        assert_eq!(tracker.pending(), 0);
+
+        // Nothing follows the display of a notification without a pane
+        tracker.subscribe("b", None);
+        assert!(tracker.update("b", DeliveryStatus::Displayed, true).unwrap().last);
+    }
+}
@@ -0,0 +1,80 @@
+//! Recently acknowledged module for Zellij Visual Notifications
+//!
+//! Acknowledged messages are remembered per pane for a short window. When the exact same
+//! message (e.g. the same failing test) arrives on that pane again within the window, it is shown
+//! dimmed as a badge instead of alerting at full intensity: the user has just seen it.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use crate::notification::Notification;
+
+/// Default time an acknowledged message is remembered (ms)
+pub const DEFAULT_RECENT_ACK_WINDOW_MS: u64 = 120_000;
+
+/// Acknowledged messages by (pane, message hash), with the time they are forgotten (ms)
+#[derive(Debug, Clone, Default)]
+pub struct RecentAcks {
+    entries: BTreeMap<(u32, u64), u64>,
+}
+
+impl RecentAcks {
+    /// Remember an acknowledged notification for `window_ms` (no-op without a pane or window)
+    pub fn remember(&mut self, notification: &Notification, now_ms: u64, window_ms: u64) {
+        let Some(pane_id) = notification.pane_id else { return };
+        if window_ms == 0 {
+            return;
+        }
+        self.prune(now_ms);
+        self.entries.insert((pane_id, message_hash(notification)), now_ms + window_ms);
+    }
+
+    /// Whether the same message was acknowledged on the same pane within the window
+    pub fn is_repeat(&self, notification: &Notification, now_ms: u64) -> bool {
+        let Some(pane_id) = notification.pane_id else { return false };
+        self.entries.get(&(pane_id, message_hash(notification)))
+            .is_some_and(|until_ms| now_ms < *until_ms)
+    }
+
+    /// Forget entries whose window has passed
+    pub fn prune(&mut self, now_ms: u64) {
+        self.entries.retain(|_, until_ms| now_ms < *until_ms);
+    }
+}
+
+/// Hash of a notification's type and message
+fn message_hash(notification: &Notification) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    notification.notification_type.name().hash(&mut hasher);
+    notification.message.trim().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationType;
+
+    fn failure(pane_id: u32, message: &str) -> Notification {
+        let mut notification = Notification::new(NotificationType::Error, message);
+        notification.pane_id = Some(pane_id);
+        notification
+    }
+
+    #[test]
+    fn test_repeats_within_window_per_pane() {
+        let mut recent = RecentAcks::default();
+        recent.remember(&failure(3, "test_login failed"), 1_000, 60_000);
+
+        assert!(recent.is_repeat(&failure(3, "test_login failed"), 30_000));
+        assert!(!recent.is_repeat(&failure(4, "test_login failed"), 30_000));
+        assert!(!recent.is_repeat(&failure(3, "test_logout failed"), 30_000));
+        assert!(!recent.is_repeat(&Notification::info("test_login failed"), 30_000));
+        assert!(!recent.is_repeat(&failure(3, "test_login failed"), 61_000));
+
+        recent.prune(61_000);
+        assert!(recent.entries.is_empty());
+        recent.remember(&failure(3, "test_login failed"), 1_000, 0);
+        assert!(recent.entries.is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+//! Type remapping module for Zellij Visual Notifications
+//!
+//! Some tools send every message as an error. Remaps change the type of a source's
+//! notifications (`remap { source "flaky-tool" from "error" to "warning"; }`) in the event bridge,
+//! before the priority is derived from the type, so the tool's messages get the border color,
+//! icon and queue lane of the type they should have had. Sources match case-insensitively and
+//! exactly; the first remap for a source and type applies. A priority given by the sender still
+//! wins over the derived one.
+
+use serde::{Deserialize, Serialize};
+
+use crate::notification::NotificationType;
+
+/// Type change for one source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeRemap {
+    /// Source whose notifications are remapped
+    pub source: String,
+    /// Type the source sends
+    pub from: NotificationType,
+    /// Type used instead
+    pub to: NotificationType,
+}
+
+impl TypeRemap {
+    /// Create a remap from type names
+    pub fn parse(source: &str, from: &str, to: &str) -> Result<Self, String> {
+        let parse_type = |name: &str| NotificationType::from_name(name)
+            .ok_or_else(|| format!("remap {}: unknown notification type: {}", source, name));
+        Ok(Self { source: source.to_string(), from: parse_type(from)?, to: parse_type(to)? })
+    }
+
+    /// Parse the plugin configuration form, e.g. `error=warning attention=info`
+    pub fn parse_spec(source: &str, spec: &str) -> Result<Vec<Self>, String> {
+        crate::config::parse_list(spec).iter()
+            .map(|pair| {
+                let (from, to) = pair.split_once('=')
+                    .ok_or_else(|| format!("remap {}: expected from=to, got: {}", source, pair))?;
+                Self::parse(source, from, to)
+            })
+            .collect()
+    }
+}
+
+/// Type of a notification from `source` after the first matching remap
+pub fn apply(remaps: &[TypeRemap], source: &str, notification_type: NotificationType) -> NotificationType {
+    remaps.iter()
+        .find(|remap| remap.source.eq_ignore_ascii_case(source) && remap.from == notification_type)
+        .map(|remap| remap.to.clone())
+        .unwrap_or(notification_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaps_only_the_named_source() {
+        let remaps = TypeRemap::parse_spec("flaky-tool", "error=warning, attention=info").unwrap();
+        assert_eq!(apply(&remaps, "Flaky-Tool", NotificationType::Error), NotificationType::Warning);
+        assert_eq!(apply(&remaps, "flaky-tool", NotificationType::Attention), NotificationType::Info);
+        assert_eq!(apply(&remaps, "flaky-tool", NotificationType::Success), NotificationType::Success);
+        assert_eq!(apply(&remaps, "flaky-tool-2", NotificationType::Error), NotificationType::Error);
+        assert!(TypeRemap::parse_spec("flaky-tool", "error=lunch").is_err());
+        assert!(TypeRemap::parse_spec("flaky-tool", "error").is_err());
+    }
+}
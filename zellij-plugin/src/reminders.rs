@@ -0,0 +1,186 @@
+//! Reminder module for Zellij Visual Notifications
+//!
+//! Periodic reminders (`{"type":"reminder","message":"stand up","every_ms":3600000}`) are
+//! re-raised as notifications on the timer path until cancelled by ID. Reminder definitions are
+//! persisted in the plugin data directory; after a reload each reminder is re-armed to fire one
+//! interval later, as plugin time restarts with the new instance.
+
+use serde::{Deserialize, Serialize};
+use crate::lifecycle::{read_state, LifecycleContext, Subsystem};
+use crate::notification::{Notification, NotificationBuilder, NotificationType, Priority};
+
+/// Default location of the persisted reminders (plugin data directory)
+pub const REMINDER_STATE_PATH: &str = "/data/reminders";
+
+/// Shortest accepted reminder interval (ms)
+pub const MIN_REMINDER_INTERVAL_MS: u64 = 1000;
+
+/// A periodic reminder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    /// Reminder ID (used to cancel it)
+    pub id: String,
+    /// Message shown each time it fires
+    pub message: String,
+    /// Title (optional)
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Target pane (optional)
+    #[serde(default)]
+    pub pane_id: Option<u32>,
+    /// Interval between firings (ms)
+    pub every_ms: u64,
+    /// Plugin time of the next firing (ms)
+    #[serde(skip)]
+    pub next_due_ms: u64,
+}
+
+impl Reminder {
+    /// Notification raised when the reminder fires
+    fn notification(&self) -> Notification {
+        let mut builder = NotificationBuilder::new()
+            .notification_type(NotificationType::Info)
+            .priority(Priority::Normal)
+            .message(&self.message)
+            .title(self.title.as_deref().unwrap_or("Reminder"))
+            .source("reminder")
+            .icon("\u{23F0}"); // Alarm clock
+        if let Some(pane_id) = self.pane_id {
+            builder = builder.pane_id(pane_id);
+        }
+        builder.build()
+    }
+}
+
+/// Schedules reminders by plugin time
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReminderScheduler {
+    /// Active reminders, in order of creation
+    reminders: Vec<Reminder>,
+}
+
+impl ReminderScheduler {
+    /// Parse reminders from their persisted form (JSON array), armed relative to `now_ms`
+    pub fn parse(content: &str, now_ms: u64) -> Self {
+        let mut scheduler = Self::default();
+        for reminder in serde_json::from_str::<Vec<Reminder>>(content).unwrap_or_default() {
+            scheduler.add(reminder, now_ms);
+        }
+        scheduler
+    }
+
+    /// Serialize the reminders to their persisted form
+    pub fn serialize(&self) -> String {
+        serde_json::to_string(&self.reminders).unwrap_or_default()
+    }
+
+    /// Save the reminders to a file
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.serialize())
+    }
+
+    /// Schedule a reminder to first fire one interval from `now_ms` (replaces one with the same ID)
+    pub fn add(&mut self, mut reminder: Reminder, now_ms: u64) {
+        reminder.every_ms = reminder.every_ms.max(MIN_REMINDER_INTERVAL_MS);
+        reminder.next_due_ms = now_ms + reminder.every_ms;
+        self.reminders.retain(|r| r.id != reminder.id);
+        self.reminders.push(reminder);
+    }
+
+    /// Cancel a reminder, returning whether it existed
+    pub fn cancel(&mut self, id: &str) -> bool {
+        let before = self.reminders.len();
+        self.reminders.retain(|r| r.id != id);
+        self.reminders.len() < before
+    }
+
+    /// Notifications for reminders due at `now_ms` (missed firings are not repeated)
+    pub fn due(&mut self, now_ms: u64) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+        for reminder in self.reminders.iter_mut().filter(|r| r.next_due_ms <= now_ms) {
+            notifications.push(reminder.notification());
+            while reminder.next_due_ms <= now_ms {
+                reminder.next_due_ms += reminder.every_ms;
+            }
+        }
+        notifications
+    }
+
+    /// Iterate over reminders in order of creation
+    pub fn iter(&self) -> impl Iterator<Item = &Reminder> {
+        self.reminders.iter()
+    }
+
+    /// Check if no reminders are scheduled
+    pub fn is_empty(&self) -> bool {
+        self.reminders.is_empty()
+    }
+}
+
+impl Subsystem for ReminderScheduler {
+    fn name(&self) -> &'static str {
+        "reminders"
+    }
+
+    fn init(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        *self = read_state(REMINDER_STATE_PATH)?
+            .map(|content| Self::parse(&content, context.now_ms))
+            .unwrap_or_default();
+        Ok(())
+    }
+
+    fn shutdown(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        if !context.persist {
+            return Ok(());
+        }
+        self.save(REMINDER_STATE_PATH).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reminder(id: &str, every_ms: u64) -> Reminder {
+        Reminder {
+            id: id.to_string(),
+            message: "stand up".to_string(),
+            title: None,
+            pane_id: None,
+            every_ms,
+            next_due_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_reminders_fire_on_schedule() {
+        let mut scheduler = ReminderScheduler::default();
+        scheduler.add(reminder("r1", 60_000), 1_000);
+        assert!(scheduler.due(60_000).is_empty());
+
+        let fired = scheduler.due(61_000);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].message, "stand up");
+        assert_eq!(fired[0].source, "reminder");
+
+        // A long gap fires once, then keeps the schedule
+        assert_eq!(scheduler.due(500_000).len(), 1);
+        assert!(scheduler.due(520_000).is_empty());
+        assert_eq!(scheduler.due(541_000).len(), 1);
+
+        assert!(scheduler.cancel("r1"));
+        assert!(!scheduler.cancel("r1"));
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn test_reminders_round_trip_rearmed() {
+        let mut scheduler = ReminderScheduler::default();
+        scheduler.add(reminder("r1", 10), 5_000);
+        assert_eq!(scheduler.iter().next().map(|r| r.every_ms), Some(MIN_REMINDER_INTERVAL_MS));
+
+        let restored = ReminderScheduler::parse(&scheduler.serialize(), 0);
+        assert_eq!(restored.iter().next().map(|r| r.next_due_ms), Some(MIN_REMINDER_INTERVAL_MS));
+        assert!(ReminderScheduler::parse("not json", 0).is_empty());
+    }
+}
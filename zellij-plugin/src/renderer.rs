@@ -3,12 +3,177 @@
 //! Handles rendering of status bar widgets, pane borders, and badges.
 
 use std::collections::BTreeMap;
-use crate::animation::AnimationEngine;
+use crate::aggregator::{RemoteEntry, SessionAggregate};
+use crate::animation::{AnimationEngine, ClearCascade, Frame};
+use crate::attention_queue::{self, AttentionSlot};
+use crate::channels::ChannelList;
+use crate::charset::{to_ascii, AsciiMode};
+use crate::claude_panes::DEFAULT_CLAUDE_PANE_COLOR;
 use crate::colors::ColorManager;
-use crate::config::Config;
-use crate::notification::NotificationType;
+use crate::config::{AnimationTarget, Config, StatusOrder};
+use crate::correlation;
+use crate::diagnostics::{LogEntry, RecoveryTracker};
+use crate::election::Role;
+use crate::entry_cache::{EntryCache, EntryKey, EntryLayout};
+use crate::event_bridge::ConnectionState;
+use crate::health::{CheckStatus, HealthReport};
+use crate::icons::{self, Icon, IconWidthPolicy};
+use crate::legend::LegendContext;
+use crate::markup;
+use crate::notification::{Notification, NotificationType, Priority};
+use crate::pager::Pager;
+use crate::progress::ProgressEstimate;
+use crate::query::{entry_line, format_age, ResultGroup};
 use crate::queue::NotificationQueue;
+use crate::queue_diff::QueueDiff;
+use crate::quick_reply;
+use crate::raw_ansi::RawAnsi;
+use crate::screen::ScreenBuffer;
+use crate::sparkline::RateHistory;
 use crate::state::VisualState;
+use crate::surface::{DisplaySurface, Surface, SurfaceToggles};
+use crate::suppression::{SuppressionKind, SuppressionStatus};
+use crate::table::{column_widths, fit, format_row, Align, Column};
+use crate::wrap::{display_width, wrap};
+
+/// Read-only view of plugin state consumed by the renderer
+pub struct RenderView<'a> {
+    /// Visual state per pane
+    pub pane_states: &'a BTreeMap<u32, VisualState>,
+    /// Notification queue
+    pub queue: &'a NotificationQueue,
+    /// Color management
+    pub color_manager: &'a ColorManager,
+    /// Animation engine (brightness lookups)
+    pub animation_engine: &'a AnimationEngine,
+    /// Current animation frame
+    pub frame: Frame,
+    /// Active DND/snooze suppression, if any
+    pub suppression: Option<SuppressionStatus>,
+    /// Current error summary, if any
+    pub error: Option<&'a str>,
+    /// Transient confirmation message, if any
+    pub status_message: Option<&'a str>,
+    /// Automatic recovery counters
+    pub recovery: &'a RecoveryTracker,
+    /// Notification channels and their visibility
+    pub channels: &'a ChannelList,
+    /// Event bridge connection state
+    pub bridge: &'a ConnectionState,
+    /// Notifications forwarded from other sessions
+    pub aggregate: &'a SessionAggregate,
+    /// Entries fading out after clear-all, if any
+    pub cascade: Option<&'a ClearCascade>,
+    /// Notifications per type over the last hour
+    pub rate: &'a RateHistory,
+    /// Changes to the queue since the queue inspector opened
+    pub queue_diff: &'a QueueDiff,
+    /// Role of this instance while there are several, if any
+    pub instance: Option<Role>,
+}
+
+/// ANSI escape for bold text (static emphasis under reduced motion)
+const BOLD_ESCAPE: &str = "\x1b[1m";
+
+/// ANSI escape for bold reverse text (urgent notifications when color is off)
+const BOLD_REVERSE_ESCAPE: &str = "\x1b[1;7m";
+
+/// ANSI escape for reverse video (inverse flash of animated entries)
+const REVERSE_ESCAPE: &str = "\x1b[7m";
+
+/// How far an animated entry's background fill is blended toward the theme background
+const ANIMATION_FILL_TINT: f32 = 0.6;
+
+/// Brightness from which an inverse-flash entry is drawn in reverse video
+const INVERSE_FLASH_THRESHOLD: f32 = 0.75;
+
+/// Time between the overdue flash's changes (ms)
+const OVERDUE_FLASH_MS: u64 = 1000;
+
+/// Pattern of overdue entries, in place of their type's
+const OVERDUE_PATTERN: &str = "><";
+
+/// Rows needed for the big mode view (one enlarged entry between two separators)
+pub const BIG_MODE_MIN_ROWS: usize = 5;
+
+/// Rows needed for the focus ring (the ring and one row of content)
+pub const FOCUS_RING_MIN_ROWS: usize = 3;
+
+/// Columns needed for the focus ring
+const FOCUS_RING_MIN_COLS: usize = 12;
+
+/// Time between the focus ring's changes of line weight (ms)
+const FOCUS_RING_BLINK_MS: u64 = 500;
+
+/// Width of the big mode block glyphs (columns)
+const BIG_GLYPH_WIDTH: usize = 5;
+
+/// Lines of the detail view above the message pages (key hints, type, ID, TTL)
+const DETAIL_HEADER_ROWS: usize = 4;
+
+/// Builds a status bar segment; None when it has nothing to show
+type SegmentBuilder = fn(&Renderer, &RenderView) -> Option<String>;
+
+/// Status bar segments by configuration name. A new segment is a builder method registered
+/// here, which makes it available to the `segments` option.
+const SEGMENT_REGISTRY: &[(&str, SegmentBuilder)] = &[
+    ("error", Renderer::error_segment),
+    ("icon", Renderer::icon_segment),
+    ("counts", Renderer::counts_segment),
+    ("entries", Renderer::entries_segment),
+    ("sessions", Renderer::sessions_segment),
+    ("queue-depth", Renderer::queue_segment),
+    ("muted", Renderer::muted_segment),
+    ("surfaces", Renderer::surfaces_segment),
+    ("dnd-indicator", Renderer::dnd_segment),
+    ("bridge-health", Renderer::bridge_segment),
+    ("clock", Renderer::clock_segment),
+    ("rate", Renderer::rate_segment),
+    ("instance", Renderer::instance_segment),
+    ("message", Renderer::message_segment),
+];
+
+/// Status bar segments shown by default, in order
+pub const DEFAULT_STATUS_SEGMENTS: &[&str] =
+    &["error", "icon", "entries", "sessions", "queue-depth", "muted", "surfaces", "dnd-indicator", "instance", "message"];
+
+/// Fields of a status entry in the wide layout: icon, pane, age, message. The message gives up
+/// width first and the icon last.
+const WIDE_COLUMNS: [Column; 4] = [
+    Column::left(1, 3, 3),
+    Column::right(1, 5, 2),
+    Column::right(2, 4, 1),
+    Column::left(8, 60, 0),
+];
+
+/// Default pane width from which status entries are listed instead of only counted
+pub const DEFAULT_COMPACT_LAYOUT_MIN_COLS: usize = 30;
+
+/// Default pane width from which status entries are laid out as a table
+pub const DEFAULT_WIDE_LAYOUT_MIN_COLS: usize = 81;
+
+/// Narrowest entry cell in the wide layout; the pane width decides how many fit side by side
+const WIDE_CELL_MIN_WIDTH: usize = 36;
+
+/// Longest branch label shown after an entry (longer ones end in an ellipsis)
+const MAX_BRANCH_LABEL_CHARS: usize = 20;
+
+/// Look up a segment builder by name (`queue`, `dnd` and `health` are accepted as short names)
+fn segment_builder(name: &str) -> Option<(&'static str, SegmentBuilder)> {
+    let name = name.to_lowercase().replace('_', "-");
+    let name = match name.as_str() {
+        "queue" => "queue-depth",
+        "dnd" => "dnd-indicator",
+        "health" | "bridge" => "bridge-health",
+        other => other,
+    };
+    SEGMENT_REGISTRY.iter().find(|(registered, _)| *registered == name).copied()
+}
+
+/// Resolve configured segment names to builders (unknown names are skipped)
+fn resolve_segments<S: AsRef<str>>(names: &[S]) -> Vec<(&'static str, SegmentBuilder)> {
+    names.iter().filter_map(|name| segment_builder(name.as_ref())).collect()
+}
 
 /// Renderer for visual elements
 #[derive(Debug, Clone)]
@@ -19,10 +184,46 @@ pub struct Renderer {
     show_border_colors: bool,
     /// Show tab badges
     show_tab_badges: bool,
+    /// Show notification entries in the status bar (switched off at runtime)
+    show_status_entries: bool,
+    /// Names of the surfaces switched off at runtime (status bar indicator)
+    disabled_surfaces: Vec<&'static str>,
     /// Use unicode icons
     use_unicode: bool,
+    /// Which icon glyphs are replaced by single-width alternatives
+    icon_width_policy: IconWidthPolicy,
     /// Accessibility mode (patterns instead of colors only)
     use_patterns: bool,
+    /// Order of notification entries in the status bar
+    status_order: StatusOrder,
+    /// Reduced motion: urgent notifications get static emphasis instead of animation
+    reduced_motion: bool,
+    /// Gradient stops per notification type name (Gradient animation style)
+    gradient_stops: BTreeMap<String, Vec<String>>,
+    /// What the animation modulates in status entries
+    animation_target: AnimationTarget,
+    /// Status bar segments by name, in order
+    segments: Vec<(&'static str, SegmentBuilder)>,
+    /// Pane width from which entries are listed; narrower panes get the icon and count (0 = always)
+    compact_layout_min_cols: usize,
+    /// Pane width from which entries are laid out as a table (0 = never)
+    wide_layout_min_cols: usize,
+    /// Frame the pane while a Critical notification is active
+    show_focus_ring: bool,
+    /// Status entries drawn in earlier frames
+    entry_cache: EntryCache,
+    /// Number the first nine status entries
+    entry_hotkeys: bool,
+    /// Draw status entries as tinted chips instead of brackets
+    entry_chips: bool,
+    /// Accent of Claude pane entries
+    claude_pane_color: String,
+    /// Raw ANSI snippets by registered segment name
+    raw_ansi_segments: BTreeMap<&'static str, RawAnsi>,
+    /// Raw ANSI snippets by notification type name
+    raw_ansi_types: BTreeMap<String, RawAnsi>,
+    /// Panes of the status entries as last drawn, for entry numbers
+    entry_layout: EntryLayout,
 }
 
 impl Default for Renderer {
@@ -31,8 +232,26 @@ impl Default for Renderer {
             show_status_bar: true,
             show_border_colors: true,
             show_tab_badges: true,
+            show_status_entries: true,
+            disabled_surfaces: Vec::new(),
             use_unicode: true,
+            icon_width_policy: IconWidthPolicy::Any,
             use_patterns: true,
+            status_order: StatusOrder::Urgency,
+            reduced_motion: false,
+            gradient_stops: BTreeMap::new(),
+            animation_target: AnimationTarget::Foreground,
+            segments: resolve_segments(DEFAULT_STATUS_SEGMENTS),
+            compact_layout_min_cols: DEFAULT_COMPACT_LAYOUT_MIN_COLS,
+            wide_layout_min_cols: DEFAULT_WIDE_LAYOUT_MIN_COLS,
+            show_focus_ring: true,
+            entry_cache: EntryCache::default(),
+            entry_hotkeys: false,
+            entry_chips: false,
+            entry_layout: EntryLayout::default(),
+            claude_pane_color: DEFAULT_CLAUDE_PANE_COLOR.to_string(),
+            raw_ansi_segments: BTreeMap::new(),
+            raw_ansi_types: BTreeMap::new(),
         }
     }
 }
@@ -44,125 +263,1382 @@ impl Renderer {
             show_status_bar: config.show_status_bar,
             show_border_colors: config.show_border_colors,
             show_tab_badges: config.show_tab_badges,
-            use_unicode: true,
+            show_status_entries: true,
+            disabled_surfaces: Vec::new(),
+            use_unicode: config.ascii_only != AsciiMode::On,
+            icon_width_policy: config.icon_width_policy,
             use_patterns: config.accessibility.use_patterns,
+            status_order: config.status_order,
+            reduced_motion: config.motion_reduced(),
+            gradient_stops: config.animation.gradient_stops.clone(),
+            animation_target: config.animation.target,
+            segments: resolve_segments(&config.status_segments),
+            compact_layout_min_cols: config.compact_layout_min_cols,
+            wide_layout_min_cols: config.wide_layout_min_cols,
+            show_focus_ring: config.focus_ring,
+            entry_cache: EntryCache::default(),
+            entry_hotkeys: config.entry_hotkeys,
+            entry_chips: config.entry_chips,
+            entry_layout: EntryLayout::default(),
+            claude_pane_color: config.claude_pane_color.clone(),
+            raw_ansi_segments: config.raw_ansi.segments.iter()
+                .filter_map(|(name, raw)| segment_builder(name).map(|(registered, _)| (registered, raw.clone())))
+                .collect(),
+            raw_ansi_types: config.raw_ansi.types.clone(),
+        }
+    }
+
+    /// Hide the surfaces switched off at runtime and show them in the status bar indicator
+    pub fn with_surface_toggles(mut self, toggles: &SurfaceToggles) -> Self {
+        self.show_border_colors &= toggles.is_enabled(DisplaySurface::Border);
+        self.show_tab_badges &= toggles.is_enabled(DisplaySurface::TabBadges);
+        self.show_status_entries = toggles.is_enabled(DisplaySurface::StatusEntries);
+        self.disabled_surfaces = toggles.disabled_names();
+        self
+    }
+
+    /// Draw with the pure-ASCII fallback set instead of Unicode glyphs
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.use_unicode = !ascii;
+        self
+    }
+
+    /// Text as drawn: unchanged, or reduced to ASCII in the fallback set
+    fn glyphs(&self, text: String) -> String {
+        if self.use_unicode { text } else { to_ascii(&text) }
+    }
+
+    /// Compose lines into the screen buffer and write the frame in one piece
+    fn present(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, lines: &[String]) {
+        if self.use_unicode {
+            present(screen, rows, cols, lines);
+        } else {
+            present(screen, rows, cols, &lines.iter().map(|line| to_ascii(line)).collect::<Vec<_>>());
+        }
+    }
+
+    /// Render the status bar widget, returning whether it filled the whole pane (wide layout).
+    /// The layout follows the pane width, so it switches as the pane is resized: the icon and
+    /// count below `compact_layout_min_cols`, the entries in a line up to `wide_layout_min_cols`,
+    /// and the table from there.
+    pub fn render_status_bar(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, view: &RenderView) -> bool {
+        if !self.show_status_bar || cols < 10 {
+            return false;
+        }
+
+        if cols < self.compact_layout_min_cols {
+            print!("{}", self.glyphs(self.build_minimal_status_line(view)));
+            return false;
+        }
+
+        if let Some(lines) = self.build_wide_status_lines(rows, cols, view) {
+            self.present(screen, rows, cols, &lines);
+            return true;
+        }
+
+        // Print the status bar (Zellij will capture this)
+        print!("{}", self.glyphs(self.build_status_line(view)));
+        false
+    }
+
+    /// Newest active Critical notification the focus ring is drawn for
+    fn focus_ring_target<'a>(&self, pane_states: &'a BTreeMap<u32, VisualState>) -> Option<(u32, &'a VisualState)> {
+        if !self.show_focus_ring {
+            return None;
         }
+        pane_states.iter()
+            .filter(|(_, state)| state.has_notification() && !state.acknowledged && state.is_listed())
+            .filter(|(_, state)| state.priority == Priority::Critical)
+            .max_by_key(|(pane_id, state)| (state.notification_timestamp, **pane_id))
+            .map(|(pane_id, state)| (*pane_id, state))
+    }
+
+    /// Whether the focus ring is drawn (and animates) for these notifications
+    pub fn focus_ring_active(&self, pane_states: &BTreeMap<u32, VisualState>) -> bool {
+        self.focus_ring_target(pane_states).is_some()
     }
 
-    /// Render the status bar widget
-    pub fn render_status_bar(
+    /// Render the status view inside the focus ring when a Critical notification is active and
+    /// the pane has room; returns whether it did
+    pub fn render_focus_ring(
         &self,
+        screen: &mut ScreenBuffer,
         rows: usize,
         cols: usize,
-        pane_states: &BTreeMap<u32, VisualState>,
-        queue: &NotificationQueue,
+        view: &RenderView,
+        footer: Option<&str>,
+    ) -> bool {
+        if rows < FOCUS_RING_MIN_ROWS || cols < FOCUS_RING_MIN_COLS {
+            return false;
+        }
+        match self.build_focus_ring_lines(rows, cols, view, footer) {
+            Some(lines) => {
+                self.present(screen, rows, cols, &lines);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Build the focus ring around the status view: the type color pulsing with the notification's
+    /// animation and, unless motion is reduced, the line weight alternating twice a second
+    fn build_focus_ring_lines(&self, rows: usize, cols: usize, view: &RenderView, footer: Option<&str>) -> Option<Vec<String>> {
+        let (pane_id, state) = self.focus_ring_target(view.pane_states)?;
+        let cm = view.color_manager;
+        let notification_type = state.notification_type.as_ref()?;
+        let base_color = cm.resolve_color(notification_type, state.custom_color.as_deref())
+            .unwrap_or_else(|| cm.get_foreground_color());
+        let color = self.animated_color(&base_color, notification_type, state, cm, view.animation_engine, view.frame);
+        let style = if self.reduced_motion || (view.frame.now_ms / FOCUS_RING_BLINK_MS).is_multiple_of(2) {
+            BorderLineStyle::Double
+        } else {
+            BorderLineStyle::Bold
+        };
+        let chars = style.chars();
+        let (inner_rows, inner_cols) = (rows - 2, cols - 2);
+        let edge = cm.fg_escape(&color);
+        let reset = cm.reset_escape();
+
+        let mut content = Vec::new();
+        if self.show_status_bar {
+            content = self.build_wide_status_lines(inner_rows, inner_cols, view)
+                .unwrap_or_else(|| vec![self.build_status_line(view)]);
+        }
+        content.extend(footer.map(str::to_string));
+
+        // The top edge names the notification the ring is for
+        let label = truncate(&format!(" {} pane {} ", notification_type.name().to_uppercase(), pane_id), inner_cols);
+        let top = format!("{}{}", label, chars.horizontal.to_string().repeat(inner_cols - display_width(&label)));
+        let mut lines = vec![format!("{}{}{}{}{}", edge, chars.top_left, top, chars.top_right, reset)];
+        for row in 0..inner_rows {
+            let text = content.get(row).map(|line| fit(line, inner_cols, Align::Left)).unwrap_or_else(|| " ".repeat(inner_cols));
+            lines.push(format!("{}{}{}{}{}{}{}{}", edge, chars.vertical, reset, text, reset, edge, chars.vertical, reset));
+        }
+        lines.push(format!("{}{}{}{}{}",
+            edge,
+            chars.bottom_left,
+            chars.horizontal.to_string().repeat(inner_cols),
+            chars.bottom_right,
+            reset));
+        Some(lines)
+    }
+
+    /// Build the status bar from the configured segments, in order
+    fn build_status_line(&self, view: &RenderView) -> String {
+        self.build_segments(view, |_| true)
+    }
+
+    /// Build the status bar for narrow panes: the error banner if any, then the icon and the
+    /// number of active notifications in the color of the most urgent one
+    fn build_minimal_status_line(&self, view: &RenderView) -> String {
+        self.entry_layout.record(&[]);
+        let cm = view.color_manager;
+        let active: Vec<&NotificationType> = view.pane_states.values()
+            .filter(|state| state.has_notification() && !state.acknowledged)
+            .filter_map(|state| state.notification_type.as_ref())
+            .collect();
+        let mut line = self.build_segments(view, |name| name == "error");
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(self.icon(Icon::Bell));
+        if let Some(notification_type) = URGENCY_ORDER.iter().find(|urgent| active.contains(urgent)) {
+            let color = cm.get_notification_color(notification_type).unwrap_or_else(|| cm.get_foreground_color());
+            line = format!("{}{}{}{}", line, cm.fg_escape(&color), active.len(), cm.reset_escape());
+        }
+        line
+    }
+
+    /// Join the configured segments accepted by a name filter
+    fn build_segments(&self, view: &RenderView, include: impl Fn(&str) -> bool) -> String {
+        self.segments.iter()
+            .filter(|(name, _)| include(name))
+            .filter_map(|(name, build)| {
+                build(self, view).map(|text| self.raw_ansi(self.raw_ansi_segments.get(name), text, view.color_manager))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Build the wide layout when the pane is wide enough and the entries segment has entries:
+    /// the other segments, then the entries as a table of aligned cells (icon, pane, age,
+    /// message), as many side by side as fit. With one row the cells follow the segments on the
+    /// same line; otherwise the segments get the first row and the cells the rows below.
+    fn build_wide_status_lines(&self, rows: usize, cols: usize, view: &RenderView) -> Option<Vec<String>> {
+        if self.wide_layout_min_cols == 0 || cols < self.wide_layout_min_cols || !self.show_status_entries
+            || !self.segments.iter().any(|(name, _)| *name == "entries")
+        {
+            return None;
+        }
+        let entries: Vec<(&u32, &VisualState)> = self.ordered_entries(view.pane_states).into_iter()
+            .filter(|(_, state)| state.notification_type.is_some() && !state.acknowledged)
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+
+        let cm = view.color_manager;
+        let dimmed = cm.fg_escape(&cm.get_dimmed_color());
+        let mut prefix = self.build_segments(view, |name| name != "entries");
+        for count in self.unlisted_counts(view.pane_states) {
+            prefix = format!("{} {}({}){}", prefix, dimmed, count, cm.reset_escape());
+        }
+        let prefix = prefix.trim().to_string();
+        let (grid_rows, grid_cols) = match rows {
+            0 | 1 if !prefix.is_empty() => (1, cols.saturating_sub(display_width(&prefix) + 1)),
+            0 | 1 => (1, cols),
+            _ => (rows - 1, cols),
+        };
+
+        let separator = if self.use_unicode { " \u{2502} " } else { " | " }; // Light vertical
+        let per_row = ((grid_cols + 3) / (WIDE_CELL_MIN_WIDTH + 3)).clamp(1, entries.len());
+        let cell_width = grid_cols.saturating_sub((per_row - 1) * 3) / per_row;
+
+        // The last cell counts the entries that do not fit
+        let capacity = grid_rows * per_row;
+        let shown = if entries.len() > capacity { capacity - 1 } else { entries.len() };
+
+        let drawn: Vec<u32> = entries[..shown].iter().map(|(pane_id, _)| **pane_id).collect();
+        self.entry_layout.record(&drawn);
+        let fields: Vec<Vec<String>> = entries[..shown].iter().enumerate()
+            .map(|(index, (pane_id, state))| {
+                let notification_type = state.notification_type.as_ref().unwrap_or(&NotificationType::Info);
+                vec![
+                    format!("{}{}{}",
+                        self.entry_label(index).map(String::from).unwrap_or_default(),
+                        if state.pinned { "^" } else { "" },
+                        self.state_icon(state, notification_type, view.frame)),
+                    pane_id.to_string(),
+                    format_age(view.frame.now_ms.saturating_sub(state.notification_timestamp)),
+                    format!("{}{}{}",
+                        markup::strip(state.notification_message.as_deref().unwrap_or(notification_type.name())),
+                        state.progress.as_ref().map(format_progress).unwrap_or_default(),
+                        branch_suffix(state),
+                        reply_suffix(state)),
+                ]
+            })
+            .collect();
+        let widths = column_widths(&WIDE_COLUMNS, &fields, cell_width, 1);
+
+        let mut cells: Vec<String> = entries[..shown].iter().zip(&fields)
+            .map(|((_, state), fields)| {
+                let notification_type = state.notification_type.as_ref().unwrap_or(&NotificationType::Info);
+                let cell = format!("{}{}{}{}",
+                    self.entry_escape(state, notification_type, view),
+                    self.entry_attributes(notification_type, cm),
+                    fit(&format_row(&WIDE_COLUMNS, &widths, fields, 1), cell_width, Align::Left),
+                    cm.reset_escape());
+                self.raw_ansi(self.raw_ansi_types.get(notification_type.name()), cell, cm)
+            })
+            .collect();
+        if shown < entries.len() {
+            cells.push(format!("{}{}{}",
+                dimmed,
+                fit(&format!("+{} more", entries.len() - shown), cell_width, Align::Left),
+                cm.reset_escape()));
+        }
+
+        let separator = format!("{}{}{}", dimmed, separator, cm.reset_escape());
+        let grid: Vec<String> = cells.chunks(per_row).map(|row| row.join(&separator)).collect();
+        let first = grid.first().cloned().unwrap_or_default();
+        Some(match rows {
+            0 | 1 if !prefix.is_empty() => vec![format!("{} {}", prefix, first)],
+            0 | 1 => vec![first],
+            _ => std::iter::once(prefix).chain(grid).collect(),
+        })
+    }
+
+    /// Error segment: the error banner, if the plugin is in an error state
+    fn error_segment(&self, view: &RenderView) -> Option<String> {
+        view.error.map(|error| self.build_error_banner(error, view.recovery, view.color_manager))
+    }
+
+    /// Icon segment: the plugin icon
+    fn icon_segment(&self, _view: &RenderView) -> Option<String> {
+        Some(self.icon(Icon::Bell).to_string())
+    }
+
+    /// Counts segment: active notifications per type, e.g. `✘2 ⚠1`
+    fn counts_segment(&self, view: &RenderView) -> Option<String> {
+        let cm = view.color_manager;
+        let counts: Vec<String> = [
+            NotificationType::Error,
+            NotificationType::Attention,
+            NotificationType::Warning,
+            NotificationType::Progress,
+            NotificationType::Success,
+            NotificationType::Info,
+        ]
+            .iter()
+            .filter_map(|notification_type| {
+                let count = view.pane_states.values()
+                    .filter(|s| s.has_notification() && s.notification_type.as_ref() == Some(notification_type))
+                    .count();
+                (count > 0).then(|| format!("{}{}{}{}",
+                    cm.fg_escape(&cm.get_notification_color(notification_type).unwrap_or_else(|| cm.get_foreground_color())),
+                    self.get_notification_icon(notification_type),
+                    count,
+                    cm.reset_escape()))
+            })
+            .collect();
+        (!counts.is_empty()).then(|| counts.join(" "))
+    }
+
+    /// Entries segment: one entry per pane with a notification
+    fn entries_segment(&self, view: &RenderView) -> Option<String> {
+        let cm = view.color_manager;
+        let active_count = view.pane_states.values().filter(|s| s.has_notification()).count();
+        let queue_count = view.queue.len();
+
+        if active_count == 0 && queue_count == 0 {
+            self.entry_layout.record(&[]);
+            return Some(format!("{}No notifications{}", cm.fg_escape(&cm.get_dimmed_color()), cm.reset_escape()));
+        }
+        if !self.show_status_entries {
+            // Entries are switched off; keep a count so nothing goes unnoticed
+            self.entry_layout.record(&[]);
+            return Some(format!("{}{} active{}",
+                cm.fg_escape(&cm.get_dimmed_color()),
+                active_count + queue_count,
+                cm.reset_escape()
+            ));
+        }
+
+        let mut parts = Vec::new();
+        let mut drawn = Vec::new();
+        for (pane_id, state) in self.ordered_entries(view.pane_states) {
+            let Some(ref notif_type) = state.notification_type else { continue };
+            if state.acknowledged {
+                continue;
+            }
+            let pane_id = *pane_id;
+            let label = match self.entry_label(drawn.len()) {
+                Some(label) => format!("{}{}{}", cm.fg_escape(&cm.get_dimmed_color()), label, cm.reset_escape()),
+                None => String::new(),
+            };
+            drawn.push(pane_id);
+            let key = self.entry_key(state, notif_type, view);
+            parts.push(format!("{}{}", label,
+                self.entry_cache.get_or_build(pane_id, key, || self.format_entry(pane_id, state, notif_type, view))));
+        }
+        self.entry_cache.retain(&drawn);
+        self.entry_layout.record(&drawn);
+
+        for count in self.unlisted_counts(view.pane_states) {
+            parts.push(format!("{}({}){}", cm.fg_escape(&cm.get_dimmed_color()), count, cm.reset_escape()));
+        }
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+
+    /// Status entries drawn in earlier frames
+    pub fn entry_cache(&self) -> &EntryCache {
+        &self.entry_cache
+    }
+
+    /// Number label of the entry at a position, with `entry_hotkeys`
+    fn entry_label(&self, index: usize) -> Option<char> {
+        self.entry_hotkeys.then(|| EntryLayout::label(index)).flatten()
+    }
+
+    /// Pane of the status entry drawn with a number (1-9) in the last frame
+    pub fn entry_pane(&self, number: usize) -> Option<u32> {
+        self.entry_layout.pane_at(number)
+    }
+
+    /// Everything a status entry is drawn from; entries with an unchanged key are reused
+    fn entry_key(&self, state: &VisualState, notification_type: &NotificationType, view: &RenderView) -> EntryKey {
+        let brightness_bucket = match view.animation_engine.gradient_position(state, view.frame) {
+            Some(position) => EntryKey::bucket(position, true),
+            None => EntryKey::bucket(view.animation_engine.get_brightness(state, view.frame), false),
+        };
+        EntryKey {
+            notification_id: state.notification_id.clone(),
+            kind: notification_type.name(),
+            custom_color: state.custom_color.clone(),
+            correlation_id: state.correlation_id.clone(),
+            claude_pane: state.claude_pane,
+            brightness_bucket,
+            age_bucket: state.progress.as_ref().map(|p| (p.elapsed_ms / 1000, p.eta_ms.map(|eta| eta / 1000))),
+            percent: state.progress.as_ref().map(|p| (p.percent * 10.0).round() as u32),
+            icon: self.state_icon(state, notification_type, view.frame).to_string(),
+            priority: state.priority,
+            read: state.is_read(),
+            pinned: state.pinned,
+            animating: state.is_animating,
+            fallback: state.is_fallback(),
+            slo_breached: state.slo_breached,
+            deadline: state.deadline_at_ms.map(|_| (self.deadline_suffix(state, view.frame), self.overdue_flash(state, view.frame))),
+        }
+    }
+
+    /// One status entry, e.g. `[✓=:3]`
+    fn format_entry(&self, pane_id: u32, state: &VisualState, notif_type: &NotificationType, view: &RenderView) -> String {
+        let cm = view.color_manager;
+        let icon = self.state_icon(state, notif_type, view.frame);
+        let pattern = if !(self.use_patterns || cm.is_mono()) {
+            ""
+        } else if state.overdue {
+            OVERDUE_PATTERN
+        } else {
+            self.get_pattern_suffix(notif_type)
+        };
+
+        // Correlated entries share an accent bar in front of the entry; Claude panes have their own
+        let accent_color = match state.correlation_id {
+            Some(ref correlation_id) => Some(cm.accent_color(correlation_id)),
+            None if state.claude_pane => Some(self.claude_pane_color.clone()),
+            None => None,
+        };
+        let accent = match accent_color {
+            Some(color) => format!("{}{}{}",
+                cm.fg_escape(&color),
+                if self.use_unicode { "\u{258C}" } else { "|" }, // Left half block
+                cm.reset_escape()),
+            None => String::new(),
+        };
+
+        // Chips are told apart by their tint, so the brackets give way to padding
+        let (open, close) = if self.chips(cm) { (" ", " ") } else { ("[", "]") };
+        let entry = format!("{}{}{}{}{}{}{}:{}{}{}{}{}{}{}{}{}",
+            self.entry_escape(state, notif_type, view),
+            self.entry_attributes(notif_type, cm),
+            if self.overdue_flash(state, view.frame) { REVERSE_ESCAPE } else { "" },
+            open,
+            if state.pinned { self.icon(Icon::Pin) } else { "" },
+            icon,
+            pattern,
+            pane_id,
+            if state.is_animating { "*" } else { "" },
+            if state.is_fallback() { "\u{2261}" } else { "" }, // Collapsed/stacked marker
+            state.progress.as_ref().map(format_progress).unwrap_or_default(),
+            if state.slo_breached { format!(" {}", self.icon(Icon::Stopwatch)) } else { String::new() },
+            self.deadline_suffix(state, view.frame),
+            branch_suffix(state),
+            close,
+            cm.reset_escape()
+        );
+        format!("{}{}", accent, self.raw_ansi(self.raw_ansi_types.get(notif_type.name()), entry, cm))
+    }
+
+    /// Deadline countdown after an entry, e.g. ` ⌛9m`, counting the time past once overdue (` ⌛-2m`)
+    fn deadline_suffix(&self, state: &VisualState, frame: Frame) -> String {
+        let Some(deadline_at_ms) = state.deadline_at_ms else { return String::new() };
+        let countdown = match deadline_at_ms.checked_sub(frame.now_ms).filter(|_| !state.overdue) {
+            Some(remaining_ms) => format_countdown_ms(remaining_ms),
+            None => format!("-{}", format_countdown_ms(frame.now_ms.saturating_sub(deadline_at_ms))),
+        };
+        format!(" {}{}", self.icon(Icon::Deadline), countdown)
+    }
+
+    /// Whether an overdue entry is drawn in reverse video this frame (steadily under reduced motion)
+    fn overdue_flash(&self, state: &VisualState, frame: Frame) -> bool {
+        state.overdue && (self.reduced_motion || (frame.now_ms / OVERDUE_FLASH_MS).is_multiple_of(2))
+    }
+
+    /// Text in the raw ANSI snippets configured for it (left out in monochrome output)
+    fn raw_ansi(&self, raw: Option<&RawAnsi>, text: String, color_manager: &ColorManager) -> String {
+        match raw {
+            Some(raw) if !color_manager.is_mono() => raw.wrap(&text),
+            _ => text,
+        }
+    }
+
+    /// Counts standing in for entries left out of the list: `2 hidden` (hidden channels) and
+    /// `other projects: 3` (outside `project_roots`)
+    fn unlisted_counts(&self, pane_states: &BTreeMap<u32, VisualState>) -> Vec<String> {
+        let active = || pane_states.values().filter(|s| s.has_notification());
+        let hidden = active().filter(|s| s.hidden).count();
+        let other_projects = active().filter(|s| !s.hidden && s.other_project).count();
+        let waiting = attention_queue::waiting_count(pane_states);
+        let mut counts = Vec::new();
+        if waiting > 0 {
+            counts.push(format!("+{} waiting", waiting));
+        }
+        if hidden > 0 {
+            counts.push(format!("{} hidden", hidden));
+        }
+        if other_projects > 0 {
+            counts.push(format!("other projects: {}", other_projects));
+        }
+        counts
+    }
+
+    /// Color of a status entry: the type color adjusted for priority (read entries stay listed,
+    /// dimmed)
+    fn entry_color(&self, state: &VisualState, notification_type: &NotificationType, cm: &ColorManager) -> String {
+        match cm.resolve_color(notification_type, state.custom_color.as_deref()) {
+            _ if state.is_read() => cm.get_dimmed_color(),
+            Some(c) => cm.priority_adjusted(&c, &state.priority),
+            None => cm.get_foreground_color(),
+        }
+    }
+
+    /// Whether entries are drawn as chips (not in monochrome output, which has no tint to show)
+    fn chips(&self, cm: &ColorManager) -> bool {
+        self.entry_chips && !cm.is_mono()
+    }
+
+    /// Color escapes of a status entry, with the running animation applied to the configured
+    /// target: text color, a background fill, both, or an inverse-video flash. A chip is tinted
+    /// with the (animated) entry color under a text color that contrasts with it.
+    fn entry_escape(&self, state: &VisualState, notification_type: &NotificationType, view: &RenderView) -> String {
+        let cm = view.color_manager;
+        let color = self.entry_color(state, notification_type, cm);
+        let animated = self.animated_color(&color, notification_type, state, cm, view.animation_engine, view.frame);
+        if self.chips(cm) {
+            let chip = cm.chip_background(&animated);
+            return format!("{}{}", cm.bg_escape(&chip), cm.fg_escape(&cm.chip_foreground(&chip)));
+        }
+        if !state.is_animating {
+            return cm.fg_escape(&animated);
+        }
+        let fill = |color: &str| cm.bg_escape(&cm.interpolate(color, &cm.get_background_color(), ANIMATION_FILL_TINT));
+        match self.animation_target {
+            AnimationTarget::Foreground => cm.fg_escape(&animated),
+            AnimationTarget::Background => format!("{}{}", cm.fg_escape(&color), fill(&animated)),
+            AnimationTarget::Both => format!("{}{}", cm.fg_escape(&animated), fill(&animated)),
+            AnimationTarget::Inverse => {
+                let bright = match view.animation_engine.gradient_position(state, view.frame) {
+                    Some(position) => (0.25..0.75).contains(&position),
+                    None => view.animation_engine.get_brightness(state, view.frame) >= INVERSE_FLASH_THRESHOLD,
+                };
+                format!("{}{}", cm.fg_escape(&color), if bright { REVERSE_ESCAPE } else { "" })
+            }
+        }
+    }
+
+    /// Queue depth segment, e.g. `(+3 queued)`
+    fn queue_segment(&self, view: &RenderView) -> Option<String> {
+        let queue_count = view.queue.len();
+        (queue_count > 0).then(|| format!("(+{} queued)", queue_count))
+    }
+
+    /// Muted segment: panes whose notifications are muted
+    fn muted_segment(&self, view: &RenderView) -> Option<String> {
+        let muted: Vec<String> = view.pane_states.iter()
+            .filter(|(_, state)| state.muted)
+            .map(|(pane_id, _)| pane_id.to_string())
+            .collect();
+        if muted.is_empty() {
+            return None;
+        }
+        let marker = self.icon(Icon::Muted);
+        Some(format!("{}{}:{}{}",
+            view.color_manager.fg_escape(&view.color_manager.get_dimmed_color()),
+            marker,
+            muted.join(","),
+            view.color_manager.reset_escape()
+        ))
+    }
+
+    /// Surfaces segment: display surfaces switched off at runtime
+    fn surfaces_segment(&self, view: &RenderView) -> Option<String> {
+        if self.disabled_surfaces.is_empty() {
+            return None;
+        }
+        let marker = if self.use_unicode { "\u{2298}" } else { "off:" }; // Circled slash
+        Some(format!("{}{}{}{}",
+            view.color_manager.fg_escape(&view.color_manager.get_dimmed_color()),
+            marker,
+            self.disabled_surfaces.join(","),
+            view.color_manager.reset_escape()
+        ))
+    }
+
+    /// DND indicator segment: active DND or snooze
+    fn dnd_segment(&self, view: &RenderView) -> Option<String> {
+        view.suppression.as_ref().map(|status| self.build_suppression_segment(status, view.color_manager))
+    }
+
+    /// Bridge health segment: dimmed until the first message, error color with the reason on failure
+    fn bridge_segment(&self, view: &RenderView) -> Option<String> {
+        let cm = view.color_manager;
+        let marker = if self.use_unicode { "\u{21C4}" } else { "<>" }; // Left-right arrows
+        let (color, detail) = match view.bridge {
+            ConnectionState::Connected => (cm.get_notification_color(&NotificationType::Success), String::new()),
+            ConnectionState::Disconnected | ConnectionState::Connecting => (Some(cm.get_dimmed_color()), String::new()),
+            ConnectionState::Error(reason) => {
+                (cm.get_notification_color(&NotificationType::Error), format!(" {}", truncate(reason, 30)))
+            }
+        };
+        Some(format!("{}{}{}{}",
+            cm.fg_escape(&color.unwrap_or_else(|| cm.get_foreground_color())),
+            marker,
+            detail,
+            cm.reset_escape()))
+    }
+
+    /// Sessions segment: forwarded notifications per origin session with the icon of the most
+    /// urgent type, e.g. `api:✘2 web:✔1`
+    fn sessions_segment(&self, view: &RenderView) -> Option<String> {
+        let cm = view.color_manager;
+        let sessions: Vec<String> = view.aggregate.sessions()
+            .map(|(session, entries)| {
+                let urgent = most_urgent(entries);
+                format!("{}{}:{}{}{}",
+                    session,
+                    cm.fg_escape(&cm.get_notification_color(&urgent).unwrap_or_else(|| cm.get_foreground_color())),
+                    self.get_notification_icon(&urgent),
+                    entries.len(),
+                    cm.reset_escape())
+            })
+            .collect();
+        (!sessions.is_empty()).then(|| sessions.join(" "))
+    }
+
+    /// Clock segment: time since the most recent notification, e.g. `⏲ 3m05s`
+    fn clock_segment(&self, view: &RenderView) -> Option<String> {
+        let latest = view.pane_states.values()
+            .filter(|s| s.has_notification())
+            .map(|s| s.notification_timestamp)
+            .max()?;
+        Some(format!("{}{} {}{}",
+            view.color_manager.fg_escape(&view.color_manager.get_dimmed_color()),
+            self.icon(Icon::Timer),
+            format_duration_ms(view.frame.now_ms.saturating_sub(latest)),
+            view.color_manager.reset_escape()))
+    }
+
+    /// Rate segment: a sparkline of the notifications over the last hour
+    fn rate_segment(&self, view: &RenderView) -> Option<String> {
+        let sparkline = view.rate.total(self.use_unicode, view.frame.now_ms)?;
+        Some(format!("{}{}{}",
+            view.color_manager.fg_escape(&view.color_manager.get_dimmed_color()),
+            sparkline,
+            view.color_manager.reset_escape()))
+    }
+
+    /// Instance segment: this instance's role while several listen on the same pipes
+    fn instance_segment(&self, view: &RenderView) -> Option<String> {
+        let role = view.instance?;
+        let marker = match (role, self.use_unicode) {
+            (Role::Primary, true) => "\u{25CF}", // Black circle
+            (Role::Standby, true) => "\u{25CC}", // Dotted circle
+            (Role::Primary, false) => "*",
+            (Role::Standby, false) => "o",
+        };
+        Some(format!("{}{} {}{}",
+            view.color_manager.fg_escape(&view.color_manager.get_dimmed_color()),
+            marker,
+            role.name(),
+            view.color_manager.reset_escape()))
+    }
+
+    /// Message segment: a transient confirmation message
+    fn message_segment(&self, view: &RenderView) -> Option<String> {
+        view.status_message.map(|message| format!("{}{}{}",
+            view.color_manager.fg_escape(&view.color_manager.get_dimmed_color()),
+            message,
+            view.color_manager.reset_escape()))
+    }
+
+    /// Build the error banner segment, e.g. `✘ Permissions denied (retry 1/3) · d details`
+    fn build_error_banner(&self, error: &str, recovery: &RecoveryTracker, color_manager: &ColorManager) -> String {
+        let error_color = color_manager.get_notification_color(&NotificationType::Error)
+            .unwrap_or_else(|| color_manager.get_foreground_color());
+        let retry = if recovery.is_exhausted() {
+            " (recovery failed)".to_string()
+        } else if recovery.attempts > 0 {
+            format!(" (retry {}/{})", recovery.attempts, recovery.max_attempts)
+        } else {
+            String::new()
+        };
+        let separator = if self.use_unicode { " \u{00B7} " } else { " - " };
+
+        format!("{}{} {}{}{}{}d details{}",
+            color_manager.fg_escape(&error_color),
+            self.get_notification_icon(&NotificationType::Error),
+            truncate(error, 40),
+            retry,
+            separator,
+            color_manager.fg_escape(&color_manager.get_dimmed_color()),
+            color_manager.reset_escape()
+        )
+    }
+
+    /// Render the diagnostics view (error, recovery counters, recent log entries)
+    pub fn render_diagnostics(
+        &self,
+        screen: &mut ScreenBuffer,
+        rows: usize,
+        cols: usize,
+        view: &RenderView,
+        noisy: Option<&str>,
+        update: Option<&str>,
+        logs: &[LogEntry],
+    ) {
+        let lines = self.build_diagnostics_lines(rows, cols, view.error, view.recovery, noisy, update, view.color_manager, logs);
+        self.present(screen, rows, cols, &lines);
+    }
+
+    /// Build the diagnostics lines, keeping the newest log entries that fit; a newer release is
+    /// mentioned in a dimmed line
+    #[allow(clippy::too_many_arguments)]
+    fn build_diagnostics_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        error: Option<&str>,
+        recovery: &RecoveryTracker,
+        noisy: Option<&str>,
+        update: Option<&str>,
+        cm: &ColorManager,
+        logs: &[LogEntry],
+    ) -> Vec<String> {
+        let mut lines = vec![
+            truncate("Diagnostics - d to close", cols),
+            truncate(&format!("Error: {}", error.unwrap_or("none")), cols),
+            truncate(&format!("Recovery: {}/{} attempts, {} recovered",
+                recovery.attempts, recovery.max_attempts, recovery.recovered), cols),
+        ];
+        lines.extend(noisy.map(|suggestion| truncate(suggestion, cols)));
+        lines.extend(update.map(|update| format!("{}{}{}", cm.fg_escape(&cm.get_dimmed_color()), truncate(update, cols), cm.reset_escape())));
+
+        let room = rows.max(1).saturating_sub(lines.len());
+        let skip = logs.len().saturating_sub(room);
+        for entry in logs.iter().skip(skip) {
+            lines.push(truncate(&format!("{:>8} {} {}",
+                format_duration_ms(entry.at_ms),
+                entry.level.label(),
+                entry.message
+            ), cols));
+        }
+
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Render the health checklist view
+    pub fn render_health(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, report: &HealthReport, view: &RenderView) {
+        self.present(screen, rows, cols, &self.build_health_lines(rows, cols, report, view.color_manager));
+    }
+
+    /// Build the health checklist lines, marks colored by outcome
+    fn build_health_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        report: &HealthReport,
         color_manager: &ColorManager,
-        animation_engine: &AnimationEngine,
-        tick: u64,
+    ) -> Vec<String> {
+        let mut lines = vec![truncate("Health check - h to close, r to re-request permissions", cols)];
+        for (check, line) in report.checks.iter().zip(report.lines()) {
+            let notification_type = match check.status {
+                CheckStatus::Pass => NotificationType::Success,
+                CheckStatus::Warn => NotificationType::Warning,
+                CheckStatus::Fail => NotificationType::Error,
+            };
+            let color = color_manager.get_notification_color(&notification_type)
+                .unwrap_or_else(|| color_manager.get_foreground_color());
+            lines.push(format!("{}{}{}", color_manager.fg_escape(&color), truncate(&line, cols), color_manager.reset_escape()));
+        }
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Render the channel list view
+    pub fn render_channels(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, view: &RenderView) {
+        self.present(screen, rows, cols, &self.build_channel_lines(rows, cols, view.channels));
+    }
+
+    /// Build the channel list lines: number key, visibility box, name and count per channel
+    fn build_channel_lines(&self, rows: usize, cols: usize, channels: &ChannelList) -> Vec<String> {
+        let mut lines = vec![truncate("Channels - 1-9 to show/hide, c to close", cols)];
+        let channel_lines = channels.lines();
+        if channel_lines.is_empty() {
+            lines.push(truncate("No channels yet", cols));
+        }
+        lines.extend(channel_lines.iter().map(|line| truncate(line, cols)));
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Render the legend view
+    pub fn render_legend(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, context: &LegendContext, view: &RenderView) {
+        let lines = self.build_legend_lines(rows, cols, context, view.channels, view.suppression.as_ref(), view.color_manager);
+        self.present(screen, rows, cols, &lines);
+    }
+
+    /// Build the legend lines: the type icons in their colors with their pattern suffixes, the
+    /// entry markers, the keys, the channels and sources in their accent colors, and what is
+    /// held back right now
+    fn build_legend_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        context: &LegendContext,
+        channels: &ChannelList,
+        suppression: Option<&SuppressionStatus>,
+        cm: &ColorManager,
+    ) -> Vec<String> {
+        let reset = cm.reset_escape();
+        let with_patterns = self.use_patterns || cm.is_mono();
+        let mut lines = vec![truncate("Legend - ? to close", cols)];
+
+        let types: Vec<String> = URGENCY_ORDER.iter()
+            .map(|notification_type| format!("{}{}{} {}{}",
+                cm.fg_escape(&cm.get_notification_color(notification_type).unwrap_or_else(|| cm.get_foreground_color())),
+                self.get_notification_icon(notification_type),
+                if with_patterns { self.get_pattern_suffix(notification_type) } else { "" },
+                notification_type.name(),
+                reset))
+            .collect();
+        lines.push(fit(&format!("Types: {}", types.join("  ")), cols, Align::Left));
+        lines.push(truncate(&format!("Markers: {} pinned  * animating  \u{2261} collapsed pane  {} past SLO  {} correlated",
+            self.icon(Icon::Pin), self.icon(Icon::Stopwatch), if self.use_unicode { "\u{258C}" } else { "|" }), cols));
+
+        let keys: Vec<String> = context.keys.iter().map(|(key, action)| format!("{} {}", key, action)).collect();
+        lines.extend(wrap(&format!("Keys: {}", keys.join(", ")), cols));
+
+        let accented = |name: &str, note: &str| format!("{}{}{}{}", cm.fg_escape(&cm.accent_color(name)), name, reset, note);
+        let channels: Vec<String> = channels.iter()
+            .map(|channel| accented(&channel.name, if channel.visible { "" } else { " (hidden)" }))
+            .collect();
+        let sources: Vec<String> = context.sources.iter()
+            .filter(|source| !context.muted_sources.contains(*source))
+            .map(|source| accented(source, ""))
+            .chain(context.muted_sources.iter().map(|source| accented(source, " (muted)")))
+            .collect();
+        for (label, names) in [("Channels", channels), ("Sources", sources)] {
+            let names = if names.is_empty() { "none".to_string() } else { names.join(", ") };
+            lines.push(fit(&format!("{}: {}", label, names), cols, Align::Left));
+        }
+
+        let suppression = match suppression {
+            Some(status) => format!("{} on{}{}",
+                status.kind.label(),
+                status.remaining_ms.map(|ms| format!(" for {}", format_countdown_ms(ms))).unwrap_or_default(),
+                if status.held > 0 { format!(", {} held", status.held) } else { String::new() }),
+            None => "DND off".to_string(),
+        };
+        let muted: Vec<String> = context.muted_panes.iter().map(|pane_id| pane_id.to_string()).collect();
+        lines.push(truncate(&format!("Now: {}, muted panes: {}", suppression,
+            if muted.is_empty() { "none".to_string() } else { muted.join(", ") }), cols));
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Render the correlation group view
+    pub fn render_groups(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, view: &RenderView) {
+        self.present(screen, rows, cols, &self.build_group_lines(rows, cols, view.pane_states, view.color_manager));
+    }
+
+    /// Render the history view
+    pub fn render_history(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, title: &str, groups: &[ResultGroup], now_ms: u64) {
+        self.present(screen, rows, cols, &self.build_history_lines(rows, cols, title, groups, now_ms));
+    }
+
+    /// Render the plain text log (no color, icons or animation)
+    pub fn render_text_log(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, lines: &[String]) {
+        self.present(screen, rows, cols, lines);
+    }
+
+    /// Render the startup banner, joined into one line when the pane is too short for it
+    pub fn render_banner(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, lines: &[String]) {
+        let lines: Vec<String> = if rows >= lines.len() {
+            lines.iter().map(|line| truncate(line, cols)).collect()
+        } else {
+            vec![truncate(&lines.join(" | "), cols)]
+        };
+        self.present(screen, rows, cols, &lines);
+    }
+
+    /// Render the first-run tutorial
+    pub fn render_tutorial(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, lines: &[String]) {
+        let lines: Vec<String> = lines.iter().take(rows.max(1)).map(|line| truncate(line, cols)).collect();
+        self.present(screen, rows, cols, &lines);
+    }
+
+    /// Build the history view lines: a header with the count per group, then its entries; entries
+    /// that don't fit are counted in a last line
+    fn build_history_lines(&self, rows: usize, cols: usize, title: &str, groups: &[ResultGroup], now_ms: u64) -> Vec<String> {
+        let mut lines = vec![truncate(title, cols)];
+        if groups.is_empty() {
+            lines.push(truncate("No notifications yet", cols));
+        }
+        for group in groups {
+            lines.extend(group.header().map(|header| truncate(&header, cols)));
+            let indent = if group.label.is_some() { "  " } else { "" };
+            lines.extend(group.entries.iter().map(|entry| truncate(&format!("{}{}", indent, entry_line(entry, now_ms)), cols)));
+        }
+        let rows = rows.max(2);
+        if lines.len() > rows {
+            let hidden = lines.len() - (rows - 1);
+            lines.truncate(rows - 1);
+            lines.push(truncate(&format!("... {} more lines", hidden), cols));
+        }
+        lines
+    }
+
+    /// Render the sessions view (notifications forwarded from other sessions)
+    pub fn render_sessions(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, view: &RenderView) {
+        self.present(screen, rows, cols, &self.build_session_lines(rows, cols, view.aggregate, view.color_manager, view.frame.now_ms));
+    }
+
+    /// Build the sessions view lines: an accent-colored header with the jump hint per session,
+    /// then its entries, newest first
+    fn build_session_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        aggregate: &SessionAggregate,
+        color_manager: &ColorManager,
+        now_ms: u64,
+    ) -> Vec<String> {
+        let mut lines = vec![truncate("Sessions - j to close", cols)];
+        if aggregate.is_empty() {
+            lines.push(truncate("No forwarded notifications", cols));
+        }
+        for (session, entries) in aggregate.sessions() {
+            lines.push(format!("{}{}{}",
+                color_manager.fg_escape(&color_manager.accent_color(session)),
+                truncate(&format!("{} ({}) - zellij attach {}", session, entries.len(), session), cols),
+                color_manager.reset_escape()));
+            lines.extend(entries.iter().rev().map(|entry| truncate(&format!("  {} {}",
+                self.get_notification_icon(&entry.notification_type), entry.line(now_ms)), cols)));
+        }
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Render the detail view of one notification with its TTL controls
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_detail(
+        &self,
+        screen: &mut ScreenBuffer,
+        rows: usize,
+        cols: usize,
+        notification: Option<&Notification>,
+        pager: &Pager,
+        extend_ms: u64,
+        view: &RenderView,
     ) {
-        if !self.show_status_bar || cols < 10 {
-            return;
+        self.present(screen, rows, cols, &self.build_detail_lines(rows, cols, notification, pager, extend_ms, view.color_manager));
+    }
+
+    /// Message lines of the detail view below the header, wrapped to `cols`: none when the
+    /// message fits on the line with the notification type
+    pub fn detail_body(&self, notification: &Notification, cols: usize) -> Vec<String> {
+        let paragraphs: Vec<String> = markup::parse(&notification.message).iter()
+            .map(|line| line.iter().map(|span| span.text.as_str()).collect())
+            .collect();
+        let inline = format!("{}: {}", self.detail_type_label(notification), markup::strip(&notification.message));
+        if paragraphs.len() <= 1 && display_width(&inline) <= cols {
+            return Vec::new();
         }
+        paragraphs.iter().flat_map(|paragraph| wrap(paragraph, cols)).collect()
+    }
 
-        // Count active notifications
-        let active_count = pane_states.values().filter(|s| s.has_notification()).count();
-        let queue_count = queue.len();
+    /// Rows per page of the detail view's message lines
+    pub fn detail_page_rows(rows: usize) -> usize {
+        rows.saturating_sub(DETAIL_HEADER_ROWS).max(1)
+    }
 
-        // Build status bar content
-        let content = self.build_status_content(
-            active_count,
-            queue_count,
-            pane_states,
-            color_manager,
-            animation_engine,
-            tick,
-        );
+    /// Icon, type and pane of a notification, e.g. `✘ error pane 3`
+    fn detail_type_label(&self, notification: &Notification) -> String {
+        let pane = notification.pane_id.map(|pane_id| format!(" pane {}", pane_id)).unwrap_or_default();
+        format!("{} {}{}", self.get_notification_icon(&notification.notification_type), notification.notification_type.name(), pane)
+    }
 
-        // Print the status bar (Zellij will capture this)
-        print!("{}", content);
+    /// Build the detail view lines: the key hints, the notification in its type color, then its
+    /// ID, source and priority, and how its TTL stands. A message too long for the type line
+    /// follows, wrapped, one page at a time.
+    fn build_detail_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        notification: Option<&Notification>,
+        pager: &Pager,
+        extend_ms: u64,
+        color_manager: &ColorManager,
+    ) -> Vec<String> {
+        let body = notification.map(|notification| self.detail_body(notification, cols)).unwrap_or_default();
+        let page_rows = Self::detail_page_rows(rows);
+        let pages = Pager::page_count(body.len(), page_rows);
+        let title = if pages > 1 {
+            format!("Notification {} - PgUp/PgDn page, End last, e extend {}, k keep, x expire now, i to close",
+                pager.indicator(pages), format_age(extend_ms))
+        } else {
+            format!("Notification - e extend {}, k keep, x expire now, i to close", format_age(extend_ms))
+        };
+        let mut lines = vec![truncate(&title, cols)];
+        let Some(notification) = notification else {
+            lines.push(truncate("No notification to show", cols));
+            return lines;
+        };
+        let notification_type = &notification.notification_type;
+        let type_line = if body.is_empty() {
+            format!("{}: {}", self.detail_type_label(notification), markup::strip(&notification.message))
+        } else {
+            self.detail_type_label(notification)
+        };
+        lines.push(format!("{}{}{}",
+            color_manager.fg_escape(&color_manager.get_notification_color(notification_type)
+                .unwrap_or_else(|| color_manager.get_foreground_color())),
+            truncate(&type_line, cols),
+            color_manager.reset_escape()));
+        lines.push(truncate(&format!("id {}, source {}, priority {}",
+            notification.id, notification.source, notification.priority.name()), cols));
+        let ttl = match notification.ttl_left() {
+            Some(left_ms) => format!("expires in {}, then {}", format_duration_ms(left_ms), notification.on_expire.name()),
+            None => "never expires".to_string(),
+        };
+        let ttl = if notification.reply_options.is_empty() {
+            ttl
+        } else {
+            format!("{}, reply {}", ttl, quick_reply::hints(&notification.reply_options))
+        };
+        lines.push(truncate(&ttl, cols));
+        lines.extend(pager.visible(&body, page_rows).iter().cloned());
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Build the correlation group lines: an accent-colored header per group, then its members
+    fn build_group_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        pane_states: &BTreeMap<u32, VisualState>,
+        color_manager: &ColorManager,
+    ) -> Vec<String> {
+        let mut lines = vec![truncate("Groups - g to close", cols)];
+        let groups = correlation::groups(pane_states);
+        if groups.is_empty() {
+            lines.push(truncate("No correlated notifications", cols));
+        }
+        for group in groups {
+            lines.push(format!("{}{}{}",
+                color_manager.fg_escape(&color_manager.accent_color(group.id)),
+                truncate(&group.header(), cols),
+                color_manager.reset_escape()));
+            lines.extend(group.member_lines().iter().map(|line| truncate(line, cols)));
+        }
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Build the suppression segment, e.g. `(DND 34m · 5 held)`
+    fn build_suppression_segment(&self, status: &SuppressionStatus, color_manager: &ColorManager) -> String {
+        let mut parts = vec![status.kind.label().to_string()];
+        if let Some(remaining) = status.remaining_ms {
+            parts[0].push_str(&format!(" {}", format_countdown_ms(remaining)));
+        }
+        // Focus mode stands out: it was started on purpose and ends with a digest
+        let focus = status.kind == SuppressionKind::Focus;
+        if status.held > 0 {
+            parts.push(format!("{} {}", status.held, if focus { "batched" } else { "held" }));
+        }
+        let separator = if self.use_unicode { " \u{00B7} " } else { " - " };
+        let color = if focus { color_manager.get_highlight_color() } else { color_manager.get_dimmed_color() };
+
+        format!("{}({}){}",
+            color_manager.fg_escape(&color),
+            parts.join(separator),
+            color_manager.reset_escape()
+        )
+    }
+
+    /// Render the big mode view (enlarged entries for presentations and screen sharing)
+    pub fn render_big_status(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, view: &RenderView) {
+        self.present(screen, rows, cols, &self.build_big_status_lines(rows, cols, view.pane_states, view.color_manager));
     }
 
-    /// Build the status bar content string
-    fn build_status_content(
+    /// Build the big mode lines: block glyph, type and pane, and message per entry, as many
+    /// entries as fit between double-line separators
+    fn build_big_status_lines(
         &self,
-        active_count: usize,
-        queue_count: usize,
+        rows: usize,
+        cols: usize,
+        pane_states: &BTreeMap<u32, VisualState>,
+        color_manager: &ColorManager,
+    ) -> Vec<String> {
+        let separator = "\u{2550}".repeat(cols); // Double horizontal line
+        let reset = color_manager.reset_escape();
+        let text_width = cols.saturating_sub(BIG_GLYPH_WIDTH + 2);
+        let entries: Vec<(&u32, &VisualState)> = self.ordered_entries(pane_states).into_iter()
+            .filter(|(_, state)| state.has_notification())
+            .collect();
+
+        let mut lines = vec![separator.clone()];
+        if entries.is_empty() {
+            lines.push(truncate("No notifications", cols));
+        }
+
+        let fit = (rows.saturating_sub(1) / 4).max(1);
+        let more = entries.len().saturating_sub(fit);
+        for (index, (pane_id, state)) in entries.iter().take(fit).enumerate() {
+            let notif_type = match state.notification_type {
+                Some(ref notif_type) => notif_type,
+                None => continue,
+            };
+            if index > 0 {
+                lines.push(String::new());
+            }
+            let color = color_manager.resolve_color(notif_type, state.custom_color.as_deref())
+                .unwrap_or_else(|| color_manager.get_foreground_color());
+            // The message takes the second row, and the third when it runs onto a second line;
+            // otherwise the third shows the quick reply options, if any
+            let message = markup::parse(state.notification_message.as_deref().unwrap_or(""));
+            let text = [
+                format!("{}{} \u{00B7} pane {}{}", BOLD_ESCAPE, notif_type.name().to_uppercase(), pane_id, reset),
+                message.first().map(|line| markup::render_line(line, text_width)).unwrap_or_default(),
+                match message.get(1) {
+                    _ if index + 1 == fit && more > 0 => format!("+{} more", more),
+                    Some(line) => markup::render_line(line, text_width),
+                    None if !state.reply_options.is_empty() => truncate(&quick_reply::hints(&state.reply_options), text_width),
+                    None => String::new(),
+                },
+            ];
+            for (glyph_row, text_row) in big_glyph(notif_type).iter().zip(text) {
+                lines.push(format!("{}{}{}{}  {}",
+                    color_manager.fg_escape(&color),
+                    self.entry_attributes(notif_type, color_manager),
+                    glyph_row,
+                    reset,
+                    text_row
+                ));
+            }
+        }
+
+        lines.push(separator);
+        lines
+    }
+
+    /// Render the queue inspection overlay (per-priority lanes with TTL, source and target)
+    pub fn render_queue_overlay(&self, screen: &mut ScreenBuffer, rows: usize, cols: usize, view: &RenderView) {
+        let lines = self.build_queue_overlay_lines(rows, cols, view.queue, view.queue_diff, view.frame.now_ms, view.color_manager);
+        self.present(screen, rows, cols, &lines);
+    }
+
+    /// Render the inline clear-all confirmation prompt
+    pub fn render_clear_confirm(&self, cols: usize, count: usize, view: &RenderView) {
+        println!("{}", self.glyphs(self.build_clear_confirm_line(cols, count, view.color_manager)));
+    }
+
+    /// Build the clear-all confirmation prompt line
+    fn build_clear_confirm_line(&self, cols: usize, count: usize, color_manager: &ColorManager) -> String {
+        let warning = color_manager.fg_escape(&color_manager.get_notification_color(&NotificationType::Warning)
+            .unwrap_or_else(|| color_manager.get_foreground_color()));
+        let noun = if count == 1 { "notification" } else { "notifications" };
+        format!(
+            "{}{}{}",
+            warning,
+            truncate(&format!("Clear {} {}? y/n", count, noun), cols),
+            color_manager.reset_escape()
+        )
+    }
+
+    /// Render the clear-all cascade
+    pub fn render_clear_cascade(&self, cols: usize, cascade: &ClearCascade, view: &RenderView) {
+        println!("{}", self.glyphs(self.build_clear_cascade_line(cols, cascade, view)));
+    }
+
+    /// Build the clear-all cascade line: the count, then the cleared entries fading out left to
+    /// right (gone ones leave blanks, so the others keep their places)
+    fn build_clear_cascade_line(&self, cols: usize, cascade: &ClearCascade, view: &RenderView) -> String {
+        let cm = view.color_manager;
+        let elapsed_ms = cascade.elapsed_ms(view.frame.now_ms);
+        let count = cascade.entries.len();
+        let mut line = format!("{}Cleared {}{}", cm.fg_escape(&cm.get_dimmed_color()), count, cm.reset_escape());
+        for (index, (pane_id, notification_type)) in cascade.entries.iter().enumerate() {
+            let entry = format!("[{}:{}]", self.get_notification_icon(notification_type), pane_id);
+            let brightness = view.animation_engine.cascade_brightness(index, count, elapsed_ms);
+            if brightness <= 0.0 {
+                line.push_str(&" ".repeat(display_width(&entry) + 1));
+                continue;
+            }
+            let color = cm.get_notification_color(notification_type).unwrap_or_else(|| cm.get_foreground_color());
+            line.push_str(&format!(" {}{}{}", cm.fg_escape(&cm.apply_brightness(&color, brightness)), entry, cm.reset_escape()));
+        }
+        if display_width(&line) > cols {
+            return truncate(&format!("Cleared {}", count), cols);
+        }
+        line
+    }
+
+    /// Pane and type of the listed entries, in status bar order
+    pub fn listed_entries(&self, pane_states: &BTreeMap<u32, VisualState>) -> Vec<(u32, NotificationType)> {
+        self.ordered_entries(pane_states).into_iter()
+            .filter_map(|(pane_id, state)| state.notification_type.clone().map(|notification_type| (*pane_id, notification_type)))
+            .collect()
+    }
+
+    /// Render an automation's confirmation prompt
+    pub fn render_automation_confirm(&self, cols: usize, prompt: &str, view: &RenderView) {
+        let color_manager = view.color_manager;
+        let warning = color_manager.fg_escape(&color_manager.get_notification_color(&NotificationType::Warning)
+            .unwrap_or_else(|| color_manager.get_foreground_color()));
+        println!("{}{}{}", warning, self.glyphs(truncate(prompt, cols)), color_manager.reset_escape());
+    }
+
+    /// Render a sender's yes/no question with its countdown (reverse video, so it stands out)
+    pub fn render_sender_confirm(&self, cols: usize, prompt: &str, view: &RenderView) {
+        let color_manager = view.color_manager;
+        let attention = color_manager.fg_escape(&color_manager.get_notification_color(&NotificationType::Attention)
+            .unwrap_or_else(|| color_manager.get_foreground_color()));
+        println!("{}\x1b[7m{}{}", attention, self.glyphs(truncate(prompt, cols)), color_manager.reset_escape());
+    }
+
+    /// Render the auto-focus countdown
+    pub fn render_auto_focus_countdown(&self, cols: usize, countdown: &str, view: &RenderView) {
+        let color_manager = view.color_manager;
+        let attention = color_manager.fg_escape(&color_manager.get_notification_color(&NotificationType::Attention)
+            .unwrap_or_else(|| color_manager.get_foreground_color()));
+        println!("{}{}{}", attention, self.glyphs(truncate(countdown, cols)), color_manager.reset_escape());
+    }
+
+    /// Render the hint bar of a pending leader chord
+    pub fn render_chord_hint(&self, cols: usize, hint: &str, view: &RenderView) {
+        let color_manager = view.color_manager;
+        println!("{}{}{}", color_manager.fg_escape(&color_manager.get_foreground_color()),
+            self.glyphs(truncate(hint, cols)), color_manager.reset_escape());
+    }
+
+    /// Render the acknowledge-by-type chord prompt
+    pub fn render_ack_chord_prompt(&self, cols: usize, view: &RenderView) {
+        println!("{}", self.glyphs(self.build_ack_chord_line(cols, view.pane_states, view.color_manager)));
+    }
+
+    /// Build the acknowledge-by-type chord prompt, with active counts per type key
+    fn build_ack_chord_line(
+        &self,
+        cols: usize,
         pane_states: &BTreeMap<u32, VisualState>,
         color_manager: &ColorManager,
-        animation_engine: &AnimationEngine,
-        tick: u64,
     ) -> String {
-        let mut output = String::new();
+        let keys = [
+            ('e', NotificationType::Error),
+            ('s', NotificationType::Success),
+            ('w', NotificationType::Warning),
+            ('i', NotificationType::Info),
+            ('p', NotificationType::Progress),
+            ('a', NotificationType::Attention),
+        ];
+        let choices: Vec<String> = keys.iter()
+            .map(|(key, t)| {
+                let count = pane_states.values()
+                    .filter(|s| s.has_notification() && s.notification_type.as_ref() == Some(t))
+                    .count();
+                format!("{} {}({})", key, t.name(), count)
+            })
+            .collect();
 
-        // Plugin name/icon
-        let icon = if self.use_unicode { "\u{1F514}" } else { "[N]" };  // Bell icon
-        output.push_str(&format!("{} ", icon));
+        format!(
+            "{}{}{}",
+            color_manager.fg_escape(&color_manager.get_foreground_color()),
+            truncate(&format!("Acknowledge: {}", choices.join(" ")), cols),
+            color_manager.reset_escape()
+        )
+    }
 
-        // Show notification counts
-        if active_count == 0 && queue_count == 0 {
-            output.push_str(&format!("{}No notifications{}",
-                color_manager.fg_escape(&color_manager.get_dimmed_color()),
-                color_manager.reset_escape()
+    /// Build the queue overlay lines, bounded to the available rows
+    fn build_queue_overlay_lines(
+        &self,
+        rows: usize,
+        cols: usize,
+        queue: &NotificationQueue,
+        diff: &QueueDiff,
+        now_ms: u64,
+        color_manager: &ColorManager,
+    ) -> Vec<String> {
+        let mut lines = Vec::new();
+        let dimmed = color_manager.fg_escape(&color_manager.get_dimmed_color());
+        let reset = color_manager.reset_escape();
+        let added = color_manager.get_notification_color(&NotificationType::Success)
+            .unwrap_or_else(|| color_manager.get_foreground_color());
+        let target = |notification: &Notification| match (notification.pane_id, notification.tab_index) {
+            (Some(pane_id), _) => format!("pane {}", pane_id),
+            (None, Some(tab_index)) => format!("tab {}", tab_index),
+            (None, None) => "-".to_string(),
+        };
+
+        let stats = queue.stats();
+        lines.push(truncate(&format!("Queue inspector ({} queued, {}/{} KiB, {} evicted) - q to close",
+            stats.total_queued,
+            stats.queued_bytes / 1024,
+            stats.max_bytes / 1024,
+            stats.total_evicted
+        ), cols));
+
+        for priority in [Priority::Critical, Priority::High, Priority::Normal, Priority::Low] {
+            let lane = queue.lane(&priority);
+            lines.push(format!("{}{}{}",
+                dimmed,
+                truncate(&format!("{} ({}, {} dropped)",
+                    priority.name().to_uppercase(),
+                    lane.len(),
+                    queue.dropped_count(&priority)
+                ), cols),
+                reset
             ));
-        } else {
-            // Show active notification indicators
-            for (pane_id, state) in pane_states.iter() {
-                if let Some(ref notif_type) = state.notification_type {
-                    if !state.acknowledged {
-                        let color = color_manager.get_notification_color(notif_type)
-                            .unwrap_or_else(|| color_manager.get_foreground_color());
-
-                        let brightness = animation_engine.get_brightness(state, tick);
-                        let adjusted_color = color_manager.apply_brightness(&color, brightness);
-
-                        let icon = self.get_notification_icon(notif_type);
-                        let pattern = if self.use_patterns {
-                            self.get_pattern_suffix(notif_type)
-                        } else {
-                            ""
-                        };
-
-                        output.push_str(&format!("{}[{}{}:{}{}]{} ",
-                            color_manager.fg_escape(&adjusted_color),
-                            icon,
-                            pattern,
-                            pane_id,
-                            if state.is_animating { "*" } else { "" },
-                            color_manager.reset_escape()
-                        ));
-                    }
-                }
+
+            for notification in lane {
+                let ttl = match queue.ttl_remaining(notification) {
+                    Some(remaining) => format_duration_ms(remaining),
+                    None => "\u{221E}".to_string(), // Infinity
+                };
+                // Arrived since the inspector was opened: green tint
+                let is_new = diff.is_new(&notification.id, now_ms);
+                let color = if is_new {
+                    added.clone()
+                } else {
+                    color_manager.resolve_color(&notification.notification_type, notification.color.as_deref())
+                        .map(|c| color_manager.priority_adjusted(&c, &notification.priority))
+                        .unwrap_or_else(|| color_manager.get_foreground_color())
+                };
+
+                let prefix = format!("{} {} {:>6} {} -> {} | ",
+                    if is_new { "+" } else { " " },
+                    self.get_notification_icon(&notification.notification_type),
+                    ttl,
+                    notification.source,
+                    target(notification)
+                );
+                lines.push(format!("{}{}{}{}",
+                    color_manager.fg_escape(&color),
+                    truncate(&prefix, cols),
+                    markup::render_inline(&notification.display_text(), cols.saturating_sub(prefix.chars().count())),
+                    reset
+                ));
             }
 
-            // Show queue count if any
-            if queue_count > 0 {
-                output.push_str(&format!("(+{} queued)", queue_count));
+            // Left the queue since the last refreshes: struck through until the highlight runs out
+            for notification in diff.removed(&priority, now_ms) {
+                let row = format!("- {} {:>6} {} -> {} | {}",
+                    self.get_notification_icon(&notification.notification_type),
+                    "gone",
+                    notification.source,
+                    target(notification),
+                    notification.display_text()
+                );
+                lines.push(format!("{}\x1b[9m{}{}", dimmed, truncate(&row, cols), reset));
             }
         }
 
-        output
+        lines.truncate(rows.max(1));
+        lines
+    }
+
+    /// Glyph of an icon in the current set, under the width policy
+    fn icon(&self, icon: Icon) -> &'static str {
+        icons::glyph(icon, self.use_unicode, self.icon_width_policy)
     }
 
     /// Get the icon for a notification type
     fn get_notification_icon(&self, notification_type: &NotificationType) -> &'static str {
-        if self.use_unicode {
-            match notification_type {
-                NotificationType::Success => "\u{2714}",   // Check mark
-                NotificationType::Error => "\u{2718}",     // X mark
-                NotificationType::Warning => "\u{26A0}",   // Warning triangle
-                NotificationType::Info => "\u{2139}",      // Info symbol
-                NotificationType::Progress => "\u{21BB}",  // Rotating arrow
-                NotificationType::Attention => "\u{2757}", // Exclamation mark
-            }
-        } else {
-            match notification_type {
-                NotificationType::Success => "+",
-                NotificationType::Error => "X",
-                NotificationType::Warning => "!",
-                NotificationType::Info => "i",
-                NotificationType::Progress => "~",
-                NotificationType::Attention => "!",
-            }
+        self.icon(Icon::for_type(notification_type))
+    }
+
+    /// Get the icon for a pane's visual state, preferring a sender override. Progress that has
+    /// not completed shows the spinner frame for the tick (the static icon under reduced motion).
+    fn state_icon<'a>(&self, state: &'a VisualState, notification_type: &NotificationType, frame: Frame) -> &'a str {
+        if let Some(ref icon) = state.custom_icon {
+            return icon.as_str();
+        }
+        match notification_type.icon_frames(self.use_unicode) {
+            Some(frames) if state.is_spinning() && !self.reduced_motion => frames.frame(frame.tick),
+            _ => self.get_notification_icon(notification_type),
         }
     }
 
@@ -183,15 +1659,21 @@ impl Renderer {
         &self,
         state: &VisualState,
         color_manager: &ColorManager,
+        frame: Frame,
     ) -> Option<String> {
-        if !self.show_tab_badges {
+        // Collapsed panes force the tab badge regardless of configuration
+        if !self.show_tab_badges && !state.is_fallback() {
             return None;
         }
 
         if let Some(ref notif_type) = state.notification_type {
             if !state.acknowledged {
-                let icon = self.get_notification_icon(notif_type);
-                let color = color_manager.get_notification_color(notif_type)?;
+                let icon = self.state_icon(state, notif_type, frame);
+                let color = match color_manager.resolve_color(notif_type, state.custom_color.as_deref()) {
+                    Some(_) if state.is_read() => color_manager.get_dimmed_color(),
+                    Some(c) => color_manager.priority_adjusted(&c, &state.priority),
+                    None => return None,
+                };
 
                 return Some(format!("{}{}{}",
                     color_manager.fg_escape(&color),
@@ -204,29 +1686,103 @@ impl Renderer {
         None
     }
 
+    /// Apply the running animation to a base color: a gradient step or a brightness change
+    fn animated_color(
+        &self,
+        base_color: &str,
+        notification_type: &NotificationType,
+        state: &VisualState,
+        color_manager: &ColorManager,
+        animation_engine: &AnimationEngine,
+        frame: Frame,
+    ) -> String {
+        if let Some(position) = animation_engine.gradient_position(state, frame) {
+            return match self.gradient_stops.get(notification_type.name()) {
+                Some(stops) => color_manager.gradient_color(stops, position),
+                None => {
+                    // Default: swing toward the foreground color and back
+                    let peak = color_manager.interpolate(base_color, &color_manager.get_foreground_color(), 0.5);
+                    let stops = [base_color.to_string(), peak, base_color.to_string()];
+                    color_manager.gradient_color(&stops, position)
+                }
+            };
+        }
+
+        let brightness = animation_engine.get_brightness(state, frame);
+        color_manager.apply_brightness(base_color, brightness)
+    }
+
+    /// Status bar entries in the configured order (entries on hidden channels, of other projects
+    /// and waiting in the attention queue left out)
+    ///
+    /// The surfaced attention queue entry comes first, then pinned entries. Urgency order then sorts by priority, then type urgency,
+    /// then recency, with the pane ID as the final tie-breaker; none of these change while a
+    /// notification animates, so entries keep their places between frames.
+    fn ordered_entries<'a>(&self, pane_states: &'a BTreeMap<u32, VisualState>) -> Vec<(&'a u32, &'a VisualState)> {
+        let mut entries: Vec<(&u32, &VisualState)> = pane_states.iter().filter(|(_, state)| state.is_listed()).collect();
+        if self.status_order == StatusOrder::Urgency {
+            entries.sort_by_key(|(pane_id, state)| (
+                state.attention_slot != AttentionSlot::Surfaced,
+                std::cmp::Reverse(state.pinned),
+                std::cmp::Reverse(state.claude_pane),
+                std::cmp::Reverse(state.display_priority()),
+                std::cmp::Reverse(state.notification_type.as_ref().map(|t| t.urgency()).unwrap_or(0)),
+                std::cmp::Reverse(state.notification_timestamp),
+                **pane_id,
+            ));
+        } else {
+            // Surfaced, pinned and Claude pane entries first, otherwise in pane order
+            entries.sort_by_key(|(_, state)| (
+                state.attention_slot != AttentionSlot::Surfaced,
+                std::cmp::Reverse(state.pinned),
+                std::cmp::Reverse(state.claude_pane),
+            ));
+        }
+        entries
+    }
+
+    /// Whether a notification gets static emphasis (bold, double border) in place of animation
+    fn static_emphasis(&self, notification_type: &NotificationType) -> bool {
+        self.reduced_motion && notification_type.is_urgent()
+    }
+
+    /// Text attributes for a status bar entry (bold/reverse carry the emphasis when color is off)
+    fn entry_attributes(&self, notification_type: &NotificationType, color_manager: &ColorManager) -> &'static str {
+        if color_manager.is_mono() {
+            if notification_type.is_urgent() { BOLD_REVERSE_ESCAPE } else { BOLD_ESCAPE }
+        } else if self.static_emphasis(notification_type) {
+            BOLD_ESCAPE
+        } else {
+            ""
+        }
+    }
+
     /// Get border style for a pane
     pub fn get_border_style(
         &self,
         state: &VisualState,
         color_manager: &ColorManager,
         animation_engine: &AnimationEngine,
-        tick: u64,
+        frame: impl Into<Frame>,
     ) -> Option<BorderStyle> {
-        if !self.show_border_colors {
+        if !self.show_border_colors || !state.uses_surface(Surface::Border) {
             return None;
         }
 
         if let Some(ref notif_type) = state.notification_type {
             if !state.acknowledged {
-                let base_color = color_manager.get_notification_color(notif_type)?;
+                let base_color = color_manager.resolve_color(notif_type, state.custom_color.as_deref())
+                    .map(|c| color_manager.priority_adjusted(&c, &state.priority))?;
 
-                // Apply animation brightness
-                let brightness = animation_engine.get_brightness(state, tick);
-                let color = color_manager.apply_brightness(&base_color, brightness);
+                // Apply animation brightness (or gradient)
+                let color = self.animated_color(&base_color, notif_type, state, color_manager, animation_engine, frame.into());
 
                 return Some(BorderStyle {
                     color,
-                    style: if state.is_animating {
+                    style: if state.is_animating
+                        || self.static_emphasis(notif_type)
+                        || (color_manager.is_mono() && notif_type.is_urgent())
+                    {
                         BorderLineStyle::Double
                     } else {
                         BorderLineStyle::Single
@@ -249,7 +1805,7 @@ impl Renderer {
                 .map(|t| self.get_notification_icon(t))
                 .unwrap_or("");
 
-            Some(format!("{} {}", icon, message))
+            Some(format!("{} {}", icon, markup::strip(message)))
         } else {
             None
         }
@@ -335,11 +1891,114 @@ impl Renderer {
             ));
         }
 
-        if parts.is_empty() {
-            "No notifications".to_string()
-        } else {
-            parts.join(" ")
-        }
+        if parts.is_empty() {
+            "No notifications".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// Compose lines into the screen buffer and write the frame in one piece
+fn present(screen: &mut ScreenBuffer, rows: usize, cols: usize, lines: &[String]) {
+    screen.begin(rows, cols);
+    screen.write_lines(lines);
+    print!("{}", screen.present());
+}
+
+/// Branch context after an entry, e.g. ` (feat/login)` (shortened to `MAX_BRANCH_LABEL_CHARS`)
+fn branch_suffix(state: &VisualState) -> String {
+    state.branch.as_deref()
+        .map(|branch| format!(" ({})", truncate(branch, MAX_BRANCH_LABEL_CHARS)))
+        .unwrap_or_default()
+}
+
+/// Numbered quick reply options after an entry, e.g. ` [1 yes  2 no]`
+fn reply_suffix(state: &VisualState) -> String {
+    if state.reply_options.is_empty() {
+        return String::new();
+    }
+    format!(" [{}]", quick_reply::hints(&state.reply_options))
+}
+
+/// Truncate a string to a maximum number of characters
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let mut result: String = text.chars().take(max_chars - 1).collect();
+    result.push('\u{2026}'); // Ellipsis
+    result
+}
+
+/// Block-character glyph for a notification type in big mode (three rows)
+fn big_glyph(notification_type: &NotificationType) -> [&'static str; 3] {
+    match notification_type {
+        NotificationType::Success => ["    \u{2588}", "\u{2588}  \u{2588} ", " \u{2588}\u{2588}  "],
+        NotificationType::Error => ["\u{2580}\u{2584} \u{2584}\u{2580}", "  \u{2588}  ", "\u{2584}\u{2580} \u{2580}\u{2584}"],
+        NotificationType::Warning => ["  \u{2588}  ", "  \u{2588}  ", "  \u{2584}  "],
+        NotificationType::Info => ["  \u{2580}  ", "  \u{2588}  ", "  \u{2588}  "],
+        NotificationType::Progress => ["\u{2588}\u{2584}   ", "  \u{2588}\u{2588} ", "\u{2588}\u{2580}   "],
+        NotificationType::Attention => ["\u{2580}\u{2580}\u{2588} ", " \u{2588}\u{2580}  ", " \u{2584}   "],
+    }
+}
+
+/// Notification types from most to least urgent
+const URGENCY_ORDER: [NotificationType; 6] = [
+    NotificationType::Error,
+    NotificationType::Attention,
+    NotificationType::Warning,
+    NotificationType::Progress,
+    NotificationType::Success,
+    NotificationType::Info,
+];
+
+/// Most urgent type among forwarded entries (error first, info last)
+fn most_urgent(entries: &[RemoteEntry]) -> NotificationType {
+    URGENCY_ORDER.into_iter()
+        .find(|notification_type| entries.iter().any(|entry| entry.notification_type == *notification_type))
+        .unwrap_or(NotificationType::Info)
+}
+
+/// Format a progress snapshot for a status entry, e.g. ` 42% 35s eta 1m20s`
+fn format_progress(progress: &ProgressEstimate) -> String {
+    let mut text = format!(" {:.0}% {}", progress.percent, format_duration_ms(progress.elapsed_ms));
+    if let Some(eta_ms) = progress.eta_ms {
+        text.push_str(&format!(" eta {}", format_duration_ms(eta_ms)));
+    }
+    text
+}
+
+/// Format a millisecond duration compactly (e.g. "4m59s", "12s")
+pub fn format_duration_ms(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h{:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format a countdown coarsely, rounding up to whole minutes above one minute (e.g. "34m", "45s")
+fn format_countdown_ms(ms: u64) -> String {
+    let total_secs = ms.div_ceil(1000);
+    if total_secs < 60 {
+        return format!("{}s", total_secs);
+    }
+    let total_minutes = total_secs.div_ceil(60);
+    if total_minutes < 60 {
+        format!("{}m", total_minutes)
+    } else {
+        format!("{}h{:02}m", total_minutes / 60, total_minutes % 60)
     }
 }
 
@@ -429,6 +2088,30 @@ pub struct BorderChars {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::AccessibilityConfig;
+
+    /// Status line for the given pane states, with an empty queue and no errors
+    fn status_line(renderer: &Renderer, pane_states: &BTreeMap<u32, VisualState>, color_manager: &ColorManager) -> String {
+        let view = RenderView {
+            pane_states,
+            queue: &NotificationQueue::default(),
+            color_manager,
+            animation_engine: &AnimationEngine::default(),
+            frame: Frame::default(),
+            suppression: None,
+            error: None,
+            status_message: None,
+            recovery: &RecoveryTracker::default(),
+            channels: &ChannelList::default(),
+            bridge: &ConnectionState::Connected,
+            aggregate: &SessionAggregate::default(),
+            cascade: None,
+            rate: &RateHistory::default(),
+            queue_diff: &QueueDiff::default(),
+            instance: None,
+        };
+        renderer.build_status_line(&view)
+    }
 
     #[test]
     fn test_renderer_creation() {
@@ -463,6 +2146,954 @@ mod tests {
         assert_ne!(single_chars.vertical, double_chars.vertical);
     }
 
+    #[test]
+    fn test_gradient_border_color() {
+        use crate::config::AnimationStyle;
+
+        let mut config = Config::default();
+        config.animation.style = AnimationStyle::Gradient;
+        config.animation.gradient_stops.insert(
+            "error".to_string(),
+            vec!["#ff0000".to_string(), "#ff8000".to_string(), "#ff0000".to_string()],
+        );
+        let renderer = Renderer::new(&config);
+        let color_manager = ColorManager::default();
+        let animation_engine = AnimationEngine::new(&config.animation);
+
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        state.priority = Priority::Critical;
+        animation_engine.start_animation(&mut state, 0, AnimationStyle::Gradient);
+
+        let start = renderer.get_border_style(&state, &color_manager, &animation_engine, 0).unwrap();
+        assert_eq!(start.color, "#ff0000");
+        let half_cycle = animation_engine.gradient_position(&state, 51).unwrap();
+        assert!((half_cycle - 0.5).abs() < 0.01);
+        let mid = renderer.get_border_style(&state, &color_manager, &animation_engine, 51).unwrap();
+        assert_eq!(mid.color, "#ff8000");
+    }
+
+    #[test]
+    fn test_branch_label_follows_entry() {
+        let color_manager = ColorManager::default();
+        let renderer = Renderer::default();
+        let plain = |line: String| crate::wrap::clusters(&line).into_iter()
+            .filter(|(text, _)| !text.starts_with('\u{1b}'))
+            .map(|(text, _)| text)
+            .collect::<String>();
+        let mut pane_states = BTreeMap::new();
+        for (pane_id, branch) in [(3, "feat/login"), (4, "chore/upgrade-every-dependency-at-once")] {
+            let mut state = VisualState::new();
+            state.notification_type = Some(NotificationType::Error);
+            state.branch = Some(branch.to_string());
+            pane_states.insert(pane_id, state);
+        }
+
+        let line = plain(status_line(&renderer, &pane_states, &color_manager));
+        assert!(line.contains(":3 (feat/login)]"), "{}", line);
+        assert!(line.contains(":4 (chore/upgrade-every\u{2026})]"), "{}", line);
+    }
+
+    #[test]
+    fn test_raw_ansi_wraps_segments_and_types() {
+        let mut config = Config::default();
+        let raw = |prefix: &str, suffix: &str| RawAnsi { prefix: prefix.to_string(), suffix: suffix.to_string() };
+        config.raw_ansi.types.insert("error".to_string(), raw("\x1b[4:3m", "\x1b[24m"));
+        config.raw_ansi.segments.insert("icon".to_string(), raw("\x1b[5m", "\x1b[25m"));
+        let renderer = Renderer::new(&config);
+        let mut color_manager = ColorManager::default();
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        pane_states.insert(3, state);
+
+        let line = status_line(&renderer, &pane_states, &color_manager);
+        assert!(line.contains(&format!("\x1b[5m{}\x1b[25m", renderer.icon(Icon::Bell))), "{:?}", line);
+        // The prefix is applied again after the entry's own reset
+        assert!(line.contains("\x1b[4:3m\x1b["), "{:?}", line);
+        assert!(line.contains(":3]\x1b[0m\x1b[4:3m\x1b[24m"), "{:?}", line);
+
+        color_manager.set_no_color(true);
+        let line = status_line(&Renderer::new(&config), &pane_states, &color_manager);
+        assert!(!line.contains("\x1b[4:3m") && !line.contains("\x1b[5m"), "{:?}", line);
+    }
+
+    #[test]
+    fn test_entry_hotkeys_number_entries_as_drawn() {
+        let color_manager = ColorManager::default();
+        let renderer = Renderer::new(&Config { entry_hotkeys: true, ..Config::default() });
+        let plain = |line: String| crate::wrap::clusters(&line).into_iter()
+            .filter(|(text, _)| !text.starts_with('\u{1b}'))
+            .map(|(text, _)| text)
+            .collect::<String>();
+        let mut pane_states = BTreeMap::new();
+        for (pane_id, notification_type) in [(3, NotificationType::Info), (7, NotificationType::Error)] {
+            let mut state = VisualState::new();
+            state.notification_type = Some(notification_type);
+            pane_states.insert(pane_id, state);
+        }
+
+        // Urgency order puts the error first, so it is number 1
+        let line = plain(status_line(&renderer, &pane_states, &color_manager));
+        assert!(line.contains("1[\u{2718}"), "{}", line);
+        assert!(line.contains("2[\u{2139}"), "{}", line);
+        assert_eq!(renderer.entry_pane(1), Some(7));
+        assert_eq!(renderer.entry_pane(2), Some(3));
+        assert_eq!(renderer.entry_pane(3), None);
+
+        // Without the option there are no labels, but the layout is still kept
+        assert!(plain(status_line(&Renderer::default(), &pane_states, &color_manager)).contains(" [\u{2718}"));
+        pane_states.clear();
+        status_line(&renderer, &pane_states, &color_manager);
+        assert_eq!(renderer.entry_pane(1), None);
+    }
+
+    #[test]
+    fn test_entry_chips_replace_brackets_with_a_tint() {
+        let mut color_manager = ColorManager::default();
+        let renderer = Renderer::new(&Config { entry_chips: true, ..Config::default() });
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        pane_states.insert(3, state);
+
+        let chip = color_manager.chip_background(&color_manager.priority_adjusted("#ef4444", &Priority::Normal));
+        let line = status_line(&renderer, &pane_states, &color_manager);
+        assert!(line.contains(&color_manager.bg_escape(&chip)), "{:?}", line);
+        assert!(line.contains(" \u{2718}##:3 "), "{:?}", line);
+        assert!(!line.contains("[\u{2718}"), "{:?}", line);
+
+        // Without color there is no tint, so the brackets stay
+        color_manager.set_no_color(true);
+        assert!(status_line(&renderer, &pane_states, &color_manager).contains("[\u{2718}##:3]"));
+    }
+
+    #[test]
+    fn test_deadline_counts_down_then_flashes_overdue() {
+        let color_manager = ColorManager::default();
+        let renderer = Renderer::default();
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        state.deadline_at_ms = Some(540_000);
+        pane_states.insert(3, state.clone());
+
+        let line = status_line(&renderer, &pane_states, &color_manager);
+        assert!(line.contains("[\u{2718}##:3 \u{231B}9m]"), "{:?}", line);
+
+        // Overdue: the time past, another pattern, and reverse video every other second
+        state.overdue = true;
+        assert_eq!(renderer.deadline_suffix(&state, Frame { tick: 0, now_ms: 660_000 }), " \u{231B}-2m");
+        assert!(!renderer.overdue_flash(&state, Frame { tick: 0, now_ms: 661_000 }));
+        pane_states.insert(3, state);
+        let line = status_line(&renderer, &pane_states, &color_manager);
+        assert!(line.contains("\x1b[7m[\u{2718}><:3"), "{:?}", line);
+    }
+
+    #[test]
+    fn test_clear_cascade_line_blanks_faded_entries() {
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+        let renderer = Renderer::default();
+        let cascade = ClearCascade::new(vec![(3, NotificationType::Error), (7, NotificationType::Warning)], 0);
+        let pane_states = BTreeMap::new();
+        let mut view = RenderView {
+            pane_states: &pane_states,
+            queue: &NotificationQueue::default(),
+            color_manager: &color_manager,
+            animation_engine: &AnimationEngine::default(),
+            frame: Frame::default(),
+            suppression: None,
+            error: None,
+            status_message: None,
+            recovery: &RecoveryTracker::default(),
+            channels: &ChannelList::default(),
+            bridge: &ConnectionState::Connected,
+            aggregate: &SessionAggregate::default(),
+            cascade: Some(&cascade),
+            rate: &RateHistory::default(),
+            queue_diff: &QueueDiff::default(),
+            instance: None,
+        };
+        assert_eq!(renderer.build_clear_cascade_line(80, &cascade, &view), "Cleared 2 [\u{2718}:3] [\u{26A0}:7]");
+        assert_eq!(renderer.build_clear_cascade_line(5, &cascade, &view), "Clea\u{2026}");
+
+        // The first entry is gone halfway through, leaving its place blank
+        view.frame.now_ms = 150;
+        assert_eq!(renderer.build_clear_cascade_line(80, &cascade, &view), "Cleared 2       [\u{26A0}:7]");
+    }
+
+    #[test]
+    fn test_detail_view_shows_ttl_controls() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut notification = crate::notification::Notification::error("Build failed").for_pane(3).with_ttl(600_000);
+        notification.advance_ttl(60_000);
+
+        let lines = renderer.build_detail_lines(10, 80, Some(&notification), &Pager::default(), 300_000, &color_manager);
+        assert_eq!(lines[0], "Notification - e extend 5m, k keep, x expire now, i to close");
+        assert!(lines[1].contains("\u{2718} error pane 3: Build failed"));
+        assert!(lines[2].starts_with(&format!("id {}, source ", notification.id)));
+        assert_eq!(lines[3], "expires in 9m00s, then discard");
+
+        notification.make_sticky();
+        assert_eq!(renderer.build_detail_lines(10, 80, Some(&notification), &Pager::default(), 300_000, &color_manager)[3], "never expires");
+        assert_eq!(renderer.build_detail_lines(10, 80, None, &Pager::default(), 300_000, &color_manager)[1], "No notification to show");
+    }
+
+    #[test]
+    fn test_detail_view_pages_long_messages() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let paragraphs: Vec<String> = (1..=9).map(|n| format!("Paragraph {} of the explanation", n)).collect();
+        let notification = crate::notification::Notification::info(&paragraphs.join("\n")).for_pane(3);
+        let body = renderer.detail_body(&notification, 40);
+        assert_eq!(body.len(), 9);
+
+        // 8 rows: the header and 4 message lines per page
+        let mut pager = Pager::default();
+        let lines = renderer.build_detail_lines(8, 40, Some(&notification), &pager, 300_000, &color_manager);
+        assert!(lines[0].starts_with("Notification 1/3 - PgUp/PgDn page"));
+        assert!(lines[1].contains("info pane 3") && !lines[1].contains("Paragraph"));
+        assert_eq!(&lines[4..], &body[..4]);
+
+        assert!(pager.last_page(Pager::page_count(body.len(), Renderer::detail_page_rows(8))));
+        let lines = renderer.build_detail_lines(8, 40, Some(&notification), &pager, 300_000, &color_manager);
+        assert!(lines[0].starts_with("Notification 3/3"));
+        assert_eq!(&lines[4..], &body[8..]);
+    }
+
+    #[test]
+    fn test_legend_lists_live_state() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let plain = |line: &String| crate::wrap::clusters(line).into_iter()
+            .filter(|(text, _)| !text.starts_with('\u{1b}'))
+            .map(|(text, _)| text)
+            .collect::<String>();
+        let context = LegendContext {
+            keys: crate::legend::key_bindings(&Config::default()),
+            sources: vec!["ci".to_string(), "claude".to_string()],
+            muted_sources: ["ci".to_string()].into_iter().collect(),
+            muted_panes: vec![4],
+        };
+        let mut channels = ChannelList::default();
+        channels.record("deploys");
+        channels.set_visible("deploys", false);
+
+        let lines = renderer.build_legend_lines(20, 200, &context, &channels, None, &color_manager);
+        assert_eq!(lines[0], "Legend - ? to close");
+        assert!(plain(&lines[1]).starts_with("Types: \u{2718}## error  "));
+        assert!(lines.iter().any(|line| line.starts_with("Keys: q queue, l history")));
+        assert!(plain(&lines[lines.len() - 3]).starts_with("Channels: deploys (hidden)"));
+        assert!(plain(&lines[lines.len() - 2]).starts_with("Sources: claude, ci (muted)"));
+        assert_eq!(lines[lines.len() - 1], "Now: DND off, muted panes: 4");
+
+        let status = SuppressionStatus { kind: crate::suppression::SuppressionKind::Dnd, remaining_ms: Some(90_000), held: 2 };
+        let lines = renderer.build_legend_lines(20, 200, &LegendContext::default(), &ChannelList::default(), Some(&status), &color_manager);
+        assert_eq!(lines[lines.len() - 1], "Now: DND on for 2m, 2 held, muted panes: none");
+        assert!(plain(&lines[lines.len() - 2]).starts_with("Sources: none"));
+    }
+
+    #[test]
+    fn test_sessions_view_groups_forwarded_entries() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut aggregate = SessionAggregate::default();
+        aggregate.record("api", &crate::notification::Notification::success("Deployed"), 0);
+        aggregate.record("api", &crate::notification::Notification::error("Build failed").for_pane(3), 30_000);
+
+        let lines = renderer.build_session_lines(10, 80, &aggregate, &color_manager, 90_000);
+        assert_eq!(lines[0], "Sessions - j to close");
+        assert!(lines[1].contains("api (2) - zellij attach api"));
+        assert_eq!(lines[2], "  \u{2718} error pane 3, 1m ago: Build failed");
+        assert_eq!(lines.len(), 4);
+        assert_eq!(most_urgent(aggregate.sessions().next().unwrap().1), NotificationType::Error);
+
+        let empty = renderer.build_session_lines(10, 80, &SessionAggregate::default(), &color_manager, 0);
+        assert_eq!(empty[1], "No forwarded notifications");
+    }
+
+    #[test]
+    fn test_queue_overlay_lanes() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut queue = NotificationQueue::new(100, 300_000);
+        queue.enqueue(crate::notification::Notification::error("Build failed").for_pane(3));
+
+        let lines = renderer.build_queue_overlay_lines(20, 80, &queue, &QueueDiff::default(), 0, &color_manager);
+        // Header + 4 lane headers + 1 entry
+        assert_eq!(lines.len(), 6);
+        assert!(lines[1].contains("CRITICAL (1, 0 dropped)"));
+        assert!(lines[2].contains("pane 3"));
+
+        let bounded = renderer.build_queue_overlay_lines(3, 80, &queue, &QueueDiff::default(), 0, &color_manager);
+        assert_eq!(bounded.len(), 3);
+    }
+
+    #[test]
+    fn test_queue_overlay_highlights_changes() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut queue = NotificationQueue::new(100, 300_000);
+        let mut diff = QueueDiff::default();
+        diff.refresh(&queue, 0);
+        let mut failed = crate::notification::Notification::error("Build failed").for_pane(3);
+        failed.id = "failed".to_string();
+        queue.enqueue(failed);
+        diff.refresh(&queue, 100);
+
+        let lines = renderer.build_queue_overlay_lines(20, 80, &queue, &diff, 100, &color_manager);
+        let green = color_manager.fg_escape(&color_manager.get_notification_color(&NotificationType::Success).unwrap());
+        assert!(lines[2].starts_with(&format!("{}+ ", green)));
+
+        // Gone from the queue: struck through in its lane
+        queue.remove_by_id("failed");
+        diff.refresh(&queue, 200);
+        let lines = renderer.build_queue_overlay_lines(20, 80, &queue, &diff, 200, &color_manager);
+        assert!(lines[1].contains("CRITICAL (0, 0 dropped)"));
+        assert!(lines[2].contains("\x1b[9m- "));
+        assert!(lines[2].contains("pane 3 | Build failed"));
+    }
+
+    #[test]
+    fn test_status_entries_ordered_by_urgency() {
+        let state = |notification_type, priority, timestamp| VisualState {
+            notification_type: Some(notification_type),
+            priority,
+            notification_timestamp: timestamp,
+            ..VisualState::default()
+        };
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(1, state(NotificationType::Success, Priority::Normal, 100));
+        pane_states.insert(2, state(NotificationType::Error, Priority::High, 50));
+        pane_states.insert(3, state(NotificationType::Attention, Priority::Critical, 10));
+        pane_states.insert(4, state(NotificationType::Error, Priority::High, 80));
+        pane_states.insert(5, state(NotificationType::Error, Priority::High, 80));
+
+        let renderer = Renderer::default();
+        let order: Vec<u32> = renderer.ordered_entries(&pane_states).iter().map(|(id, _)| **id).collect();
+        assert_eq!(order, vec![3, 4, 5, 2, 1]);
+
+        let renderer = Renderer::new(&Config { status_order: StatusOrder::Pane, ..Config::default() });
+        let order: Vec<u32> = renderer.ordered_entries(&pane_states).iter().map(|(id, _)| **id).collect();
+        assert_eq!(order, vec![1, 2, 3, 4, 5]);
+
+        // Pinned entries come first in either order
+        pane_states.get_mut(&1).unwrap().pinned = true;
+        let order: Vec<u32> = renderer.ordered_entries(&pane_states).iter().map(|(id, _)| **id).collect();
+        assert_eq!(order, vec![1, 2, 3, 4, 5]);
+        let order: Vec<u32> = Renderer::default().ordered_entries(&pane_states).iter().map(|(id, _)| **id).collect();
+        assert_eq!(order, vec![1, 3, 4, 5, 2]);
+
+        // Then Claude pane entries
+        pane_states.get_mut(&4).unwrap().claude_pane = true;
+        let order: Vec<u32> = renderer.ordered_entries(&pane_states).iter().map(|(id, _)| **id).collect();
+        assert_eq!(order, vec![1, 4, 2, 3, 5]);
+        let order: Vec<u32> = Renderer::default().ordered_entries(&pane_states).iter().map(|(id, _)| **id).collect();
+        assert_eq!(order, vec![1, 4, 3, 5, 2]);
+    }
+
+    #[test]
+    fn test_collapsed_pane_falls_back_to_badge() {
+        let renderer = Renderer::new(&Config {
+            show_tab_badges: false,
+            ..Config::default()
+        });
+        let color_manager = ColorManager::default();
+        let animation_engine = AnimationEngine::default();
+
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        assert!(renderer.get_border_style(&state, &color_manager, &animation_engine, 0).is_some());
+        assert!(renderer.render_pane_badge(&state, &color_manager, Frame::default()).is_none());
+
+        state.surfaces = vec![Surface::StatusEntry, Surface::TabBadge];
+        assert!(renderer.get_border_style(&state, &color_manager, &animation_engine, 0).is_none());
+        assert!(renderer.render_pane_badge(&state, &color_manager, Frame::default()).is_some());
+    }
+
+    #[test]
+    fn test_animation_target_picks_modulated_attribute() {
+        use crate::config::AnimationStyle;
+
+        let color_manager = ColorManager::default();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        state.notification_message = Some("Build failed".to_string());
+        AnimationEngine::default().start_animation(&mut state, 0, AnimationStyle::Flash);
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(1, state);
+
+        let line = |animation_target, pane_states: &BTreeMap<u32, VisualState>| {
+            status_line(&Renderer { animation_target, ..Renderer::default() }, pane_states, &color_manager)
+        };
+        let foreground = line(AnimationTarget::Foreground, &pane_states);
+        assert!(!foreground.contains("\x1b[48;") && !foreground.contains(REVERSE_ESCAPE));
+        assert!(line(AnimationTarget::Background, &pane_states).contains("\x1b[48;"));
+        assert!(line(AnimationTarget::Both, &pane_states).contains("\x1b[48;"));
+        // Flash starts bright, so the inverse flash is on
+        assert!(line(AnimationTarget::Inverse, &pane_states).contains(REVERSE_ESCAPE));
+
+        // Entries that are not animating keep plain text color
+        pane_states.get_mut(&1).unwrap().is_animating = false;
+        assert!(!line(AnimationTarget::Both, &pane_states).contains("\x1b[48;"));
+    }
+
+    #[test]
+    fn test_progress_icon_spins_until_complete() {
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+        let renderer = Renderer::default();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Progress);
+
+        let badge = |renderer: &Renderer, state: &VisualState, tick| {
+            renderer.render_pane_badge(state, &color_manager, Frame::from(tick)).unwrap()
+        };
+        assert!(badge(&renderer, &state, 0).contains("\u{280B}"));
+        assert!(badge(&renderer, &state, 1).contains("\u{2819}"));
+        assert!(badge(&renderer, &state, 10).contains("\u{280B}"));
+
+        // Completed progress and reduced motion keep the static icon
+        let reduced = Renderer::new(&Config {
+            accessibility: AccessibilityConfig { reduced_motion: true, ..AccessibilityConfig::default() },
+            ..Config::default()
+        });
+        assert!(badge(&reduced, &state, 1).contains("\u{21BB}"));
+        state.progress = Some(ProgressEstimate { percent: 100.0, elapsed_ms: 0, eta_ms: None });
+        assert!(badge(&renderer, &state, 1).contains("\u{21BB}"));
+    }
+
+    #[test]
+    fn test_ascii_fallback_set() {
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+        let renderer = Renderer::new(&Config { ascii_only: AsciiMode::On, ..Config::default() });
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Progress);
+
+        // The spinner switches to line frames
+        let badge = renderer.render_pane_badge(&state, &color_manager, Frame::from(1)).unwrap();
+        assert!(badge.contains('/') && badge.is_ascii());
+        // Anything drawn is reduced to ASCII; the hint can also come at runtime
+        assert_eq!(renderer.glyphs("\u{2718}3 \u{2502} \u{1F514} build\u{2026}".to_string()), "x3 | [N] build...");
+        assert!(Renderer::default().with_ascii(true).glyphs("\u{2550}\u{2588}".to_string()).is_ascii());
+    }
+
+    #[test]
+    fn test_icon_width_policy_substitutes_glyphs() {
+        let renderer = Renderer::new(&Config { icon_width_policy: IconWidthPolicy::Single, ..Config::default() });
+        assert_eq!(renderer.get_notification_icon(&NotificationType::Warning), "!");
+        assert_eq!(renderer.get_notification_icon(&NotificationType::Info), "i");
+        assert_eq!(renderer.get_notification_icon(&NotificationType::Error), "\u{2718}");
+        assert_eq!(Renderer::default().get_notification_icon(&NotificationType::Warning), "\u{26A0}");
+    }
+
+    #[test]
+    fn test_status_shows_muted_panes() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.muted = true;
+        pane_states.insert(4, state);
+
+        let content = status_line(&renderer, &pane_states, &color_manager);
+        assert!(content.contains("No notifications"));
+        assert!(content.contains("\u{1F507}:4"));
+    }
+
+    #[test]
+    fn test_hidden_channel_entries_are_counted() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        state.channel = Some("cron".to_string());
+        state.hidden = true;
+        pane_states.insert(6, state);
+
+        let content = status_line(&renderer, &pane_states, &color_manager);
+        assert!(!content.contains(":6"));
+        assert!(content.contains("(1 hidden)"));
+
+        let mut channels = ChannelList::default();
+        channels.record("cron");
+        channels.toggle("cron");
+        let lines = renderer.build_channel_lines(5, 80, &channels);
+        assert_eq!(lines[1], "1 [ ] cron (1)");
+
+        let mut other = VisualState::new();
+        other.notification_type = Some(NotificationType::Success);
+        other.other_project = true;
+        pane_states.insert(7, other);
+        let content = status_line(&renderer, &pane_states, &color_manager);
+        assert!(!content.contains(":7"));
+        assert!(content.contains("(1 hidden)"));
+        assert!(content.contains("(other projects: 1)"));
+    }
+
+    #[test]
+    fn test_static_entries_reuse_their_drawn_form() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let animation_engine = AnimationEngine::default();
+
+        let mut pane_states = BTreeMap::new();
+        for pane_id in 1..=3 {
+            let mut state = VisualState::new();
+            state.notification_type = Some(NotificationType::Success);
+            state.notification_id = Some(format!("n{}", pane_id));
+            pane_states.insert(pane_id, state);
+        }
+        let mut animating = VisualState::new();
+        animating.notification_type = Some(NotificationType::Error);
+        animating.notification_id = Some("n4".to_string());
+        animating.is_animating = true;
+        pane_states.insert(4, animating);
+
+        for tick in 0..10 {
+            let view = RenderView {
+                pane_states: &pane_states,
+                queue: &NotificationQueue::default(),
+                color_manager: &color_manager,
+                animation_engine: &animation_engine,
+                frame: Frame::from(tick),
+                suppression: None,
+                error: None,
+                status_message: None,
+                recovery: &RecoveryTracker::default(),
+                channels: &ChannelList::default(),
+                bridge: &ConnectionState::Connected,
+                aggregate: &SessionAggregate::default(),
+                cascade: None,
+                rate: &RateHistory::default(),
+                queue_diff: &QueueDiff::default(),
+                instance: None,
+            };
+            // Cached entries draw exactly what a fresh renderer draws
+            assert_eq!(renderer.build_status_line(&view), Renderer::default().build_status_line(&view));
+        }
+        // The static entries are only formatted in the first frame
+        assert_eq!(renderer.entry_cache().line(), "status entries: 27 reused, 13 formatted");
+    }
+
+    #[test]
+    fn test_surface_toggles_hide_entries_and_show_indicator() {
+        let mut toggles = SurfaceToggles::default();
+        toggles.set(DisplaySurface::StatusEntries, Some(false));
+        toggles.set(DisplaySurface::Border, Some(false));
+        let renderer = Renderer::default().with_surface_toggles(&toggles);
+        let color_manager = ColorManager::default();
+        let animation_engine = AnimationEngine::default();
+
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        pane_states.insert(2, state);
+
+        let content = status_line(&renderer, &pane_states, &color_manager);
+        assert!(!content.contains(":2"));
+        assert!(content.contains("1 active"));
+        assert!(content.contains("\u{2298}border,status"));
+        assert!(renderer.get_border_style(&pane_states[&2], &color_manager, &animation_engine, 0).is_none());
+    }
+
+    #[test]
+    fn test_focus_ring_frames_critical_notifications() {
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(2, VisualState {
+            notification_type: Some(NotificationType::Warning),
+            ..VisualState::default()
+        });
+        let ring = |renderer: &Renderer, pane_states: &BTreeMap<u32, VisualState>, now_ms, footer| {
+            let view = RenderView {
+                pane_states,
+                queue: &NotificationQueue::default(),
+                color_manager: &color_manager,
+                animation_engine: &AnimationEngine::default(),
+                frame: Frame { tick: 0, now_ms },
+                suppression: None,
+                error: None,
+                status_message: None,
+                recovery: &RecoveryTracker::default(),
+                channels: &ChannelList::default(),
+                bridge: &ConnectionState::Connected,
+                aggregate: &SessionAggregate::default(),
+                cascade: None,
+                rate: &RateHistory::default(),
+                queue_diff: &QueueDiff::default(),
+                instance: None,
+            };
+            renderer.build_focus_ring_lines(4, 30, &view, footer)
+        };
+        let renderer = Renderer::default();
+        assert!(ring(&renderer, &pane_states, 0, None).is_none());
+
+        pane_states.insert(5, VisualState {
+            notification_type: Some(NotificationType::Error),
+            priority: Priority::Critical,
+            ..VisualState::default()
+        });
+        assert!(renderer.focus_ring_active(&pane_states));
+        let lines = ring(&renderer, &pane_states, 0, Some("Announced")).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| display_width(line) == 30));
+        assert!(lines[0].contains("\u{2554} ERROR pane 5 \u{2550}"));
+        assert!(lines[1].contains('\u{2551}') && lines[2].contains("Announced"));
+        assert!(lines[3].contains('\u{255A}'));
+        // The line weight alternates, unless motion is reduced
+        let lines = ring(&renderer, &pane_states, 500, None).unwrap();
+        assert!(lines[0].contains('\u{250F}'));
+        let calm = Renderer { reduced_motion: true, ..Renderer::default() };
+        assert!(ring(&calm, &pane_states, 500, None).unwrap()[0].contains('\u{2554}'));
+
+        let off = Renderer { show_focus_ring: false, ..Renderer::default() };
+        assert!(!off.focus_ring_active(&pane_states));
+    }
+
+    #[test]
+    fn test_wide_layout_aligns_entries_in_cells() {
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+        let state = |notification_type, message: &str, timestamp| VisualState {
+            notification_type: Some(notification_type),
+            notification_message: Some(message.to_string()),
+            notification_timestamp: timestamp,
+            ..VisualState::default()
+        };
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(3, state(NotificationType::Error, "Build failed: 3 errors in crates/core", 0));
+        pane_states.insert(7, state(NotificationType::Warning, "Lint warnings", 30_000));
+        pane_states.insert(12, state(NotificationType::Success, "Tests passed", 90_000));
+        let view = RenderView {
+            pane_states: &pane_states,
+            queue: &NotificationQueue::default(),
+            color_manager: &color_manager,
+            animation_engine: &AnimationEngine::default(),
+            frame: Frame { tick: 0, now_ms: 120_000 },
+            suppression: None,
+            error: None,
+            status_message: None,
+            recovery: &RecoveryTracker::default(),
+            channels: &ChannelList::default(),
+            bridge: &ConnectionState::Connected,
+            aggregate: &SessionAggregate::default(),
+            cascade: None,
+            rate: &RateHistory::default(),
+            queue_diff: &QueueDiff::default(),
+            instance: None,
+        };
+        let plain = |line: &String| crate::wrap::clusters(line).into_iter()
+            .filter(|(text, _)| !text.starts_with('\u{1b}'))
+            .map(|(text, _)| text)
+            .collect::<String>();
+
+        let renderer = Renderer::new(&Config { wide_layout_min_cols: 160, ..Config::default() });
+        assert!(renderer.build_wide_status_lines(2, 159, &view).is_none());
+
+        // Segments on the first row, the entries side by side below with aligned fields
+        let lines: Vec<String> = renderer.build_wide_status_lines(2, 160, &view).unwrap().iter().map(plain).collect();
+        assert_eq!(lines[0], "\u{1F514}");
+        let cells: Vec<&str> = lines[1].split(" \u{2502} ").collect();
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0].trim_end(), "\u{2718}  3  2m Build failed: 3 errors in crates/core");
+        assert_eq!(cells[1].trim_end(), "\u{26A0}  7  1m Lint warnings");
+        assert_eq!(cells[2].trim_end(), "\u{2714} 12 30s Tests passed");
+        assert!(cells.iter().take(2).all(|cell| display_width(cell) == 51));
+
+        // One row: the cells follow the segments
+        let lines = renderer.build_wide_status_lines(1, 160, &view).unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(plain(&lines[0]).starts_with("\u{1F514} \u{2718}  3  2m Build"));
+
+        // Entries beyond the rows are counted in the last cell
+        let mut crowded = pane_states.clone();
+        crowded.insert(20, state(NotificationType::Info, "Deploy queued", 0));
+        crowded.insert(21, state(NotificationType::Info, "Cache warmed", 0));
+        let view = RenderView { pane_states: &crowded, ..view };
+        let lines: Vec<String> = renderer.build_wide_status_lines(2, 160, &view).unwrap().iter().map(plain).collect();
+        assert!(lines[1].trim_end().ends_with("+2 more"));
+
+        let renderer = Renderer::new(&Config { wide_layout_min_cols: 0, ..Config::default() });
+        assert!(renderer.build_wide_status_lines(2, 400, &view).is_none());
+    }
+
+    #[test]
+    fn test_minimal_layout_counts_active_entries() {
+        let color_manager = ColorManager::default();
+        let mut pane_states = BTreeMap::new();
+        for (pane_id, notification_type, acknowledged) in [
+            (1, NotificationType::Warning, false),
+            (2, NotificationType::Error, false),
+            (3, NotificationType::Error, true),
+        ] {
+            pane_states.insert(pane_id, VisualState {
+                notification_type: Some(notification_type),
+                notification_message: Some("Build".to_string()),
+                acknowledged,
+                ..VisualState::default()
+            });
+        }
+        let view = RenderView {
+            pane_states: &pane_states,
+            queue: &NotificationQueue::default(),
+            color_manager: &color_manager,
+            animation_engine: &AnimationEngine::default(),
+            frame: Frame { tick: 0, now_ms: 0 },
+            suppression: None,
+            error: None,
+            status_message: None,
+            recovery: &RecoveryTracker::default(),
+            channels: &ChannelList::default(),
+            bridge: &ConnectionState::Connected,
+            aggregate: &SessionAggregate::default(),
+            cascade: None,
+            rate: &RateHistory::default(),
+            queue_diff: &QueueDiff::default(),
+            instance: None,
+        };
+        let renderer = Renderer::default();
+
+        // Icon and count in the color of the most urgent entry
+        let line = renderer.build_minimal_status_line(&view);
+        let error_color = color_manager.get_notification_color(&NotificationType::Error).unwrap();
+        assert_eq!(line, format!("\u{1F514}{}2{}", color_manager.fg_escape(&error_color), color_manager.reset_escape()));
+
+        // Breakpoints: the table only from the wide width
+        assert!(renderer.build_wide_status_lines(1, DEFAULT_WIDE_LAYOUT_MIN_COLS - 1, &view).is_none());
+        assert!(renderer.build_wide_status_lines(1, DEFAULT_WIDE_LAYOUT_MIN_COLS, &view).is_some());
+
+        let empty = BTreeMap::new();
+        let view = RenderView { pane_states: &empty, ..view };
+        assert_eq!(renderer.build_minimal_status_line(&view), "\u{1F514}");
+    }
+
+    #[test]
+    fn test_configured_segments_in_order() {
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        state.notification_timestamp = 0;
+        pane_states.insert(2, state);
+
+        let renderer = Renderer::new(&Config {
+            status_segments: vec!["health".to_string(), "counts".to_string(), "bogus".to_string(), "clock".to_string()],
+            ..Config::default()
+        });
+        assert_eq!(renderer.segments.len(), 3);
+        let line = status_line(&renderer, &pane_states, &color_manager);
+        assert!(line.starts_with("\u{21C4}"));
+        assert!(line.contains("\u{2718}1\x1b[0m \u{23F2} 0s"));
+        assert!(!line.contains(":2") && !line.contains("\u{1F514}"));
+
+        // The default layout starts with the icon and entries
+        let line = status_line(&Renderer::default(), &pane_states, &color_manager);
+        assert!(line.starts_with("\u{1F514} "));
+        assert!(line.contains(":2"));
+    }
+
+    #[test]
+    fn test_no_color_uses_attributes_and_patterns() {
+        let renderer = Renderer::new(&Config {
+            accessibility: AccessibilityConfig { use_patterns: false, ..AccessibilityConfig::default() },
+            ..Config::default()
+        });
+        let mut color_manager = ColorManager::default();
+        color_manager.set_no_color(true);
+
+        let mut pane_states = BTreeMap::new();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Error);
+        pane_states.insert(2, state);
+
+        let content = status_line(&renderer, &pane_states, &color_manager);
+        assert!(!content.contains("\x1b[38;"));
+        assert!(content.contains(BOLD_REVERSE_ESCAPE));
+        assert!(content.contains(renderer.get_pattern_suffix(&NotificationType::Error)));
+    }
+
+    #[test]
+    fn test_big_mode_lines() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+
+        let mut pane_states = BTreeMap::new();
+        for (pane_id, notification_type) in [(1, NotificationType::Success), (2, NotificationType::Error)] {
+            let mut state = VisualState::new();
+            state.notification_type = Some(notification_type);
+            state.notification_message = Some(format!("message {}", pane_id));
+            pane_states.insert(pane_id, state);
+        }
+
+        // Five rows fit one entry; the most urgent comes first
+        let lines = renderer.build_big_status_lines(5, 40, &pane_states, &color_manager);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "\u{2550}".repeat(40));
+        assert!(lines[1].contains("ERROR \u{00B7} pane 2"));
+        assert!(lines[2].contains("message 2"));
+        assert!(lines[3].contains("+1 more"));
+
+        let lines = renderer.build_big_status_lines(9, 40, &pane_states, &color_manager);
+        assert_eq!(lines.len(), 9);
+        assert!(lines[6].contains("message 1"));
+        assert!(lines.iter().all(|line| !line.contains("more")));
+    }
+
+    #[test]
+    fn test_big_mode_draws_message_markup() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut state = VisualState::new();
+        state.notification_type = Some(NotificationType::Attention);
+        state.notification_message = Some("**Permission needed**\nAllow `cargo test`?".to_string());
+        let pane_states = BTreeMap::from([(3, state)]);
+
+        let lines = renderer.build_big_status_lines(5, 60, &pane_states, &color_manager);
+        assert!(lines[2].ends_with("\x1b[1mPermission needed\x1b[22m"));
+        assert!(lines[3].ends_with("Allow \x1b[7mcargo test\x1b[27m?"));
+        assert!(lines.iter().all(|line| !line.contains("**") && !line.contains('`')));
+    }
+
+    #[test]
+    fn test_clear_confirm_prompt() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+
+        assert!(renderer.build_clear_confirm_line(80, 7, &color_manager).contains("Clear 7 notifications? y/n"));
+        assert!(renderer.build_clear_confirm_line(80, 1, &color_manager).contains("Clear 1 notification? y/n"));
+    }
+
+    #[test]
+    fn test_format_duration_ms() {
+        assert_eq!(format_duration_ms(12_000), "12s");
+        assert_eq!(format_duration_ms(299_000), "4m59s");
+        assert_eq!(format_duration_ms(3_660_000), "1h01m");
+        assert_eq!(truncate("abcdef", 4), "abc\u{2026}");
+    }
+
+    #[test]
+    fn test_format_progress() {
+        let progress = ProgressEstimate { percent: 42.4, elapsed_ms: 35_000, eta_ms: Some(80_000) };
+        assert_eq!(format_progress(&progress), " 42% 35s eta 1m20s");
+        assert_eq!(format_progress(&ProgressEstimate { eta_ms: None, ..progress }), " 42% 35s");
+    }
+
+    #[test]
+    fn test_history_lines_group_and_truncate() {
+        use crate::history::NotificationHistory;
+        use crate::notification::Notification;
+        use crate::query::{arrange, Grouping, SortMode};
+
+        let renderer = Renderer::default();
+        let mut history = NotificationHistory::default();
+        for i in 0..5 {
+            let mut notification = Notification::error(&format!("Failure {}", i));
+            notification.source = if i % 2 == 0 { "ci".to_string() } else { "tests".to_string() };
+            history.record(&notification, i * 1000);
+        }
+        let entries: Vec<_> = history.newest_first().collect();
+        let groups = arrange(&entries, SortMode::Time, Grouping::Source, 5000);
+
+        let lines = renderer.build_history_lines(20, 80, "History", &groups, 5000);
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[1], "ci (3)");
+        assert!(lines[2].starts_with("      1s ago  error") && lines[2].ends_with("ci: Failure 4"));
+        assert_eq!(lines[5], "tests (2)");
+
+        let lines = renderer.build_history_lines(4, 80, "History", &groups, 5000);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[3], "... 5 more lines");
+        assert_eq!(renderer.build_history_lines(4, 80, "History", &[], 0)[1], "No notifications yet");
+    }
+
+    #[test]
+    fn test_ack_chord_prompt_counts() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(1, VisualState { notification_type: Some(NotificationType::Error), ..VisualState::default() });
+        pane_states.insert(2, VisualState { notification_type: Some(NotificationType::Error), ..VisualState::default() });
+
+        let line = renderer.build_ack_chord_line(200, &pane_states, &color_manager);
+        assert!(line.contains("Acknowledge: e error(2) s success(0)"));
+    }
+
+    #[test]
+    fn test_error_banner_and_diagnostics() {
+        use crate::diagnostics::LogLevel;
+
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+        let mut recovery = RecoveryTracker::new(3, 1000);
+        recovery.begin(0);
+        recovery.attempt_due(1000);
+
+        let banner = renderer.build_error_banner("Permissions denied", &recovery, &color_manager);
+        assert!(banner.contains("Permissions denied (retry 1/3)"));
+        assert!(banner.contains("d details"));
+
+        let logs: Vec<LogEntry> = (0..10)
+            .map(|i| LogEntry { at_ms: i * 1000, level: LogLevel::Warn, message: format!("entry {}", i) })
+            .collect();
+        let lines = renderer.build_diagnostics_lines(5, 80, Some("Permissions denied"), &recovery, None, None, &color_manager, &logs);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "Error: Permissions denied");
+        assert!(lines[4].ends_with("WARN entry 9"));
+
+        let noisy = "Noisy source: ci (32/min) - n mutes it";
+        let lines = renderer.build_diagnostics_lines(5, 80, None, &recovery, Some(noisy), None, &color_manager, &logs);
+        assert_eq!(lines[3], noisy);
+        assert!(lines[4].ends_with("WARN entry 9"));
+
+        let update = "Update available: 1.2.0 (running 1.0.0) - Focus mode";
+        let lines = renderer.build_diagnostics_lines(6, 80, None, &recovery, Some(noisy), Some(update), &color_manager, &logs);
+        assert!(lines[4].contains(update) && lines[4].starts_with("\x1b["));
+        assert!(lines[5].ends_with("WARN entry 9"));
+    }
+
+    #[test]
+    fn test_health_lines() {
+        use crate::health::HealthCheck;
+
+        let renderer = Renderer::default();
+        let report = HealthReport {
+            checks: vec![HealthCheck { name: "timer", status: CheckStatus::Fail, detail: "stalled".to_string() }],
+        };
+        let lines = renderer.build_health_lines(5, 80, &report, &ColorManager::default());
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains("\u{2717} timer: stalled"));
+        assert_eq!(renderer.build_health_lines(1, 80, &report, &ColorManager::default()).len(), 1);
+    }
+
+    #[test]
+    fn test_suppression_segment() {
+        let renderer = Renderer::default();
+        let color_manager = ColorManager::default();
+
+        let status = SuppressionStatus { kind: SuppressionKind::Dnd, remaining_ms: Some(2_000_000), held: 5 };
+        assert!(renderer.build_suppression_segment(&status, &color_manager).contains("(DND 34m \u{00B7} 5 held)"));
+
+        let status = SuppressionStatus { kind: SuppressionKind::Dnd, remaining_ms: None, held: 0 };
+        assert!(renderer.build_suppression_segment(&status, &color_manager).contains("(DND)"));
+
+        let status = SuppressionStatus { kind: SuppressionKind::Focus, remaining_ms: Some(1_500_000), held: 3 };
+        let segment = renderer.build_suppression_segment(&status, &color_manager);
+        assert!(segment.contains("(Focus 25m \u{00B7} 3 batched)"));
+        assert!(segment.starts_with(&color_manager.fg_escape(&color_manager.get_highlight_color())));
+
+        assert_eq!(format_countdown_ms(45_000), "45s");
+        assert_eq!(format_countdown_ms(3_900_000), "1h05m");
+    }
+
     #[test]
     fn test_pattern_suffix() {
         let renderer = Renderer::default();
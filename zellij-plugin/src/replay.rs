@@ -0,0 +1,223 @@
+//! Replay module for Zellij Visual Notifications
+//!
+//! The `replay <id|last N>` command runs stored history entries through the rules, the queue and
+//! the sinks again, with the current configuration, to check that a new rule or webhook setup
+//! would have handled them as intended. The replay is sandboxed: it uses its own queue and sink
+//! pipeline and only reports what would have happened; nothing is shown, sent or recorded.
+
+use crate::config::Config;
+use crate::history::HistoryEntry;
+use crate::notification::Notification;
+use crate::queue::{Admission, NotificationQueue};
+use crate::rules::{self, RuleMatchOutcome};
+use crate::sinks::{SinkEffect, SinkPipeline};
+
+/// History entries to replay
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayTarget {
+    /// One notification by ID
+    Id(String),
+    /// The most recent N notifications
+    Last(usize),
+}
+
+impl ReplayTarget {
+    /// Parse the command arguments: `<id>`, `last` or `last <N>`
+    pub fn parse(args: &[&str]) -> Result<Self, String> {
+        match args {
+            ["last"] => Ok(Self::Last(1)),
+            ["last", count] => match count.parse::<usize>() {
+                Ok(count) if count > 0 => Ok(Self::Last(count)),
+                _ => Err(format!("expected a positive count, got: {}", count)),
+            },
+            [id] => Ok(Self::Id(id.to_string())),
+            _ => Err("expected a notification ID or last N".to_string()),
+        }
+    }
+
+    /// Matching entries, oldest first
+    pub fn select<'a>(&self, newest_first: impl Iterator<Item = &'a HistoryEntry>) -> Vec<&'a HistoryEntry> {
+        let mut entries: Vec<&HistoryEntry> = match self {
+            Self::Id(id) => newest_first.filter(|entry| &entry.notification.id == id).take(1).collect(),
+            Self::Last(count) => newest_first.take(*count).collect(),
+        };
+        entries.reverse();
+        entries
+    }
+}
+
+/// How the queue would have taken a replayed notification
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayVerdict {
+    /// A rule dropped it
+    Dropped(String),
+    /// Queued as a new entry
+    Admitted,
+    /// Folded into an equivalent notification replayed before it
+    Merged,
+    /// Rejected for lack of queue capacity
+    Rejected,
+}
+
+/// What the pipeline would have done with one notification
+#[derive(Debug, Clone)]
+pub struct ReplayResult {
+    /// The notification after the rules
+    pub notification: Notification,
+    /// Rules that matched
+    pub rules: Vec<RuleMatchOutcome>,
+    /// Queue outcome
+    pub verdict: ReplayVerdict,
+    /// Sink effects it would have requested
+    pub effects: Vec<SinkEffect>,
+}
+
+impl ReplayResult {
+    /// Report lines for this notification
+    pub fn lines(&self) -> Vec<String> {
+        let notification = &self.notification;
+        let mut lines = vec![format!("{} [{}] {}", notification.id,
+            notification.notification_type.name(), notification.message)];
+        if self.rules.is_empty() {
+            lines.push("  rules: none matched".to_string());
+        }
+        for outcome in &self.rules {
+            let actions: Vec<String> = outcome.actions.iter().map(|action| action.describe()).collect();
+            lines.push(format!("  rule {}: {}", outcome.rule, actions.join(", ")));
+        }
+        lines.push(match &self.verdict {
+            ReplayVerdict::Dropped(rule) => format!("  queue: dropped by rule {}", rule),
+            ReplayVerdict::Admitted => format!("  queue: admitted ({} priority{})", notification.priority.name(),
+                notification.pane_id.map(|pane_id| format!(", pane {}", pane_id)).unwrap_or_default()),
+            ReplayVerdict::Merged => "  queue: merged into an equivalent notification".to_string(),
+            ReplayVerdict::Rejected => "  queue: rejected, queue near capacity".to_string(),
+        });
+        if matches!(self.verdict, ReplayVerdict::Admitted | ReplayVerdict::Merged) {
+            let effects: Vec<String> = self.effects.iter().map(|effect| match effect {
+                SinkEffect::WebRequest { url, .. } => format!("webhook {}", url),
+                SinkEffect::Forward { session, .. } => format!("forward {}", session),
+                other => other.label().to_string(),
+            }).collect();
+            lines.push(format!("  sinks: {}", if effects.is_empty() { "none".to_string() } else { effects.join(", ") }));
+        }
+        lines
+    }
+}
+
+/// Sandboxed pipeline with its own queue and sinks
+pub struct Replay<'a> {
+    /// Configuration the notifications are replayed against
+    config: &'a Config,
+    /// Queue the replayed notifications go into
+    queue: NotificationQueue,
+    /// Sinks asked for their effects
+    sinks: SinkPipeline,
+}
+
+impl<'a> Replay<'a> {
+    /// Create a sandbox for the current configuration
+    pub fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            queue: NotificationQueue::from_config(config),
+            sinks: SinkPipeline::from_config(config),
+        }
+    }
+
+    /// Run one notification through rules, queue and sinks
+    pub fn run(&mut self, notification: &Notification) -> ReplayResult {
+        let mut notification = notification.clone();
+        let outcomes = rules::apply_rules(&self.config.rules, &mut notification, false);
+        let verdict = match outcomes.iter().find(|outcome| outcome.drops()) {
+            Some(outcome) => ReplayVerdict::Dropped(outcome.rule.clone()),
+            None => match self.queue.enqueue(notification.clone()) {
+                Admission::Admitted => ReplayVerdict::Admitted,
+                Admission::Merged => ReplayVerdict::Merged,
+                Admission::Rejected => ReplayVerdict::Rejected,
+            },
+        };
+        let effects = match verdict {
+            ReplayVerdict::Admitted | ReplayVerdict::Merged => self.sinks.notify(&notification),
+            _ => Vec::new(),
+        };
+        ReplayResult { notification, rules: outcomes, verdict, effects }
+    }
+}
+
+/// Replay entries (oldest first) and format the report
+pub fn replay(entries: &[&HistoryEntry], config: &Config) -> String {
+    if entries.is_empty() {
+        return "no matching notifications in history\n".to_string();
+    }
+    let mut sandbox = Replay::new(config);
+    let mut lines = vec![format!("replaying {} notification{} (sandbox: nothing is shown or sent)",
+        entries.len(), if entries.len() == 1 { "" } else { "s" })];
+    for entry in entries {
+        lines.extend(sandbox.run(&entry.notification).lines());
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::NotificationHistory;
+    use crate::notification::Priority;
+    use crate::rules::Rule;
+
+    #[test]
+    fn test_parse_and_select() {
+        assert_eq!(ReplayTarget::parse(&["last"]), Ok(ReplayTarget::Last(1)));
+        assert_eq!(ReplayTarget::parse(&["last", "3"]), Ok(ReplayTarget::Last(3)));
+        assert_eq!(ReplayTarget::parse(&["n-4"]), Ok(ReplayTarget::Id("n-4".to_string())));
+        assert!(ReplayTarget::parse(&["last", "0"]).is_err());
+        assert!(ReplayTarget::parse(&[]).is_err());
+
+        let mut history = NotificationHistory::default();
+        for (i, message) in ["one", "two", "three"].iter().enumerate() {
+            let mut notification = Notification::info(message);
+            notification.id = format!("n-{}", i);
+            history.record(&notification, i as u64);
+        }
+        let ids = |target: ReplayTarget| -> Vec<String> {
+            target.select(history.newest_first()).iter().map(|entry| entry.notification.id.clone()).collect()
+        };
+        assert_eq!(ids(ReplayTarget::Last(2)), vec!["n-1", "n-2"]);
+        assert_eq!(ids(ReplayTarget::Id("n-0".to_string())), vec!["n-0"]);
+        assert!(ids(ReplayTarget::Id("n-9".to_string())).is_empty());
+    }
+
+    #[test]
+    fn test_replay_applies_current_rules_and_sinks() {
+        let mut config = Config {
+            rules: vec![
+                Rule::parse("quiet-ci", "source=ci", "priority=low pane=4").unwrap(),
+                Rule::parse("no-heartbeat", "message=heartbeat", "drop").unwrap(),
+            ],
+            sinks: vec!["visual".to_string(), "webhook".to_string()],
+            webhook_url: Some("http://localhost:9000/hook".to_string()),
+            ..Config::default()
+        };
+        let mut sandbox = Replay::new(&config);
+
+        let mut build = Notification::error("Build failed");
+        build.source = "ci".to_string();
+        build.priority = Priority::High;
+        let result = sandbox.run(&build);
+        assert_eq!(result.verdict, ReplayVerdict::Admitted);
+        assert_eq!(result.notification.priority, Priority::Low);
+        assert_eq!(result.lines()[1], "  rule quiet-ci: priority low, pane 4");
+        assert_eq!(result.lines()[2], "  queue: admitted (low priority, pane 4)");
+        assert_eq!(result.lines()[3], "  sinks: visual");
+
+        let result = sandbox.run(&Notification::info("heartbeat ok"));
+        assert_eq!(result.verdict, ReplayVerdict::Dropped("no-heartbeat".to_string()));
+        assert!(result.effects.is_empty());
+
+        config.rules.clear();
+        let mut sandbox = Replay::new(&config);
+        let result = sandbox.run(&build);
+        assert_eq!(result.lines()[1], "  rules: none matched");
+        assert!(result.lines()[3].starts_with("  sinks: webhook http://localhost:9000/hook"), "{:?}", result.lines());
+    }
+}
@@ -0,0 +1,228 @@
+//! Rules module for Zellij Visual Notifications
+//!
+//! User rules adjust notifications as they enter the pipeline: a `match` filter
+//! (`type=info source=ci`) selects notifications, and actions (`priority=low channel=ci`,
+//! `pane=4`, `ttl=60000`, `drop`) change them. With `rules_dry_run`, matches are only recorded in
+//! the audit trail (`explain`), so a new rule set can be checked against live traffic before it
+//! takes effect.
+
+use serde::{Deserialize, Serialize};
+use crate::notification::{Notification, NotificationType, Priority};
+
+/// Notifications a rule applies to; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleMatch {
+    /// Notification type
+    pub notification_type: Option<NotificationType>,
+    /// Case-insensitive substring of the source
+    pub source: Option<String>,
+    /// Target pane
+    pub pane_id: Option<u32>,
+    /// Channel
+    pub channel: Option<String>,
+    /// Case-insensitive substring of the message
+    pub message: Option<String>,
+}
+
+impl RuleMatch {
+    /// Parse `key=value` filters (`type=error source=tests pane=3 channel=ci message=timeout`)
+    pub fn parse(filters: &str) -> Result<Self, String> {
+        let mut matcher = Self::default();
+        for filter in filters.split_whitespace() {
+            let (key, value) = filter.split_once('=')
+                .ok_or_else(|| format!("expected key=value, got: {}", filter))?;
+            match key {
+                "type" => matcher.notification_type = Some(NotificationType::from_name(value)
+                    .ok_or_else(|| format!("unknown notification type: {}", value))?),
+                "source" => matcher.source = Some(value.to_lowercase()),
+                "pane" => matcher.pane_id = Some(value.parse().map_err(|_| format!("invalid pane: {}", value))?),
+                "channel" => matcher.channel = Some(value.to_string()),
+                "message" => matcher.message = Some(value.to_lowercase()),
+                other => return Err(format!("unknown match key: {}", other)),
+            }
+        }
+        Ok(matcher)
+    }
+
+    /// Check a notification against the filters
+    pub fn matches(&self, notification: &Notification) -> bool {
+        self.notification_type.as_ref().is_none_or(|t| *t == notification.notification_type)
+            && self.source.as_deref().is_none_or(|s| notification.source.to_lowercase().contains(s))
+            && self.pane_id.is_none_or(|p| notification.pane_id == Some(p))
+            && self.channel.as_deref().is_none_or(|c| notification.channel.as_deref() == Some(c))
+            && self.message.as_deref().is_none_or(|m| notification.message.to_lowercase().contains(m))
+    }
+}
+
+/// A change a rule makes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RuleAction {
+    /// Discard the notification
+    Drop,
+    /// Set the priority
+    Priority(Priority),
+    /// Route to another pane
+    Pane(u32),
+    /// Move to a channel
+    Channel(String),
+    /// Set the time to live (ms)
+    Ttl(u64),
+}
+
+impl RuleAction {
+    /// Parse actions (`drop`, `priority=low`, `pane=4`, `channel=ci`, `ttl=60000`)
+    pub fn parse_all(actions: &str) -> Result<Vec<Self>, String> {
+        actions.split_whitespace()
+            .map(|action| match action.split_once('=') {
+                None if action == "drop" => Ok(Self::Drop),
+                Some(("priority", value)) => Priority::from_name(value)
+                    .map(Self::Priority)
+                    .ok_or_else(|| format!("invalid priority: {}", value)),
+                Some(("pane", value)) => value.parse().map(Self::Pane).map_err(|_| format!("invalid pane: {}", value)),
+                Some(("channel", value)) => Ok(Self::Channel(value.to_string())),
+                Some(("ttl", value)) => value.parse().map(Self::Ttl).map_err(|_| format!("invalid ttl: {}", value)),
+                _ => Err(format!("unknown rule action: {}", action)),
+            })
+            .collect()
+    }
+
+    /// Short description, e.g. `priority low`
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Drop => "drop".to_string(),
+            Self::Priority(priority) => format!("priority {}", priority.name()),
+            Self::Pane(pane_id) => format!("pane {}", pane_id),
+            Self::Channel(channel) => format!("channel {}", channel),
+            Self::Ttl(ttl_ms) => format!("ttl {}ms", ttl_ms),
+        }
+    }
+
+    /// Apply the change
+    fn apply(&self, notification: &mut Notification) {
+        match self {
+            Self::Drop => {}
+            Self::Priority(priority) => notification.priority = *priority,
+            Self::Pane(pane_id) => notification.pane_id = Some(*pane_id),
+            Self::Channel(channel) => notification.channel = Some(channel.clone()),
+            Self::Ttl(ttl_ms) => notification.ttl_ms = *ttl_ms,
+        }
+    }
+}
+
+/// A named rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Name shown in the audit trail
+    pub name: String,
+    /// Notifications it applies to
+    pub matcher: RuleMatch,
+    /// Changes, in order
+    pub actions: Vec<RuleAction>,
+}
+
+impl Rule {
+    /// Parse a rule from its match filters and actions
+    pub fn parse(name: &str, filters: &str, actions: &str) -> Result<Self, String> {
+        let actions = RuleAction::parse_all(actions).map_err(|e| format!("rule {}: {}", name, e))?;
+        if actions.is_empty() {
+            return Err(format!("rule {}: no actions", name));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            matcher: RuleMatch::parse(filters).map_err(|e| format!("rule {}: {}", name, e))?,
+            actions,
+        })
+    }
+
+    /// Parse the plugin-config form `<filters> -> <actions>`, e.g. `source=ci type=info -> priority=low`
+    pub fn parse_spec(name: &str, spec: &str) -> Result<Self, String> {
+        let (filters, actions) = spec.split_once("->")
+            .ok_or_else(|| format!("rule {}: expected `<match> -> <actions>`", name))?;
+        Self::parse(name, filters, actions)
+    }
+}
+
+/// A rule that matched a notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatchOutcome {
+    /// Rule name
+    pub rule: String,
+    /// Actions it took (or would have taken in dry-run mode)
+    pub actions: Vec<RuleAction>,
+}
+
+impl RuleMatchOutcome {
+    /// Whether the rule drops the notification
+    pub fn drops(&self) -> bool {
+        self.actions.contains(&RuleAction::Drop)
+    }
+}
+
+/// Run all rules over a notification, in order. Without `dry_run` the actions are applied (later
+/// rules see the changes, a drop stops further rules); with it the notification is left untouched.
+pub fn apply_rules(rules: &[Rule], notification: &mut Notification, dry_run: bool) -> Vec<RuleMatchOutcome> {
+    let mut outcomes = Vec::new();
+    for rule in rules {
+        if !rule.matcher.matches(notification) {
+            continue;
+        }
+        let outcome = RuleMatchOutcome { rule: rule.name.clone(), actions: rule.actions.clone() };
+        let drops = outcome.drops();
+        if !dry_run {
+            for action in &rule.actions {
+                action.apply(notification);
+            }
+        }
+        outcomes.push(outcome);
+        if drops && !dry_run {
+            break;
+        }
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<Rule> {
+        vec![
+            Rule::parse_spec("quiet-ci", "source=ci type=info -> priority=low channel=ci").unwrap(),
+            Rule::parse("no-heartbeat", "message=heartbeat", "drop").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let mut notification = Notification::info("Pipeline started");
+        notification.source = "CI".to_string();
+        notification.priority = Priority::High;
+        let outcomes = apply_rules(&rules(), &mut notification, false);
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(notification.priority, Priority::Low);
+        assert_eq!(notification.channel.as_deref(), Some("ci"));
+
+        let mut heartbeat = Notification::info("heartbeat ok");
+        let outcomes = apply_rules(&rules(), &mut heartbeat, false);
+        assert!(outcomes[0].drops());
+    }
+
+    #[test]
+    fn test_dry_run_leaves_notification_untouched() {
+        let mut notification = Notification::info("Pipeline started");
+        notification.source = "ci".to_string();
+        notification.priority = Priority::High;
+        let outcomes = apply_rules(&rules(), &mut notification, true);
+        assert_eq!(outcomes[0].rule, "quiet-ci");
+        assert_eq!(notification.priority, Priority::High);
+        assert_eq!(notification.channel, None);
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        assert!(Rule::parse_spec("bad", "type=lunch -> drop").is_err());
+        assert!(Rule::parse_spec("bad", "type=info").is_err());
+        assert!(Rule::parse("bad", "type=info", "explode").is_err());
+        assert!(Rule::parse("bad", "type=info", "").is_err());
+    }
+}
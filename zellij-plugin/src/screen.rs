@@ -0,0 +1,150 @@
+//! Screen buffer module for Zellij Visual Notifications
+//!
+//! Multi-row views (diagnostics, health, channels, groups, queue overlay, big mode) are composed
+//! into an off-screen buffer of rows×cols cells and written in one piece, rather than printed
+//! line by line. Each row is addressed with an explicit cursor position and cleared before it is
+//! drawn, and no newline follows the last row, so switching views or scrolling never scrolls the
+//! pane, shows a half-drawn frame, or leaves the tail of a longer previous line behind. The pane
+//! holds the front buffer; the plugin keeps and reuses the back buffer between renders.
+
+use crate::wrap::clusters;
+
+/// SGR reset
+const RESET: &str = "\u{1b}[0m";
+
+/// One terminal cell
+#[derive(Debug, Clone, PartialEq)]
+struct Cell {
+    /// Grapheme cluster shown (empty for the right half of a wide character)
+    text: String,
+    /// Escape sequences in effect for the cell
+    style: String,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self { text: " ".to_string(), style: String::new() }
+    }
+}
+
+/// Off-screen frame of rows×cols cells
+#[derive(Debug, Clone, Default)]
+pub struct ScreenBuffer {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Cell>,
+}
+
+impl ScreenBuffer {
+    /// Start a new frame: blank cells at the given size (the allocation is kept when it fits)
+    pub fn begin(&mut self, rows: usize, cols: usize) {
+        self.rows = rows;
+        self.cols = cols;
+        self.cells.clear();
+        self.cells.resize(rows * cols, Cell::default());
+    }
+
+    /// Draw a line (which may contain color escapes) on a row, cut at the last column. Rows
+    /// outside the frame are ignored.
+    pub fn write_line(&mut self, row: usize, line: &str) {
+        if row >= self.rows {
+            return;
+        }
+        let mut style = String::new();
+        let mut col = 0;
+        for (text, width) in clusters(line) {
+            if text.starts_with('\u{1b}') {
+                if text == RESET || text == "\u{1b}[m" {
+                    style.clear();
+                } else {
+                    style.push_str(&text);
+                }
+                continue;
+            }
+            if width == 0 {
+                continue;
+            }
+            if col + width > self.cols {
+                break;
+            }
+            let start = row * self.cols + col;
+            self.cells[start] = Cell { text, style: style.clone() };
+            for cell in &mut self.cells[start + 1..start + width] {
+                *cell = Cell { text: String::new(), style: style.clone() };
+            }
+            col += width;
+        }
+    }
+
+    /// Draw lines from the top row down
+    pub fn write_lines(&mut self, lines: &[String]) {
+        for (row, line) in lines.iter().enumerate() {
+            self.write_line(row, line);
+        }
+    }
+
+    /// Output writing the whole frame: per row, a cursor position, a line clear and the cells,
+    /// with escapes emitted only where the style changes
+    pub fn present(&self) -> String {
+        let mut output = String::new();
+        for row in 0..self.rows {
+            output.push_str(&format!("\u{1b}[{};1H\u{1b}[2K", row + 1));
+            let mut current = "";
+            for cell in &self.cells[row * self.cols..(row + 1) * self.cols] {
+                if cell.style != current {
+                    output.push_str(RESET);
+                    output.push_str(&cell.style);
+                    current = &cell.style;
+                }
+                output.push_str(&cell.text);
+            }
+            if !current.is_empty() {
+                output.push_str(RESET);
+            }
+        }
+        output
+    }
+
+    /// Text of a row, without styles or trailing blanks
+    #[cfg(test)]
+    fn row_text(&self, row: usize) -> String {
+        self.cells[row * self.cols..(row + 1) * self.cols]
+            .iter()
+            .map(|cell| cell.text.as_str())
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_cut_to_the_frame() {
+        let mut screen = ScreenBuffer::default();
+        screen.begin(2, 5);
+        screen.write_lines(&["Health check".to_string(), "a\u{1F514}b\u{1F514}".to_string(), "gone".to_string()]);
+        assert_eq!(screen.row_text(0), "Healt");
+        // The second bell would cross the last column
+        assert_eq!(screen.row_text(1), "a\u{1F514}b");
+
+        // A shorter frame leaves nothing of the previous one
+        screen.begin(2, 6);
+        screen.write_line(0, "ok");
+        assert_eq!(screen.row_text(0), "ok");
+        assert_eq!(screen.row_text(1), "");
+    }
+
+    #[test]
+    fn test_present_positions_and_clears_rows() {
+        let mut screen = ScreenBuffer::default();
+        screen.begin(2, 4);
+        screen.write_line(0, "\u{1b}[31mab\u{1b}[0mc");
+        let output = screen.present();
+        assert!(output.starts_with("\u{1b}[1;1H\u{1b}[2K\u{1b}[0m\u{1b}[31mab\u{1b}[0mc "));
+        assert!(output.ends_with("\u{1b}[2;1H\u{1b}[2K    "));
+        assert!(!output.contains('\n'));
+    }
+}
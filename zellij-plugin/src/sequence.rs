@@ -0,0 +1,186 @@
+//! Sequence module for Zellij Visual Notifications
+//!
+//! Senders that burst writes can number their notifications with `seq` (per source). Messages
+//! that arrive ahead of a missing predecessor are held briefly and released in order, so a
+//! Success never lands before the Progress it supersedes. When the missing message does not
+//! show up within the hold time, the gap is flagged and the held messages are released anyway.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use crate::notification::Notification;
+
+/// Default time an out-of-order notification waits for its predecessor (ms)
+pub const DEFAULT_SEQUENCE_HOLD_MS: u64 = 500;
+
+/// Missing sequence numbers skipped after the hold time ran out
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceGap {
+    /// Sender the numbers belong to
+    pub source: String,
+    /// First missing number
+    pub first: u64,
+    /// Last missing number
+    pub last: u64,
+}
+
+impl fmt::Display for SequenceGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.first == self.last {
+            write!(f, "seq {} from {} missing", self.first, self.source)
+        } else {
+            write!(f, "seq {}-{} from {} missing", self.first, self.last, self.source)
+        }
+    }
+}
+
+/// Ordering state of one source
+#[derive(Debug, Clone, Default)]
+struct SourceSequence {
+    /// Next number to release
+    next: u64,
+    /// Out-of-order notifications by number, with the time they were held (ms)
+    held: BTreeMap<u64, (Notification, u64)>,
+}
+
+impl SourceSequence {
+    /// Release held notifications that continue the sequence
+    fn release(&mut self) -> Vec<Notification> {
+        let mut released = Vec::new();
+        while let Some((notification, _)) = self.held.remove(&self.next) {
+            released.push(notification);
+            self.next += 1;
+        }
+        released
+    }
+}
+
+/// Per-source reordering of numbered notifications
+#[derive(Debug, Clone)]
+pub struct Sequencer {
+    /// How long a notification waits for a missing predecessor (ms)
+    hold_ms: u64,
+    /// Ordering state per source
+    sources: BTreeMap<String, SourceSequence>,
+    /// Gaps flagged so far
+    gaps: u64,
+}
+
+impl Sequencer {
+    /// Create a sequencer holding out-of-order notifications for up to `hold_ms`
+    pub fn new(hold_ms: u64) -> Self {
+        Self { hold_ms, sources: BTreeMap::new(), gaps: 0 }
+    }
+
+    /// Change the hold time (held notifications keep their arrival time)
+    pub fn set_hold_ms(&mut self, hold_ms: u64) {
+        self.hold_ms = hold_ms;
+    }
+
+    /// Accept a notification, returning those now ready in order. Unnumbered notifications
+    /// pass straight through; numbers already released are dropped as stale, except `1`, which
+    /// restarts the source's sequence (sender restarted).
+    pub fn accept(&mut self, notification: Notification, now_ms: u64) -> Vec<Notification> {
+        let Some(seq) = notification.seq else {
+            return vec![notification];
+        };
+
+        let source = self.sources.entry(notification.source.clone()).or_insert_with(|| SourceSequence {
+            next: seq,
+            held: BTreeMap::new(),
+        });
+        if seq == 1 && source.next > 1 && source.held.is_empty() {
+            source.next = 1;
+        }
+        if seq < source.next {
+            return Vec::new();
+        }
+
+        source.held.insert(seq, (notification, now_ms));
+        source.release()
+    }
+
+    /// Release notifications held longer than the hold time, skipping the gaps before them
+    pub fn expire(&mut self, now_ms: u64) -> (Vec<Notification>, Vec<SequenceGap>) {
+        let mut released = Vec::new();
+        let mut gaps = Vec::new();
+        for (name, source) in self.sources.iter_mut() {
+            let overdue = source.held.values()
+                .any(|(_, held_since)| now_ms.saturating_sub(*held_since) >= self.hold_ms);
+            if !overdue {
+                continue;
+            }
+            if let Some(&first_held) = source.held.keys().next() {
+                gaps.push(SequenceGap { source: name.clone(), first: source.next, last: first_held - 1 });
+                source.next = first_held;
+                released.extend(source.release());
+            }
+        }
+        self.gaps += gaps.len() as u64;
+        (released, gaps)
+    }
+
+    /// Notifications currently held
+    pub fn held_count(&self) -> usize {
+        self.sources.values().map(|source| source.held.len()).sum()
+    }
+
+    /// Gaps flagged since load
+    pub fn gap_count(&self) -> u64 {
+        self.gaps
+    }
+}
+
+impl Default for Sequencer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEQUENCE_HOLD_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationType;
+
+    fn numbered(seq: u64, message: &str) -> Notification {
+        let mut notification = Notification::new(NotificationType::Progress, message);
+        notification.source = "ci".to_string();
+        notification.seq = Some(seq);
+        notification
+    }
+
+    fn messages(notifications: &[Notification]) -> Vec<&str> {
+        notifications.iter().map(|n| n.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_reorders_held_notifications() {
+        let mut sequencer = Sequencer::new(500);
+        assert_eq!(messages(&sequencer.accept(numbered(1, "start"), 0)), vec!["start"]);
+        assert!(sequencer.accept(numbered(3, "done"), 10).is_empty());
+        assert_eq!(sequencer.held_count(), 1);
+        assert_eq!(messages(&sequencer.accept(numbered(2, "50%"), 20)), vec!["50%", "done"]);
+        assert_eq!(sequencer.held_count(), 0);
+
+        // Stale numbers are dropped
+        assert!(sequencer.accept(numbered(2, "late"), 30).is_empty());
+    }
+
+    #[test]
+    fn test_gap_is_flagged_after_hold_time() {
+        let mut sequencer = Sequencer::new(500);
+        sequencer.accept(numbered(1, "start"), 0);
+        sequencer.accept(numbered(4, "done"), 100);
+
+        let (released, gaps) = sequencer.expire(400);
+        assert!(released.is_empty() && gaps.is_empty());
+
+        let (released, gaps) = sequencer.expire(600);
+        assert_eq!(messages(&released), vec!["done"]);
+        assert_eq!(gaps, vec![SequenceGap { source: "ci".to_string(), first: 2, last: 3 }]);
+        assert_eq!(gaps[0].to_string(), "seq 2-3 from ci missing");
+        assert_eq!(sequencer.gap_count(), 1);
+
+        // A restarted sender begins again at 1
+        assert_eq!(messages(&sequencer.accept(numbered(1, "again"), 700)), vec!["again"]);
+    }
+}
@@ -0,0 +1,667 @@
+//! Notification sink module for Zellij Visual Notifications
+//!
+//! Fans notification lifecycle events (notify, display, acknowledge, expire, overdue) out to an ordered set of
+//! sinks: visual renderer, terminal bell, webhook, desktop (OSC), desktop daemon, sound, screen
+//! reader and forwarding to another session. Sinks do not call the host directly; they return [`SinkEffect`]s which the
+//! plugin applies.
+
+use crate::config::Config;
+use crate::desktop_daemon;
+use crate::markup;
+use crate::notification::{Notification, Priority};
+use crate::sound::SoundTheme;
+
+/// Side effect requested by a sink
+#[derive(Debug, Clone, PartialEq)]
+pub enum SinkEffect {
+    /// Apply the notification's visual state (border, badge, animation); on arrival this keeps
+    /// the notification queued until the queue's display rate lets it through
+    ShowVisual,
+    /// Clear the visual state of a pane
+    ClearVisual(u32),
+    /// Ring the terminal bell
+    Bell,
+    /// Emit a raw terminal escape sequence
+    Escape(String),
+    /// Announce a plain text line (for screen readers)
+    Announce(String),
+    /// POST a JSON body to a URL
+    WebRequest { url: String, body: String },
+    /// Show the notification in a floating toast pane
+    Toast,
+    /// Run a command playing a sound on the host
+    Sound(Vec<String>),
+    /// Pipe the notification to the plugin in another session, or withdraw it there
+    Forward { session: String, withdraw: bool },
+    /// Run the desktop daemon helper to show the notification's desktop copy, or close it
+    Daemon { command: Vec<String>, close: bool },
+}
+
+impl SinkEffect {
+    /// Short label for audit records
+    pub fn label(&self) -> &'static str {
+        match self {
+            SinkEffect::ShowVisual => "visual",
+            SinkEffect::ClearVisual(_) => "clear visual",
+            SinkEffect::Bell => "bell",
+            SinkEffect::Escape(_) => "desktop escape",
+            SinkEffect::Announce(_) => "announce",
+            SinkEffect::WebRequest { .. } => "webhook",
+            SinkEffect::Toast => "toast",
+            SinkEffect::Sound(_) => "sound",
+            SinkEffect::Forward { .. } => "forward",
+            SinkEffect::Daemon { .. } => "desktop daemon",
+        }
+    }
+}
+
+/// A destination for notification lifecycle events
+pub trait NotificationSink {
+    /// Sink name as used in configuration
+    fn name(&self) -> &'static str;
+
+    /// Called when a notification is accepted for display
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect>;
+
+    /// Called when a notification is shown on its pane (or by pane-less sinks)
+    fn on_display(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        Vec::new()
+    }
+
+    /// Called when a displayed notification is acknowledged
+    fn on_ack(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        Vec::new()
+    }
+
+    /// Called when a notification expires
+    fn on_expire(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        Vec::new()
+    }
+
+    /// Called once when a Critical notification stays unacknowledged (out-of-terminal sinks only)
+    fn on_escalate(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        Vec::new()
+    }
+
+    /// Called once when a notification's response deadline passes unacknowledged
+    fn on_overdue(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        Vec::new()
+    }
+}
+
+/// Visual renderer sink (borders, badges, status bar entries)
+#[derive(Debug, Default)]
+pub struct VisualSink;
+
+impl NotificationSink for VisualSink {
+    fn name(&self) -> &'static str {
+        "visual"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        if notification.pane_id.is_some() {
+            vec![SinkEffect::ShowVisual]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn on_expire(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        notification.pane_id.map(SinkEffect::ClearVisual).into_iter().collect()
+    }
+}
+
+/// Terminal bell sink (rings only for escalated notifications)
+#[derive(Debug, Default)]
+pub struct BellSink;
+
+impl NotificationSink for BellSink {
+    fn name(&self) -> &'static str {
+        "bell"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        if notification.escalated {
+            vec![SinkEffect::Bell]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn on_overdue(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        vec![SinkEffect::Bell]
+    }
+}
+
+/// Toast sink (floating pane per notification, opt-in)
+#[derive(Debug, Default)]
+pub struct ToastSink;
+
+impl NotificationSink for ToastSink {
+    fn name(&self) -> &'static str {
+        "toast"
+    }
+
+    fn on_notify(&mut self, _notification: &Notification) -> Vec<SinkEffect> {
+        vec![SinkEffect::Toast]
+    }
+}
+
+/// Sound sink (plays the sound theme's sound for the notification type, opt-in)
+#[derive(Debug)]
+pub struct SoundSink {
+    /// Sounds by type
+    theme: SoundTheme,
+    /// Command playing sound files
+    player: String,
+}
+
+impl SoundSink {
+    /// Create a new sound sink
+    pub fn new(theme: SoundTheme, player: &str) -> Self {
+        Self { theme, player: player.to_string() }
+    }
+}
+
+impl NotificationSink for SoundSink {
+    fn name(&self) -> &'static str {
+        "sound"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.theme.command(&notification.notification_type, &self.player)
+            .map(SinkEffect::Sound)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Lifecycle events the webhook sink can POST besides `notify`, `escalate` and `overdue`
+pub const WEBHOOK_LIFECYCLE_EVENTS: [&str; 3] = ["display", "ack", "expire"];
+
+/// Webhook sink (POSTs high priority or escalated notifications as JSON, and optionally the
+/// lifecycle events in `webhook_events` for the same notifications)
+#[derive(Debug)]
+pub struct WebhookSink {
+    /// Target URL
+    url: String,
+    /// Lifecycle events to POST (`display`, `ack`, `expire`)
+    events: Vec<String>,
+}
+
+impl WebhookSink {
+    /// Create a new webhook sink posting the given lifecycle events
+    pub fn new(url: &str, events: &[String]) -> Self {
+        Self { url: url.to_string(), events: events.iter().map(|event| event.to_lowercase()).collect() }
+    }
+
+    /// POST a lifecycle event if configured, for notifications whose arrival was posted
+    fn lifecycle(&self, event: &str, notification: &Notification) -> Vec<SinkEffect> {
+        if !is_out_of_band(notification) || !self.events.iter().any(|wanted| wanted == event) {
+            return Vec::new();
+        }
+        vec![self.request(event, notification)]
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        if !is_out_of_band(notification) {
+            return Vec::new();
+        }
+        vec![self.request("notify", notification)]
+    }
+
+    fn on_display(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.lifecycle("display", notification)
+    }
+
+    fn on_ack(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.lifecycle("ack", notification)
+    }
+
+    fn on_expire(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.lifecycle("expire", notification)
+    }
+
+    fn on_escalate(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        vec![self.request("escalate", notification)]
+    }
+
+    fn on_overdue(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        vec![self.request("overdue", notification)]
+    }
+}
+
+impl WebhookSink {
+    /// JSON POST for a lifecycle event
+    fn request(&self, event: &str, notification: &Notification) -> SinkEffect {
+        let body = serde_json::json!({
+            "event": event,
+            "notification": notification,
+        });
+        SinkEffect::WebRequest {
+            url: self.url.clone(),
+            body: body.to_string(),
+        }
+    }
+}
+
+/// Forwarding sink (pipes notifications to the aggregator in another session, withdrawing them
+/// once acknowledged or expired)
+#[derive(Debug)]
+pub struct ForwardSink {
+    /// Target session
+    session: String,
+}
+
+impl ForwardSink {
+    /// Create a new forwarding sink
+    pub fn new(session: &str) -> Self {
+        Self { session: session.to_string() }
+    }
+
+    /// Forward or withdraw a notification; forwarded ones are never passed on again
+    fn forward(&self, notification: &Notification, withdraw: bool) -> Vec<SinkEffect> {
+        if notification.origin_session.is_some() {
+            return Vec::new();
+        }
+        vec![SinkEffect::Forward { session: self.session.clone(), withdraw }]
+    }
+}
+
+impl NotificationSink for ForwardSink {
+    fn name(&self) -> &'static str {
+        "forward"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.forward(notification, false)
+    }
+
+    fn on_ack(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.forward(notification, true)
+    }
+
+    fn on_expire(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.forward(notification, true)
+    }
+}
+
+/// Desktop notification sink (OSC 777 escape forwarded to the host terminal)
+#[derive(Debug, Default)]
+pub struct DesktopSink;
+
+impl NotificationSink for DesktopSink {
+    fn name(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        if !is_out_of_band(notification) {
+            return Vec::new();
+        }
+        let title = notification.title.as_deref().unwrap_or("Zellij");
+        vec![desktop_escape(title, &notification.message)]
+    }
+
+    fn on_escalate(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        let title = format!("Unacknowledged: {}", notification.title.as_deref().unwrap_or("Zellij"));
+        vec![desktop_escape(&title, &notification.message)]
+    }
+
+    fn on_overdue(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        let title = format!("Overdue: {}", notification.title.as_deref().unwrap_or("Zellij"));
+        vec![desktop_escape(&title, &notification.message)]
+    }
+}
+
+/// OSC 777 desktop notification escape
+fn desktop_escape(title: &str, message: &str) -> SinkEffect {
+    SinkEffect::Escape(format!("\x1b]777;notify;{};{}\x07", sanitize_osc(title), sanitize_osc(&markup::strip(message))))
+}
+
+/// Desktop daemon sink (desktop copies of high/critical or escalated notifications through a
+/// host helper, closed once the terminal copy is acknowledged or expires)
+#[derive(Debug)]
+pub struct DesktopDaemonSink {
+    /// Helper command
+    helper: String,
+}
+
+impl DesktopDaemonSink {
+    /// Create a new desktop daemon sink
+    pub fn new(helper: &str) -> Self {
+        Self { helper: helper.to_string() }
+    }
+
+    /// Close the desktop copy, for notifications that got one
+    fn close(&self, notification: &Notification) -> Vec<SinkEffect> {
+        if !is_out_of_band(notification) {
+            return Vec::new();
+        }
+        vec![SinkEffect::Daemon { command: desktop_daemon::close_command(&self.helper, &notification.id), close: true }]
+    }
+}
+
+impl NotificationSink for DesktopDaemonSink {
+    fn name(&self) -> &'static str {
+        "desktop_daemon"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        if !is_out_of_band(notification) {
+            return Vec::new();
+        }
+        vec![SinkEffect::Daemon { command: desktop_daemon::show_command(&self.helper, notification), close: false }]
+    }
+
+    fn on_ack(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.close(notification)
+    }
+
+    fn on_expire(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.close(notification)
+    }
+}
+
+/// Screen reader sink (plain text announcements for every lifecycle event)
+#[derive(Debug, Default)]
+pub struct ScreenReaderSink;
+
+impl NotificationSink for ScreenReaderSink {
+    fn name(&self) -> &'static str {
+        "screen_reader"
+    }
+
+    fn on_notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        vec![SinkEffect::Announce(format!(
+            "{}: {}",
+            notification.notification_type.name(),
+            notification.display_text()
+        ))]
+    }
+
+    fn on_ack(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        vec![SinkEffect::Announce(format!("Acknowledged: {}", notification.display_text()))]
+    }
+
+    fn on_expire(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        vec![SinkEffect::Announce(format!("Expired: {}", notification.display_text()))]
+    }
+}
+
+/// Ordered fan-out pipeline over the configured sinks
+pub struct SinkPipeline {
+    /// Registered sinks in delivery order
+    sinks: Vec<Box<dyn NotificationSink>>,
+}
+
+impl Default for SinkPipeline {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+impl SinkPipeline {
+    /// Create an empty pipeline
+    pub fn new() -> Self {
+        Self { sinks: Vec::new() }
+    }
+
+    /// Build the pipeline from the configured sink names (unknown names are skipped)
+    pub fn from_config(config: &Config) -> Self {
+        let mut pipeline = Self::new();
+        for name in &config.sinks {
+            if let Some(sink) = create_sink(name, config) {
+                pipeline.register(sink);
+            }
+        }
+        pipeline
+    }
+
+    /// Register a sink at the end of the pipeline
+    pub fn register(&mut self, sink: Box<dyn NotificationSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Names of the registered sinks, in order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.sinks.iter().map(|s| s.name()).collect()
+    }
+
+    /// Check whether a sink is registered
+    pub fn has_sink(&self, name: &str) -> bool {
+        self.sinks.iter().any(|s| s.name() == name)
+    }
+
+    /// Deliver a notify event to all sinks
+    pub fn notify(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.sinks.iter_mut().flat_map(|s| s.on_notify(notification)).collect()
+    }
+
+    /// Deliver a display event to all sinks
+    pub fn display(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.sinks.iter_mut().flat_map(|s| s.on_display(notification)).collect()
+    }
+
+    /// Deliver an acknowledge event to all sinks
+    pub fn ack(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.sinks.iter_mut().flat_map(|s| s.on_ack(notification)).collect()
+    }
+
+    /// Deliver an expire event to all sinks
+    pub fn expire(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.sinks.iter_mut().flat_map(|s| s.on_expire(notification)).collect()
+    }
+
+    /// Deliver an unacknowledged-escalation event to all sinks
+    pub fn escalate(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.sinks.iter_mut().flat_map(|s| s.on_escalate(notification)).collect()
+    }
+
+    /// Deliver a missed-deadline event to all sinks
+    pub fn overdue(&mut self, notification: &Notification) -> Vec<SinkEffect> {
+        self.sinks.iter_mut().flat_map(|s| s.on_overdue(notification)).collect()
+    }
+}
+
+/// Create a built-in sink by configuration name
+fn create_sink(name: &str, config: &Config) -> Option<Box<dyn NotificationSink>> {
+    match name.to_lowercase().as_str() {
+        "visual" => Some(Box::new(VisualSink)),
+        "bell" => Some(Box::new(BellSink)),
+        // Experimental: needs `features { experimental "toasts"; }`
+        "toast" => config.experimental("toasts").then(|| Box::new(ToastSink) as Box<dyn NotificationSink>),
+        "webhook" => config.webhook_url.as_deref()
+            .map(|url| Box::new(WebhookSink::new(url, &config.webhook_events)) as Box<dyn NotificationSink>),
+        "desktop" | "osc" => Some(Box::new(DesktopSink)),
+        "desktop_daemon" => config.desktop_daemon_helper.as_deref()
+            .map(|helper| Box::new(DesktopDaemonSink::new(helper)) as Box<dyn NotificationSink>),
+        // Experimental: needs `features { experimental "sounds"; }`
+        "sound" => SoundTheme::from_config(config)
+            .filter(|_| config.experimental("sounds"))
+            .map(|theme| Box::new(SoundSink::new(theme, &config.sound_player)) as Box<dyn NotificationSink>),
+        "forward" => config.forward_session.as_deref()
+            .map(|session| Box::new(ForwardSink::new(session)) as Box<dyn NotificationSink>),
+        "screen_reader" | "screen-reader" => Some(Box::new(ScreenReaderSink)),
+        _ => None,
+    }
+}
+
+/// Whether a notification warrants out-of-terminal delivery
+fn is_out_of_band(notification: &Notification) -> bool {
+    notification.escalated || notification.priority >= Priority::High
+}
+
+/// Strip characters that would terminate or corrupt an OSC sequence
+fn sanitize_osc(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() && *c != ';')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_sinks(sinks: &[&str]) -> Config {
+        Config {
+            sinks: sinks.iter().map(|s| s.to_string()).collect(),
+            webhook_url: Some("http://localhost:9000/hook".to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_pipeline_order_from_config() {
+        let pipeline = SinkPipeline::from_config(&config_with_sinks(&["screen_reader", "visual", "bogus"]));
+        assert_eq!(pipeline.names(), vec!["screen_reader", "visual"]);
+        assert!(pipeline.has_sink("visual"));
+        assert!(!pipeline.has_sink("bell"));
+    }
+
+    #[test]
+    fn test_webhook_requires_url() {
+        let mut config = config_with_sinks(&["webhook"]);
+        config.webhook_url = None;
+        assert!(SinkPipeline::from_config(&config).names().is_empty());
+    }
+
+    #[test]
+    fn test_visual_sink_requires_pane() {
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["visual"]));
+        assert!(pipeline.notify(&Notification::success("No pane")).is_empty());
+        assert_eq!(pipeline.notify(&Notification::success("Pane").for_pane(1)), vec![SinkEffect::ShowVisual]);
+        assert_eq!(pipeline.expire(&Notification::success("Pane").for_pane(1)), vec![SinkEffect::ClearVisual(1)]);
+    }
+
+    #[test]
+    fn test_bell_only_for_escalated() {
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["bell"]));
+        let mut notification = Notification::attention("Waiting");
+        assert!(pipeline.notify(&notification).is_empty());
+
+        notification.escalated = true;
+        assert_eq!(pipeline.notify(&notification), vec![SinkEffect::Bell]);
+    }
+
+    #[test]
+    fn test_out_of_band_sinks_filter_priority() {
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["webhook", "desktop"]));
+        assert!(pipeline.notify(&Notification::info("Low")).is_empty());
+
+        let effects = pipeline.notify(&Notification::error("Build failed"));
+        assert_eq!(effects.len(), 2);
+        assert!(matches!(effects[0], SinkEffect::WebRequest { .. }));
+        assert!(matches!(effects[1], SinkEffect::Escape(_)));
+    }
+
+    #[test]
+    fn test_desktop_daemon_shows_and_closes() {
+        let mut config = config_with_sinks(&["desktop_daemon"]);
+        assert!(SinkPipeline::from_config(&config).names().is_empty());
+        config.desktop_daemon_helper = Some("notify-sync".to_string());
+        let mut pipeline = SinkPipeline::from_config(&config);
+        assert!(pipeline.notify(&Notification::info("Low")).is_empty());
+
+        let notification = Notification::error("Build failed");
+        assert!(matches!(&pipeline.notify(&notification)[..],
+            [SinkEffect::Daemon { command, close: false }] if command[1] == "show"));
+        assert!(matches!(&pipeline.ack(&notification)[..],
+            [SinkEffect::Daemon { command, close: true }] if command[1..] == ["close".to_string(), notification.id.clone()]));
+        assert_eq!(pipeline.expire(&notification).len(), 1);
+        assert!(pipeline.escalate(&notification).is_empty());
+    }
+
+    #[test]
+    fn test_escalate_only_out_of_terminal() {
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["visual", "bell", "desktop", "webhook"]));
+        let effects = pipeline.escalate(&Notification::error("Deploy failed").for_pane(2));
+        assert_eq!(effects.len(), 2);
+        assert!(matches!(&effects[0], SinkEffect::Escape(escape) if escape.contains("Unacknowledged")));
+        assert!(matches!(&effects[1], SinkEffect::WebRequest { body, .. } if body.contains("\"escalate\"")));
+
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["visual", "bell"]));
+        assert!(pipeline.escalate(&Notification::error("Deploy failed")).is_empty());
+    }
+
+    #[test]
+    fn test_overdue_reaches_bell_desktop_and_webhook() {
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["visual", "bell", "desktop", "webhook"]));
+        let notification = Notification::attention("Approve the deploy").for_pane(2);
+        let effects = pipeline.overdue(&notification);
+        assert_eq!(effects.len(), 3);
+        assert_eq!(effects[0], SinkEffect::Bell);
+        assert!(matches!(&effects[1], SinkEffect::Escape(escape) if escape.contains("Overdue")));
+        assert!(matches!(&effects[2], SinkEffect::WebRequest { body, .. } if body.contains("\"overdue\"")));
+    }
+
+    #[test]
+    fn test_webhook_lifecycle_events_opt_in() {
+        let notification = Notification::error("Build failed").for_pane(2);
+        let mut pipeline = SinkPipeline::from_config(&config_with_sinks(&["webhook"]));
+        assert!(pipeline.display(&notification).is_empty());
+        assert!(pipeline.ack(&notification).is_empty());
+
+        let mut config = config_with_sinks(&["webhook"]);
+        config.webhook_events = vec!["display".to_string(), "ACK".to_string()];
+        let mut pipeline = SinkPipeline::from_config(&config);
+        let effects = pipeline.display(&notification);
+        assert!(matches!(&effects[0], SinkEffect::WebRequest { body, .. }
+            if body.contains("\"display\"") && body.contains("Build failed")));
+        assert!(matches!(&pipeline.ack(&notification)[0], SinkEffect::WebRequest { body, .. } if body.contains("\"ack\"")));
+        assert!(pipeline.expire(&notification).is_empty());
+        // Only for notifications whose arrival was posted
+        assert!(pipeline.ack(&Notification::info("Low").for_pane(2)).is_empty());
+    }
+
+    #[test]
+    fn test_toast_sink_is_opt_in() {
+        assert!(!SinkPipeline::default().has_sink("toast"));
+        let mut config = config_with_sinks(&["toast"]);
+        assert!(!SinkPipeline::from_config(&config).has_sink("toast"));
+        config.experimental_features = vec!["toasts".to_string()];
+        let mut pipeline = SinkPipeline::from_config(&config);
+        assert_eq!(pipeline.notify(&Notification::info("No pane needed")), vec![SinkEffect::Toast]);
+    }
+
+    #[test]
+    fn test_sound_sink_plays_the_theme() {
+        let mut config = config_with_sinks(&["sound"]);
+        assert!(!SinkPipeline::from_config(&config).has_sink("sound"));
+        config.experimental_features = vec!["sounds".to_string()];
+        let mut pipeline = SinkPipeline::from_config(&config);
+        assert_eq!(pipeline.notify(&Notification::error("Build failed").for_pane(3)), vec![SinkEffect::Sound(vec![
+            "paplay".to_string(),
+            "/usr/share/sounds/freedesktop/stereo/dialog-warning.oga".to_string(),
+        ])]);
+        // The subtle theme has no sound for successes
+        assert!(pipeline.notify(&Notification::success("Deployed").for_pane(3)).is_empty());
+    }
+
+    #[test]
+    fn test_forward_sink_withdraws_and_skips_forwarded() {
+        let mut config = config_with_sinks(&["forward"]);
+        assert!(SinkPipeline::from_config(&config).names().is_empty());
+
+        config.forward_session = Some("dashboard".to_string());
+        let mut pipeline = SinkPipeline::from_config(&config);
+        let notification = Notification::error("Build failed").for_pane(3);
+        assert_eq!(pipeline.notify(&notification), vec![SinkEffect::Forward { session: "dashboard".to_string(), withdraw: false }]);
+        assert_eq!(pipeline.ack(&notification), vec![SinkEffect::Forward { session: "dashboard".to_string(), withdraw: true }]);
+
+        let mut forwarded = notification;
+        forwarded.origin_session = Some("dev".to_string());
+        assert!(pipeline.notify(&forwarded).is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_osc() {
+        assert_eq!(sanitize_osc("a;b\x07c\x1bd"), "abcd");
+    }
+}
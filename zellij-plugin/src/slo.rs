@@ -0,0 +1,118 @@
+//! Response-time SLO module for Zellij Visual Notifications
+//!
+//! Users can set how quickly notifications of a type should be acknowledged
+//! (`slo { attention 300000 }`). A notification still unacknowledged past its target is marked
+//! as an SLO breach (and optionally escalated out of terminal), and response times are collected
+//! per type for the `slo` pipe command. Useful when watching several autonomous Claude sessions.
+
+use std::collections::{BTreeMap, BTreeSet};
+use crate::notification::NotificationType;
+
+/// Response statistics for one notification type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SloStats {
+    /// Notifications acknowledged
+    pub acknowledged: u64,
+    /// Notifications that crossed their target
+    pub breached: u64,
+    /// Sum of response times of acknowledged notifications (ms)
+    pub total_response_ms: u64,
+    /// Slowest response time (ms)
+    pub max_response_ms: u64,
+}
+
+impl SloStats {
+    /// Average response time (ms)
+    pub fn average_response_ms(&self) -> Option<u64> {
+        (self.acknowledged > 0).then(|| self.total_response_ms / self.acknowledged)
+    }
+}
+
+/// Breach detection and response statistics
+#[derive(Debug, Clone, Default)]
+pub struct SloTracker {
+    /// Notifications currently breaching their target
+    breached: BTreeSet<String>,
+    /// Statistics per notification type name
+    stats: BTreeMap<&'static str, SloStats>,
+}
+
+impl SloTracker {
+    /// Record a breach if `waited_ms` exceeds the target; true the first time a notification breaches
+    pub fn check(&mut self, id: &str, notification_type: &NotificationType, waited_ms: u64, target_ms: u64) -> bool {
+        if waited_ms < target_ms || self.breached.contains(id) {
+            return false;
+        }
+        self.breached.insert(id.to_string());
+        self.stats.entry(notification_type.name()).or_default().breached += 1;
+        true
+    }
+
+    /// Record the acknowledgement of a notification after `response_ms`
+    pub fn acknowledge(&mut self, id: &str, notification_type: &NotificationType, response_ms: u64) {
+        self.breached.remove(id);
+        let stats = self.stats.entry(notification_type.name()).or_default();
+        stats.acknowledged += 1;
+        stats.total_response_ms += response_ms;
+        stats.max_response_ms = stats.max_response_ms.max(response_ms);
+    }
+
+    /// Forget breaches of notifications that left without acknowledgement (expired, cancelled)
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.breached.retain(|id| keep(id));
+    }
+
+    /// One line per type with a target, e.g. `attention  target 5m  acked 4  avg 1m12s  max 6m3s  breaches 1`
+    pub fn lines(&self, targets: &BTreeMap<String, u64>) -> Vec<String> {
+        targets.iter()
+            .map(|(type_name, target_ms)| {
+                let stats = self.stats.get(type_name.as_str()).cloned().unwrap_or_default();
+                format!("{:<10} target {}  acked {}  avg {}  max {}  breaches {}",
+                    type_name,
+                    format_ms(*target_ms),
+                    stats.acknowledged,
+                    stats.average_response_ms().map(format_ms).unwrap_or_else(|| "-".to_string()),
+                    if stats.acknowledged > 0 { format_ms(stats.max_response_ms) } else { "-".to_string() },
+                    stats.breached)
+            })
+            .collect()
+    }
+}
+
+/// Format a duration as `45s`, `5m`, `1m12s` or `2h`
+fn format_ms(ms: u64) -> String {
+    let secs = ms / 1000;
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, 0) => format!("{}m", m),
+        (0, m, s) => format!("{}m{}s", m, s),
+        (h, 0, _) => format!("{}h", h),
+        (h, m, _) => format!("{}h{}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breach_and_response_stats() {
+        let mut tracker = SloTracker::default();
+        let attention = NotificationType::Attention;
+        assert!(!tracker.check("a", &attention, 1_000, 300_000));
+        assert!(tracker.check("a", &attention, 300_000, 300_000));
+        assert!(!tracker.check("a", &attention, 400_000, 300_000));
+        assert!(tracker.breached.contains("a"));
+
+        tracker.acknowledge("a", &attention, 360_000);
+        tracker.acknowledge("b", &attention, 60_000);
+        assert!(!tracker.breached.contains("a"));
+
+        let stats = &tracker.stats["attention"];
+        assert_eq!((stats.acknowledged, stats.breached), (2, 1));
+        assert_eq!(stats.average_response_ms(), Some(210_000));
+
+        let targets = BTreeMap::from([("attention".to_string(), 300_000)]);
+        assert_eq!(tracker.lines(&targets), vec!["attention  target 5m  acked 2  avg 3m30s  max 6m  breaches 1"]);
+    }
+}
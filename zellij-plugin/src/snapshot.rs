@@ -0,0 +1,52 @@
+//! Pane snapshot module for Zellij Visual Notifications
+//!
+//! On opt-in, Error notifications for a pane get the last lines of that pane's screen attached
+//! to their history entry, so the output that caused them can still be read after the pane has
+//! scrolled on. The screen is dumped with `zellij action dump-screen` to a temporary file (in
+//! `temp_dir`), which is printed and removed in the same command; the result comes back as a
+//! `RunCommandResult`.
+
+/// Context key marking snapshot command results (value: notification ID)
+pub const SNAPSHOT_CONTEXT_KEY: &str = "snapshot";
+
+/// Most lines a snapshot keeps
+pub const MAX_SNAPSHOT_LINES: usize = 200;
+
+/// Shell command dumping the focused pane's screen to a temporary file, printing and removing it
+pub fn capture_command(notification_id: &str, temp_dir: &str) -> Vec<String> {
+    let id: String = notification_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        "zellij action dump-screen \"$1\" && cat \"$1\"; rm -f \"$1\"".to_string(),
+        "sh".to_string(),
+        format!("{}/zellij-notify-snapshot-{}.txt", temp_dir.trim_end_matches('/'), id),
+    ]
+}
+
+/// The last `lines` lines of a screen dump, without the blank rows below the output
+pub fn last_lines(dump: &[u8], lines: usize) -> Vec<String> {
+    let text = String::from_utf8_lossy(dump);
+    let mut all: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while all.last().is_some_and(|line| line.is_empty()) {
+        all.pop();
+    }
+    let start = all.len().saturating_sub(lines.min(MAX_SNAPSHOT_LINES));
+    all[start..].iter().map(|line| line.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_lines_skip_trailing_blank_rows() {
+        let dump = b"$ cargo test\ncompiling\nerror[E0308]: mismatched types   \n\n\n";
+        assert_eq!(last_lines(dump, 2), vec!["compiling", "error[E0308]: mismatched types"]);
+        assert_eq!(last_lines(b"", 5), Vec::<String>::new());
+        assert_eq!(capture_command("a/b", "/tmp")[4], "/tmp/zellij-notify-snapshot-a_b.txt");
+    }
+}
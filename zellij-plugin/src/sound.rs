@@ -0,0 +1,114 @@
+//! Sound theme module for Zellij Visual Notifications
+//!
+//! The `sound` sink plays a sound per notification type through an external player on the
+//! host. Which sound goes with which type comes from a sound theme: a named set of per-type
+//! entries, picked with `sound_theme` and switched at runtime with the `sound_theme <name>`
+//! pipe command. Two themes are built in, `subtle` (errors and attention requests only) and
+//! `loud` (every type but progress), both using the freedesktop sound theme files; packs defined
+//! in the configuration add to them or replace them by name. An entry without spaces is a sound
+//! file played with `sound_player`; anything else is run as a shell command, e.g. `say done`.
+
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+use crate::notification::NotificationType;
+
+/// Run command context key marking sound player runs
+pub const SOUND_CONTEXT_KEY: &str = "sound";
+
+/// Directory of the freedesktop sound theme files used by the built-in themes
+const FREEDESKTOP_SOUNDS: &str = "/usr/share/sounds/freedesktop/stereo";
+
+/// Built-in sound themes: name and (type, freedesktop sound) entries
+const BUILTIN_THEMES: &[(&str, &[(&str, &str)])] = &[
+    ("subtle", &[
+        ("error", "dialog-warning"),
+        ("attention", "message"),
+    ]),
+    ("loud", &[
+        ("error", "suspend-error"),
+        ("warning", "dialog-warning"),
+        ("attention", "phone-incoming-call"),
+        ("success", "complete"),
+        ("info", "message-new-instant"),
+    ]),
+];
+
+/// A named set of sounds per notification type
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SoundTheme {
+    /// Theme name
+    pub name: String,
+    /// Sound file or shell command by type name
+    pub sounds: BTreeMap<String, String>,
+}
+
+impl SoundTheme {
+    /// Look up a theme: a configured pack, else a built-in theme
+    pub fn find(name: &str, packs: &BTreeMap<String, BTreeMap<String, String>>) -> Option<Self> {
+        if let Some(sounds) = packs.get(name) {
+            return Some(Self { name: name.to_string(), sounds: sounds.clone() });
+        }
+        BUILTIN_THEMES.iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(builtin, entries)| Self {
+                name: builtin.to_string(),
+                sounds: entries.iter()
+                    .map(|(type_name, sound)| (type_name.to_string(), format!("{}/{}.oga", FREEDESKTOP_SOUNDS, sound)))
+                    .collect(),
+            })
+    }
+
+    /// The configured theme
+    pub fn from_config(config: &Config) -> Option<Self> {
+        Self::find(&config.sound_theme, &config.sound_packs)
+    }
+
+    /// Command line playing the sound for a type, if the theme has one
+    pub fn command(&self, notification_type: &NotificationType, player: &str) -> Option<Vec<String>> {
+        let sound = self.sounds.get(notification_type.name())?;
+        if sound.contains(char::is_whitespace) {
+            return Some(vec!["sh".to_string(), "-c".to_string(), sound.clone()]);
+        }
+        let mut command: Vec<String> = player.split_whitespace().map(str::to_string).collect();
+        command.push(sound.clone());
+        Some(command)
+    }
+}
+
+/// Names of the available themes (built-in and configured), sorted
+pub fn theme_names(packs: &BTreeMap<String, BTreeMap<String, String>>) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|(name, _)| name.to_string())
+        .chain(packs.keys().cloned())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_themes_resolve_to_commands() {
+        let subtle = SoundTheme::find("subtle", &BTreeMap::new()).unwrap();
+        assert_eq!(subtle.command(&NotificationType::Error, "paplay"),
+            Some(vec!["paplay".to_string(), "/usr/share/sounds/freedesktop/stereo/dialog-warning.oga".to_string()]));
+        assert_eq!(subtle.command(&NotificationType::Success, "paplay"), None);
+        assert!(SoundTheme::find("quiet", &BTreeMap::new()).is_none());
+
+        // A configured pack replaces the built-in theme of the same name; commands run in sh
+        let packs = BTreeMap::from([("loud".to_string(), BTreeMap::from([
+            ("success".to_string(), "say done".to_string()),
+            ("error".to_string(), "/home/me/fail.wav".to_string()),
+        ]))]);
+        let loud = SoundTheme::find("loud", &packs).unwrap();
+        assert_eq!(loud.command(&NotificationType::Success, "paplay"),
+            Some(vec!["sh".to_string(), "-c".to_string(), "say done".to_string()]));
+        assert_eq!(loud.command(&NotificationType::Error, "aplay -q"),
+            Some(vec!["aplay".to_string(), "-q".to_string(), "/home/me/fail.wav".to_string()]));
+        assert_eq!(loud.command(&NotificationType::Warning, "paplay"), None);
+        assert_eq!(theme_names(&packs), vec!["loud", "subtle"]);
+    }
+}
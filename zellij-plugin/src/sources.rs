@@ -0,0 +1,191 @@
+//! Source statistics module for Zellij Visual Notifications
+//!
+//! Counts notifications per source over rolling windows (the last minute and the last hour) and
+//! flags sources that send more than `noisy_source_threshold` notifications a minute. The noisiest
+//! one is suggested for muting in the diagnostics view, where `n` applies the suggestion as a
+//! runtime `drop` rule - the same thing `config_set rule_<name> source=<source> -> drop` does.
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// Default notifications per minute above which a source is noisy (0 = off)
+pub const DEFAULT_NOISY_SOURCE_THRESHOLD: u32 = 20;
+
+/// Width of one counting bucket (ms)
+const BUCKET_MS: u64 = 10_000;
+
+/// Short window, used to detect noisy sources (ms)
+const MINUTE_MS: u64 = 60_000;
+
+/// Long window (ms)
+const HOUR_MS: u64 = 3_600_000;
+
+/// Sources tracked at once; the one quiet for longest is dropped first
+pub const MAX_TRACKED_SOURCES: usize = 50;
+
+/// Sources listed in the stats
+const TOP_SOURCES: usize = 5;
+
+/// A source sending more than the threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoisySource {
+    /// Source name
+    pub source: String,
+    /// Notifications in the last minute
+    pub per_minute: u32,
+}
+
+impl NoisySource {
+    /// Name of the rule that mutes it (`mute_<source>`, characters other than letters, digits,
+    /// `-` and `_` replaced)
+    pub fn rule_name(&self) -> String {
+        let name: String = self.source.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        format!("mute_{}", name)
+    }
+
+    /// Rule spec that drops its notifications, or None when the source can't be matched on its
+    /// own (rule filters are whitespace-separated)
+    pub fn rule_spec(&self) -> Option<String> {
+        (!self.source.is_empty() && !self.source.contains(char::is_whitespace))
+            .then(|| format!("source={} -> drop", self.source))
+    }
+
+    /// Suggestion line, e.g. `Noisy source: ci (32/min) - n mutes it`
+    pub fn suggestion(&self) -> String {
+        match self.rule_spec() {
+            Some(_) => format!("Noisy source: {} ({}/min) - n mutes it", self.source, self.per_minute),
+            None => format!("Noisy source: {} ({}/min) - mute it with a rule", self.source, self.per_minute),
+        }
+    }
+}
+
+/// Rolling per-source counts
+#[derive(Debug, Clone, Default)]
+pub struct SourceStats {
+    /// Buckets `(start ms, count)` per source, oldest first
+    buckets: BTreeMap<String, VecDeque<(u64, u32)>>,
+}
+
+impl SourceStats {
+    /// Count a notification from `source`
+    pub fn record(&mut self, source: &str, now_ms: u64) {
+        self.prune(now_ms);
+        if !self.buckets.contains_key(source) && self.buckets.len() >= MAX_TRACKED_SOURCES {
+            let quietest = self.buckets.iter()
+                .min_by_key(|(_, buckets)| buckets.back().map(|(start, _)| *start))
+                .map(|(name, _)| name.clone());
+            if let Some(name) = quietest {
+                self.buckets.remove(&name);
+            }
+        }
+        let start = now_ms - now_ms % BUCKET_MS;
+        let buckets = self.buckets.entry(source.to_string()).or_default();
+        match buckets.back_mut() {
+            Some((last, count)) if *last == start => *count += 1,
+            _ => buckets.push_back((start, 1)),
+        }
+    }
+
+    /// Drop buckets that left the long window, and sources without any
+    fn prune(&mut self, now_ms: u64) {
+        for buckets in self.buckets.values_mut() {
+            while buckets.front().is_some_and(|(start, _)| start + BUCKET_MS + HOUR_MS <= now_ms) {
+                buckets.pop_front();
+            }
+        }
+        self.buckets.retain(|_, buckets| !buckets.is_empty());
+    }
+
+    /// Notifications from `source` in the last `window_ms` (whole buckets)
+    fn count(&self, source: &str, window_ms: u64, now_ms: u64) -> u32 {
+        self.buckets.get(source).map_or(0, |buckets| {
+            buckets.iter()
+                .filter(|(start, _)| start + BUCKET_MS + window_ms > now_ms)
+                .map(|(_, count)| count)
+                .sum()
+        })
+    }
+
+    /// Sources above `threshold` notifications in the last minute, noisiest first
+    pub fn noisy(&self, threshold: u32, now_ms: u64) -> Vec<NoisySource> {
+        if threshold == 0 {
+            return Vec::new();
+        }
+        let mut noisy: Vec<NoisySource> = self.buckets.keys()
+            .map(|source| NoisySource { source: source.clone(), per_minute: self.count(source, MINUTE_MS, now_ms) })
+            .filter(|noisy| noisy.per_minute > threshold)
+            .collect();
+        noisy.sort_by(|a, b| b.per_minute.cmp(&a.per_minute).then(a.source.cmp(&b.source)));
+        noisy
+    }
+
+    /// Sources with notifications in the last hour, by name
+    pub fn sources(&self, now_ms: u64) -> Vec<String> {
+        self.buckets.keys()
+            .filter(|source| self.count(source, HOUR_MS, now_ms) > 0)
+            .cloned()
+            .collect()
+    }
+
+    /// Forget a source (after it was muted)
+    pub fn forget(&mut self, source: &str) {
+        self.buckets.remove(source);
+    }
+
+    /// Stats line with the busiest sources of the last hour, e.g. `sources (1m/1h): ci 32/120, tests 0/4`
+    pub fn line(&self, now_ms: u64) -> String {
+        let mut sources: Vec<(&String, u32, u32)> = self.buckets.keys()
+            .map(|source| (source, self.count(source, MINUTE_MS, now_ms), self.count(source, HOUR_MS, now_ms)))
+            .filter(|(_, _, hour)| *hour > 0)
+            .collect();
+        sources.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(b.0)));
+        let top: Vec<String> = sources.iter().take(TOP_SOURCES)
+            .map(|(source, minute, hour)| format!("{} {}/{}", source, minute, hour))
+            .collect();
+        format!("sources (1m/1h): {}", if top.is_empty() { "-".to_string() } else { top.join(", ") })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_windows_flag_noisy_sources() {
+        let mut stats = SourceStats::default();
+        for i in 0..25 {
+            stats.record("ci", 1000 + i * 1000);
+        }
+        stats.record("tests", 5000);
+        assert_eq!(stats.noisy(20, 26_000), vec![NoisySource { source: "ci".to_string(), per_minute: 25 }]);
+        assert!(stats.noisy(0, 26_000).is_empty());
+        assert_eq!(stats.line(26_000), "sources (1m/1h): ci 25/25, tests 1/1");
+
+        // The minute window moves on, the hour window keeps counting
+        assert!(stats.noisy(20, 200_000).is_empty());
+        assert_eq!(stats.line(200_000), "sources (1m/1h): ci 0/25, tests 0/1");
+        assert_eq!(stats.line(HOUR_MS + 40_000), "sources (1m/1h): -");
+        stats.record("tests", HOUR_MS + 40_000);
+        assert_eq!(stats.buckets.len(), 1);
+    }
+
+    #[test]
+    fn test_suggested_mute_rule() {
+        let noisy = NoisySource { source: "CI/nightly".to_string(), per_minute: 32 };
+        assert_eq!(noisy.rule_name(), "mute_ci_nightly");
+        assert_eq!(noisy.rule_spec().as_deref(), Some("source=CI/nightly -> drop"));
+        assert_eq!(noisy.suggestion(), "Noisy source: CI/nightly (32/min) - n mutes it");
+
+        let spaced = NoisySource { source: "my hook".to_string(), per_minute: 21 };
+        assert!(spaced.rule_spec().is_none());
+        assert_eq!(spaced.suggestion(), "Noisy source: my hook (21/min) - mute it with a rule");
+
+        let mut stats = SourceStats::default();
+        for i in 0..MAX_TRACKED_SOURCES as u64 + 1 {
+            stats.record(&format!("source-{}", i), i * BUCKET_MS);
+        }
+        assert_eq!(stats.buckets.len(), MAX_TRACKED_SOURCES);
+        assert!(!stats.buckets.contains_key("source-0"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Rate history module for Zellij Visual Notifications
+//!
+//! Counts notifications per type in five-minute buckets over the last hour and draws the counts as
+//! sparklines (`▁▂▃▅▇`), oldest bucket on the left. The `stats` command shows one line per type
+//! seen in the hour, and the optional `rate` status bar segment shows all types together, giving an
+//! at-a-glance sense of how noisy the session has been.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::notification::NotificationType;
+
+/// Width of one bucket, and of one sparkline column (ms)
+const BUCKET_MS: u64 = 300_000;
+
+/// Buckets in the window (one hour)
+pub const RATE_BUCKETS: usize = 12;
+
+/// Sparkline levels, lowest first
+const LEVELS: [char; 8] = ['\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
+
+/// Sparkline levels without unicode block elements
+const ASCII_LEVELS: [char; 8] = ['_', '.', '-', ':', '=', '+', '*', '#'];
+
+/// Rolling per-type counts over the last hour
+#[derive(Debug, Clone, Default)]
+pub struct RateHistory {
+    /// Buckets `(start ms, count)` per type name, oldest first
+    buckets: BTreeMap<&'static str, VecDeque<(u64, u32)>>,
+}
+
+impl RateHistory {
+    /// Count a notification of `notification_type`
+    pub fn record(&mut self, notification_type: &NotificationType, now_ms: u64) {
+        self.prune(now_ms);
+        let start = now_ms - now_ms % BUCKET_MS;
+        let buckets = self.buckets.entry(notification_type.name()).or_default();
+        match buckets.back_mut() {
+            Some((last, count)) if *last == start => *count += 1,
+            _ => buckets.push_back((start, 1)),
+        }
+    }
+
+    /// Drop buckets that left the window, and types without any
+    fn prune(&mut self, now_ms: u64) {
+        for buckets in self.buckets.values_mut() {
+            while buckets.front().is_some_and(|(start, _)| Self::age(*start, now_ms) >= RATE_BUCKETS) {
+                buckets.pop_front();
+            }
+        }
+        self.buckets.retain(|_, buckets| !buckets.is_empty());
+    }
+
+    /// Buckets between the one starting at `start` and the current one
+    fn age(start: u64, now_ms: u64) -> usize {
+        ((now_ms - now_ms % BUCKET_MS).saturating_sub(start) / BUCKET_MS) as usize
+    }
+
+    /// Counts per bucket of the types `matches` selects, oldest first and the current one last
+    fn series(&self, matches: impl Fn(&str) -> bool, now_ms: u64) -> [u32; RATE_BUCKETS] {
+        let mut series = [0; RATE_BUCKETS];
+        for (_, buckets) in self.buckets.iter().filter(|(name, _)| matches(name)) {
+            for (start, count) in buckets.iter().filter(|(start, _)| *start <= now_ms) {
+                let age = Self::age(*start, now_ms);
+                if age < RATE_BUCKETS {
+                    series[RATE_BUCKETS - 1 - age] += count;
+                }
+            }
+        }
+        series
+    }
+
+    /// Sparkline of all types over the last hour, None when nothing arrived in it
+    pub fn total(&self, unicode: bool, now_ms: u64) -> Option<String> {
+        let series = self.series(|_| true, now_ms);
+        series.iter().any(|count| *count > 0).then(|| sparkline(&series, unicode))
+    }
+
+    /// Stats lines, one per type seen in the last hour, after a header,
+    /// e.g. `  error     ▁▁▃█▂▁▁▁▁▁▁▁ 14/h`
+    pub fn lines(&self, unicode: bool, now_ms: u64) -> Vec<String> {
+        let rows: Vec<(&str, [u32; RATE_BUCKETS])> = self.buckets.keys()
+            .map(|name| (*name, self.series(|other| other == *name, now_ms)))
+            .filter(|(_, series)| series.iter().any(|count| *count > 0))
+            .collect();
+        if rows.is_empty() {
+            return vec!["rate (1h): -".to_string()];
+        }
+        let mut lines = vec![format!("rate (1h, {}m per column):", BUCKET_MS / 60_000)];
+        lines.extend(rows.iter().map(|(name, series)| {
+            format!("  {:<9} {} {}/h", name, sparkline(series, unicode), series.iter().sum::<u32>())
+        }));
+        lines
+    }
+}
+
+/// Draw counts as a sparkline scaled to the largest; empty buckets use the lowest level
+pub fn sparkline(counts: &[u32], unicode: bool) -> String {
+    let levels = if unicode { &LEVELS } else { &ASCII_LEVELS };
+    let max = counts.iter().copied().max().unwrap_or(0) as u64;
+    counts.iter()
+        .map(|count| match *count as u64 {
+            0 => levels[0],
+            count => levels[(count * 7).div_ceil(max).min(7) as usize],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline_levels() {
+        assert_eq!(sparkline(&[0, 1, 4, 7], true), "\u{2581}\u{2582}\u{2585}\u{2588}");
+        assert_eq!(sparkline(&[0, 1, 4, 7], false), "_.=#");
+        assert_eq!(sparkline(&[0, 3, 3], true), "\u{2581}\u{2588}\u{2588}");
+        assert_eq!(sparkline(&[0, 0], false), "__");
+    }
+
+    #[test]
+    fn test_hourly_buckets_per_type() {
+        let mut history = RateHistory::default();
+        assert_eq!(history.total(true, 0), None);
+        assert_eq!(history.lines(false, 0), vec!["rate (1h): -"]);
+
+        for _ in 0..3 {
+            history.record(&NotificationType::Error, 1_000);
+        }
+        history.record(&NotificationType::Success, 1_000);
+        history.record(&NotificationType::Success, BUCKET_MS + 1_000);
+        let now = BUCKET_MS * 2;
+        assert_eq!(history.lines(false, now), vec![
+            "rate (1h, 5m per column):".to_string(),
+            "  error     _________#__ 3/h".to_string(),
+            "  success   _________##_ 2/h".to_string(),
+        ]);
+        assert_eq!(history.total(false, now).as_deref(), Some("_________#-_"));
+
+        // An hour later the old buckets have left the window
+        let later = now + BUCKET_MS * RATE_BUCKETS as u64;
+        assert_eq!(history.total(false, later), None);
+        history.record(&NotificationType::Info, later);
+        assert_eq!(history.buckets.len(), 1);
+    }
+}
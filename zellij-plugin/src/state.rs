@@ -1,10 +1,23 @@
 //! State management module for Zellij Visual Notifications
 //!
 //! Manages visual states for panes and the overall plugin state machine.
+//!
+//! Notifications arriving in quick succession can replace each other on a pane before the user
+//! has registered them. With `min_display_ms` set for its type, a shown notification keeps its
+//! pane for that long: a replacement of the same or lower priority waits behind it and is shown
+//! once the time is up (one at a time, each keeping the pane for its own minimum), while one of
+//! strictly higher priority takes the pane right away.
+
+use std::collections::{BTreeMap, VecDeque};
 
 use serde::{Deserialize, Serialize};
+use crate::animation::Frame;
+use crate::attention_queue::AttentionSlot;
+use crate::automation::AutomationTrigger;
 use crate::config::AnimationStyle;
-use crate::notification::NotificationType;
+use crate::notification::{Notification, NotificationType, Priority};
+use crate::progress::ProgressEstimate;
+use crate::surface::Surface;
 
 /// Plugin lifecycle state
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -24,6 +37,50 @@ pub enum PluginState {
     ShuttingDown,
 }
 
+/// Transient interaction mode for plugin-local key handling
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum InteractionMode {
+    /// Keys are handled normally
+    #[default]
+    Normal,
+    /// Waiting for y/n confirmation before clearing all notifications
+    ConfirmClearAll {
+        /// Number of notifications that will be cleared
+        count: usize,
+        /// Plugin time at which the prompt is dismissed (ms)
+        deadline_ms: u64,
+    },
+    /// Waiting for the type key of an acknowledge-by-type chord (`a` then `e`, `s`, ...)
+    AckTypeChord {
+        /// Plugin time at which the chord is abandoned (ms)
+        deadline_ms: u64,
+    },
+    /// Waiting for the action key of a leader chord (`chord_leader`, then `a`, `p`, ...)
+    Chord {
+        /// Plugin time at which the chord is abandoned (ms)
+        deadline_ms: u64,
+    },
+    /// Waiting for y/n confirmation before an `on_notify` automation acts
+    ConfirmAutomation {
+        /// Automation waiting to act
+        trigger: AutomationTrigger,
+        /// Plugin time at which the prompt is dismissed (ms)
+        deadline_ms: u64,
+    },
+}
+
+/// Where a pane is relative to the tab on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TabLocality {
+    /// Not known yet (or the focused-tab boost is off)
+    #[default]
+    Unknown,
+    /// In the active tab: actionable right now
+    Active,
+    /// In a tab that is not shown
+    Hidden,
+}
+
 /// Visual state for a single pane
 #[derive(Debug, Clone, Default)]
 pub struct VisualState {
@@ -37,6 +94,10 @@ pub struct VisualState {
     pub is_animating: bool,
     /// Animation start tick
     pub animation_start_tick: u64,
+    /// Plugin time the animation started (ms)
+    pub animation_start_ms: u64,
+    /// Frame the animation was paused at while its pane's tab is not shown
+    pub animation_paused_at: Option<Frame>,
     /// Current animation phase (0.0 - 1.0)
     pub animation_phase: f32,
     /// Animation style for this notification
@@ -45,12 +106,54 @@ pub struct VisualState {
     pub notification_message: Option<String>,
     /// Notification type
     pub notification_type: Option<NotificationType>,
+    /// Priority of the displayed notification (drives color intensity)
+    pub priority: Priority,
+    /// Sender-provided color override
+    pub custom_color: Option<String>,
+    /// Sender-provided icon override
+    pub custom_icon: Option<String>,
+    /// Surfaces selected for signaling (empty until a selection is made)
+    pub surfaces: Vec<Surface>,
+    /// Whether visual updates for this pane are muted (survives clear)
+    pub muted: bool,
     /// Timestamp when notification was received
     pub notification_timestamp: u64,
+    /// ID of the displayed notification (status entry cache)
+    pub notification_id: Option<String>,
     /// Whether the notification has been acknowledged
     pub acknowledged: bool,
     /// Brightness multiplier for animation (0.0 - 1.0)
     pub brightness: f32,
+    /// Progress snapshot (percent, elapsed, ETA), refreshed each tick
+    pub progress: Option<ProgressEstimate>,
+    /// Channel of the displayed notification
+    pub channel: Option<String>,
+    /// Whether the displayed notification's channel is hidden (still counted)
+    pub hidden: bool,
+    /// Whether the displayed notification comes from another project (still counted)
+    pub other_project: bool,
+    /// Whether the pane runs Claude Code (own accent, listed first; kept across clears)
+    pub claude_pane: bool,
+    /// Whether the displayed notification is pinned (no TTL, survives clear-all, listed first)
+    pub pinned: bool,
+    /// Correlation ID of the displayed notification (shared accent, group view)
+    pub correlation_id: Option<String>,
+    /// Branch (or worktree) label of the displayed notification, shown after the entry
+    pub branch: Option<String>,
+    /// Whether the displayed notification stayed unacknowledged past its response-time SLO
+    pub slo_breached: bool,
+    /// Plugin time the displayed notification's response deadline passes (ms), if it has one
+    pub deadline_at_ms: Option<u64>,
+    /// Whether the deadline passed unacknowledged (flashing entry, overdue pattern)
+    pub overdue: bool,
+    /// Whether only the badge is shown (demoted on expiry: no border, no animation)
+    pub badge_only: bool,
+    /// Quick reply options of the displayed notification, shown numbered after the message
+    pub reply_options: Vec<String>,
+    /// Whether the pane is in the active tab (adjusts the display priority)
+    pub tab_locality: TabLocality,
+    /// Place of the notification in the attention queue (waiting ones are counted, not drawn)
+    pub attention_slot: AttentionSlot,
 }
 
 impl VisualState {
@@ -62,13 +165,36 @@ impl VisualState {
             badge_icon: None,
             is_animating: false,
             animation_start_tick: 0,
+            animation_start_ms: 0,
+            animation_paused_at: None,
             animation_phase: 0.0,
             animation_style: AnimationStyle::Pulse,
             notification_message: None,
             notification_type: None,
+            priority: Priority::Normal,
+            custom_color: None,
+            custom_icon: None,
+            surfaces: Vec::new(),
+            muted: false,
             notification_timestamp: 0,
+            notification_id: None,
             acknowledged: false,
             brightness: 1.0,
+            progress: None,
+            channel: None,
+            hidden: false,
+            other_project: false,
+            claude_pane: false,
+            pinned: false,
+            correlation_id: None,
+            branch: None,
+            slo_breached: false,
+            deadline_at_ms: None,
+            overdue: false,
+            badge_only: false,
+            reply_options: Vec::new(),
+            tab_locality: TabLocality::Unknown,
+            attention_slot: AttentionSlot::None,
         }
     }
 
@@ -78,11 +204,43 @@ impl VisualState {
         self.border_color = None;
         self.badge_icon = None;
         self.is_animating = false;
+        self.animation_paused_at = None;
         self.animation_phase = 0.0;
         self.notification_message = None;
         self.notification_type = None;
+        self.priority = Priority::Normal;
+        self.custom_color = None;
+        self.custom_icon = None;
+        self.surfaces.clear();
+        self.notification_id = None;
         self.acknowledged = false;
         self.brightness = 1.0;
+        self.progress = None;
+        self.channel = None;
+        self.hidden = false;
+        self.other_project = false;
+        self.pinned = false;
+        self.correlation_id = None;
+        self.branch = None;
+        self.slo_breached = false;
+        self.deadline_at_ms = None;
+        self.overdue = false;
+        self.badge_only = false;
+        self.reply_options.clear();
+        self.attention_slot = AttentionSlot::None;
+    }
+
+    /// Check if a surface is used for this state (all surfaces apply before a selection is made)
+    pub fn uses_surface(&self, surface: Surface) -> bool {
+        if self.badge_only && surface == Surface::Border {
+            return false;
+        }
+        self.surfaces.is_empty() || self.surfaces.contains(&surface)
+    }
+
+    /// Check if the pane is signaled through fallback surfaces (collapsed or hidden pane)
+    pub fn is_fallback(&self) -> bool {
+        !self.surfaces.is_empty() && !self.surfaces.contains(&Surface::Border)
     }
 
     /// Check if this state has an active notification
@@ -90,6 +248,47 @@ impl VisualState {
         self.notification_type.is_some() && !self.acknowledged
     }
 
+    /// Check if the notification was marked read (kept as a dimmed badge until dismissed)
+    pub fn is_read(&self) -> bool {
+        self.state == VisualNotificationState::Read
+    }
+
+    /// Check if this state has an active notification that has not been read yet
+    pub fn is_unread(&self) -> bool {
+        self.has_notification() && !self.is_read()
+    }
+
+    /// Check if the pane shows a Progress notification that has not completed yet
+    pub fn is_spinning(&self) -> bool {
+        self.is_shown()
+            && !self.is_read()
+            && self.notification_type == Some(NotificationType::Progress)
+            && self.progress.as_ref().is_none_or(|p| p.percent < 100.0)
+    }
+
+    /// Check if this state has an active notification on a visible channel
+    pub fn is_shown(&self) -> bool {
+        self.has_notification() && self.is_listed()
+    }
+
+    /// Check if the entry is listed in the status bar rather than only counted (hidden channel,
+    /// other project, waiting in the attention queue)
+    pub fn is_listed(&self) -> bool {
+        !self.hidden && !self.other_project && self.attention_slot != AttentionSlot::Waiting
+    }
+
+    /// Priority used for ordering: one step up in the active tab, one step down in a hidden tab
+    /// (Critical is never lowered). The notification's own priority is left untouched, so
+    /// switching tabs back and forth restores the previous order.
+    pub fn display_priority(&self) -> Priority {
+        match self.tab_locality {
+            TabLocality::Unknown => self.priority,
+            TabLocality::Active => self.priority.raised().unwrap_or(self.priority),
+            TabLocality::Hidden if self.priority == Priority::Critical => self.priority,
+            TabLocality::Hidden => self.priority.lowered().unwrap_or(self.priority),
+        }
+    }
+
     /// Set the notification state
     pub fn set_notification(
         &mut self,
@@ -107,6 +306,45 @@ impl VisualState {
         self.brightness = 1.0;
     }
 
+    /// Lines describing the state for `debug_pane`, e.g. `state: Active (error, high priority)`
+    pub fn debug_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("state: {} ({}, {} priority{})",
+            self.state.display_name(),
+            self.notification_type.as_ref().map(|t| t.name()).unwrap_or("no notification"),
+            self.priority.name(),
+            if self.acknowledged { ", acknowledged" } else { "" })];
+        if let Some(message) = &self.notification_message {
+            lines.push(format!("message: {}", message));
+        }
+        if let Some(id) = &self.notification_id {
+            lines.push(format!("notification: {}", id));
+        }
+        let surfaces = if self.surfaces.is_empty() {
+            "all".to_string()
+        } else {
+            self.surfaces.iter().map(|s| format!("{:?}", s)).collect::<Vec<_>>().join(",")
+        };
+        lines.push(format!("border: {}, badge: {}, surfaces: {}{}",
+            self.border_color.as_deref().unwrap_or("-"),
+            self.badge_icon.as_deref().unwrap_or("-"),
+            surfaces,
+            if self.badge_only { " (badge only)" } else { "" }));
+        let flags: Vec<&str> = [
+            (self.is_animating, "animating"),
+            (self.muted, "muted"),
+            (self.pinned, "pinned"),
+            (self.hidden, "hidden channel"),
+            (self.other_project, "other project"),
+            (self.slo_breached, "SLO breached"),
+            (self.overdue, "overdue"),
+            (self.attention_slot == AttentionSlot::Waiting, "waiting for attention"),
+        ].into_iter().filter(|(set, _)| *set).map(|(_, flag)| flag).collect();
+        if !flags.is_empty() {
+            lines.push(format!("flags: {}", flags.join(", ")));
+        }
+        lines
+    }
+
     /// Start fading animation
     pub fn start_fade(&mut self, tick: u64) {
         self.state = VisualNotificationState::Fading;
@@ -115,6 +353,14 @@ impl VisualState {
         self.animation_phase = 0.0;
     }
 
+    /// Mark the notification read: animation stops and only a dimmed badge remains
+    pub fn mark_read(&mut self) {
+        self.state = VisualNotificationState::Read;
+        self.is_animating = false;
+        self.badge_only = true;
+        self.brightness = 1.0;
+    }
+
     /// Acknowledge the notification
     pub fn acknowledge(&mut self) {
         self.acknowledged = true;
@@ -132,6 +378,8 @@ pub enum VisualNotificationState {
     Pending,
     /// Notification is active and displayed
     Active,
+    /// Notification was seen but kept as a dimmed badge until dismissed
+    Read,
     /// Notification is fading out
     Fading,
     /// Error state
@@ -151,6 +399,11 @@ impl VisualNotificationState {
             // From Active
             (VisualNotificationState::Active, VisualNotificationState::Fading) => true,
             (VisualNotificationState::Active, VisualNotificationState::Idle) => true, // Instant clear
+            (VisualNotificationState::Active, VisualNotificationState::Read) => true, // Mark read
+            // From Read
+            (VisualNotificationState::Read, VisualNotificationState::Idle) => true, // Dismiss
+            (VisualNotificationState::Read, VisualNotificationState::Fading) => true,
+            (VisualNotificationState::Read, VisualNotificationState::Active) => true, // New notification
             // From Fading
             (VisualNotificationState::Fading, VisualNotificationState::Idle) => true,
             (VisualNotificationState::Fading, VisualNotificationState::Active) => true, // New notification
@@ -170,6 +423,7 @@ impl VisualNotificationState {
             VisualNotificationState::Idle => "Idle",
             VisualNotificationState::Pending => "Pending",
             VisualNotificationState::Active => "Active",
+            VisualNotificationState::Read => "Read",
             VisualNotificationState::Fading => "Fading",
             VisualNotificationState::Error => "Error",
         }
@@ -199,49 +453,161 @@ impl StateTransition {
             reason: reason.to_string(),
         }
     }
+
+    /// Set the transition timestamp (from the plugin clock)
+    pub fn at(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
 }
 
-/// State manager for tracking multiple pane states
+/// Minimum display time of the notification shown on a pane
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DisplayHold {
+    /// Plugin time until which the notification keeps the pane (ms)
+    until_ms: u64,
+    /// Priority a replacement must exceed to take the pane earlier
+    priority: Priority,
+}
+
+/// State manager keeping each pane's recent state transitions (for `debug_pane`) and the
+/// replacements waiting for a shown notification's minimum display time
 #[derive(Debug, Default)]
 pub struct StateManager {
-    /// History of state transitions (for debugging)
-    transition_history: Vec<StateTransition>,
-    /// Maximum history size
+    /// Recent state transitions per pane, oldest first
+    transitions: BTreeMap<u32, VecDeque<StateTransition>>,
+    /// Maximum transitions retained per pane
     max_history_size: usize,
+    /// Number of transitions evicted to keep history bounded
+    evicted_count: u64,
+    /// Minimum display time of the notification shown per pane
+    holds: BTreeMap<u32, DisplayHold>,
+    /// Replacements waiting per pane, oldest first
+    waiting: BTreeMap<u32, VecDeque<Notification>>,
 }
 
 impl StateManager {
     /// Create a new state manager
     pub fn new() -> Self {
         Self {
-            transition_history: Vec::new(),
+            transitions: BTreeMap::new(),
             max_history_size: 100,
+            evicted_count: 0,
+            holds: BTreeMap::new(),
+            waiting: BTreeMap::new(),
         }
     }
 
-    /// Record a state transition
-    pub fn record_transition(&mut self, transition: StateTransition) {
-        self.transition_history.push(transition);
+    /// Set the maximum number of transitions retained per pane
+    pub fn with_max_history(mut self, max_history_size: usize) -> Self {
+        self.max_history_size = max_history_size.max(1);
+        self
+    }
+
+    /// Record a state transition of a pane
+    pub fn record_transition(&mut self, pane_id: u32, transition: StateTransition) {
+        let history = self.transitions.entry(pane_id).or_default();
+        history.push_back(transition);
 
-        // Keep history bounded
-        while self.transition_history.len() > self.max_history_size {
-            self.transition_history.remove(0);
+        // Keep each pane's history bounded
+        while history.len() > self.max_history_size {
+            history.pop_front();
+            self.evicted_count += 1;
         }
     }
 
-    /// Get recent transitions
-    pub fn recent_transitions(&self, count: usize) -> &[StateTransition] {
-        let start = if self.transition_history.len() > count {
-            self.transition_history.len() - count
+    /// Get a pane's most recent transitions, oldest first
+    pub fn recent_transitions(&self, pane_id: u32, count: usize) -> Vec<&StateTransition> {
+        let Some(history) = self.transitions.get(&pane_id) else {
+            return Vec::new();
+        };
+        history.iter().skip(history.len().saturating_sub(count)).collect()
+    }
+
+    /// Number of transitions evicted to keep history bounded
+    pub fn evicted_count(&self) -> u64 {
+        self.evicted_count
+    }
+
+    /// Drop the history of a pane that was closed
+    pub fn forget_pane(&mut self, pane_id: u32) {
+        self.transitions.remove(&pane_id);
+        self.holds.remove(&pane_id);
+    }
+
+    /// Note a notification shown on a pane, keeping the pane for `min_display_ms` (0 = no minimum)
+    pub fn shown(&mut self, pane_id: u32, priority: Priority, min_display_ms: u64, now_ms: u64) {
+        if min_display_ms > 0 {
+            self.holds.insert(pane_id, DisplayHold { until_ms: now_ms + min_display_ms, priority });
+        } else {
+            self.holds.remove(&pane_id);
+        }
+    }
+
+    /// Note that a pane's notification is gone (acknowledged, expired, cleared)
+    pub fn ended(&mut self, pane_id: u32) {
+        self.holds.remove(&pane_id);
+    }
+
+    /// Whether a notification may be shown on a pane now; if not, it waits behind the shown one
+    /// and the replacements already waiting
+    pub fn admit(&mut self, pane_id: u32, notification: &Notification, now_ms: u64) -> bool {
+        let hold = self.holds.get(&pane_id).copied();
+        let queue = self.waiting.entry(pane_id).or_default();
+        let admitted = if hold.is_some_and(|hold| notification.priority > hold.priority) {
+            // Strictly more urgent: takes the pane at once
+            queue.retain(|waiting| waiting.id != notification.id);
+            true
+        } else if hold.is_none_or(|hold| now_ms >= hold.until_ms)
+            && queue.front().is_none_or(|first| first.id == notification.id)
+        {
+            queue.pop_front();
+            true
         } else {
-            0
+            if !queue.iter().any(|waiting| waiting.id == notification.id) {
+                queue.push_back(notification.clone());
+            }
+            false
         };
-        &self.transition_history[start..]
+        if queue.is_empty() {
+            self.waiting.remove(&pane_id);
+        }
+        admitted
+    }
+
+    /// Replacements whose turn has come: the first one waiting on each pane that is free
+    pub fn due(&self, now_ms: u64) -> Vec<Notification> {
+        self.waiting.iter()
+            .filter(|(pane_id, _)| self.holds.get(pane_id).is_none_or(|hold| now_ms >= hold.until_ms))
+            .filter_map(|(_, queue)| queue.front().cloned())
+            .collect()
+    }
+
+    /// A waiting replacement by ID
+    pub fn find_waiting(&self, id: &str) -> Option<&Notification> {
+        self.waiting.values().flatten().find(|notification| notification.id == id)
+    }
+
+    /// Stop the waiting replacements `matches` selects, returning them
+    pub fn take_waiting(&mut self, matches: impl Fn(&Notification) -> bool) -> Vec<Notification> {
+        let mut taken = Vec::new();
+        for queue in self.waiting.values_mut() {
+            let (matching, rest): (VecDeque<_>, VecDeque<_>) = std::mem::take(queue).into_iter().partition(|n| matches(n));
+            taken.extend(matching);
+            *queue = rest;
+        }
+        self.waiting.retain(|_, queue| !queue.is_empty());
+        taken
+    }
+
+    /// Number of replacements waiting
+    pub fn waiting_count(&self) -> usize {
+        self.waiting.values().map(VecDeque::len).sum()
     }
 
     /// Clear transition history
     pub fn clear_history(&mut self) {
-        self.transition_history.clear();
+        self.transitions.clear();
     }
 }
 
@@ -301,6 +667,35 @@ mod tests {
         assert!(!state.is_animating);
     }
 
+    #[test]
+    fn test_display_priority_follows_tab_locality() {
+        let mut state = VisualState { priority: Priority::High, ..VisualState::new() };
+        assert_eq!(state.display_priority(), Priority::High);
+        state.tab_locality = TabLocality::Active;
+        assert_eq!(state.display_priority(), Priority::Critical);
+        state.tab_locality = TabLocality::Hidden;
+        assert_eq!(state.display_priority(), Priority::Normal);
+        // Critical notifications are never pushed down
+        state.priority = Priority::Critical;
+        assert_eq!(state.display_priority(), Priority::Critical);
+        assert_eq!(state.priority, Priority::Critical);
+    }
+
+    #[test]
+    fn test_visual_state_surfaces() {
+        let mut state = VisualState::new();
+        assert!(state.uses_surface(Surface::Border));
+        assert!(!state.is_fallback());
+
+        state.surfaces = vec![Surface::StatusEntry, Surface::TabBadge];
+        assert!(!state.uses_surface(Surface::Border));
+        assert!(state.uses_surface(Surface::TabBadge));
+        assert!(state.is_fallback());
+
+        state.clear();
+        assert!(state.surfaces.is_empty());
+    }
+
     #[test]
     fn test_state_transitions() {
         let idle = VisualNotificationState::Idle;
@@ -317,6 +712,13 @@ mod tests {
         // Invalid transitions
         assert!(!pending.can_transition_to(&fading));
         assert!(!idle.can_transition_to(&fading));
+
+        let read = VisualNotificationState::Read;
+        assert!(active.can_transition_to(&read));
+        assert!(read.can_transition_to(&idle));
+        assert!(read.can_transition_to(&active));
+        assert!(!idle.can_transition_to(&read));
+        assert!(!pending.can_transition_to(&read));
     }
 
     #[test]
@@ -329,10 +731,70 @@ mod tests {
                 VisualNotificationState::Active,
                 &format!("Test {}", i),
             );
-            manager.record_transition(transition);
+            manager.record_transition(i % 2, transition);
         }
 
-        let recent = manager.recent_transitions(5);
-        assert_eq!(recent.len(), 5);
+        let recent = manager.recent_transitions(1, 3);
+        assert_eq!(recent.len(), 3);
+        assert_eq!(recent[2].reason, "Test 9");
+        assert_eq!(manager.recent_transitions(0, 100).len(), 5);
+        assert!(manager.recent_transitions(7, 100).is_empty());
+
+        manager.forget_pane(0);
+        assert!(manager.recent_transitions(0, 100).is_empty());
+        assert_eq!(manager.recent_transitions(1, 100).len(), 5);
+    }
+
+    #[test]
+    fn test_state_manager_bounded_per_pane() {
+        let mut manager = StateManager::new().with_max_history(3);
+
+        for i in 0..10 {
+            manager.record_transition(1, StateTransition::new(
+                VisualNotificationState::Idle,
+                VisualNotificationState::Active,
+                &format!("Test {}", i),
+            ));
+        }
+        // A busy pane doesn't push out the history of a quiet one
+        manager.record_transition(2, StateTransition::new(
+            VisualNotificationState::Active,
+            VisualNotificationState::Read,
+            "quiet",
+        ));
+
+        assert_eq!(manager.recent_transitions(1, 100).len(), 3);
+        assert_eq!(manager.evicted_count(), 7);
+        assert_eq!(manager.recent_transitions(1, 1)[0].reason, "Test 9");
+        assert_eq!(manager.recent_transitions(2, 100)[0].reason, "quiet");
+    }
+
+    #[test]
+    fn test_replacements_wait_for_minimum_display_time() {
+        let mut manager = StateManager::new();
+        manager.shown(1, Priority::Normal, 2000, 0);
+
+        // Same priority waits, in arrival order
+        let first = Notification::success("first");
+        let second = Notification::success("second");
+        assert!(!manager.admit(1, &first, 500));
+        assert!(!manager.admit(1, &second, 600));
+        assert!(manager.admit(2, &Notification::success("other pane"), 600));
+        assert_eq!(manager.waiting_count(), 2);
+        assert!(manager.due(1999).is_empty());
+
+        // Once the time is up, the first waiting one is shown, then keeps the pane itself
+        let due: Vec<String> = manager.due(2000).into_iter().map(|n| n.id).collect();
+        assert_eq!(due, vec![first.id.clone()]);
+        assert!(!manager.admit(1, &second, 2000));
+        assert!(manager.admit(1, &first, 2000));
+        manager.shown(1, Priority::Normal, 2000, 2000);
+        assert!(manager.due(3000).is_empty());
+
+        // Strictly more urgent takes the pane at once
+        let urgent = Notification::error("urgent").with_priority(Priority::Critical);
+        assert!(manager.admit(1, &urgent, 2100));
+        assert_eq!(manager.take_waiting(|n| n.id == second.id).len(), 1);
+        assert_eq!(manager.waiting_count(), 0);
     }
 }
@@ -0,0 +1,171 @@
+//! Stress test module for Zellij Visual Notifications
+//!
+//! `stress <count> <rate>` synthesizes `count` notifications at `rate` per second and feeds them
+//! through the regular pipeline (rules, DND, queue admission, sinks), cycling through the
+//! notification types and the terminal panes, so users can see how their terminal and Zellij
+//! setup cope with a burst and check that coalescing, admission control and the action rate limit
+//! behave as configured. The notifications come from source `stress`; webhook and forward sinks
+//! skip them. The `stats` command summarizes the run next to the frame timings and action counts.
+
+use crate::notification::{Notification, NotificationType};
+use crate::queue::Admission;
+
+/// Source of synthesized notifications
+pub const STRESS_SOURCE: &str = "stress";
+
+/// Most notifications one run may synthesize
+pub const MAX_STRESS_COUNT: u32 = 100_000;
+
+/// Highest synthesis rate (notifications per second)
+pub const MAX_STRESS_RATE: u32 = 1000;
+
+/// Types cycled through (Attention is left out: it is deferred while the user types)
+const STRESS_TYPES: [NotificationType; 5] = [
+    NotificationType::Info,
+    NotificationType::Success,
+    NotificationType::Warning,
+    NotificationType::Error,
+    NotificationType::Progress,
+];
+
+/// What the queue did with the synthesized notifications
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StressOutcomes {
+    /// Queued as new entries
+    pub queued: u32,
+    /// Folded into an equivalent queued notification
+    pub coalesced: u32,
+    /// Dropped by admission control
+    pub rejected: u32,
+}
+
+/// A stress run in progress or finished
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressRun {
+    count: u32,
+    rate: u32,
+    /// Panes the notifications are spread over (none: pane-less)
+    panes: Vec<u32>,
+    sent: u32,
+    started_ms: u64,
+    /// Plugin time the last notification was sent or the run was stopped (ms)
+    finished_ms: Option<u64>,
+    pub outcomes: StressOutcomes,
+}
+
+impl StressRun {
+    /// Start a run (count and rate clamped to their limits)
+    pub fn new(count: u32, rate: u32, panes: Vec<u32>, now_ms: u64) -> Self {
+        Self {
+            count: count.clamp(1, MAX_STRESS_COUNT),
+            rate: rate.clamp(1, MAX_STRESS_RATE),
+            panes,
+            sent: 0,
+            started_ms: now_ms,
+            finished_ms: None,
+            outcomes: StressOutcomes::default(),
+        }
+    }
+
+    /// Whether notifications are still to be sent
+    pub fn is_running(&self) -> bool {
+        self.finished_ms.is_none()
+    }
+
+    /// Notifications due by `now_ms` at the run's rate
+    pub fn due(&mut self, now_ms: u64) -> Vec<Notification> {
+        if !self.is_running() {
+            return Vec::new();
+        }
+        let elapsed_ms = now_ms.saturating_sub(self.started_ms);
+        let target = (elapsed_ms * self.rate as u64 / 1000).min(self.count as u64) as u32;
+        let notifications = (self.sent..target).map(|index| self.synthesize(index)).collect();
+        self.sent = self.sent.max(target);
+        if self.sent == self.count {
+            self.finished_ms = Some(now_ms);
+        }
+        notifications
+    }
+
+    /// Stop sending, returning whether the run was still going
+    pub fn stop(&mut self, now_ms: u64) -> bool {
+        let running = self.is_running();
+        if running {
+            self.finished_ms = Some(now_ms);
+        }
+        running
+    }
+
+    /// Count what the queue did with a synthesized notification
+    pub fn record(&mut self, admission: Admission) {
+        match admission {
+            Admission::Admitted => self.outcomes.queued += 1,
+            Admission::Merged => self.outcomes.coalesced += 1,
+            Admission::Rejected => self.outcomes.rejected += 1,
+        }
+    }
+
+    /// Notification number `index` of the run
+    fn synthesize(&self, index: u32) -> Notification {
+        let notification_type = STRESS_TYPES[index as usize % STRESS_TYPES.len()].clone();
+        let notification = Notification::new(notification_type, &format!("Stress {}/{}", index + 1, self.count))
+            .from_source(STRESS_SOURCE);
+        match self.panes.len() {
+            0 => notification,
+            n => notification.for_pane(self.panes[index as usize % n]),
+        }
+    }
+
+    /// Stats line, e.g. `stress: 500/500 at 100/s in 5.0s - 310 queued, 170 coalesced, 20 rejected, 0 filtered`
+    /// (filtered: dropped by rules, held or deferred before reaching the queue)
+    pub fn line(&self, now_ms: u64) -> String {
+        let elapsed_ms = self.finished_ms.unwrap_or(now_ms).saturating_sub(self.started_ms);
+        let outcomes = self.outcomes;
+        let filtered = self.sent.saturating_sub(outcomes.queued + outcomes.coalesced + outcomes.rejected);
+        format!("stress: {}/{} at {}/s in {:.1}s{} - {} queued, {} coalesced, {} rejected, {} filtered",
+            self.sent, self.count, self.rate, elapsed_ms as f64 / 1000.0,
+            if self.is_running() { " (running)" } else { "" },
+            outcomes.queued, outcomes.coalesced, outcomes.rejected, filtered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_paces_notifications_and_summarizes() {
+        let mut run = StressRun::new(10, 4, vec![3, 7], 1_000);
+        assert!(run.due(1_100).is_empty());
+        let first = run.due(1_500);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].message, "Stress 1/10");
+        assert_eq!((first[0].pane_id, first[1].pane_id), (Some(3), Some(7)));
+        assert_eq!(first[1].notification_type, NotificationType::Success);
+        assert_eq!(first[0].source, STRESS_SOURCE);
+
+        // Never more than the count, however late the tick
+        assert_eq!(run.due(60_000).len(), 8);
+        assert!(!run.is_running());
+        assert!(run.due(70_000).is_empty());
+
+        for admission in [Admission::Admitted; 6].into_iter().chain([Admission::Merged; 2]).chain([Admission::Rejected]) {
+            run.record(admission);
+        }
+        assert_eq!(run.line(80_000), "stress: 10/10 at 4/s in 59.0s - 6 queued, 2 coalesced, 1 rejected, 1 filtered");
+    }
+
+    #[test]
+    fn test_stop_and_limits() {
+        let mut run = StressRun::new(0, 5_000, Vec::new(), 0);
+        assert_eq!(run.due(10).len(), 1);
+        assert!(run.due(10).is_empty());
+
+        let mut run = StressRun::new(100, 10, Vec::new(), 0);
+        assert_eq!(run.due(1_000)[0].pane_id, None);
+        assert!(run.stop(1_500));
+        assert!(!run.stop(2_000));
+        assert!(run.due(5_000).is_empty());
+        assert!(run.line(9_000).starts_with("stress: 10/100 at 10/s in 1.5s -"));
+    }
+}
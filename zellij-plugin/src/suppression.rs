@@ -0,0 +1,260 @@
+//! Suppression module for Zellij Visual Notifications
+//!
+//! Do-not-disturb and snooze: while active, non-critical notifications are held instead of
+//! displayed and released when the suppression ends. Exposes remaining-time information so
+//! suppression is visible in the status bar rather than silent.
+//!
+//! Focus mode is a timed suppression for a stretch of deep work: it counts down in the status
+//! bar, and when it ends the batch is not replayed one by one but summed up in a digest, with only
+//! the newest notification per pane or tab shown.
+
+use std::collections::BTreeMap;
+
+use crate::notification::{Notification, Priority};
+
+/// Kind of active suppression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuppressionKind {
+    /// Do not disturb (optionally timed)
+    Dnd,
+    /// Snooze (always timed)
+    Snooze,
+    /// Focus mode (always timed, ends with a digest)
+    Focus,
+}
+
+impl SuppressionKind {
+    /// Short label for status display
+    pub fn label(&self) -> &'static str {
+        match self {
+            SuppressionKind::Dnd => "DND",
+            SuppressionKind::Snooze => "Snoozed",
+            SuppressionKind::Focus => "Focus",
+        }
+    }
+}
+
+/// Snapshot of the active suppression for rendering
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuppressionStatus {
+    /// Kind of suppression
+    pub kind: SuppressionKind,
+    /// Time until suppression ends (None = until turned off)
+    pub remaining_ms: Option<u64>,
+    /// Number of held notifications
+    pub held: usize,
+}
+
+/// Manager for DND/snooze state and held notifications
+#[derive(Debug, Clone, Default)]
+pub struct SuppressionManager {
+    /// Active suppression kind
+    kind: Option<SuppressionKind>,
+    /// Time at which suppression ends (ms, None = indefinite)
+    until_ms: Option<u64>,
+    /// Notifications held while suppressed
+    held: Vec<Notification>,
+}
+
+impl SuppressionManager {
+    /// Create an inactive suppression manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable do-not-disturb, optionally for a limited duration
+    pub fn start_dnd(&mut self, now_ms: u64, duration_ms: Option<u64>) {
+        self.kind = Some(SuppressionKind::Dnd);
+        self.until_ms = duration_ms.map(|d| now_ms.saturating_add(d));
+    }
+
+    /// Snooze notifications for a duration
+    pub fn snooze(&mut self, now_ms: u64, duration_ms: u64) {
+        self.kind = Some(SuppressionKind::Snooze);
+        self.until_ms = Some(now_ms.saturating_add(duration_ms));
+    }
+
+    /// Start focus mode for a duration
+    pub fn start_focus(&mut self, now_ms: u64, duration_ms: u64) {
+        self.kind = Some(SuppressionKind::Focus);
+        self.until_ms = Some(now_ms.saturating_add(duration_ms));
+    }
+
+    /// Kind of the suppression in effect (or whose deadline just passed)
+    pub fn kind(&self) -> Option<SuppressionKind> {
+        self.kind
+    }
+
+    /// End suppression, returning the held notifications for release
+    pub fn stop(&mut self) -> Vec<Notification> {
+        self.kind = None;
+        self.until_ms = None;
+        std::mem::take(&mut self.held)
+    }
+
+    /// Check if suppression is active at the given time
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        match (self.kind, self.until_ms) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(_), Some(until)) => now_ms < until,
+        }
+    }
+
+    /// Whether do-not-disturb is on without a time limit
+    pub fn is_open_ended_dnd(&self) -> bool {
+        self.kind == Some(SuppressionKind::Dnd) && self.until_ms.is_none()
+    }
+
+    /// Time remaining until suppression ends (None if inactive or indefinite)
+    pub fn remaining_ms(&self, now_ms: u64) -> Option<u64> {
+        if !self.is_active(now_ms) {
+            return None;
+        }
+        self.until_ms.map(|until| until.saturating_sub(now_ms))
+    }
+
+    /// End a timed suppression once its deadline passes, returning held notifications
+    pub fn expire(&mut self, now_ms: u64) -> Option<Vec<Notification>> {
+        if self.kind.is_some() && !self.is_active(now_ms) {
+            Some(self.stop())
+        } else {
+            None
+        }
+    }
+
+    /// Hold a notification if suppression applies to it, returning it otherwise
+    pub fn try_hold(&mut self, notification: Notification, now_ms: u64) -> Option<Notification> {
+        if self.is_active(now_ms) && notification.priority < Priority::Critical {
+            self.held.push(notification);
+            None
+        } else {
+            Some(notification)
+        }
+    }
+
+    /// Number of held notifications
+    pub fn held_count(&self) -> usize {
+        self.held.len()
+    }
+
+    /// Status snapshot for rendering (None if inactive)
+    pub fn status(&self, now_ms: u64) -> Option<SuppressionStatus> {
+        if !self.is_active(now_ms) {
+            return None;
+        }
+        self.kind.map(|kind| SuppressionStatus {
+            kind,
+            remaining_ms: self.remaining_ms(now_ms),
+            held: self.held.len(),
+        })
+    }
+}
+
+/// What arrived during a focus period
+#[derive(Debug, Clone, Default)]
+pub struct FocusDigest {
+    /// Summary, e.g. `Focus ended: 5 arrived (3 success, 2 warning)`
+    pub summary: String,
+    /// Newest notification per pane or tab (and every untargeted one), to show now
+    pub latest: Vec<Notification>,
+    /// Older notifications for the same targets, left in the history
+    pub superseded: Vec<Notification>,
+}
+
+impl FocusDigest {
+    /// Sum up the notifications batched during a focus period, in arrival order
+    pub fn new(held: Vec<Notification>) -> Self {
+        let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+        for notification in &held {
+            *counts.entry(notification.notification_type.name()).or_insert(0) += 1;
+        }
+        let summary = if held.is_empty() {
+            "Focus ended: nothing arrived".to_string()
+        } else {
+            let counts: Vec<String> = counts.iter().map(|(name, count)| format!("{} {}", count, name)).collect();
+            format!("Focus ended: {} arrived ({})", held.len(), counts.join(", "))
+        };
+
+        let mut latest: Vec<Notification> = Vec::new();
+        let mut superseded = Vec::new();
+        for notification in held {
+            let target = (notification.pane_id, notification.tab_index);
+            let older = latest.iter().position(|n| target != (None, None) && (n.pane_id, n.tab_index) == target);
+            if let Some(index) = older {
+                superseded.push(latest.remove(index));
+            }
+            latest.push(notification);
+        }
+        Self { summary, latest, superseded }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timed_dnd_remaining_and_expiry() {
+        let mut manager = SuppressionManager::new();
+        manager.start_dnd(1000, Some(60_000));
+
+        assert!(manager.is_active(30_000));
+        assert_eq!(manager.remaining_ms(31_000), Some(30_000));
+
+        assert!(manager.try_hold(Notification::info("Held"), 31_000).is_none());
+        assert!(manager.try_hold(Notification::error("Critical passes"), 31_000).is_some());
+        assert_eq!(manager.held_count(), 1);
+
+        assert!(manager.expire(30_000).is_none());
+        let released = manager.expire(61_000).unwrap();
+        assert_eq!(released.len(), 1);
+        assert!(manager.status(61_000).is_none());
+    }
+
+    #[test]
+    fn test_indefinite_dnd_status() {
+        let mut manager = SuppressionManager::new();
+        manager.start_dnd(0, None);
+        manager.try_hold(Notification::info("Held"), 5);
+
+        let status = manager.status(10_000_000).unwrap();
+        assert_eq!(status.kind, SuppressionKind::Dnd);
+        assert_eq!(status.remaining_ms, None);
+        assert_eq!(status.held, 1);
+        assert!(manager.is_open_ended_dnd());
+        assert_eq!(manager.stop().len(), 1);
+        assert!(!manager.is_open_ended_dnd());
+    }
+
+    #[test]
+    fn test_focus_digest_keeps_newest_per_pane() {
+        let mut manager = SuppressionManager::new();
+        manager.start_focus(0, 25 * 60_000);
+        assert_eq!(manager.status(0).unwrap().kind.label(), "Focus");
+        for notification in [
+            Notification::success("Built").for_pane(1),
+            Notification::info("Deploy started"),
+            Notification::warning("Tests flaky").for_pane(1),
+            Notification::success("Lint clean").for_pane(2),
+        ] {
+            assert!(manager.try_hold(notification, 10).is_none());
+        }
+        assert_eq!(manager.kind(), Some(SuppressionKind::Focus));
+
+        let digest = FocusDigest::new(manager.expire(25 * 60_000).unwrap());
+        assert_eq!(digest.summary, "Focus ended: 4 arrived (1 info, 2 success, 1 warning)");
+        let latest: Vec<&str> = digest.latest.iter().map(|n| n.message.as_str()).collect();
+        assert_eq!(latest, vec!["Deploy started", "Tests flaky", "Lint clean"]);
+        assert_eq!(digest.superseded.len(), 1);
+        assert_eq!(FocusDigest::new(Vec::new()).summary, "Focus ended: nothing arrived");
+    }
+
+    #[test]
+    fn test_snooze_label() {
+        let mut manager = SuppressionManager::new();
+        manager.snooze(0, 5000);
+        assert_eq!(manager.status(0).unwrap().kind.label(), "Snoozed");
+        assert_eq!(manager.remaining_ms(1000), Some(4000));
+    }
+}
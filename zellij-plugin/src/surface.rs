@@ -0,0 +1,210 @@
+//! Surface selection module for Zellij Visual Notifications
+//!
+//! Decides which signaling surfaces (border, status entry, tab badge, pane title badge) are
+//! used for a pane. Border and badge cues are invisible on stacked/collapsed or suppressed
+//! panes, so those fall back to alternative surfaces automatically. Display surfaces can also
+//! be switched off at runtime, e.g. to silence visual noise during a screen share.
+
+use crate::config::Config;
+
+/// A place where a notification can be signaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Surface {
+    /// Colored pane border (with animation)
+    Border,
+    /// Entry in the plugin status bar
+    StatusEntry,
+    /// Badge in the tab bar
+    TabBadge,
+    /// Icon prefixed to the target pane's title
+    TitleBadge,
+}
+
+/// A display surface that can be switched off at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplaySurface {
+    /// Pane border colors
+    Border,
+    /// Notification badges in tab names and the tab bar
+    TabBadges,
+    /// Notification entries in the status bar
+    StatusEntries,
+    /// Floating toasts
+    Toasts,
+}
+
+impl DisplaySurface {
+    /// All toggleable surfaces, in indicator order
+    pub const ALL: [Self; 4] = [Self::Border, Self::TabBadges, Self::StatusEntries, Self::Toasts];
+
+    /// Parse a surface name (`border`, `tabs`, `status`, `toasts`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "border" | "borders" => Some(Self::Border),
+            "tabs" | "tab_badges" => Some(Self::TabBadges),
+            "status" | "entries" => Some(Self::StatusEntries),
+            "toasts" | "toast" => Some(Self::Toasts),
+            _ => None,
+        }
+    }
+
+    /// Short name used by the pipe command and the indicator
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Border => "border",
+            Self::TabBadges => "tabs",
+            Self::StatusEntries => "status",
+            Self::Toasts => "toasts",
+        }
+    }
+
+    /// Plugin-local key toggling the surface
+    pub fn key(&self) -> char {
+        match self {
+            Self::Border => 'b',
+            Self::TabBadges => 't',
+            Self::StatusEntries => 's',
+            Self::Toasts => 'o',
+        }
+    }
+
+    /// Surface toggled by a plugin-local key (`b`, `t`, `s`, `o`)
+    pub fn from_key(key: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|surface| surface.key() == key)
+    }
+
+    /// Whether the configuration has this surface at all (toasts are experimental and need a
+    /// shell); the key of one it lacks does nothing and is not listed in the legend
+    pub fn available(&self, config: &Config) -> bool {
+        match self {
+            Self::Toasts => config.experimental("toasts") && config.host_platform.has_shell(),
+            _ => true,
+        }
+    }
+}
+
+/// Display surfaces switched off at runtime
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SurfaceToggles {
+    /// Surfaces currently off
+    disabled: Vec<DisplaySurface>,
+}
+
+impl SurfaceToggles {
+    /// Whether a surface is on
+    pub fn is_enabled(&self, surface: DisplaySurface) -> bool {
+        !self.disabled.contains(&surface)
+    }
+
+    /// Switch a surface on or off (toggle if unspecified), returning whether it is now on
+    pub fn set(&mut self, surface: DisplaySurface, enabled: Option<bool>) -> bool {
+        let enabled = enabled.unwrap_or(!self.is_enabled(surface));
+        self.disabled.retain(|s| *s != surface);
+        if !enabled {
+            self.disabled.push(surface);
+        }
+        enabled
+    }
+
+    /// Names of the surfaces switched off, in indicator order
+    pub fn disabled_names(&self) -> Vec<&'static str> {
+        DisplaySurface::ALL.iter()
+            .filter(|surface| !self.is_enabled(**surface))
+            .map(|surface| surface.name())
+            .collect()
+    }
+}
+
+/// How much of a pane is currently visible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaneVisibility {
+    /// Pane content is on screen
+    #[default]
+    Visible,
+    /// Pane is collapsed to its title line (e.g. in a stack)
+    Collapsed,
+    /// Pane is suppressed and not on screen at all
+    Hidden,
+}
+
+impl PaneVisibility {
+    /// Classify visibility from pane manifest geometry
+    pub fn from_geometry(pane_rows: usize, pane_content_rows: usize, is_suppressed: bool) -> Self {
+        if is_suppressed {
+            Self::Hidden
+        } else if pane_rows > 0 && pane_content_rows == 0 {
+            Self::Collapsed
+        } else {
+            Self::Visible
+        }
+    }
+}
+
+/// Chooses signaling surfaces based on pane visibility
+#[derive(Debug, Clone, Default)]
+pub struct SurfaceSelector {
+    /// Prefix the pane title with the notification icon when the pane is not visible
+    title_badge: bool,
+}
+
+impl SurfaceSelector {
+    /// Create a new surface selector
+    pub fn new(title_badge: bool) -> Self {
+        Self { title_badge }
+    }
+
+    /// Select the surfaces to signal a notification on a pane with the given visibility
+    pub fn select(&self, visibility: PaneVisibility) -> Vec<Surface> {
+        match visibility {
+            PaneVisibility::Visible => vec![Surface::Border, Surface::StatusEntry],
+            PaneVisibility::Collapsed | PaneVisibility::Hidden => {
+                let mut surfaces = vec![Surface::StatusEntry, Surface::TabBadge];
+                if self.title_badge {
+                    surfaces.push(Surface::TitleBadge);
+                }
+                surfaces
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visibility_from_geometry() {
+        assert_eq!(PaneVisibility::from_geometry(20, 18, false), PaneVisibility::Visible);
+        assert_eq!(PaneVisibility::from_geometry(1, 0, false), PaneVisibility::Collapsed);
+        assert_eq!(PaneVisibility::from_geometry(20, 18, true), PaneVisibility::Hidden);
+        // Unknown geometry is treated as visible
+        assert_eq!(PaneVisibility::from_geometry(0, 0, false), PaneVisibility::Visible);
+    }
+
+    #[test]
+    fn test_collapsed_panes_fall_back() {
+        let selector = SurfaceSelector::new(false);
+        assert_eq!(selector.select(PaneVisibility::Visible), vec![Surface::Border, Surface::StatusEntry]);
+        assert_eq!(
+            selector.select(PaneVisibility::Collapsed),
+            vec![Surface::StatusEntry, Surface::TabBadge]
+        );
+
+        let selector = SurfaceSelector::new(true);
+        assert!(selector.select(PaneVisibility::Hidden).contains(&Surface::TitleBadge));
+        assert!(!selector.select(PaneVisibility::Visible).contains(&Surface::TitleBadge));
+    }
+
+    #[test]
+    fn test_surface_toggles() {
+        let mut toggles = SurfaceToggles::default();
+        assert!(toggles.disabled_names().is_empty());
+        assert!(!toggles.set(DisplaySurface::Toasts, None));
+        assert!(!toggles.set(DisplaySurface::Border, Some(false)));
+        assert_eq!(toggles.disabled_names(), vec!["border", "toasts"]);
+        assert!(toggles.set(DisplaySurface::Toasts, None));
+        assert!(toggles.is_enabled(DisplaySurface::Toasts) && !toggles.is_enabled(DisplaySurface::Border));
+        assert_eq!(DisplaySurface::from_name("tabs"), Some(DisplaySurface::TabBadges));
+        assert_eq!(DisplaySurface::from_key('s'), Some(DisplaySurface::StatusEntries));
+    }
+}
@@ -0,0 +1,201 @@
+//! Tab badge module for Zellij Visual Notifications
+//!
+//! Formats per-tab notification badges (`✘2⚠1`) into tab names as a prefix, a suffix, or in
+//! place of the name, and strips previously applied badges so names restored after a crash or
+//! reload never accumulate duplicates.
+
+use crate::config::{Config, TabBadgePlacement};
+use crate::notification::{IconFrames, NotificationType};
+use crate::state::VisualState;
+
+/// Notification types in badge order (most urgent first)
+const BADGE_ORDER: [NotificationType; 6] = [
+    NotificationType::Error,
+    NotificationType::Attention,
+    NotificationType::Warning,
+    NotificationType::Progress,
+    NotificationType::Success,
+    NotificationType::Info,
+];
+
+/// Separator used when the configured one is empty (badges must stay separable)
+const DEFAULT_SEPARATOR: &str = " ";
+
+/// How badges are placed in tab names
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabBadgeStyle {
+    /// Badge placement
+    pub placement: TabBadgePlacement,
+    /// Separator between badge and name
+    pub separator: String,
+    /// Maximum badge width (characters, truncated with an ellipsis)
+    pub max_width: usize,
+    /// Spin the Progress icon (off under reduced motion)
+    pub spin: bool,
+}
+
+impl Default for TabBadgeStyle {
+    fn default() -> Self {
+        Self {
+            placement: TabBadgePlacement::Suffix,
+            separator: DEFAULT_SEPARATOR.to_string(),
+            max_width: 8,
+            spin: true,
+        }
+    }
+}
+
+impl TabBadgeStyle {
+    /// Take the badge style from the plugin configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            placement: config.tab_badge_placement,
+            separator: if config.tab_badge_separator.is_empty() {
+                DEFAULT_SEPARATOR.to_string()
+            } else {
+                config.tab_badge_separator.clone()
+            },
+            max_width: config.tab_badge_max_width.max(1),
+            spin: !config.accessibility.reduced_motion,
+        }
+    }
+
+    /// Build the badge for a tab's panes, truncated to the maximum width. `step` selects the
+    /// spinner frame while a Progress notification is running.
+    pub fn badge<'a>(&self, states: impl Iterator<Item = &'a VisualState> + Clone, step: u64) -> String {
+        let mut badge = String::new();
+        for notification_type in BADGE_ORDER.iter() {
+            let count = states.clone()
+                .filter(|s| s.is_shown() && !s.is_read() && s.notification_type.as_ref() == Some(notification_type))
+                .count();
+            if count > 0 {
+                let spinning = self.spin && states.clone()
+                    .any(|s| s.is_spinning() && s.notification_type.as_ref() == Some(notification_type));
+                let icon = match notification_type.icon_frames(true) {
+                    Some(frames) if spinning => frames.frame(step).to_string(),
+                    _ => notification_type.icon().unwrap_or_default(),
+                };
+                badge.push_str(&format!("{}{}", icon, count));
+            }
+        }
+
+        if badge.chars().count() > self.max_width {
+            let mut truncated: String = badge.chars().take(self.max_width.saturating_sub(1)).collect();
+            truncated.push('\u{2026}'); // Ellipsis
+            truncated
+        } else {
+            badge
+        }
+    }
+
+    /// Apply a badge to a base tab name (the base is returned unchanged for an empty badge)
+    pub fn apply(&self, base: &str, badge: &str) -> String {
+        if badge.is_empty() {
+            return base.to_string();
+        }
+        match self.placement {
+            TabBadgePlacement::Prefix => format!("{}{}{}", badge, self.separator, base),
+            TabBadgePlacement::Suffix => format!("{}{}{}", base, self.separator, badge),
+            TabBadgePlacement::Replace => badge.to_string(),
+        }
+    }
+
+    /// Remove any badges at either end of a name, whatever placement applied them
+    pub fn strip(&self, name: &str) -> String {
+        let separator = self.separator.as_str();
+        let mut rest = name;
+
+        loop {
+            if is_badge(rest) {
+                return String::new();
+            }
+            if let Some(index) = rest.find(separator) {
+                if is_badge(&rest[..index]) {
+                    rest = &rest[index + separator.len()..];
+                    continue;
+                }
+            }
+            if let Some(index) = rest.rfind(separator) {
+                if is_badge(&rest[index + separator.len()..]) {
+                    rest = &rest[..index];
+                    continue;
+                }
+            }
+            return rest.to_string();
+        }
+    }
+}
+
+/// Whether text consists only of badge icons (spinner frames included), counts and ellipses
+fn is_badge(text: &str) -> bool {
+    let icons: Vec<String> = BADGE_ORDER.iter().filter_map(|t| t.icon()).collect();
+    let is_icon = |c: char| icons.iter().any(|icon| icon.starts_with(c)) || IconFrames::BRAILLE.contains(c);
+
+    text.chars().any(is_icon)
+        && text.chars().all(|c| c.is_ascii_digit() || c == '\u{2026}' || is_icon(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn states() -> Vec<VisualState> {
+        [NotificationType::Warning, NotificationType::Error, NotificationType::Error]
+            .iter()
+            .map(|t| VisualState {
+                notification_type: Some(t.clone()),
+                ..VisualState::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_badge_placement() {
+        let states = states();
+        let style = TabBadgeStyle::default();
+        let badge = style.badge(states.iter(), 0);
+        assert_eq!(badge, "\u{2718}2\u{26A0}1");
+        assert_eq!(style.apply("3:work", &badge), "3:work \u{2718}2\u{26A0}1");
+
+        let prefix = TabBadgeStyle { placement: TabBadgePlacement::Prefix, ..TabBadgeStyle::default() };
+        assert_eq!(prefix.apply("3:work", "\u{2718}2"), "\u{2718}2 3:work");
+
+        let replace = TabBadgeStyle { placement: TabBadgePlacement::Replace, ..TabBadgeStyle::default() };
+        assert_eq!(replace.apply("3:work", "\u{2718}2"), "\u{2718}2");
+        assert_eq!(replace.apply("3:work", ""), "3:work");
+    }
+
+    #[test]
+    fn test_badge_max_width() {
+        let states = states();
+        let style = TabBadgeStyle { max_width: 3, ..TabBadgeStyle::default() };
+        assert_eq!(style.badge(states.iter(), 0), "\u{2718}2\u{2026}");
+    }
+
+    #[test]
+    fn test_progress_badge_spins() {
+        let states = [VisualState {
+            notification_type: Some(NotificationType::Progress),
+            ..VisualState::default()
+        }];
+        let style = TabBadgeStyle::default();
+        assert_eq!(style.badge(states.iter(), 0), "\u{280B}1");
+        assert_eq!(style.badge(states.iter(), 1), "\u{2819}1");
+        assert_eq!(style.strip("3:work \u{2819}1"), "3:work");
+
+        let still = TabBadgeStyle { spin: false, ..TabBadgeStyle::default() };
+        assert_eq!(still.badge(states.iter(), 1), "\u{21BB}1");
+    }
+
+    #[test]
+    fn test_strip_never_accumulates() {
+        let style = TabBadgeStyle::default();
+        assert_eq!(style.strip("3:work \u{2718}2"), "3:work");
+        assert_eq!(style.strip("\u{2718}2 \u{26A0}1 3:work \u{2718}2 \u{2718}1\u{2026}"), "3:work");
+        assert_eq!(style.strip("v2 release"), "v2 release");
+        assert_eq!(style.strip("\u{2718}2"), "");
+
+        let name = style.apply(&style.strip("3:work \u{2718}2"), "\u{2718}3");
+        assert_eq!(name, "3:work \u{2718}3");
+    }
+}
@@ -0,0 +1,159 @@
+//! Table layout module for Zellij Visual Notifications
+//!
+//! Lays rows of fields out in aligned columns within a width budget. Each column has a natural
+//! width (its widest cell, capped at a maximum), a minimum it can shrink to, and a truncation
+//! priority. When even the minimums do not fit the budget, columns are dropped, lowest priority
+//! first. The columns left shrink in the same order, each down to its minimum before the next one
+//! gives up width. Cells that do not fit their column end in an ellipsis.
+
+use crate::wrap::{clusters, display_width};
+
+/// Alignment of a column's cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Padded on the right
+    Left,
+    /// Padded on the left (numbers)
+    Right,
+}
+
+/// Width budget and truncation priority of a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    /// Narrowest the column is shrunk to before it is dropped
+    pub min_width: usize,
+    /// Widest the column grows
+    pub max_width: usize,
+    /// Truncation priority; lower gives up width first
+    pub priority: u8,
+    /// Cell alignment
+    pub align: Align,
+}
+
+impl Column {
+    /// Left-aligned column
+    pub const fn left(min_width: usize, max_width: usize, priority: u8) -> Self {
+        Self { min_width, max_width, priority, align: Align::Left }
+    }
+
+    /// Right-aligned column
+    pub const fn right(min_width: usize, max_width: usize, priority: u8) -> Self {
+        Self { min_width, max_width, priority, align: Align::Right }
+    }
+}
+
+/// Column widths for the rows within `budget` columns, with `gap` columns between fields. A
+/// dropped column gets width 0.
+pub fn column_widths(columns: &[Column], rows: &[Vec<String>], budget: usize, gap: usize) -> Vec<usize> {
+    let mut widths: Vec<usize> = columns.iter().enumerate()
+        .map(|(i, column)| {
+            let widest = rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| display_width(cell))
+                .max()
+                .unwrap_or(0);
+            widest.min(column.max_width)
+        })
+        .collect();
+
+    let total = |widths: &[usize]| {
+        let shown = widths.iter().filter(|w| **w > 0).count();
+        widths.iter().sum::<usize>() + gap * shown.saturating_sub(1)
+    };
+    // Lowest priority first; among equals, the rightmost column first
+    let mut order: Vec<usize> = (0..columns.len()).collect();
+    order.sort_by_key(|i| (columns[*i].priority, std::cmp::Reverse(*i)));
+
+    let mut floors: Vec<usize> = columns.iter()
+        .zip(&widths)
+        .map(|(column, width)| column.min_width.min(*width))
+        .collect();
+    for &i in &order {
+        if total(&floors) <= budget {
+            break;
+        }
+        floors[i] = 0;
+        widths[i] = 0;
+    }
+    for &i in &order {
+        let excess = total(&widths).saturating_sub(budget);
+        if excess == 0 {
+            break;
+        }
+        widths[i] -= excess.min(widths[i] - floors[i]);
+    }
+    widths
+}
+
+/// Lay a row out in the given widths (dropped columns are skipped)
+pub fn format_row(columns: &[Column], widths: &[usize], cells: &[String], gap: usize) -> String {
+    columns.iter()
+        .zip(widths)
+        .zip(cells)
+        .filter(|((_, width), _)| **width > 0)
+        .map(|((column, width), cell)| fit(cell, *width, column.align))
+        .collect::<Vec<_>>()
+        .join(&" ".repeat(gap))
+}
+
+/// Pad or cut text to exactly `width` columns, ending a cut with an ellipsis
+pub fn fit(text: &str, width: usize, align: Align) -> String {
+    let text_width = display_width(text);
+    let text = if text_width <= width {
+        text.to_string()
+    } else {
+        let mut cut = String::new();
+        let mut cut_width = 0;
+        for (cluster, cluster_width) in clusters(text) {
+            if cut_width + cluster_width + 1 > width {
+                break;
+            }
+            cut.push_str(&cluster);
+            cut_width += cluster_width;
+        }
+        if width > 0 {
+            cut.push('\u{2026}'); // Ellipsis
+        }
+        cut
+    };
+    let padding = " ".repeat(width.saturating_sub(display_width(&text)));
+    match align {
+        Align::Left => format!("{}{}", text, padding),
+        Align::Right => format!("{}{}", padding, text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: [Column; 3] = [Column::right(1, 4, 2), Column::left(2, 4, 1), Column::left(5, 30, 0)];
+
+    fn rows() -> Vec<Vec<String>> {
+        vec![
+            vec!["3".to_string(), "12s".to_string(), "Build failed".to_string()],
+            vec!["14".to_string(), "2m".to_string(), "Tests passed on main".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_lowest_priority_shrinks_then_drops() {
+        // Natural widths fit
+        assert_eq!(column_widths(&COLUMNS, &rows(), 40, 1), [2, 3, 20]);
+        // The message shrinks first, down to its minimum
+        assert_eq!(column_widths(&COLUMNS, &rows(), 16, 1), [2, 3, 9]);
+        assert_eq!(column_widths(&COLUMNS, &rows(), 11, 1), [2, 2, 5]);
+        // When the minimums do not fit, the lowest-priority column is dropped
+        assert_eq!(column_widths(&COLUMNS, &rows(), 9, 1), [2, 3, 0]);
+        assert_eq!(column_widths(&COLUMNS, &rows(), 2, 1), [2, 0, 0]);
+    }
+
+    #[test]
+    fn test_rows_are_aligned_and_cut() {
+        let widths = column_widths(&COLUMNS, &rows(), 16, 1);
+        let formatted: Vec<String> = rows().iter().map(|row| format_row(&COLUMNS, &widths, row, 1)).collect();
+        assert_eq!(formatted, [" 3 12s Build fa\u{2026}", "14 2m  Tests pa\u{2026}"]);
+        assert_eq!(fit("\u{1F514}ab", 3, Align::Left), "\u{1F514}\u{2026}");
+        assert_eq!(fit("ab", 4, Align::Right), "  ab");
+    }
+}
@@ -0,0 +1,81 @@
+//! Message template module for Zellij Visual Notifications
+//!
+//! Senders can write generic messages that fit the receiving context: `{session}`, `{tab_name}`,
+//! `{pane_title}` and `{time}` in a notification's title or message are replaced when it is
+//! displayed, from what the plugin knows about the session then (the pane is the notification's
+//! target pane; the time is `HH:MM` UTC). A variable that can't be resolved, such as `{tab_name}`
+//! for a notification without a pane, is left as written, and so is any other text in braces.
+
+use chrono::DateTime;
+
+/// Values of the context variables, None when unknown
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    /// Zellij session name
+    pub session: Option<String>,
+    /// Name of the tab holding the target pane
+    pub tab_name: Option<String>,
+    /// Title of the target pane
+    pub pane_title: Option<String>,
+    /// Unix time of display (ms)
+    pub unix_ms: Option<u64>,
+}
+
+impl TemplateContext {
+    /// Value of a variable, None when unknown or not a variable
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "session" => self.session.clone(),
+            "tab_name" => self.tab_name.clone(),
+            "pane_title" => self.pane_title.clone(),
+            "time" => self.unix_ms
+                .and_then(|unix_ms| DateTime::from_timestamp_millis(unix_ms as i64))
+                .map(|time| time.format("%H:%M").to_string()),
+            _ => None,
+        }
+    }
+
+    /// Replace the context variables in `text`
+    pub fn expand(&self, text: &str) -> String {
+        let mut expanded = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            expanded.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let value = after.find('}').and_then(|close| Some((close, self.value(&after[..close])?)));
+            match value {
+                Some((close, value)) => {
+                    expanded.push_str(&value);
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    expanded.push('{');
+                    rest = after;
+                }
+            }
+        }
+        expanded.push_str(rest);
+        expanded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expands_known_variables() {
+        let context = TemplateContext {
+            session: Some("work".to_string()),
+            tab_name: Some("api".to_string()),
+            pane_title: None,
+            // 2026-10-16 14:03:22 UTC
+            unix_ms: Some(1_792_159_402_000),
+        };
+        assert_eq!(context.expand("Build done in {tab_name} ({session}) at {time}"), "Build done in api (work) at 14:03");
+        // Unknown values and other braces are left alone
+        assert_eq!(context.expand("{pane_title}: {command} {"), "{pane_title}: {command} {");
+        assert_eq!(context.expand("{{session}}"), "{work}");
+        assert_eq!(context.expand("no variables"), "no variables");
+    }
+}
@@ -96,6 +96,7 @@ mod integration_tests {
             speed: 50,
             cycles: 2,
             duration_ms: 1000,
+            ..AnimationConfig::default()
         };
         let engine = AnimationEngine::new(&config);
 
@@ -270,6 +271,2116 @@ mod integration_tests {
         assert_eq!(queue.len(), 2);
     }
 
+    #[test]
+    fn test_event_bus_drives_notification_lifecycle() {
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+
+        // Arrival through the bus shows the notification on its pane
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(2))));
+        assert!(state.dispatch());
+        assert!(state.pane_states.get(&2).map(|s| s.has_notification()).unwrap_or(false));
+
+        // Focusing the pane acknowledges it
+        state.bus.emit(DomainEvent::PaneFocused(2));
+        assert!(state.dispatch());
+        assert!(!state.pane_states[&2].has_notification());
+        assert!(state.bus.is_empty());
+    }
+
+    #[test]
+    fn test_dnd_holds_and_releases_notifications() {
+        use crate::bus::DomainEvent;
+        use crate::suppression::SuppressionKind;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::SuppressionStarted { kind: SuppressionKind::Dnd, duration_ms: None });
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Done").for_pane(3))));
+        state.dispatch();
+
+        // Held while DND is active, and visible in the render view
+        assert!(!state.pane_states.get(&3).map(|s| s.has_notification()).unwrap_or(false));
+        assert_eq!(state.view().suppression.map(|s| s.held), Some(1));
+
+        // Resuming releases the held notification
+        state.bus.emit(DomainEvent::SuppressionEnded);
+        state.dispatch();
+        assert!(state.pane_states[&3].has_notification());
+        assert!(state.view().suppression.is_none());
+    }
+
+    #[test]
+    fn test_focus_mode_ends_with_a_digest() {
+        use crate::bus::DomainEvent;
+        use crate::suppression::SuppressionKind;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::SuppressionStarted { kind: SuppressionKind::Focus, duration_ms: Some(25 * 60_000) });
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Built").for_pane(3))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Tested").for_pane(3))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::info("Indexed").for_pane(4))));
+        state.dispatch();
+
+        // Batched invisibly, with the countdown in the status bar
+        assert!(!state.pane_states.get(&3).map(|s| s.has_notification()).unwrap_or(false));
+        let suppression = state.view().suppression.unwrap();
+        assert_eq!((suppression.kind, suppression.held), (SuppressionKind::Focus, 3));
+
+        // Ending it shows the newest notification per pane and the digest
+        state.bus.emit(DomainEvent::SuppressionEnded);
+        state.dispatch();
+        assert_eq!(state.pane_states[&3].notification_message.as_deref(), Some("Tested"));
+        assert!(state.pane_states[&4].has_notification());
+        assert_eq!(state.view().status_message, Some("Focus ended: 3 arrived (1 info, 2 success)"));
+        assert!(state.view().suppression.is_none());
+    }
+
+    #[test]
+    fn test_huge_focus_duration_does_not_overflow() {
+        use crate::suppression::SuppressionKind;
+
+        let mut state = crate::State::default();
+        state.handle_timer(1.0);
+        state.toggle_focus(Some(u64::MAX));
+        state.dispatch();
+        assert_eq!(state.view().suppression.unwrap().kind, SuppressionKind::Focus);
+    }
+
+    #[test]
+    fn test_deadline_turns_overdue_once() {
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+        let mut notification = Notification::attention("Approve the deploy").for_pane(3);
+        notification.deadline_ms = Some(10_000);
+        let id = notification.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        state.dispatch();
+        let deadline_at_ms = state.pane_states[&3].deadline_at_ms.unwrap();
+        assert_eq!(deadline_at_ms, state.clock.now_ms() + 10_000);
+        assert!(state.check_deadlines());
+        assert!(!state.pane_states[&3].overdue);
+
+        state.bus.emit(DomainEvent::TickElapsed(11.0));
+        state.dispatch();
+        state.check_deadlines();
+        assert!(state.pane_states[&3].overdue);
+        assert!(state.audit.explain(&id).unwrap().contains("overdue (not acknowledged within its 10000ms deadline)"));
+
+        // Reported once; acknowledging ends the countdown
+        state.check_deadlines();
+        assert_eq!(state.audit.explain(&id).unwrap().matches("overdue").count(), 1);
+        state.bus.emit(DomainEvent::NotificationAcknowledged(id));
+        state.dispatch();
+        assert!(!state.check_deadlines());
+    }
+
+    #[test]
+    fn test_acknowledge_by_type_keeps_other_types() {
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Built").for_pane(1))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Tested").for_pane(2))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Deploy failed").for_pane(3))));
+        state.dispatch();
+
+        state.bus.emit(DomainEvent::TypeAcknowledged(NotificationType::Success));
+        state.dispatch();
+        assert!(!state.pane_states[&1].has_notification());
+        assert!(!state.pane_states[&2].has_notification());
+        assert!(state.pane_states[&3].has_notification());
+        assert_eq!(state.view().status_message, Some("Acknowledged 2 success"));
+    }
+
+    #[test]
+    fn test_history_records_held_notifications_once() {
+        use crate::bus::DomainEvent;
+        use crate::query::HistoryQuery;
+        use crate::suppression::SuppressionKind;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::SuppressionStarted { kind: SuppressionKind::Dnd, duration_ms: None });
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(1))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Built").for_pane(2))));
+        state.bus.emit(DomainEvent::SuppressionEnded);
+        state.dispatch();
+
+        let query = HistoryQuery::parse(&["type=error"]).unwrap();
+        let results = query.evaluate(&state.history, 0);
+        assert_eq!(results.len(), 1);
+        assert_eq!(state.history.len(), 2);
+    }
+
+    #[test]
+    fn test_audit_trail_explains_held_notification() {
+        use crate::bus::DomainEvent;
+        use crate::suppression::SuppressionKind;
+
+        let mut state = crate::State::default();
+        let notification = Notification::success("Done").for_pane(3);
+        let id = notification.id.clone();
+        state.bus.emit(DomainEvent::SuppressionStarted { kind: SuppressionKind::Dnd, duration_ms: None });
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        state.bus.emit(DomainEvent::SuppressionEnded);
+        state.dispatch();
+
+        let text = state.audit.explain(&id).unwrap();
+        assert!(text.contains("held (DND)"));
+        assert!(text.contains("routed to pane 3"));
+        assert!(text.contains("sink: visual"));
+        assert_eq!(state.audit.latest(), Some(id.as_str()));
+    }
+
+    #[test]
+    fn test_explain_interprets_exit_codes() {
+        use crate::bus::DomainEvent;
+        use crate::notification::NotificationBuilder;
+
+        let mut state = crate::State::default();
+        state.config.exit_codes.insert(3, "lint failed".to_string());
+        for (id, code) in [("oom", 137), ("lint", 3)] {
+            let mut notification = NotificationBuilder::new()
+                .notification_type(NotificationType::Error)
+                .message("cargo build failed")
+                .command("cargo build")
+                .exit_code(code)
+                .pane_id(2)
+                .build();
+            notification.id = id.to_string();
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+
+        assert!(state.explain(Some("oom".to_string()))
+            .contains("cargo build exited with 137: killed, often by the out-of-memory killer (SIGKILL)"));
+        assert!(state.explain(None).contains("cargo build exited with 3: lint failed"));
+        assert!(!state.explain(Some("missing".to_string())).contains("exited"));
+    }
+
+    #[test]
+    fn test_debug_pane_dumps_transitions() {
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        let payload = r#"{"type":"error","message":"Tests failed","pane_id":2}"#.to_string();
+        state.pipe(PipeMessage::new(PipeSource::Cli("1".to_string()), "claude-notify", &Some(payload.clone()), &None, false));
+        state.clear_pane_notification(2);
+        state.pipe(PipeMessage::new(PipeSource::Cli("1".to_string()), "claude-notify", &Some(payload), &None, false));
+
+        let dump = state.debug_pane(2);
+        assert!(dump.contains("state: Active (error, "), "{}", dump);
+        assert!(dump.contains("Idle -> Active (showed error "), "{}", dump);
+        assert!(dump.contains("Active -> Idle (cleared "), "{}", dump);
+        assert!(dump.contains("Queued (0):"), "{}", dump);
+        assert!(state.debug_pane(9).contains("no visual state"));
+    }
+
+    #[test]
+    fn test_exit_code_ranges_color_failures() {
+        use crate::exitcode::ExitCodeColor;
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.color_manager.set_exit_code_colors(vec![
+            ExitCodeColor::parse("1", "#ff6666").unwrap(),
+            ExitCodeColor::parse("128..", "#cc0000").unwrap(),
+        ]);
+        for (pane_id, exit_code) in [(2, 1), (3, 137), (4, 2)] {
+            let payload = format!(r#"{{"type":"error","priority":"critical","message":"failed","pane_id":{},"command":"make","exit_code":{}}}"#, pane_id, exit_code);
+            state.pipe(PipeMessage::new(PipeSource::Cli("1".to_string()), "claude-notify", &Some(payload), &None, false));
+        }
+        assert_eq!(state.pane_states[&2].border_color.as_deref(), Some("#ff6666"));
+        assert_eq!(state.pane_states[&3].border_color.as_deref(), Some("#cc0000"));
+        assert_eq!(state.pane_states[&4].border_color, state.color_manager.get_notification_color(&NotificationType::Error));
+    }
+
+    #[test]
+    fn test_extend_pushes_back_expiry() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier, PipeSource};
+
+        let mut state = crate::State::default();
+        state.config.ttl_extend_ms = 20_000;
+        let notification = Notification::error("Build failed").for_pane(2).with_ttl(10_000);
+        let id = notification.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        state.dispatch();
+        state.handle_timer(5.0);
+
+        let command = ControlCommand::parse(&format!("extend {} 30s", id)).unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert_eq!(state.displayed[&2].ttl_left(), Some(35_000));
+        assert!(state.audit.explain(&id).unwrap().contains("expiry extended by 30000ms"));
+
+        // The key extends the newest notification by ttl_extend_ms
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('e'))));
+        state.handle_timer(50.0);
+        assert!(state.pane_states[&2].has_notification());
+        state.handle_timer(10.0);
+        assert!(!state.pane_states.get(&2).is_some_and(|s| s.has_notification()));
+
+        let command = ControlCommand::parse("extend missing 30s").unwrap();
+        assert!(!state.handle_control_command(command, &PipeSource::Keybind));
+    }
+
+    #[test]
+    fn test_detail_view_ttl_controls() {
+        use crate::bus::DomainEvent;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        let notification = Notification::error("Build failed").for_pane(2).with_ttl(10_000);
+        let id = notification.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        state.dispatch();
+        let key = |c| KeyWithModifier::new(BareKey::Char(c));
+
+        // Keep: the notification outlives its TTL
+        assert!(state.handle_key(&key('i')));
+        assert!(state.handle_key(&key('k')));
+        assert_eq!(state.status_message.as_ref().unwrap().0, "Kept until acknowledged");
+        state.handle_timer(60.0);
+        assert!(state.pane_states[&2].has_notification());
+        assert!(state.audit.explain(&id).unwrap().contains("expiry removed (sticky)"));
+
+        // Expire now: gone without waiting
+        assert!(state.handle_key(&key('x')));
+        state.dispatch();
+        assert!(!state.pane_states.get(&2).is_some_and(|s| s.has_notification()));
+        assert!(state.audit.explain(&id).unwrap().contains("expired early by the user"));
+        assert!(state.make_sticky("missing").is_err());
+        assert!(state.expire_now("missing").is_err());
+    }
+
+    #[test]
+    fn test_attention_queue_surfaces_one_at_a_time() {
+        use crate::attention_queue::{self, AttentionQueue, AttentionSlot};
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+        state.config.attention_queue = AttentionQueue::Priority;
+        // Past the typing pause, so Attention is not deferred
+        state.handle_timer(5.0);
+        for (pane_id, priority) in [(1, Priority::Normal), (2, Priority::Critical), (3, Priority::Normal)] {
+            let message = format!("Session {} waits for input", pane_id);
+            let notification = Notification::attention(&message).for_pane(pane_id).with_priority(priority);
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+        assert_eq!(state.pane_states[&2].attention_slot, AttentionSlot::Surfaced);
+        assert!(!state.pane_states[&1].is_listed() && !state.pane_states[&3].is_listed());
+        assert_eq!(attention_queue::waiting_count(&state.pane_states), 2);
+
+        // Acknowledging the surfaced session brings up the next one, animating afresh
+        state.pane_states.get_mut(&1).unwrap().is_animating = false;
+        state.bus.emit(DomainEvent::PaneDismissed(2));
+        assert!(state.dispatch());
+        assert_eq!(state.pane_states[&1].attention_slot, AttentionSlot::Surfaced);
+        assert!(state.pane_states[&1].is_animating);
+        assert_eq!(attention_queue::waiting_count(&state.pane_states), 1);
+
+        // Switching the mode off lists them all again
+        state.config.attention_queue = AttentionQueue::Off;
+        state.dispatch();
+        assert!(state.pane_states[&1].is_listed() && state.pane_states[&3].is_listed());
+    }
+
+    #[test]
+    fn test_context_variables_resolved_on_display() {
+        use crate::bus::DomainEvent;
+        use crate::world::{PaneRecord, TabRecord};
+
+        let mut state = crate::State::default();
+        state.mode_info.session_name = Some("work".to_string());
+        state.world.set_panes(vec![PaneRecord { id: 3, title: "cargo test".to_string(), tab_position: 1, ..PaneRecord::default() }]);
+        state.world.set_tabs(vec![TabRecord { position: 1, name: "api".to_string(), ..TabRecord::default() }]);
+        let notification = Notification::success("{pane_title} passed in {tab_name} ({session})").for_pane(3);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::info("Done in {tab_name}"))));
+        state.dispatch();
+
+        assert_eq!(state.displayed[&3].message, "cargo test passed in api (work)");
+        assert_eq!(state.pane_states[&3].notification_message.as_deref(), Some("cargo test passed in api (work)"));
+        // The history keeps what was sent; a pane-less notification has no tab
+        assert!(state.history.newest_first().any(|entry| entry.notification.message == "{pane_title} passed in {tab_name} ({session})"));
+        assert!(state.history.newest_first().any(|entry| entry.notification.message == "Done in {tab_name}"));
+    }
+
+    #[test]
+    fn test_leader_chord_acts_on_focused_pane() {
+        use crate::bus::DomainEvent;
+        use crate::config::FocusedPaneSuppression;
+        use crate::state::InteractionMode;
+        use crate::world::PaneRecord;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        state.config.suppress_for_focused_pane = FocusedPaneSuppression::Off;
+        state.world.set_panes(vec![
+            PaneRecord { id: 1, is_focused: true, ..PaneRecord::default() },
+            PaneRecord { id: 2, ..PaneRecord::default() },
+        ]);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Focused").for_pane(1))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Newest").for_pane(2))));
+        state.dispatch();
+
+        // The leader opens the chord (and its hint bar); the action key acknowledges the focused pane
+        let leader = KeyWithModifier::new(BareKey::Char('y')).with_ctrl_modifier();
+        assert!(state.handle_key(&leader));
+        assert!(matches!(state.interaction_mode, InteractionMode::Chord { .. }));
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('a'))));
+        state.dispatch();
+        assert_eq!(state.interaction_mode, InteractionMode::Normal);
+        assert!(!state.pane_states[&1].has_notification());
+        assert!(state.pane_states[&2].has_notification());
+
+        // Without a notification on the focused pane, the chord acts on the newest one
+        state.handle_key(&leader);
+        state.handle_key(&KeyWithModifier::new(BareKey::Char('p')));
+        assert!(state.pane_states[&2].pinned);
+
+        // Other keys cancel, and so does waiting
+        state.handle_key(&leader);
+        state.handle_key(&KeyWithModifier::new(BareKey::Char('z')));
+        assert_eq!(state.interaction_mode, InteractionMode::Normal);
+        state.handle_key(&leader);
+        state.handle_timer(5.0);
+        assert_eq!(state.interaction_mode, InteractionMode::Normal);
+
+        // Ctrl+n stays clear-all unless it is the leader
+        state.config.chord_leader = "Ctrl n".to_string();
+        let ctrl_n = KeyWithModifier::new(BareKey::Char('n')).with_ctrl_modifier();
+        assert!(state.is_chord_leader(&ctrl_n));
+        assert!(!state.is_chord_leader(&leader));
+    }
+
+    #[test]
+    fn test_focused_pane_notifications_suppressed() {
+        use crate::bus::DomainEvent;
+        use crate::config::FocusedPaneSuppression;
+        use crate::world::PaneRecord;
+
+        let mut state = crate::State::default();
+        state.world.set_panes(vec![
+            PaneRecord { id: 1, is_focused: true, ..PaneRecord::default() },
+            PaneRecord { id: 2, ..PaneRecord::default() },
+        ]);
+
+        state.config.suppress_for_focused_pane = FocusedPaneSuppression::HistoryOnly;
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Focused").for_pane(1))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Background").for_pane(2))));
+        state.dispatch();
+        assert!(!state.pane_states.get(&1).map(|s| s.has_notification()).unwrap_or(false));
+        assert!(state.pane_states[&2].has_notification());
+        assert_eq!(state.history.len(), 2);
+
+        state.config.suppress_for_focused_pane = FocusedPaneSuppression::Acknowledge;
+        let notification = Notification::warning("Acknowledged").for_pane(1);
+        let id = notification.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        state.dispatch();
+        assert!(!state.pane_states[&1].has_notification());
+        assert!(state.audit.explain(&id).unwrap().contains("acknowledged"));
+    }
+
+    #[test]
+    fn test_progress_updates_drive_eta() {
+        use crate::bus::DomainEvent;
+        use crate::notification::NotificationBuilder;
+
+        let mut state = crate::State::default();
+        let update = |percent| NotificationBuilder::new()
+            .notification_type(NotificationType::Progress)
+            .message("Building")
+            .pane_id(2)
+            .percent(percent)
+            .build();
+
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(update(10.0))));
+        state.dispatch();
+        for _ in 0..20 {
+            state.handle_timer(0.05);
+        }
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(update(20.0))));
+        state.dispatch();
+
+        // 10% per second leaves 8 seconds
+        let progress = state.pane_states[&2].progress.unwrap();
+        assert_eq!(progress.percent, 20.0);
+        assert_eq!(progress.elapsed_ms, 1000);
+        assert_eq!(progress.eta_ms, Some(8000));
+
+        state.handle_timer(0.5);
+        assert_eq!(state.pane_states[&2].progress.unwrap().eta_ms, Some(7500));
+    }
+
+    #[test]
+    fn test_closed_pane_notification_is_cancelled() {
+        use crate::bus::DomainEvent;
+        use zellij_tile::prelude::{PaneInfo, PaneManifest};
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(4))));
+        state.dispatch();
+
+        let mut manifest = PaneManifest::default();
+        manifest.panes.insert(0, vec![PaneInfo { id: 4, ..PaneInfo::default() }]);
+        state.handle_pane_update(manifest);
+        state.dispatch();
+        assert!(state.pane_states[&4].has_notification());
+        assert_eq!(state.world.tab_of_pane(4), Some(0));
+
+        state.handle_pane_update(PaneManifest::default());
+        state.dispatch();
+        assert!(!state.pane_states[&4].has_notification());
+    }
+
+    #[test]
+    fn test_pane_updates_do_not_defer_attention() {
+        use crate::bus::DomainEvent;
+        use zellij_tile::prelude::{Event, ModeInfo, PaneInfo, PaneManifest, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        let titled = |title: String| {
+            let mut manifest = PaneManifest::default();
+            manifest.panes.insert(0, vec![PaneInfo { id: 4, title, ..PaneInfo::default() }]);
+            manifest
+        };
+
+        // A running program keeps retitling its pane, from startup on
+        for percent in [0, 20, 40, 60, 80] {
+            state.update(Event::PaneUpdate(titled(format!("cargo build {}%", percent))));
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::attention("Allow Bash?").for_pane(4))));
+            state.dispatch();
+            assert!(state.deferred_notifications.is_empty());
+            state.update(Event::Timer(0.5));
+        }
+        assert_eq!(state.pane_states[&4].notification_type, Some(NotificationType::Attention));
+
+        // A mode change is the user at work: the next one waits for a pause
+        state.update(Event::ModeUpdate(ModeInfo::default()));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::attention("Continue?").for_pane(5))));
+        state.dispatch();
+        assert_eq!(state.deferred_notifications.len(), 1);
+    }
+
+    #[test]
+    fn test_pane_title_reverting_raises_completion() {
+        use zellij_tile::prelude::{PaneInfo, PaneManifest};
+
+        let mut state = crate::State::default();
+        state.config.passive_detection = true;
+        let titled = |title: &str| {
+            let mut manifest = PaneManifest::default();
+            manifest.panes.insert(0, vec![PaneInfo { id: 4, title: title.to_string(), ..PaneInfo::default() }]);
+            manifest
+        };
+        state.handle_pane_update(titled("zsh"));
+        state.handle_pane_update(titled("cargo build"));
+        state.handle_timer(15.0);
+        state.handle_pane_update(titled("zsh"));
+        state.dispatch();
+        assert_eq!(state.pane_states[&4].notification_type, Some(NotificationType::Success));
+        assert_eq!(state.pane_states[&4].notification_message.as_deref(), Some("`cargo build` finished (title reverted to zsh)"));
+        let entry = state.history.newest_first().next().unwrap();
+        assert_eq!(entry.notification.source, "passive");
+        assert_eq!(entry.notification.metadata.duration_ms, Some(15_000));
+    }
+
+    #[test]
+    fn test_claude_panes_skip_passive_detection() {
+        use crate::bus::DomainEvent;
+        use zellij_tile::prelude::{PaneInfo, PaneManifest};
+
+        let mut state = crate::State::default();
+        state.config.passive_detection = true;
+        let titled = |title: &str| {
+            let mut manifest = PaneManifest::default();
+            manifest.panes.insert(0, vec![
+                PaneInfo { id: 4, title: title.to_string(), terminal_command: Some("claude".to_string()), ..PaneInfo::default() },
+                PaneInfo { id: 5, title: "zsh".to_string(), ..PaneInfo::default() },
+            ]);
+            manifest
+        };
+        state.handle_pane_update(titled("zsh"));
+        state.handle_pane_update(titled("npm test"));
+        state.handle_timer(15.0);
+        state.handle_pane_update(titled("zsh"));
+        state.dispatch();
+        assert!(state.history.newest_first().next().is_none());
+
+        // Its hook notifications are marked for the accent and ordering
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(4))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Build failed").for_pane(5))));
+        state.dispatch();
+        assert!(state.pane_states[&4].claude_pane);
+        assert!(!state.pane_states[&5].claude_pane);
+    }
+
+    #[test]
+    fn test_missing_pane_handling() {
+        use crate::bus::DomainEvent;
+        use crate::config::MissingPane;
+        use crate::world::TabRecord;
+        use zellij_tile::prelude::{PaneInfo, PaneManifest};
+
+        let mut state = crate::State::default();
+        let layout = |ids: &[u32]| {
+            let mut manifest = PaneManifest::default();
+            manifest.panes.insert(0, ids.iter().map(|&id| PaneInfo { id, ..PaneInfo::default() }).collect());
+            manifest
+        };
+        state.handle_pane_update(layout(&[1]));
+        state.world.set_tabs(vec![TabRecord { position: 2, active: true, ..TabRecord::default() }]);
+
+        // Held until the sender's new pane shows up
+        let early = Notification::error("Tests failed").for_pane(7);
+        let early_id = early.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(early)));
+        state.dispatch();
+        assert!(!state.pane_states.contains_key(&7));
+        state.handle_pane_update(layout(&[1, 7]));
+        state.dispatch();
+        assert!(state.pane_states[&7].has_notification());
+        assert!(state.audit.explain(&early_id).unwrap().contains("pane 7 not in layout: held until it appears"));
+
+        // Dropped once the hold time runs out
+        let lost = Notification::error("Build failed").for_pane(8);
+        let lost_id = lost.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(lost)));
+        state.dispatch();
+        state.handle_timer(6.0);
+        state.handle_pane_update(layout(&[1, 7, 8]));
+        state.dispatch();
+        assert!(!state.pane_states.contains_key(&8));
+        assert!(state.audit.explain(&lost_id).unwrap().contains("pane 8 not in layout: dropped"));
+
+        // Moved to the active tab
+        state.config.missing_pane = MissingPane::Tab;
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::warning("Disk almost full").for_pane(9))));
+        state.dispatch();
+        let entry = state.history.newest_first().next().unwrap();
+        assert_eq!((entry.notification.pane_id, entry.notification.tab_index), (None, Some(2)));
+
+        state.config.missing_pane = MissingPane::Drop;
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::warning("Gone").for_pane(9))));
+        state.dispatch();
+        assert_eq!(state.history.newest_first().next().unwrap().notification.message, "Disk almost full");
+    }
+
+    #[test]
+    fn test_delivery_latency_from_sender_timestamp() {
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+        let sent = 1_760_000_000_000;
+        // Without Unix time, sender timestamps cannot be compared
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::info("Early").for_pane(1).at_time(sent))));
+        state.dispatch();
+
+        state.clock.sync_unix(sent + 300);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Build failed").for_pane(2).at_time(sent))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::info("Unstamped").for_pane(3))));
+        state.dispatch();
+        assert_eq!(state.latency.line(), "latency: arrival p50 300ms, p95 300ms; display p50 300ms, p95 300ms; 0 slow");
+    }
+
+    #[test]
+    fn test_reduced_motion_at_runtime_stops_animations() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::renderer::BorderLineStyle;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(2))));
+        state.dispatch();
+        assert!(state.pane_states[&2].is_animating);
+
+        let command = ControlCommand::Set { setting: "reduced_motion".to_string(), enabled: true };
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert!(!state.pane_states[&2].is_animating);
+
+        // Urgent notifications keep a static double border instead
+        let border = state.renderer
+            .get_border_style(&state.pane_states[&2], &state.color_manager, &state.animation_engine, state.tick_count)
+            .unwrap();
+        assert!(matches!(border.style, BorderLineStyle::Double));
+    }
+
+    #[test]
+    fn test_low_power_follows_battery_signal() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::power::PowerSource;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(2))));
+        state.dispatch();
+        assert!(state.pane_states[&2].is_animating);
+
+        // On battery: slow ticks and static emphasis
+        assert!(state.handle_control_command(ControlCommand::Power(PowerSource::Battery), &PipeSource::Keybind));
+        assert!(state.config.low_power);
+        assert!(!state.pane_states[&2].is_animating);
+        assert_eq!(state.tick_interval_secs(), 1.0);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Build failed").for_pane(3))));
+        state.dispatch();
+        assert!(!state.pane_states[&3].is_animating);
+
+        state.handle_control_command(ControlCommand::Power(PowerSource::Ac), &PipeSource::Keybind);
+        assert!(!state.config.low_power);
+        assert_eq!(state.tick_interval_secs(), 0.05);
+
+        // Switched on by hand, it stays on when plugged in
+        state.handle_control_command(ControlCommand::LowPower(Some(true)), &PipeSource::Keybind);
+        state.handle_control_command(ControlCommand::Power(PowerSource::Ac), &PipeSource::Keybind);
+        assert!(state.config.low_power);
+    }
+
+    #[test]
+    fn test_ttl_paused_while_tab_hidden() {
+        use crate::bus::DomainEvent;
+        use crate::world::{PaneRecord, TabRecord};
+
+        let mut state = crate::State::default();
+        state.config.pause_ttl_in_background = true;
+        let tab = |position, active| TabRecord { position, active, ..TabRecord::default() };
+        state.world.set_tabs(vec![tab(0, true), tab(1, false)]);
+        state.world.set_panes(vec![PaneRecord { id: 5, tab_position: 1, ..PaneRecord::default() }]);
+        state.update_ttl_pause();
+
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(
+            Notification::error("Background failure").for_pane(5).with_ttl(1000),
+        )));
+        state.bus.emit(DomainEvent::TickElapsed(2.0));
+        state.dispatch();
+        assert!(state.pane_states[&5].has_notification());
+
+        // Switching to the pane's tab resumes the countdown
+        state.world.set_tabs(vec![tab(0, false), tab(1, true)]);
+        state.update_ttl_pause();
+        state.bus.emit(DomainEvent::TickElapsed(2.0));
+        state.dispatch();
+        assert!(!state.pane_states[&5].has_notification());
+    }
+
+    #[test]
+    fn test_animations_hold_while_tab_hidden() {
+        use crate::bus::DomainEvent;
+        use crate::config::HiddenTabAnimation;
+        use crate::world::{PaneRecord, TabRecord};
+
+        let mut state = crate::State::default();
+        let tab = |position, active| TabRecord { position, active, ..TabRecord::default() };
+        state.world.set_tabs(vec![tab(0, true), tab(1, false)]);
+        state.world.set_panes(vec![
+            PaneRecord { id: 5, tab_position: 1, ..PaneRecord::default() },
+            PaneRecord { id: 6, tab_position: 1, ..PaneRecord::default() },
+        ]);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Background failure").for_pane(5))));
+        state.dispatch();
+        assert!(state.pane_states[&5].is_animating);
+
+        // Far longer than the animation: it waits for the tab
+        for _ in 0..500 {
+            state.handle_timer(0.05);
+        }
+        assert!(state.pane_states[&5].is_animating);
+        assert!(state.pane_states[&5].animation_paused_at.is_some());
+
+        // Shown again, it carries on where it stopped
+        state.world.set_tabs(vec![tab(0, false), tab(1, true)]);
+        state.handle_timer(0.05);
+        assert!(state.pane_states[&5].is_animating);
+        assert!(state.pane_states[&5].animation_paused_at.is_none());
+        for _ in 0..500 {
+            state.handle_timer(0.05);
+        }
+        assert!(!state.pane_states[&5].is_animating);
+
+        // Skip ends the animation of a hidden pane at once
+        state.config.animation.hidden_tabs = HiddenTabAnimation::Skip;
+        state.world.set_tabs(vec![tab(0, true), tab(1, false)]);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Another failure").for_pane(6))));
+        state.dispatch();
+        state.handle_timer(0.05);
+        assert!(!state.pane_states[&6].is_animating);
+        assert_eq!(state.pane_states[&6].brightness, 1.0);
+    }
+
+    #[test]
+    fn test_focused_tab_boost_follows_tab_switches() {
+        use crate::bus::DomainEvent;
+        use crate::state::TabLocality;
+        use crate::world::{PaneRecord, TabRecord};
+
+        let mut state = crate::State::default();
+        let tab = |position, active| TabRecord { position, active, ..TabRecord::default() };
+        state.world.set_tabs(vec![tab(0, true), tab(1, false)]);
+        state.world.set_panes(vec![
+            PaneRecord { id: 1, tab_position: 0, ..PaneRecord::default() },
+            PaneRecord { id: 2, tab_position: 1, ..PaneRecord::default() },
+        ]);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Build finished").for_pane(1))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(
+            Notification::warning("Disk almost full").for_pane(2).with_priority(Priority::High),
+        )));
+        state.dispatch();
+        let display = |state: &crate::State| (state.pane_states[&1].display_priority(), state.pane_states[&2].display_priority());
+        assert_eq!(state.pane_states[&1].tab_locality, TabLocality::Active);
+        assert_eq!(display(&state), (Priority::High, Priority::Normal));
+
+        // Switching tabs flips the order; the notifications' own priorities are untouched
+        state.world.set_tabs(vec![tab(0, false), tab(1, true)]);
+        state.sync_tab_locality();
+        assert_eq!(display(&state), (Priority::Low, Priority::Critical));
+        state.world.set_tabs(vec![tab(0, true), tab(1, false)]);
+        state.sync_tab_locality();
+        assert_eq!(display(&state), (Priority::High, Priority::Normal));
+        assert_eq!((state.pane_states[&1].priority, state.pane_states[&2].priority), (Priority::Normal, Priority::High));
+
+        // Switched off, only the notifications' priorities count
+        state.config.focused_tab_boost = false;
+        state.sync_tab_locality();
+        assert_eq!(display(&state), (Priority::Normal, Priority::High));
+    }
+
+    #[test]
+    fn test_hidden_channel_keeps_counting() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::PipeSource;
+
+        let mut state = crate::State::default();
+        let notify = |pane_id| NotificationBuilder::new()
+            .notification_type(NotificationType::Error)
+            .message("nightly backup failed")
+            .pane_id(pane_id)
+            .channel("cron")
+            .build();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notify(3))));
+        state.dispatch();
+        assert!(state.pane_states[&3].is_shown());
+
+        let command = ControlCommand::Channel { name: "cron".to_string(), visible: Some(false) };
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert!(state.pane_states[&3].has_notification());
+        assert!(!state.pane_states[&3].is_shown());
+        assert_eq!(crate::title::compact_summary(&state.pane_states), "");
+
+        // New notifications on the hidden channel arrive hidden and are counted
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(notify(4))));
+        state.dispatch();
+        assert!(state.pane_states[&4].hidden);
+        assert_eq!(state.channels.lines(), vec!["1 [ ] cron (2)"]);
+    }
+
+    #[test]
+    fn test_unacknowledged_critical_escalates_once() {
+        use crate::bus::DomainEvent;
+        use crate::sinks::SinkPipeline;
+
+        let mut state = crate::State::default();
+        state.config.sinks = vec!["visual".to_string(), "desktop".to_string()];
+        state.config.ack_escalation_ms = 1000;
+        state.sinks = SinkPipeline::from_config(&state.config);
+
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Deploy failed").for_pane(2))));
+        state.dispatch();
+        let escalations = |state: &crate::State| state.pending_output.iter()
+            .filter(|output| output.contains("Unacknowledged"))
+            .count();
+
+        state.handle_timer(0.5);
+        assert_eq!(escalations(&state), 0);
+        state.handle_timer(0.6);
+        state.handle_timer(5.0);
+        assert_eq!(escalations(&state), 1);
+        assert!(state.history.newest_first().next().unwrap().ack_escalated_at_ms.is_some());
+    }
+
+    #[test]
+    fn test_simulated_focus_and_close() {
+        use crate::bus::DomainEvent;
+        use crate::commands::{ControlCommand, SimulatedEvent};
+        use crate::PipeSource;
+        use zellij_tile::prelude::{PaneInfo, PaneManifest};
+
+        let mut state = crate::State::default();
+        let pane = |id, is_focused| PaneInfo { id, is_focused, pane_rows: 20, pane_content_rows: 18, ..PaneInfo::default() };
+        let mut manifest = PaneManifest::default();
+        manifest.panes.insert(0, vec![pane(1, true), pane(2, false), pane(3, false)]);
+        state.handle_pane_update(manifest);
+
+        for pane_id in [2, 3] {
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Failed").for_pane(pane_id))));
+        }
+        state.dispatch();
+
+        // Focusing pane 2 acknowledges its notification; closing pane 3 cancels its one
+        assert!(state.handle_control_command(ControlCommand::Simulate(SimulatedEvent::Focus(2)), &PipeSource::Keybind));
+        state.dispatch();
+        assert!(!state.pane_states[&2].has_notification());
+        assert_eq!(state.world.focused_pane(), Some(2));
+
+        assert!(state.handle_control_command(ControlCommand::Simulate(SimulatedEvent::PaneClose(3)), &PipeSource::Keybind));
+        state.dispatch();
+        assert!(!state.pane_states[&3].has_notification());
+        assert!(state.world.pane(3).is_none());
+
+        assert!(!state.handle_control_command(ControlCommand::Simulate(SimulatedEvent::Focus(9)), &PipeSource::Keybind));
+    }
+
+    #[test]
+    fn test_reminder_repeats_until_cancelled() {
+        use crate::event_bridge::BridgeMessage;
+
+        let mut state = crate::State::default();
+        let message = state.event_bridge
+            .parse_message(r#"{"type": "reminder", "id": "water", "message": "drink water", "pane_id": 2, "every_ms": 1000}"#)
+            .unwrap();
+        assert!(matches!(message, BridgeMessage::Remind(_)));
+        state.handle_bridge_message(message);
+        state.dispatch();
+
+        state.handle_timer(1.0);
+        state.dispatch();
+        assert_eq!(state.pane_states[&2].notification_message.as_deref(), Some("drink water"));
+
+        // Acknowledged reminders come back on the next interval
+        state.clear_pane_notification(2);
+        state.handle_timer(1.0);
+        state.dispatch();
+        assert!(state.pane_states[&2].has_notification());
+
+        let cancel = state.event_bridge.parse_message(r#"{"type": "cancel", "id": "water"}"#).unwrap();
+        state.handle_bridge_message(cancel);
+        state.dispatch();
+        state.clear_pane_notification(2);
+        state.handle_timer(1.0);
+        state.dispatch();
+        assert!(!state.pane_states[&2].has_notification());
+        assert!(state.reminders.is_empty());
+    }
+
+    #[test]
+    fn test_pinned_notification_survives_ttl_and_clear_all() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::PipeSource;
+
+        let mut state = crate::State::default();
+        let failure = Notification::error("Release build failed").for_pane(2).with_ttl(1000);
+        let id = failure.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(failure)));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::info("Lint ok").for_pane(3))));
+        state.dispatch();
+
+        assert!(state.handle_control_command(ControlCommand::Pin { id: id.clone(), pinned: true }, &PipeSource::Keybind));
+        assert!(!state.handle_control_command(
+            ControlCommand::Pin { id: "unknown".to_string(), pinned: true },
+            &PipeSource::Keybind,
+        ));
+        state.handle_timer(5.0);
+        assert!(state.pane_states[&2].has_notification());
+
+        // Clear-all keeps the pinned entry unless forced
+        state.handle_control_command(ControlCommand::ClearAll { force: false }, &PipeSource::Keybind);
+        state.dispatch();
+        assert!(state.pane_states[&2].has_notification());
+        assert!(!state.pane_states[&3].has_notification());
+
+        state.handle_control_command(ControlCommand::ClearAll { force: true }, &PipeSource::Keybind);
+        state.dispatch();
+        assert!(!state.pane_states[&2].has_notification());
+        assert!(!state.pane_states[&2].pinned);
+    }
+
+    #[test]
+    fn test_stress_run_goes_through_the_queue() {
+        use crate::commands::ControlCommand;
+        use crate::PipeSource;
+
+        let mut state = crate::State::default();
+        state.handle_control_command(ControlCommand::Stress { count: 12, rate: 100 }, &PipeSource::Keybind);
+        state.handle_timer(0.05);
+        state.dispatch();
+        let run = state.stress.as_ref().unwrap();
+        assert!(run.is_running());
+        assert!(run.line(state.clock.now_ms()).starts_with("stress: 5/12 at 100/s"));
+
+        state.handle_timer(1.0);
+        state.dispatch();
+        let run = state.stress.as_ref().unwrap();
+        assert!(!run.is_running());
+        let outcomes = run.outcomes;
+        assert_eq!(outcomes.queued + outcomes.coalesced + outcomes.rejected, 12);
+        assert!(state.history.newest_first().all(|entry| entry.notification.source == crate::stress::STRESS_SOURCE));
+
+        assert!(!state.handle_control_command(ControlCommand::StressStop, &PipeSource::Keybind));
+    }
+
+    #[test]
+    fn test_preferences_follow_runtime_changes_until_reset() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::surface::DisplaySurface;
+        use crate::PipeSource;
+
+        let mut state = crate::State::default();
+        let failure = Notification::error("Release build failed").for_pane(2);
+        let id = failure.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(failure)));
+        state.dispatch();
+        state.handle_control_command(ControlCommand::Pin { id: id.clone(), pinned: true }, &PipeSource::Keybind);
+        state.handle_control_command(ControlCommand::Dnd(None), &PipeSource::Keybind);
+        state.handle_control_command(
+            ControlCommand::Surface { surface: DisplaySurface::Toasts, enabled: Some(false) },
+            &PipeSource::Keybind,
+        );
+        state.dispatch();
+        state.handle_timer(0.05);
+        assert!(state.prefs.dnd);
+        assert_eq!(state.prefs.disabled_surfaces, vec!["toasts".to_string()]);
+        assert_eq!(state.prefs.pinned[0].id, id);
+
+        // A timed DND is not a preference
+        state.handle_control_command(ControlCommand::Dnd(Some(5)), &PipeSource::Keybind);
+        state.dispatch();
+        state.handle_timer(0.05);
+        assert!(!state.prefs.dnd);
+
+        state.handle_control_command(ControlCommand::PrefsReset, &PipeSource::Keybind);
+        state.dispatch();
+        state.handle_timer(0.05);
+        assert!(state.prefs.is_empty());
+        assert!(state.surface_toggles.is_enabled(DisplaySurface::Toasts));
+        assert!(!state.pane_states[&2].pinned && state.pane_states[&2].has_notification());
+    }
+
+    #[test]
+    fn test_claude_hook_reasons_get_their_policies() {
+        use crate::config::AnimationStyle;
+
+        let mut state = crate::State::default();
+        let permission = state.event_bridge.parse_message(
+            r#"{"hook_event_name": "Notification", "notification_type": "permission_prompt",
+                "message": "Claude needs your permission to use Bash", "session_id": "abc", "pane_id": 2}"#,
+        ).unwrap();
+        let finished = state.event_bridge
+            .parse_message(r#"{"hook_event_name": "Stop", "stop_hook_active": false, "pane_id": 3}"#)
+            .unwrap();
+        state.handle_bridge_message(permission);
+        state.handle_bridge_message(finished);
+        state.dispatch();
+
+        let permission = &state.pane_states[&2];
+        assert_eq!(permission.priority, Priority::Critical);
+        assert_eq!(permission.animation_style, AnimationStyle::Flash);
+        assert_eq!(permission.custom_icon.as_deref(), Some("\u{1F510}"));
+
+        let finished = &state.pane_states[&3];
+        assert_eq!(finished.notification_type, Some(NotificationType::Success));
+        assert_eq!(finished.animation_style, AnimationStyle::Breathe);
+        assert_eq!(finished.notification_message.as_deref(), Some("Claude finished"));
+    }
+
+    #[test]
+    fn test_other_project_notifications_collapse() {
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.config.project_roots = vec!["/work/api".to_string()];
+        // Hook payloads carry the cwd as-is; the line protocol has a field for it
+        let api = state.event_bridge.parse_message(
+            r#"{"hook_event_name": "Stop", "stop_hook_active": false, "cwd": "/work/api/crates/core", "pane_id": 2}"#,
+        ).unwrap();
+        let web = state.event_bridge.parse_line("error|msg=Build failed|cwd=/work/web|pane=3").unwrap();
+        let unscoped = state.event_bridge.parse_line("info|msg=Backup done|pane=4").unwrap();
+        for message in [api, web, unscoped] {
+            state.handle_bridge_message(message);
+        }
+        state.dispatch();
+        assert!(state.pane_states[&2].is_listed());
+        assert!(!state.pane_states[&3].is_listed());
+        assert!(state.pane_states[&4].is_listed());
+
+        let command = ControlCommand::parse("config_set project_roots /work/web").unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert!(!state.pane_states[&2].is_listed());
+        assert!(state.pane_states[&3].is_listed());
+    }
+
+    #[test]
+    fn test_correlated_notifications_are_grouped() {
+        let mut state = crate::State::default();
+        for (pane_id, kind, message, correlation) in [
+            (2, "success", "Server up", "deploy-42"),
+            (3, "error", "Tests failed", "deploy-42"),
+            (4, "info", "Unrelated", ""),
+        ] {
+            let msg = state.event_bridge.parse_message(&format!(
+                r#"{{"type": "{}", "message": "{}", "pane_id": {}, "correlation_id": "{}"}}"#,
+                kind, message, pane_id, correlation
+            )).unwrap();
+            state.handle_bridge_message(msg);
+        }
+        state.dispatch();
+
+        assert_eq!(state.pane_states[&2].correlation_id.as_deref(), Some("deploy-42"));
+        assert_eq!(state.pane_states[&4].correlation_id, None);
+
+        let groups = crate::correlation::groups(&state.pane_states);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].header(), "deploy-42 (2)");
+        assert_eq!(groups[0].members.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_numbered_notifications_are_reordered() {
+        let mut state = crate::State::default();
+        for (seq, kind, message) in [(1, "progress", "Deploying"), (3, "success", "Deployed"), (2, "progress", "Deploying 50%")] {
+            let msg = state.event_bridge.parse_message(&format!(
+                r#"{{"type": "{}", "message": "{}", "pane_id": 2, "source": "ci", "seq": {}}}"#,
+                kind, message, seq
+            )).unwrap();
+            state.handle_bridge_message(msg);
+            state.dispatch();
+        }
+        assert_eq!(state.pane_states[&2].notification_message.as_deref(), Some("Deployed"));
+
+        // A gap is skipped once the hold time runs out
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "Rolled back", "pane_id": 2, "source": "ci", "seq": 6}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert_eq!(state.pane_states[&2].notification_message.as_deref(), Some("Deployed"));
+
+        state.handle_timer(1.0);
+        state.dispatch();
+        assert_eq!(state.pane_states[&2].notification_message.as_deref(), Some("Rolled back"));
+        assert!(state.health_report().lines().iter().any(|line| line.contains("1 sequence gaps skipped")));
+    }
+
+    #[test]
+    fn test_pane_snapshot_is_attached_to_history() {
+        use std::collections::BTreeMap;
+
+        let mut state = crate::State::default();
+        state.config.capture_error_lines = 2;
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "Build failed", "pane_id": 3, "id": "build-1"}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+
+        let mut context = BTreeMap::new();
+        context.insert(crate::snapshot::SNAPSHOT_CONTEXT_KEY.to_string(), "build-1".to_string());
+        state.handle_run_command_result(Some(0), b"$ make\ncc main.c\nmain.c:3: error\n\n", b"", &context);
+
+        let entry = state.history.get("build-1").unwrap();
+        assert_eq!(entry.snapshot.as_deref(), Some(&["cc main.c".to_string(), "main.c:3: error".to_string()][..]));
+    }
+
+    #[test]
+    fn test_unacknowledged_attention_breaches_slo() {
+        let mut state = crate::State::default();
+        state.config.slo.insert("attention".to_string(), 2000);
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "attention", "message": "Allow Bash?", "pane_id": 2, "reason": "permission"}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert!(!state.pane_states[&2].slo_breached);
+
+        state.handle_timer(1.0);
+        state.handle_timer(1.5);
+        assert!(state.pane_states[&2].slo_breached);
+
+        state.bus.emit(crate::bus::DomainEvent::PaneFocused(2));
+        state.dispatch();
+        let lines = state.slo.lines(&state.config.slo);
+        assert!(lines[0].starts_with("attention  target 2s  acked 1"), "{}", lines[0]);
+        assert!(lines[0].ends_with("breaches 1"));
+    }
+
+    #[test]
+    fn test_rules_dry_run_only_annotates() {
+        use crate::rules::Rule;
+
+        let mut state = crate::State::default();
+        state.config.rules = vec![Rule::parse("quiet-ci", "source=ci", "priority=low pane=4").unwrap()];
+        state.config.rules_dry_run = true;
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "Build failed", "pane_id": 3, "source": "ci", "id": "ci-1"}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert!(state.pane_states[&3].has_notification());
+        assert!(state.audit.explain("ci-1").unwrap().contains("rule quiet-ci (dry run): would priority low, pane 4"));
+
+        state.config.rules_dry_run = false;
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "Build failed", "pane_id": 3, "source": "ci", "id": "ci-2"}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert_eq!(state.pane_states[&4].priority, Priority::Low);
+        assert!(state.audit.explain("ci-2").unwrap().contains("rule quiet-ci: priority low, pane 4"));
+    }
+
+    #[test]
+    fn test_expired_error_is_demoted_to_badge_only_warning() {
+        use crate::surface::Surface;
+
+        let mut state = crate::State::default();
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "Build failed", "pane_id": 3, "id": "b-1", "ttl_ms": 1000, "on_expire": "demote"}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert!(state.pane_states[&3].uses_surface(Surface::Border));
+
+        state.handle_timer(1.5);
+        let visual_state = &state.pane_states[&3];
+        assert_eq!(visual_state.notification_type, Some(NotificationType::Warning));
+        assert_eq!(visual_state.priority, Priority::Low);
+        assert!(!visual_state.uses_surface(Surface::Border) && !visual_state.is_animating);
+        assert!(state.audit.explain("b-1").unwrap().contains("demoted to badge only"));
+
+        // After the second TTL it is archived
+        state.handle_timer(1.5);
+        assert!(state.audit.explain("b-1").unwrap().contains("expired, archived"));
+    }
+
+    #[test]
+    fn test_recurring_notification_rearms_until_acknowledged() {
+        let mut state = crate::State::default();
+        let msg = state.event_bridge
+            .parse_line("error|pane=3|id=t-1|ttl=1000|recur=5|msg=tests red")
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert_eq!(state.displayed[&3].recur_until_ack_ms, Some(5000));
+
+        // Outlives its TTL, and re-animates once the interval is up without escalating
+        state.handle_timer(1.5);
+        state.pane_states.get_mut(&3).unwrap().is_animating = false;
+        state.handle_timer(1.5);
+        assert!(!state.pane_states[&3].is_animating);
+        state.handle_timer(2.5);
+        let visual_state = &state.pane_states[&3];
+        assert!(visual_state.is_animating);
+        assert_eq!(visual_state.priority, Priority::Critical);
+        assert!(!state.displayed[&3].escalated);
+
+        // Acknowledging stops it
+        state.clear_pane_notification(3);
+        state.handle_timer(6.0);
+        assert!(!state.displayed.contains_key(&3));
+        assert!(!state.pane_states.get(&3).is_some_and(|s| s.is_animating));
+    }
+
+    #[test]
+    fn test_repeat_of_acknowledged_failure_is_dimmed() {
+        let mut state = crate::State::default();
+        let failure = r#"{"type": "error", "message": "test_login failed", "pane_id": 3}"#;
+        let msg = state.event_bridge.parse_message(failure).unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert!(state.pane_states[&3].is_animating);
+        state.clear_pane_notification(3);
+
+        // Same failure again shortly after: badge only, no animation
+        state.handle_timer(10.0);
+        let msg = state.event_bridge.parse_message(failure).unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        let visual_state = &state.pane_states[&3];
+        assert!(visual_state.badge_only && !visual_state.is_animating);
+        assert_eq!(visual_state.priority, Priority::Low);
+        let id = state.displayed[&3].id.clone();
+        assert!(state.audit.explain(&id).unwrap().contains("dimmed"));
+
+        // A different failure, or the same one after the window, alerts in full
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "test_logout failed", "pane_id": 3}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert!(!state.pane_states[&3].badge_only);
+        state.clear_pane_notification(3);
+        state.handle_timer(200.0);
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "error", "message": "test_logout failed", "pane_id": 3}"#)
+            .unwrap();
+        state.handle_bridge_message(msg);
+        state.dispatch();
+        assert!(state.pane_states[&3].is_animating);
+    }
+
+    #[test]
+    fn test_delivery_receipts_track_transitions() {
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        let msg = state.event_bridge
+            .parse_message(r#"{"type": "success", "message": "Deployed", "pane_id": 3, "id": "d-1", "request_ack": true}"#)
+            .unwrap();
+        state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        state.dispatch();
+        assert_eq!(state.receipts.pending(), 1);
+
+        // A newer notification on the pane ends the first subscription
+        let msg = state.event_bridge.parse_line("error|pane=3|id=d-2|request_ack=true|msg=Rolled back").unwrap();
+        state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        state.dispatch();
+        assert_eq!(state.receipts.pending(), 1);
+
+        // Notifications without request_ack are not tracked
+        let msg = state.event_bridge.parse_line("info|pane=4|msg=Idle").unwrap();
+        state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        state.dispatch();
+        assert_eq!(state.receipts.pending(), 1);
+
+        state.clear_pane_notification(3);
+        assert_eq!(state.receipts.pending(), 0);
+        assert!(state.audit.explain("d-2").unwrap().contains("acknowledged"));
+    }
+
+    #[test]
+    fn test_id_requests_acknowledge_dismiss_and_query() {
+        use crate::id_api::{IdRequestKind, IdStatus};
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        for line in ["error|pane=3|id=q-1|msg=Build failed", "attention|pane=4|id=q-2|msg=Approve?"] {
+            let msg = state.event_bridge.parse_line(line).unwrap();
+            state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        }
+        state.dispatch();
+
+        let reply = state.answer_id_request(IdRequestKind::Query, "q-1");
+        assert_eq!((reply.ok, reply.status, reply.pane_id), (true, IdStatus::Displayed, Some(3)));
+
+        // Acknowledging clears the pane like a focus would
+        assert!(state.answer_id_request(IdRequestKind::Ack, "q-1").ok);
+        state.dispatch();
+        assert!(!state.pane_states[&3].has_notification());
+        assert!(state.audit.explain("q-1").unwrap().contains("acknowledged"));
+        assert_eq!(state.answer_id_request(IdRequestKind::Query, "q-1").status, IdStatus::Gone);
+
+        assert!(state.answer_id_request(IdRequestKind::Dismiss, "q-2").ok);
+        state.dispatch();
+        assert!(!state.pane_states[&4].has_notification());
+
+        // Handled or unknown notifications are reported, not acted on
+        let reply = state.answer_id_request(IdRequestKind::Ack, "q-2");
+        assert_eq!((reply.ok, reply.status, reply.error.as_deref()), (false, IdStatus::Gone, Some("already handled")));
+        let reply = state.answer_id_request(IdRequestKind::Dismiss, "nope");
+        assert_eq!((reply.ok, reply.status), (false, IdStatus::Unknown));
+    }
+
+    #[test]
+    fn test_min_display_time_holds_replacements() {
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.config.min_display_ms.insert("success".to_string(), 2000);
+        for line in ["success|pane=3|id=m-1|msg=Built", "success|pane=3|id=m-2|msg=Tested"] {
+            let msg = state.event_bridge.parse_line(line).unwrap();
+            state.handle_pipe_notification(msg, &PipeSource::Keybind);
+            state.dispatch();
+        }
+        assert_eq!(state.displayed[&3].id, "m-1");
+        assert!(state.audit.explain("m-2").unwrap().contains("held (minimum display time)"));
+
+        // The replacement is shown once the first has had its time
+        state.handle_timer(1.0);
+        assert_eq!(state.displayed[&3].id, "m-1");
+        state.handle_timer(1.0);
+        assert_eq!(state.displayed[&3].id, "m-2");
+
+        // A more urgent notification does not wait
+        let msg = state.event_bridge.parse_line("error|pane=3|id=m-3|msg=Deploy failed").unwrap();
+        state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        state.dispatch();
+        assert_eq!(state.displayed[&3].id, "m-3");
+    }
+
+    #[test]
+    fn test_quick_reply_answers_and_acknowledges() {
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.actions.configure(true, 0);
+        for line in ["attention|pane=3|id=q-1|msg=Allow Bash?|reply=yes, no, always", "success|pane=4|id=q-2|msg=Done|reply=ok"] {
+            let msg = state.event_bridge.parse_line(line).unwrap();
+            state.handle_pipe_notification(msg, &PipeSource::Keybind);
+            state.dispatch();
+        }
+        assert_eq!(state.pane_states[&3].reply_options, vec!["yes", "no", "always"]);
+        assert!(state.displayed[&4].reply_options.is_empty());
+
+        // An option the notification does not offer changes nothing
+        state.handle_control_command(ControlCommand::Reply { number: 4, pane_id: Some(3) }, &PipeSource::Keybind);
+        state.dispatch();
+        assert!(state.displayed.contains_key(&3));
+
+        state.handle_control_command(ControlCommand::Reply { number: 3, pane_id: Some(3) }, &PipeSource::Keybind);
+        state.dispatch();
+        assert!(!state.displayed.contains_key(&3));
+        let explained = state.audit.explain("q-1").unwrap();
+        assert!(explained.contains("replied \"always\""));
+        assert!(explained.contains("acknowledged"));
+    }
+
+    #[test]
+    fn test_digits_reply_only_in_the_detail_view() {
+        use zellij_tile::prelude::{BareKey, KeyWithModifier, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.actions.configure(true, 0);
+        state.config.entry_hotkeys = true;
+        state.rebuild_renderer();
+        let msg = state.event_bridge.parse_line("attention|pane=3|id=q-1|msg=Allow Bash?|reply=yes, no").unwrap();
+        state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        state.dispatch();
+        state.render(1, 200);
+
+        // Outside the detail view the digit acknowledges entry 1 and types nothing
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('1'))));
+        state.dispatch();
+        assert!(!state.displayed.contains_key(&3));
+        assert!(!state.audit.explain("q-1").unwrap().contains("replied"));
+
+        // In the detail view it answers the prompt shown there
+        let msg = state.event_bridge.parse_line("attention|pane=3|id=q-2|msg=Allow Edit?|reply=yes, no").unwrap();
+        state.handle_pipe_notification(msg, &PipeSource::Keybind);
+        state.dispatch();
+        state.show_detail = true;
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('1'))));
+        state.dispatch();
+        assert!(state.audit.explain("q-2").unwrap().contains("replied \"yes\""));
+    }
+
+    #[test]
+    fn test_check_result_feeds_the_queue() {
+        use std::collections::BTreeMap;
+
+        let mut state = crate::State::default();
+        state.config.checks.push(crate::checks::Check::parse_spec("dirty",
+            r#""git status --porcelain" every 5m notify-if-output warning "uncommitted changes""#).unwrap());
+        let mut context = BTreeMap::new();
+        context.insert(crate::checks::CHECK_CONTEXT_KEY.to_string(), "dirty".to_string());
+
+        state.handle_run_command_result(Some(0), b"", b"", &context);
+        state.dispatch();
+        assert_eq!(state.history.len(), 0);
+
+        state.handle_run_command_result(Some(0), b" M src/main.rs\n", b"", &context);
+        state.dispatch();
+        let entry = state.history.newest_first().next().unwrap();
+        assert_eq!(entry.notification.message, "uncommitted changes");
+        assert_eq!(entry.notification.source, "check");
+    }
+
+    #[test]
+    fn test_mark_read_keeps_dimmed_badge_until_dismissed() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(2))));
+        state.dispatch();
+        let id = state.displayed[&2].id.clone();
+        assert!(state.pane_states[&2].is_animating);
+
+        state.handle_control_command(ControlCommand::MarkRead(2), &PipeSource::Keybind);
+        state.dispatch();
+        let visual_state = &state.pane_states[&2];
+        assert!(visual_state.is_read() && visual_state.has_notification() && !visual_state.is_unread());
+        assert!(!visual_state.is_animating && visual_state.badge_only);
+        assert!(state.displayed.contains_key(&2));
+        assert!(state.audit.explain(&id).unwrap().contains("marked read"));
+
+        // A new notification on the pane is unread again
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::warning("Lint warnings").for_pane(2))));
+        state.dispatch();
+        assert!(state.pane_states[&2].is_unread());
+
+        state.handle_control_command(ControlCommand::Dismiss(2), &PipeSource::Keybind);
+        state.dispatch();
+        assert!(!state.pane_states[&2].has_notification());
+        assert!(!state.displayed.contains_key(&2));
+    }
+
+    #[test]
+    fn test_session_timeline_measures_time_blocked_on_the_user() {
+        use crate::clock::Clock;
+        use crate::timeline::SessionEventKind;
+
+        let mut state = crate::State::default();
+        let start = state.event_bridge
+            .parse_message(r#"{"hook_event_name": "SessionStart", "session_id": "abc", "pane_id": 2}"#)
+            .unwrap();
+        state.handle_bridge_message(start);
+        state.dispatch();
+        // Lifecycle-only hooks raise no notification
+        assert!(!state.pane_states.get(&2).is_some_and(|s| s.has_notification()));
+
+        state.handle_timer(30.0);
+        let permission = state.event_bridge.parse_message(
+            r#"{"hook_event_name": "Notification", "notification_type": "permission_prompt",
+                "message": "Claude needs your permission to use Bash", "session_id": "abc", "pane_id": 2}"#,
+        ).unwrap();
+        state.handle_bridge_message(permission);
+        state.dispatch();
+
+        // Focusing the pane acknowledges the prompt and ends the wait
+        state.handle_timer(45.0);
+        state.clear_pane_notification(2);
+        let finished = state.event_bridge
+            .parse_line("success|msg=done|session=abc|reason=finished|pane=2")
+            .unwrap();
+        state.handle_bridge_message(finished);
+        state.dispatch();
+
+        let session = state.timeline.get("abc").unwrap();
+        let kinds: Vec<_> = session.events.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, [
+            SessionEventKind::Started,
+            SessionEventKind::WaitingForInput,
+            SessionEventKind::Resumed,
+            SessionEventKind::Finished,
+        ]);
+        assert_eq!(session.blocked_ms, 45_000);
+        assert!(state.timeline.lines("abc", state.clock.now_ms()).unwrap()[0].contains("blocked 45s over 1 wait,"));
+
+        let orphan = r#"{"hook_event_name": "SessionEnd", "pane_id": 2}"#;
+        assert!(state.event_bridge.parse_message(orphan).is_err());
+    }
+
+    #[test]
+    fn test_strict_pipes_ignore_other_instances_broadcasts() {
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.config.pipes = vec!["build-notify".to_string()];
+        state.config.strict_pipes = true;
+        let message = |name: &str, pane: u32, is_private| PipeMessage::new(
+            PipeSource::Cli("1".to_string()),
+            name,
+            &Some(format!("error|pane={}|msg=Build failed", pane)),
+            &None,
+            is_private,
+        );
+
+        // Broadcast for another instance
+        state.pipe(message("claude-notify", 2, false));
+        assert!(!state.pane_states.get(&2).is_some_and(|s| s.has_notification()));
+        assert_eq!(state.ignored_pipe_messages, 1);
+
+        state.pipe(message("build-notify", 3, false));
+        assert!(state.pane_states[&3].has_notification());
+
+        // Addressed to this instance directly
+        state.pipe(message("5f0c8a", 4, true));
+        assert!(state.pane_states[&4].has_notification());
+
+        // Without strict mode the unexpected name is only logged
+        state.config.strict_pipes = false;
+        state.pipe(message("claude-notify", 5, false));
+        assert!(state.pane_states[&5].has_notification());
+        assert!(state.health_report().lines()[2].contains("on build-notify, 1 ignored on other pipes"));
+    }
+
+    #[test]
+    fn test_mirror_follows_broadcasts_only() {
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.config.mirror = true;
+        let message = |payload: &str, is_private| PipeMessage::new(
+            PipeSource::Cli("1".to_string()),
+            "claude-notify",
+            &Some(payload.to_string()),
+            &None,
+            is_private,
+        );
+
+        state.pipe(message("error|pane=2|msg=Build failed", false));
+        assert!(state.pane_states[&2].has_notification());
+
+        // Nothing addressed to it, and no commands
+        state.pipe(message("error|pane=3|msg=Tests failed", true));
+        assert!(!state.pane_states.contains_key(&3));
+        state.pipe(message("clear", false));
+        state.pipe(message("mute_pane 2", false));
+        assert!(state.pane_states[&2].has_notification());
+        assert!(!state.muted_panes.is_muted(2));
+    }
+
+    #[test]
+    fn test_refused_badge_restore_is_retried() {
+        use crate::artifacts::Artifact;
+        use crate::state::PluginState;
+        use crate::world::{PaneRecord, TabRecord};
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.plugin_state = PluginState::Running;
+        state.config.tab_name_badges = true;
+        state.actions.configure(true, 0);
+        state.world.set_panes(vec![PaneRecord { id: 2, ..PaneRecord::default() }]);
+        state.world.set_tabs(vec![TabRecord { position: 0, name: "api".to_string(), ..TabRecord::default() }]);
+        state.pipe(PipeMessage::new(PipeSource::Cli("1".to_string()), "claude-notify",
+            &Some("error|pane=2|msg=Build failed".to_string()), &None, false));
+        let badged = state.artifacts.applied(Artifact::TabName(0)).cloned().unwrap();
+        assert_eq!(badged.original, "api");
+        assert_ne!(badged.value, "api");
+
+        // Acknowledged while the host refuses renames: the badge stays registered
+        state.actions.set_permitted(false);
+        state.clear_pane_notification(2);
+        state.sync_derived_artifacts();
+        assert_eq!(state.artifacts.applied(Artifact::TabName(0)), Some(&badged));
+        assert!(state.artifacts.line().contains("restore pending: tab 1 name"));
+
+        // ... and is restored on the next pass once renames go through again
+        state.actions.set_permitted(true);
+        state.sync_derived_artifacts();
+        assert_eq!(state.artifacts.applied(Artifact::TabName(0)), None);
+        assert_eq!(state.artifacts.line(), "artifacts: none");
+    }
+
+    #[test]
+    fn test_standby_instance_renders_only_until_failover() {
+        use crate::election::{Role, ELECTION_PIPE};
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.own_pane_id = Some(5);
+        state.sync_election_membership();
+        assert!(!state.render_only());
+
+        // An older instance announces itself: this one stands by
+        state.pipe(PipeMessage::new(PipeSource::Plugin(3), ELECTION_PIPE, &Some("hello 3".to_string()), &None, false));
+        assert_eq!(state.election.visible_role(), Some(Role::Standby));
+        assert!(state.render_only());
+        assert!(!state.actions.rename_tab(0, "build"));
+
+        // Notifications are still shown
+        state.pipe(PipeMessage::new(PipeSource::Cli("1".to_string()), "claude-notify",
+            &Some("error|pane=2|msg=Build failed".to_string()), &None, false));
+        assert!(state.pane_states[&2].has_notification());
+
+        // The older instance goes silent: this one takes over
+        state.handle_timer(7.0);
+        assert_eq!(state.election.role(), Role::Primary);
+        assert!(!state.render_only());
+
+        state.config.instance_election = false;
+        state.sync_election_membership();
+        assert_eq!(state.election.line(), "instance: primary (no election)");
+    }
+
+    #[test]
+    fn test_standby_instance_announces_but_leaves_pipes_to_the_primary() {
+        use crate::commands::ControlCommand;
+        use crate::election::ELECTION_PIPE;
+        use zellij_tile::prelude::{PipeMessage, PipeSource, ZellijPlugin};
+
+        let mut state = crate::State::default();
+        state.actions.configure(true, 0);
+        state.own_pane_id = Some(5);
+        state.sync_election_membership();
+        let cli = PipeSource::Cli("7".to_string());
+        state.handle_control_command(ControlCommand::Stats, &cli);
+        assert!(crate::diagnostics::recent_logs(50).iter().any(|entry| entry.message.starts_with("Dry run: pipe_output")));
+
+        // On standby the command output goes nowhere, while the announcements continue
+        state.pipe(PipeMessage::new(PipeSource::Plugin(3), ELECTION_PIPE, &Some("hello 3".to_string()), &None, false));
+        assert!(state.render_only());
+        state.handle_control_command(ControlCommand::Stats, &cli);
+        state.handle_timer(2.0);
+        let line = state.actions.line();
+        assert!(line.contains("announce 0 (2 dry run)"), "{}", line);
+        assert!(line.contains("pipe_output 0 (1 dry run, 1 read-only)"), "{}", line);
+    }
+
+    #[test]
+    fn test_ingest_file_reads_appended_lines() {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join("zellij-notify-test-ingest-state.jsonl");
+        std::fs::write(&path, "{\"type\":\"error\",\"message\":\"Old\",\"pane_id\":2}\n").unwrap();
+        let mut state = crate::State::default();
+        state.config.ingest_file = Some(path.display().to_string());
+
+        // Lines written before the file was first read are not replayed
+        state.handle_timer(0.05);
+        state.dispatch();
+        assert!(!state.pane_states.contains_key(&2));
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"error\",\"message\":\"Deploy failed\",\"pane_id\":3}\n").unwrap();
+        state.handle_timer(0.5);
+        state.dispatch();
+        assert!(!state.pane_states.contains_key(&3));
+        state.handle_timer(0.5);
+        state.dispatch();
+        assert!(state.pane_states[&3].has_notification());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_on_notify_automation_waits_for_confirmation() {
+        use crate::automation::Automation;
+        use crate::bus::DomainEvent;
+        use crate::state::InteractionMode;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        state.config.on_notify.push(Automation::parse_spec("tests",
+            r#"type=error source=tests -> run "cargo test""#).unwrap());
+        let failed = NotificationBuilder::new()
+            .notification_type(NotificationType::Error)
+            .message("3 failed")
+            .source("tests")
+            .pane_id(4)
+            .build();
+        let id = failed.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(failed.clone())));
+        state.dispatch();
+        match &state.interaction_mode {
+            InteractionMode::ConfirmAutomation { trigger, .. } => {
+                assert_eq!(trigger.prompt(), "tests: run \"cargo test\"? y/n");
+            }
+            other => panic!("expected a confirmation prompt, got {:?}", other),
+        }
+        assert!(state.audit.explain(&id).unwrap().contains("automation tests: run \"cargo test\" (asked for confirmation)"));
+
+        // Declined; a repeat within the cooldown does not ask again
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('n'))));
+        assert_eq!(state.interaction_mode, InteractionMode::Normal);
+        let mut repeat = failed;
+        repeat.id = "repeat".to_string();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(repeat)));
+        state.dispatch();
+        assert_eq!(state.interaction_mode, InteractionMode::Normal);
+    }
+
+    #[test]
+    fn test_auto_focus_counts_down_and_cancels_on_key() {
+        use crate::bus::DomainEvent;
+        use crate::clock::Clock;
+        use crate::world::PaneRecord;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        state.config.auto_focus.insert("attention".to_string(), true);
+        state.config.auto_focus.insert("error".to_string(), false);
+        state.world.set_panes(vec![
+            PaneRecord { id: 1, is_focused: true, ..PaneRecord::default() },
+            PaneRecord { id: 2, ..PaneRecord::default() },
+        ]);
+        // Past the typing pause, so Attention is not deferred
+        state.handle_timer(5.0);
+
+        // Errors never steal focus
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Build failed").for_pane(2))));
+        state.dispatch();
+        assert!(state.auto_focus.pending().is_none());
+
+        let waiting = Notification::attention("Claude needs input").for_pane(2);
+        let id = waiting.id.clone();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(waiting)));
+        state.dispatch();
+        assert!(state.audit.explain(&id).unwrap().contains("auto-focus of pane 2 scheduled"));
+        state.handle_timer(1.0);
+        assert_eq!(state.auto_focus.pending().unwrap().countdown(state.clock.now_ms()),
+            "Focusing pane 2 in 2s - any key cancels");
+
+        // A key cancels the countdown and is not handled otherwise
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('q'))));
+        assert!(state.auto_focus.pending().is_none());
+        assert!(!state.show_queue_overlay);
+
+        // Left alone, the countdown runs out
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::attention("Still waiting").for_pane(2))));
+        state.dispatch();
+        state.handle_timer(3.0);
+        assert!(state.auto_focus.pending().is_none());
+    }
+
+    #[test]
+    fn test_tutorial_command_shows_overlay_until_a_key() {
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier, PipeSource};
+
+        let mut state = crate::State::default();
+        assert!(state.handle_control_command(ControlCommand::Tutorial, &PipeSource::Keybind));
+        assert!(state.show_tutorial);
+
+        // The key closes the tutorial and does nothing else
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('q'))));
+        assert!(!state.show_tutorial);
+        assert!(!state.show_queue_overlay);
+    }
+
+    #[test]
+    fn test_startup_banner_closes_on_a_key_or_by_itself() {
+        use crate::banner::STARTUP_BANNER_MS;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        state.startup_banner_until = Some(STARTUP_BANNER_MS);
+        state.handle_timer(1.0);
+        assert!(state.startup_banner_until.is_some());
+        assert!(state.handle_timer((STARTUP_BANNER_MS / 1000) as f64));
+        assert!(state.startup_banner_until.is_none());
+
+        // The key closes the banner and does nothing else
+        state.startup_banner_until = Some(state.clock.now_ms() + STARTUP_BANNER_MS);
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('q'))));
+        assert!(state.startup_banner_until.is_none());
+        assert!(!state.show_queue_overlay);
+    }
+
+    #[test]
+    fn test_slow_frames_show_in_stats_and_health() {
+        use crate::profile::FramePhase;
+        use std::time::{Duration, Instant};
+
+        let mut state = crate::State::default();
+        state.config.frame_time_warn_ms = 5;
+        state.record_frame(FramePhase::Render, Instant::now() - Duration::from_millis(20), false);
+        state.record_frame(FramePhase::Tick, Instant::now(), true);
+
+        let stats = state.frames.lines(state.config.animation.tick_ms, state.config.frame_time_warn_ms);
+        assert!(stats[2].starts_with("render: 1 frames") && stats[2].ends_with("1 slow"));
+        assert!(stats[4].starts_with("idle ticks: 1"));
+        let health = state.health_report().lines().join("\n");
+        assert!(health.contains("! frames: 1 frames over 5ms (see `stats`)"));
+    }
+
+    #[test]
+    fn test_usage_counters_cover_arrivals_and_acknowledgements() {
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Build failed").for_pane(3))));
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::success("Deployed").for_pane(4))));
+        state.dispatch();
+        state.handle_timer(12.0);
+        state.bus.emit(DomainEvent::PaneDismissed(3));
+        state.dispatch();
+
+        assert_eq!(state.metrics.lines()[0], "session: 2 notifications (error 1, success 1), 1 acknowledged, avg ack 12s");
+    }
+
+    #[test]
+    fn test_memory_cap_evictions_show_in_stats() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::history::NotificationHistory;
+        use crate::state::StateManager;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.history = NotificationHistory::new(2);
+        state.state_manager = StateManager::new().with_max_history(1);
+        for message in ["Build failed", "Tests failed", "Lint failed"] {
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error(message).for_pane(3))));
+            state.dispatch();
+        }
+        assert!(state.state_manager.evicted_count() > 0);
+
+        state.handle_control_command(ControlCommand::Stats, &PipeSource::Keybind);
+        let logs = crate::diagnostics::recent_logs(50);
+        let stats = logs.iter().rev().find(|entry| entry.message.contains("memory: ")).unwrap();
+        assert!(stats.message.contains("history 2/2, 1 evicted;"));
+        assert!(stats.message.contains(&format!("transitions {} evicted", state.state_manager.evicted_count())));
+    }
+
+    #[test]
+    fn test_burst_is_shown_at_display_rate_in_priority_order() {
+        use crate::bus::DomainEvent;
+
+        let mut state = crate::State::default();
+        state.notification_queue = NotificationQueue::new(100, 300_000).with_display_rate(2);
+        for notification in [Notification::info("Indexing").for_pane(1), Notification::warning("Slow test").for_pane(2),
+            Notification::error("Build failed").for_pane(3)] {
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+
+        // The error and the warning are shown; the info waits in the queue for the next second
+        assert_eq!(state.displayed.keys().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(state.notification_queue.len(), 1);
+        state.handle_timer(0.5);
+        assert!(!state.displayed.contains_key(&1));
+        state.handle_timer(0.5);
+        assert_eq!(state.displayed[&1].message, "Indexing");
+        assert!(state.notification_queue.is_empty());
+    }
+
+    #[test]
+    fn test_queue_moves_change_what_is_shown_next() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use crate::queue::QueueMove;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.notification_queue = NotificationQueue::new(100, 300_000).with_display_rate(1);
+        let notifications: Vec<Notification> = (1..=4)
+            .map(|pane_id| Notification::info(&format!("step {}", pane_id)).for_pane(pane_id))
+            .collect();
+        let ids: Vec<String> = notifications.iter().map(|n| n.id.clone()).collect();
+        for notification in notifications {
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+        assert!(state.displayed.contains_key(&1));
+        assert_eq!(state.notification_queue.len(), 3);
+
+        // Step 4 is promoted past the others and step 3 moved to the front of the low lane
+        let queue_move = |id: &String, queue_move| ControlCommand::QueueMove { id: id.clone(), queue_move };
+        assert!(state.handle_control_command(queue_move(&ids[3], QueueMove::Promote), &PipeSource::Keybind));
+        assert!(state.handle_control_command(queue_move(&ids[2], QueueMove::Front), &PipeSource::Keybind));
+        assert!(!state.handle_control_command(queue_move(&ids[0], QueueMove::Front), &PipeSource::Keybind));
+
+        state.handle_timer(1.0);
+        assert!(state.displayed.contains_key(&4) && !state.displayed.contains_key(&3));
+        state.handle_timer(1.0);
+        assert!(state.displayed.contains_key(&3) && !state.displayed.contains_key(&2));
+
+        // Step 2 can't go below the low lane and comes up last
+        assert!(!state.handle_control_command(queue_move(&ids[1], QueueMove::Demote), &PipeSource::Keybind));
+        state.handle_timer(1.0);
+        assert!(state.displayed.contains_key(&2));
+    }
+
+    #[test]
+    fn test_fair_strategy_shows_a_quiet_source_during_a_flood() {
+        use crate::bus::DomainEvent;
+        use crate::queue::DequeueStrategy;
+
+        let flood = |pane_id: u32| Notification::info(&format!("flood {}", pane_id)).from_source("flood").for_pane(pane_id);
+        let burst = || (1..=4).map(flood).chain([Notification::info("ci passed").from_source("ci").for_pane(9)]);
+
+        // Taking turns, the ci notification is among the first two shown
+        let mut state = crate::State::default();
+        state.notification_queue = NotificationQueue::new(100, 300_000)
+            .with_strategy(DequeueStrategy::Fair)
+            .with_display_rate(2);
+        for notification in burst() {
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+        assert_eq!(state.displayed.keys().copied().collect::<Vec<_>>(), vec![1, 9]);
+
+        // In arrival order it waits behind the flood
+        let mut state = crate::State::default();
+        state.notification_queue = NotificationQueue::new(100, 300_000).with_display_rate(2);
+        for notification in burst() {
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+        assert_eq!(state.displayed.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_title_mirror_leaves_the_session_name_alone() {
+        use crate::bus::DomainEvent;
+        use crate::config::TitleMirror;
+
+        let mut state = crate::State::default();
+        state.config.title_mirror = TitleMirror::from_str("session");
+        state.mode_info.session_name = Some("(api) work".to_string());
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Build failed").for_pane(2))));
+        state.dispatch();
+
+        // A name that looks like a summary prefix is kept whole in the window title
+        assert!(state.sync_title_mirror());
+        assert_eq!(state.pending_output.last().map(String::as_str), Some("\x1b]2;(1\u{2718}) (api) work\x07"));
+        assert_eq!(state.mode_info.session_name.as_deref(), Some("(api) work"));
+    }
+
+    #[test]
+    fn test_legend_lists_the_toasts_key_only_when_it_works() {
+        use crate::surface::DisplaySurface;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let toasts_key = KeyWithModifier::new(BareKey::Char('o'));
+        let listed = |state: &crate::State| crate::legend::key_bindings(&state.config).iter().any(|(key, _)| key == "o");
+
+        let mut state = crate::State::default();
+        assert!(!listed(&state));
+        assert!(!state.handle_key(&toasts_key));
+        assert!(state.surface_toggles.is_enabled(DisplaySurface::Toasts));
+
+        state.config.experimental_features.push("toasts".to_string());
+        assert!(listed(&state));
+        assert!(state.handle_key(&toasts_key));
+        assert!(!state.surface_toggles.is_enabled(DisplaySurface::Toasts));
+    }
+
+    #[test]
+    fn test_noisy_source_suggestion_mutes_with_one_key() {
+        use crate::bus::DomainEvent;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        state.config.noisy_source_threshold = 5;
+        for i in 0..6 {
+            let notification = Notification::info(&format!("heartbeat {}", i)).from_source("ci").for_pane(2);
+            state.bus.emit(DomainEvent::NotificationArrived(Box::new(notification)));
+        }
+        state.dispatch();
+        assert_eq!(state.noisy_source().unwrap().suggestion(), "Noisy source: ci (6/min) - n mutes it");
+
+        // The key only acts in the diagnostics view
+        assert!(!state.handle_key(&KeyWithModifier::new(BareKey::Char('n'))));
+        state.show_diagnostics = true;
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('n'))));
+        assert!(state.config.rules.iter().any(|rule| rule.name == "mute_ci"));
+        assert!(state.noisy_source().is_none());
+
+        let muted = Notification::error("Build failed").from_source("ci").for_pane(3);
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(muted)));
+        state.dispatch();
+        assert!(!state.pane_states.get(&3).is_some_and(|s| s.has_notification()));
+    }
+
+    #[test]
+    fn test_sender_question_answered_by_key_or_timeout() {
+        use crate::event_bridge::EventBridge;
+        use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+        let mut state = crate::State::default();
+        let mut bridge = EventBridge::new();
+        for line in ["confirm|id=a|msg=Deploy?|timeout=5000", "confirm|id=b|msg=Tag?|default=yes"] {
+            state.handle_bridge_message(bridge.parse_line(line).unwrap());
+        }
+        state.dispatch();
+        assert_eq!(state.confirms.len(), 2);
+
+        // 'y' answers the question shown; unrelated keys are left alone
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('y'))));
+        assert_eq!(state.confirms.current().unwrap().id, "b");
+        assert!(state.handle_key(&KeyWithModifier::new(BareKey::Char('q'))));
+        assert_eq!(state.confirms.len(), 1);
+
+        // The countdown runs out: the default is taken
+        assert!(state.handle_timer(31.0));
+        assert!(state.confirms.current().is_none());
+    }
+
+    #[test]
+    fn test_aggregator_keeps_forwarded_notifications_by_session() {
+        use crate::aggregator::{forward_payload, withdraw_payload};
+        use crate::event_bridge::EventBridge;
+
+        let mut state = crate::State::default();
+        state.config.aggregator = true;
+        let mut bridge = EventBridge::new();
+        let build = Notification::error("Build failed").for_pane(3);
+        state.handle_bridge_message(bridge.parse_message(&forward_payload(&build, "api")).unwrap());
+        state.dispatch();
+
+        // Kept apart from this session's panes
+        assert!(state.pane_states.values().all(|s| !s.has_notification()));
+        assert!(state.aggregate.describe(0).starts_with("api (1) - zellij attach api"));
+
+        state.handle_bridge_message(bridge.parse_message(&withdraw_payload(&build.id, "api")).unwrap());
+        state.dispatch();
+        assert!(state.aggregate.is_empty());
+    }
+
+    #[test]
+    fn test_garbled_notifications_switch_to_ascii_glyphs() {
+        use crate::charset::AsciiMode;
+        use crate::event_bridge::BridgeMessage;
+
+        let mut state = crate::State { render_size: Some((80, 1)), ..Default::default() };
+        for message in ["Caf\u{C3}\u{A9} build failed", "\u{FFFD}\u{FFFD} tests", "Deploy \u{E2}\u{20AC}\u{201D} done"] {
+            state.handle_bridge_message(BridgeMessage::Notify(Box::new(Notification::error(message).for_pane(1))));
+        }
+        let health = state.health_report().lines().join("\n");
+        assert!(health.contains("render: 80x1, ASCII glyphs (garbled text)"));
+
+        // An explicit setting wins over the hints
+        state.config.ascii_only = AsciiMode::Off;
+        assert!(!state.health_report().lines().join("\n").contains("ASCII"));
+    }
+
+    #[test]
+    fn test_runtime_overrides_survive_reload() {
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        let command = ControlCommand::parse("config_set notification_timeout_ms 60000").unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert_eq!(state.config.notification_timeout_ms, 60_000);
+
+        // Other runtime changes are kept as overrides too
+        let command = ControlCommand::parse("theme_set error_color #ff0000").unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert!(state.reload_config().is_ok());
+        assert_eq!(state.config.notification_timeout_ms, 60_000);
+        assert_eq!(state.config.theme.error_color, "#ff0000");
+        assert!(state.config_manager.describe(&state.config, "theme.error_color").ends_with("(runtime)"));
+
+        // Invalid results are rejected and the override dropped
+        let command = ControlCommand::parse("config_set notification_timeout_ms 10").unwrap();
+        assert!(!state.handle_control_command(command, &PipeSource::Keybind));
+        assert_eq!(state.config.notification_timeout_ms, 60_000);
+
+        let command = ControlCommand::parse("config_unset notification_timeout_ms").unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert_eq!(state.config.notification_timeout_ms, 300_000);
+    }
+
+    #[test]
+    fn test_surface_toggle_hides_border_until_switched_back() {
+        use crate::bus::DomainEvent;
+        use crate::commands::ControlCommand;
+        use zellij_tile::prelude::PipeSource;
+
+        let mut state = crate::State::default();
+        state.bus.emit(DomainEvent::NotificationArrived(Box::new(Notification::error("Tests failed").for_pane(2))));
+        state.dispatch();
+        let border = |state: &crate::State| state.renderer
+            .get_border_style(&state.pane_states[&2], &state.color_manager, &state.animation_engine, state.tick_count)
+            .is_some();
+        assert!(border(&state));
+
+        let command = ControlCommand::parse("surface border off").unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert!(!border(&state));
+
+        // The toggle survives a renderer rebuild
+        state.apply_accessibility();
+        assert!(!border(&state));
+
+        let command = ControlCommand::parse("surface border").unwrap();
+        assert!(state.handle_control_command(command, &PipeSource::Keybind));
+        assert!(border(&state));
+    }
+
     // ==================== Component Tests ====================
 
     #[test]
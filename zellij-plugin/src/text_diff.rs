@@ -0,0 +1,110 @@
+//! Text diff module for Zellij Visual Notifications
+//!
+//! Repeated failures often differ only in a count or a line number (`tests failed: 3`, then
+//! `tests failed: 5`), and once history compaction merges them into one entry only the newest
+//! message is left, which hides how the failure developed. [`change`] finds the prefix and suffix
+//! two messages share, widened to whole words so `13` and `15` differ as numbers rather than in
+//! their last digit, and [`Change::compact`] writes the varying part in place: `tests failed: 3→5`.
+//! Messages with nothing stable around a short varying part are left as they are.
+
+/// Longest varying part (characters, either side) written in place
+const MAX_VARYING_CHARS: usize = 24;
+
+/// Where two messages differ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change<'a> {
+    /// Text both start with
+    pub prefix: &'a str,
+    /// Varying part of the earlier message
+    pub before: &'a str,
+    /// Varying part of the later message
+    pub after: &'a str,
+    /// Text both end with
+    pub suffix: &'a str,
+}
+
+impl Change<'_> {
+    /// The later message with its varying part shown as `before→after`, e.g.
+    /// `tests failed: 3→5`; None when the change is an insertion or deletion, too long, or has
+    /// no stable text around it
+    pub fn compact(&self) -> Option<String> {
+        let short = |part: &str| !part.trim().is_empty() && part.chars().count() <= MAX_VARYING_CHARS;
+        if !short(self.before) || !short(self.after) || (self.prefix.trim().is_empty() && self.suffix.trim().is_empty()) {
+            return None;
+        }
+        Some(format!("{}{}\u{2192}{}{}", self.prefix, self.before, self.after, self.suffix))
+    }
+}
+
+/// Where `after` differs from `before` (None if they are equal)
+pub fn change<'a>(before: &'a str, after: &'a str) -> Option<Change<'a>> {
+    if before == after {
+        return None;
+    }
+
+    // Common prefix, backed off to the start of a word it would cut
+    let mut prefix = before.char_indices().zip(after.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((index, _), _)| index)
+        .unwrap_or(before.len().min(after.len()));
+    while let Some(last) = before[..prefix].chars().next_back() {
+        if !is_word(last) || !(starts_with_word(&before[prefix..]) || starts_with_word(&after[prefix..])) {
+            break;
+        }
+        prefix -= last.len_utf8();
+    }
+
+    // Common suffix of the rest, likewise not ending inside a word
+    let (before_rest, after_rest) = (&before[prefix..], &after[prefix..]);
+    let mut suffix: usize = before_rest.chars().rev().zip(after_rest.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    while let Some(first) = before_rest[before_rest.len() - suffix..].chars().next() {
+        let cut = |rest: &str| rest[..rest.len() - suffix].chars().next_back().is_some_and(is_word);
+        if !is_word(first) || !(cut(before_rest) || cut(after_rest)) {
+            break;
+        }
+        suffix -= first.len_utf8();
+    }
+
+    Some(Change {
+        prefix: &before[..prefix],
+        before: &before_rest[..before_rest.len() - suffix],
+        after: &after_rest[..after_rest.len() - suffix],
+        suffix: &before_rest[before_rest.len() - suffix..],
+    })
+}
+
+/// Whether a character belongs to a word (or number)
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+fn starts_with_word(text: &str) -> bool {
+    text.chars().next().is_some_and(is_word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compacts_varying_counts_and_numbers() {
+        let compact = |a, b| change(a, b).and_then(|c| c.compact());
+        assert_eq!(compact("tests failed: 3", "tests failed: 5").as_deref(), Some("tests failed: 3\u{2192}5"));
+        // Whole numbers, not their last digits
+        assert_eq!(compact("error at src/lib.rs:113 (2 warnings)", "error at src/lib.rs:117 (2 warnings)").as_deref(),
+            Some("error at src/lib.rs:113\u{2192}117 (2 warnings)"));
+        let parts = change("12 of 40 suites red", "15 of 40 suites red").unwrap();
+        assert_eq!((parts.prefix, parts.before, parts.after, parts.suffix), ("", "12", "15", " of 40 suites red"));
+        assert_eq!(compact("took 1.5s", "took 12.25s").as_deref(), Some("took 1.5s\u{2192}12.25s"));
+
+        // Equal, unrelated, or only grown messages are left alone
+        assert_eq!(change("Build failed", "Build failed"), None);
+        assert_eq!(compact("Build failed", "Deploy done"), None);
+        assert_eq!(compact("Build failed", "Build failed again"), None);
+        assert_eq!(compact("tests failed: 3", &format!("tests failed: {}", "x".repeat(30))), None);
+        assert_eq!(compact("3 \u{00e9}checs", "5 \u{00e9}checs").as_deref(), Some("3\u{2192}5 \u{00e9}checs"));
+    }
+}
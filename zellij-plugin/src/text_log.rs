@@ -0,0 +1,70 @@
+//! Text log module for Zellij Visual Notifications
+//!
+//! A plain chronological log of the notifications in the plugin pane, for braille displays and
+//! copy-paste: one line per notification with the time, the type as a word and the message,
+//! oldest first with the newest at the bottom, and no color, icons or animation. Long lines are
+//! wrapped rather than cut. It is on by default with `screen_reader`, and switched with `text_log`
+//! in the configuration or `text_log on|off` at runtime. Times are UTC, as the plugin can't see
+//! the host's time zone.
+
+use chrono::DateTime;
+use crate::history::HistoryEntry;
+use crate::wrap::wrap;
+
+/// One log line, e.g. `14:03:22 error ci: Tests failed (pane 3)`; `received_unix_ms` is the Unix
+/// time the notification arrived, when the host clock is known
+pub fn line(entry: &HistoryEntry, received_unix_ms: Option<u64>) -> String {
+    let n = &entry.notification;
+    let time = received_unix_ms
+        .and_then(|unix_ms| DateTime::from_timestamp_millis(unix_ms as i64))
+        .map(|time| time.format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string());
+    let pane = n.pane_id.map(|pane_id| format!(" (pane {})", pane_id)).unwrap_or_default();
+    format!("{} {} {}: {}{}", time, n.notification_type.name(), n.source, entry.display_text(), pane)
+}
+
+/// Lines of the log view: a header, then the entries (oldest first) wrapped to `cols`, keeping the
+/// newest that fit in `rows`
+pub fn lines(entries: &[(&HistoryEntry, Option<u64>)], rows: usize, cols: usize) -> Vec<String> {
+    let mut body: Vec<String> = entries.iter()
+        .flat_map(|(entry, received_unix_ms)| wrap(&line(entry, *received_unix_ms), cols))
+        .collect();
+    if body.is_empty() {
+        body.push("No notifications yet".to_string());
+    }
+    let room = rows.max(2) - 1;
+    let mut lines = vec![wrap("Notification log (UTC, newest last)", cols).remove(0)];
+    lines.extend(body.split_off(body.len().saturating_sub(room)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::Notification;
+
+    fn entry(notification: Notification) -> HistoryEntry {
+        HistoryEntry::new(notification, 0)
+    }
+
+    #[test]
+    fn test_plain_lines_newest_last() {
+        let failed = entry(Notification::error("Tests failed").from_source("ci").for_pane(3));
+        let done = entry(Notification::success("Deployed to staging").from_source("deploy"));
+        // 2026-10-16 14:03:22 UTC
+        assert_eq!(line(&failed, Some(1_792_159_402_000)), "14:03:22 error ci: Tests failed (pane 3)");
+        assert_eq!(line(&done, None), "--:--:-- success deploy: Deployed to staging");
+
+        let entries = [(&failed, Some(1_792_159_402_000)), (&done, Some(1_792_159_405_000))];
+        assert_eq!(lines(&entries, 3, 80), vec![
+            "Notification log (UTC, newest last)".to_string(),
+            "14:03:22 error ci: Tests failed (pane 3)".to_string(),
+            "14:03:25 success deploy: Deployed to staging".to_string(),
+        ]);
+        // Wrapped, and only the newest lines when short of rows
+        let narrow = lines(&entries, 3, 30);
+        assert_eq!(narrow.len(), 3);
+        assert_eq!(narrow[1..], ["14:03:25 success deploy:".to_string(), "Deployed to staging".to_string()]);
+        assert_eq!(lines(&[], 5, 80)[1], "No notifications yet");
+    }
+}
@@ -0,0 +1,323 @@
+//! Session timeline module for Zellij Visual Notifications
+//!
+//! Records the lifecycle of each Claude Code session (by the hook payload's `session_id`):
+//! when it was first seen, when it started waiting for input, when it resumed and when it
+//! finished. A wait ends when its notification is acknowledged or when the session reports any
+//! other activity, so the timeline shows how much wall-clock time the agent spent blocked on the
+//! user. `timeline <session>` renders it; `format=json` exports it.
+
+use std::collections::{BTreeMap, VecDeque};
+use serde::Serialize;
+use crate::attention::AttentionReason;
+use crate::renderer::format_duration_ms;
+
+/// Events kept per session (oldest dropped first)
+pub const MAX_SESSION_EVENTS: usize = 200;
+
+/// Sessions kept (the least recently active dropped first)
+pub const MAX_SESSIONS: usize = 32;
+
+/// Lifecycle-only Claude Code hook, recorded without raising a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionHook {
+    /// `SessionStart`
+    Start,
+    /// `UserPromptSubmit`, `PreToolUse`, `PostToolUse`: the agent is working
+    Activity,
+    /// `SessionEnd`
+    End,
+}
+
+impl SessionHook {
+    /// Map a hook event name
+    pub fn from_event(event: &str) -> Option<Self> {
+        match event {
+            "SessionStart" => Some(Self::Start),
+            "UserPromptSubmit" | "PreToolUse" | "PostToolUse" => Some(Self::Activity),
+            "SessionEnd" => Some(Self::End),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of a recorded lifecycle event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEventKind {
+    /// Session first seen
+    Started,
+    /// Agent blocked on the user (permission prompt or idle prompt)
+    WaitingForInput,
+    /// Agent unblocked
+    Resumed,
+    /// Agent finished its task
+    Finished,
+    /// Session closed
+    Ended,
+}
+
+impl SessionEventKind {
+    /// Human-readable name
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::WaitingForInput => "waiting for input",
+            Self::Resumed => "resumed",
+            Self::Finished => "finished",
+            Self::Ended => "ended",
+        }
+    }
+}
+
+/// One lifecycle event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionEvent {
+    /// Plugin time (ms)
+    pub at_ms: u64,
+    /// What happened
+    pub kind: SessionEventKind,
+    /// Why the agent waited
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<AttentionReason>,
+    /// How long a wait lasted (set when it ends)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waited_ms: Option<u64>,
+}
+
+/// Timeline of one session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Session {
+    /// Claude Code session ID
+    pub session_id: String,
+    /// Pane the session last reported from
+    pub pane_id: Option<u32>,
+    /// Completed waits
+    pub waits: u32,
+    /// Total time of completed waits (ms)
+    pub blocked_ms: u64,
+    /// Start of the wait in progress
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_since_ms: Option<u64>,
+    /// Events, oldest first
+    pub events: VecDeque<SessionEvent>,
+    /// Plugin time of the last event (ms)
+    #[serde(skip)]
+    last_seen_ms: u64,
+}
+
+impl Session {
+    fn new(session_id: &str, now_ms: u64) -> Self {
+        let mut session = Self {
+            session_id: session_id.to_string(),
+            pane_id: None,
+            waits: 0,
+            blocked_ms: 0,
+            waiting_since_ms: None,
+            events: VecDeque::new(),
+            last_seen_ms: now_ms,
+        };
+        session.push(now_ms, SessionEventKind::Started, None);
+        session
+    }
+
+    fn push(&mut self, at_ms: u64, kind: SessionEventKind, reason: Option<AttentionReason>) {
+        if self.events.len() >= MAX_SESSION_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(SessionEvent { at_ms, kind, reason, waited_ms: None });
+        self.last_seen_ms = at_ms;
+    }
+
+    /// End the wait in progress, if any
+    fn resume(&mut self, now_ms: u64) {
+        let Some(since) = self.waiting_since_ms.take() else {
+            return;
+        };
+        let waited_ms = now_ms.saturating_sub(since);
+        if let Some(wait) = self.events.iter_mut().rev().find(|e| e.kind == SessionEventKind::WaitingForInput) {
+            wait.waited_ms = Some(waited_ms);
+        }
+        self.waits += 1;
+        self.blocked_ms += waited_ms;
+        self.push(now_ms, SessionEventKind::Resumed, None);
+    }
+
+    /// Blocked time including the wait in progress (ms)
+    pub fn blocked_ms_at(&self, now_ms: u64) -> u64 {
+        self.blocked_ms + self.waiting_since_ms.map(|since| now_ms.saturating_sub(since)).unwrap_or(0)
+    }
+}
+
+/// Timelines of recent sessions
+#[derive(Debug, Clone, Default)]
+pub struct SessionTimeline {
+    sessions: BTreeMap<String, Session>,
+}
+
+impl SessionTimeline {
+    /// Session by ID, created (with a `started` event) on first sight
+    fn session(&mut self, session_id: &str, pane_id: Option<u32>, now_ms: u64) -> &mut Session {
+        if !self.sessions.contains_key(session_id) && self.sessions.len() >= MAX_SESSIONS {
+            let stale = self.sessions.values().min_by_key(|s| s.last_seen_ms).map(|s| s.session_id.clone());
+            if let Some(stale) = stale {
+                self.sessions.remove(&stale);
+            }
+        }
+        let session = self.sessions.entry(session_id.to_string())
+            .or_insert_with(|| Session::new(session_id, now_ms));
+        if pane_id.is_some() {
+            session.pane_id = pane_id;
+        }
+        session
+    }
+
+    /// Record a notification from a session: a permission or input prompt starts a wait, a
+    /// finished task ends the session's turn, anything else counts as activity
+    pub fn record_notification(
+        &mut self,
+        session_id: &str,
+        pane_id: Option<u32>,
+        reason: Option<AttentionReason>,
+        now_ms: u64,
+    ) {
+        let session = self.session(session_id, pane_id, now_ms);
+        match reason {
+            Some(AttentionReason::Permission | AttentionReason::Input) => {
+                // A second prompt while waiting belongs to the same wait
+                if session.waiting_since_ms.is_none() {
+                    session.waiting_since_ms = Some(now_ms);
+                    session.push(now_ms, SessionEventKind::WaitingForInput, reason);
+                }
+            }
+            Some(AttentionReason::Finished) => {
+                session.resume(now_ms);
+                session.push(now_ms, SessionEventKind::Finished, None);
+            }
+            Some(AttentionReason::Error) | None => session.resume(now_ms),
+        }
+    }
+
+    /// Record a lifecycle-only hook
+    pub fn record_hook(&mut self, session_id: &str, pane_id: Option<u32>, hook: SessionHook, now_ms: u64) {
+        let session = self.session(session_id, pane_id, now_ms);
+        match hook {
+            SessionHook::Start => {}
+            SessionHook::Activity => session.resume(now_ms),
+            SessionHook::End => {
+                session.resume(now_ms);
+                session.push(now_ms, SessionEventKind::Ended, None);
+            }
+        }
+    }
+
+    /// End a session's wait (its notification was acknowledged)
+    pub fn resume(&mut self, session_id: &str, now_ms: u64) {
+        if let Some(session) = self.sessions.get_mut(session_id) {
+            session.resume(now_ms);
+        }
+    }
+
+    /// Session by ID
+    pub fn get(&self, session_id: &str) -> Option<&Session> {
+        self.sessions.get(session_id)
+    }
+
+    /// One summary line per session
+    pub fn summary_lines(&self, now_ms: u64) -> Vec<String> {
+        self.sessions.values()
+            .map(|s| format!("{}: {} events, blocked {}{}",
+                s.session_id,
+                s.events.len(),
+                format_duration_ms(s.blocked_ms_at(now_ms)),
+                if s.waiting_since_ms.is_some() { " (waiting)" } else { "" }))
+            .collect()
+    }
+
+    /// Chronological view of a session, times relative to its first event
+    pub fn lines(&self, session_id: &str, now_ms: u64) -> Option<Vec<String>> {
+        let session = self.get(session_id)?;
+        let start = session.events.front().map(|e| e.at_ms).unwrap_or(now_ms);
+        let pane = session.pane_id.map(|p| format!(" (pane {})", p)).unwrap_or_default();
+        let waits = session.waits + u32::from(session.waiting_since_ms.is_some());
+        let mut lines = vec![format!("Session {}{}: blocked {} over {} wait{}, {} elapsed",
+            session.session_id,
+            pane,
+            format_duration_ms(session.blocked_ms_at(now_ms)),
+            waits,
+            if waits == 1 { "" } else { "s" },
+            format_duration_ms(now_ms.saturating_sub(start)))];
+        for event in &session.events {
+            let mut line = format!("  +{} {}", format_duration_ms(event.at_ms.saturating_sub(start)), event.kind.describe());
+            if let Some(reason) = event.reason {
+                line.push_str(&format!(" ({})", reason.name()));
+            }
+            match event.waited_ms {
+                Some(waited_ms) => line.push_str(&format!(", waited {}", format_duration_ms(waited_ms))),
+                None if event.kind == SessionEventKind::WaitingForInput && session.waiting_since_ms == Some(event.at_ms) => {
+                    line.push_str(&format!(", still waiting after {}", format_duration_ms(now_ms.saturating_sub(event.at_ms))));
+                }
+                None => {}
+            }
+            lines.push(line);
+        }
+        Some(lines)
+    }
+
+    /// JSON export of a session
+    pub fn to_json(&self, session_id: &str) -> Option<String> {
+        self.get(session_id).and_then(|s| serde_json::to_string(s).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waits_are_measured_until_resumed() {
+        let mut timeline = SessionTimeline::default();
+        timeline.record_hook("s1", Some(2), SessionHook::Start, 0);
+        timeline.record_notification("s1", Some(2), Some(AttentionReason::Permission), 60_000);
+        // A repeated prompt does not start a second wait
+        timeline.record_notification("s1", Some(2), Some(AttentionReason::Input), 90_000);
+        timeline.resume("s1", 150_000);
+        timeline.record_notification("s1", Some(2), Some(AttentionReason::Input), 200_000);
+        timeline.record_hook("s1", Some(2), SessionHook::Activity, 230_000);
+        timeline.record_notification("s1", Some(2), Some(AttentionReason::Finished), 300_000);
+
+        let session = timeline.get("s1").unwrap();
+        let kinds: Vec<_> = session.events.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, [
+            SessionEventKind::Started,
+            SessionEventKind::WaitingForInput,
+            SessionEventKind::Resumed,
+            SessionEventKind::WaitingForInput,
+            SessionEventKind::Resumed,
+            SessionEventKind::Finished,
+        ]);
+        assert_eq!(session.waits, 2);
+        assert_eq!(session.blocked_ms, 120_000);
+        assert_eq!(session.events[1].waited_ms, Some(90_000));
+
+        let lines = timeline.lines("s1", 300_000).unwrap();
+        assert_eq!(lines[0], "Session s1 (pane 2): blocked 2m00s over 2 waits, 5m00s elapsed");
+        assert_eq!(lines[2], "  +1m00s waiting for input (permission), waited 1m30s");
+        assert_eq!(lines[6], "  +5m00s finished");
+        assert!(timeline.lines("missing", 0).is_none());
+    }
+
+    #[test]
+    fn test_open_wait_and_json_export() {
+        let mut timeline = SessionTimeline::default();
+        timeline.record_notification("s2", None, Some(AttentionReason::Permission), 10_000);
+        let lines = timeline.lines("s2", 40_000).unwrap();
+        assert!(lines[2].ends_with("still waiting after 30s"));
+        assert_eq!(timeline.summary_lines(40_000), ["s2: 2 events, blocked 30s (waiting)"]);
+
+        let json: serde_json::Value = serde_json::from_str(&timeline.to_json("s2").unwrap()).unwrap();
+        assert_eq!(json["session_id"], "s2");
+        assert_eq!(json["waiting_since_ms"], 10_000);
+        assert_eq!(json["events"][1]["kind"], "waiting_for_input");
+        assert_eq!(json["events"][1]["reason"], "permission");
+    }
+}
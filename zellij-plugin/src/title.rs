@@ -0,0 +1,100 @@
+//! Title mirroring module for Zellij Visual Notifications
+//!
+//! Reflects the aggregate notification state into the terminal window title (OSC escape), so
+//! notifications remain visible when the window is elsewhere. The session name is never touched:
+//! other sessions address this one by it.
+
+use std::collections::BTreeMap;
+use crate::notification::NotificationType;
+use crate::state::VisualState;
+
+/// Notification types in summary display order (most urgent first)
+const SUMMARY_ORDER: [NotificationType; 6] = [
+    NotificationType::Error,
+    NotificationType::Attention,
+    NotificationType::Warning,
+    NotificationType::Progress,
+    NotificationType::Success,
+    NotificationType::Info,
+];
+
+/// Build a compact, escape-free summary of active notifications (e.g. "2✘ 1⚠")
+pub fn compact_summary(pane_states: &BTreeMap<u32, VisualState>) -> String {
+    let mut parts = Vec::new();
+
+    for notification_type in SUMMARY_ORDER.iter() {
+        let count = pane_states
+            .values()
+            .filter(|s| s.is_shown() && s.notification_type.as_ref() == Some(notification_type))
+            .count();
+        if count > 0 {
+            parts.push(format!("{}{}", count, notification_type.icon().unwrap_or_default()));
+        }
+    }
+
+    parts.join(" ")
+}
+
+/// Prefix a base title with the summary (`(2✘) base`), or return the base when empty
+pub fn format_title(summary: &str, base: &str) -> String {
+    if summary.is_empty() {
+        base.to_string()
+    } else {
+        format!("({}) {}", summary, base)
+    }
+}
+
+/// Title for the plugin's own pane (`notify: 1✘ 2⚠`, or `notify` when there is nothing to show)
+pub fn pane_title(summary: &str) -> String {
+    if summary.is_empty() {
+        "notify".to_string()
+    } else {
+        format!("notify: {}", summary)
+    }
+}
+
+/// OSC escape sequence that sets the terminal window title
+pub fn window_title_escape(title: &str) -> String {
+    let clean: String = title.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]2;{}\x07", clean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with(notification_type: NotificationType) -> VisualState {
+        let mut state = VisualState::new();
+        state.notification_type = Some(notification_type);
+        state
+    }
+
+    #[test]
+    fn test_compact_summary_orders_by_urgency() {
+        let mut pane_states = BTreeMap::new();
+        pane_states.insert(1, state_with(NotificationType::Warning));
+        pane_states.insert(2, state_with(NotificationType::Error));
+        pane_states.insert(3, state_with(NotificationType::Error));
+
+        assert_eq!(compact_summary(&pane_states), "2\u{2718} 1\u{26A0}");
+        assert_eq!(compact_summary(&BTreeMap::new()), "");
+    }
+
+    #[test]
+    fn test_format_title() {
+        assert_eq!(format_title("2\u{2718}", "work"), "(2\u{2718}) work");
+        assert_eq!(format_title("1\u{26A0}", "(old) work"), "(1\u{26A0}) (old) work");
+        assert_eq!(format_title("", "work"), "work");
+    }
+
+    #[test]
+    fn test_pane_title() {
+        assert_eq!(pane_title("1\u{2718} 2\u{26A0}"), "notify: 1\u{2718} 2\u{26A0}");
+        assert_eq!(pane_title(""), "notify");
+    }
+
+    #[test]
+    fn test_window_title_escape_strips_controls() {
+        assert_eq!(window_title_escape("a\x07b"), "\x1b]2;ab\x07");
+    }
+}
@@ -0,0 +1,269 @@
+//! Toast module for Zellij Visual Notifications
+//!
+//! Opt-in toast presentation: each new notification opens a small floating pane in a corner of
+//! the screen for a few seconds and closes it again, for users who don't keep a docked status
+//! pane. Toasts stack away from the configured corner and are capped in number.
+
+use crate::config::{Config, ToastPosition};
+use crate::markup;
+use crate::notification::Notification;
+use crate::wrap::wrap;
+
+/// Context key carrying the toast ID through the host command pane
+pub const TOAST_CONTEXT_KEY: &str = "toast_id";
+
+/// Toast layout and lifetime settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastSettings {
+    /// Corner toasts are anchored to
+    pub position: ToastPosition,
+    /// Toast width (columns)
+    pub width: usize,
+    /// Toast height (rows)
+    pub height: usize,
+    /// How long a toast stays open (ms)
+    pub duration_ms: u64,
+    /// Maximum simultaneous toasts (the oldest is closed to make room)
+    pub max_visible: usize,
+}
+
+impl ToastSettings {
+    /// Take toast settings from the plugin configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            position: config.toast_position,
+            width: config.toast_width,
+            height: config.toast_height,
+            duration_ms: config.toast_duration_ms,
+            max_visible: config.toast_max_visible,
+        }
+    }
+}
+
+impl Default for ToastSettings {
+    fn default() -> Self {
+        Self {
+            position: ToastPosition::default(),
+            width: 40,
+            height: 4,
+            duration_ms: 5000,
+            max_visible: 3,
+        }
+    }
+}
+
+/// Placement of a toast on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToastGeometry {
+    /// Column of the top left corner
+    pub x: usize,
+    /// Row of the top left corner
+    pub y: usize,
+    /// Width (columns)
+    pub width: usize,
+    /// Height (rows)
+    pub height: usize,
+}
+
+/// A toast that is open or being opened
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// Toast ID (sent as command pane context)
+    pub id: u64,
+    /// Stacking slot (0 = nearest the corner)
+    pub slot: usize,
+    /// Floating pane ID, once the host reports it opened
+    pub pane_id: Option<u32>,
+    /// Time at which the toast closes (ms)
+    pub expires_at_ms: u64,
+}
+
+/// Requested host action for a toast
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastAction {
+    /// Open a floating pane showing the text
+    Open {
+        /// Toast ID
+        id: u64,
+        /// Text to display
+        text: String,
+        /// Placement
+        geometry: ToastGeometry,
+    },
+    /// Close a toast's floating pane
+    Close(u32),
+}
+
+/// Tracks open toasts, their slots and deadlines
+#[derive(Debug, Clone, Default)]
+pub struct ToastManager {
+    /// Layout and lifetime settings
+    settings: ToastSettings,
+    /// Open toasts, oldest first
+    toasts: Vec<Toast>,
+    /// Next toast ID
+    next_id: u64,
+}
+
+impl ToastManager {
+    /// Create a toast manager with settings
+    pub fn new(settings: ToastSettings) -> Self {
+        Self {
+            settings,
+            toasts: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Show a toast for a notification, closing the oldest if at capacity
+    pub fn show(&mut self, notification: &Notification, now_ms: u64, screen: (usize, usize)) -> Vec<ToastAction> {
+        let mut actions = Vec::new();
+        while self.toasts.len() >= self.settings.max_visible.max(1) {
+            let oldest = self.toasts.remove(0);
+            actions.extend(oldest.pane_id.map(ToastAction::Close));
+        }
+
+        let slot = (0..).find(|slot| !self.toasts.iter().any(|t| t.slot == *slot)).unwrap_or(0);
+        self.next_id += 1;
+        self.toasts.push(Toast {
+            id: self.next_id,
+            slot,
+            pane_id: None,
+            expires_at_ms: now_ms + self.settings.duration_ms,
+        });
+
+        let geometry = self.geometry(slot, screen);
+        actions.push(ToastAction::Open {
+            id: self.next_id,
+            text: toast_text(notification, geometry),
+            geometry,
+        });
+        actions
+    }
+
+    /// Record the floating pane opened for a toast (closing it if the toast already ended)
+    pub fn opened(&mut self, id: u64, pane_id: u32) -> Option<ToastAction> {
+        match self.toasts.iter_mut().find(|t| t.id == id) {
+            Some(toast) => {
+                toast.pane_id = Some(pane_id);
+                None
+            }
+            None => Some(ToastAction::Close(pane_id)),
+        }
+    }
+
+    /// Close toasts whose time is up
+    pub fn expire(&mut self, now_ms: u64) -> Vec<ToastAction> {
+        let mut actions = Vec::new();
+        self.toasts.retain(|toast| {
+            if toast.expires_at_ms > now_ms {
+                return true;
+            }
+            // Toasts whose pane has not opened yet are closed when it does
+            actions.extend(toast.pane_id.map(ToastAction::Close));
+            false
+        });
+        actions
+    }
+
+    /// Replace the settings (applies to toasts opened afterwards)
+    pub fn set_settings(&mut self, settings: ToastSettings) {
+        self.settings = settings;
+    }
+
+    /// How long toast panes should keep their command alive (s), past the close deadline
+    pub fn pane_lifetime_secs(&self) -> u64 {
+        self.settings.duration_ms / 1000 + 2
+    }
+
+    /// Number of open toasts
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// Check if no toasts are open
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Placement of a stacking slot on a screen of (columns, rows)
+    pub fn geometry(&self, slot: usize, screen: (usize, usize)) -> ToastGeometry {
+        let (cols, rows) = screen;
+        let width = self.settings.width.min(cols.max(1));
+        let height = self.settings.height.min(rows.max(1));
+        let offset = slot * height;
+
+        let x = if self.settings.position.is_right() { cols.saturating_sub(width) } else { 0 };
+        let y = if self.settings.position.is_bottom() {
+            rows.saturating_sub(height).saturating_sub(offset)
+        } else {
+            offset.min(rows.saturating_sub(height))
+        };
+
+        ToastGeometry { x, y, width, height }
+    }
+}
+
+/// Text shown in a toast, wrapped to the pane's inner area (inside the frame)
+fn toast_text(notification: &Notification, geometry: ToastGeometry) -> String {
+    let text = format!("{} {}", notification.icon().unwrap_or_default(), markup::render(&notification.display_text()).join("\n"));
+    let lines = wrap(&text, geometry.width.saturating_sub(2));
+    lines.into_iter().take(geometry.height.saturating_sub(2).max(1)).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_geometry_stacks_from_corner() {
+        let manager = ToastManager::new(ToastSettings::default());
+        assert_eq!(manager.geometry(0, (120, 40)), ToastGeometry { x: 80, y: 0, width: 40, height: 4 });
+        assert_eq!(manager.geometry(1, (120, 40)).y, 4);
+
+        let manager = ToastManager::new(ToastSettings {
+            position: ToastPosition::BottomLeft,
+            ..ToastSettings::default()
+        });
+        assert_eq!(manager.geometry(0, (120, 40)), ToastGeometry { x: 0, y: 36, width: 40, height: 4 });
+        assert_eq!(manager.geometry(1, (120, 40)).y, 32);
+    }
+
+    #[test]
+    fn test_toast_cap_and_expiry() {
+        let mut manager = ToastManager::new(ToastSettings { max_visible: 2, ..ToastSettings::default() });
+        let notification = Notification::success("Build done");
+
+        manager.show(&notification, 0, (120, 40));
+        assert_eq!(manager.opened(1, 10), None);
+        manager.show(&notification, 100, (120, 40));
+        manager.opened(2, 11);
+
+        // Third toast closes the oldest and reuses its slot
+        let actions = manager.show(&notification, 200, (120, 40));
+        assert_eq!(actions[0], ToastAction::Close(10));
+        assert!(matches!(actions[1], ToastAction::Open { id: 3, geometry: ToastGeometry { y: 0, .. }, .. }));
+        assert_eq!(manager.len(), 2);
+
+        assert_eq!(manager.expire(5100), vec![ToastAction::Close(11)]);
+        // Toast 3 expires before its pane opens, so the pane is closed on arrival
+        assert!(manager.expire(5200).is_empty());
+        assert!(manager.is_empty());
+        assert_eq!(manager.opened(3, 12), Some(ToastAction::Close(12)));
+    }
+
+    #[test]
+    fn test_toast_text_wraps_to_inner_area() {
+        let mut manager = ToastManager::new(ToastSettings { width: 14, height: 4, ..ToastSettings::default() });
+        let notification = Notification::success("Build finished with no warnings at all");
+
+        match &manager.show(&notification, 0, (120, 40))[0] {
+            ToastAction::Open { text, .. } => {
+                let lines: Vec<&str> = text.lines().collect();
+                assert_eq!(lines.len(), 2);
+                assert!(lines.iter().all(|line| crate::wrap::display_width(line) <= 12));
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+    }
+}
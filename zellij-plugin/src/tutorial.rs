@@ -0,0 +1,95 @@
+//! Tutorial module for Zellij Visual Notifications
+//!
+//! On the first load (nothing persisted in the plugin data directory yet) the plugin pane shows
+//! a short tutorial: what the icons mean, the default keys, and a `zellij pipe` command with the
+//! actual session name to send a test notification. Any key closes it for good; the `tutorial`
+//! command shows it again.
+
+use std::path::Path;
+use crate::chord;
+use crate::notification::NotificationType;
+
+/// Marker file written once the tutorial was dismissed (plugin data directory)
+pub const TUTORIAL_STATE_PATH: &str = "/data/tutorial_seen";
+
+/// Notification types, in tutorial order
+const TUTORIAL_TYPES: [NotificationType; 6] = [
+    NotificationType::Success,
+    NotificationType::Error,
+    NotificationType::Warning,
+    NotificationType::Info,
+    NotificationType::Progress,
+    NotificationType::Attention,
+];
+
+/// Whether this is the first load: none of the given state files exists
+pub fn is_first_run(paths: &[&str]) -> bool {
+    !paths.iter().any(|path| Path::new(path).exists())
+}
+
+/// Remember that the tutorial was seen
+pub fn mark_seen(path: &str) -> std::io::Result<()> {
+    std::fs::write(path, "seen\n")
+}
+
+/// Command sending a test notification to this plugin in the given session
+pub fn test_command(session_name: Option<&str>) -> String {
+    let session = session_name.map(|name| format!(" --session {}", name)).unwrap_or_default();
+    format!("zellij{} pipe -p visual-notifications -- 'success|msg=Hello from the tutorial'", session)
+}
+
+/// Tutorial text
+pub fn lines(session_name: Option<&str>, chord_leader: &str) -> Vec<String> {
+    let icons: Vec<String> = TUTORIAL_TYPES.iter()
+        .map(|notification_type| format!("{} {}", notification_type.icon().unwrap_or_default(), notification_type.name()))
+        .collect();
+    let mut lines = vec![
+        "Welcome to visual notifications - any key closes this tutorial".to_string(),
+        String::new(),
+        format!("Icons: {}", icons.join("  ")),
+        "Keys in this pane: q queue  l history  d diagnostics  h health  c channels  g groups".to_string(),
+        "  v mark read  x dismiss  p pin  e extend  m mute  a + type key ack a type  Ctrl+n clear all".to_string(),
+    ];
+    if !chord::is_off(chord_leader) {
+        lines.push(format!("  {} then a/r/p/e/m/c acts on the focused pane's notification", chord_leader.trim()));
+    }
+    lines.extend([
+        String::new(),
+        "Send a test notification:".to_string(),
+        format!("  {}", test_command(session_name)),
+        String::new(),
+        "Pipe `tutorial` to the plugin to see this again.".to_string(),
+    ]);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_run_detection() {
+        let dir = std::env::temp_dir().join(format!("visual-notifications-tutorial-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = dir.join("tutorial_seen");
+        let marker = marker.to_str().unwrap();
+        let _ = std::fs::remove_file(marker);
+
+        assert!(is_first_run(&[marker, "/nonexistent/metrics"]));
+        mark_seen(marker).unwrap();
+        assert!(!is_first_run(&[marker, "/nonexistent/metrics"]));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_lines_fill_in_session_and_leader() {
+        let lines = lines(Some("work"), "Ctrl y");
+        assert!(lines.iter().any(|line| line.contains("zellij --session work pipe -p visual-notifications")));
+        assert!(lines.iter().any(|line| line.starts_with("  Ctrl y then")));
+        assert!(lines[2].starts_with("Icons: \u{2714} success  \u{2718} error"));
+
+        let lines = super::lines(None, "off");
+        assert!(lines.iter().any(|line| line == "  zellij pipe -p visual-notifications -- 'success|msg=Hello from the tutorial'"));
+        assert!(!lines.iter().any(|line| line.contains(" then a/r/p")));
+    }
+}
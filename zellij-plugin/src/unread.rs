@@ -0,0 +1,87 @@
+//! Unread count file module for Zellij Visual Notifications
+//!
+//! Mirrors the number of unread notifications into a plain file on the host (by default
+//! `/tmp/zellij-notify-<session>.count`) so shell prompts, status bars and nested multiplexers
+//! can show it with a simple `cat`, without speaking the pipe protocol.
+
+/// Default path template (`{session}` is replaced by the sanitized session name)
+pub const DEFAULT_UNREAD_COUNT_PATH: &str = "/tmp/zellij-notify-{session}.count";
+
+/// Tracks the unread count written to the host file, rewriting it only on change
+#[derive(Debug, Clone, Default)]
+pub struct UnreadCountFile {
+    /// Last count written (None = not written yet)
+    last_written: Option<usize>,
+    /// Path of the last write (a session rename moves the file)
+    last_path: Option<String>,
+}
+
+impl UnreadCountFile {
+    /// Create a tracker that has written nothing yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shell command that writes the count if it or the path changed
+    ///
+    /// The file is replaced atomically (write to a temporary file, then rename) so readers never
+    /// see a partial write.
+    pub fn update(&mut self, template: &str, session: &str, count: usize) -> Option<Vec<String>> {
+        let path = count_path(template, session);
+        if self.last_written == Some(count) && self.last_path.as_deref() == Some(path.as_str()) {
+            return None;
+        }
+
+        self.last_written = Some(count);
+        self.last_path = Some(path.clone());
+        Some(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "printf '%s\\n' \"$1\" > \"$2.tmp\" && mv \"$2.tmp\" \"$2\"".to_string(),
+            "sh".to_string(),
+            count.to_string(),
+            path,
+        ])
+    }
+
+    /// Forget the last write (forces a rewrite, e.g. after a config reload)
+    pub fn reset(&mut self) {
+        self.last_written = None;
+        self.last_path = None;
+    }
+}
+
+/// Expand a path template for a session, keeping only filename-safe characters of the name
+pub fn count_path(template: &str, session: &str) -> String {
+    let session: String = session
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    template.replace("{session}", &session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_path_sanitizes_session() {
+        assert_eq!(count_path(DEFAULT_UNREAD_COUNT_PATH, "dev box/1"), "/tmp/zellij-notify-dev_box_1.count");
+        assert_eq!(count_path("/run/user/count", "main"), "/run/user/count");
+    }
+
+    #[test]
+    fn test_update_writes_only_on_change() {
+        let mut file = UnreadCountFile::new();
+        let command = file.update(DEFAULT_UNREAD_COUNT_PATH, "main", 2).unwrap();
+        assert_eq!(command[4], "2");
+        assert_eq!(command[5], "/tmp/zellij-notify-main.count");
+
+        assert!(file.update(DEFAULT_UNREAD_COUNT_PATH, "main", 2).is_none());
+        assert!(file.update(DEFAULT_UNREAD_COUNT_PATH, "main", 0).is_some());
+        assert!(file.update(DEFAULT_UNREAD_COUNT_PATH, "renamed", 0).is_some());
+
+        file.reset();
+        assert!(file.update(DEFAULT_UNREAD_COUNT_PATH, "renamed", 0).is_some());
+    }
+}
@@ -0,0 +1,206 @@
+//! Update check module for Zellij Visual Notifications
+//!
+//! With `update_check` on, the plugin asks at most once a day whether a newer release is out. It
+//! runs `curl` against the GitHub releases API, or `update_check_helper` on hosts where the plugin
+//! can't reach GitHub, with the RunCommands permission. When the latest release's tag is newer than
+//! the running version, the diagnostics view shows a dimmed `Update available` line with the
+//! release's headline. Nothing is ever downloaded or installed. The time of the last check and the
+//! release it found are kept in the plugin data directory, so reloads and new sessions don't ask
+//! again within the day. A failed check also waits for the next day.
+
+use serde::{Deserialize, Serialize};
+use crate::lifecycle::{read_state, LifecycleContext, Subsystem};
+
+/// Run command context key marking update check runs
+pub const UPDATE_CONTEXT_KEY: &str = "update_check";
+
+/// Default location of the persisted update check state (plugin data directory)
+pub const UPDATE_STATE_PATH: &str = "/data/update_check.json";
+
+/// Time between update checks (ms, once a day)
+pub const UPDATE_CHECK_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Latest release of the plugin
+const RELEASES_URL: &str = "https://api.github.com/repos/delorenj/claude-notifications/releases/latest";
+
+/// Longest release headline shown (characters)
+const MAX_HEADLINE_CHARS: usize = 60;
+
+/// A published release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Release {
+    /// Version, from the release tag without its `v`
+    pub version: String,
+    /// First line of the changelog, or the release name
+    pub headline: String,
+}
+
+impl Release {
+    /// Release from the GitHub API's JSON (None if it has no tag)
+    pub fn parse(stdout: &[u8]) -> Option<Self> {
+        let json: serde_json::Value = serde_json::from_slice(stdout).ok()?;
+        let version = json.get("tag_name")?.as_str()?.trim().trim_start_matches('v').to_string();
+        if version.is_empty() {
+            return None;
+        }
+        let text = |key: &str| json.get(key).and_then(|value| value.as_str()).unwrap_or_default().to_string();
+        let body = text("body");
+        let headline = body.lines()
+            .map(|line| line.trim().trim_start_matches(['#', '-', '*']).trim())
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| text("name").trim().to_string());
+        Some(Self { version, headline: headline.chars().filter(|c| !c.is_control()).take(MAX_HEADLINE_CHARS).collect() })
+    }
+}
+
+/// Whether `latest` is a newer version than `running` (numeric `major.minor.patch`; pre-releases
+/// and unreadable versions never are)
+pub fn is_newer(latest: &str, running: &str) -> bool {
+    match (parse_version(latest), parse_version(running)) {
+        (Some(latest), Some(running)) => latest > running,
+        _ => false,
+    }
+}
+
+fn parse_version(version: &str) -> Option<[u64; 3]> {
+    let mut parts = [0; 3];
+    let mut numbers = version.trim().trim_start_matches('v').split('.');
+    for part in parts.iter_mut() {
+        match numbers.next() {
+            Some(number) => *part = number.parse().ok()?,
+            None => break,
+        }
+    }
+    numbers.next().is_none().then_some(parts)
+}
+
+/// Command printing the latest release JSON: the helper if configured, `curl` otherwise
+pub fn command(helper: Option<&str>) -> Vec<String> {
+    match helper {
+        Some(helper) => helper.split_whitespace().map(str::to_string).collect(),
+        None => ["curl", "-fsSL", "--max-time", "10", RELEASES_URL].iter().map(|word| word.to_string()).collect(),
+    }
+}
+
+/// Last update check and what it found
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateChecker {
+    /// When the last check started (Unix ms)
+    pub checked_at_ms: Option<u64>,
+    /// Latest release found by the last successful check
+    pub latest: Option<Release>,
+    /// Check running on the host
+    #[serde(skip)]
+    in_flight: bool,
+}
+
+impl UpdateChecker {
+    /// Parse the check state from its persisted form (JSON; defaults for anything unreadable)
+    pub fn parse(content: &str) -> Self {
+        serde_json::from_str(content).unwrap_or_default()
+    }
+
+    /// Serialize the check state to its persisted form
+    pub fn serialize(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Save the check state to a file
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.serialize())
+    }
+
+    /// Whether a check is due (none running, none in the last day)
+    pub fn due(&self, unix_ms: u64) -> bool {
+        !self.in_flight && self.checked_at_ms.is_none_or(|at| unix_ms.saturating_sub(at) >= UPDATE_CHECK_INTERVAL_MS)
+    }
+
+    /// Note a check as started; the next is due a day later whatever its result
+    pub fn start(&mut self, unix_ms: u64) {
+        self.in_flight = true;
+        self.checked_at_ms = Some(unix_ms);
+    }
+
+    /// Take a check's result, returning whether a release newer than the running version is out
+    pub fn finish(&mut self, release: Option<Release>) -> bool {
+        self.in_flight = false;
+        if release.is_some() {
+            self.latest = release;
+        }
+        self.available().is_some()
+    }
+
+    /// Latest release found, if newer than the running version
+    pub fn available(&self) -> Option<&Release> {
+        self.latest.as_ref().filter(|release| is_newer(&release.version, env!("CARGO_PKG_VERSION")))
+    }
+
+    /// Diagnostics line, e.g. `Update available: 1.2.0 (running 1.0.0) - Focus mode`
+    pub fn line(&self) -> Option<String> {
+        self.available().map(|release| {
+            let mut line = format!("Update available: {} (running {})", release.version, env!("CARGO_PKG_VERSION"));
+            if !release.headline.is_empty() {
+                line.push_str(" - ");
+                line.push_str(&release.headline);
+            }
+            line
+        })
+    }
+}
+
+impl Subsystem for UpdateChecker {
+    fn name(&self) -> &'static str {
+        "update_check"
+    }
+
+    fn init(&mut self, _context: &LifecycleContext) -> Result<(), String> {
+        *self = read_state(UPDATE_STATE_PATH)?.map(|content| Self::parse(&content)).unwrap_or_default();
+        Ok(())
+    }
+
+    fn shutdown(&mut self, context: &LifecycleContext) -> Result<(), String> {
+        if !context.persist {
+            return Ok(());
+        }
+        self.save(UPDATE_STATE_PATH).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks_daily_and_reports_newer_releases() {
+        let release = Release::parse(br#"{"tag_name": "v99.1.0", "name": "Big one",
+            "body": "\n## Focus mode batches notifications\n- more"}"#).unwrap();
+        assert_eq!(release, Release { version: "99.1.0".to_string(), headline: "Focus mode batches notifications".to_string() });
+        assert_eq!(Release::parse(br#"{"tag_name": "v2.0.0", "name": "Two", "body": null}"#).unwrap().headline, "Two");
+        assert_eq!(Release::parse(b"curl: (6) Could not resolve host"), None);
+
+        assert!(is_newer("1.10.0", "1.9.3"));
+        assert!(is_newer("2", "1.9.3"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("1.1.0-rc1", "1.0.0"));
+
+        let mut checker = UpdateChecker::default();
+        assert!(checker.due(5));
+        checker.start(5);
+        assert!(!checker.due(10));
+        assert!(!checker.finish(None));
+        assert!(!checker.due(5 + UPDATE_CHECK_INTERVAL_MS - 1));
+        assert!(checker.due(5 + UPDATE_CHECK_INTERVAL_MS));
+        assert!(checker.finish(Some(release)));
+        assert_eq!(checker.line().as_deref(),
+            Some(format!("Update available: 99.1.0 (running {}) - Focus mode batches notifications", env!("CARGO_PKG_VERSION")).as_str()));
+
+        // The state survives a reload; an old release is not advertised
+        let restored = UpdateChecker::parse(&checker.serialize());
+        assert_eq!(restored.checked_at_ms, Some(5));
+        assert!(restored.line().is_some());
+        checker.finish(Some(Release { version: "0.9.0".to_string(), headline: String::new() }));
+        assert_eq!(checker.line(), None);
+    }
+}
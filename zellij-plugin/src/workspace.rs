@@ -0,0 +1,59 @@
+//! Workspace module for Zellij Visual Notifications
+//!
+//! Senders may tell the working directory a notification comes from (`cwd`, also part of the
+//! Claude Code hook payloads). With `project_roots` set, notifications from directories outside
+//! every root belong to other projects: they keep their pane signals but leave the status bar
+//! list for an `other projects: N` count, so one session can host many repos without the
+//! current project's notifications getting lost.
+
+/// Root without trailing slashes (`/` stays `/`)
+fn normalize(path: &str) -> &str {
+    let trimmed = path.trim().trim_end_matches('/');
+    if trimmed.is_empty() && path.trim().starts_with('/') {
+        "/"
+    } else {
+        trimmed
+    }
+}
+
+/// Whether `path` is `root` or below it
+fn is_under(path: &str, root: &str) -> bool {
+    let (path, root) = (normalize(path), normalize(root));
+    if root.is_empty() {
+        return false;
+    }
+    root == "/" || path == root || path.strip_prefix(root).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Whether a notification from `cwd` belongs to another project than `roots`. Without roots, or
+/// without a `cwd`, every notification belongs to the current project.
+pub fn is_other_project(roots: &[String], cwd: Option<&str>) -> bool {
+    match cwd {
+        Some(cwd) if !roots.is_empty() && !cwd.trim().is_empty() => !roots.iter().any(|root| is_under(cwd, root)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_under_roots() {
+        let roots = vec!["/home/me/api/".to_string(), "/srv/web".to_string()];
+        assert!(!is_other_project(&roots, Some("/home/me/api")));
+        assert!(!is_other_project(&roots, Some("/home/me/api/crates/core")));
+        assert!(!is_other_project(&roots, Some("/srv/web/")));
+        // A shared prefix is not enough
+        assert!(is_other_project(&roots, Some("/home/me/api-client")));
+        assert!(is_other_project(&roots, Some("/tmp")));
+    }
+
+    #[test]
+    fn test_unscoped_notifications_stay_current() {
+        assert!(!is_other_project(&[], Some("/tmp")));
+        assert!(!is_other_project(&["/srv/web".to_string()], None));
+        assert!(!is_other_project(&["/srv/web".to_string()], Some(" ")));
+        assert!(!is_other_project(&["/".to_string()], Some("/tmp")));
+    }
+}
@@ -0,0 +1,201 @@
+//! World model module for Zellij Visual Notifications
+//!
+//! Owns the authoritative picture of the session layout (panes, tabs, focus and titles) built
+//! from `PaneUpdate` and `TabUpdate` events, and answers layout questions (`tab_of_pane`,
+//! `visible_panes`, `focused_pane`) for routing, badges and rendering.
+
+use std::collections::{BTreeMap, BTreeSet};
+use zellij_tile::prelude::{PaneManifest, TabInfo};
+use crate::surface::PaneVisibility;
+
+/// Screen size assumed until the host reports the tab display area (columns, rows)
+pub const DEFAULT_SCREEN_SIZE: (usize, usize) = (80, 24);
+
+/// What the plugin knows about a pane
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PaneRecord {
+    /// Pane ID
+    pub id: u32,
+    /// Whether the pane is focused in its tab
+    pub is_focused: bool,
+    /// Pane title
+    pub title: String,
+    /// Command of a command pane
+    pub command: Option<String>,
+    /// Whether the pane is a plugin pane
+    pub is_plugin: bool,
+    /// How much of the pane is on screen
+    pub visibility: PaneVisibility,
+    /// Position of the tab holding the pane
+    pub tab_position: usize,
+}
+
+/// What the plugin knows about a tab
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TabRecord {
+    /// Tab position (0-based)
+    pub position: usize,
+    /// Tab name
+    pub name: String,
+    /// Whether the tab is the active one
+    pub active: bool,
+    /// Display area rows
+    pub display_rows: usize,
+    /// Display area columns
+    pub display_cols: usize,
+}
+
+/// Panes that appeared or disappeared in an update
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PaneChanges {
+    /// Panes seen for the first time
+    pub opened: Vec<u32>,
+    /// Panes no longer present
+    pub closed: Vec<u32>,
+}
+
+/// Session layout: panes by ID and tabs by position
+#[derive(Debug, Default, Clone)]
+pub struct World {
+    /// Panes by ID
+    panes: BTreeMap<u32, PaneRecord>,
+    /// Tabs by position
+    tabs: BTreeMap<usize, TabRecord>,
+}
+
+impl World {
+    /// Create an empty world
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the panes from a host pane manifest
+    pub fn apply_pane_manifest(&mut self, manifest: &PaneManifest) -> PaneChanges {
+        let panes = manifest.panes.iter().flat_map(|(tab_position, panes)| {
+            panes.iter().map(move |pane| PaneRecord {
+                id: pane.id,
+                is_focused: pane.is_focused,
+                title: pane.title.clone(),
+                command: pane.terminal_command.clone(),
+                is_plugin: pane.is_plugin,
+                visibility: PaneVisibility::from_geometry(pane.pane_rows, pane.pane_content_rows, pane.is_suppressed),
+                tab_position: *tab_position,
+            })
+        });
+        self.set_panes(panes.collect())
+    }
+
+    /// Replace the panes, returning which appeared and disappeared
+    pub fn set_panes(&mut self, panes: Vec<PaneRecord>) -> PaneChanges {
+        let before: BTreeSet<u32> = self.panes.keys().copied().collect();
+        self.panes = panes.into_iter().map(|pane| (pane.id, pane)).collect();
+
+        PaneChanges {
+            opened: self.panes.keys().filter(|id| !before.contains(id)).copied().collect(),
+            closed: before.into_iter().filter(|id| !self.panes.contains_key(id)).collect(),
+        }
+    }
+
+    /// Replace the tabs from a host tab update
+    pub fn apply_tabs(&mut self, tabs: &[TabInfo]) {
+        self.set_tabs(tabs.iter().map(|tab| TabRecord {
+            position: tab.position,
+            name: tab.name.clone(),
+            active: tab.active,
+            display_rows: tab.display_area_rows,
+            display_cols: tab.display_area_columns,
+        }).collect());
+    }
+
+    /// Replace the tabs
+    pub fn set_tabs(&mut self, tabs: Vec<TabRecord>) {
+        self.tabs = tabs.into_iter().map(|tab| (tab.position, tab)).collect();
+    }
+
+    /// Look up a pane
+    pub fn pane(&self, pane_id: u32) -> Option<&PaneRecord> {
+        self.panes.get(&pane_id)
+    }
+
+    /// All panes, by ID
+    pub fn panes(&self) -> impl Iterator<Item = &PaneRecord> {
+        self.panes.values()
+    }
+
+    /// All tabs, by position
+    pub fn tabs(&self) -> impl Iterator<Item = &TabRecord> {
+        self.tabs.values()
+    }
+
+    /// The active tab, once known
+    pub fn active_tab(&self) -> Option<&TabRecord> {
+        self.tabs.values().find(|tab| tab.active)
+    }
+
+    /// Position of the tab holding a pane
+    pub fn tab_of_pane(&self, pane_id: u32) -> Option<usize> {
+        self.panes.get(&pane_id).map(|pane| pane.tab_position)
+    }
+
+    /// Panes in the active tab (all panes while the active tab is unknown)
+    pub fn visible_panes(&self) -> impl Iterator<Item = &PaneRecord> {
+        let active = self.active_tab().map(|tab| tab.position);
+        self.panes.values().filter(move |pane| active.is_none_or(|position| pane.tab_position == position))
+    }
+
+    /// Panes focused in their tabs
+    pub fn focused_panes(&self) -> impl Iterator<Item = &PaneRecord> {
+        self.panes.values().filter(|pane| pane.is_focused)
+    }
+
+    /// The focused pane of the active tab
+    pub fn focused_pane(&self) -> Option<u32> {
+        self.visible_panes().find(|pane| pane.is_focused).map(|pane| pane.id)
+    }
+
+    /// Display area of the active tab as (columns, rows)
+    pub fn screen_size(&self) -> (usize, usize) {
+        self.active_tab()
+            .map(|tab| (tab.display_cols, tab.display_rows))
+            .filter(|(cols, rows)| *cols > 0 && *rows > 0)
+            .unwrap_or(DEFAULT_SCREEN_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(id: u32, tab_position: usize, is_focused: bool) -> PaneRecord {
+        PaneRecord { id, tab_position, is_focused, ..PaneRecord::default() }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.set_panes(vec![pane(1, 0, true), pane(2, 0, false), pane(3, 1, true)]);
+        world.set_tabs(vec![
+            TabRecord { position: 0, ..TabRecord::default() },
+            TabRecord { position: 1, active: true, display_rows: 40, display_cols: 120, ..TabRecord::default() },
+        ]);
+        world
+    }
+
+    #[test]
+    fn test_layout_queries() {
+        let world = world();
+        assert_eq!(world.tab_of_pane(2), Some(0));
+        assert_eq!(world.tab_of_pane(9), None);
+        assert_eq!(world.visible_panes().map(|p| p.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(world.focused_pane(), Some(3));
+        assert_eq!(world.focused_panes().count(), 2);
+        assert_eq!(world.screen_size(), (120, 40));
+        assert_eq!(World::new().screen_size(), DEFAULT_SCREEN_SIZE);
+    }
+
+    #[test]
+    fn test_pane_changes() {
+        let mut world = world();
+        let changes = world.set_panes(vec![pane(1, 0, true), pane(3, 1, true), pane(4, 1, false)]);
+        assert_eq!(changes, PaneChanges { opened: vec![4], closed: vec![2] });
+    }
+}
@@ -0,0 +1,217 @@
+//! Word wrap module for Zellij Visual Notifications
+//!
+//! Width-aware word wrapping for multi-line views (toasts and other paragraph text). Widths are
+//! measured in terminal columns per grapheme cluster: combining marks, variation selectors and
+//! zero-width-joined sequences stay with their base character, and wide characters count as two
+//! columns. ANSI escape sequences pass through without width. Each paragraph's leading indent is
+//! repeated on its continuation lines, and words longer than a line are broken without hyphens.
+
+/// A unit of text that is never split
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    /// ANSI escape sequence (no width)
+    Escape(String),
+    /// Grapheme cluster and its width in columns
+    Cluster(String, usize),
+}
+
+impl Piece {
+    fn width(&self) -> usize {
+        match self {
+            Piece::Escape(_) => 0,
+            Piece::Cluster(_, width) => *width,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Piece::Escape(text) | Piece::Cluster(text, _) => text,
+        }
+    }
+
+    fn is_space(&self) -> bool {
+        matches!(self, Piece::Cluster(text, _) if text == " " || text == "\t")
+    }
+}
+
+/// Wrap text to lines of at most `width` columns (newlines start new paragraphs)
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    text.split('\n').flat_map(|paragraph| wrap_paragraph(paragraph, width)).collect()
+}
+
+/// Display width of text in terminal columns (escape sequences excluded)
+pub fn display_width(text: &str) -> usize {
+    pieces(text).iter().map(Piece::width).sum()
+}
+
+/// Split text into escape sequences (zero width) and grapheme clusters with their widths
+pub fn clusters(text: &str) -> Vec<(String, usize)> {
+    pieces(text).into_iter()
+        .map(|piece| {
+            let width = piece.width();
+            (piece.as_str().to_string(), width)
+        })
+        .collect()
+}
+
+/// Wrap a single paragraph, repeating its indent on continuation lines
+fn wrap_paragraph(paragraph: &str, width: usize) -> Vec<String> {
+    let pieces = pieces(paragraph.trim_end());
+    let indent_len = pieces.iter().take_while(|piece| piece.is_space()).count();
+    let indent: String = pieces[..indent_len].iter().map(Piece::as_str).collect();
+    let indent_width = display_width(&indent);
+    // An indent that leaves no room for text is dropped on continuation lines
+    let (indent, indent_width) = if indent_width < width { (indent, indent_width) } else { (String::new(), 0) };
+
+    let mut words: Vec<Vec<Piece>> = Vec::new();
+    let mut word = Vec::new();
+    for piece in pieces.into_iter().skip(indent_len) {
+        if piece.is_space() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            word.push(piece);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let mut lines = Vec::new();
+    let mut line = indent.clone();
+    let mut line_width = indent_width;
+    let mut line_empty = true;
+    for word in words {
+        let word_width: usize = word.iter().map(Piece::width).sum();
+        let gap = if line_empty { 0 } else { 1 };
+        if line_width + gap + word_width > width && !line_empty {
+            lines.push(std::mem::replace(&mut line, indent.clone()));
+            line_width = indent_width;
+            line_empty = true;
+        }
+        if !line_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        // Fits, or gets broken across lines at cluster boundaries
+        for piece in word {
+            if line_width + piece.width() > width && line_width > indent_width {
+                lines.push(std::mem::replace(&mut line, indent.clone()));
+                line_width = indent_width;
+            }
+            line_width += piece.width();
+            line.push_str(piece.as_str());
+        }
+        line_empty = false;
+    }
+    lines.push(line);
+    lines
+}
+
+/// Split text into escape sequences and grapheme clusters
+fn pieces(text: &str) -> Vec<Piece> {
+    let mut pieces: Vec<Piece> = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut joined = false;
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // CSI sequence (ESC [ params final) or a two-character escape
+            let mut sequence = c.to_string();
+            if chars.peek() == Some(&'[') {
+                sequence.extend(chars.next());
+                for next in chars.by_ref() {
+                    sequence.push(next);
+                    if ('\u{40}'..='\u{7e}').contains(&next) {
+                        break;
+                    }
+                }
+            } else {
+                sequence.extend(chars.next());
+            }
+            pieces.push(Piece::Escape(sequence));
+            continue;
+        }
+
+        let extends = is_zero_width(c) || joined;
+        match pieces.last_mut() {
+            Some(Piece::Cluster(cluster, _)) if extends => cluster.push(c),
+            _ => pieces.push(Piece::Cluster(c.to_string(), char_width(c))),
+        }
+        joined = c == '\u{200d}'; // Zero width joiner glues the next character on
+    }
+    pieces
+}
+
+/// Whether a character attaches to the previous one (combining marks, selectors, joiners)
+fn is_zero_width(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036f}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{200b}'..='\u{200d}'
+        | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe00}'..='\u{fe0f}'
+        | '\u{fe20}'..='\u{fe2f}'
+        | '\u{1f3fb}'..='\u{1f3ff}'
+        | '\u{e0100}'..='\u{e01ef}'
+    )
+}
+
+/// Terminal columns taken by a character
+fn char_width(c: char) -> usize {
+    match c {
+        '\u{0}'..='\u{1f}' | '\u{7f}' => 0,
+        '\u{1100}'..='\u{115f}'
+        | '\u{2e80}'..='\u{a4cf}'
+        | '\u{ac00}'..='\u{d7a3}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{fe30}'..='\u{fe4f}'
+        | '\u{ff00}'..='\u{ff60}'
+        | '\u{ffe0}'..='\u{ffe6}'
+        | '\u{1f300}'..='\u{1f64f}'
+        | '\u{1f900}'..='\u{1f9ff}'
+        | '\u{20000}'..='\u{3fffd}' => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraps_at_word_boundaries() {
+        assert_eq!(
+            wrap("Build finished. All tests passed on the first try.", 16),
+            vec!["Build finished.", "All tests passed", "on the first", "try."]
+        );
+        assert_eq!(wrap("one\n\ntwo", 10), vec!["one", "", "two"]);
+    }
+
+    #[test]
+    fn test_long_words_break_without_hyphens() {
+        assert_eq!(wrap("see /very/long/path/name", 8), vec!["see", "/very/lo", "ng/path/", "name"]);
+    }
+
+    #[test]
+    fn test_indent_is_preserved() {
+        assert_eq!(wrap("  - first item wraps", 12), vec!["  - first", "  item wraps"]);
+    }
+
+    #[test]
+    fn test_escapes_and_clusters() {
+        // Escapes take no width, and a combining accent stays with its letter
+        let red = "\u{1b}[31mred\u{1b}[0m text";
+        assert_eq!(display_width(red), 8);
+        assert_eq!(wrap(red, 3), vec!["\u{1b}[31mred\u{1b}[0m", "tex", "t"]);
+        assert_eq!(wrap("cafe\u{301}s", 4), vec!["cafe\u{301}", "s"]);
+
+        // Wide characters count double; a ZWJ family emoji is one cluster
+        assert_eq!(display_width("\u{4f60}\u{597d}"), 4);
+        assert_eq!(display_width("\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}"), 2);
+    }
+}